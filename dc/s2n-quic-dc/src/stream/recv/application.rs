@@ -248,8 +248,10 @@ where
                         .transition(LocalState::Drained, &self.shared);
                     break;
                 }
-                // we already checked for an error above
-                state::Receiver::ResetRecvd | state::Receiver::ResetRead => unreachable!(),
+                // we already checked for an error above and RESET_STREAM_AT isn't used by dc
+                state::Receiver::ResetAtRecvd
+                | state::Receiver::ResetRecvd
+                | state::Receiver::ResetRead => unreachable!(),
             }
 
             match self.read_mode {
@@ -5,9 +5,6 @@
 ///
 /// This example serves only to illustrate the integration points for incorporating a custom
 /// congestion controller into s2n-quic, and not as an actual congestion controller implementation.
-///
-/// NOTE: The `CongestionController` trait is considered unstable and may be subject to change
-///       in a future release.
 pub mod custom_congestion_controller {
     use s2n_quic::provider::{
         congestion_controller,
@@ -17,12 +14,29 @@ pub mod custom_congestion_controller {
     };
 
     /// Define a congestion controller containing any state you wish to track.
-    /// For this example, we track the size of the congestion window in bytes and
-    /// the number of bytes in flight.
+    /// For this example, we track the size of the congestion window in bytes,
+    /// the number of bytes in flight and the maximum datagram size.
     #[derive(Debug, Clone)]
     pub struct MyCongestionController {
         congestion_window: u32,
         bytes_in_flight: u32,
+        max_datagram_size: u16,
+    }
+
+    impl MyCongestionController {
+        pub fn new(max_datagram_size: u16) -> Self {
+            Self {
+                // Specify the initial congestion window
+                congestion_window: 10 * max_datagram_size as u32,
+                bytes_in_flight: 0,
+                max_datagram_size,
+            }
+        }
+
+        // The congestion window must never be reduced below twice the maximum datagram size
+        fn minimum_window(&self) -> u32 {
+            2 * self.max_datagram_size as u32
+        }
     }
 
     /// The following is a simple implementation of the `CongestionController` trait
@@ -44,7 +58,10 @@ pub mod custom_congestion_controller {
         }
 
         fn is_congestion_limited(&self) -> bool {
-            self.congestion_window < self.bytes_in_flight
+            // The window is full if there isn't enough room left for a maximum sized packet
+            let available_congestion_window =
+                self.congestion_window.saturating_sub(self.bytes_in_flight);
+            available_congestion_window < self.max_datagram_size as u32
         }
 
         fn requires_fast_retransmission(&self) -> bool {
@@ -103,7 +120,7 @@ pub mod custom_congestion_controller {
             // occur once for the initial lost packet, and subsequent lost packets would not lead to
             // further reduction.
             self.bytes_in_flight -= lost_bytes;
-            self.congestion_window = (self.congestion_window as f32 * 0.5) as u32;
+            self.congestion_window = (self.congestion_window / 2).max(self.minimum_window());
         }
 
         fn on_explicit_congestion<Pub: Publisher>(
@@ -112,11 +129,12 @@ pub mod custom_congestion_controller {
             event_time: Timestamp,
             publisher: &mut Pub,
         ) {
-            self.congestion_window = (self.congestion_window as f32 * 0.5) as u32;
+            self.congestion_window = (self.congestion_window / 2).max(self.minimum_window());
         }
 
         fn on_mtu_update<Pub: Publisher>(&mut self, max_data_size: u16, publisher: &mut Pub) {
-            // no op
+            self.max_datagram_size = max_data_size;
+            self.congestion_window = self.congestion_window.max(self.minimum_window());
         }
 
         fn on_packet_discarded<Pub: Publisher>(&mut self, bytes_sent: usize, publisher: &mut Pub) {
@@ -141,11 +159,48 @@ pub mod custom_congestion_controller {
             &mut self,
             path_info: congestion_controller::PathInfo,
         ) -> Self::CongestionController {
-            MyCongestionController {
-                // Specify the initial congestion window
-                congestion_window: 10 * path_info.max_datagram_size as u32,
-                bytes_in_flight: 0,
-            }
+            MyCongestionController::new(path_info.max_datagram_size)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::custom_congestion_controller::MyCongestionController;
+    use s2n_quic::provider::congestion_controller::conformance::{self, Operation};
+
+    /// Checks the custom congestion controller upholds the invariants s2n-quic relies on
+    #[test]
+    fn conformance_test() {
+        let operations = [
+            Operation::PacketSent {
+                count: 20,
+                bytes_sent: 1200,
+                app_limited: Some(false),
+            },
+            Operation::IncrementTime { millis: 50 },
+            Operation::AckReceived {
+                index: 0,
+                count: 5,
+                rtt: 50,
+            },
+            Operation::PacketLost { index: 0 },
+            Operation::PacketLost { index: 0 },
+            Operation::ExplicitCongestion { ce_count: 1 },
+            Operation::MtuUpdated { mtu: 1500 },
+            Operation::PacketSent {
+                count: 20,
+                bytes_sent: 1500,
+                app_limited: None,
+            },
+            Operation::PacketDiscarded,
+            Operation::AckReceived {
+                index: 0,
+                count: 255,
+                rtt: 60,
+            },
+        ];
+
+        conformance::check(MyCongestionController::new(1200), 1200, 0, &operations);
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use s2n_quic::{client::Connect, Client};
+use std::{error::Error, net::SocketAddr};
+use tokio::io::AsyncWriteExt;
+
+/// NOTE: this certificate is to be used for demonstration purposes only!
+pub static CERT_PEM: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../quic/s2n-quic-core/certs/cert.pem"
+));
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let path = std::env::args().nth(1).unwrap_or_else(|| "/".to_string());
+
+    let client = Client::builder()
+        .with_tls(CERT_PEM)?
+        .with_io("0.0.0.0:0")?
+        .start()?;
+
+    let addr: SocketAddr = "127.0.0.1:4433".parse()?;
+    let connect = Connect::new(addr).with_server_name("localhost");
+    let connection = client.connect(connect).await?;
+
+    let (mut driver, mut send_request) =
+        h3::client::new(s2n_quic_h3::Connection::new(connection)).await?;
+    tokio::spawn(async move {
+        let _ = futures::future::poll_fn(|cx| driver.poll_close(cx)).await;
+    });
+
+    let request = http::Request::get(format!("https://localhost{path}")).body(())?;
+    let mut stream = send_request.send_request(request).await?;
+    stream.finish().await?;
+
+    let response = stream.recv_response().await?;
+    eprintln!("{:?} {}", response.version(), response.status());
+
+    let mut stdout = tokio::io::stdout();
+    while let Some(mut chunk) = stream.recv_data().await? {
+        stdout.write_all_buf(&mut chunk).await?;
+    }
+    stdout.flush().await?;
+
+    if let Some(trailers) = stream.recv_trailers().await? {
+        eprintln!("{trailers:?}");
+    }
+
+    Ok(())
+}
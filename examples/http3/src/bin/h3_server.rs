@@ -0,0 +1,92 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use bytes::Bytes;
+use core::{
+    convert::Infallible,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use futures::FutureExt;
+use http::{Request, Response, StatusCode};
+use http_body_util::Full;
+use s2n_quic::Server;
+use s2n_quic_h3::server::RequestBody;
+use std::{error::Error, path::PathBuf};
+use tower_service::Service;
+
+/// NOTE: this certificate is to be used for demonstration purposes only!
+pub static CERT_PEM: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../quic/s2n-quic-core/certs/cert.pem"
+));
+/// NOTE: this certificate is to be used for demonstration purposes only!
+pub static KEY_PEM: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../quic/s2n-quic-core/certs/key.pem"
+));
+
+/// Responds with the files in the `root` directory
+#[derive(Clone)]
+struct StaticFiles {
+    root: PathBuf,
+}
+
+impl Service<Request<RequestBody>> for StaticFiles {
+    type Response = Response<Full<Bytes>>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<RequestBody>) -> Self::Future {
+        eprintln!("{} {}", request.method(), request.uri());
+
+        let path = request.uri().path().trim_start_matches('/');
+        // don't allow requests to escape the root directory
+        let path = (!path.split('/').any(|segment| segment == "..")).then(|| self.root.join(path));
+
+        async move {
+            let contents = match path {
+                Some(path) => tokio::fs::read(path).await.ok(),
+                None => None,
+            };
+
+            let response = match contents {
+                Some(contents) => Response::new(Full::new(Bytes::from(contents))),
+                None => Response::builder()
+                    .status(StatusCode::NOT_FOUND)
+                    .body(Full::default())
+                    .unwrap(),
+            };
+
+            Ok(response)
+        }
+        .boxed()
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let server = Server::builder()
+        .with_tls((CERT_PEM, KEY_PEM))?
+        .with_io("127.0.0.1:4433")?
+        .start()?;
+
+    let service = StaticFiles {
+        root: std::env::current_dir()?,
+    };
+
+    // stop accepting requests on ctrl-c and finish the in-flight ones
+    let shutdown = tokio::signal::ctrl_c().map(|_| ());
+
+    s2n_quic_h3::server::Server::builder()
+        .build(service)
+        .serve_with_shutdown(server, shutdown)
+        .await;
+
+    Ok(())
+}
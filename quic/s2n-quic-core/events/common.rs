@@ -19,7 +19,10 @@ struct EndpointMeta {
     timestamp: crate::event::Timestamp,
 }
 
-struct ConnectionInfo {}
+struct ConnectionInfo<'a> {
+    /// The Destination Connection ID of the first Initial packet sent by the client
+    original_destination_connection_id: ConnectionId<'a>,
+}
 
 // https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.3.3
 struct TransportParameters<'a> {
@@ -371,6 +374,29 @@ enum Frame {
         len: u16,
     },
     DcStatelessResetTokens,
+    AckFrequency {
+        sequence_number: u64,
+        ack_eliciting_threshold: u64,
+        request_max_ack_delay: Duration,
+        reordering_threshold: u64,
+    },
+    ImmediateAck,
+    ResetStreamAt {
+        id: u64,
+        error_code: u64,
+        final_size: u64,
+        reliable_size: u64,
+    },
+    PathAck {
+        path_id: u64,
+        ecn_counts: Option<EcnCounts>,
+        largest_acknowledged: u64,
+        ack_range_count: u64,
+    },
+    PathAbandon {
+        path_id: u64,
+        error_code: u64,
+    },
 }
 
 impl IntoEvent<builder::Frame> for &crate::frame::Padding {
@@ -581,6 +607,61 @@ impl<'a> IntoEvent<builder::Frame> for &crate::frame::DcStatelessResetTokens<'a>
     }
 }
 
+impl IntoEvent<builder::Frame> for &crate::frame::AckFrequency {
+    #[inline]
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::AckFrequency {
+            sequence_number: self.sequence_number.as_u64(),
+            ack_eliciting_threshold: self.ack_eliciting_threshold.as_u64(),
+            request_max_ack_delay: self.request_max_ack_delay(),
+            reordering_threshold: self.reordering_threshold.as_u64(),
+        }
+    }
+}
+
+impl IntoEvent<builder::Frame> for &crate::frame::ImmediateAck {
+    #[inline]
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::ImmediateAck {}
+    }
+}
+
+impl IntoEvent<builder::Frame> for &crate::frame::ResetStreamAt {
+    #[inline]
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::ResetStreamAt {
+            id: self.stream_id.as_u64(),
+            error_code: self.application_error_code.as_u64(),
+            final_size: self.final_size.as_u64(),
+            reliable_size: self.reliable_size.as_u64(),
+        }
+    }
+}
+
+impl<AckRanges: crate::frame::ack::AckRanges> IntoEvent<builder::Frame>
+    for &crate::frame::PathAck<AckRanges>
+{
+    #[inline]
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::PathAck {
+            path_id: self.path_id.as_u64(),
+            ecn_counts: self.ack.ecn_counts.map(|val| val.into_event()),
+            largest_acknowledged: self.largest_acknowledged().into_event(),
+            ack_range_count: self.ack.ack_ranges().len() as u64,
+        }
+    }
+}
+
+impl IntoEvent<builder::Frame> for &crate::frame::PathAbandon {
+    #[inline]
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::PathAbandon {
+            path_id: self.path_id.as_u64(),
+            error_code: self.error_code.as_u64(),
+        }
+    }
+}
+
 #[derive(Clone)]
 struct ConnectionCloseFrame<'a> {
     error_code: u64,
@@ -920,6 +1001,31 @@ enum MigrationDenyReason {
     ConnectionMigrationDisabled,
 }
 
+/// The status of a client's migration to the server's preferred address
+enum PreferredAddressMigrationStatus {
+    /// Path validation to the preferred address was started
+    Started,
+    /// The preferred address was validated and the client migrated to it
+    Migrated,
+    /// Path validation to the preferred address failed and the client keeps using the
+    /// original server address
+    Failed,
+}
+
+/// The status of a client-initiated migration to a new local address
+enum ActiveMigrationStatus {
+    /// Path validation from the new local address was started
+    Started,
+    /// The path from the new local address was validated and the client migrated to it
+    Migrated,
+    /// Path validation from the new local address failed and the client keeps using the
+    /// current local address
+    Failed,
+    /// The migration was not started since the connection is no longer able to migrate,
+    /// for example after the peer retired the connection ID reserved for the new path
+    Aborted,
+}
+
 /// The current state of the ECN controller for the path
 enum EcnState {
     /// ECN capability is being actively tested
@@ -1016,6 +1122,14 @@ enum MtuUpdatedCause {
     InitialMtuPacketAcknowledged,
     /// MTU probes larger than the current MTU were not acknowledged
     LargerProbesLost,
+    /// The search for a larger MTU was restarted after the probe interval elapsed
+    ProbeIntervalElapsed,
+    /// The search for a larger MTU was restarted after the cool off period following a blackhole
+    BlackholeCoolOffElapsed,
+    /// The application requested a new search for a larger MTU
+    ProbeRequested,
+    /// An ICMP message reported that a datagram exceeded the MTU of the path
+    PacketTooBig,
 }
 
 /// A bandwidth delivery rate estimate with associated metadata
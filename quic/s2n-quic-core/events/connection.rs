@@ -328,6 +328,24 @@ struct ConnectionMigrationDenied {
     reason: MigrationDenyReason,
 }
 
+#[event("connectivity:preferred_address_migration_updated")]
+/// The status of a client's migration to the server's preferred address was updated
+struct PreferredAddressMigrationUpdated<'a> {
+    #[nominal_counter("status")]
+    status: PreferredAddressMigrationStatus,
+    /// The path to the server's preferred address
+    path: Path<'a>,
+}
+
+#[event("connectivity:active_migration_updated")]
+/// The status of a client-initiated migration to a new local address was updated
+struct ActiveMigrationUpdated<'a> {
+    #[nominal_counter("status")]
+    status: ActiveMigrationStatus,
+    /// The local address the client is migrating to
+    local_addr: SocketAddress<'a>,
+}
+
 #[event("connectivity:handshake_status_updated")]
 #[checkpoint("complete.latency", |evt| matches!(evt.status, HandshakeStatus::Complete { .. }))]
 #[checkpoint("confirmed.latency", |evt| matches!(evt.status, HandshakeStatus::Confirmed { .. }))]
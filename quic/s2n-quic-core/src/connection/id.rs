@@ -13,6 +13,8 @@ use s2n_codec::{decoder_value, Encoder, EncoderValue};
 #[cfg(any(test, feature = "generator"))]
 use bolero_generator::prelude::*;
 
+pub mod shard;
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1
 //# Each connection possesses a set of connection identifiers, or
 //# connection IDs, each of which can identify the connection.
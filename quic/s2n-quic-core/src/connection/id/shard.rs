@@ -0,0 +1,205 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Encodes the index of the endpoint shard that owns a connection in its local connection IDs
+//!
+//! A single byte of each connection ID, at a configurable offset, identifies the shard. The shard
+//! is recovered by taking the byte modulo the number of shards, which allows both the endpoint
+//! and a kernel packet steering program to route packets without any shared state. Since the
+//! remaining bits of the byte are random, the encoding doesn't make connection IDs linkable.
+
+use super::MAX_LEN;
+
+/// The maximum number of shards that can be encoded in a single byte
+pub const MAX_COUNT: u16 = 256;
+
+/// The offset of the destination connection ID in a short header packet
+pub const SHORT_HEADER_OFFSET: usize = 1;
+
+/// The offset of the destination connection ID length in a long header packet
+pub const LONG_HEADER_LEN_OFFSET: usize = 5;
+
+/// The offset of the destination connection ID in a long header packet
+pub const LONG_HEADER_OFFSET: usize = LONG_HEADER_LEN_OFFSET + 1;
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-17.2
+//# Header Form:  The most significant bit (0x80) of byte 0 (the first
+//#   byte) is set to 1 for long headers.
+const HEADER_FORM_BIT: u8 = 0x80;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    InvalidCount,
+    InvalidIndex,
+    InvalidOffset,
+}
+
+impl Error {
+    fn message(&self) -> &'static str {
+        match self {
+            Error::InvalidCount => "invalid shard count",
+            Error::InvalidIndex => "invalid shard index",
+            Error::InvalidOffset => "invalid shard connection id offset",
+        }
+    }
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Describes how the shard index is encoded in a connection ID
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Encoding {
+    count: u16,
+    offset: u8,
+}
+
+impl Encoding {
+    /// Creates an encoding for `count` shards, stored in the first byte of the connection ID
+    #[inline]
+    pub const fn new(count: u16) -> Result<Self, Error> {
+        if count == 0 || count > MAX_COUNT {
+            return Err(Error::InvalidCount);
+        }
+        Ok(Self { count, offset: 0 })
+    }
+
+    /// Sets the offset of the byte in the connection ID which stores the shard index
+    #[inline]
+    pub const fn with_offset(mut self, offset: u8) -> Result<Self, Error> {
+        if offset as usize >= MAX_LEN {
+            return Err(Error::InvalidOffset);
+        }
+        self.offset = offset;
+        Ok(self)
+    }
+
+    /// Returns the number of shards
+    #[inline]
+    pub const fn count(&self) -> u16 {
+        self.count
+    }
+
+    /// Returns the offset of the byte in the connection ID which stores the shard index
+    #[inline]
+    pub const fn offset(&self) -> u8 {
+        self.offset
+    }
+
+    /// Encodes the shard `index` in a randomly generated connection ID
+    ///
+    /// The random bits of the shard byte that aren't needed to store the index are preserved.
+    #[inline]
+    pub fn encode(&self, index: u16, id: &mut [u8]) -> Result<(), Error> {
+        if index >= self.count {
+            return Err(Error::InvalidIndex);
+        }
+
+        let byte = id
+            .get_mut(self.offset as usize)
+            .ok_or(Error::InvalidOffset)?;
+
+        // the number of multiples of `count` that fit in a byte
+        let spread = MAX_COUNT / self.count;
+        *byte = (index + self.count * (*byte as u16 % spread)) as u8;
+
+        Ok(())
+    }
+
+    /// Returns the shard index encoded in the connection ID
+    ///
+    /// `None` is returned if the connection ID is too short to store the index.
+    #[inline]
+    pub fn decode(&self, id: &[u8]) -> Option<u16> {
+        let byte = *id.get(self.offset as usize)?;
+        Some(byte as u16 % self.count)
+    }
+
+    /// Returns the shard index encoded in the destination connection ID of a QUIC packet
+    ///
+    /// Short header packets don't include the length of the destination connection ID so the
+    /// shard byte is read assuming the connection ID is long enough to contain it. `None` is
+    /// returned if the packet is too short to include the shard byte.
+    #[inline]
+    pub fn decode_packet(&self, packet: &[u8]) -> Option<u16> {
+        let first = *packet.first()?;
+
+        let id = if first & HEADER_FORM_BIT == HEADER_FORM_BIT {
+            let len = *packet.get(LONG_HEADER_LEN_OFFSET)? as usize;
+            packet.get(LONG_HEADER_OFFSET..LONG_HEADER_OFFSET + len)?
+        } else {
+            packet.get(SHORT_HEADER_OFFSET..)?
+        };
+
+        self.decode(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_config() {
+        assert_eq!(Encoding::new(0), Err(Error::InvalidCount));
+        assert_eq!(Encoding::new(MAX_COUNT + 1), Err(Error::InvalidCount));
+        assert_eq!(
+            Encoding::new(4).unwrap().with_offset(MAX_LEN as _),
+            Err(Error::InvalidOffset)
+        );
+
+        let encoding = Encoding::new(4).unwrap().with_offset(2).unwrap();
+        assert_eq!(encoding.encode(4, &mut [0; 8]), Err(Error::InvalidIndex));
+        assert_eq!(encoding.encode(1, &mut [0; 2]), Err(Error::InvalidOffset));
+        assert_eq!(encoding.decode(&[0; 2]), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        bolero::check!()
+            .with_type::<(u16, u8, u8, u8)>()
+            .cloned()
+            .for_each(|(count, offset, index, random)| {
+                let count = count % MAX_COUNT + 1;
+                let offset = offset % MAX_LEN as u8;
+                let index = index as u16 % count;
+
+                let encoding = Encoding::new(count).unwrap().with_offset(offset).unwrap();
+
+                let mut id = [random; MAX_LEN];
+                encoding.encode(index, &mut id).unwrap();
+                assert_eq!(encoding.decode(&id), Some(index));
+            });
+    }
+
+    #[test]
+    fn decode_packet() {
+        let encoding = Encoding::new(4).unwrap().with_offset(1).unwrap();
+
+        let mut id = [0xff; 8];
+        encoding.encode(3, &mut id).unwrap();
+
+        // short header
+        let mut packet = vec![0x40];
+        packet.extend_from_slice(&id);
+        assert_eq!(encoding.decode_packet(&packet), Some(3));
+        assert_eq!(encoding.decode_packet(&packet[..2]), None);
+
+        // long header
+        let mut packet = vec![0xc0, 0, 0, 0, 1, id.len() as u8];
+        packet.extend_from_slice(&id);
+        assert_eq!(encoding.decode_packet(&packet), Some(3));
+
+        // the connection ID is too short to contain the shard byte
+        let packet = [0xc0, 0, 0, 0, 1, 1, 0];
+        assert_eq!(encoding.decode_packet(&packet), None);
+
+        assert_eq!(encoding.decode_packet(&[]), None);
+    }
+}
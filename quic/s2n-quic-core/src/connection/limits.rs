@@ -5,12 +5,14 @@ use crate::application::ServerName;
 use crate::{
     ack,
     event::{api::SocketAddress, IntoEvent},
-    inet, recovery, stream,
+    inet::{self, Unspecified as _},
+    recovery, stream,
     transport::parameters::{
         AckDelayExponent, ActiveConnectionIdLimit, InitialFlowControlLimits, InitialMaxData,
-        InitialMaxStreamDataBidiLocal, InitialMaxStreamDataBidiRemote, InitialMaxStreamDataUni,
-        InitialMaxStreamsBidi, InitialMaxStreamsUni, InitialStreamLimits, MaxAckDelay,
-        MaxDatagramFrameSize, MaxIdleTimeout, MigrationSupport, TransportParameters,
+        InitialMaxPathId, InitialMaxStreamDataBidiLocal, InitialMaxStreamDataBidiRemote,
+        InitialMaxStreamDataUni, InitialMaxStreamsBidi, InitialMaxStreamsUni, InitialStreamLimits,
+        MaxAckDelay, MaxDatagramFrameSize, MaxIdleTimeout, MigrationSupport, MinAckDelay,
+        ResetStreamAtSupport, TransportParameters,
     },
 };
 #[cfg(feature = "alloc")]
@@ -92,6 +94,7 @@ pub struct Limits {
     pub(crate) max_open_remote_bidirectional_streams: InitialMaxStreamsBidi,
     pub(crate) max_open_remote_unidirectional_streams: InitialMaxStreamsUni,
     pub(crate) max_ack_delay: MaxAckDelay,
+    pub(crate) min_ack_delay: Option<MinAckDelay>,
     pub(crate) ack_delay_exponent: AckDelayExponent,
     pub(crate) max_active_connection_ids: ActiveConnectionIdLimit,
     pub(crate) ack_elicitation_interval: u8,
@@ -102,6 +105,10 @@ pub struct Limits {
     pub(crate) max_datagram_frame_size: MaxDatagramFrameSize,
     pub(crate) initial_round_trip_time: Duration,
     pub(crate) migration_support: MigrationSupport,
+    pub(crate) reset_stream_at_support: ResetStreamAtSupport,
+    pub(crate) initial_max_path_id: Option<InitialMaxPathId>,
+    pub(crate) preferred_address_v4: Option<inet::SocketAddressV4>,
+    pub(crate) preferred_address_v6: Option<inet::SocketAddressV6>,
     pub(crate) anti_amplification_multiplier: u8,
     pub(crate) stream_batch_size: u8,
 }
@@ -139,6 +146,7 @@ impl Limits {
             max_open_remote_bidirectional_streams: InitialMaxStreamsBidi::RECOMMENDED,
             max_open_remote_unidirectional_streams: InitialMaxStreamsUni::RECOMMENDED,
             max_ack_delay: MaxAckDelay::RECOMMENDED,
+            min_ack_delay: None,
             ack_delay_exponent: AckDelayExponent::RECOMMENDED,
             max_active_connection_ids: ActiveConnectionIdLimit::RECOMMENDED,
             ack_elicitation_interval: ack::Settings::RECOMMENDED.ack_elicitation_interval,
@@ -149,6 +157,10 @@ impl Limits {
             max_datagram_frame_size: MaxDatagramFrameSize::DEFAULT,
             initial_round_trip_time: recovery::DEFAULT_INITIAL_RTT,
             migration_support: MigrationSupport::RECOMMENDED,
+            reset_stream_at_support: ResetStreamAtSupport::DEFAULT,
+            initial_max_path_id: None,
+            preferred_address_v4: None,
+            preferred_address_v6: None,
             anti_amplification_multiplier: ANTI_AMPLIFICATION_MULTIPLIER,
             stream_batch_size: DEFAULT_STREAM_BATCH_SIZE,
         }
@@ -248,6 +260,28 @@ impl Limits {
         u64
     );
     setter!(with_max_ack_delay, max_ack_delay, Duration);
+
+    /// Enables the ACK frequency extension with the given minimum ACK delay (default: disabled)
+    ///
+    /// The `min_ack_delay` transport parameter is sent to the peer, indicating that it is
+    /// permitted to send ACK_FREQUENCY frames requesting ACKs be delayed by no less than
+    /// `value`. This reduces the number of ACKs sent on high bandwidth connections, at the
+    /// cost of slower feedback to the peer.
+    ///
+    /// The value must not exceed the `max_ack_delay`; otherwise the extension will not be
+    /// advertised.
+    pub fn with_min_ack_delay(mut self, value: Duration) -> Result<Self, ValidationError> {
+        ensure!(
+            value <= self.max_ack_delay.as_duration(),
+            Err(ValidationError(
+                "min_ack_delay must not exceed the max_ack_delay",
+            ))
+        );
+
+        self.min_ack_delay = Some(value.try_into()?);
+        Ok(self)
+    }
+
     setter!(
         with_max_active_connection_ids,
         max_active_connection_ids,
@@ -294,6 +328,60 @@ impl Limits {
         Ok(self)
     }
 
+    /// Sets whether RESET_STREAM_AT frames can be received from the peer (default: false)
+    ///
+    /// If set to true, the `reset_stream_at` transport parameter will be sent to the peer,
+    /// allowing it to reset streams while guaranteeing delivery of the data up to a given
+    /// offset.
+    pub fn with_reset_stream_at(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        if enabled {
+            self.reset_stream_at_support = ResetStreamAtSupport::Enabled
+        } else {
+            self.reset_stream_at_support = ResetStreamAtSupport::Disabled
+        }
+        Ok(self)
+    }
+
+    /// Enables the multipath extension with the given maximum path identifier (default: disabled)
+    ///
+    /// The `initial_max_path_id` transport parameter is sent to the peer, indicating that
+    /// application data may be sent on several paths at the same time. Each path is identified
+    /// by the sequence number of the connection IDs it uses, so the number of concurrent paths
+    /// is also bound by the `max_active_connection_ids` limit.
+    pub fn with_max_path_id(mut self, value: u32) -> Result<Self, ValidationError> {
+        self.initial_max_path_id = Some(InitialMaxPathId::new(value));
+        Ok(self)
+    }
+
+    /// Sets an address clients are asked to migrate to once the handshake is confirmed (default: none)
+    ///
+    /// The address is sent to clients in the `preferred_address` transport parameter, along with
+    /// a connection ID and stateless reset token issued for the connection. One address of each
+    /// family can be provided by calling this method for both an IPv4 and an IPv6 address.
+    ///
+    /// The server must be able to receive packets sent to the address. Clients validate the
+    /// address before migrating and keep using the original address if validation fails.
+    pub fn with_preferred_address<A: Into<inet::SocketAddress>>(
+        mut self,
+        address: A,
+    ) -> Result<Self, ValidationError> {
+        let address = address.into();
+
+        ensure!(
+            !address.ip().is_unspecified() && address.port() != 0,
+            Err(ValidationError(
+                "preferred address must have a specified IP and port",
+            ))
+        );
+
+        match address {
+            inet::SocketAddress::IpV4(address) => self.preferred_address_v4 = Some(address),
+            inet::SocketAddress::IpV6(address) => self.preferred_address_v6 = Some(address),
+        }
+
+        Ok(self)
+    }
+
     /// Sets the initial round trip time (RTT) for use in recovery mechanisms prior to
     /// measuring an actual RTT sample.
     ///
@@ -399,6 +487,15 @@ impl Limits {
         self.max_keep_alive_period
     }
 
+    #[doc(hidden)]
+    #[inline]
+    pub fn min_ack_delay(&self) -> Option<MinAckDelay> {
+        // A min_ack_delay larger than the max_ack_delay is invalid, so the extension is
+        // disabled if the max_ack_delay was lowered after enabling it
+        self.min_ack_delay
+            .filter(|min_ack_delay| min_ack_delay.as_duration() <= self.max_ack_delay.as_duration())
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn initial_round_trip_time(&self) -> Duration {
@@ -411,6 +508,26 @@ impl Limits {
         matches!(self.migration_support, MigrationSupport::Enabled)
     }
 
+    #[doc(hidden)]
+    #[inline]
+    pub fn reset_stream_at_enabled(&self) -> bool {
+        self.reset_stream_at_support.is_enabled()
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn initial_max_path_id(&self) -> Option<InitialMaxPathId> {
+        self.initial_max_path_id
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn preferred_address(
+        &self,
+    ) -> (Option<inet::SocketAddressV4>, Option<inet::SocketAddressV6>) {
+        (self.preferred_address_v4, self.preferred_address_v6)
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn anti_amplification_multiplier(&self) -> u8 {
@@ -482,6 +599,54 @@ mod tests {
         assert!(limits.with_unidirectional_data_window(data).is_ok());
     }
 
+    // The min_ack_delay must not exceed the max_ack_delay
+    #[test]
+    fn min_ack_delay_validation() {
+        let limits = Limits::default();
+        assert!(limits.min_ack_delay().is_none());
+
+        let max_ack_delay = limits.max_ack_delay.as_duration();
+        assert!(limits
+            .with_min_ack_delay(max_ack_delay + Duration::from_micros(1))
+            .is_err());
+
+        let limits = limits.with_min_ack_delay(max_ack_delay).unwrap();
+        assert_eq!(
+            limits.min_ack_delay().map(|value| value.as_duration()),
+            Some(max_ack_delay)
+        );
+
+        // lowering the max_ack_delay disables the extension
+        let limits = limits
+            .with_max_ack_delay(max_ack_delay - Duration::from_millis(1))
+            .unwrap();
+        assert!(limits.min_ack_delay().is_none());
+    }
+
+    // One preferred address can be configured for each address family
+    #[test]
+    fn preferred_address_validation() {
+        let limits = Limits::default();
+        assert_eq!(limits.preferred_address(), (None, None));
+
+        let unspecified = inet::SocketAddressV4::new([0, 0, 0, 0], 443);
+        assert!(limits.with_preferred_address(unspecified).is_err());
+        let no_port = inet::SocketAddressV4::new([192, 0, 2, 1], 0);
+        assert!(limits.with_preferred_address(no_port).is_err());
+
+        let v4 = inet::SocketAddressV4::new([192, 0, 2, 1], 443);
+        let v6 = inet::SocketAddressV6::new(
+            [0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+            443,
+        );
+        let limits = limits
+            .with_preferred_address(v4)
+            .unwrap()
+            .with_preferred_address(v6)
+            .unwrap();
+        assert_eq!(limits.preferred_address(), (Some(v4), Some(v6)));
+    }
+
     // Limits can be updated through the UpdatableLimits wrapper
     #[test]
     fn updatable_limits() {
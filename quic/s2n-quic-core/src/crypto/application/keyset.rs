@@ -3,7 +3,7 @@
 
 use crate::{
     connection::ProcessingError,
-    crypto::{application::limited, OneRttKey, ProtectedPayload},
+    crypto::{application::limited, OneRttKey, PathKey, ProtectedPayload},
     packet::{
         encoding::PacketEncodingError,
         number::PacketNumber,
@@ -115,6 +115,19 @@ impl<K: OneRttKey> KeySet<K> {
         packet: EncryptedShort<'a>,
         largest_acknowledged_packet_number: PacketNumber,
         pto: Timestamp,
+    ) -> Result<(CleartextShort<'a>, Option<u16>), ProcessingError> {
+        self.decrypt_packet_for_path(packet, 0, largest_acknowledged_packet_number, pto)
+    }
+
+    /// Decrypts a packet received in the packet number space of the given multipath path
+    ///
+    /// Returns the decrypted packet and generation if the key phase was rotated.
+    pub fn decrypt_packet_for_path<'a>(
+        &mut self,
+        packet: EncryptedShort<'a>,
+        path_id: u32,
+        largest_acknowledged_packet_number: PacketNumber,
+        pto: Timestamp,
     ) -> Result<(CleartextShort<'a>, Option<u16>), ProcessingError> {
         let mut phase_to_use = self.key_phase() as u8;
         let packet_phase = packet.key_phase();
@@ -142,7 +155,7 @@ impl<K: OneRttKey> KeySet<K> {
 
         let key = &mut self.crypto[phase_to_use.into()];
 
-        let result = packet.decrypt(key.key_mut());
+        let result = packet.decrypt(&PathKey::new(key.key_mut(), path_id));
 
         key.on_packet_decryption();
 
@@ -12,8 +12,15 @@ use hex_literal::hex;
 pub trait InitialKey: crypto::Key + Sized {
     type HeaderKey: crypto::HeaderKey;
 
-    fn new_server(connection_id: &[u8]) -> (Self, Self::HeaderKey);
-    fn new_client(connection_id: &[u8]) -> (Self, Self::HeaderKey);
+    /// Derives the server's Initial keys for the given QUIC version and connection ID
+    ///
+    /// Versions other than [`crate::version::V2`] derive keys as specified for QUIC version 1.
+    fn new_server(version: u32, connection_id: &[u8]) -> (Self, Self::HeaderKey);
+
+    /// Derives the client's Initial keys for the given QUIC version and connection ID
+    ///
+    /// Versions other than [`crate::version::V2`] derive keys as specified for QUIC version 1.
+    fn new_client(version: u32, connection_id: &[u8]) -> (Self, Self::HeaderKey);
 }
 
 /// Types for which are able to perform initial header cryptography.
@@ -28,6 +35,11 @@ pub trait InitialHeaderKey: crypto::HeaderKey {}
 
 pub const INITIAL_SALT: [u8; 20] = hex!("38762cf7f55934b34d179ae6a4c80cadccbb7f0a");
 
+/// The salt used to derive Initial secrets for QUIC version 2
+///
+/// See <https://www.rfc-editor.org/rfc/rfc9369#section-3.3.1>
+pub const INITIAL_SALT_V2: [u8; 20] = hex!("0dede3def700a6db819381be6e269dcbf9bd2ed9");
+
 //= https://www.rfc-editor.org/rfc/rfc9001#section-5.2
 //# client_initial_secret = HKDF-Expand-Label(initial_secret,
 //#                                           "client in", "",
@@ -74,6 +86,22 @@ pub const EXAMPLE_SERVER_INITIAL_SECRET: [u8; 32] = hex!(
     "
 );
 
+/// The client Initial secret from <https://www.rfc-editor.org/rfc/rfc9369#appendix-A.1>
+pub const EXAMPLE_CLIENT_INITIAL_SECRET_V2: [u8; 32] = hex!(
+    "
+    14ec9d6eb9fd7af83bf5a668bc17a7e2
+    83766aade7ecd0891f70f9ff7f4bf47b
+    "
+);
+
+/// The server Initial secret from <https://www.rfc-editor.org/rfc/rfc9369#appendix-A.1>
+pub const EXAMPLE_SERVER_INITIAL_SECRET_V2: [u8; 32] = hex!(
+    "
+    0263db1782731bf4588e7e4d93b74639
+    07cb8cd8200b5da55a8bd488eafc37c1
+    "
+);
+
 //= https://www.rfc-editor.org/rfc/rfc9001#appendix-A.2
 //# The client sends an Initial packet.  The unprotected payload of this
 //# packet contains the following CRYPTO frame, plus enough PADDING
@@ -22,6 +22,44 @@ pub trait Key: Send {
         payload: &mut scatter::Buffer,
     ) -> Result<(), packet_protection::Error>;
 
+    /// Decrypt a payload received in the packet number space of a multipath path
+    ///
+    /// Keys that are unable to include the path identifier in the nonce only accept packets
+    /// for the initial path.
+    #[inline]
+    fn decrypt_for_path(
+        &self,
+        path_id: u32,
+        packet_number: u64,
+        header: &[u8],
+        payload: &mut [u8],
+    ) -> Result<(), packet_protection::Error> {
+        if path_id != 0 {
+            return Err(packet_protection::Error::DECRYPT_ERROR);
+        }
+
+        self.decrypt(packet_number, header, payload)
+    }
+
+    /// Encrypt a payload sent in the packet number space of a multipath path
+    ///
+    /// Keys that are unable to include the path identifier in the nonce only accept packets
+    /// for the initial path.
+    #[inline]
+    fn encrypt_for_path(
+        &mut self,
+        path_id: u32,
+        packet_number: u64,
+        header: &[u8],
+        payload: &mut scatter::Buffer,
+    ) -> Result<(), packet_protection::Error> {
+        if path_id != 0 {
+            return Err(packet_protection::Error::INTERNAL_ERROR);
+        }
+
+        self.encrypt(packet_number, header, payload)
+    }
+
     /// Length of the appended tag
     fn tag_len(&self) -> usize;
 
@@ -98,6 +136,26 @@ pub mod testing {
             Ok(())
         }
 
+        fn decrypt_for_path(
+            &self,
+            _path_id: u32,
+            packet_number: u64,
+            header: &[u8],
+            payload: &mut [u8],
+        ) -> Result<(), packet_protection::Error> {
+            self.decrypt(packet_number, header, payload)
+        }
+
+        fn encrypt_for_path(
+            &mut self,
+            _path_id: u32,
+            packet_number: u64,
+            header: &[u8],
+            payload: &mut scatter::Buffer,
+        ) -> Result<(), packet_protection::Error> {
+            self.encrypt(packet_number, header, payload)
+        }
+
         /// Length of the appended tag
         fn tag_len(&self) -> usize {
             0
@@ -119,11 +177,11 @@ pub mod testing {
     impl InitialKey for Key {
         type HeaderKey = HeaderKey;
 
-        fn new_server(_connection_id: &[u8]) -> (Self, Self::HeaderKey) {
+        fn new_server(_version: u32, _connection_id: &[u8]) -> (Self, Self::HeaderKey) {
             (Key::default(), HeaderKey::default())
         }
 
-        fn new_client(_connection_id: &[u8]) -> (Self, Self::HeaderKey) {
+        fn new_client(_version: u32, _connection_id: &[u8]) -> (Self, Self::HeaderKey) {
             (Key::default(), HeaderKey::default())
         }
     }
@@ -140,10 +198,14 @@ pub mod testing {
     }
     impl ZeroRttKey for Key {}
     impl RetryKey for Key {
-        fn generate_tag(_payload: &[u8]) -> IntegrityTag {
+        fn generate_tag(_version: u32, _payload: &[u8]) -> IntegrityTag {
             [0u8; INTEGRITY_TAG_LEN]
         }
-        fn validate(_payload: &[u8], _tag: IntegrityTag) -> Result<(), packet_protection::Error> {
+        fn validate(
+            _version: u32,
+            _payload: &[u8],
+            _tag: IntegrityTag,
+        ) -> Result<(), packet_protection::Error> {
             Ok(())
         }
     }
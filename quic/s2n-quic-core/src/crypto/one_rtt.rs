@@ -1,7 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::crypto::{HeaderKey, Key};
+use crate::crypto::{packet_protection, scatter, tls, HeaderKey, Key};
 
 /// Types for which are able to perform 1-RTT cryptography.
 ///
@@ -19,3 +19,81 @@ pub trait OneRttKey: Key {
 /// are used with Short packets. Any key misuses are
 /// caught by the type system.
 pub trait OneRttHeaderKey: HeaderKey {}
+
+/// Types which are able to protect the payload of Short packets.
+///
+/// This is implemented for all [`OneRttKey`]s as well as [`PathKey`]s wrapping them.
+pub trait OneRttPacketKey: Key {}
+
+impl<K: OneRttKey> OneRttPacketKey for K {}
+
+/// A 1-RTT key bound to the packet number space of a multipath path
+///
+/// The path identifier is included in the AEAD nonce for any path other than the initial path.
+/// See <https://datatracker.ietf.org/doc/draft-ietf-quic-multipath/>
+#[derive(Debug)]
+pub struct PathKey<'a, K: OneRttKey> {
+    key: &'a mut K,
+    path_id: u32,
+}
+
+impl<'a, K: OneRttKey> PathKey<'a, K> {
+    #[inline]
+    pub fn new(key: &'a mut K, path_id: u32) -> Self {
+        Self { key, path_id }
+    }
+}
+
+impl<K: OneRttKey> OneRttPacketKey for PathKey<'_, K> {}
+
+impl<K: OneRttKey> Key for PathKey<'_, K> {
+    #[inline]
+    fn decrypt(
+        &self,
+        packet_number: u64,
+        header: &[u8],
+        payload: &mut [u8],
+    ) -> Result<(), packet_protection::Error> {
+        if self.path_id == 0 {
+            self.key.decrypt(packet_number, header, payload)
+        } else {
+            self.key
+                .decrypt_for_path(self.path_id, packet_number, header, payload)
+        }
+    }
+
+    #[inline]
+    fn encrypt(
+        &mut self,
+        packet_number: u64,
+        header: &[u8],
+        payload: &mut scatter::Buffer,
+    ) -> Result<(), packet_protection::Error> {
+        if self.path_id == 0 {
+            self.key.encrypt(packet_number, header, payload)
+        } else {
+            self.key
+                .encrypt_for_path(self.path_id, packet_number, header, payload)
+        }
+    }
+
+    #[inline]
+    fn tag_len(&self) -> usize {
+        self.key.tag_len()
+    }
+
+    #[inline]
+    fn aead_confidentiality_limit(&self) -> u64 {
+        self.key.aead_confidentiality_limit()
+    }
+
+    #[inline]
+    fn aead_integrity_limit(&self) -> u64 {
+        self.key.aead_integrity_limit()
+    }
+
+    #[inline]
+    fn cipher_suite(&self) -> tls::CipherSuite {
+        self.key.cipher_suite()
+    }
+}
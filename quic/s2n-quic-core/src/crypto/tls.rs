@@ -43,6 +43,43 @@ impl PartialEq for NamedGroup {
     }
 }
 
+/// The status of early data (0-RTT) on a connection
+///
+/// Early data is only sent if the TLS provider derives 0-RTT keys. The `s2n-tls` provider
+/// doesn't, since s2n-tls doesn't support early data on QUIC connections.
+///
+/// See <https://www.rfc-editor.org/rfc/rfc9001#section-4.6>.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EarlyDataStatus {
+    /// The connection did not attempt to use early data
+    #[default]
+    NotAttempted,
+    /// The client sent early data and is waiting for the server to accept or reject it
+    Pending,
+    /// The server accepted the early data
+    Accepted,
+    /// The server rejected the early data
+    ///
+    /// Any streams opened before the handshake completed fail with
+    /// [`StreamError::EarlyDataRejected`](crate::stream::StreamError::EarlyDataRejected).
+    Rejected,
+}
+
+impl EarlyDataStatus {
+    /// Returns `true` if early data was accepted by the server
+    #[inline]
+    pub fn is_accepted(self) -> bool {
+        matches!(self, Self::Accepted)
+    }
+
+    /// Returns `true` if early data was rejected by the server
+    #[inline]
+    pub fn is_rejected(self) -> bool {
+        matches!(self, Self::Rejected)
+    }
+}
+
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum TlsExportError {
@@ -132,6 +169,12 @@ pub trait Context<Crypto: crate::crypto::CryptoSuite> {
         application_parameters: ApplicationParameters,
     ) -> Result<(), crate::transport::Error>;
 
+    /// Called by the client when the server rejected early data
+    ///
+    /// This is called prior to [`Context::on_one_rtt_keys`] and only if
+    /// [`Context::on_zero_rtt_keys`] was previously called.
+    fn on_zero_rtt_rejected(&mut self) -> Result<(), crate::transport::Error>;
+
     fn on_server_name(
         &mut self,
         server_name: crate::application::ServerName,
@@ -190,6 +233,14 @@ pub trait Context<Crypto: crate::crypto::CryptoSuite> {
     fn send_application(&mut self, transmission: Bytes);
 
     fn waker(&self) -> &core::task::Waker;
+
+    /// The QUIC version in use on the connection
+    ///
+    /// The version determines the labels used to derive packet protection keys.
+    /// See <https://www.rfc-editor.org/rfc/rfc9369#section-3.3.2>.
+    fn quic_version(&self) -> u32 {
+        crate::version::V1
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -209,6 +260,17 @@ pub trait Endpoint: 'static + Sized + Send {
 
     /// The maximum length of a tag for any algorithm that may be negotiated
     fn max_tag_length(&self) -> usize;
+
+    /// Returns `true` if sessions are able to switch to a compatible QUIC version
+    /// after the handshake has started
+    ///
+    /// Compatible version negotiation requires the packet protection keys to be derived
+    /// with [`Context::quic_version`] at the time each key is installed. Server sessions
+    /// also need to call [`Context::on_client_application_params`], which selects the
+    /// version and appends the `version_information` transport parameter.
+    fn supports_compatible_version_negotiation(&self) -> bool {
+        false
+    }
 }
 
 #[cfg(feature = "alloc")]
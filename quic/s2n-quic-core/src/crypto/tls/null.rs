@@ -348,6 +348,28 @@ mod key {
             Ok(())
         }
 
+        #[inline(always)]
+        fn decrypt_for_path(
+            &self,
+            _path_id: u32,
+            packet_number: u64,
+            header: &[u8],
+            payload: &mut [u8],
+        ) -> Result<(), crypto::packet_protection::Error> {
+            self.decrypt(packet_number, header, payload)
+        }
+
+        #[inline(always)]
+        fn encrypt_for_path(
+            &mut self,
+            _path_id: u32,
+            packet_number: u64,
+            header: &[u8],
+            payload: &mut scatter::Buffer,
+        ) -> Result<(), crypto::packet_protection::Error> {
+            self.encrypt(packet_number, header, payload)
+        }
+
         #[inline(always)]
         fn tag_len(&self) -> usize {
             0
@@ -405,12 +427,12 @@ mod key {
         type HeaderKey = NoCrypto;
 
         #[inline(always)]
-        fn new_server(_connection_id: &[u8]) -> (Self, Self::HeaderKey) {
+        fn new_server(_version: u32, _connection_id: &[u8]) -> (Self, Self::HeaderKey) {
             (NoCrypto, NoCrypto)
         }
 
         #[inline(always)]
-        fn new_client(_connection_id: &[u8]) -> (Self, Self::HeaderKey) {
+        fn new_client(_version: u32, _connection_id: &[u8]) -> (Self, Self::HeaderKey) {
             (NoCrypto, NoCrypto)
         }
     }
@@ -432,12 +454,13 @@ mod key {
 
     impl crypto::RetryKey for NoCrypto {
         #[inline(always)]
-        fn generate_tag(_payload: &[u8]) -> crypto::retry::IntegrityTag {
+        fn generate_tag(_version: u32, _payload: &[u8]) -> crypto::retry::IntegrityTag {
             Default::default()
         }
 
         #[inline(always)]
         fn validate(
+            _version: u32,
             _payload: &[u8],
             _tag: crypto::retry::IntegrityTag,
         ) -> Result<(), crypto::packet_protection::Error> {
@@ -179,6 +179,25 @@ impl<S: tls::Session, C: tls::Session> Pair<S, C> {
         client_endpoint: &mut CE,
         server_name: ServerName,
     ) -> Self
+    where
+        SE: tls::Endpoint<Session = S>,
+        CE: tls::Endpoint<Session = C>,
+    {
+        Self::new_with_version(
+            server_endpoint,
+            client_endpoint,
+            server_name,
+            crate::version::V1,
+        )
+    }
+
+    /// Creates a new pair of sessions which protect packets for the given QUIC version
+    pub fn new_with_version<SE, CE>(
+        server_endpoint: &mut SE,
+        client_endpoint: &mut CE,
+        server_name: ServerName,
+        version: u32,
+    ) -> Self
     where
         SE: tls::Endpoint<Session = S>,
         CE: tls::Endpoint<Session = C>,
@@ -188,11 +207,15 @@ impl<S: tls::Session, C: tls::Session> Pair<S, C> {
         let server = server_endpoint.new_server_session(&&server_params()[..]);
         let mut server_context =
             Context::new(endpoint::Type::Server, ServerState::WaitingClientHello);
-        server_context.initial.crypto = Some(S::InitialKey::new_server(server_name.as_bytes()));
+        server_context.quic_version = version;
+        server_context.initial.crypto =
+            Some(S::InitialKey::new_server(version, server_name.as_bytes()));
 
         let client = client_endpoint.new_client_session(&&client_params()[..], server_name.clone());
         let mut client_context = Context::new(endpoint::Type::Client, ClientState::ClientHelloSent);
-        client_context.initial.crypto = Some(C::InitialKey::new_client(server_name.as_bytes()));
+        client_context.quic_version = version;
+        client_context.initial.crypto =
+            Some(C::InitialKey::new_client(version, server_name.as_bytes()));
 
         Self {
             server: TlsEndpoint::new(server, server_context),
@@ -404,6 +427,7 @@ where
     pub application_protocol: Option<Bytes>,
     pub key_exchange_group: Option<NamedGroup>,
     pub transport_parameters: Option<Bytes>,
+    pub quic_version: u32,
     endpoint: endpoint::Type,
     pub state: State,
     waker: Waker,
@@ -446,6 +470,7 @@ where
             application_protocol: None,
             key_exchange_group: None,
             transport_parameters: None,
+            quic_version: crate::version::V1,
             endpoint,
             state,
             waker,
@@ -707,6 +732,25 @@ where
         Ok(())
     }
 
+    fn on_zero_rtt_rejected(&mut self) -> Result<(), transport::Error> {
+        assert!(
+            self.endpoint.is_client(),
+            "only clients are notified of 0-rtt rejection"
+        );
+        assert!(
+            self.zero_rtt_crypto.is_some(),
+            "0-rtt rejected without 0-rtt keys"
+        );
+        assert!(
+            self.application.crypto.is_none(),
+            "0-rtt rejected after 1-rtt keys"
+        );
+        self.log("0-rtt rejected");
+        // the server never derived 0-rtt keys so discard them to stay consistent with the peer
+        self.zero_rtt_crypto = None;
+        Ok(())
+    }
+
     fn on_one_rtt_keys(
         &mut self,
         key: C::OneRttKey,
@@ -829,6 +873,10 @@ where
     fn waker(&self) -> &Waker {
         &self.waker
     }
+
+    fn quic_version(&self) -> u32 {
+        self.quic_version
+    }
     /// Set tls context to [`quic::connection::Connection`]
     #[cfg(feature = "alloc")]
     fn on_tls_context(&mut self, _context: Box<dyn core::any::Any + Send>) {}
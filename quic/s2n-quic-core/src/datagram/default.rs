@@ -6,6 +6,8 @@
 use crate::{
     connection,
     datagram::{ConnectionInfo, Packet, PreConnectionInfo, ReceiveContext},
+    packet::number::{PacketNumber, PacketNumberRange},
+    query,
     transport::parameters::MaxDatagramFrameSize,
 };
 use alloc::collections::VecDeque;
@@ -23,12 +25,21 @@ use core::{
 /// and Receiver behavior can be swapped out by implementing the respective [`Sender`](s2n-quic-core::datagram::traits::Sender) and
 /// [`Receiver`](s2n-quic-core::datagram::traits::Receiver) traits.
 ///
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct Endpoint {
     send_queue_capacity: usize,
     recv_queue_capacity: usize,
 }
 
+impl Default for Endpoint {
+    fn default() -> Self {
+        Self {
+            send_queue_capacity: DEFAULT_QUEUE_CAPACITY,
+            recv_queue_capacity: DEFAULT_QUEUE_CAPACITY,
+        }
+    }
+}
+
 impl Endpoint {
     /// Creates a builder for the default datagram endpoint
     pub fn builder() -> EndpointBuilder {
@@ -36,13 +47,25 @@ impl Endpoint {
     }
 }
 
+/// The number of datagrams each send and receive queue holds by default
+const DEFAULT_QUEUE_CAPACITY: usize = 200;
+
 /// A builder for the default datagram endpoint
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct EndpointBuilder {
     send_queue_capacity: usize,
     recv_queue_capacity: usize,
 }
 
+impl Default for EndpointBuilder {
+    fn default() -> Self {
+        Self {
+            send_queue_capacity: DEFAULT_QUEUE_CAPACITY,
+            recv_queue_capacity: DEFAULT_QUEUE_CAPACITY,
+        }
+    }
+}
+
 #[non_exhaustive]
 #[derive(Debug)]
 pub enum BuilderError {
@@ -200,7 +223,7 @@ struct ReceiverBuilder {
 impl Default for ReceiverBuilder {
     fn default() -> Self {
         Self {
-            queue_capacity: 200,
+            queue_capacity: DEFAULT_QUEUE_CAPACITY,
             max_datagram_frame_size: MaxDatagramFrameSize::RECOMMENDED,
         }
     }
@@ -247,6 +270,9 @@ impl ReceiverBuilder {
 /// Implement the [`Sender`](s2n-quic-core::datagram::traits::Sender) trait if
 /// this behavior is necessary for your use-case.
 ///
+/// Datagrams enqueued with [`Sender::poll_send_tracked_datagram`] are assigned a
+/// [`DatagramId`], and the [`SendOutcome`] of each is reported by
+/// [`Sender::poll_send_outcome`].
 #[derive(Debug)]
 pub struct Sender {
     queue: VecDeque<Datagram>,
@@ -258,12 +284,52 @@ pub struct Sender {
     waker: Option<Waker>,
     max_datagram_payload: u64,
     error: Option<connection::Error>,
+    next_id: u64,
+    in_flight: VecDeque<(PacketNumber, DatagramId)>,
+    outcomes: VecDeque<(DatagramId, SendOutcome)>,
+    outcome_waker: Option<Waker>,
 }
 
 #[non_exhaustive]
 #[derive(Debug, PartialEq)]
 pub struct Datagram {
     pub data: Bytes,
+    id: Option<DatagramId>,
+}
+
+impl Datagram {
+    #[inline]
+    fn new(data: Bytes, id: Option<DatagramId>) -> Self {
+        Self { data, id }
+    }
+}
+
+/// Identifies a datagram enqueued with [`Sender::poll_send_tracked_datagram`]
+///
+/// Identifiers are assigned in increasing order for each connection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DatagramId(u64);
+
+impl DatagramId {
+    /// Returns the identifier as an integer
+    #[inline]
+    pub fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
+/// The outcome of sending a tracked datagram
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SendOutcome {
+    /// The packet containing the datagram was acknowledged by the peer
+    Acked,
+    /// The packet containing the datagram was declared lost
+    ///
+    /// Datagrams are never retransmitted, so the peer will not receive it.
+    Lost,
+    /// The datagram was dropped without being sent, since it didn't fit in a packet
+    Dropped,
 }
 
 #[non_exhaustive]
@@ -275,8 +341,31 @@ pub enum DatagramError {
     ExceedsPeerTransportLimits,
     #[non_exhaustive]
     ConnectionError { error: connection::Error },
+    /// The connection's datagram provider doesn't support the operation
+    #[non_exhaustive]
+    Unsupported,
+}
+
+impl From<connection::Error> for DatagramError {
+    #[inline]
+    fn from(error: connection::Error) -> Self {
+        Self::ConnectionError { error }
+    }
 }
 
+impl From<query::Error> for DatagramError {
+    #[inline]
+    fn from(error: query::Error) -> Self {
+        match error {
+            query::Error::ConnectionLockPoisoned => connection::Error::unspecified().into(),
+            query::Error::ContextTypeMismatch => Self::Unsupported,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DatagramError {}
+
 impl fmt::Display for DatagramError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -292,6 +381,9 @@ impl fmt::Display for DatagramError {
             Self::ConnectionError { .. } => {
                 write!(f, "Connection-level error occurred.")
             }
+            Self::Unsupported => {
+                write!(f, "The datagram provider doesn't support the operation.")
+            }
         }
     }
 }
@@ -318,6 +410,66 @@ impl Sender {
         data: &mut bytes::Bytes,
         cx: &mut Context,
     ) -> Poll<Result<(), DatagramError>> {
+        self.poll_enqueue(data, false, cx).map_ok(|_id| ())
+    }
+
+    /// Enqueues a datagram for sending it towards the peer and tracks its outcome
+    ///
+    /// The outcome of sending the datagram can be polled with [`Self::poll_send_outcome`]
+    /// using the returned [`DatagramId`].
+    ///
+    /// # Return value
+    ///
+    /// - `Poll::Pending` if the datagram's send buffer capacity is currently exhausted
+    ///   and the datagram was not added to the queue. In this case, the caller should
+    ///   retry sending after the [`Waker`](core::task::Waker) on the provided
+    ///   [`Context`](core::task::Context) is notified.
+    /// - `Poll::Ready(Ok(DatagramId))` if the datagram was enqueued for sending.
+    /// - `Poll::Ready(Err(DatagramError))` if an error occurred while trying
+    ///   to send the datagram.
+    pub fn poll_send_tracked_datagram(
+        &mut self,
+        data: &mut bytes::Bytes,
+        cx: &mut Context,
+    ) -> Poll<Result<DatagramId, DatagramError>> {
+        self.poll_enqueue(data, true, cx)
+            .map_ok(|id| id.expect("tracked datagrams are assigned an id"))
+    }
+
+    /// Dequeues the outcome of sending a tracked datagram
+    ///
+    /// Outcomes are reported in the order they become known, which may differ from the order
+    /// the datagrams were enqueued. If the application doesn't keep up, the oldest outcomes
+    /// are discarded once the number of pending outcomes reaches the send queue capacity.
+    ///
+    /// # Return value
+    ///
+    /// - `Poll::Pending` if there are no outcomes to report. In this case, the caller should
+    ///   retry after the [`Waker`](core::task::Waker) on the provided
+    ///   [`Context`](core::task::Context) is notified.
+    /// - `Poll::Ready(Ok((DatagramId, SendOutcome)))` if the outcome of a datagram is known.
+    /// - `Poll::Ready(Err(DatagramError))` if a connection error occurred and no more outcomes
+    ///   will be reported.
+    pub fn poll_send_outcome(
+        &mut self,
+        cx: &mut Context,
+    ) -> Poll<Result<(DatagramId, SendOutcome), DatagramError>> {
+        if let Some(outcome) = self.outcomes.pop_front() {
+            Poll::Ready(Ok(outcome))
+        } else if let Some(error) = self.error {
+            Poll::Ready(Err(DatagramError::ConnectionError { error }))
+        } else {
+            self.outcome_waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    fn poll_enqueue(
+        &mut self,
+        data: &mut bytes::Bytes,
+        is_tracked: bool,
+        cx: &mut Context,
+    ) -> Poll<Result<Option<DatagramId>, DatagramError>> {
         if data.len() as u64 > self.max_datagram_payload {
             return Poll::Ready(Err(DatagramError::ExceedsPeerTransportLimits));
         }
@@ -333,11 +485,64 @@ impl Sender {
             return Poll::Pending;
         }
 
-        let datagram = Datagram {
-            data: core::mem::replace(data, bytes::Bytes::new()),
-        };
+        let id = is_tracked.then(|| self.next_id());
+        let datagram = Datagram::new(core::mem::replace(data, bytes::Bytes::new()), id);
         self.queue.push_back(datagram);
-        Poll::Ready(Ok(()))
+        Poll::Ready(Ok(id))
+    }
+
+    #[inline]
+    fn next_id(&mut self) -> DatagramId {
+        let id = DatagramId(self.next_id);
+        self.next_id += 1;
+        id
+    }
+
+    fn on_outcome(&mut self, id: DatagramId, outcome: SendOutcome) {
+        Self::push_outcome(&mut self.outcomes, self.capacity, id, outcome);
+
+        if let Some(w) = self.outcome_waker.take() {
+            w.wake();
+        }
+    }
+
+    #[inline]
+    fn push_outcome(
+        outcomes: &mut VecDeque<(DatagramId, SendOutcome)>,
+        capacity: usize,
+        id: DatagramId,
+        outcome: SendOutcome,
+    ) {
+        // The oldest outcome is discarded if the application isn't keeping up
+        if outcomes.len() == capacity {
+            outcomes.pop_front();
+        }
+        outcomes.push_back((id, outcome));
+    }
+
+    fn on_packet_outcome(&mut self, packet_number_range: &PacketNumberRange, outcome: SendOutcome) {
+        // Datagrams are written in packet number order, so the datagrams in the range are
+        // contiguous and can be found without visiting every datagram in flight. The oldest
+        // packets are usually acknowledged or declared lost first, so the range is typically
+        // at the front of the queue.
+        let start = self
+            .in_flight
+            .partition_point(|(packet_number, _)| *packet_number < packet_number_range.start());
+        let end = self
+            .in_flight
+            .partition_point(|(packet_number, _)| *packet_number <= packet_number_range.end());
+
+        if start == end {
+            return;
+        }
+
+        for (_packet_number, id) in self.in_flight.drain(start..end) {
+            Self::push_outcome(&mut self.outcomes, self.capacity, id, outcome);
+        }
+
+        if let Some(w) = self.outcome_waker.take() {
+            w.wake();
+        }
     }
 
     /// Adds datagrams on the queue to be sent
@@ -369,7 +574,7 @@ impl Sender {
             oldest = self.queue.pop_front();
         }
 
-        let datagram = Datagram { data };
+        let datagram = Datagram::new(data, None);
         self.queue.push_back(datagram);
 
         match oldest {
@@ -400,7 +605,7 @@ impl Sender {
             return Err(DatagramError::QueueAtCapacity);
         }
 
-        let datagram = Datagram { data };
+        let datagram = Datagram::new(data, None);
         self.queue.push_back(datagram);
         Ok(())
     }
@@ -490,14 +695,25 @@ impl super::Sender for Sender {
                 // the datagram is too large for the current packet and unlikely to ever fit so
                 // record a metric and try the next datagram in the queue
                 self.dropped_datagrams += 1;
+                if let Some(id) = datagram.id {
+                    self.on_outcome(id, SendOutcome::Dropped);
+                }
                 continue;
             }
 
             match packet.write_datagram(&datagram.data) {
-                Ok(()) => has_written = true,
+                Ok(()) => {
+                    has_written = true;
+                    if let Some(id) = datagram.id {
+                        self.in_flight.push_back((packet.packet_number(), id));
+                    }
+                }
                 Err(_error) => {
                     // TODO log this
                     self.dropped_datagrams += 1;
+                    if let Some(id) = datagram.id {
+                        self.on_outcome(id, SendOutcome::Dropped);
+                    }
                     continue;
                 }
             }
@@ -522,6 +738,19 @@ impl super::Sender for Sender {
         if let Some(w) = self.waker.take() {
             w.wake();
         }
+        if let Some(w) = self.outcome_waker.take() {
+            w.wake();
+        }
+    }
+
+    #[inline]
+    fn on_packet_ack(&mut self, packet_number_range: &PacketNumberRange) {
+        self.on_packet_outcome(packet_number_range, SendOutcome::Acked);
+    }
+
+    #[inline]
+    fn on_packet_loss(&mut self, packet_number_range: &PacketNumberRange) {
+        self.on_packet_outcome(packet_number_range, SendOutcome::Lost);
     }
 }
 
@@ -537,7 +766,7 @@ struct SenderBuilder {
 impl Default for SenderBuilder {
     fn default() -> Self {
         Self {
-            queue_capacity: 200,
+            queue_capacity: DEFAULT_QUEUE_CAPACITY,
             max_datagram_payload: 0,
         }
     }
@@ -568,6 +797,10 @@ impl SenderBuilder {
             smoothed_packet_size: 0.0,
             waker: None,
             error: None,
+            next_id: 0,
+            in_flight: VecDeque::new(),
+            outcomes: VecDeque::new(),
+            outcome_waker: None,
         })
     }
 }
@@ -701,6 +934,7 @@ mod tests {
             remaining_capacity: 10,
             has_pending_streams: false,
             datagrams_prioritized: false,
+            packet_number: pn(0),
         };
         crate::datagram::Sender::on_transmit(&mut default_sender, &mut packet);
 
@@ -794,6 +1028,7 @@ mod tests {
             remaining_capacity: 5,
             has_pending_streams: false,
             datagrams_prioritized: false,
+            packet_number: pn(0),
         };
         crate::datagram::Sender::on_transmit(&mut default_sender, &mut packet);
 
@@ -834,6 +1069,7 @@ mod tests {
             remaining_capacity: 2,
             has_pending_streams: false,
             datagrams_prioritized: false,
+            packet_number: pn(0),
         };
         crate::datagram::Sender::on_transmit(&mut default_sender, &mut packet);
 
@@ -847,6 +1083,117 @@ mod tests {
         assert_eq!(default_sender.dropped_datagrams(), 1);
     }
 
+    /// Ensures tracked datagrams report an outcome once their packet is acked or lost
+    #[test]
+    fn tracked_datagram_outcomes() {
+        let (waker, wake_count) = new_count_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let conn_info = ConnectionInfo::new(100, noop_waker());
+        let mut default_sender = Sender::builder()
+            .with_connection_info(&conn_info)
+            .build()
+            .unwrap();
+
+        assert!(default_sender.poll_send_outcome(&mut cx).is_pending());
+
+        let mut datagram = bytes::Bytes::from_static(&[1, 2, 3]);
+        let Poll::Ready(Ok(first)) =
+            default_sender.poll_send_tracked_datagram(&mut datagram.clone(), &mut cx)
+        else {
+            panic!("datagram should be enqueued")
+        };
+        // Untracked datagrams don't report an outcome
+        assert!(default_sender
+            .poll_send_datagram(&mut datagram.clone(), &mut cx)
+            .is_ready());
+
+        let mut packet = MockPacket {
+            remaining_capacity: 10,
+            has_pending_streams: false,
+            datagrams_prioritized: false,
+            packet_number: pn(1),
+        };
+        crate::datagram::Sender::on_transmit(&mut default_sender, &mut packet);
+
+        let second = default_sender.poll_send_tracked_datagram(&mut datagram, &mut cx);
+        let Poll::Ready(Ok(second)) = second else {
+            panic!("datagram should be enqueued")
+        };
+        assert!(first < second);
+
+        packet.packet_number = pn(2);
+        crate::datagram::Sender::on_transmit(&mut default_sender, &mut packet);
+        assert!(default_sender.poll_send_outcome(&mut cx).is_pending());
+
+        crate::datagram::Sender::on_packet_loss(
+            &mut default_sender,
+            &PacketNumberRange::new(pn(2), pn(2)),
+        );
+        assert_eq!(wake_count.get(), 1);
+        crate::datagram::Sender::on_packet_ack(
+            &mut default_sender,
+            &PacketNumberRange::new(pn(0), pn(2)),
+        );
+
+        assert_eq!(
+            default_sender.poll_send_outcome(&mut cx),
+            Poll::Ready(Ok((second, SendOutcome::Lost)))
+        );
+        assert_eq!(
+            default_sender.poll_send_outcome(&mut cx),
+            Poll::Ready(Ok((first, SendOutcome::Acked)))
+        );
+        assert!(default_sender.poll_send_outcome(&mut cx).is_pending());
+
+        // Outcomes are only reported once per datagram
+        crate::datagram::Sender::on_packet_ack(
+            &mut default_sender,
+            &PacketNumberRange::new(pn(0), pn(2)),
+        );
+        assert!(default_sender.poll_send_outcome(&mut cx).is_pending());
+
+        let connection_error = connection::Error::closed(crate::endpoint::Location::Remote);
+        crate::datagram::Sender::on_connection_error(&mut default_sender, connection_error);
+        assert_eq!(
+            default_sender.poll_send_outcome(&mut cx),
+            Poll::Ready(Err(DatagramError::ConnectionError {
+                error: connection_error
+            }))
+        );
+    }
+
+    /// Ensures tracked datagrams that don't fit in a packet report the `Dropped` outcome
+    #[test]
+    fn tracked_datagram_dropped() {
+        let mut cx = Context::from_waker(&noop_waker());
+
+        let conn_info = ConnectionInfo::new(100, noop_waker());
+        let mut default_sender = Sender::builder()
+            .with_connection_info(&conn_info)
+            .build()
+            .unwrap();
+
+        let mut datagram = bytes::Bytes::from_static(&[1, 2, 3]);
+        let Poll::Ready(Ok(id)) = default_sender.poll_send_tracked_datagram(&mut datagram, &mut cx)
+        else {
+            panic!("datagram should be enqueued")
+        };
+
+        let mut packet = MockPacket {
+            remaining_capacity: 2,
+            has_pending_streams: false,
+            datagrams_prioritized: false,
+            packet_number: pn(0),
+        };
+        crate::datagram::Sender::on_transmit(&mut default_sender, &mut packet);
+
+        assert_eq!(
+            default_sender.poll_send_outcome(&mut cx),
+            Poll::Ready(Ok((id, SendOutcome::Dropped)))
+        );
+    }
+
     fn fake_receive_context() -> crate::datagram::ReceiveContext<'static> {
         crate::datagram::ReceiveContext {
             path: crate::event::api::Path {
@@ -973,6 +1320,12 @@ mod tests {
         has_pending_streams: bool,
         datagrams_prioritized: bool,
         remaining_capacity: usize,
+        packet_number: PacketNumber,
+    }
+
+    fn pn(value: u8) -> PacketNumber {
+        crate::packet::number::PacketNumberSpace::ApplicationData
+            .new_packet_number(crate::varint::VarInt::from_u8(value))
     }
 
     impl crate::datagram::Packet for MockPacket {
@@ -1000,5 +1353,9 @@ mod tests {
         fn datagrams_prioritized(&self) -> bool {
             self.datagrams_prioritized
         }
+
+        fn packet_number(&self) -> PacketNumber {
+            self.packet_number
+        }
     }
 }
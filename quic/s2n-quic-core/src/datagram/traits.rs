@@ -3,7 +3,10 @@
 
 use core::task::Waker;
 
-use crate::connection;
+use crate::{
+    connection,
+    packet::number::{PacketNumber, PacketNumberRange},
+};
 
 /// The datagram endpoint trait provides a way to implement custom unreliable datagram
 /// sending and receiving logic. The Sender type should be implemented for custom
@@ -95,6 +98,24 @@ pub trait Sender: 'static + Send {
 
     /// A callback used to notify the application in the case of a connection error
     fn on_connection_error(&mut self, error: connection::Error);
+
+    /// A callback that notifies the sender that packets were acknowledged by the peer
+    ///
+    /// Use [`Packet::packet_number`] to associate written datagrams with the packets
+    /// they were sent in.
+    #[inline]
+    fn on_packet_ack(&mut self, packet_number_range: &PacketNumberRange) {
+        let _ = packet_number_range;
+    }
+
+    /// A callback that notifies the sender that packets were declared lost
+    ///
+    /// Use [`Packet::packet_number`] to associate written datagrams with the packets
+    /// they were sent in.
+    #[inline]
+    fn on_packet_loss(&mut self, packet_number_range: &PacketNumberRange) {
+        let _ = packet_number_range;
+    }
 }
 
 /// A packet will be available during the on_transmit callback. Use the methods
@@ -121,6 +142,9 @@ pub trait Packet {
     /// Datagrams get prioritized every other packet, which gives the application the best
     /// chance to send a large datagram.
     fn datagrams_prioritized(&self) -> bool;
+
+    /// Returns the number of the packet the datagrams are written to
+    fn packet_number(&self) -> PacketNumber;
 }
 
 #[non_exhaustive]
@@ -48,11 +48,18 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
-    pub struct ConnectionInfo {}
+    pub struct ConnectionInfo<'a> {
+        #[doc = " The Destination Connection ID of the first Initial packet sent by the client"]
+        pub original_destination_connection_id: ConnectionId<'a>,
+    }
     #[cfg(any(test, feature = "testing"))]
-    impl crate::event::snapshot::Fmt for ConnectionInfo {
+    impl<'a> crate::event::snapshot::Fmt for ConnectionInfo<'a> {
         fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
             let mut fmt = fmt.debug_struct("ConnectionInfo");
+            fmt.field(
+                "original_destination_connection_id",
+                &self.original_destination_connection_id,
+            );
             fmt.finish()
         }
     }
@@ -419,6 +426,31 @@ pub mod api {
         Datagram { len: u16 },
         #[non_exhaustive]
         DcStatelessResetTokens {},
+        #[non_exhaustive]
+        AckFrequency {
+            sequence_number: u64,
+            ack_eliciting_threshold: u64,
+            request_max_ack_delay: Duration,
+            reordering_threshold: u64,
+        },
+        #[non_exhaustive]
+        ImmediateAck {},
+        #[non_exhaustive]
+        ResetStreamAt {
+            id: u64,
+            error_code: u64,
+            final_size: u64,
+            reliable_size: u64,
+        },
+        #[non_exhaustive]
+        PathAck {
+            path_id: u64,
+            ecn_counts: Option<EcnCounts>,
+            largest_acknowledged: u64,
+            ack_range_count: u64,
+        },
+        #[non_exhaustive]
+        PathAbandon { path_id: u64, error_code: u64 },
     }
     impl aggregate::AsVariant for Frame {
         const VARIANTS: &'static [aggregate::info::Variant] = &[
@@ -532,6 +564,31 @@ pub mod api {
                 id: 21usize,
             }
             .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("ACK_FREQUENCY\0"),
+                id: 22usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("IMMEDIATE_ACK\0"),
+                id: 23usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("RESET_STREAM_AT\0"),
+                id: 24usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("PATH_ACK\0"),
+                id: 25usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("PATH_ABANDON\0"),
+                id: 26usize,
+            }
+            .build(),
         ];
         #[inline]
         fn variant_idx(&self) -> usize {
@@ -558,6 +615,11 @@ pub mod api {
                 Self::HandshakeDone { .. } => 19usize,
                 Self::Datagram { .. } => 20usize,
                 Self::DcStatelessResetTokens { .. } => 21usize,
+                Self::AckFrequency { .. } => 22usize,
+                Self::ImmediateAck { .. } => 23usize,
+                Self::ResetStreamAt { .. } => 24usize,
+                Self::PathAck { .. } => 25usize,
+                Self::PathAbandon { .. } => 26usize,
             }
         }
     }
@@ -1320,6 +1382,100 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The status of a client's migration to the server's preferred address"]
+    pub enum PreferredAddressMigrationStatus {
+        #[non_exhaustive]
+        #[doc = " Path validation to the preferred address was started"]
+        Started {},
+        #[non_exhaustive]
+        #[doc = " The preferred address was validated and the client migrated to it"]
+        Migrated {},
+        #[non_exhaustive]
+        #[doc = " Path validation to the preferred address failed and the client keeps using the"]
+        #[doc = " original server address"]
+        Failed {},
+    }
+    impl aggregate::AsVariant for PreferredAddressMigrationStatus {
+        const VARIANTS: &'static [aggregate::info::Variant] = &[
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("STARTED\0"),
+                id: 0usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("MIGRATED\0"),
+                id: 1usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("FAILED\0"),
+                id: 2usize,
+            }
+            .build(),
+        ];
+        #[inline]
+        fn variant_idx(&self) -> usize {
+            match self {
+                Self::Started { .. } => 0usize,
+                Self::Migrated { .. } => 1usize,
+                Self::Failed { .. } => 2usize,
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The status of a client-initiated migration to a new local address"]
+    pub enum ActiveMigrationStatus {
+        #[non_exhaustive]
+        #[doc = " Path validation from the new local address was started"]
+        Started {},
+        #[non_exhaustive]
+        #[doc = " The path from the new local address was validated and the client migrated to it"]
+        Migrated {},
+        #[non_exhaustive]
+        #[doc = " Path validation from the new local address failed and the client keeps using the"]
+        #[doc = " current local address"]
+        Failed {},
+        #[non_exhaustive]
+        #[doc = " The migration was not started since the connection is no longer able to migrate,"]
+        #[doc = " for example after the peer retired the connection ID reserved for the new path"]
+        Aborted {},
+    }
+    impl aggregate::AsVariant for ActiveMigrationStatus {
+        const VARIANTS: &'static [aggregate::info::Variant] = &[
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("STARTED\0"),
+                id: 0usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("MIGRATED\0"),
+                id: 1usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("FAILED\0"),
+                id: 2usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("ABORTED\0"),
+                id: 3usize,
+            }
+            .build(),
+        ];
+        #[inline]
+        fn variant_idx(&self) -> usize {
+            match self {
+                Self::Started { .. } => 0usize,
+                Self::Migrated { .. } => 1usize,
+                Self::Failed { .. } => 2usize,
+                Self::Aborted { .. } => 3usize,
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " The current state of the ECN controller for the path"]
     pub enum EcnState {
         #[non_exhaustive]
@@ -1608,6 +1764,18 @@ pub mod api {
         #[non_exhaustive]
         #[doc = " MTU probes larger than the current MTU were not acknowledged"]
         LargerProbesLost {},
+        #[non_exhaustive]
+        #[doc = " The search for a larger MTU was restarted after the probe interval elapsed"]
+        ProbeIntervalElapsed {},
+        #[non_exhaustive]
+        #[doc = " The search for a larger MTU was restarted after the cool off period following a blackhole"]
+        BlackholeCoolOffElapsed {},
+        #[non_exhaustive]
+        #[doc = " The application requested a new search for a larger MTU"]
+        ProbeRequested {},
+        #[non_exhaustive]
+        #[doc = " An ICMP message reported that a datagram exceeded the MTU of the path"]
+        PacketTooBig {},
     }
     impl aggregate::AsVariant for MtuUpdatedCause {
         const VARIANTS: &'static [aggregate::info::Variant] = &[
@@ -1641,6 +1809,26 @@ pub mod api {
                 id: 5usize,
             }
             .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("PROBE_INTERVAL_ELAPSED\0"),
+                id: 6usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("BLACKHOLE_COOL_OFF_ELAPSED\0"),
+                id: 7usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("PROBE_REQUESTED\0"),
+                id: 8usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("PACKET_TOO_BIG\0"),
+                id: 9usize,
+            }
+            .build(),
         ];
         #[inline]
         fn variant_idx(&self) -> usize {
@@ -1651,6 +1839,10 @@ pub mod api {
                 Self::InitialMtuPacketLost { .. } => 3usize,
                 Self::InitialMtuPacketAcknowledged { .. } => 4usize,
                 Self::LargerProbesLost { .. } => 5usize,
+                Self::ProbeIntervalElapsed { .. } => 6usize,
+                Self::BlackholeCoolOffElapsed { .. } => 7usize,
+                Self::ProbeRequested { .. } => 8usize,
+                Self::PacketTooBig { .. } => 9usize,
             }
         }
     }
@@ -2422,6 +2614,46 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The status of a client's migration to the server's preferred address was updated"]
+    pub struct PreferredAddressMigrationUpdated<'a> {
+        pub status: PreferredAddressMigrationStatus,
+        #[doc = " The path to the server's preferred address"]
+        pub path: Path<'a>,
+    }
+    #[cfg(any(test, feature = "testing"))]
+    impl<'a> crate::event::snapshot::Fmt for PreferredAddressMigrationUpdated<'a> {
+        fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let mut fmt = fmt.debug_struct("PreferredAddressMigrationUpdated");
+            fmt.field("status", &self.status);
+            fmt.field("path", &self.path);
+            fmt.finish()
+        }
+    }
+    impl<'a> Event for PreferredAddressMigrationUpdated<'a> {
+        const NAME: &'static str = "connectivity:preferred_address_migration_updated";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The status of a client-initiated migration to a new local address was updated"]
+    pub struct ActiveMigrationUpdated<'a> {
+        pub status: ActiveMigrationStatus,
+        #[doc = " The local address the client is migrating to"]
+        pub local_addr: SocketAddress<'a>,
+    }
+    #[cfg(any(test, feature = "testing"))]
+    impl<'a> crate::event::snapshot::Fmt for ActiveMigrationUpdated<'a> {
+        fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let mut fmt = fmt.debug_struct("ActiveMigrationUpdated");
+            fmt.field("status", &self.status);
+            fmt.field("local_addr", &self.local_addr);
+            fmt.finish()
+        }
+    }
+    impl<'a> Event for ActiveMigrationUpdated<'a> {
+        const NAME: &'static str = "connectivity:active_migration_updated";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     pub struct HandshakeStatusUpdated {
         pub status: HandshakeStatus,
     }
@@ -3470,6 +3702,56 @@ pub mod api {
             builder::Frame::DcStatelessResetTokens {}
         }
     }
+    impl IntoEvent<builder::Frame> for &crate::frame::AckFrequency {
+        #[inline]
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::AckFrequency {
+                sequence_number: self.sequence_number.as_u64(),
+                ack_eliciting_threshold: self.ack_eliciting_threshold.as_u64(),
+                request_max_ack_delay: self.request_max_ack_delay(),
+                reordering_threshold: self.reordering_threshold.as_u64(),
+            }
+        }
+    }
+    impl IntoEvent<builder::Frame> for &crate::frame::ImmediateAck {
+        #[inline]
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::ImmediateAck {}
+        }
+    }
+    impl IntoEvent<builder::Frame> for &crate::frame::ResetStreamAt {
+        #[inline]
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::ResetStreamAt {
+                id: self.stream_id.as_u64(),
+                error_code: self.application_error_code.as_u64(),
+                final_size: self.final_size.as_u64(),
+                reliable_size: self.reliable_size.as_u64(),
+            }
+        }
+    }
+    impl<AckRanges: crate::frame::ack::AckRanges> IntoEvent<builder::Frame>
+        for &crate::frame::PathAck<AckRanges>
+    {
+        #[inline]
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::PathAck {
+                path_id: self.path_id.as_u64(),
+                ecn_counts: self.ack.ecn_counts.map(|val| val.into_event()),
+                largest_acknowledged: self.largest_acknowledged().into_event(),
+                ack_range_count: self.ack.ack_ranges().len() as u64,
+            }
+        }
+    }
+    impl IntoEvent<builder::Frame> for &crate::frame::PathAbandon {
+        #[inline]
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::PathAbandon {
+                path_id: self.path_id.as_u64(),
+                error_code: self.error_code.as_u64(),
+            }
+        }
+    }
     #[cfg(feature = "alloc")]
     impl<'a> ConnectionCloseFrame<'a> {
         #[doc = " Converts the reason to a UTF-8 `str`, including invalid characters"]
@@ -4056,6 +4338,28 @@ pub mod tracing {
             tracing :: event ! (target : "connection_migration_denied" , parent : id , tracing :: Level :: DEBUG , { reason = tracing :: field :: debug (reason) });
         }
         #[inline]
+        fn on_preferred_address_migration_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::PreferredAddressMigrationUpdated,
+        ) {
+            let id = context.id();
+            let api::PreferredAddressMigrationUpdated { status, path } = event;
+            tracing :: event ! (target : "preferred_address_migration_updated" , parent : id , tracing :: Level :: DEBUG , { status = tracing :: field :: debug (status) , path = tracing :: field :: debug (path) });
+        }
+        #[inline]
+        fn on_active_migration_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::ActiveMigrationUpdated,
+        ) {
+            let id = context.id();
+            let api::ActiveMigrationUpdated { status, local_addr } = event;
+            tracing :: event ! (target : "active_migration_updated" , parent : id , tracing :: Level :: DEBUG , { status = tracing :: field :: debug (status) , local_addr = tracing :: field :: debug (local_addr) });
+        }
+        #[inline]
         fn on_handshake_status_updated(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -4454,12 +4758,19 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
-    pub struct ConnectionInfo {}
-    impl IntoEvent<api::ConnectionInfo> for ConnectionInfo {
+    pub struct ConnectionInfo<'a> {
+        #[doc = " The Destination Connection ID of the first Initial packet sent by the client"]
+        pub original_destination_connection_id: ConnectionId<'a>,
+    }
+    impl<'a> IntoEvent<api::ConnectionInfo<'a>> for ConnectionInfo<'a> {
         #[inline]
-        fn into_event(self) -> api::ConnectionInfo {
-            let ConnectionInfo {} = self;
-            api::ConnectionInfo {}
+        fn into_event(self) -> api::ConnectionInfo<'a> {
+            let ConnectionInfo {
+                original_destination_connection_id,
+            } = self;
+            api::ConnectionInfo {
+                original_destination_connection_id: original_destination_connection_id.into_event(),
+            }
         }
     }
     #[derive(Clone, Debug)]
@@ -4826,6 +5137,29 @@ pub mod builder {
             len: u16,
         },
         DcStatelessResetTokens,
+        AckFrequency {
+            sequence_number: u64,
+            ack_eliciting_threshold: u64,
+            request_max_ack_delay: Duration,
+            reordering_threshold: u64,
+        },
+        ImmediateAck,
+        ResetStreamAt {
+            id: u64,
+            error_code: u64,
+            final_size: u64,
+            reliable_size: u64,
+        },
+        PathAck {
+            path_id: u64,
+            ecn_counts: Option<EcnCounts>,
+            largest_acknowledged: u64,
+            ack_range_count: u64,
+        },
+        PathAbandon {
+            path_id: u64,
+            error_code: u64,
+        },
     }
     impl IntoEvent<api::Frame> for Frame {
         #[inline]
@@ -4921,6 +5255,47 @@ pub mod builder {
                     len: len.into_event(),
                 },
                 Self::DcStatelessResetTokens => DcStatelessResetTokens {},
+                Self::AckFrequency {
+                    sequence_number,
+                    ack_eliciting_threshold,
+                    request_max_ack_delay,
+                    reordering_threshold,
+                } => AckFrequency {
+                    sequence_number: sequence_number.into_event(),
+                    ack_eliciting_threshold: ack_eliciting_threshold.into_event(),
+                    request_max_ack_delay: request_max_ack_delay.into_event(),
+                    reordering_threshold: reordering_threshold.into_event(),
+                },
+                Self::ImmediateAck => ImmediateAck {},
+                Self::ResetStreamAt {
+                    id,
+                    error_code,
+                    final_size,
+                    reliable_size,
+                } => ResetStreamAt {
+                    id: id.into_event(),
+                    error_code: error_code.into_event(),
+                    final_size: final_size.into_event(),
+                    reliable_size: reliable_size.into_event(),
+                },
+                Self::PathAck {
+                    path_id,
+                    ecn_counts,
+                    largest_acknowledged,
+                    ack_range_count,
+                } => PathAck {
+                    path_id: path_id.into_event(),
+                    ecn_counts: ecn_counts.into_event(),
+                    largest_acknowledged: largest_acknowledged.into_event(),
+                    ack_range_count: ack_range_count.into_event(),
+                },
+                Self::PathAbandon {
+                    path_id,
+                    error_code,
+                } => PathAbandon {
+                    path_id: path_id.into_event(),
+                    error_code: error_code.into_event(),
+                },
             }
         }
     }
@@ -5347,6 +5722,54 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The status of a client's migration to the server's preferred address"]
+    pub enum PreferredAddressMigrationStatus {
+        #[doc = " Path validation to the preferred address was started"]
+        Started,
+        #[doc = " The preferred address was validated and the client migrated to it"]
+        Migrated,
+        #[doc = " Path validation to the preferred address failed and the client keeps using the"]
+        #[doc = " original server address"]
+        Failed,
+    }
+    impl IntoEvent<api::PreferredAddressMigrationStatus> for PreferredAddressMigrationStatus {
+        #[inline]
+        fn into_event(self) -> api::PreferredAddressMigrationStatus {
+            use api::PreferredAddressMigrationStatus::*;
+            match self {
+                Self::Started => Started {},
+                Self::Migrated => Migrated {},
+                Self::Failed => Failed {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The status of a client-initiated migration to a new local address"]
+    pub enum ActiveMigrationStatus {
+        #[doc = " Path validation from the new local address was started"]
+        Started,
+        #[doc = " The path from the new local address was validated and the client migrated to it"]
+        Migrated,
+        #[doc = " Path validation from the new local address failed and the client keeps using the"]
+        #[doc = " current local address"]
+        Failed,
+        #[doc = " The migration was not started since the connection is no longer able to migrate,"]
+        #[doc = " for example after the peer retired the connection ID reserved for the new path"]
+        Aborted,
+    }
+    impl IntoEvent<api::ActiveMigrationStatus> for ActiveMigrationStatus {
+        #[inline]
+        fn into_event(self) -> api::ActiveMigrationStatus {
+            use api::ActiveMigrationStatus::*;
+            match self {
+                Self::Started => Started {},
+                Self::Migrated => Migrated {},
+                Self::Failed => Failed {},
+                Self::Aborted => Aborted {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " The current state of the ECN controller for the path"]
     pub enum EcnState {
         #[doc = " ECN capability is being actively tested"]
@@ -5497,6 +5920,14 @@ pub mod builder {
         InitialMtuPacketAcknowledged,
         #[doc = " MTU probes larger than the current MTU were not acknowledged"]
         LargerProbesLost,
+        #[doc = " The search for a larger MTU was restarted after the probe interval elapsed"]
+        ProbeIntervalElapsed,
+        #[doc = " The search for a larger MTU was restarted after the cool off period following a blackhole"]
+        BlackholeCoolOffElapsed,
+        #[doc = " The application requested a new search for a larger MTU"]
+        ProbeRequested,
+        #[doc = " An ICMP message reported that a datagram exceeded the MTU of the path"]
+        PacketTooBig,
     }
     impl IntoEvent<api::MtuUpdatedCause> for MtuUpdatedCause {
         #[inline]
@@ -5509,6 +5940,10 @@ pub mod builder {
                 Self::InitialMtuPacketLost => InitialMtuPacketLost {},
                 Self::InitialMtuPacketAcknowledged => InitialMtuPacketAcknowledged {},
                 Self::LargerProbesLost => LargerProbesLost {},
+                Self::ProbeIntervalElapsed => ProbeIntervalElapsed {},
+                Self::BlackholeCoolOffElapsed => BlackholeCoolOffElapsed {},
+                Self::ProbeRequested => ProbeRequested {},
+                Self::PacketTooBig => PacketTooBig {},
             }
         }
     }
@@ -6192,6 +6627,42 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The status of a client's migration to the server's preferred address was updated"]
+    pub struct PreferredAddressMigrationUpdated<'a> {
+        pub status: PreferredAddressMigrationStatus,
+        #[doc = " The path to the server's preferred address"]
+        pub path: Path<'a>,
+    }
+    impl<'a> IntoEvent<api::PreferredAddressMigrationUpdated<'a>>
+        for PreferredAddressMigrationUpdated<'a>
+    {
+        #[inline]
+        fn into_event(self) -> api::PreferredAddressMigrationUpdated<'a> {
+            let PreferredAddressMigrationUpdated { status, path } = self;
+            api::PreferredAddressMigrationUpdated {
+                status: status.into_event(),
+                path: path.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The status of a client-initiated migration to a new local address was updated"]
+    pub struct ActiveMigrationUpdated<'a> {
+        pub status: ActiveMigrationStatus,
+        #[doc = " The local address the client is migrating to"]
+        pub local_addr: SocketAddress<'a>,
+    }
+    impl<'a> IntoEvent<api::ActiveMigrationUpdated<'a>> for ActiveMigrationUpdated<'a> {
+        #[inline]
+        fn into_event(self) -> api::ActiveMigrationUpdated<'a> {
+            let ActiveMigrationUpdated { status, local_addr } = self;
+            api::ActiveMigrationUpdated {
+                status: status.into_event(),
+                local_addr: local_addr.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub struct HandshakeStatusUpdated {
         pub status: HandshakeStatus,
     }
@@ -7309,6 +7780,30 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `PreferredAddressMigrationUpdated` event is triggered"]
+        #[inline]
+        fn on_preferred_address_migration_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PreferredAddressMigrationUpdated,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `ActiveMigrationUpdated` event is triggered"]
+        #[inline]
+        fn on_active_migration_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ActiveMigrationUpdated,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `HandshakeStatusUpdated` event is triggered"]
         #[inline]
         fn on_handshake_status_updated(
@@ -8045,6 +8540,26 @@ mod traits {
             (self.1).on_connection_migration_denied(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_preferred_address_migration_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PreferredAddressMigrationUpdated,
+        ) {
+            (self.0).on_preferred_address_migration_updated(&mut context.0, meta, event);
+            (self.1).on_preferred_address_migration_updated(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_active_migration_updated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ActiveMigrationUpdated,
+        ) {
+            (self.0).on_active_migration_updated(&mut context.0, meta, event);
+            (self.1).on_active_migration_updated(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_handshake_status_updated(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -8603,6 +9118,13 @@ mod traits {
         fn on_ecn_state_changed(&mut self, event: builder::EcnStateChanged);
         #[doc = "Publishes a `ConnectionMigrationDenied` event to the publisher's subscriber"]
         fn on_connection_migration_denied(&mut self, event: builder::ConnectionMigrationDenied);
+        #[doc = "Publishes a `PreferredAddressMigrationUpdated` event to the publisher's subscriber"]
+        fn on_preferred_address_migration_updated(
+            &mut self,
+            event: builder::PreferredAddressMigrationUpdated,
+        );
+        #[doc = "Publishes a `ActiveMigrationUpdated` event to the publisher's subscriber"]
+        fn on_active_migration_updated(&mut self, event: builder::ActiveMigrationUpdated);
         #[doc = "Publishes a `HandshakeStatusUpdated` event to the publisher's subscriber"]
         fn on_handshake_status_updated(&mut self, event: builder::HandshakeStatusUpdated);
         #[doc = "Publishes a `TlsExporterReady` event to the publisher's subscriber"]
@@ -8967,6 +9489,30 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_preferred_address_migration_updated(
+            &mut self,
+            event: builder::PreferredAddressMigrationUpdated,
+        ) {
+            let event = event.into_event();
+            self.subscriber.on_preferred_address_migration_updated(
+                self.context,
+                &self.meta,
+                &event,
+            );
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_active_migration_updated(&mut self, event: builder::ActiveMigrationUpdated) {
+            let event = event.into_event();
+            self.subscriber
+                .on_active_migration_updated(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn on_handshake_status_updated(&mut self, event: builder::HandshakeStatusUpdated) {
             let event = event.into_event();
             self.subscriber
@@ -9395,6 +9941,8 @@ pub mod testing {
         pub connection_id_updated: u64,
         pub ecn_state_changed: u64,
         pub connection_migration_denied: u64,
+        pub preferred_address_migration_updated: u64,
+        pub active_migration_updated: u64,
         pub handshake_status_updated: u64,
         pub tls_exporter_ready: u64,
         pub path_challenge_updated: u64,
@@ -9488,6 +10036,8 @@ pub mod testing {
                 connection_id_updated: 0,
                 ecn_state_changed: 0,
                 connection_migration_denied: 0,
+                preferred_address_migration_updated: 0,
+                active_migration_updated: 0,
                 handshake_status_updated: 0,
                 tls_exporter_ready: 0,
                 path_challenge_updated: 0,
@@ -9965,6 +10515,34 @@ pub mod testing {
                 self.output.push(out);
             }
         }
+        fn on_preferred_address_migration_updated(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PreferredAddressMigrationUpdated,
+        ) {
+            self.preferred_address_migration_updated += 1;
+            if self.location.is_some() {
+                let meta = crate::event::snapshot::Fmt::to_snapshot(meta);
+                let event = crate::event::snapshot::Fmt::to_snapshot(event);
+                let out = format!("{meta:?} {event:?}");
+                self.output.push(out);
+            }
+        }
+        fn on_active_migration_updated(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ActiveMigrationUpdated,
+        ) {
+            self.active_migration_updated += 1;
+            if self.location.is_some() {
+                let meta = crate::event::snapshot::Fmt::to_snapshot(meta);
+                let event = crate::event::snapshot::Fmt::to_snapshot(event);
+                let out = format!("{meta:?} {event:?}");
+                self.output.push(out);
+            }
+        }
         fn on_handshake_status_updated(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -10374,6 +10952,8 @@ pub mod testing {
         pub connection_id_updated: u64,
         pub ecn_state_changed: u64,
         pub connection_migration_denied: u64,
+        pub preferred_address_migration_updated: u64,
+        pub active_migration_updated: u64,
         pub handshake_status_updated: u64,
         pub tls_exporter_ready: u64,
         pub path_challenge_updated: u64,
@@ -10457,6 +11037,8 @@ pub mod testing {
                 connection_id_updated: 0,
                 ecn_state_changed: 0,
                 connection_migration_denied: 0,
+                preferred_address_migration_updated: 0,
+                active_migration_updated: 0,
                 handshake_status_updated: 0,
                 tls_exporter_ready: 0,
                 path_challenge_updated: 0,
@@ -10897,6 +11479,27 @@ pub mod testing {
                 self.output.push(out);
             }
         }
+        fn on_preferred_address_migration_updated(
+            &mut self,
+            event: builder::PreferredAddressMigrationUpdated,
+        ) {
+            self.preferred_address_migration_updated += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                let event = crate::event::snapshot::Fmt::to_snapshot(&event);
+                let out = format!("{event:?}");
+                self.output.push(out);
+            }
+        }
+        fn on_active_migration_updated(&mut self, event: builder::ActiveMigrationUpdated) {
+            self.active_migration_updated += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                let event = crate::event::snapshot::Fmt::to_snapshot(&event);
+                let out = format!("{event:?}");
+                self.output.push(out);
+            }
+        }
         fn on_handshake_status_updated(&mut self, event: builder::HandshakeStatusUpdated) {
             self.handshake_status_updated += 1;
             let event = event.into_event();
@@ -57,6 +57,8 @@ pub struct Context<R: Recorder> {
     connection_id_updated: u64,
     ecn_state_changed: u64,
     connection_migration_denied: u64,
+    preferred_address_migration_updated: u64,
+    active_migration_updated: u64,
     handshake_status_updated: u64,
     tls_exporter_ready: u64,
     path_challenge_updated: u64,
@@ -117,6 +119,8 @@ where
             connection_id_updated: 0,
             ecn_state_changed: 0,
             connection_migration_denied: 0,
+            preferred_address_migration_updated: 0,
+            active_migration_updated: 0,
             handshake_status_updated: 0,
             tls_exporter_ready: 0,
             path_challenge_updated: 0,
@@ -481,6 +485,28 @@ where
             .on_connection_migration_denied(&mut context.recorder, meta, event);
     }
     #[inline]
+    fn on_preferred_address_migration_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::PreferredAddressMigrationUpdated,
+    ) {
+        context.preferred_address_migration_updated += 1;
+        self.subscriber
+            .on_preferred_address_migration_updated(&mut context.recorder, meta, event);
+    }
+    #[inline]
+    fn on_active_migration_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ActiveMigrationUpdated,
+    ) {
+        context.active_migration_updated += 1;
+        self.subscriber
+            .on_active_migration_updated(&mut context.recorder, meta, event);
+    }
+    #[inline]
     fn on_handshake_status_updated(
         &mut self,
         context: &mut Self::ConnectionContext,
@@ -731,6 +757,14 @@ impl<R: Recorder> Drop for Context<R> {
             "connection_migration_denied",
             self.connection_migration_denied as _,
         );
+        self.recorder.increment_counter(
+            "preferred_address_migration_updated",
+            self.preferred_address_migration_updated as _,
+        );
+        self.recorder.increment_counter(
+            "active_migration_updated",
+            self.active_migration_updated as _,
+        );
         self.recorder.increment_counter(
             "handshake_status_updated",
             self.handshake_status_updated as _,
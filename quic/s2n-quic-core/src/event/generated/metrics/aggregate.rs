@@ -13,7 +13,7 @@ use crate::event::{
     },
 };
 use alloc::{boxed::Box, vec::Vec};
-static INFO: &[Info; 164usize] = &[
+static INFO: &[Info; 168usize] = &[
     info::Builder {
         id: 0usize,
         name: Str::new("application_protocol_information\0"),
@@ -466,534 +466,558 @@ static INFO: &[Info; 164usize] = &[
     .build(),
     info::Builder {
         id: 75usize,
-        name: Str::new("handshake_status_updated\0"),
+        name: Str::new("preferred_address_migration_updated\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
         id: 76usize,
+        name: Str::new("preferred_address_migration_updated.status\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 77usize,
+        name: Str::new("active_migration_updated\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 78usize,
+        name: Str::new("active_migration_updated.status\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 79usize,
+        name: Str::new("handshake_status_updated\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 80usize,
         name: Str::new("handshake_status_updated.complete.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 77usize,
+        id: 81usize,
         name: Str::new("handshake_status_updated.confirmed.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 78usize,
+        id: 82usize,
         name: Str::new("handshake_status_updated.handshake_done_acked.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 79usize,
+        id: 83usize,
         name: Str::new("handshake_status_updated.status\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 80usize,
+        id: 84usize,
         name: Str::new("tls_exporter_ready\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 81usize,
+        id: 85usize,
         name: Str::new("path_challenge_updated\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 82usize,
+        id: 86usize,
         name: Str::new("path_challenge_updated.status\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 83usize,
+        id: 87usize,
         name: Str::new("tls_client_hello\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 84usize,
+        id: 88usize,
         name: Str::new("tls_client_hello.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 85usize,
+        id: 89usize,
         name: Str::new("tls_server_hello\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 86usize,
+        id: 90usize,
         name: Str::new("tls_server_hello.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 87usize,
+        id: 91usize,
         name: Str::new("rx_stream_progress\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 88usize,
+        id: 92usize,
         name: Str::new("rx_stream_progress.bytes.total\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 89usize,
+        id: 93usize,
         name: Str::new("rx_stream_progress.bytes\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 90usize,
+        id: 94usize,
         name: Str::new("tx_stream_progress\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 91usize,
+        id: 95usize,
         name: Str::new("tx_stream_progress.bytes.total\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 92usize,
+        id: 96usize,
         name: Str::new("tx_stream_progress.bytes\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 93usize,
+        id: 97usize,
         name: Str::new("keep_alive_timer_expired\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 94usize,
+        id: 98usize,
         name: Str::new("mtu_updated\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 95usize,
+        id: 99usize,
         name: Str::new("mtu_updated.mtu\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 96usize,
+        id: 100usize,
         name: Str::new("mtu_updated.cause\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 97usize,
+        id: 101usize,
         name: Str::new("mtu_updated.search_complete\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 98usize,
+        id: 102usize,
         name: Str::new("slow_start_exited\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 99usize,
+        id: 103usize,
         name: Str::new("slow_start_exited.cause\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 100usize,
+        id: 104usize,
         name: Str::new("slow_start_exited.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 101usize,
+        id: 105usize,
         name: Str::new("slow_start_exited.congestion_window\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 102usize,
+        id: 106usize,
         name: Str::new("delivery_rate_sampled\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 103usize,
+        id: 107usize,
         name: Str::new("pacing_rate_updated\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 104usize,
+        id: 108usize,
         name: Str::new("pacing_rate_updated.bytes_per_second\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 105usize,
+        id: 109usize,
         name: Str::new("pacing_rate_updated.burst_size\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 106usize,
+        id: 110usize,
         name: Str::new("pacing_rate_updated.pacing_gain\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 107usize,
+        id: 111usize,
         name: Str::new("bbr_state_changed\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 108usize,
+        id: 112usize,
         name: Str::new("bbr_state_changed.state\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 109usize,
+        id: 113usize,
         name: Str::new("dc_state_changed\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 110usize,
+        id: 114usize,
         name: Str::new("dc_state_changed.version_negotiated.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 111usize,
+        id: 115usize,
         name: Str::new("dc_state_changed.no_version_negotiated.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 112usize,
+        id: 116usize,
         name: Str::new("dc_state_changed.path_secrets.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 113usize,
+        id: 117usize,
         name: Str::new("dc_state_changed.complete.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 114usize,
+        id: 118usize,
         name: Str::new("dc_state_changed.state\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 115usize,
+        id: 119usize,
         name: Str::new("dc_path_created\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 116usize,
+        id: 120usize,
         name: Str::new("connection_closed\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 117usize,
+        id: 121usize,
         name: Str::new("connection_closed.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 118usize,
+        id: 122usize,
         name: Str::new("connection_closed.error\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 119usize,
+        id: 123usize,
         name: Str::new("version_information\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 120usize,
+        id: 124usize,
         name: Str::new("endpoint_packet_sent\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 121usize,
+        id: 125usize,
         name: Str::new("endpoint_packet_received\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 122usize,
+        id: 126usize,
         name: Str::new("endpoint_datagram_sent\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 123usize,
+        id: 127usize,
         name: Str::new("endpoint_datagram_sent.bytes\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 124usize,
+        id: 128usize,
         name: Str::new("endpoint_datagram_sent.bytes.total\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 125usize,
+        id: 129usize,
         name: Str::new("endpoint_datagram_sent.gso_offset\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 126usize,
+        id: 130usize,
         name: Str::new("endpoint_datagram_received\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 127usize,
+        id: 131usize,
         name: Str::new("endpoint_datagram_received.bytes\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 128usize,
+        id: 132usize,
         name: Str::new("endpoint_datagram_received.bytes.total\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 129usize,
+        id: 133usize,
         name: Str::new("endpoint_datagram_dropped\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 130usize,
+        id: 134usize,
         name: Str::new("endpoint_datagram_dropped.bytes\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 131usize,
+        id: 135usize,
         name: Str::new("endpoint_datagram_dropped.bytes.total\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 132usize,
+        id: 136usize,
         name: Str::new("endpoint_datagram_dropped.reason\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 133usize,
+        id: 137usize,
         name: Str::new("endpoint_connection_attempt_failed\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 134usize,
+        id: 138usize,
         name: Str::new("endpoint_connection_attempt_failed.error\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 135usize,
+        id: 139usize,
         name: Str::new("platform_tx\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 136usize,
+        id: 140usize,
         name: Str::new("platform_tx.packets.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 137usize,
+        id: 141usize,
         name: Str::new("platform_tx.packets\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 138usize,
+        id: 142usize,
         name: Str::new("platform_tx.syscalls.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 139usize,
+        id: 143usize,
         name: Str::new("platform_tx.syscalls\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 140usize,
+        id: 144usize,
         name: Str::new("platform_tx.syscalls.blocked.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 141usize,
+        id: 145usize,
         name: Str::new("platform_tx.syscalls.blocked\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 142usize,
+        id: 146usize,
         name: Str::new("platform_tx.errors.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 143usize,
+        id: 147usize,
         name: Str::new("platform_tx.errors\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 144usize,
+        id: 148usize,
         name: Str::new("platform_tx.errors.dropped.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 145usize,
+        id: 149usize,
         name: Str::new("platform_tx.errors.dropped\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 146usize,
+        id: 150usize,
         name: Str::new("platform_tx_error\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 147usize,
+        id: 151usize,
         name: Str::new("platform_rx\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 148usize,
+        id: 152usize,
         name: Str::new("platform_rx.packets.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 149usize,
+        id: 153usize,
         name: Str::new("platform_rx.packets\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 150usize,
+        id: 154usize,
         name: Str::new("platform_rx.syscalls.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 151usize,
+        id: 155usize,
         name: Str::new("platform_rx.syscalls\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 152usize,
+        id: 156usize,
         name: Str::new("platform_rx.syscalls.blocked.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 153usize,
+        id: 157usize,
         name: Str::new("platform_rx.syscalls.blocked\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 154usize,
+        id: 158usize,
         name: Str::new("platform_rx.errors.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 155usize,
+        id: 159usize,
         name: Str::new("platform_rx.errors\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 156usize,
+        id: 160usize,
         name: Str::new("platform_rx.errors.dropped.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 157usize,
+        id: 161usize,
         name: Str::new("platform_rx.errors.dropped\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 158usize,
+        id: 162usize,
         name: Str::new("platform_rx_error\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 159usize,
+        id: 163usize,
         name: Str::new("platform_feature_configured\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 160usize,
+        id: 164usize,
         name: Str::new("platform_event_loop_wakeup\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 161usize,
+        id: 165usize,
         name: Str::new("platform_event_loop_sleep\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 162usize,
+        id: 166usize,
         name: Str::new("platform_event_loop_sleep.processing_duration\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 163usize,
+        id: 167usize,
         name: Str::new("platform_event_loop_started\0"),
         units: Units::None,
     }
@@ -1006,13 +1030,13 @@ pub struct ConnectionContext {
 }
 pub struct Subscriber<R: Registry> {
     #[allow(dead_code)]
-    counters: Box<[R::Counter; 78usize]>,
+    counters: Box<[R::Counter; 80usize]>,
     #[allow(dead_code)]
     bool_counters: Box<[R::BoolCounter; 3usize]>,
     #[allow(dead_code)]
     nominal_counters: Box<[R::NominalCounter]>,
     #[allow(dead_code)]
-    nominal_counter_offsets: Box<[usize; 29usize]>,
+    nominal_counter_offsets: Box<[usize; 31usize]>,
     #[allow(dead_code)]
     measures: Box<[R::Measure; 38usize]>,
     #[allow(dead_code)]
@@ -1041,10 +1065,10 @@ impl<R: Registry> Subscriber<R> {
     #[allow(unused_mut)]
     #[inline]
     pub fn new(registry: R) -> Self {
-        let mut counters = Vec::with_capacity(78usize);
+        let mut counters = Vec::with_capacity(80usize);
         let mut bool_counters = Vec::with_capacity(3usize);
-        let mut nominal_counters = Vec::with_capacity(29usize);
-        let mut nominal_counter_offsets = Vec::with_capacity(29usize);
+        let mut nominal_counters = Vec::with_capacity(31usize);
+        let mut nominal_counter_offsets = Vec::with_capacity(31usize);
         let mut measures = Vec::with_capacity(38usize);
         let mut gauges = Vec::with_capacity(0usize);
         let mut timers = Vec::with_capacity(15usize);
@@ -1086,51 +1110,53 @@ impl<R: Registry> Subscriber<R> {
         counters.push(registry.register_counter(&INFO[71usize]));
         counters.push(registry.register_counter(&INFO[73usize]));
         counters.push(registry.register_counter(&INFO[75usize]));
-        counters.push(registry.register_counter(&INFO[80usize]));
-        counters.push(registry.register_counter(&INFO[81usize]));
-        counters.push(registry.register_counter(&INFO[83usize]));
+        counters.push(registry.register_counter(&INFO[77usize]));
+        counters.push(registry.register_counter(&INFO[79usize]));
+        counters.push(registry.register_counter(&INFO[84usize]));
         counters.push(registry.register_counter(&INFO[85usize]));
         counters.push(registry.register_counter(&INFO[87usize]));
-        counters.push(registry.register_counter(&INFO[88usize]));
-        counters.push(registry.register_counter(&INFO[90usize]));
+        counters.push(registry.register_counter(&INFO[89usize]));
         counters.push(registry.register_counter(&INFO[91usize]));
-        counters.push(registry.register_counter(&INFO[93usize]));
+        counters.push(registry.register_counter(&INFO[92usize]));
         counters.push(registry.register_counter(&INFO[94usize]));
+        counters.push(registry.register_counter(&INFO[95usize]));
+        counters.push(registry.register_counter(&INFO[97usize]));
         counters.push(registry.register_counter(&INFO[98usize]));
         counters.push(registry.register_counter(&INFO[102usize]));
-        counters.push(registry.register_counter(&INFO[103usize]));
+        counters.push(registry.register_counter(&INFO[106usize]));
         counters.push(registry.register_counter(&INFO[107usize]));
-        counters.push(registry.register_counter(&INFO[109usize]));
-        counters.push(registry.register_counter(&INFO[115usize]));
-        counters.push(registry.register_counter(&INFO[116usize]));
+        counters.push(registry.register_counter(&INFO[111usize]));
+        counters.push(registry.register_counter(&INFO[113usize]));
         counters.push(registry.register_counter(&INFO[119usize]));
         counters.push(registry.register_counter(&INFO[120usize]));
-        counters.push(registry.register_counter(&INFO[121usize]));
-        counters.push(registry.register_counter(&INFO[122usize]));
+        counters.push(registry.register_counter(&INFO[123usize]));
+        counters.push(registry.register_counter(&INFO[124usize]));
+        counters.push(registry.register_counter(&INFO[125usize]));
         counters.push(registry.register_counter(&INFO[126usize]));
-        counters.push(registry.register_counter(&INFO[129usize]));
+        counters.push(registry.register_counter(&INFO[130usize]));
         counters.push(registry.register_counter(&INFO[133usize]));
-        counters.push(registry.register_counter(&INFO[135usize]));
-        counters.push(registry.register_counter(&INFO[136usize]));
-        counters.push(registry.register_counter(&INFO[138usize]));
+        counters.push(registry.register_counter(&INFO[137usize]));
+        counters.push(registry.register_counter(&INFO[139usize]));
         counters.push(registry.register_counter(&INFO[140usize]));
         counters.push(registry.register_counter(&INFO[142usize]));
         counters.push(registry.register_counter(&INFO[144usize]));
         counters.push(registry.register_counter(&INFO[146usize]));
-        counters.push(registry.register_counter(&INFO[147usize]));
         counters.push(registry.register_counter(&INFO[148usize]));
         counters.push(registry.register_counter(&INFO[150usize]));
+        counters.push(registry.register_counter(&INFO[151usize]));
         counters.push(registry.register_counter(&INFO[152usize]));
         counters.push(registry.register_counter(&INFO[154usize]));
         counters.push(registry.register_counter(&INFO[156usize]));
         counters.push(registry.register_counter(&INFO[158usize]));
-        counters.push(registry.register_counter(&INFO[159usize]));
         counters.push(registry.register_counter(&INFO[160usize]));
-        counters.push(registry.register_counter(&INFO[161usize]));
+        counters.push(registry.register_counter(&INFO[162usize]));
         counters.push(registry.register_counter(&INFO[163usize]));
+        counters.push(registry.register_counter(&INFO[164usize]));
+        counters.push(registry.register_counter(&INFO[165usize]));
+        counters.push(registry.register_counter(&INFO[167usize]));
         bool_counters.push(registry.register_bool_counter(&INFO[24usize]));
         bool_counters.push(registry.register_bool_counter(&INFO[34usize]));
-        bool_counters.push(registry.register_bool_counter(&INFO[97usize]));
+        bool_counters.push(registry.register_bool_counter(&INFO[101usize]));
         {
             #[allow(unused_imports)]
             use api::*;
@@ -1354,12 +1380,34 @@ impl<R: Registry> Subscriber<R> {
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
                 nominal_counter_offsets.push(offset);
             }
+            {
+                let offset = nominal_counters.len();
+                let mut count = 0;
+                for variant in <PreferredAddressMigrationStatus as AsVariant>::VARIANTS.iter() {
+                    nominal_counters
+                        .push(registry.register_nominal_counter(&INFO[76usize], variant));
+                    count += 1;
+                }
+                debug_assert_ne!(count, 0, "field type needs at least one variant");
+                nominal_counter_offsets.push(offset);
+            }
+            {
+                let offset = nominal_counters.len();
+                let mut count = 0;
+                for variant in <ActiveMigrationStatus as AsVariant>::VARIANTS.iter() {
+                    nominal_counters
+                        .push(registry.register_nominal_counter(&INFO[78usize], variant));
+                    count += 1;
+                }
+                debug_assert_ne!(count, 0, "field type needs at least one variant");
+                nominal_counter_offsets.push(offset);
+            }
             {
                 let offset = nominal_counters.len();
                 let mut count = 0;
                 for variant in <HandshakeStatus as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[79usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[83usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1370,7 +1418,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <PathChallengeStatus as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[82usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[86usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1381,7 +1429,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <MtuUpdatedCause as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[96usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[100usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1392,7 +1440,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <SlowStartExitCause as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[99usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[103usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1403,7 +1451,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <BbrState as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[108usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[112usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1414,7 +1462,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <DcState as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[114usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[118usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1425,7 +1473,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <crate::connection::Error as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[118usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[122usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1436,7 +1484,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <DatagramDropReason as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[132usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[136usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1447,7 +1495,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <crate::connection::Error as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[134usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[138usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1468,45 +1516,45 @@ impl<R: Registry> Subscriber<R> {
         measures.push(registry.register_measure(&INFO[61usize]));
         measures.push(registry.register_measure(&INFO[64usize]));
         measures.push(registry.register_measure(&INFO[67usize]));
-        measures.push(registry.register_measure(&INFO[89usize]));
-        measures.push(registry.register_measure(&INFO[92usize]));
-        measures.push(registry.register_measure(&INFO[95usize]));
-        measures.push(registry.register_measure(&INFO[101usize]));
-        measures.push(registry.register_measure(&INFO[104usize]));
+        measures.push(registry.register_measure(&INFO[93usize]));
+        measures.push(registry.register_measure(&INFO[96usize]));
+        measures.push(registry.register_measure(&INFO[99usize]));
         measures.push(registry.register_measure(&INFO[105usize]));
-        measures.push(registry.register_measure(&INFO[106usize]));
-        measures.push(registry.register_measure(&INFO[123usize]));
-        measures.push(registry.register_measure(&INFO[124usize]));
-        measures.push(registry.register_measure(&INFO[125usize]));
+        measures.push(registry.register_measure(&INFO[108usize]));
+        measures.push(registry.register_measure(&INFO[109usize]));
+        measures.push(registry.register_measure(&INFO[110usize]));
         measures.push(registry.register_measure(&INFO[127usize]));
         measures.push(registry.register_measure(&INFO[128usize]));
-        measures.push(registry.register_measure(&INFO[130usize]));
+        measures.push(registry.register_measure(&INFO[129usize]));
         measures.push(registry.register_measure(&INFO[131usize]));
-        measures.push(registry.register_measure(&INFO[137usize]));
-        measures.push(registry.register_measure(&INFO[139usize]));
+        measures.push(registry.register_measure(&INFO[132usize]));
+        measures.push(registry.register_measure(&INFO[134usize]));
+        measures.push(registry.register_measure(&INFO[135usize]));
         measures.push(registry.register_measure(&INFO[141usize]));
         measures.push(registry.register_measure(&INFO[143usize]));
         measures.push(registry.register_measure(&INFO[145usize]));
+        measures.push(registry.register_measure(&INFO[147usize]));
         measures.push(registry.register_measure(&INFO[149usize]));
-        measures.push(registry.register_measure(&INFO[151usize]));
         measures.push(registry.register_measure(&INFO[153usize]));
         measures.push(registry.register_measure(&INFO[155usize]));
         measures.push(registry.register_measure(&INFO[157usize]));
+        measures.push(registry.register_measure(&INFO[159usize]));
+        measures.push(registry.register_measure(&INFO[161usize]));
         timers.push(registry.register_timer(&INFO[48usize]));
         timers.push(registry.register_timer(&INFO[49usize]));
         timers.push(registry.register_timer(&INFO[50usize]));
         timers.push(registry.register_timer(&INFO[57usize]));
-        timers.push(registry.register_timer(&INFO[76usize]));
-        timers.push(registry.register_timer(&INFO[77usize]));
-        timers.push(registry.register_timer(&INFO[78usize]));
-        timers.push(registry.register_timer(&INFO[84usize]));
-        timers.push(registry.register_timer(&INFO[86usize]));
-        timers.push(registry.register_timer(&INFO[110usize]));
-        timers.push(registry.register_timer(&INFO[111usize]));
-        timers.push(registry.register_timer(&INFO[112usize]));
-        timers.push(registry.register_timer(&INFO[113usize]));
+        timers.push(registry.register_timer(&INFO[80usize]));
+        timers.push(registry.register_timer(&INFO[81usize]));
+        timers.push(registry.register_timer(&INFO[82usize]));
+        timers.push(registry.register_timer(&INFO[88usize]));
+        timers.push(registry.register_timer(&INFO[90usize]));
+        timers.push(registry.register_timer(&INFO[114usize]));
+        timers.push(registry.register_timer(&INFO[115usize]));
+        timers.push(registry.register_timer(&INFO[116usize]));
         timers.push(registry.register_timer(&INFO[117usize]));
-        timers.push(registry.register_timer(&INFO[162usize]));
+        timers.push(registry.register_timer(&INFO[121usize]));
+        timers.push(registry.register_timer(&INFO[166usize]));
         {
             #[allow(unused_imports)]
             use api::*;
@@ -1514,7 +1562,7 @@ impl<R: Registry> Subscriber<R> {
                 let offset = nominal_timers.len();
                 let mut count = 0;
                 for variant in <SlowStartExitCause as AsVariant>::VARIANTS.iter() {
-                    nominal_timers.push(registry.register_nominal_timer(&INFO[100usize], variant));
+                    nominal_timers.push(registry.register_nominal_timer(&INFO[104usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1587,48 +1635,50 @@ impl<R: Registry> Subscriber<R> {
                 33usize => (&INFO[71usize], entry),
                 34usize => (&INFO[73usize], entry),
                 35usize => (&INFO[75usize], entry),
-                36usize => (&INFO[80usize], entry),
-                37usize => (&INFO[81usize], entry),
-                38usize => (&INFO[83usize], entry),
+                36usize => (&INFO[77usize], entry),
+                37usize => (&INFO[79usize], entry),
+                38usize => (&INFO[84usize], entry),
                 39usize => (&INFO[85usize], entry),
                 40usize => (&INFO[87usize], entry),
-                41usize => (&INFO[88usize], entry),
-                42usize => (&INFO[90usize], entry),
-                43usize => (&INFO[91usize], entry),
-                44usize => (&INFO[93usize], entry),
-                45usize => (&INFO[94usize], entry),
-                46usize => (&INFO[98usize], entry),
-                47usize => (&INFO[102usize], entry),
-                48usize => (&INFO[103usize], entry),
-                49usize => (&INFO[107usize], entry),
-                50usize => (&INFO[109usize], entry),
-                51usize => (&INFO[115usize], entry),
-                52usize => (&INFO[116usize], entry),
+                41usize => (&INFO[89usize], entry),
+                42usize => (&INFO[91usize], entry),
+                43usize => (&INFO[92usize], entry),
+                44usize => (&INFO[94usize], entry),
+                45usize => (&INFO[95usize], entry),
+                46usize => (&INFO[97usize], entry),
+                47usize => (&INFO[98usize], entry),
+                48usize => (&INFO[102usize], entry),
+                49usize => (&INFO[106usize], entry),
+                50usize => (&INFO[107usize], entry),
+                51usize => (&INFO[111usize], entry),
+                52usize => (&INFO[113usize], entry),
                 53usize => (&INFO[119usize], entry),
                 54usize => (&INFO[120usize], entry),
-                55usize => (&INFO[121usize], entry),
-                56usize => (&INFO[122usize], entry),
-                57usize => (&INFO[126usize], entry),
-                58usize => (&INFO[129usize], entry),
-                59usize => (&INFO[133usize], entry),
-                60usize => (&INFO[135usize], entry),
-                61usize => (&INFO[136usize], entry),
-                62usize => (&INFO[138usize], entry),
+                55usize => (&INFO[123usize], entry),
+                56usize => (&INFO[124usize], entry),
+                57usize => (&INFO[125usize], entry),
+                58usize => (&INFO[126usize], entry),
+                59usize => (&INFO[130usize], entry),
+                60usize => (&INFO[133usize], entry),
+                61usize => (&INFO[137usize], entry),
+                62usize => (&INFO[139usize], entry),
                 63usize => (&INFO[140usize], entry),
                 64usize => (&INFO[142usize], entry),
                 65usize => (&INFO[144usize], entry),
                 66usize => (&INFO[146usize], entry),
-                67usize => (&INFO[147usize], entry),
-                68usize => (&INFO[148usize], entry),
-                69usize => (&INFO[150usize], entry),
+                67usize => (&INFO[148usize], entry),
+                68usize => (&INFO[150usize], entry),
+                69usize => (&INFO[151usize], entry),
                 70usize => (&INFO[152usize], entry),
                 71usize => (&INFO[154usize], entry),
                 72usize => (&INFO[156usize], entry),
                 73usize => (&INFO[158usize], entry),
-                74usize => (&INFO[159usize], entry),
-                75usize => (&INFO[160usize], entry),
-                76usize => (&INFO[161usize], entry),
-                77usize => (&INFO[163usize], entry),
+                74usize => (&INFO[160usize], entry),
+                75usize => (&INFO[162usize], entry),
+                76usize => (&INFO[163usize], entry),
+                77usize => (&INFO[164usize], entry),
+                78usize => (&INFO[165usize], entry),
+                79usize => (&INFO[167usize], entry),
                 _ => unsafe { core::hint::unreachable_unchecked() },
             })
     }
@@ -1648,7 +1698,7 @@ impl<R: Registry> Subscriber<R> {
             .map(|(idx, entry)| match idx {
                 0usize => (&INFO[24usize], entry),
                 1usize => (&INFO[34usize], entry),
-                2usize => (&INFO[97usize], entry),
+                2usize => (&INFO[101usize], entry),
                 _ => unsafe { core::hint::unreachable_unchecked() },
             })
     }
@@ -1791,57 +1841,69 @@ impl<R: Registry> Subscriber<R> {
                 }
                 20usize => {
                     let offset = *entry;
-                    let variants = <HandshakeStatus as AsVariant>::VARIANTS;
+                    let variants = <PreferredAddressMigrationStatus as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[79usize], entries, variants)
+                    (&INFO[76usize], entries, variants)
                 }
                 21usize => {
                     let offset = *entry;
-                    let variants = <PathChallengeStatus as AsVariant>::VARIANTS;
+                    let variants = <ActiveMigrationStatus as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[82usize], entries, variants)
+                    (&INFO[78usize], entries, variants)
                 }
                 22usize => {
                     let offset = *entry;
-                    let variants = <MtuUpdatedCause as AsVariant>::VARIANTS;
+                    let variants = <HandshakeStatus as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[96usize], entries, variants)
+                    (&INFO[83usize], entries, variants)
                 }
                 23usize => {
                     let offset = *entry;
-                    let variants = <SlowStartExitCause as AsVariant>::VARIANTS;
+                    let variants = <PathChallengeStatus as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[99usize], entries, variants)
+                    (&INFO[86usize], entries, variants)
                 }
                 24usize => {
                     let offset = *entry;
-                    let variants = <BbrState as AsVariant>::VARIANTS;
+                    let variants = <MtuUpdatedCause as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[108usize], entries, variants)
+                    (&INFO[100usize], entries, variants)
                 }
                 25usize => {
                     let offset = *entry;
-                    let variants = <DcState as AsVariant>::VARIANTS;
+                    let variants = <SlowStartExitCause as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[114usize], entries, variants)
+                    (&INFO[103usize], entries, variants)
                 }
                 26usize => {
                     let offset = *entry;
-                    let variants = <crate::connection::Error as AsVariant>::VARIANTS;
+                    let variants = <BbrState as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[118usize], entries, variants)
+                    (&INFO[112usize], entries, variants)
                 }
                 27usize => {
                     let offset = *entry;
-                    let variants = <DatagramDropReason as AsVariant>::VARIANTS;
+                    let variants = <DcState as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[132usize], entries, variants)
+                    (&INFO[118usize], entries, variants)
                 }
                 28usize => {
                     let offset = *entry;
                     let variants = <crate::connection::Error as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[134usize], entries, variants)
+                    (&INFO[122usize], entries, variants)
+                }
+                29usize => {
+                    let offset = *entry;
+                    let variants = <DatagramDropReason as AsVariant>::VARIANTS;
+                    let entries = &self.nominal_counters[offset..offset + variants.len()];
+                    (&INFO[136usize], entries, variants)
+                }
+                30usize => {
+                    let offset = *entry;
+                    let variants = <crate::connection::Error as AsVariant>::VARIANTS;
+                    let entries = &self.nominal_counters[offset..offset + variants.len()];
+                    (&INFO[138usize], entries, variants)
                 }
                 _ => unsafe { core::hint::unreachable_unchecked() },
             })
@@ -1875,30 +1937,30 @@ impl<R: Registry> Subscriber<R> {
                 11usize => (&INFO[61usize], entry),
                 12usize => (&INFO[64usize], entry),
                 13usize => (&INFO[67usize], entry),
-                14usize => (&INFO[89usize], entry),
-                15usize => (&INFO[92usize], entry),
-                16usize => (&INFO[95usize], entry),
-                17usize => (&INFO[101usize], entry),
-                18usize => (&INFO[104usize], entry),
-                19usize => (&INFO[105usize], entry),
-                20usize => (&INFO[106usize], entry),
-                21usize => (&INFO[123usize], entry),
-                22usize => (&INFO[124usize], entry),
-                23usize => (&INFO[125usize], entry),
-                24usize => (&INFO[127usize], entry),
-                25usize => (&INFO[128usize], entry),
-                26usize => (&INFO[130usize], entry),
-                27usize => (&INFO[131usize], entry),
-                28usize => (&INFO[137usize], entry),
-                29usize => (&INFO[139usize], entry),
-                30usize => (&INFO[141usize], entry),
-                31usize => (&INFO[143usize], entry),
-                32usize => (&INFO[145usize], entry),
-                33usize => (&INFO[149usize], entry),
-                34usize => (&INFO[151usize], entry),
-                35usize => (&INFO[153usize], entry),
-                36usize => (&INFO[155usize], entry),
-                37usize => (&INFO[157usize], entry),
+                14usize => (&INFO[93usize], entry),
+                15usize => (&INFO[96usize], entry),
+                16usize => (&INFO[99usize], entry),
+                17usize => (&INFO[105usize], entry),
+                18usize => (&INFO[108usize], entry),
+                19usize => (&INFO[109usize], entry),
+                20usize => (&INFO[110usize], entry),
+                21usize => (&INFO[127usize], entry),
+                22usize => (&INFO[128usize], entry),
+                23usize => (&INFO[129usize], entry),
+                24usize => (&INFO[131usize], entry),
+                25usize => (&INFO[132usize], entry),
+                26usize => (&INFO[134usize], entry),
+                27usize => (&INFO[135usize], entry),
+                28usize => (&INFO[141usize], entry),
+                29usize => (&INFO[143usize], entry),
+                30usize => (&INFO[145usize], entry),
+                31usize => (&INFO[147usize], entry),
+                32usize => (&INFO[149usize], entry),
+                33usize => (&INFO[153usize], entry),
+                34usize => (&INFO[155usize], entry),
+                35usize => (&INFO[157usize], entry),
+                36usize => (&INFO[159usize], entry),
+                37usize => (&INFO[161usize], entry),
                 _ => unsafe { core::hint::unreachable_unchecked() },
             })
     }
@@ -1932,17 +1994,17 @@ impl<R: Registry> Subscriber<R> {
                 1usize => (&INFO[49usize], entry),
                 2usize => (&INFO[50usize], entry),
                 3usize => (&INFO[57usize], entry),
-                4usize => (&INFO[76usize], entry),
-                5usize => (&INFO[77usize], entry),
-                6usize => (&INFO[78usize], entry),
-                7usize => (&INFO[84usize], entry),
-                8usize => (&INFO[86usize], entry),
-                9usize => (&INFO[110usize], entry),
-                10usize => (&INFO[111usize], entry),
-                11usize => (&INFO[112usize], entry),
-                12usize => (&INFO[113usize], entry),
-                13usize => (&INFO[117usize], entry),
-                14usize => (&INFO[162usize], entry),
+                4usize => (&INFO[80usize], entry),
+                5usize => (&INFO[81usize], entry),
+                6usize => (&INFO[82usize], entry),
+                7usize => (&INFO[88usize], entry),
+                8usize => (&INFO[90usize], entry),
+                9usize => (&INFO[114usize], entry),
+                10usize => (&INFO[115usize], entry),
+                11usize => (&INFO[116usize], entry),
+                12usize => (&INFO[117usize], entry),
+                13usize => (&INFO[121usize], entry),
+                14usize => (&INFO[166usize], entry),
                 _ => unsafe { core::hint::unreachable_unchecked() },
             })
     }
@@ -2482,6 +2544,36 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
         let _ = event;
     }
     #[inline]
+    fn on_preferred_address_migration_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::PreferredAddressMigrationUpdated,
+    ) {
+        #[allow(unused_imports)]
+        use api::*;
+        self.count(75usize, 35usize, 1usize);
+        self.count_nominal(76usize, 20usize, &event.status);
+        let _ = context;
+        let _ = meta;
+        let _ = event;
+    }
+    #[inline]
+    fn on_active_migration_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ActiveMigrationUpdated,
+    ) {
+        #[allow(unused_imports)]
+        use api::*;
+        self.count(77usize, 36usize, 1usize);
+        self.count_nominal(78usize, 21usize, &event.status);
+        let _ = context;
+        let _ = meta;
+        let _ = event;
+    }
+    #[inline]
     fn on_handshake_status_updated(
         &mut self,
         context: &mut Self::ConnectionContext,
@@ -2490,14 +2582,14 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(75usize, 35usize, 1usize);
+        self.count(79usize, 37usize, 1usize);
         {
             fn check(evt: &api::HandshakeStatusUpdated) -> bool {
                 matches!(evt.status, HandshakeStatus::Complete { .. })
             }
             if check(event) {
                 self.time(
-                    76usize,
+                    80usize,
                     4usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
@@ -2509,7 +2601,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
             }
             if check(event) {
                 self.time(
-                    77usize,
+                    81usize,
                     5usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
@@ -2521,13 +2613,13 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
             }
             if check(event) {
                 self.time(
-                    78usize,
+                    82usize,
                     6usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
             }
         }
-        self.count_nominal(79usize, 20usize, &event.status);
+        self.count_nominal(83usize, 22usize, &event.status);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2541,7 +2633,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(80usize, 36usize, 1usize);
+        self.count(84usize, 38usize, 1usize);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2555,8 +2647,8 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(81usize, 37usize, 1usize);
-        self.count_nominal(82usize, 21usize, &event.path_challenge_status);
+        self.count(85usize, 39usize, 1usize);
+        self.count_nominal(86usize, 23usize, &event.path_challenge_status);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2570,9 +2662,9 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(83usize, 38usize, 1usize);
+        self.count(87usize, 40usize, 1usize);
         self.time(
-            84usize,
+            88usize,
             7usize,
             meta.timestamp.saturating_duration_since(context.start_time),
         );
@@ -2589,9 +2681,9 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(85usize, 39usize, 1usize);
+        self.count(89usize, 41usize, 1usize);
         self.time(
-            86usize,
+            90usize,
             8usize,
             meta.timestamp.saturating_duration_since(context.start_time),
         );
@@ -2608,9 +2700,9 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(87usize, 40usize, 1usize);
-        self.count(88usize, 41usize, event.bytes);
-        self.measure(89usize, 14usize, event.bytes);
+        self.count(91usize, 42usize, 1usize);
+        self.count(92usize, 43usize, event.bytes);
+        self.measure(93usize, 14usize, event.bytes);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2624,9 +2716,9 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(90usize, 42usize, 1usize);
-        self.count(91usize, 43usize, event.bytes);
-        self.measure(92usize, 15usize, event.bytes);
+        self.count(94usize, 44usize, 1usize);
+        self.count(95usize, 45usize, event.bytes);
+        self.measure(96usize, 15usize, event.bytes);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2640,7 +2732,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(93usize, 44usize, 1usize);
+        self.count(97usize, 46usize, 1usize);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2654,10 +2746,10 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(94usize, 45usize, 1usize);
-        self.measure(95usize, 16usize, event.mtu);
-        self.count_nominal(96usize, 22usize, &event.cause);
-        self.count_bool(97usize, 2usize, event.search_complete);
+        self.count(98usize, 47usize, 1usize);
+        self.measure(99usize, 16usize, event.mtu);
+        self.count_nominal(100usize, 24usize, &event.cause);
+        self.count_bool(101usize, 2usize, event.search_complete);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2671,15 +2763,15 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(98usize, 46usize, 1usize);
-        self.count_nominal(99usize, 23usize, &event.cause);
+        self.count(102usize, 48usize, 1usize);
+        self.count_nominal(103usize, 25usize, &event.cause);
         self.time_nominal(
-            100usize,
+            104usize,
             0usize,
             &event.cause,
             meta.timestamp.saturating_duration_since(context.start_time),
         );
-        self.measure(101usize, 17usize, event.congestion_window);
+        self.measure(105usize, 17usize, event.congestion_window);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2693,7 +2785,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(102usize, 47usize, 1usize);
+        self.count(106usize, 49usize, 1usize);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2707,10 +2799,10 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(103usize, 48usize, 1usize);
-        self.measure(104usize, 18usize, event.bytes_per_second);
-        self.measure(105usize, 19usize, event.burst_size);
-        self.measure(106usize, 20usize, event.pacing_gain);
+        self.count(107usize, 50usize, 1usize);
+        self.measure(108usize, 18usize, event.bytes_per_second);
+        self.measure(109usize, 19usize, event.burst_size);
+        self.measure(110usize, 20usize, event.pacing_gain);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2724,8 +2816,8 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(107usize, 49usize, 1usize);
-        self.count_nominal(108usize, 24usize, &event.state);
+        self.count(111usize, 51usize, 1usize);
+        self.count_nominal(112usize, 26usize, &event.state);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2739,14 +2831,14 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(109usize, 50usize, 1usize);
+        self.count(113usize, 52usize, 1usize);
         {
             fn check(evt: &api::DcStateChanged) -> bool {
                 matches!(evt.state, DcState::VersionNegotiated { .. })
             }
             if check(event) {
                 self.time(
-                    110usize,
+                    114usize,
                     9usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
@@ -2758,7 +2850,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
             }
             if check(event) {
                 self.time(
-                    111usize,
+                    115usize,
                     10usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
@@ -2770,7 +2862,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
             }
             if check(event) {
                 self.time(
-                    112usize,
+                    116usize,
                     11usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
@@ -2782,13 +2874,13 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
             }
             if check(event) {
                 self.time(
-                    113usize,
+                    117usize,
                     12usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
             }
         }
-        self.count_nominal(114usize, 25usize, &event.state);
+        self.count_nominal(118usize, 27usize, &event.state);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2802,7 +2894,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(115usize, 51usize, 1usize);
+        self.count(119usize, 53usize, 1usize);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2816,13 +2908,13 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(116usize, 52usize, 1usize);
+        self.count(120usize, 54usize, 1usize);
         self.time(
-            117usize,
+            121usize,
             13usize,
             meta.timestamp.saturating_duration_since(context.start_time),
         );
-        self.count_nominal(118usize, 26usize, &event.error);
+        self.count_nominal(122usize, 28usize, &event.error);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2835,7 +2927,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(119usize, 53usize, 1usize);
+        self.count(123usize, 55usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -2847,7 +2939,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(120usize, 54usize, 1usize);
+        self.count(124usize, 56usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -2859,7 +2951,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(121usize, 55usize, 1usize);
+        self.count(125usize, 57usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -2871,10 +2963,10 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(122usize, 56usize, 1usize);
-        self.measure(123usize, 21usize, event.len);
-        self.measure(124usize, 22usize, event.len);
-        self.measure(125usize, 23usize, event.gso_offset);
+        self.count(126usize, 58usize, 1usize);
+        self.measure(127usize, 21usize, event.len);
+        self.measure(128usize, 22usize, event.len);
+        self.measure(129usize, 23usize, event.gso_offset);
         let _ = event;
         let _ = meta;
     }
@@ -2886,9 +2978,9 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(126usize, 57usize, 1usize);
-        self.measure(127usize, 24usize, event.len);
-        self.measure(128usize, 25usize, event.len);
+        self.count(130usize, 59usize, 1usize);
+        self.measure(131usize, 24usize, event.len);
+        self.measure(132usize, 25usize, event.len);
         let _ = event;
         let _ = meta;
     }
@@ -2900,10 +2992,10 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(129usize, 58usize, 1usize);
-        self.measure(130usize, 26usize, event.len);
-        self.measure(131usize, 27usize, event.len);
-        self.count_nominal(132usize, 27usize, &event.reason);
+        self.count(133usize, 60usize, 1usize);
+        self.measure(134usize, 26usize, event.len);
+        self.measure(135usize, 27usize, event.len);
+        self.count_nominal(136usize, 29usize, &event.reason);
         let _ = event;
         let _ = meta;
     }
@@ -2915,8 +3007,8 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(133usize, 59usize, 1usize);
-        self.count_nominal(134usize, 28usize, &event.error);
+        self.count(137usize, 61usize, 1usize);
+        self.count_nominal(138usize, 30usize, &event.error);
         let _ = event;
         let _ = meta;
     }
@@ -2924,17 +3016,17 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     fn on_platform_tx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTx) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(135usize, 60usize, 1usize);
-        self.count(136usize, 61usize, event.count);
-        self.measure(137usize, 28usize, event.count);
-        self.count(138usize, 62usize, event.syscalls);
-        self.measure(139usize, 29usize, event.syscalls);
-        self.count(140usize, 63usize, event.blocked_syscalls);
-        self.measure(141usize, 30usize, event.blocked_syscalls);
-        self.count(142usize, 64usize, event.total_errors);
-        self.measure(143usize, 31usize, event.total_errors);
-        self.count(144usize, 65usize, event.dropped_errors);
-        self.measure(145usize, 32usize, event.dropped_errors);
+        self.count(139usize, 62usize, 1usize);
+        self.count(140usize, 63usize, event.count);
+        self.measure(141usize, 28usize, event.count);
+        self.count(142usize, 64usize, event.syscalls);
+        self.measure(143usize, 29usize, event.syscalls);
+        self.count(144usize, 65usize, event.blocked_syscalls);
+        self.measure(145usize, 30usize, event.blocked_syscalls);
+        self.count(146usize, 66usize, event.total_errors);
+        self.measure(147usize, 31usize, event.total_errors);
+        self.count(148usize, 67usize, event.dropped_errors);
+        self.measure(149usize, 32usize, event.dropped_errors);
         let _ = event;
         let _ = meta;
     }
@@ -2942,7 +3034,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     fn on_platform_tx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTxError) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(146usize, 66usize, 1usize);
+        self.count(150usize, 68usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -2950,17 +3042,17 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     fn on_platform_rx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRx) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(147usize, 67usize, 1usize);
-        self.count(148usize, 68usize, event.count);
-        self.measure(149usize, 33usize, event.count);
-        self.count(150usize, 69usize, event.syscalls);
-        self.measure(151usize, 34usize, event.syscalls);
-        self.count(152usize, 70usize, event.blocked_syscalls);
-        self.measure(153usize, 35usize, event.blocked_syscalls);
-        self.count(154usize, 71usize, event.total_errors);
-        self.measure(155usize, 36usize, event.total_errors);
-        self.count(156usize, 72usize, event.dropped_errors);
-        self.measure(157usize, 37usize, event.dropped_errors);
+        self.count(151usize, 69usize, 1usize);
+        self.count(152usize, 70usize, event.count);
+        self.measure(153usize, 33usize, event.count);
+        self.count(154usize, 71usize, event.syscalls);
+        self.measure(155usize, 34usize, event.syscalls);
+        self.count(156usize, 72usize, event.blocked_syscalls);
+        self.measure(157usize, 35usize, event.blocked_syscalls);
+        self.count(158usize, 73usize, event.total_errors);
+        self.measure(159usize, 36usize, event.total_errors);
+        self.count(160usize, 74usize, event.dropped_errors);
+        self.measure(161usize, 37usize, event.dropped_errors);
         let _ = event;
         let _ = meta;
     }
@@ -2968,7 +3060,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     fn on_platform_rx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRxError) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(158usize, 73usize, 1usize);
+        self.count(162usize, 75usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -2980,7 +3072,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(159usize, 74usize, 1usize);
+        self.count(163usize, 76usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -2992,7 +3084,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(160usize, 75usize, 1usize);
+        self.count(164usize, 77usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -3004,8 +3096,8 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(161usize, 76usize, 1usize);
-        self.time(162usize, 14usize, event.processing_duration);
+        self.count(165usize, 78usize, 1usize);
+        self.time(166usize, 14usize, event.processing_duration);
         let _ = event;
         let _ = meta;
     }
@@ -3017,7 +3109,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(163usize, 77usize, 1usize);
+        self.count(167usize, 79usize, 1usize);
         let _ = event;
         let _ = meta;
     }
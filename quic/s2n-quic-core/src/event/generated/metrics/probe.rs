@@ -54,49 +54,51 @@ mod counter {
                 70usize => Self(connection_id_updated),
                 71usize => Self(ecn_state_changed),
                 73usize => Self(connection_migration_denied),
-                75usize => Self(handshake_status_updated),
-                80usize => Self(tls_exporter_ready),
-                81usize => Self(path_challenge_updated),
-                83usize => Self(tls_client_hello),
-                85usize => Self(tls_server_hello),
-                87usize => Self(rx_stream_progress),
-                88usize => Self(rx_stream_progress__bytes__total),
-                90usize => Self(tx_stream_progress),
-                91usize => Self(tx_stream_progress__bytes__total),
-                93usize => Self(keep_alive_timer_expired),
-                94usize => Self(mtu_updated),
-                98usize => Self(slow_start_exited),
-                102usize => Self(delivery_rate_sampled),
-                103usize => Self(pacing_rate_updated),
-                107usize => Self(bbr_state_changed),
-                109usize => Self(dc_state_changed),
-                115usize => Self(dc_path_created),
-                116usize => Self(connection_closed),
-                119usize => Self(version_information),
-                120usize => Self(endpoint_packet_sent),
-                121usize => Self(endpoint_packet_received),
-                122usize => Self(endpoint_datagram_sent),
-                126usize => Self(endpoint_datagram_received),
-                129usize => Self(endpoint_datagram_dropped),
-                133usize => Self(endpoint_connection_attempt_failed),
-                135usize => Self(platform_tx),
-                136usize => Self(platform_tx__packets__total),
-                138usize => Self(platform_tx__syscalls__total),
-                140usize => Self(platform_tx__syscalls__blocked__total),
-                142usize => Self(platform_tx__errors__total),
-                144usize => Self(platform_tx__errors__dropped__total),
-                146usize => Self(platform_tx_error),
-                147usize => Self(platform_rx),
-                148usize => Self(platform_rx__packets__total),
-                150usize => Self(platform_rx__syscalls__total),
-                152usize => Self(platform_rx__syscalls__blocked__total),
-                154usize => Self(platform_rx__errors__total),
-                156usize => Self(platform_rx__errors__dropped__total),
-                158usize => Self(platform_rx_error),
-                159usize => Self(platform_feature_configured),
-                160usize => Self(platform_event_loop_wakeup),
-                161usize => Self(platform_event_loop_sleep),
-                163usize => Self(platform_event_loop_started),
+                75usize => Self(preferred_address_migration_updated),
+                77usize => Self(active_migration_updated),
+                79usize => Self(handshake_status_updated),
+                84usize => Self(tls_exporter_ready),
+                85usize => Self(path_challenge_updated),
+                87usize => Self(tls_client_hello),
+                89usize => Self(tls_server_hello),
+                91usize => Self(rx_stream_progress),
+                92usize => Self(rx_stream_progress__bytes__total),
+                94usize => Self(tx_stream_progress),
+                95usize => Self(tx_stream_progress__bytes__total),
+                97usize => Self(keep_alive_timer_expired),
+                98usize => Self(mtu_updated),
+                102usize => Self(slow_start_exited),
+                106usize => Self(delivery_rate_sampled),
+                107usize => Self(pacing_rate_updated),
+                111usize => Self(bbr_state_changed),
+                113usize => Self(dc_state_changed),
+                119usize => Self(dc_path_created),
+                120usize => Self(connection_closed),
+                123usize => Self(version_information),
+                124usize => Self(endpoint_packet_sent),
+                125usize => Self(endpoint_packet_received),
+                126usize => Self(endpoint_datagram_sent),
+                130usize => Self(endpoint_datagram_received),
+                133usize => Self(endpoint_datagram_dropped),
+                137usize => Self(endpoint_connection_attempt_failed),
+                139usize => Self(platform_tx),
+                140usize => Self(platform_tx__packets__total),
+                142usize => Self(platform_tx__syscalls__total),
+                144usize => Self(platform_tx__syscalls__blocked__total),
+                146usize => Self(platform_tx__errors__total),
+                148usize => Self(platform_tx__errors__dropped__total),
+                150usize => Self(platform_tx_error),
+                151usize => Self(platform_rx),
+                152usize => Self(platform_rx__packets__total),
+                154usize => Self(platform_rx__syscalls__total),
+                156usize => Self(platform_rx__syscalls__blocked__total),
+                158usize => Self(platform_rx__errors__total),
+                160usize => Self(platform_rx__errors__dropped__total),
+                162usize => Self(platform_rx_error),
+                163usize => Self(platform_feature_configured),
+                164usize => Self(platform_event_loop_wakeup),
+                165usize => Self(platform_event_loop_sleep),
+                167usize => Self(platform_event_loop_started),
                 _ => unreachable!("invalid info: {info:?}"),
             }
         }
@@ -178,6 +180,10 @@ mod counter {
             fn ecn_state_changed(value: u64);
             # [link_name = s2n_quic__event__counter__connection_migration_denied]
             fn connection_migration_denied(value: u64);
+            # [link_name = s2n_quic__event__counter__preferred_address_migration_updated]
+            fn preferred_address_migration_updated(value: u64);
+            # [link_name = s2n_quic__event__counter__active_migration_updated]
+            fn active_migration_updated(value: u64);
             # [link_name = s2n_quic__event__counter__handshake_status_updated]
             fn handshake_status_updated(value: u64);
             # [link_name = s2n_quic__event__counter__tls_exporter_ready]
@@ -275,7 +281,7 @@ mod counter {
                 match info.id {
                     24usize => Self(packet_lost__is_mtu_probe),
                     34usize => Self(recovery_metrics__congestion_limited),
-                    97usize => Self(mtu_updated__search_complete),
+                    101usize => Self(mtu_updated__search_complete),
                     _ => unreachable!("invalid info: {info:?}"),
                 }
             }
@@ -324,15 +330,17 @@ mod counter {
                     68usize => Self(datagram_dropped__reason),
                     72usize => Self(ecn_state_changed__state),
                     74usize => Self(connection_migration_denied__reason),
-                    79usize => Self(handshake_status_updated__status),
-                    82usize => Self(path_challenge_updated__status),
-                    96usize => Self(mtu_updated__cause),
-                    99usize => Self(slow_start_exited__cause),
-                    108usize => Self(bbr_state_changed__state),
-                    114usize => Self(dc_state_changed__state),
-                    118usize => Self(connection_closed__error),
-                    132usize => Self(endpoint_datagram_dropped__reason),
-                    134usize => Self(endpoint_connection_attempt_failed__error),
+                    76usize => Self(preferred_address_migration_updated__status),
+                    78usize => Self(active_migration_updated__status),
+                    83usize => Self(handshake_status_updated__status),
+                    86usize => Self(path_challenge_updated__status),
+                    100usize => Self(mtu_updated__cause),
+                    103usize => Self(slow_start_exited__cause),
+                    112usize => Self(bbr_state_changed__state),
+                    118usize => Self(dc_state_changed__state),
+                    122usize => Self(connection_closed__error),
+                    136usize => Self(endpoint_datagram_dropped__reason),
+                    138usize => Self(endpoint_connection_attempt_failed__error),
                     _ => unreachable!("invalid info: {info:?}"),
                 }
             }
@@ -397,6 +405,18 @@ mod counter {
                     variant: u64,
                     variant_name: &info::Str,
                 );
+                # [link_name = s2n_quic__event__counter__nominal__preferred_address_migration_updated__status]
+                fn preferred_address_migration_updated__status(
+                    value: u64,
+                    variant: u64,
+                    variant_name: &info::Str,
+                );
+                # [link_name = s2n_quic__event__counter__nominal__active_migration_updated__status]
+                fn active_migration_updated__status(
+                    value: u64,
+                    variant: u64,
+                    variant_name: &info::Str,
+                );
                 # [link_name = s2n_quic__event__counter__nominal__handshake_status_updated__status]
                 fn handshake_status_updated__status(
                     value: u64,
@@ -457,30 +477,30 @@ mod measure {
                 61usize => Self(datagram_sent__gso_offset),
                 64usize => Self(datagram_received__bytes),
                 67usize => Self(datagram_dropped__bytes),
-                89usize => Self(rx_stream_progress__bytes),
-                92usize => Self(tx_stream_progress__bytes),
-                95usize => Self(mtu_updated__mtu),
-                101usize => Self(slow_start_exited__congestion_window),
-                104usize => Self(pacing_rate_updated__bytes_per_second),
-                105usize => Self(pacing_rate_updated__burst_size),
-                106usize => Self(pacing_rate_updated__pacing_gain),
-                123usize => Self(endpoint_datagram_sent__bytes),
-                124usize => Self(endpoint_datagram_sent__bytes__total),
-                125usize => Self(endpoint_datagram_sent__gso_offset),
-                127usize => Self(endpoint_datagram_received__bytes),
-                128usize => Self(endpoint_datagram_received__bytes__total),
-                130usize => Self(endpoint_datagram_dropped__bytes),
-                131usize => Self(endpoint_datagram_dropped__bytes__total),
-                137usize => Self(platform_tx__packets),
-                139usize => Self(platform_tx__syscalls),
-                141usize => Self(platform_tx__syscalls__blocked),
-                143usize => Self(platform_tx__errors),
-                145usize => Self(platform_tx__errors__dropped),
-                149usize => Self(platform_rx__packets),
-                151usize => Self(platform_rx__syscalls),
-                153usize => Self(platform_rx__syscalls__blocked),
-                155usize => Self(platform_rx__errors),
-                157usize => Self(platform_rx__errors__dropped),
+                93usize => Self(rx_stream_progress__bytes),
+                96usize => Self(tx_stream_progress__bytes),
+                99usize => Self(mtu_updated__mtu),
+                105usize => Self(slow_start_exited__congestion_window),
+                108usize => Self(pacing_rate_updated__bytes_per_second),
+                109usize => Self(pacing_rate_updated__burst_size),
+                110usize => Self(pacing_rate_updated__pacing_gain),
+                127usize => Self(endpoint_datagram_sent__bytes),
+                128usize => Self(endpoint_datagram_sent__bytes__total),
+                129usize => Self(endpoint_datagram_sent__gso_offset),
+                131usize => Self(endpoint_datagram_received__bytes),
+                132usize => Self(endpoint_datagram_received__bytes__total),
+                134usize => Self(endpoint_datagram_dropped__bytes),
+                135usize => Self(endpoint_datagram_dropped__bytes__total),
+                141usize => Self(platform_tx__packets),
+                143usize => Self(platform_tx__syscalls),
+                145usize => Self(platform_tx__syscalls__blocked),
+                147usize => Self(platform_tx__errors),
+                149usize => Self(platform_tx__errors__dropped),
+                153usize => Self(platform_rx__packets),
+                155usize => Self(platform_rx__syscalls),
+                157usize => Self(platform_rx__syscalls__blocked),
+                159usize => Self(platform_rx__errors),
+                161usize => Self(platform_rx__errors__dropped),
                 _ => unreachable!("invalid info: {info:?}"),
             }
         }
@@ -599,17 +619,17 @@ mod timer {
                 49usize => Self(key_space_discarded__handshake__latency),
                 50usize => Self(key_space_discarded__one_rtt__latency),
                 57usize => Self(transport_parameters_received__latency),
-                76usize => Self(handshake_status_updated__complete__latency),
-                77usize => Self(handshake_status_updated__confirmed__latency),
-                78usize => Self(handshake_status_updated__handshake_done_acked__latency),
-                84usize => Self(tls_client_hello__latency),
-                86usize => Self(tls_server_hello__latency),
-                110usize => Self(dc_state_changed__version_negotiated__latency),
-                111usize => Self(dc_state_changed__no_version_negotiated__latency),
-                112usize => Self(dc_state_changed__path_secrets__latency),
-                113usize => Self(dc_state_changed__complete__latency),
-                117usize => Self(connection_closed__latency),
-                162usize => Self(platform_event_loop_sleep__processing_duration),
+                80usize => Self(handshake_status_updated__complete__latency),
+                81usize => Self(handshake_status_updated__confirmed__latency),
+                82usize => Self(handshake_status_updated__handshake_done_acked__latency),
+                88usize => Self(tls_client_hello__latency),
+                90usize => Self(tls_server_hello__latency),
+                114usize => Self(dc_state_changed__version_negotiated__latency),
+                115usize => Self(dc_state_changed__no_version_negotiated__latency),
+                116usize => Self(dc_state_changed__path_secrets__latency),
+                117usize => Self(dc_state_changed__complete__latency),
+                121usize => Self(connection_closed__latency),
+                166usize => Self(platform_event_loop_sleep__processing_duration),
                 _ => unreachable!("invalid info: {info:?}"),
             }
         }
@@ -661,7 +681,7 @@ mod timer {
         impl Recorder {
             pub(crate) fn new(info: &'static Info, _variant: &'static info::Variant) -> Self {
                 match info.id {
-                    100usize => Self(slow_start_exited__latency),
+                    104usize => Self(slow_start_exited__latency),
                     _ => unreachable!("invalid info: {info:?}"),
                 }
             }
@@ -153,7 +153,16 @@ impl<A: AckRanges> EncoderValue for Ack<A> {
     #[inline]
     fn encode<E: Encoder>(&self, buffer: &mut E) {
         buffer.encode(&self.tag());
+        self.encode_body(buffer);
+    }
+}
 
+impl<A: AckRanges> Ack<A> {
+    /// Encodes everything in the frame following the frame type
+    ///
+    /// This is shared with the frames that carry the same fields as an ACK frame.
+    #[inline]
+    pub(crate) fn encode_body<E: Encoder>(&self, buffer: &mut E) {
         let mut iter = self.ack_ranges.ack_ranges();
 
         let first_ack_range = iter.next().expect("at least one ack range is required");
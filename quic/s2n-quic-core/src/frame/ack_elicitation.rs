@@ -73,6 +73,7 @@ impl AckElicitable for crate::frame::ConnectionClose<'_> {
         AckElicitation::NonEliciting
     }
 }
+impl AckElicitable for crate::frame::AckFrequency {}
 impl<Data> AckElicitable for crate::frame::Crypto<Data> {}
 //= https://www.rfc-editor.org/rfc/rfc9221#section-5.2
 //# Although DATAGRAM frames are not retransmitted upon loss detection,
@@ -84,11 +85,19 @@ impl AckElicitable for crate::frame::DataBlocked {}
 //# an ACK frame to be sent.
 impl AckElicitable for crate::frame::DcStatelessResetTokens<'_> {}
 impl AckElicitable for crate::frame::HandshakeDone {}
+impl AckElicitable for crate::frame::ImmediateAck {}
 impl AckElicitable for crate::frame::MaxData {}
 impl AckElicitable for crate::frame::MaxStreamData {}
 impl AckElicitable for crate::frame::MaxStreams {}
 impl AckElicitable for crate::frame::NewConnectionId<'_> {}
 impl AckElicitable for crate::frame::NewToken<'_> {}
+impl AckElicitable for crate::frame::PathAbandon {}
+impl<AckRanges> AckElicitable for crate::frame::PathAck<AckRanges> {
+    #[inline]
+    fn ack_elicitation(&self) -> AckElicitation {
+        AckElicitation::NonEliciting
+    }
+}
 impl AckElicitable for crate::frame::Padding {
     #[inline]
     fn ack_elicitation(&self) -> AckElicitation {
@@ -99,6 +108,7 @@ impl AckElicitable for crate::frame::PathChallenge<'_> {}
 impl AckElicitable for crate::frame::PathResponse<'_> {}
 impl AckElicitable for crate::frame::Ping {}
 impl AckElicitable for crate::frame::ResetStream {}
+impl AckElicitable for crate::frame::ResetStreamAt {}
 impl AckElicitable for crate::frame::RetireConnectionId {}
 impl AckElicitable for crate::frame::StopSending {}
 impl<Data> AckElicitable for crate::frame::Stream<Data> {}
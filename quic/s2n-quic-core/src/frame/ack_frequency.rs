@@ -0,0 +1,84 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{frame::ExtensionTag, varint::VarInt};
+use core::time::Duration;
+use s2n_codec::{decoder_parameterized_value, Encoder, EncoderValue};
+
+// The ACK_FREQUENCY frame (type=0xaf) is defined in
+// https://datatracker.ietf.org/doc/draft-ietf-quic-ack-frequency/ and lets a sender
+// control how often its peer acknowledges ack-eliciting packets.
+
+const TAG: VarInt = VarInt::from_u8(0xaf);
+
+macro_rules! ack_frequency_tag {
+    () => {
+        0xafu64
+    };
+}
+
+// ACK_FREQUENCY Frame {
+//   Type (i) = 0xaf,
+//   Sequence Number (i),
+//   Ack-Eliciting Threshold (i),
+//   Requested Max Ack Delay (i),
+//   Reordering Threshold (i),
+// }
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AckFrequency {
+    /// The sequence number assigned to the frame by the sender
+    pub sequence_number: VarInt,
+
+    /// The maximum number of ack-eliciting packets received before sending an acknowledgment
+    pub ack_eliciting_threshold: VarInt,
+
+    /// The value the peer should use for its max_ack_delay, in microseconds
+    pub request_max_ack_delay: VarInt,
+
+    /// The maximum packet reordering before eliciting an immediate ACK
+    pub reordering_threshold: VarInt,
+}
+
+impl AckFrequency {
+    pub const fn tag(&self) -> ExtensionTag {
+        TAG
+    }
+
+    /// Returns the `request_max_ack_delay` field as a `Duration`
+    #[inline]
+    pub fn request_max_ack_delay(&self) -> Duration {
+        Duration::from_micros(self.request_max_ack_delay.as_u64())
+    }
+}
+
+decoder_parameterized_value!(
+    impl<'a> AckFrequency {
+        fn decode(_tag: ExtensionTag, buffer: Buffer) -> Result<Self> {
+            let (sequence_number, buffer) = buffer.decode()?;
+            let (ack_eliciting_threshold, buffer) = buffer.decode()?;
+            let (request_max_ack_delay, buffer) = buffer.decode()?;
+            let (reordering_threshold, buffer) = buffer.decode()?;
+
+            let frame = AckFrequency {
+                sequence_number,
+                ack_eliciting_threshold,
+                request_max_ack_delay,
+                reordering_threshold,
+            };
+
+            Ok((frame, buffer))
+        }
+    }
+);
+
+impl EncoderValue for AckFrequency {
+    #[inline]
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&TAG);
+        buffer.encode(&self.sequence_number);
+        buffer.encode(&self.ack_eliciting_threshold);
+        buffer.encode(&self.request_max_ack_delay);
+        buffer.encode(&self.reordering_threshold);
+    }
+}
@@ -19,6 +19,7 @@ impl<AckRanges> CongestionControlled for crate::frame::Ack<AckRanges> {
         false
     }
 }
+impl CongestionControlled for crate::frame::AckFrequency {}
 impl CongestionControlled for crate::frame::ConnectionClose<'_> {}
 impl<Data> CongestionControlled for crate::frame::Crypto<Data> {}
 //= https://www.rfc-editor.org/rfc/rfc9221#section-5.4
@@ -30,6 +31,7 @@ impl CongestionControlled for crate::frame::DataBlocked {}
 //# an ACK frame to be sent.
 impl CongestionControlled for crate::frame::DcStatelessResetTokens<'_> {}
 impl CongestionControlled for crate::frame::HandshakeDone {}
+impl CongestionControlled for crate::frame::ImmediateAck {}
 impl CongestionControlled for crate::frame::MaxData {}
 impl CongestionControlled for crate::frame::MaxStreamData {}
 impl CongestionControlled for crate::frame::MaxStreams {}
@@ -52,10 +54,18 @@ impl CongestionControlled for crate::frame::Padding {
         false
     }
 }
+impl CongestionControlled for crate::frame::PathAbandon {}
+impl<AckRanges> CongestionControlled for crate::frame::PathAck<AckRanges> {
+    #[inline]
+    fn is_congestion_controlled(&self) -> bool {
+        false
+    }
+}
 impl CongestionControlled for crate::frame::PathChallenge<'_> {}
 impl CongestionControlled for crate::frame::PathResponse<'_> {}
 impl CongestionControlled for crate::frame::Ping {}
 impl CongestionControlled for crate::frame::ResetStream {}
+impl CongestionControlled for crate::frame::ResetStreamAt {}
 impl CongestionControlled for crate::frame::RetireConnectionId {}
 impl CongestionControlled for crate::frame::StopSending {}
 impl CongestionControlled for crate::frame::StreamsBlocked {}
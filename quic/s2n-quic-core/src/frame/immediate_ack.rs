@@ -0,0 +1,27 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+// The IMMEDIATE_ACK frame (type=0x1f) is defined in
+// https://datatracker.ietf.org/doc/draft-ietf-quic-ack-frequency/ and asks the peer
+// to send an ACK frame as soon as possible.
+
+macro_rules! immediate_ack_tag {
+    () => {
+        0x1fu8
+    };
+}
+
+// IMMEDIATE_ACK Frame {
+//   Type (i) = 0x1f,
+// }
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ImmediateAck;
+
+impl ImmediateAck {
+    pub const fn tag(self) -> u8 {
+        immediate_ack_tag!()
+    }
+}
+
+simple_frame_codec!(ImmediateAck {}, immediate_ack_tag!());
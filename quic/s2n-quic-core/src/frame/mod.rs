@@ -101,7 +101,10 @@ macro_rules! frames {
         )*
 
         impl<'a, $ack, $data: DecoderValueMut<'a>> DecoderValueMut<'a> for Frame<'a, $ack, $data>
-        where ack::Ack<$ack>: DecoderParameterizedValueMut<'a, Parameter = Tag> {
+        where
+            ack::Ack<$ack>: DecoderParameterizedValueMut<'a, Parameter = Tag>,
+            path_ack::PathAck<$ack>: DecoderParameterizedValueMut<'a, Parameter = ExtensionTag>,
+        {
             #[inline]
             fn decode_mut(buffer: DecoderBufferMut<'a>) -> DecoderBufferMutResult<'a, Self> {
                 BasicFrameDecoder.decode_frame(buffer)
@@ -122,7 +125,10 @@ macro_rules! frames {
         struct BasicFrameDecoder;
 
         impl<'a, $ack, $data: DecoderValueMut<'a>> FrameDecoder<'a, $ack, $data> for BasicFrameDecoder
-        where ack::Ack<$ack>: DecoderParameterizedValueMut<'a, Parameter = Tag> {
+        where
+            ack::Ack<$ack>: DecoderParameterizedValueMut<'a, Parameter = Tag>,
+            path_ack::PathAck<$ack>: DecoderParameterizedValueMut<'a, Parameter = ExtensionTag>,
+        {
             type Output = Frame<'a, $ack, $data>;
 
             $(
@@ -134,7 +140,10 @@ macro_rules! frames {
         }
 
         pub trait FrameDecoder<'a, $ack, $data: DecoderValueMut<'a>>
-        where ack::Ack<$ack>: DecoderParameterizedValueMut<'a, Parameter = Tag> {
+        where
+            ack::Ack<$ack>: DecoderParameterizedValueMut<'a, Parameter = Tag>,
+            path_ack::PathAck<$ack>: DecoderParameterizedValueMut<'a, Parameter = ExtensionTag>,
+        {
             type Output;
 
             $(
@@ -263,6 +272,11 @@ frames! {
     [connection_close_tag] => connection_close, handle_connection_close_frame, ConnectionClose['a];
     [handshake_done_tag] => handshake_done, handle_handshake_done_frame, HandshakeDone;
     [datagram_tag] => datagram, handle_datagram_frame, Datagram[Data];
+    [immediate_ack_tag] => immediate_ack, handle_immediate_ack_frame, ImmediateAck;
+    [reset_stream_at_tag] => reset_stream_at, handle_reset_stream_at_frame, ResetStreamAt;
+    extension[ack_frequency_tag] => ack_frequency, handle_ack_frequency_frame, AckFrequency;
+    extension[path_ack_tag] => path_ack, handle_path_ack_frame, PathAck[AckRanges];
+    extension[path_abandon_tag] => path_abandon, handle_path_abandon_frame, PathAbandon;
     extension[dc_stateless_reset_tokens_tag] => dc_stateless_reset_tokens, handle_dc_stateless_reset_tokens_frame, DcStatelessResetTokens['a];
 }
 
@@ -0,0 +1,63 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{frame::ExtensionTag, varint::VarInt};
+use s2n_codec::{decoder_parameterized_value, Encoder, EncoderValue};
+
+// The PATH_ABANDON frame (type=0x15228c05) is defined in
+// https://datatracker.ietf.org/doc/draft-ietf-quic-multipath/ and informs the peer that
+// the sender will no longer send or receive packets on a path.
+
+const TAG: VarInt = VarInt::from_u32(0x15228c05);
+
+macro_rules! path_abandon_tag {
+    () => {
+        0x15228c05u64
+    };
+}
+
+// PATH_ABANDON Frame {
+//   Type (i) = TBD-03 (experiments use 0x15228c05),
+//   Path Identifier (i),
+//   Error Code (i),
+// }
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PathAbandon {
+    /// The identifier of the path which is abandoned
+    pub path_id: VarInt,
+
+    /// A variable-length integer indicating the reason the path is abandoned
+    pub error_code: VarInt,
+}
+
+impl PathAbandon {
+    pub const fn tag(&self) -> ExtensionTag {
+        TAG
+    }
+}
+
+decoder_parameterized_value!(
+    impl<'a> PathAbandon {
+        fn decode(_tag: ExtensionTag, buffer: Buffer) -> Result<Self> {
+            let (path_id, buffer) = buffer.decode()?;
+            let (error_code, buffer) = buffer.decode()?;
+
+            let frame = PathAbandon {
+                path_id,
+                error_code,
+            };
+
+            Ok((frame, buffer))
+        }
+    }
+);
+
+impl EncoderValue for PathAbandon {
+    #[inline]
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&TAG);
+        buffer.encode(&self.path_id);
+        buffer.encode(&self.error_code);
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    frame::{
+        ack::{Ack, AckRanges, AckRangesDecoder},
+        ExtensionTag,
+    },
+    varint::VarInt,
+};
+use s2n_codec::{decoder_parameterized_value, Encoder, EncoderValue};
+
+// The PATH_ACK frame (types=0x15228c00..0x15228c01) is defined in
+// https://datatracker.ietf.org/doc/draft-ietf-quic-multipath/ and acknowledges packets
+// in the packet number space of a single path. Packets sent on the path with the path
+// identifier 0 are acknowledged with regular ACK frames.
+
+const PATH_ACK_TAG: VarInt = VarInt::from_u32(0x15228c00);
+const PATH_ACK_W_ECN_TAG: VarInt = VarInt::from_u32(0x15228c01);
+
+macro_rules! path_ack_tag {
+    () => {
+        0x15228c00u64..=0x15228c01u64
+    };
+}
+
+// PATH_ACK Frame {
+//   Type (i) = TBD-00..TBD-01 (experiments use 0x15228c00..0x15228c01),
+//   Path Identifier (i),
+//   Largest Acknowledged (i),
+//   ACK Delay (i),
+//   ACK Range Count (i),
+//   First ACK Range (i),
+//   ACK Range (..) ...,
+//   [ECN Counts (..)],
+// }
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathAck<AckRanges> {
+    /// The identifier of the path whose packet number space is acknowledged
+    pub path_id: VarInt,
+
+    /// The acknowledgement information for the path, as it would be carried by
+    /// an ACK frame
+    pub ack: Ack<AckRanges>,
+}
+
+impl<AckRanges> PathAck<AckRanges> {
+    #[inline]
+    pub fn tag(&self) -> ExtensionTag {
+        if self.ack.ecn_counts.is_some() {
+            PATH_ACK_W_ECN_TAG
+        } else {
+            PATH_ACK_TAG
+        }
+    }
+}
+
+impl<A: AckRanges> PathAck<A> {
+    #[inline]
+    pub fn largest_acknowledged(&self) -> VarInt {
+        self.ack.largest_acknowledged()
+    }
+}
+
+decoder_parameterized_value!(
+    impl<'a> PathAck<AckRangesDecoder<'a>> {
+        fn decode(tag: ExtensionTag, buffer: Buffer) -> Result<Self> {
+            let (path_id, buffer) = buffer.decode()?;
+            let (largest_acknowledged, buffer) = buffer.decode()?;
+            let (ack_delay, buffer) = buffer.decode()?;
+            let (ack_ranges, buffer) = buffer.decode_parameterized(largest_acknowledged)?;
+
+            let (ecn_counts, buffer) = if tag == PATH_ACK_W_ECN_TAG {
+                let (ecn_counts, buffer) = buffer.decode()?;
+                (Some(ecn_counts), buffer)
+            } else {
+                (None, buffer)
+            };
+
+            let frame = PathAck {
+                path_id,
+                ack: Ack {
+                    ack_delay,
+                    ack_ranges,
+                    ecn_counts,
+                },
+            };
+
+            Ok((frame, buffer))
+        }
+    }
+);
+
+impl<A: AckRanges> EncoderValue for PathAck<A> {
+    #[inline]
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&self.tag());
+        buffer.encode(&self.path_id);
+        self.ack.encode_body(buffer);
+    }
+}
@@ -65,12 +65,14 @@ pub trait Probing {
 //# PATH_CHALLENGE, PATH_RESPONSE, NEW_CONNECTION_ID, and PADDING frames
 //# are "probing frames", and all other frames are "non-probing frames".
 impl<AckRanges> Probing for crate::frame::Ack<AckRanges> {}
+impl Probing for crate::frame::AckFrequency {}
 impl Probing for crate::frame::ConnectionClose<'_> {}
 impl<Data> Probing for crate::frame::Crypto<Data> {}
 impl<Data> Probing for crate::frame::Datagram<Data> {}
 impl Probing for crate::frame::DataBlocked {}
 impl Probing for crate::frame::DcStatelessResetTokens<'_> {}
 impl Probing for crate::frame::HandshakeDone {}
+impl Probing for crate::frame::ImmediateAck {}
 impl Probing for crate::frame::MaxData {}
 impl Probing for crate::frame::MaxStreamData {}
 impl Probing for crate::frame::MaxStreams {}
@@ -87,6 +89,8 @@ impl Probing for crate::frame::Padding {
         Probe::Probing
     }
 }
+impl Probing for crate::frame::PathAbandon {}
+impl<AckRanges> Probing for crate::frame::PathAck<AckRanges> {}
 impl Probing for crate::frame::PathChallenge<'_> {
     #[inline]
     fn path_validation(&self) -> Probe {
@@ -101,6 +105,7 @@ impl Probing for crate::frame::PathResponse<'_> {
 }
 impl Probing for crate::frame::Ping {}
 impl Probing for crate::frame::ResetStream {}
+impl Probing for crate::frame::ResetStreamAt {}
 impl Probing for crate::frame::RetireConnectionId {}
 impl Probing for crate::frame::StopSending {}
 impl<Data> Probing for crate::frame::Stream<Data> {}
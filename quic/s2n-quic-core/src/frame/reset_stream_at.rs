@@ -0,0 +1,88 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{frame::Tag, varint::VarInt};
+use s2n_codec::{decoder_invariant, decoder_parameterized_value, Encoder, EncoderValue};
+
+// The RESET_STREAM_AT frame (type=0x24) is defined in
+// https://datatracker.ietf.org/doc/draft-ietf-quic-reliable-stream-reset/ and resets
+// the sending part of a stream while guaranteeing delivery of the data up to a
+// given offset.
+
+macro_rules! reset_stream_at_tag {
+    () => {
+        0x24u8
+    };
+}
+
+// RESET_STREAM_AT Frame {
+//   Type (i) = 0x24,
+//   Stream ID (i),
+//   Application Protocol Error Code (i),
+//   Final Size (i),
+//   Reliable Size (i),
+// }
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ResetStreamAt {
+    /// A variable-length integer encoding of the Stream ID of the
+    /// stream being terminated.
+    pub stream_id: VarInt,
+
+    /// A variable-length integer containing the application protocol
+    /// error code which indicates why the stream is being closed.
+    pub application_error_code: VarInt,
+
+    /// A variable-length integer indicating the final size of
+    /// the stream by the RESET_STREAM_AT sender, in unit of bytes.
+    pub final_size: VarInt,
+
+    /// A variable-length integer indicating the amount of data that
+    /// needs to be delivered to the application before the reset is
+    /// surfaced, in unit of bytes.
+    pub reliable_size: VarInt,
+}
+
+impl ResetStreamAt {
+    pub const fn tag(&self) -> u8 {
+        reset_stream_at_tag!()
+    }
+}
+
+decoder_parameterized_value!(
+    impl<'a> ResetStreamAt {
+        fn decode(_tag: Tag, buffer: Buffer) -> Result<Self> {
+            let (stream_id, buffer) = buffer.decode()?;
+            let (application_error_code, buffer) = buffer.decode()?;
+            let (final_size, buffer) = buffer.decode()?;
+            let (reliable_size, buffer) = buffer.decode()?;
+
+            // A Reliable Size larger than the Final Size is treated as a
+            // connection error of type FRAME_ENCODING_ERROR.
+            decoder_invariant!(
+                reliable_size <= final_size,
+                "reliable size cannot exceed final size"
+            );
+
+            let frame = ResetStreamAt {
+                stream_id,
+                application_error_code,
+                final_size,
+                reliable_size,
+            };
+
+            Ok((frame, buffer))
+        }
+    }
+);
+
+impl EncoderValue for ResetStreamAt {
+    #[inline]
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&self.tag());
+        buffer.encode(&self.stream_id);
+        buffer.encode(&self.application_error_code);
+        buffer.encode(&self.final_size);
+        buffer.encode(&self.reliable_size);
+    }
+}
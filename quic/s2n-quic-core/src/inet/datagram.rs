@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    connection, inet::ExplicitCongestionNotification, path::LocalAddress, time::Timestamp,
+    connection,
+    inet::{icmp, ExplicitCongestionNotification},
+    path::LocalAddress,
+    time::Timestamp,
 };
 
 /// Header information for a datagram sent/received over the network
@@ -36,4 +39,9 @@ pub struct AncillaryData {
     pub local_interface: Option<u32>,
     /// Set when the packet buffer is an aggregate of multiple received packets
     pub segment_size: u16,
+    /// Set when the message was read from the socket error queue
+    ///
+    /// The payload of these messages is the datagram that was quoted by the ICMP message
+    /// rather than a datagram received from the peer.
+    pub icmp_error: Option<icmp::Error>,
 }
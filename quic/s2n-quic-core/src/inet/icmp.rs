@@ -0,0 +1,246 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    connection,
+    inet::{ipv4, ipv6, udp, SocketAddress},
+    time::{Duration, Timestamp},
+};
+use core::mem::size_of;
+
+/// The maximum number of bytes of the quoted datagram that are retained for validation
+///
+/// This is enough to contain the first byte, version and destination connection ID of any
+/// QUIC packet.
+pub const MAX_QUOTED_LEN: usize = 32;
+
+/// The maximum number of ICMP messages that are processed in each [`RateLimiter::WINDOW`]
+pub const MAX_MESSAGES_PER_WINDOW: u16 = 128;
+
+/// An ICMP error reported for a datagram sent by the endpoint
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(kani, derive(kani::Arbitrary))]
+pub enum Error {
+    /// The datagram exceeded the MTU of a link on the path
+    ///
+    /// `mtu` is the MTU of the link reported by the ICMP message, which includes the IP header.
+    PacketTooBig { mtu: u16 },
+    /// The ICMP message reported an error that isn't used by the endpoint
+    Other,
+}
+
+/// An ICMP Packet Too Big (ICMPv6) or Fragmentation Needed (ICMPv4) message
+///
+/// The message quotes the beginning of a datagram that was sent by the endpoint.
+#[derive(Clone, Copy)]
+pub struct PacketTooBig {
+    remote_address: SocketAddress,
+    mtu: u16,
+    quoted_len: u8,
+    quoted: [u8; MAX_QUOTED_LEN],
+}
+
+impl PacketTooBig {
+    /// Creates a new `PacketTooBig` message
+    ///
+    /// * `remote_address` is the destination of the quoted datagram
+    /// * `mtu` is the MTU reported by the ICMP message, including the IP header
+    /// * `quoted_payload` is the UDP payload of the quoted datagram, which is truncated to
+    ///   [`MAX_QUOTED_LEN`] bytes
+    #[inline]
+    pub fn new(remote_address: SocketAddress, mtu: u16, quoted_payload: &[u8]) -> Self {
+        let quoted_len = quoted_payload.len().min(MAX_QUOTED_LEN);
+        let mut quoted = [0; MAX_QUOTED_LEN];
+        quoted[..quoted_len].copy_from_slice(&quoted_payload[..quoted_len]);
+
+        Self {
+            remote_address,
+            mtu,
+            quoted_len: quoted_len as u8,
+            quoted,
+        }
+    }
+
+    /// The address the quoted datagram was sent to
+    #[inline]
+    pub fn remote_address(&self) -> &SocketAddress {
+        &self.remote_address
+    }
+
+    /// The MTU reported by the ICMP message, including the IP header
+    #[inline]
+    pub fn mtu(&self) -> u16 {
+        self.mtu
+    }
+
+    /// The largest UDP payload that fits in the reported MTU
+    ///
+    /// This assumes the minimum length IP headers (without IPv4 options or IPv6 extensions).
+    #[inline]
+    pub fn max_udp_payload(&self) -> u16 {
+        let ip_header_len = match self.remote_address {
+            SocketAddress::IpV4(_) => size_of::<ipv4::Header>(),
+            SocketAddress::IpV6(_) => size_of::<ipv6::Header>(),
+        };
+        let header_len = (ip_header_len + size_of::<udp::Header>()) as u16;
+        self.mtu.saturating_sub(header_len)
+    }
+
+    /// The beginning of the UDP payload of the quoted datagram
+    #[inline]
+    pub fn quoted_payload(&self) -> &[u8] {
+        &self.quoted[..self.quoted_len as usize]
+    }
+
+    /// Returns the destination connection IDs the quoted datagram could have been sent with
+    ///
+    /// Long header packets encode the length of the destination connection ID, so at most one
+    /// is returned. The length isn't encoded in short header packets, so a candidate is returned
+    /// for each possible length. Zero-length connection IDs aren't returned, since they can't
+    /// identify a connection.
+    #[inline]
+    pub fn destination_connection_ids(&self) -> impl Iterator<Item = connection::PeerId> + '_ {
+        let quoted = self.quoted_payload();
+
+        let (offset, lengths) = match quoted.first() {
+            // Long header packets contain the version followed by the length prefixed
+            // destination connection ID
+            Some(tag) if tag & 0x80 != 0 => {
+                let len = quoted.get(5).map_or(0, |len| *len as usize);
+                (6, len..=len)
+            }
+            // Short header packets contain the destination connection ID after the first byte
+            Some(_) => (1, 1..=connection::id::MAX_LEN),
+            None => (0, 1..=0),
+        };
+
+        lengths
+            .filter(|len| *len > 0)
+            .filter_map(move |len| quoted.get(offset..offset + len))
+            .filter_map(connection::PeerId::try_from_bytes)
+    }
+}
+
+/// Limits the number of ICMP messages processed by an endpoint
+///
+/// ICMP messages aren't authenticated, so this bounds the amount of work an off-path
+/// attacker can cause by sending forged messages.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RateLimiter {
+    window_start: Option<Timestamp>,
+    count: u16,
+}
+
+impl RateLimiter {
+    /// The duration over which [`MAX_MESSAGES_PER_WINDOW`] messages are allowed
+    pub const WINDOW: Duration = Duration::from_secs(1);
+
+    /// Returns `true` if a message received at `now` should be processed
+    #[inline]
+    pub fn on_message(&mut self, now: Timestamp) -> bool {
+        let window_elapsed = self.window_start.map_or(true, |start| {
+            now.saturating_duration_since(start) >= Self::WINDOW
+        });
+
+        if window_elapsed {
+            self.window_start = Some(now);
+            self.count = 0;
+        }
+
+        if self.count >= MAX_MESSAGES_PER_WINDOW {
+            return false;
+        }
+
+        self.count += 1;
+        true
+    }
+}
+
+impl core::fmt::Debug for PacketTooBig {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("PacketTooBig")
+            .field("remote_address", &self.remote_address)
+            .field("mtu", &self.mtu)
+            .field("quoted_payload", &self.quoted_payload())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        inet::{SocketAddressV4, SocketAddressV6},
+        time::{Clock, NoopClock},
+    };
+
+    #[test]
+    fn max_udp_payload_test() {
+        let addr = SocketAddressV4::new([127, 0, 0, 1], 443).into();
+        assert_eq!(1472, PacketTooBig::new(addr, 1500, &[]).max_udp_payload());
+        assert_eq!(0, PacketTooBig::new(addr, 10, &[]).max_udp_payload());
+
+        let addr = SocketAddressV6::new([0; 16], 443).into();
+        assert_eq!(1452, PacketTooBig::new(addr, 1500, &[]).max_udp_payload());
+    }
+
+    #[test]
+    fn quoted_payload_test() {
+        let addr = SocketAddress::default();
+        let payload = [1u8; 1200];
+
+        let ptb = PacketTooBig::new(addr, 1500, &payload[..4]);
+        assert_eq!(&payload[..4], ptb.quoted_payload());
+
+        let ptb = PacketTooBig::new(addr, 1500, &payload);
+        assert_eq!(&payload[..MAX_QUOTED_LEN], ptb.quoted_payload());
+    }
+
+    #[test]
+    fn destination_connection_ids_test() {
+        let addr = SocketAddress::default();
+        let ids = |quoted: &[u8]| -> Vec<_> {
+            PacketTooBig::new(addr, 1500, quoted)
+                .destination_connection_ids()
+                .collect()
+        };
+
+        // long header with a 4 byte destination connection ID
+        let quoted = [0xc0, 0, 0, 0, 1, 4, 1, 2, 3, 4, 0];
+        assert_eq!(
+            ids(&quoted),
+            [connection::PeerId::try_from_bytes(&[1, 2, 3, 4]).unwrap()]
+        );
+
+        // truncated and zero-length long header connection IDs
+        assert!(ids(&quoted[..8]).is_empty());
+        assert!(ids(&[0xc0, 0, 0, 0, 1, 0]).is_empty());
+
+        // short header candidates are limited by the quoted length
+        let quoted = [0x40, 1, 2, 3];
+        let expected: Vec<_> = (1..=3)
+            .map(|len| connection::PeerId::try_from_bytes(&quoted[1..1 + len]).unwrap())
+            .collect();
+        assert_eq!(ids(&quoted), expected);
+
+        let quoted = [0x40; MAX_QUOTED_LEN];
+        assert_eq!(ids(&quoted).len(), connection::id::MAX_LEN);
+
+        assert!(ids(&[]).is_empty());
+    }
+
+    #[test]
+    fn rate_limiter_test() {
+        let mut limiter = RateLimiter::default();
+        let now = NoopClock.get_time();
+
+        for _ in 0..MAX_MESSAGES_PER_WINDOW {
+            assert!(limiter.on_message(now));
+        }
+        assert!(!limiter.on_message(now));
+        assert!(!limiter.on_message(now + RateLimiter::WINDOW / 2));
+
+        // the limit is reset once the window has elapsed
+        assert!(limiter.on_message(now + RateLimiter::WINDOW));
+    }
+}
@@ -1,7 +1,11 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{event, inet::datagram, path};
+use crate::{
+    event,
+    inet::{datagram, icmp},
+    path,
+};
 use core::task::{Context, Poll};
 
 pub mod pair;
@@ -54,6 +58,14 @@ pub trait Queue {
     /// Iterates over all of the packets in the receive queue and processes them
     fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(&mut self, on_packet: F);
 
+    /// Iterates over all of the ICMP Packet Too Big messages received since the last call
+    ///
+    /// IO providers that don't read ICMP errors from the socket don't report any messages.
+    #[inline]
+    fn for_each_packet_too_big<F: FnMut(&icmp::PacketTooBig)>(&mut self, on_packet_too_big: F) {
+        let _ = on_packet_too_big;
+    }
+
     /// Returns if there are items in the queue or not
     fn is_empty(&self) -> bool;
 }
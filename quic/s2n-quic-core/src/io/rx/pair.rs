@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::Rx;
-use crate::{event, inet::datagram};
+use crate::{
+    event,
+    inet::{datagram, icmp},
+};
 use core::task::{Context, Poll};
 
 /// A pair of Rx channels that feed into the same endpoint
@@ -125,6 +128,12 @@ where
         self.b.for_each(&mut on_packet);
     }
 
+    #[inline]
+    fn for_each_packet_too_big<F: FnMut(&icmp::PacketTooBig)>(&mut self, mut on_packet_too_big: F) {
+        self.a.for_each_packet_too_big(&mut on_packet_too_big);
+        self.b.for_each_packet_too_big(&mut on_packet_too_big);
+    }
+
     #[inline]
     fn is_empty(&self) -> bool {
         self.a.is_empty() && self.b.is_empty()
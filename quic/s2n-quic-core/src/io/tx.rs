@@ -102,6 +102,15 @@ pub trait Queue {
     /// Returns the number of remaining datagrams that can be transmitted
     fn capacity(&self) -> usize;
 
+    /// Returns the maximum [`Message::delay`] the queue will honor
+    ///
+    /// Queues which support pacing hold each message until its delay has elapsed, so messages
+    /// can be pushed ahead of their departure time by up to this amount.
+    #[inline]
+    fn max_delay(&self) -> Duration {
+        Duration::ZERO
+    }
+
     /// Returns `true` if the queue will accept additional transmissions
     #[inline]
     fn has_capacity(&self) -> bool {
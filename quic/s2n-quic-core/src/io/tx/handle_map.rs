@@ -109,6 +109,11 @@ where
         self.tx.capacity()
     }
 
+    #[inline]
+    fn max_delay(&self) -> Duration {
+        self.tx.max_delay()
+    }
+
     #[inline]
     fn has_capacity(&self) -> bool {
         self.tx.has_capacity()
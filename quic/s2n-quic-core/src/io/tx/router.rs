@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{event, io::tx, path};
-use core::task::{Context, Poll};
+use core::{
+    task::{Context, Poll},
+    time::Duration,
+};
 
 /// Defines how to route a message between two different channels
 pub trait Router {
@@ -161,6 +164,13 @@ where
         self.a.capacity().min(self.b.capacity())
     }
 
+    #[inline]
+    fn max_delay(&self) -> Duration {
+        // take the minimum of the channel delays, since we don't know where the next message
+        // will go
+        self.a.max_delay().min(self.b.max_delay())
+    }
+
     #[inline]
     fn has_capacity(&self) -> bool {
         // we only have capacity if both channels do
@@ -50,6 +50,7 @@ pub mod token;
 pub mod transmission;
 pub mod transport;
 pub mod varint;
+pub mod version;
 pub mod xdp;
 
 #[cfg(any(test, feature = "testing"))]
@@ -37,6 +37,9 @@ use s2n_codec::{
 pub(crate) const PACKET_TYPE_MASK: u8 = 0x30;
 const PACKET_TYPE_OFFSET: u8 = 4;
 
+/// The header form and fixed bits of a long header
+const LONG_HEADER_TAG: u8 = 0xc0;
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2
 //# Type-Specific Bits:  The semantics of the lower four bits (those with
 //# a mask of 0x0f) of byte 0 are determined by the packet type.
@@ -133,7 +136,7 @@ pub(crate) fn validate_source_connection_id_len(len: usize) -> Result<(), Decode
 //#                   Table 5: Long Header Packet Types
 
 #[repr(u8)]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PacketType {
     Initial = 0x0,
     ZeroRtt = 0x1,
@@ -149,6 +152,36 @@ impl PacketType {
     pub fn from_bits(bits: u8) -> Self {
         (bits & (PACKET_TYPE_MASK >> PACKET_TYPE_OFFSET)).into()
     }
+
+    /// Returns the first byte of a long header with the packet type encoded for the given version
+    ///
+    /// QUIC version 2 rotates the long header packet type codepoints: Initial is 0b01,
+    /// 0-RTT is 0b10, Handshake is 0b11 and Retry is 0b00.
+    /// See <https://www.rfc-editor.org/rfc/rfc9369#section-3.2>.
+    #[inline]
+    pub const fn into_tag(self, version: Version) -> u8 {
+        let bits = if version == crate::version::V2 {
+            (self as u8 + 1) & 0b11
+        } else {
+            self as u8
+        };
+
+        LONG_HEADER_TAG | ((bits << PACKET_TYPE_OFFSET) & PACKET_TYPE_MASK)
+    }
+
+    /// Returns the packet type encoded in the first byte of a long header for the given version
+    ///
+    /// Versions other than QUIC version 2 use the version 1 codepoints.
+    #[inline]
+    pub fn from_tag(tag: u8, version: Version) -> Self {
+        let bits = (tag & PACKET_TYPE_MASK) >> PACKET_TYPE_OFFSET;
+
+        if version == crate::version::V2 {
+            Self::from_bits(bits.wrapping_add(0b11))
+        } else {
+            Self::from_bits(bits)
+        }
+    }
 }
 
 impl From<u8> for PacketType {
@@ -262,3 +295,31 @@ impl EncoderValue for LongPayloadLenCursor {
         self.max_value.encode(encoder)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::{V1, V2};
+
+    #[test]
+    fn packet_type_tag_test() {
+        // (packet type, version 1 bits, version 2 bits)
+        let table = [
+            (PacketType::Initial, 0b00, 0b01),
+            (PacketType::ZeroRtt, 0b01, 0b10),
+            (PacketType::Handshake, 0b10, 0b11),
+            (PacketType::Retry, 0b11, 0b00),
+        ];
+
+        let unknown_version = 0xff00_001d;
+
+        for (packet_type, v1_bits, v2_bits) in table {
+            for (version, bits) in [(V1, v1_bits), (V2, v2_bits), (unknown_version, v1_bits)] {
+                let tag = packet_type.into_tag(version);
+                assert_eq!(tag & LONG_HEADER_TAG, LONG_HEADER_TAG);
+                assert_eq!((tag & PACKET_TYPE_MASK) >> PACKET_TYPE_OFFSET, bits);
+                assert_eq!(PacketType::from_tag(tag, version), packet_type);
+            }
+        }
+    }
+}
@@ -4,7 +4,9 @@
 use crate::{
     connection,
     connection::{id::ConnectionInfo, ProcessingError},
-    crypto::{packet_protection, EncryptedPayload, OneRttHeaderKey, OneRttKey, ProtectedPayload},
+    crypto::{
+        packet_protection, EncryptedPayload, OneRttHeaderKey, OneRttPacketKey, ProtectedPayload,
+    },
     packet::{
         decoding::HeaderDecoder,
         encoding::{PacketEncoder, PacketPayloadEncoder},
@@ -197,7 +199,10 @@ impl<'a> ProtectedShort<'a> {
 }
 
 impl<'a> EncryptedShort<'a> {
-    pub fn decrypt<C: OneRttKey>(self, crypto: &C) -> Result<CleartextShort<'a>, ProcessingError> {
+    pub fn decrypt<C: OneRttPacketKey>(
+        self,
+        crypto: &C,
+    ) -> Result<CleartextShort<'a>, ProcessingError> {
         let Short {
             spin_bit,
             key_phase,
@@ -276,7 +281,7 @@ impl<DCID: EncoderValue, PacketNumber, Payload> Short<DCID, KeyPhase, PacketNumb
     }
 }
 
-impl<DCID: EncoderValue, Payload: PacketPayloadEncoder, K: OneRttKey, H: OneRttHeaderKey>
+impl<DCID: EncoderValue, Payload: PacketPayloadEncoder, K: OneRttPacketKey, H: OneRttHeaderKey>
     PacketEncoder<K, H, Payload> for Short<DCID, KeyPhase, PacketNumber, Payload>
 {
     type PayloadLenCursor = ();
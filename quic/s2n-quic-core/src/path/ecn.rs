@@ -86,9 +86,43 @@ pub struct Controller {
     // The largest acknowledged packet sent with an ECN marking. Used when tracking
     // packets that have been lost for the purpose of detecting a black hole.
     last_acked_ecn_packet_timestamp: Option<Timestamp>,
+    // True if packets are marked with ECT(1) to identify them as L4S traffic
+    l4s: bool,
 }
 
 impl Controller {
+    /// Returns a controller that marks packets with the ECT(1) codepoint instead of ECT(0)
+    ///
+    /// This should only be used when the congestion controller on the path provides a
+    /// scalable response to ECN-CE marks, as ECT(1) identifies the traffic as L4S.
+    /// See <https://www.rfc-editor.org/rfc/rfc9331#section-4.1>
+    #[inline]
+    pub fn l4s() -> Self {
+        Self {
+            l4s: true,
+            ..Default::default()
+        }
+    }
+
+    /// Sets whether packets are marked with the ECT(1) codepoint instead of ECT(0)
+    ///
+    /// The ECN counts reported by the peer are tracked separately for each codepoint, so
+    /// the codepoint can be changed while ECN validation is in progress.
+    #[inline]
+    pub fn set_l4s(&mut self, l4s: bool) {
+        self.l4s = l4s;
+    }
+
+    /// Returns the ECN Capable Transport codepoint used for marking packets
+    #[inline]
+    fn ect(&self) -> ExplicitCongestionNotification {
+        if self.l4s {
+            ExplicitCongestionNotification::Ect1
+        } else {
+            ExplicitCongestionNotification::Ect0
+        }
+    }
+
     /// Restart testing of ECN capability
     #[inline]
     pub fn restart<Pub: event::ConnectionPublisher>(
@@ -144,7 +178,7 @@ impl Controller {
             //# On paths with a "testing" or "capable" state, the endpoint
             //# sends packets with an ECT marking -- ECT(0) by default;
             //# otherwise, the endpoint sends unmarked packets.
-            State::Testing(_) => ExplicitCongestionNotification::Ect0,
+            State::Testing(_) => self.ect(),
             State::Capable(ref mut ce_suppression_timer) => {
                 if ce_suppression_timer.poll_expiration(now).is_ready() {
                     //= https://www.rfc-editor.org/rfc/rfc9002#section-8.3
@@ -156,7 +190,7 @@ impl Controller {
                     //# Upon successful validation, an endpoint MAY continue to set an ECT
                     //# codepoint in subsequent packets it sends, with the expectation that
                     //# the path is ECN-capable.
-                    ExplicitCongestionNotification::Ect0
+                    self.ect()
                 }
             }
             //= https://www.rfc-editor.org/rfc/rfc9000#section-13.4.2.2
@@ -252,6 +286,7 @@ impl Controller {
                 .checked_sub(baseline_ecn_counts)
         {
             if Self::ce_remarking(incremental_ecn_counts, newly_acked_ecn_counts)
+                || Self::ect_1_ce_remarking(incremental_ecn_counts, newly_acked_ecn_counts)
                 || Self::remarked_to_ect0_or_ect1(incremental_ecn_counts, sent_packet_ecn_counts)
                 || Self::ce_suppression(incremental_ecn_counts, newly_acked_ecn_counts)
             {
@@ -272,7 +307,8 @@ impl Controller {
         //# (see Section 13.4.2.1) causes the ECN state for the path to become "capable",
         //# unless no marked packet has been acknowledged.
         if matches!(self.state, State::Unknown)
-            && newly_acked_ecn_counts.ect_0_count > VarInt::from_u8(0)
+            && (newly_acked_ecn_counts.ect_0_count > VarInt::from_u8(0)
+                || newly_acked_ecn_counts.ect_1_count > VarInt::from_u8(0))
         {
             // Arm the ce suppression timer to send a ECN-CE marked packet to test for
             // CE suppression by the peer.
@@ -301,6 +337,22 @@ impl Controller {
         ect_0_increase < newly_acked_ecn_counts.ect_0_count
     }
 
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-13.4.2.1
+    //# Similarly, ECN
+    //# validation fails if the sum of the increases to ECT(1) and ECN-CE
+    //# counts is less than the number of newly acknowledged packets sent
+    //# with an ECT(1) marking.
+    #[inline]
+    fn ect_1_ce_remarking(
+        incremental_ecn_counts: EcnCounts,
+        newly_acked_ecn_counts: EcnCounts,
+    ) -> bool {
+        let ect_1_increase = incremental_ecn_counts
+            .ect_1_count
+            .saturating_add(incremental_ecn_counts.ce_count);
+        ect_1_increase < newly_acked_ecn_counts.ect_1_count
+    }
+
     //= https://www.rfc-editor.org/rfc/rfc9000#section-13.4.2.1
     //# ECN validation can fail if the received total count for either ECT(0) or ECT(1)
     //# exceeds the total number of packets sent with each corresponding ECT codepoint.
@@ -343,8 +395,11 @@ impl Controller {
         publisher: &mut Pub,
     ) {
         debug_assert!(
-            !matches!(ecn, ExplicitCongestionNotification::Ect1),
-            "Ect1 is not used"
+            !matches!(
+                ecn,
+                ExplicitCongestionNotification::Ect0 | ExplicitCongestionNotification::Ect1
+            ) || ecn == self.ect(),
+            "only the configured ECT codepoint is used"
         );
 
         if let (true, State::Testing(ref mut packet_count)) = (ecn.using_ecn(), &mut self.state) {
@@ -191,6 +191,43 @@ fn ecn() {
     }
 }
 
+#[test]
+fn ecn_l4s() {
+    let now = time::now();
+    let mut controller = Controller::l4s();
+
+    assert_eq!(
+        ExplicitCongestionNotification::Ect1,
+        controller.ecn(transmission::Mode::Normal, now)
+    );
+
+    let mut ce_suppression_timer = Timer::default();
+    ce_suppression_timer.set(now + Duration::from_secs(10));
+    controller.state = State::Capable(ce_suppression_timer);
+    assert_eq!(
+        ExplicitCongestionNotification::Ect1,
+        controller.ecn(transmission::Mode::Normal, now)
+    );
+
+    // Loss recovery probes are still sent without an ECN marking
+    assert_eq!(
+        ExplicitCongestionNotification::NotEct,
+        controller.ecn(transmission::Mode::LossRecoveryProbing, now)
+    );
+
+    // The codepoint can be changed when the congestion controller changes
+    controller.set_l4s(false);
+    assert_eq!(
+        ExplicitCongestionNotification::Ect0,
+        controller.ecn(transmission::Mode::Normal, now)
+    );
+    controller.set_l4s(true);
+    assert_eq!(
+        ExplicitCongestionNotification::Ect1,
+        controller.ecn(transmission::Mode::Normal, now)
+    );
+}
+
 #[test]
 fn ecn_ce_suppression() {
     let now = time::now();
@@ -393,6 +430,67 @@ fn validate_ect_0_remarking_after_restart() {
     assert!(matches!(controller.state, State::Failed(_)));
 }
 
+//= https://www.rfc-editor.org/rfc/rfc9000#section-13.4.2.1
+//= type=test
+//# Similarly, ECN
+//# validation fails if the sum of the increases to ECT(1) and ECN-CE
+//# counts is less than the number of newly acknowledged packets sent
+//# with an ECT(1) marking.
+#[test]
+fn validate_ect_1_ce_remarking() {
+    let mut publisher = Publisher::no_snapshot();
+    let mut controller = Controller {
+        state: State::Unknown,
+        ..Controller::l4s()
+    };
+    let now = time::now();
+    let expected_ecn_counts = helper_ecn_counts(0, 2, 0);
+    let sent_packet_ecn_counts = helper_ecn_counts(0, 2, 0);
+    let ack_frame_ecn_counts = helper_ecn_counts(0, 1, 0);
+    let outcome = controller.validate(
+        expected_ecn_counts,
+        sent_packet_ecn_counts,
+        EcnCounts::default(),
+        Some(ack_frame_ecn_counts),
+        now,
+        Duration::default(),
+        Path::test(),
+        &mut publisher,
+    );
+
+    assert_eq!(ValidationOutcome::Failed, outcome);
+    assert!(matches!(controller.state, State::Failed(_)));
+}
+
+#[test]
+fn validate_capable_l4s() {
+    let mut publisher = Publisher::no_snapshot();
+    let mut controller = Controller {
+        state: State::Unknown,
+        ..Controller::l4s()
+    };
+    let now = time::now();
+    let expected_ecn_counts = helper_ecn_counts(0, 2, 0);
+    let sent_packet_ecn_counts = helper_ecn_counts(0, 2, 0);
+    let ack_frame_ecn_counts = helper_ecn_counts(0, 1, 1);
+    let outcome = controller.validate(
+        expected_ecn_counts,
+        sent_packet_ecn_counts,
+        EcnCounts::default(),
+        Some(ack_frame_ecn_counts),
+        now,
+        Duration::from_millis(50),
+        Path::test(),
+        &mut publisher,
+    );
+
+    assert_eq!(
+        ValidationOutcome::CongestionExperienced(1_u8.into()),
+        outcome
+    );
+    assert!(controller.is_capable());
+}
+
 #[test]
 fn validate_no_ecn_counts() {
     let mut publisher = Publisher::snapshot();
@@ -2,13 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    event,
+    connection, event,
     event::{
         api::{Path, SocketAddress},
         IntoEvent,
     },
     inet,
 };
+use core::fmt;
 
 #[derive(Debug)]
 #[non_exhaustive]
@@ -112,6 +113,58 @@ pub trait Validator: 'static + Send {
     fn on_migration_attempt(&mut self, attempt: &Attempt) -> Outcome;
 }
 
+/// Errors returned when a connection is unable to migrate to a new local address
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The connection was closed
+    #[non_exhaustive]
+    ConnectionError { error: connection::Error },
+    /// Only clients are able to initiate a migration
+    ServerInitiated,
+    /// The handshake has not been confirmed yet
+    HandshakeNotConfirmed,
+    /// The peer sent the `disable_active_migration` transport parameter
+    Disabled,
+    /// A migration to another local address is already in progress
+    InProgress,
+    /// The connection is already using the local address
+    AlreadyActive,
+    /// The peer has not issued a connection ID that can be used for a new path
+    NoAvailableConnectionId,
+    /// The connection has reached the maximum number of paths
+    PathLimitExceeded,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ConnectionError { error } => error.fmt(f),
+            Self::ServerInitiated => write!(f, "Only clients are able to initiate a migration"),
+            Self::HandshakeNotConfirmed => write!(f, "The handshake has not been confirmed yet"),
+            Self::Disabled => write!(f, "The peer disabled active connection migration"),
+            Self::InProgress => write!(f, "A migration is already in progress"),
+            Self::AlreadyActive => write!(f, "The connection is already using the local address"),
+            Self::NoAvailableConnectionId => {
+                write!(f, "The peer has not issued an unused connection ID")
+            }
+            Self::PathLimitExceeded => {
+                write!(f, "The connection has reached the maximum number of paths")
+            }
+        }
+    }
+}
+
+impl From<connection::Error> for Error {
+    #[inline]
+    fn from(error: connection::Error) -> Self {
+        Self::ConnectionError { error }
+    }
+}
+
 pub mod default {
     use super::*;
     use crate::path::remote_port_blocked;
@@ -106,17 +106,18 @@ const ETHERNET_MTU: u16 = 1500;
 /// the current Path MTU, probing will be considered complete.
 const PROBE_THRESHOLD: u16 = 20;
 
-/// When the black_hole_counter exceeds this threshold, on_black_hole_detected will be
-/// called to reduce the MTU to the BASE_PLPMTU. The black_hole_counter is incremented when
-/// a burst of consecutive packets is lost that starts with a packet that is:
+/// The default black hole threshold. When the black_hole_counter exceeds the threshold,
+/// on_black_hole_detected will be called to reduce the MTU to the BASE_PLPMTU.
+/// The black_hole_counter is incremented when a burst of consecutive packets is lost
+/// that starts with a packet that is:
 ///      1) not an MTU probe
 ///      2) larger than the BASE_PLPMTU
 ///      3) sent after the largest MTU-sized acknowledged packet number
 /// This is a possible indication that the path cannot support the MTU that was previously confirmed.
 const BLACK_HOLE_THRESHOLD: u8 = 3;
 
-/// After a black hole has been detected, the mtu::Controller will wait this duration
-/// before probing for a larger MTU again.
+/// By default, after a black hole has been detected, the mtu::Controller will wait this
+/// duration before probing for a larger MTU again.
 const BLACK_HOLE_COOL_OFF_DURATION: Duration = Duration::from_secs(60);
 
 //= https://www.rfc-editor.org/rfc/rfc8899#section-5.1.1
@@ -126,6 +127,12 @@ const BLACK_HOLE_COOL_OFF_DURATION: Duration = Duration::from_secs(60);
 //# seconds, as recommended by PLPMTUD [RFC4821].
 const PMTU_RAISE_TIMER_DURATION: Duration = Duration::from_secs(600);
 
+/// The minimum configurable PMTU_RAISE_TIMER period
+///
+/// Shorter periods would restart the search almost immediately after it completes, continuously
+/// sending probes.
+const MIN_PROBE_INTERVAL: Duration = Duration::from_secs(1);
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-14
 //# QUIC MUST NOT be used if the network path cannot support a
 //# maximum datagram size of at least 1200 bytes.
@@ -329,12 +336,69 @@ impl Endpoint for Inherit {
     }
 }
 
+//= https://www.rfc-editor.org/rfc/rfc8899#section-5.3.2
+//# Implementations SHOULD select the set of probe packet sizes to
+//# maximize the gain in PLPMTU from each search step.
+/// The method used to select the size of the next MTU probe.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SearchStrategy {
+    /// Each probe is halfway between the current MTU and the largest size that
+    /// may still be supported by the path (default)
+    #[default]
+    Binary,
+    /// Each probe is `step` bytes larger than the current MTU. Once a probe size
+    /// is not supported by the path, the remaining sizes are searched with a binary search.
+    ///
+    /// Steps smaller than 20 bytes are increased to 20 bytes.
+    Linear { step: u16 },
+}
+
+impl SearchStrategy {
+    /// Calculates the next probe size between the `current` MTU and the `max` size to probe for
+    #[inline]
+    fn next_probe_size(&self, current: u16, max: u16) -> u16 {
+        let binary = current + ((max - current) / 2);
+
+        match self {
+            Self::Binary => binary,
+            Self::Linear { step } => {
+                let next = current.saturating_add((*step).max(PROBE_THRESHOLD));
+                if next < max {
+                    next
+                } else {
+                    binary
+                }
+            }
+        }
+    }
+}
+
 /// MTU configuration.
-#[derive(Copy, Clone, Debug, Default)]
+#[derive(Copy, Clone, Debug)]
 pub struct Config {
     initial_mtu: InitialMtu,
     base_mtu: BaseMtu,
     max_mtu: MaxMtu,
+    probe_interval: Duration,
+    search_strategy: SearchStrategy,
+    black_hole_threshold: u8,
+    black_hole_cool_off: Duration,
+}
+
+impl Default for Config {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            initial_mtu: Default::default(),
+            base_mtu: Default::default(),
+            max_mtu: Default::default(),
+            probe_interval: PMTU_RAISE_TIMER_DURATION,
+            search_strategy: Default::default(),
+            black_hole_threshold: BLACK_HOLE_THRESHOLD,
+            black_hole_cool_off: BLACK_HOLE_COOL_OFF_DURATION,
+        }
+    }
 }
 
 impl Endpoint for Config {
@@ -352,6 +416,10 @@ impl Config {
         initial_mtu: InitialMtu::MIN,
         base_mtu: BaseMtu::MIN,
         max_mtu: MaxMtu::MIN,
+        probe_interval: PMTU_RAISE_TIMER_DURATION,
+        search_strategy: SearchStrategy::Binary,
+        black_hole_threshold: BLACK_HOLE_THRESHOLD,
+        black_hole_cool_off: BLACK_HOLE_COOL_OFF_DURATION,
     };
 
     pub fn builder() -> Builder {
@@ -373,6 +441,28 @@ impl Config {
         self.max_mtu
     }
 
+    /// The period after which the search for a larger MTU is restarted once a search has completed.
+    pub fn probe_interval(&self) -> Duration {
+        self.probe_interval
+    }
+
+    /// The method used to select the size of the next MTU probe.
+    pub fn search_strategy(&self) -> SearchStrategy {
+        self.search_strategy
+    }
+
+    /// The number of bursts of lost packets larger than the base MTU that are tolerated
+    /// before an MTU black hole is detected.
+    pub fn black_hole_threshold(&self) -> u8 {
+        self.black_hole_threshold
+    }
+
+    /// The period to wait after an MTU black hole is detected before the search for
+    /// a larger MTU is restarted.
+    pub fn black_hole_cool_off(&self) -> Duration {
+        self.black_hole_cool_off
+    }
+
     /// Returns true if the MTU configuration is valid
     ///
     /// A valid MTU configuration must have base_mtu <= initial_mtu <= max_mtu
@@ -387,6 +477,10 @@ pub struct Builder {
     initial_mtu: Option<InitialMtu>,
     base_mtu: Option<BaseMtu>,
     max_mtu: Option<MaxMtu>,
+    probe_interval: Option<Duration>,
+    search_strategy: Option<SearchStrategy>,
+    black_hole_threshold: Option<u8>,
+    black_hole_cool_off: Option<Duration>,
 }
 
 impl Builder {
@@ -445,6 +539,42 @@ impl Builder {
         Ok(self)
     }
 
+    /// Sets the period after which the search for a larger MTU is restarted once a
+    /// search has completed (default: 600 seconds)
+    ///
+    /// This corresponds to the PMTU_RAISE_TIMER described in
+    /// [RFC 8899](https://www.rfc-editor.org/rfc/rfc8899#section-5.1.1). The interval must
+    /// be at least 1 second.
+    pub fn with_probe_interval(mut self, probe_interval: Duration) -> Result<Self, MtuError> {
+        ensure!(probe_interval >= MIN_PROBE_INTERVAL, Err(MtuError));
+        self.probe_interval = Some(probe_interval);
+        Ok(self)
+    }
+
+    /// Sets the method used to select the size of the next MTU probe (default: [`SearchStrategy::Binary`])
+    pub fn with_search_strategy(mut self, search_strategy: SearchStrategy) -> Self {
+        self.search_strategy = Some(search_strategy);
+        self
+    }
+
+    /// Sets the number of bursts of lost packets larger than the base MTU that are tolerated
+    /// before an MTU black hole is detected (default: 3)
+    ///
+    /// Only bursts that start with a packet sent after the most recently acknowledged packet
+    /// of the current MTU are counted. When a black hole is detected, the MTU falls back to the
+    /// base MTU and the search is restarted after the black hole cool off period.
+    pub fn with_black_hole_threshold(mut self, black_hole_threshold: u8) -> Self {
+        self.black_hole_threshold = Some(black_hole_threshold);
+        self
+    }
+
+    /// Sets the period to wait after an MTU black hole is detected before the search for
+    /// a larger MTU is restarted (default: 60 seconds)
+    pub fn with_black_hole_cool_off(mut self, black_hole_cool_off: Duration) -> Self {
+        self.black_hole_cool_off = Some(black_hole_cool_off);
+        self
+    }
+
     pub fn build(self) -> Result<Config, MtuError> {
         let base_mtu = self.base_mtu.unwrap_or_default();
         let max_mtu = self.max_mtu.unwrap_or_default();
@@ -465,6 +595,12 @@ impl Builder {
             initial_mtu,
             max_mtu,
             base_mtu,
+            probe_interval: self.probe_interval.unwrap_or(PMTU_RAISE_TIMER_DURATION),
+            search_strategy: self.search_strategy.unwrap_or_default(),
+            black_hole_threshold: self.black_hole_threshold.unwrap_or(BLACK_HOLE_THRESHOLD),
+            black_hole_cool_off: self
+                .black_hole_cool_off
+                .unwrap_or(BLACK_HOLE_COOL_OFF_DURATION),
         };
 
         ensure!(config.is_valid(), Err(MtuError));
@@ -515,6 +651,16 @@ pub struct Controller {
     //# sender will continue to use the current PLPMTU, after which it
     //# reenters the Search Phase.
     pmtu_raise_timer: Timer,
+    /// The period after which the search for a larger MTU is restarted
+    probe_interval: Duration,
+    /// The method used to select the size of the next MTU probe
+    search_strategy: SearchStrategy,
+    /// The number of loss bursts tolerated before a black hole is detected
+    black_hole_threshold: u8,
+    /// The period to wait after a black hole is detected before restarting the search
+    black_hole_cool_off: Duration,
+    /// True if the PMTU raise timer was armed following the detection of a black hole
+    is_cooling_off: bool,
 }
 
 impl Controller {
@@ -540,19 +686,21 @@ impl Controller {
         //# believes that the network path and peer both support the size that it chooses.
         let plpmtu = config.initial_mtu.max_datagram_size(peer_socket_address);
 
-        let initial_probed_size = if u16::from(config.initial_mtu) > ETHERNET_MTU - PROBE_THRESHOLD
-        {
-            // An initial MTU was provided within the probe threshold of the Ethernet MTU, so we can
-            // instead try probing for an MTU larger than the Ethernet MTU
-            Self::next_probe_size(plpmtu, max_udp_payload)
-        } else {
-            // The UDP payload size for the most likely MTU is based on standard Ethernet MTU minus
-            // the minimum length IP headers (without IPv4 options or IPv6 extensions) and UPD header
-            let min_ip_header_len = match peer_socket_address {
-                inet::SocketAddress::IpV4(_) => IPV4_MIN_HEADER_LEN,
-                inet::SocketAddress::IpV6(_) => IPV6_MIN_HEADER_LEN,
-            };
-            ETHERNET_MTU - UDP_HEADER_LEN - min_ip_header_len
+        let initial_probed_size = match config.search_strategy {
+            SearchStrategy::Binary
+                if u16::from(config.initial_mtu) <= ETHERNET_MTU - PROBE_THRESHOLD =>
+            {
+                // The UDP payload size for the most likely MTU is based on standard Ethernet MTU minus
+                // the minimum length IP headers (without IPv4 options or IPv6 extensions) and UPD header
+                let min_ip_header_len = match peer_socket_address {
+                    inet::SocketAddress::IpV4(_) => IPV4_MIN_HEADER_LEN,
+                    inet::SocketAddress::IpV6(_) => IPV6_MIN_HEADER_LEN,
+                };
+                ETHERNET_MTU - UDP_HEADER_LEN - min_ip_header_len
+            }
+            // An initial MTU was provided within the probe threshold of the Ethernet MTU, or
+            // a different search strategy was configured, so probe according to the strategy
+            search_strategy => search_strategy.next_probe_size(plpmtu, max_udp_payload),
         }
         .min(max_udp_payload);
 
@@ -579,6 +727,11 @@ impl Controller {
             black_hole_counter: Default::default(),
             largest_acked_mtu_sized_packet: None,
             pmtu_raise_timer: Timer::default(),
+            probe_interval: config.probe_interval,
+            search_strategy: config.search_strategy,
+            black_hole_threshold: config.black_hole_threshold,
+            black_hole_cool_off: config.black_hole_cool_off,
+            is_cooling_off: false,
         }
     }
 
@@ -596,9 +749,133 @@ impl Controller {
 
     /// Called when the connection timer expires
     #[inline]
-    pub fn on_timeout(&mut self, now: Timestamp) {
+    pub fn on_timeout<Pub: event::ConnectionPublisher>(
+        &mut self,
+        now: Timestamp,
+        path_id: path::Id,
+        publisher: &mut Pub,
+    ) {
         ensure!(self.pmtu_raise_timer.poll_expiration(now).is_ready());
+
+        let cause = if core::mem::take(&mut self.is_cooling_off) {
+            MtuUpdatedCause::BlackholeCoolOffElapsed
+        } else {
+            MtuUpdatedCause::ProbeIntervalElapsed
+        };
+
+        self.request_new_search(None);
+
+        publisher.on_mtu_updated(event::builder::MtuUpdated {
+            path_id: path_id.into_event(),
+            mtu: self.plpmtu,
+            cause,
+            search_complete: self.state.is_search_complete(),
+        });
+    }
+
+    /// Restarts the search for a larger MTU without waiting for the probe interval to elapse
+    ///
+    /// The search considers all sizes up to the max MTU, including sizes that were previously
+    /// not supported by the path. This has no effect if MTU probing has not been enabled yet
+    /// or a search is already in progress.
+    #[inline]
+    pub fn request_probe<Pub: event::ConnectionPublisher>(
+        &mut self,
+        path_id: path::Id,
+        publisher: &mut Pub,
+    ) {
+        ensure!(self.state.is_search_complete());
+
+        self.pmtu_raise_timer.cancel();
+        self.is_cooling_off = false;
+        self.max_probe_size = self.max_udp_payload;
+        self.update_probed_size();
         self.request_new_search(None);
+
+        publisher.on_mtu_updated(event::builder::MtuUpdated {
+            path_id: path_id.into_event(),
+            mtu: self.plpmtu,
+            cause: MtuUpdatedCause::ProbeRequested,
+            search_complete: self.state.is_search_complete(),
+        });
+    }
+
+    /// This method gets called when an ICMP Packet Too Big message has been validated for the path
+    ///
+    /// `max_udp_payload` is the largest UDP payload that fits in the MTU reported by the message.
+    /// Reported sizes below the current MTU reduce the MTU immediately, while reported sizes
+    /// between the current MTU and the size being searched for limit further probing.
+    #[inline]
+    pub fn on_packet_too_big<CC: CongestionController, Pub: event::ConnectionPublisher>(
+        &mut self,
+        max_udp_payload: u16,
+        now: Timestamp,
+        congestion_controller: &mut CC,
+        path_id: path::Id,
+        publisher: &mut Pub,
+    ) -> MtuResult {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-14.2.1
+        //# An endpoint MUST ignore an ICMP message that claims the PMTU
+        //# has decreased below QUIC's smallest allowed maximum datagram
+        //# size.
+        ensure!(max_udp_payload >= self.base_plpmtu, MtuResult::NoChange);
+
+        // The reported size doesn't constrain any size we'd send or probe for
+        ensure!(max_udp_payload < self.max_probe_size, MtuResult::NoChange);
+
+        if max_udp_payload >= self.plpmtu {
+            // The current MTU is still valid, but there is no point in probing beyond
+            // the reported size
+            self.max_probe_size = max_udp_payload;
+            self.update_probed_size();
+
+            if matches!(self.state, State::SearchRequested | State::Searching(_, _)) {
+                self.request_new_search(Some(now));
+
+                if self.is_search_completed() {
+                    publisher.on_mtu_updated(event::builder::MtuUpdated {
+                        path_id: path_id.into_event(),
+                        mtu: self.plpmtu,
+                        cause: MtuUpdatedCause::PacketTooBig,
+                        search_complete: true,
+                    });
+                }
+            }
+
+            return MtuResult::NoChange;
+        }
+
+        // Lower the plpmtu to the reported size and notify the congestion controller
+        self.black_hole_counter = Default::default();
+        self.largest_acked_mtu_sized_packet = None;
+        self.plpmtu = max_udp_payload;
+        congestion_controller.on_mtu_update(
+            self.plpmtu,
+            &mut congestion_controller::PathPublisher::new(publisher, path_id),
+        );
+
+        if self.state.is_disabled() || self.state.is_early_search_requested() {
+            // MTU probing hasn't been enabled yet, so it will start from the
+            // reported size once it is
+            self.state = State::Disabled;
+            self.max_probe_size = self.max_udp_payload;
+            self.update_probed_size();
+        } else {
+            // Cancel any current probes and try a larger MTU again after the probe interval,
+            // since the path may have changed back by then
+            self.state = State::SearchComplete;
+            self.is_cooling_off = false;
+            self.arm_pmtu_raise_timer(now + self.probe_interval);
+        }
+
+        publisher.on_mtu_updated(event::builder::MtuUpdated {
+            path_id: path_id.into_event(),
+            mtu: self.plpmtu,
+            cause: MtuUpdatedCause::PacketTooBig,
+            search_complete: self.state.is_search_complete(),
+        });
+
+        MtuResult::MtuUpdated(self.plpmtu)
     }
 
     //= https://www.rfc-editor.org/rfc/rfc8899#section-4.2
@@ -778,7 +1055,7 @@ impl Controller {
                     self.black_hole_counter += 1;
                 }
 
-                if self.black_hole_counter > BLACK_HOLE_THRESHOLD {
+                if self.black_hole_counter > self.black_hole_threshold {
                     return self.on_black_hole_detected(
                         now,
                         congestion_controller,
@@ -817,13 +1094,9 @@ impl Controller {
         //= https://www.rfc-editor.org/rfc/rfc8899#section-5.3.2
         //# Implementations SHOULD select the set of probe packet sizes to
         //# maximize the gain in PLPMTU from each search step.
-        self.probed_size = Self::next_probe_size(self.plpmtu, self.max_probe_size);
-    }
-
-    /// Calculates the next probe size as halfway from the current to the max size
-    #[inline]
-    fn next_probe_size(current: u16, max: u16) -> u16 {
-        current + ((max - current) / 2)
+        self.probed_size = self
+            .search_strategy
+            .next_probe_size(self.plpmtu, self.max_probe_size);
     }
 
     #[inline]
@@ -847,7 +1120,8 @@ impl Controller {
             self.state = State::SearchComplete;
 
             if let Some(last_probe_time) = last_probe_time {
-                self.arm_pmtu_raise_timer(last_probe_time + PMTU_RAISE_TIMER_DURATION);
+                self.is_cooling_off = false;
+                self.arm_pmtu_raise_timer(last_probe_time + self.probe_interval);
             }
         }
     }
@@ -871,8 +1145,9 @@ impl Controller {
         );
         // Cancel any current probes
         self.state = State::SearchComplete;
-        // Arm the PMTU raise timer to try a larger MTU again after a cooling off period
-        self.arm_pmtu_raise_timer(now + BLACK_HOLE_COOL_OFF_DURATION);
+        // Arm the PMTU raise timer to restart the search for a larger MTU after a cooling off period
+        self.is_cooling_off = true;
+        self.arm_pmtu_raise_timer(now + self.black_hole_cool_off);
 
         publisher.on_mtu_updated(event::builder::MtuUpdated {
             path_id: path_id.into_event(),
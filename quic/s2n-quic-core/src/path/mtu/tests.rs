@@ -33,6 +33,7 @@ fn mtu_config_is_valid() {
         initial_mtu: 1500.try_into().unwrap(),
         base_mtu: 1228.try_into().unwrap(),
         max_mtu: 9000.try_into().unwrap(),
+        ..Default::default()
     };
 
     assert!(config.is_valid());
@@ -41,6 +42,7 @@ fn mtu_config_is_valid() {
         initial_mtu: 1500.try_into().unwrap(),
         base_mtu: 1500.try_into().unwrap(),
         max_mtu: 1500.try_into().unwrap(),
+        ..Default::default()
     };
 
     assert!(config.is_valid());
@@ -49,6 +51,7 @@ fn mtu_config_is_valid() {
         initial_mtu: 1500.try_into().unwrap(),
         base_mtu: 1501.try_into().unwrap(),
         max_mtu: 9000.try_into().unwrap(),
+        ..Default::default()
     };
 
     assert!(!config.is_valid());
@@ -57,6 +60,7 @@ fn mtu_config_is_valid() {
         initial_mtu: 1500.try_into().unwrap(),
         base_mtu: 1228.try_into().unwrap(),
         max_mtu: 1400.try_into().unwrap(),
+        ..Default::default()
     };
 
     assert!(!config.is_valid());
@@ -121,6 +125,49 @@ fn mtu_config_builder() {
     assert_eq!(Some(MtuError), result.err());
 }
 
+#[test]
+fn mtu_config_builder_probing() {
+    // Default probing options
+    let config = mtu::Config::builder().build().unwrap();
+    assert_eq!(PMTU_RAISE_TIMER_DURATION, config.probe_interval());
+    assert_eq!(SearchStrategy::Binary, config.search_strategy());
+    assert_eq!(BLACK_HOLE_THRESHOLD, config.black_hole_threshold());
+    assert_eq!(BLACK_HOLE_COOL_OFF_DURATION, config.black_hole_cool_off());
+
+    let config = mtu::Config::builder()
+        .with_probe_interval(Duration::from_secs(30))
+        .unwrap()
+        .with_search_strategy(SearchStrategy::Linear { step: 100 })
+        .with_black_hole_threshold(1)
+        .with_black_hole_cool_off(Duration::from_secs(5))
+        .build()
+        .unwrap();
+    assert!(config.is_valid());
+    assert_eq!(Duration::from_secs(30), config.probe_interval());
+    assert_eq!(
+        SearchStrategy::Linear { step: 100 },
+        config.search_strategy()
+    );
+    assert_eq!(1, config.black_hole_threshold());
+    assert_eq!(Duration::from_secs(5), config.black_hole_cool_off());
+
+    // A zero or very short probe interval would restart the search continuously
+    for probe_interval in [
+        Duration::ZERO,
+        MIN_PROBE_INTERVAL - Duration::from_millis(1),
+    ] {
+        let result = mtu::Config::builder().with_probe_interval(probe_interval);
+        assert_eq!(Some(MtuError), result.err());
+    }
+
+    let config = mtu::Config::builder()
+        .with_probe_interval(MIN_PROBE_INTERVAL)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(MIN_PROBE_INTERVAL, config.probe_interval());
+}
+
 #[test]
 fn mtu_manager() {
     let remote = inet::SocketAddress::default();
@@ -148,6 +195,7 @@ fn mtu_manager() {
         initial_mtu: InitialMtu::MIN,
         base_mtu: BaseMtu(NonZeroU16::new(1500).unwrap()),
         max_mtu: MaxMtu::MIN,
+        ..Default::default()
     };
     assert!(!mtu_provider.is_valid());
     let mut manager: Manager<Config> = Manager::new(mtu_provider);
@@ -283,6 +331,7 @@ fn new_initial_and_base_mtu() {
             max_mtu: 2600.try_into().unwrap(),
             base_mtu: 1400.try_into().unwrap(),
             initial_mtu: 2500.try_into().unwrap(),
+            ..Default::default()
         },
         &addr.into(),
     );
@@ -418,7 +467,7 @@ fn on_packet_ack_within_threshold() {
 
     // Enough time passes that its time to try raising the PMTU again
     let now = now + PMTU_RAISE_TIMER_DURATION;
-    controller.on_timeout(now);
+    controller.on_timeout(now, path::Id::test_id(), &mut publisher);
 
     assert_eq!(State::SearchRequested, controller.state);
     assert_eq!(
@@ -738,6 +787,53 @@ fn on_packet_loss_black_hole() {
     assert!(controller.is_search_completed());
 }
 
+#[test]
+fn on_packet_loss_black_hole_configured() {
+    let addr = inet::SocketAddress::IpV4(SocketAddressV4::new([127, 0, 0, 1], 443));
+    let config = mtu::Config::builder()
+        .with_max_mtu(1500)
+        .unwrap()
+        .with_black_hole_threshold(0)
+        .with_black_hole_cool_off(Duration::from_secs(5))
+        .build()
+        .unwrap();
+    let mut controller = Controller::new(config, &addr);
+    let mut cc = CongestionController::default();
+    let now = now();
+    let mut publisher = Publisher::no_snapshot();
+    controller.plpmtu = 1472;
+    controller.enable();
+    let base_plpmtu = controller.base_plpmtu;
+
+    // A single loss burst exceeds the black hole threshold
+    let result = controller.on_packet_loss(
+        pn(1),
+        base_plpmtu + 1,
+        true,
+        now,
+        &mut cc,
+        path::Id::test_id(),
+        &mut publisher,
+    );
+    assert_eq!(MtuResult::MtuUpdated(MINIMUM_MAX_DATAGRAM_SIZE), result);
+    assert_eq!(base_plpmtu, controller.plpmtu);
+    assert_eq!(State::SearchComplete, controller.state);
+    assert!(controller.is_cooling_off);
+    assert_eq!(
+        Some(now + Duration::from_secs(5)),
+        controller.pmtu_raise_timer.next_expiration()
+    );
+
+    // The search is restarted once the cool off period has elapsed
+    controller.on_timeout(
+        now + Duration::from_secs(5),
+        path::Id::test_id(),
+        &mut publisher,
+    );
+    assert_eq!(State::SearchRequested, controller.state);
+    assert!(!controller.is_cooling_off);
+}
+
 #[test]
 fn on_packet_loss_disabled_controller() {
     let mut controller = new_controller(1500);
@@ -844,6 +940,7 @@ fn on_packet_loss_initial_mtu_configured() {
                     max_mtu: max_mtu.try_into().unwrap(),
                     initial_mtu: initial_mtu.min(max_mtu).try_into().unwrap(),
                     base_mtu: base_mtu.min(initial_mtu).min(max_mtu).try_into().unwrap(),
+                    ..Default::default()
                 };
                 let mut controller = Controller::new(mtu_config, &addr);
                 let base_plpmtu = controller.base_plpmtu;
@@ -1012,3 +1109,230 @@ fn on_transmit() {
     );
     assert_eq!(State::Searching(packet_number, now), controller.state);
 }
+
+#[test]
+fn linear_search_strategy() {
+    let addr = inet::SocketAddress::IpV4(SocketAddressV4::new([127, 0, 0, 1], 443));
+    let linear_controller = |max_mtu: u16, step: u16| {
+        let config = mtu::Config::builder()
+            .with_max_mtu(max_mtu)
+            .unwrap()
+            .with_search_strategy(SearchStrategy::Linear { step })
+            .build()
+            .unwrap();
+        Controller::new(config, &addr)
+    };
+
+    // Each probe is one step larger than the current MTU
+    let mut controller = linear_controller(9000, 100);
+    assert_eq!(MINIMUM_MAX_DATAGRAM_SIZE + 100, controller.probed_size);
+
+    let pn = pn(1);
+    let mut cc = CongestionController::default();
+    let mut publisher = Publisher::no_snapshot();
+    controller.state = State::Searching(pn, now());
+    let probed_size = controller.probed_size;
+    let result = controller.on_packet_ack(
+        pn,
+        probed_size,
+        &mut cc,
+        path::Id::test_id(),
+        &mut publisher,
+    );
+    assert_eq!(MtuResult::MtuUpdated(probed_size), result);
+    assert_eq!(probed_size + 100, controller.probed_size);
+    assert_eq!(State::SearchRequested, controller.state);
+
+    // Steps are at least PROBE_THRESHOLD bytes
+    let controller = linear_controller(9000, 1);
+    assert_eq!(
+        MINIMUM_MAX_DATAGRAM_SIZE + PROBE_THRESHOLD,
+        controller.probed_size
+    );
+
+    // A step beyond the max probe size falls back to a binary search
+    let controller = linear_controller(1300, 100);
+    let max_udp_payload = controller.max_udp_payload;
+    assert_eq!(
+        MINIMUM_MAX_DATAGRAM_SIZE + (max_udp_payload - MINIMUM_MAX_DATAGRAM_SIZE) / 2,
+        controller.probed_size
+    );
+}
+
+#[test]
+fn request_probe() {
+    let mut controller = new_controller(1500);
+    let mut publisher = Publisher::no_snapshot();
+    let max_udp_payload = controller.max_udp_payload;
+    let now = now();
+
+    // The request is ignored until MTU probing is enabled
+    controller.request_probe(path::Id::test_id(), &mut publisher);
+    assert_eq!(State::Disabled, controller.state);
+
+    controller.enable();
+    controller.state = State::SearchComplete;
+    controller.is_cooling_off = true;
+    controller.arm_pmtu_raise_timer(now + BLACK_HOLE_COOL_OFF_DURATION);
+    controller.max_probe_size = controller.plpmtu + PROBE_THRESHOLD;
+    assert!(controller.pmtu_raise_timer.is_armed());
+
+    // Sizes that previously failed are probed again without waiting for the timer
+    controller.request_probe(path::Id::test_id(), &mut publisher);
+    assert_eq!(State::SearchRequested, controller.state);
+    assert_eq!(max_udp_payload, controller.max_probe_size);
+    assert!(!controller.pmtu_raise_timer.is_armed());
+    assert!(!controller.is_cooling_off);
+
+    // The request is ignored while a search is in progress
+    let probed_size = controller.probed_size;
+    controller.request_probe(path::Id::test_id(), &mut publisher);
+    assert_eq!(State::SearchRequested, controller.state);
+    assert_eq!(probed_size, controller.probed_size);
+}
+
+#[test]
+fn on_packet_too_big_below_base_mtu() {
+    let mut controller = new_controller(1500);
+    let mut cc = CongestionController::default();
+    let mut publisher = Publisher::no_snapshot();
+    let base_plpmtu = controller.base_plpmtu;
+
+    controller.enable();
+    controller.plpmtu = 1400;
+
+    assert_eq!(
+        MtuResult::NoChange,
+        controller.on_packet_too_big(
+            base_plpmtu - 1,
+            now(),
+            &mut cc,
+            path::Id::test_id(),
+            &mut publisher
+        )
+    );
+    assert_eq!(1400, controller.plpmtu);
+    assert_eq!(State::SearchRequested, controller.state);
+    assert_eq!(0, cc.on_mtu_update);
+}
+
+#[test]
+fn on_packet_too_big_above_max_probe_size() {
+    let mut controller = new_controller(1500);
+    let mut cc = CongestionController::default();
+    let mut publisher = Publisher::no_snapshot();
+    let max_udp_payload = controller.max_udp_payload;
+
+    controller.enable();
+    let probed_size = controller.probed_size;
+
+    assert_eq!(
+        MtuResult::NoChange,
+        controller.on_packet_too_big(
+            max_udp_payload,
+            now(),
+            &mut cc,
+            path::Id::test_id(),
+            &mut publisher
+        )
+    );
+    assert_eq!(max_udp_payload, controller.max_probe_size);
+    assert_eq!(probed_size, controller.probed_size);
+    assert_eq!(State::SearchRequested, controller.state);
+}
+
+#[test]
+fn on_packet_too_big_limits_search() {
+    let mut controller = new_controller(1500);
+    let mut cc = CongestionController::default();
+    let mut publisher = Publisher::no_snapshot();
+    let now = now();
+
+    controller.enable();
+    controller.state = State::Searching(pn(1), now);
+    controller.probe_count = 2;
+    let plpmtu = controller.plpmtu;
+
+    // The search continues below the reported size
+    assert_eq!(
+        MtuResult::NoChange,
+        controller.on_packet_too_big(1400, now, &mut cc, path::Id::test_id(), &mut publisher)
+    );
+    assert_eq!(plpmtu, controller.plpmtu);
+    assert_eq!(1400, controller.max_probe_size);
+    assert_eq!(plpmtu + (1400 - plpmtu) / 2, controller.probed_size);
+    assert_eq!(State::SearchRequested, controller.state);
+    assert_eq!(0, controller.probe_count);
+    assert_eq!(0, cc.on_mtu_update);
+
+    // The search completes when the reported size is within the probe threshold
+    assert_eq!(
+        MtuResult::NoChange,
+        controller.on_packet_too_big(
+            plpmtu + PROBE_THRESHOLD,
+            now,
+            &mut cc,
+            path::Id::test_id(),
+            &mut publisher
+        )
+    );
+    assert_eq!(plpmtu, controller.plpmtu);
+    assert_eq!(State::SearchComplete, controller.state);
+    assert!(controller.pmtu_raise_timer.is_armed());
+    assert_eq!(0, cc.on_mtu_update);
+}
+
+#[test]
+fn on_packet_too_big_lowers_mtu() {
+    let mut controller = new_controller(1500);
+    let mut cc = CongestionController::default();
+    let mut publisher = Publisher::no_snapshot();
+    let max_udp_payload = controller.max_udp_payload;
+    let now = now();
+
+    controller.enable();
+    controller.plpmtu = 1400;
+    controller.state = State::Searching(pn(1), now);
+    controller.black_hole_counter += 2;
+    controller.largest_acked_mtu_sized_packet = Some(pn(1));
+
+    assert_eq!(
+        MtuResult::MtuUpdated(1300),
+        controller.on_packet_too_big(1300, now, &mut cc, path::Id::test_id(), &mut publisher)
+    );
+    assert_eq!(1300, controller.plpmtu);
+    assert_eq!(1, cc.on_mtu_update);
+    assert_eq!(State::SearchComplete, controller.state);
+    assert_eq!(controller.black_hole_counter, 0);
+    assert_eq!(None, controller.largest_acked_mtu_sized_packet);
+    assert!(!controller.is_cooling_off);
+    assert_eq!(max_udp_payload, controller.max_probe_size);
+    assert_eq!(
+        Some(now + PMTU_RAISE_TIMER_DURATION),
+        controller.pmtu_raise_timer.next_expiration()
+    );
+}
+
+#[test]
+fn on_packet_too_big_before_enabled() {
+    let mut controller = new_controller(1500);
+    let mut cc = CongestionController::default();
+    let mut publisher = Publisher::no_snapshot();
+    let now = now();
+
+    controller.plpmtu = 1400;
+
+    assert_eq!(
+        MtuResult::MtuUpdated(1300),
+        controller.on_packet_too_big(1300, now, &mut cc, path::Id::test_id(), &mut publisher)
+    );
+    assert_eq!(1300, controller.plpmtu);
+    assert_eq!(1, cc.on_mtu_update);
+    assert_eq!(State::Disabled, controller.state);
+    assert!(!controller.pmtu_raise_timer.is_armed());
+
+    // Probing starts from the reported size once enabled
+    controller.enable();
+    assert_eq!(State::SearchRequested, controller.state);
+    assert!(controller.probed_size > 1300);
+}
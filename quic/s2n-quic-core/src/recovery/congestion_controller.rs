@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    application::ServerName,
     event,
     event::{
         api::SocketAddress,
@@ -21,17 +22,39 @@ use core::fmt::Debug;
 use num_rational::Ratio;
 use num_traits::ToPrimitive;
 
+#[cfg(any(test, feature = "testing"))]
+pub mod conformance;
+pub mod registry;
+
 pub trait Endpoint: 'static + Debug + Send {
     type CongestionController: CongestionController;
 
     fn new_congestion_controller(&mut self, path_info: PathInfo) -> Self::CongestionController;
+
+    /// Called for each path of a connection once its handshake completes
+    ///
+    /// Congestion controllers are created before the handshake, so the `path_info` passed to
+    /// [`Self::new_congestion_controller`] doesn't include the negotiated application protocol
+    /// and server name. This allows the endpoint to update the `congestion_controller` of the
+    /// path based on them.
+    #[inline]
+    fn on_handshake_complete(
+        &mut self,
+        path_info: PathInfo,
+        congestion_controller: &mut Self::CongestionController,
+    ) {
+        let _ = (path_info, congestion_controller);
+    }
 }
 
 #[derive(Debug)]
 #[non_exhaustive]
 pub struct PathInfo<'a> {
     pub remote_address: SocketAddress<'a>,
+    /// The negotiated application protocol, which is only known once the handshake completes
     pub application_protocol: Option<&'a [u8]>,
+    /// The negotiated server name, which is only known once the handshake completes
+    pub server_name: Option<&'a ServerName>,
     pub max_datagram_size: u16,
 }
 
@@ -41,9 +64,22 @@ impl<'a> PathInfo<'a> {
         Self {
             remote_address: remote_address.into_event(),
             application_protocol: None,
+            server_name: None,
             max_datagram_size: mtu_config.initial_mtu().max_datagram_size(remote_address),
         }
     }
+
+    /// Sets the application protocol and server name negotiated in the handshake
+    #[inline]
+    pub fn with_handshake(
+        mut self,
+        application_protocol: &'a [u8],
+        server_name: Option<&'a ServerName>,
+    ) -> Self {
+        self.application_protocol = Some(application_protocol);
+        self.server_name = server_name;
+        self
+    }
 }
 
 pub trait Publisher {
@@ -126,9 +162,29 @@ impl<Pub: event::ConnectionPublisher> Publisher for PathPublisher<'_, Pub> {
 
 /// An algorithm for controlling congestion.
 ///
-/// NOTE: This trait is considered unstable and can only be implemented by
-///       including the `unstable-congestion-controller` feature.
-pub trait CongestionController: 'static + Clone + Send + Debug + private::Sealed {
+/// A congestion controller is created for each path by an [`Endpoint`] and is driven by the
+/// recovery manager as packets are sent, acknowledged, declared lost or discarded. The
+/// [`RttEstimator`] passed to each method is a read-only view of the path's round trip time
+/// samples. Controllers that need delivery rate samples can track them with the
+/// [`bandwidth::Estimator`](crate::recovery::bandwidth::Estimator), which produces a
+/// [`RateSample`] for each acknowledgement, and can pace transmissions with the
+/// [`Pacer`](crate::recovery::pacing::Pacer).
+///
+/// # Contract
+///
+/// The recovery manager relies on the following invariants, which can be checked with the
+/// `conformance` harness included with the `testing` feature:
+///
+/// * `bytes_in_flight` is the sum of the bytes passed to `on_packet_sent` that have not yet been
+///   passed to `on_ack`, `on_packet_lost` or `on_packet_discarded`.
+/// * `congestion_window` is never smaller than twice the most recent max datagram size.
+/// * `is_congestion_limited` returns `true` if and only if the space left in the congestion
+///   window is smaller than the max datagram size.
+/// * `requires_fast_retransmission` returns `false` once a congestion controlled packet has been
+///   sent, so at most one packet is sent without respecting the congestion window.
+///
+/// Implementations must not panic for any sequence of calls made by the recovery manager.
+pub trait CongestionController: 'static + Clone + Send + Debug {
     /// Additional metadata about a packet to track until a sent packet
     /// is either acknowledged or declared lost
     type PacketInfo: Copy + Send + Sized + Debug;
@@ -243,26 +299,17 @@ pub trait CongestionController: 'static + Clone + Send + Debug + private::Sealed
     fn send_quantum(&self) -> Option<usize> {
         None
     }
-}
 
-// Prevent implementation of the `CongestionController` trait if the
-// `unstable-congestion-controller` feature is not turned on.
-mod private {
-    use cfg_if::cfg_if;
-
-    pub trait Sealed {}
-
-    cfg_if!(
-        if #[cfg(any(test, feature = "unstable-congestion-controller", feature = "testing"))] {
-            // If `unstable-congestion-controller` is enabled, implement Sealed for any type that
-            // otherwise implements `CongestionController`
-            impl<T: crate::recovery::CongestionController> Sealed for T {}
-        } else {
-            // Otherwise only allow the included CUBIC and BBRv2 congestion controllers
-            impl Sealed for crate::recovery::CubicCongestionController {}
-            impl Sealed for crate::recovery::bbr::BbrCongestionController {}
-        }
-    );
+    /// Returns `true` if the congestion controller responds to ECN-CE marks in proportion
+    /// to the extent of congestion, as required for Low Latency, Low Loss, and Scalable
+    /// throughput (L4S) traffic.
+    ///
+    /// Packets on paths using an L4S capable congestion controller are marked with the
+    /// ECT(1) codepoint rather than ECT(0).
+    /// See <https://www.rfc-editor.org/rfc/rfc9331>
+    fn is_l4s_capable(&self) -> bool {
+        false
+    }
 }
 
 #[cfg(any(test, feature = "testing"))]
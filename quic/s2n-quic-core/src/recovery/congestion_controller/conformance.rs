@@ -0,0 +1,338 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A test harness for checking that a [`CongestionController`] implementation upholds the
+//! contract the rest of s2n-quic relies on.
+//!
+//! The harness drives the congestion controller through a sequence of [`Operation`]s in the
+//! same way the recovery manager would, and asserts the invariants documented on
+//! [`CongestionController`] after each operation. The operations can either be written by hand
+//! or generated, for example with [bolero](https://crates.io/crates/bolero):
+//!
+//! ```ignore
+//! use s2n_quic_core::recovery::congestion_controller::conformance;
+//!
+//! bolero::check!()
+//!     .with_type::<(u8, Vec<conformance::Operation>)>()
+//!     .for_each(|(seed, operations)| {
+//!         let congestion_controller = MyCongestionController::new(1200);
+//!         conformance::check(congestion_controller, 1200, *seed, operations);
+//!     });
+//! ```
+
+use crate::{
+    event,
+    packet::number::PacketNumberSpace,
+    path,
+    path::MINIMUM_MAX_DATAGRAM_SIZE,
+    random,
+    recovery::{
+        congestion_controller::{CongestionController, PathPublisher},
+        RttEstimator,
+    },
+    time::{testing::Clock, Clock as _, Timestamp},
+};
+use alloc::collections::VecDeque;
+use bolero_generator::*;
+use core::time::Duration;
+
+/// An operation that is applied to the congestion controller under test
+#[derive(Clone, Debug, TypeGenerator)]
+pub enum Operation {
+    /// Advances the current time
+    IncrementTime {
+        /// The milli-second value by which to increase the timestamp
+        millis: u16,
+    },
+    /// Sends `count` packets of `bytes_sent` bytes, stopping if the congestion window is full
+    PacketSent {
+        #[generator(1..=255)]
+        count: u8,
+        #[generator(0..=9000)]
+        bytes_sent: u16,
+        app_limited: Option<bool>,
+    },
+    /// Acknowledges `count` packets starting with the in flight packet at `index`
+    AckReceived {
+        index: u8,
+        #[generator(1..=255)]
+        count: u8,
+        #[generator(1..=2000)]
+        rtt: u16,
+    },
+    /// Declares the in flight packet at `index` lost
+    PacketLost { index: u8 },
+    /// Reports an increase in the ECN-CE count
+    ExplicitCongestion {
+        #[generator(1..=255)]
+        ce_count: u64,
+    },
+    /// Updates the maximum datagram size
+    MtuUpdated {
+        #[generator(1200..=9000)]
+        mtu: u16,
+    },
+    /// Discards the oldest in flight packet
+    PacketDiscarded,
+}
+
+/// Applies the `operations` to the `congestion_controller`, checking the invariants
+/// after each operation
+///
+/// `max_datagram_size` must be the max datagram size the congestion controller was
+/// created with.
+pub fn check<CC: CongestionController>(
+    congestion_controller: CC,
+    max_datagram_size: u16,
+    seed: u8,
+    operations: &[Operation],
+) {
+    let mut harness = Harness::new(congestion_controller, max_datagram_size);
+    let mut rng = random::testing::Generator(seed);
+
+    harness.invariants();
+
+    for operation in operations {
+        harness.apply(operation, &mut rng);
+        harness.invariants();
+    }
+}
+
+struct SentPacketInfo<PacketInfo> {
+    sent_bytes: u16,
+    time_sent: Timestamp,
+    cc_packet_info: PacketInfo,
+}
+
+/// Drives a congestion controller in the same way as the recovery manager
+pub struct Harness<CC: CongestionController> {
+    /// The congestion controller under test
+    subject: CC,
+    /// Tracks packets inflight
+    sent_packets: VecDeque<SentPacketInfo<CC::PacketInfo>>,
+    /// The round trip time estimator
+    rtt_estimator: RttEstimator,
+    /// A monotonically increasing timestamp
+    timestamp: Timestamp,
+    /// The current max datagram size of the path
+    max_datagram_size: u16,
+}
+
+impl<CC: CongestionController> Harness<CC> {
+    /// Creates a new harness around the given `congestion_controller`
+    pub fn new(congestion_controller: CC, max_datagram_size: u16) -> Self {
+        assert!(max_datagram_size >= MINIMUM_MAX_DATAGRAM_SIZE);
+
+        Self {
+            subject: congestion_controller,
+            sent_packets: VecDeque::new(),
+            rtt_estimator: RttEstimator::default(),
+            timestamp: Clock::default().get_time(),
+            max_datagram_size,
+        }
+    }
+
+    /// Returns the congestion controller under test
+    pub fn subject(&self) -> &CC {
+        &self.subject
+    }
+
+    /// Returns a mutable reference to the congestion controller under test
+    pub fn subject_mut(&mut self) -> &mut CC {
+        &mut self.subject
+    }
+
+    /// Applies the given `operation` to the congestion controller
+    pub fn apply(&mut self, operation: &Operation, rng: &mut dyn random::Generator) {
+        match operation {
+            Operation::IncrementTime { millis } => {
+                self.timestamp += Duration::from_millis(*millis as u64);
+            }
+            Operation::PacketSent {
+                count,
+                bytes_sent,
+                app_limited,
+            } => {
+                self.on_packet_sent(*count, *bytes_sent, *app_limited);
+            }
+            Operation::AckReceived { index, count, rtt } => {
+                self.on_ack_received(*index, *count, Duration::from_millis(*rtt as u64), rng);
+            }
+            Operation::PacketLost { index } => {
+                self.on_packet_lost(*index, rng);
+            }
+            Operation::ExplicitCongestion { ce_count } => self.on_explicit_congestion(*ce_count),
+            Operation::MtuUpdated { mtu } => self.on_mtu_updated(*mtu),
+            Operation::PacketDiscarded => self.on_packet_discarded(),
+        }
+    }
+
+    /// Asserts the invariants the rest of s2n-quic relies on
+    pub fn invariants(&self) {
+        let bytes_in_flight: u32 = self
+            .sent_packets
+            .iter()
+            .map(|sent_packet_info| sent_packet_info.sent_bytes as u32)
+            .sum();
+
+        assert_eq!(
+            bytes_in_flight,
+            self.subject.bytes_in_flight(),
+            "bytes in flight must equal the sum of sent bytes that have not been \
+             acknowledged, declared lost or discarded"
+        );
+
+        let congestion_window = self.subject.congestion_window();
+        let minimum_window = 2 * self.max_datagram_size as u32;
+        assert!(
+            congestion_window >= minimum_window,
+            "congestion window {congestion_window} must not be smaller than the \
+             minimum window {minimum_window}"
+        );
+
+        let available_congestion_window = congestion_window.saturating_sub(bytes_in_flight);
+        assert_eq!(
+            available_congestion_window < self.max_datagram_size as u32,
+            self.subject.is_congestion_limited(),
+            "congestion limited must indicate there is not enough space for a packet \
+             of max datagram size"
+        );
+    }
+
+    fn on_packet_sent(&mut self, count: u8, bytes_sent: u16, app_limited: Option<bool>) {
+        let mut publisher = event::testing::Publisher::no_snapshot();
+        let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+
+        for _ in 0..count {
+            if !self.subject.is_congestion_limited()
+                || self.subject.requires_fast_retransmission()
+                || bytes_sent == 0
+            {
+                let packet_info = self.subject.on_packet_sent(
+                    self.timestamp,
+                    bytes_sent as usize,
+                    app_limited,
+                    &self.rtt_estimator,
+                    &mut publisher,
+                );
+
+                if bytes_sent > 0 {
+                    assert!(
+                        !self.subject.requires_fast_retransmission(),
+                        "fast retransmission must only be required until a packet is sent"
+                    );
+                }
+
+                self.sent_packets.push_back(SentPacketInfo {
+                    sent_bytes: bytes_sent,
+                    time_sent: self.timestamp,
+                    cc_packet_info: packet_info,
+                });
+            }
+        }
+    }
+
+    fn on_ack_received(
+        &mut self,
+        index: u8,
+        count: u8,
+        rtt: Duration,
+        rng: &mut dyn random::Generator,
+    ) {
+        let mut publisher = event::testing::Publisher::no_snapshot();
+        let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+        let index = (index as usize).min(self.sent_packets.len().saturating_sub(1));
+        let mut rtt_updated = false;
+
+        // Acknowledge `count` amount of packets, starting at the random `index`
+        for _ in 0..count {
+            if let Some(sent_packet_info) = self.sent_packets.remove(index) {
+                if sent_packet_info.sent_bytes > 0 {
+                    // Update the RTT once for each ack range received
+                    if !rtt_updated {
+                        self.on_rtt_updated(sent_packet_info.time_sent, rtt);
+                        rtt_updated = true;
+                    }
+
+                    // `recovery::Manager` does not call `on_ack` if sent_bytes = 0
+                    self.subject.on_ack(
+                        sent_packet_info.time_sent,
+                        sent_packet_info.sent_bytes as usize,
+                        sent_packet_info.cc_packet_info,
+                        &self.rtt_estimator,
+                        rng,
+                        self.timestamp,
+                        &mut publisher,
+                    );
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn on_packet_lost(&mut self, index: u8, rng: &mut dyn random::Generator) {
+        let mut publisher = event::testing::Publisher::no_snapshot();
+        let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+        let index = (index as usize).min(self.sent_packets.len().saturating_sub(1));
+
+        // Report the packet at the random `index` as lost
+        if let Some(sent_packet_info) = self.sent_packets.remove(index) {
+            if sent_packet_info.sent_bytes > 0 {
+                // `recovery::Manager` does not call `on_packet_lost` if sent_bytes = 0
+                self.subject.on_packet_lost(
+                    sent_packet_info.sent_bytes as u32,
+                    sent_packet_info.cc_packet_info,
+                    false,
+                    false,
+                    rng,
+                    self.timestamp,
+                    &mut publisher,
+                );
+            }
+        }
+    }
+
+    fn on_rtt_updated(&mut self, time_sent: Timestamp, rtt: Duration) {
+        let mut publisher = event::testing::Publisher::no_snapshot();
+        let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+
+        self.rtt_estimator.update_rtt(
+            Duration::ZERO,
+            rtt,
+            self.timestamp,
+            false,
+            PacketNumberSpace::Initial,
+        );
+        self.subject.on_rtt_update(
+            time_sent,
+            self.timestamp,
+            &self.rtt_estimator,
+            &mut publisher,
+        );
+    }
+
+    fn on_explicit_congestion(&mut self, ce_count: u64) {
+        let mut publisher = event::testing::Publisher::no_snapshot();
+        let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+        let ce_count = ce_count.min(self.sent_packets.len() as u64);
+        self.subject
+            .on_explicit_congestion(ce_count, self.timestamp, &mut publisher)
+    }
+
+    fn on_packet_discarded(&mut self) {
+        let mut publisher = event::testing::Publisher::no_snapshot();
+        let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+        if let Some(sent_packet_info) = self.sent_packets.pop_front() {
+            self.subject
+                .on_packet_discarded(sent_packet_info.sent_bytes as usize, &mut publisher)
+        }
+    }
+
+    fn on_mtu_updated(&mut self, mtu: u16) {
+        let mut publisher = event::testing::Publisher::no_snapshot();
+        let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+        self.max_datagram_size = mtu;
+        self.subject.on_mtu_update(mtu, &mut publisher)
+    }
+}
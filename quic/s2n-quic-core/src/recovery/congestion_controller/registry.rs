@@ -0,0 +1,107 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A registry of the included congestion controllers, allowing a congestion controller
+//! to be selected by name at runtime.
+//!
+//! Each congestion controller endpoint is a distinct type, so rather than returning the
+//! endpoint directly, the selected endpoint is passed to a [`Visitor`].
+//!
+//! The registry is also the source of the names of the [`Algorithm`]s that can be selected
+//! for a [`dynamic`](crate::recovery::dynamic) congestion controller.
+
+use super::Endpoint;
+use crate::recovery::{
+    bbr, cubic,
+    dynamic::{Algorithm, UnknownAlgorithm},
+    new_reno, prague,
+};
+use core::str::FromStr;
+
+/// Receives the congestion controller endpoint selected from the registry
+pub trait Visitor {
+    type Output;
+
+    fn visit<E: Endpoint>(self, endpoint: E) -> Self::Output;
+}
+
+macro_rules! registry {
+    ($($name:literal => $algorithm:ident($endpoint:ty)),* $(,)?) => {
+        /// The names of all of the registered congestion controllers
+        pub const NAMES: &[&str] = &[$($name),*];
+
+        /// Calls the `visitor` with the default endpoint of the congestion controller
+        /// registered with the given `name`
+        ///
+        /// Returns `None` if there is no congestion controller registered with the `name`.
+        pub fn visit<V: Visitor>(name: &str, visitor: V) -> Option<V::Output> {
+            match name {
+                $(
+                    $name => Some(visitor.visit(<$endpoint>::default())),
+                )*
+                _ => None,
+            }
+        }
+
+        impl Algorithm {
+            /// Returns the name of the algorithm, as used by the congestion controller registry
+            #[inline]
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(
+                        Self::$algorithm => $name,
+                    )*
+                }
+            }
+        }
+
+        impl FromStr for Algorithm {
+            type Err = UnknownAlgorithm;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $(
+                        $name => Ok(Self::$algorithm),
+                    )*
+                    _ => Err(UnknownAlgorithm),
+                }
+            }
+        }
+    };
+}
+
+registry!(
+    "bbr" => Bbr(bbr::Endpoint),
+    "cubic" => Cubic(cubic::Endpoint),
+    "new_reno" => NewReno(new_reno::Endpoint),
+    "prague" => Prague(prague::Endpoint),
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::any::type_name;
+
+    struct TypeName;
+
+    impl Visitor for TypeName {
+        type Output = &'static str;
+
+        fn visit<E: Endpoint>(self, _endpoint: E) -> Self::Output {
+            type_name::<E::CongestionController>()
+        }
+    }
+
+    #[test]
+    fn visit_test() {
+        for name in NAMES {
+            assert!(visit(name, TypeName).is_some(), "{name} is not registered");
+        }
+
+        assert_eq!(
+            Some(type_name::<new_reno::NewRenoCongestionController>()),
+            visit("new_reno", TypeName)
+        );
+        assert_eq!(None, visit("reno", TypeName));
+    }
+}
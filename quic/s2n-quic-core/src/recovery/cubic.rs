@@ -884,7 +884,7 @@ impl Cubic {
 #[non_exhaustive]
 #[derive(Debug, Default)]
 pub struct Endpoint {
-    app_settings: ApplicationSettings,
+    pub(super) app_settings: ApplicationSettings,
 }
 
 impl congestion_controller::Endpoint for Endpoint {
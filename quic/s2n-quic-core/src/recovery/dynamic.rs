@@ -0,0 +1,630 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A congestion controller that dispatches to one of the included congestion control
+//! algorithms, which can be selected for each path and switched on a live connection.
+
+use crate::{
+    random,
+    recovery::{
+        bandwidth,
+        bbr::{self, BbrCongestionController},
+        congestion_controller::{self, CongestionController, PathInfo, Publisher},
+        cubic::{self, CubicCongestionController},
+        new_reno::{self, NewRenoCongestionController},
+        prague::{self, PragueCongestionController},
+        RttEstimator,
+    },
+    time::Timestamp,
+};
+use core::fmt;
+
+#[cfg(any(test, feature = "generator"))]
+use bolero_generator::prelude::*;
+
+/// The congestion control algorithms that can be selected for a [`DynamicCongestionController`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(any(test, feature = "generator"), derive(TypeGenerator))]
+pub enum Algorithm {
+    Bbr,
+    #[default]
+    Cubic,
+    NewReno,
+    Prague,
+}
+
+// The names of the algorithms are defined by the congestion controller registry, which
+// implements `Algorithm::as_str` and `FromStr` for `Algorithm`
+impl Algorithm {
+    /// Returns `true` if the algorithm provides a scalable response to ECN-CE marks, so
+    /// packets are marked with the L4S ECT(1) codepoint
+    #[inline]
+    pub fn is_l4s_capable(&self) -> bool {
+        matches!(self, Self::Prague)
+    }
+}
+
+impl fmt::Display for Algorithm {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The error returned when parsing an unknown [`Algorithm`] name
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct UnknownAlgorithm;
+
+impl fmt::Display for UnknownAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown congestion control algorithm")
+    }
+}
+
+/// Selects the congestion control algorithm used for a path
+///
+/// The selector is called each time a path is created, which allows the algorithm to be chosen
+/// based on the peer address. Paths are created before the handshake, so the selector is called
+/// again for each path once the handshake completes, with the negotiated application protocol
+/// and server name in the [`PathInfo`]. If the selected algorithm changed, the path switches to
+/// it as if [`DynamicCongestionController::switch_to`] was called.
+///
+/// An [`Algorithm`] can be used as a selector to use the same algorithm for every path, and
+/// closures accepting a [`PathInfo`] are also selectors.
+pub trait Selector: 'static + Send {
+    fn select(&mut self, path_info: &PathInfo) -> Algorithm;
+}
+
+impl Selector for Algorithm {
+    #[inline]
+    fn select(&mut self, _path_info: &PathInfo) -> Algorithm {
+        *self
+    }
+}
+
+impl<F> Selector for F
+where
+    F: 'static + Send + FnMut(&PathInfo) -> Algorithm,
+{
+    #[inline]
+    fn select(&mut self, path_info: &PathInfo) -> Algorithm {
+        (self)(path_info)
+    }
+}
+
+/// The application provided overrides for each of the algorithms
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ApplicationSettings {
+    bbr: bbr::ApplicationSettings,
+    cubic: cubic::ApplicationSettings,
+    new_reno: new_reno::ApplicationSettings,
+    prague: prague::ApplicationSettings,
+}
+
+// The controllers are stored inline rather than boxed to avoid an indirection for each call
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug)]
+enum Controller {
+    Bbr(BbrCongestionController),
+    Cubic(CubicCongestionController),
+    NewReno(NewRenoCongestionController),
+    Prague(PragueCongestionController),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Info {
+    Bbr(bandwidth::PacketInfo),
+    Cubic,
+    NewReno,
+    Prague,
+}
+
+impl Info {
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        match self {
+            Self::Bbr(_) => Algorithm::Bbr,
+            Self::Cubic => Algorithm::Cubic,
+            Self::NewReno => Algorithm::NewReno,
+            Self::Prague => Algorithm::Prague,
+        }
+    }
+}
+
+/// Dispatches a method call to the congestion controller of the selected algorithm
+macro_rules! dispatch {
+    ($controller:expr, |$cc:ident| $call:expr) => {
+        match $controller {
+            Controller::Bbr($cc) => $call,
+            Controller::Cubic($cc) => $call,
+            Controller::NewReno($cc) => $call,
+            Controller::Prague($cc) => $call,
+        }
+    };
+}
+
+impl Controller {
+    #[inline]
+    fn new(algorithm: Algorithm, max_datagram_size: u16, settings: &ApplicationSettings) -> Self {
+        match algorithm {
+            Algorithm::Bbr => Self::Bbr(BbrCongestionController::new(
+                max_datagram_size,
+                settings.bbr,
+            )),
+            Algorithm::Cubic => Self::Cubic(CubicCongestionController::new(
+                max_datagram_size,
+                settings.cubic,
+            )),
+            Algorithm::NewReno => Self::NewReno(NewRenoCongestionController::new(
+                max_datagram_size,
+                settings.new_reno,
+            )),
+            Algorithm::Prague => Self::Prague(PragueCongestionController::new(
+                max_datagram_size,
+                settings.prague,
+            )),
+        }
+    }
+
+    #[inline]
+    fn algorithm(&self) -> Algorithm {
+        match self {
+            Self::Bbr(_) => Algorithm::Bbr,
+            Self::Cubic(_) => Algorithm::Cubic,
+            Self::NewReno(_) => Algorithm::NewReno,
+            Self::Prague(_) => Algorithm::Prague,
+        }
+    }
+
+    #[inline]
+    fn on_packet_sent<Pub: Publisher>(
+        &mut self,
+        time_sent: Timestamp,
+        sent_bytes: usize,
+        app_limited: Option<bool>,
+        rtt_estimator: &RttEstimator,
+        publisher: &mut Pub,
+    ) -> Info {
+        match self {
+            Self::Bbr(cc) => Info::Bbr(cc.on_packet_sent(
+                time_sent,
+                sent_bytes,
+                app_limited,
+                rtt_estimator,
+                publisher,
+            )),
+            Self::Cubic(cc) => {
+                cc.on_packet_sent(time_sent, sent_bytes, app_limited, rtt_estimator, publisher);
+                Info::Cubic
+            }
+            Self::NewReno(cc) => {
+                cc.on_packet_sent(time_sent, sent_bytes, app_limited, rtt_estimator, publisher);
+                Info::NewReno
+            }
+            Self::Prague(cc) => {
+                cc.on_packet_sent(time_sent, sent_bytes, app_limited, rtt_estimator, publisher);
+                Info::Prague
+            }
+        }
+    }
+}
+
+/// Metadata tracked for each packet sent by a [`DynamicCongestionController`]
+#[derive(Clone, Copy, Debug)]
+pub struct PacketInfo {
+    /// The number of algorithm switches made before the packet was sent
+    ///
+    /// The count wraps, so a packet from an earlier generation can have the same count as the
+    /// current generation. The [`Info`] is also checked against the current congestion controller
+    /// to make sure it is never passed to a congestion controller it wasn't created by.
+    generation: u16,
+    info: Info,
+}
+
+/// A congestion controller that dispatches to the congestion controller of the selected
+/// [`Algorithm`]
+///
+/// The algorithm can be changed on a live connection with [`Self::switch_to`], which takes
+/// effect when the next packet is sent. The new congestion controller starts in its initial
+/// state, with the bytes in flight of the previous congestion controller carried over. Packets
+/// sent before the switch are then acknowledged or declared lost against the new congestion
+/// controller.
+///
+/// A requested switch is reflected by [`CongestionController::is_l4s_capable`] immediately,
+/// so the path marks packets with the ECN codepoint of the new algorithm starting with the
+/// packet that applies the switch.
+#[derive(Clone, Debug)]
+pub struct DynamicCongestionController {
+    controller: Controller,
+    settings: ApplicationSettings,
+    max_datagram_size: u16,
+    /// The algorithm to switch to when the next packet is sent
+    pending: Option<Algorithm>,
+    /// The number of algorithm switches made, used to identify packets sent before a switch
+    generation: u16,
+    /// The packet info returned by the current congestion controller for the bytes in flight
+    /// carried over from the previous congestion controller
+    carried_over: Option<Info>,
+}
+
+impl DynamicCongestionController {
+    /// Constructs a new `DynamicCongestionController` using the given `algorithm`
+    pub fn new(
+        algorithm: Algorithm,
+        max_datagram_size: u16,
+        settings: ApplicationSettings,
+    ) -> Self {
+        Self {
+            controller: Controller::new(algorithm, max_datagram_size, &settings),
+            settings,
+            max_datagram_size,
+            pending: None,
+            generation: 0,
+            carried_over: None,
+        }
+    }
+
+    /// Returns the algorithm currently controlling congestion
+    ///
+    /// A switch requested with [`Self::switch_to`] is not reflected until the next packet is sent.
+    #[inline]
+    pub fn algorithm(&self) -> Algorithm {
+        self.controller.algorithm()
+    }
+
+    /// Switches to the given `algorithm` when the next packet is sent
+    ///
+    /// Switching to the algorithm that is currently in use has no effect.
+    #[inline]
+    pub fn switch_to(&mut self, algorithm: Algorithm) {
+        self.pending = Some(algorithm);
+    }
+
+    #[inline]
+    fn apply_pending_switch<Pub: Publisher>(
+        &mut self,
+        now: Timestamp,
+        rtt_estimator: &RttEstimator,
+        publisher: &mut Pub,
+    ) {
+        let Some(algorithm) = self.pending.take() else {
+            return;
+        };
+
+        if algorithm == self.algorithm() {
+            return;
+        }
+
+        let bytes_in_flight = self.controller.bytes_in_flight() as usize;
+        let mut controller = Controller::new(algorithm, self.max_datagram_size, &self.settings);
+        // Register the outstanding bytes with the new congestion controller as a single packet,
+        // so the bytes in flight remain accurate as the previously sent packets are acknowledged
+        // or declared lost
+        let carried_over =
+            controller.on_packet_sent(now, bytes_in_flight, None, rtt_estimator, publisher);
+
+        self.controller = controller;
+        self.generation = self.generation.wrapping_add(1);
+        self.carried_over = Some(carried_over);
+    }
+
+    /// Returns the packet info to pass to the current congestion controller for the given
+    /// `packet_info`
+    #[inline]
+    fn info(&self, packet_info: PacketInfo) -> Info {
+        if packet_info.generation == self.generation
+            && packet_info.info.algorithm() == self.algorithm()
+        {
+            packet_info.info
+        } else {
+            self.carried_over
+                .expect("packets from a previous generation are carried over")
+        }
+    }
+}
+
+impl CongestionController for DynamicCongestionController {
+    type PacketInfo = PacketInfo;
+
+    #[inline]
+    fn congestion_window(&self) -> u32 {
+        dispatch!(&self.controller, |cc| cc.congestion_window())
+    }
+
+    #[inline]
+    fn bytes_in_flight(&self) -> u32 {
+        dispatch!(&self.controller, |cc| cc.bytes_in_flight())
+    }
+
+    #[inline]
+    fn is_congestion_limited(&self) -> bool {
+        dispatch!(&self.controller, |cc| cc.is_congestion_limited())
+    }
+
+    #[inline]
+    fn requires_fast_retransmission(&self) -> bool {
+        dispatch!(&self.controller, |cc| cc.requires_fast_retransmission())
+    }
+
+    #[inline]
+    fn on_packet_sent<Pub: Publisher>(
+        &mut self,
+        time_sent: Timestamp,
+        sent_bytes: usize,
+        app_limited: Option<bool>,
+        rtt_estimator: &RttEstimator,
+        publisher: &mut Pub,
+    ) -> Self::PacketInfo {
+        self.apply_pending_switch(time_sent, rtt_estimator, publisher);
+
+        let info = self.controller.on_packet_sent(
+            time_sent,
+            sent_bytes,
+            app_limited,
+            rtt_estimator,
+            publisher,
+        );
+
+        PacketInfo {
+            generation: self.generation,
+            info,
+        }
+    }
+
+    #[inline]
+    fn on_rtt_update<Pub: Publisher>(
+        &mut self,
+        time_sent: Timestamp,
+        now: Timestamp,
+        rtt_estimator: &RttEstimator,
+        publisher: &mut Pub,
+    ) {
+        dispatch!(&mut self.controller, |cc| cc.on_rtt_update(
+            time_sent,
+            now,
+            rtt_estimator,
+            publisher
+        ))
+    }
+
+    #[inline]
+    fn on_ack<Pub: Publisher>(
+        &mut self,
+        newest_acked_time_sent: Timestamp,
+        bytes_acknowledged: usize,
+        newest_acked_packet_info: Self::PacketInfo,
+        rtt_estimator: &RttEstimator,
+        random_generator: &mut dyn random::Generator,
+        ack_receive_time: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        let info = self.info(newest_acked_packet_info);
+
+        match (&mut self.controller, info) {
+            (Controller::Bbr(cc), Info::Bbr(info)) => cc.on_ack(
+                newest_acked_time_sent,
+                bytes_acknowledged,
+                info,
+                rtt_estimator,
+                random_generator,
+                ack_receive_time,
+                publisher,
+            ),
+            (Controller::Cubic(cc), Info::Cubic) => cc.on_ack(
+                newest_acked_time_sent,
+                bytes_acknowledged,
+                (),
+                rtt_estimator,
+                random_generator,
+                ack_receive_time,
+                publisher,
+            ),
+            (Controller::NewReno(cc), Info::NewReno) => cc.on_ack(
+                newest_acked_time_sent,
+                bytes_acknowledged,
+                (),
+                rtt_estimator,
+                random_generator,
+                ack_receive_time,
+                publisher,
+            ),
+            (Controller::Prague(cc), Info::Prague) => cc.on_ack(
+                newest_acked_time_sent,
+                bytes_acknowledged,
+                (),
+                rtt_estimator,
+                random_generator,
+                ack_receive_time,
+                publisher,
+            ),
+            _ => unreachable!("packet info must match the current congestion controller"),
+        }
+    }
+
+    #[inline]
+    fn on_packet_lost<Pub: Publisher>(
+        &mut self,
+        lost_bytes: u32,
+        packet_info: Self::PacketInfo,
+        persistent_congestion: bool,
+        new_loss_burst: bool,
+        random_generator: &mut dyn random::Generator,
+        timestamp: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        let info = self.info(packet_info);
+
+        match (&mut self.controller, info) {
+            (Controller::Bbr(cc), Info::Bbr(info)) => cc.on_packet_lost(
+                lost_bytes,
+                info,
+                persistent_congestion,
+                new_loss_burst,
+                random_generator,
+                timestamp,
+                publisher,
+            ),
+            (Controller::Cubic(cc), Info::Cubic) => cc.on_packet_lost(
+                lost_bytes,
+                (),
+                persistent_congestion,
+                new_loss_burst,
+                random_generator,
+                timestamp,
+                publisher,
+            ),
+            (Controller::NewReno(cc), Info::NewReno) => cc.on_packet_lost(
+                lost_bytes,
+                (),
+                persistent_congestion,
+                new_loss_burst,
+                random_generator,
+                timestamp,
+                publisher,
+            ),
+            (Controller::Prague(cc), Info::Prague) => cc.on_packet_lost(
+                lost_bytes,
+                (),
+                persistent_congestion,
+                new_loss_burst,
+                random_generator,
+                timestamp,
+                publisher,
+            ),
+            _ => unreachable!("packet info must match the current congestion controller"),
+        }
+    }
+
+    #[inline]
+    fn on_explicit_congestion<Pub: Publisher>(
+        &mut self,
+        ce_count: u64,
+        event_time: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        dispatch!(&mut self.controller, |cc| cc
+            .on_explicit_congestion(ce_count, event_time, publisher))
+    }
+
+    #[inline]
+    fn on_mtu_update<Pub: Publisher>(&mut self, max_data_size: u16, publisher: &mut Pub) {
+        self.max_datagram_size = max_data_size;
+        dispatch!(&mut self.controller, |cc| cc
+            .on_mtu_update(max_data_size, publisher))
+    }
+
+    #[inline]
+    fn on_packet_discarded<Pub: Publisher>(&mut self, bytes_sent: usize, publisher: &mut Pub) {
+        dispatch!(&mut self.controller, |cc| cc
+            .on_packet_discarded(bytes_sent, publisher))
+    }
+
+    #[inline]
+    fn earliest_departure_time(&self) -> Option<Timestamp> {
+        dispatch!(&self.controller, |cc| cc.earliest_departure_time())
+    }
+
+    #[inline]
+    fn send_quantum(&self) -> Option<usize> {
+        dispatch!(&self.controller, |cc| cc.send_quantum())
+    }
+
+    #[inline]
+    fn is_l4s_capable(&self) -> bool {
+        if let Some(algorithm) = self.pending {
+            return algorithm.is_l4s_capable();
+        }
+
+        dispatch!(&self.controller, |cc| cc.is_l4s_capable())
+    }
+}
+
+/// A congestion controller endpoint that selects the [`Algorithm`] for each path with a
+/// [`Selector`]
+pub struct Endpoint<S = Algorithm> {
+    selector: S,
+    app_settings: ApplicationSettings,
+}
+
+impl Default for Endpoint {
+    fn default() -> Self {
+        builder::Builder::default().build(Algorithm::default())
+    }
+}
+
+impl<S> fmt::Debug for Endpoint<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Endpoint")
+            .field("app_settings", &self.app_settings)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Selector> congestion_controller::Endpoint for Endpoint<S> {
+    type CongestionController = DynamicCongestionController;
+
+    fn new_congestion_controller(&mut self, path_info: PathInfo) -> Self::CongestionController {
+        let algorithm = self.selector.select(&path_info);
+        DynamicCongestionController::new(algorithm, path_info.max_datagram_size, self.app_settings)
+    }
+
+    fn on_handshake_complete(
+        &mut self,
+        path_info: PathInfo,
+        congestion_controller: &mut Self::CongestionController,
+    ) {
+        let algorithm = self.selector.select(&path_info);
+        congestion_controller.switch_to(algorithm);
+    }
+}
+
+pub mod builder {
+    use super::{bbr, cubic, new_reno, prague, ApplicationSettings, Endpoint, Selector};
+
+    /// Build the congestion controller endpoint with application provided overrides
+    ///
+    /// The overrides of each algorithm are taken from an endpoint built with the builder of
+    /// that algorithm.
+    #[derive(Default)]
+    pub struct Builder {
+        app_settings: ApplicationSettings,
+    }
+
+    impl Builder {
+        /// Use the overrides of the given BBR endpoint
+        pub fn with_bbr(mut self, endpoint: bbr::Endpoint) -> Self {
+            self.app_settings.bbr = endpoint.app_settings;
+            self
+        }
+
+        /// Use the overrides of the given CUBIC endpoint
+        pub fn with_cubic(mut self, endpoint: cubic::Endpoint) -> Self {
+            self.app_settings.cubic = endpoint.app_settings;
+            self
+        }
+
+        /// Use the overrides of the given NewReno endpoint
+        pub fn with_new_reno(mut self, endpoint: new_reno::Endpoint) -> Self {
+            self.app_settings.new_reno = endpoint.app_settings;
+            self
+        }
+
+        /// Use the overrides of the given Prague endpoint
+        pub fn with_prague(mut self, endpoint: prague::Endpoint) -> Self {
+            self.app_settings.prague = endpoint.app_settings;
+            self
+        }
+
+        /// Builds the endpoint, using the `selector` to choose the algorithm for each path
+        pub fn build<S: Selector>(self, selector: S) -> Endpoint<S> {
+            Endpoint {
+                selector,
+                app_settings: self.app_settings,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,271 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    event, inet, path,
+    recovery::congestion_controller::{Endpoint as _, PathPublisher},
+    time::{Clock, NoopClock},
+};
+use core::time::Duration;
+
+const MAX_DATAGRAM_SIZE: u16 = 1200;
+
+fn new_controller(algorithm: Algorithm) -> DynamicCongestionController {
+    DynamicCongestionController::new(algorithm, MAX_DATAGRAM_SIZE, Default::default())
+}
+
+#[test]
+fn algorithm_name_test() {
+    for algorithm in [
+        Algorithm::Bbr,
+        Algorithm::Cubic,
+        Algorithm::NewReno,
+        Algorithm::Prague,
+    ] {
+        assert_eq!(Ok(algorithm), algorithm.as_str().parse());
+        assert!(
+            congestion_controller::registry::NAMES.contains(&algorithm.as_str()),
+            "{algorithm} is not registered"
+        );
+    }
+
+    assert_eq!(Err(UnknownAlgorithm), "reno".parse::<Algorithm>());
+}
+
+#[test]
+fn selector_test() {
+    // Use BBR for peers on port 443 and CUBIC for everyone else
+    let mut endpoint = builder::Builder::default().build(|path_info: &PathInfo| {
+        if path_info.remote_address.port() == 443 {
+            Algorithm::Bbr
+        } else {
+            Algorithm::Cubic
+        }
+    });
+    let mtu_config = path::Config::default();
+
+    let remote_address = inet::SocketAddressV4::new([127, 0, 0, 1], 443).into();
+    let path_info = PathInfo::new(&mtu_config, &remote_address);
+    let cc = endpoint.new_congestion_controller(path_info);
+    assert_eq!(Algorithm::Bbr, cc.algorithm());
+
+    let remote_address = inet::SocketAddressV4::new([127, 0, 0, 1], 4433).into();
+    let path_info = PathInfo::new(&mtu_config, &remote_address);
+    let cc = endpoint.new_congestion_controller(path_info);
+    assert_eq!(Algorithm::Cubic, cc.algorithm());
+
+    let remote_address = inet::SocketAddress::default();
+    let path_info = PathInfo::new(&mtu_config, &remote_address);
+    let cc = Endpoint::default().new_congestion_controller(path_info);
+    assert_eq!(Algorithm::Cubic, cc.algorithm());
+}
+
+#[test]
+fn handshake_selector_test() {
+    // Use BBR for h3 and CUBIC for everything else, including paths that haven't completed
+    // the handshake yet
+    let mut endpoint = builder::Builder::default().build(|path_info: &PathInfo| {
+        match path_info.application_protocol {
+            Some(b"h3") => Algorithm::Bbr,
+            _ => Algorithm::Cubic,
+        }
+    });
+    let mtu_config = path::Config::default();
+    let remote_address = inet::SocketAddress::default();
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+
+    let path_info = PathInfo::new(&mtu_config, &remote_address);
+    let mut cc = endpoint.new_congestion_controller(path_info);
+    assert_eq!(Algorithm::Cubic, cc.algorithm());
+
+    let server_name = "localhost".into();
+    let path_info =
+        PathInfo::new(&mtu_config, &remote_address).with_handshake(b"h3", Some(&server_name));
+    endpoint.on_handshake_complete(path_info, &mut cc);
+    // The switch is applied when the next packet is sent
+    assert!(cc.pending.is_some());
+    cc.on_packet_sent(now, 1000, None, &rtt_estimator, &mut publisher);
+    assert_eq!(Algorithm::Bbr, cc.algorithm());
+
+    let path_info = PathInfo::new(&mtu_config, &remote_address).with_handshake(b"hq-interop", None);
+    let mut cc = endpoint.new_congestion_controller(PathInfo::new(&mtu_config, &remote_address));
+    endpoint.on_handshake_complete(path_info, &mut cc);
+    cc.on_packet_sent(now, 1000, None, &rtt_estimator, &mut publisher);
+    assert_eq!(Algorithm::Cubic, cc.algorithm());
+    assert_eq!(0, cc.generation);
+}
+
+#[test]
+fn app_settings_test() {
+    let mut endpoint = builder::Builder::default()
+        .with_new_reno(
+            new_reno::builder::Builder::default()
+                .with_initial_congestion_window(50_000)
+                .build(),
+        )
+        .build(Algorithm::NewReno);
+    let remote_address = inet::SocketAddress::default();
+    let path_info = PathInfo::new(&path::Config::default(), &remote_address);
+    let mut cc = endpoint.new_congestion_controller(path_info);
+    assert_eq!(50_000, cc.congestion_window());
+
+    // The overrides are also applied to a controller that is switched to
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+
+    cc.switch_to(Algorithm::Cubic);
+    cc.on_packet_sent(now, 1000, None, &rtt_estimator, &mut publisher);
+    cc.switch_to(Algorithm::NewReno);
+    cc.on_packet_sent(now, 1000, None, &rtt_estimator, &mut publisher);
+    assert_eq!(Algorithm::NewReno, cc.algorithm());
+    assert_eq!(50_000, cc.congestion_window());
+}
+
+#[test]
+fn switch_test() {
+    let mut cc = new_controller(Algorithm::Cubic);
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let random = &mut random::testing::Generator::default();
+    let rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+
+    let cubic_info = cc.on_packet_sent(now, 1000, Some(false), &rtt_estimator, &mut publisher);
+    let lost_info = cc.on_packet_sent(now, 1000, Some(false), &rtt_estimator, &mut publisher);
+    assert_eq!(Algorithm::Cubic, cc.algorithm());
+    assert_eq!(2000, cc.bytes_in_flight());
+
+    // The switch isn't applied until the next packet is sent
+    cc.switch_to(Algorithm::Bbr);
+    assert_eq!(Algorithm::Cubic, cc.algorithm());
+
+    let now = now + Duration::from_millis(10);
+    let bbr_info = cc.on_packet_sent(now, 1000, Some(false), &rtt_estimator, &mut publisher);
+    assert_eq!(Algorithm::Bbr, cc.algorithm());
+    assert!(matches!(cc.controller, Controller::Bbr(_)));
+    // The bytes in flight are carried over to the new congestion controller
+    assert_eq!(3000, cc.bytes_in_flight());
+
+    // Packets sent before the switch are accounted against the new congestion controller
+    let now = now + Duration::from_millis(10);
+    cc.on_ack(
+        now,
+        1000,
+        cubic_info,
+        &rtt_estimator,
+        random,
+        now,
+        &mut publisher,
+    );
+    assert_eq!(2000, cc.bytes_in_flight());
+
+    cc.on_packet_lost(1000, lost_info, false, true, random, now, &mut publisher);
+    assert_eq!(1000, cc.bytes_in_flight());
+
+    cc.on_ack(
+        now,
+        1000,
+        bbr_info,
+        &rtt_estimator,
+        random,
+        now,
+        &mut publisher,
+    );
+    assert_eq!(0, cc.bytes_in_flight());
+
+    // Switching to the current algorithm has no effect
+    let generation = cc.generation;
+    cc.switch_to(Algorithm::Bbr);
+    cc.on_packet_sent(now, 1000, Some(false), &rtt_estimator, &mut publisher);
+    assert_eq!(generation, cc.generation);
+    assert_eq!(1000, cc.bytes_in_flight());
+}
+
+#[test]
+fn generation_wrap_test() {
+    let mut cc = new_controller(Algorithm::Cubic);
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let random = &mut random::testing::Generator::default();
+    let rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+
+    let cubic_info = cc.on_packet_sent(now, 1000, Some(false), &rtt_estimator, &mut publisher);
+
+    // Switch enough times for the generation to wrap back around to the same count
+    for algorithm in [Algorithm::Bbr, Algorithm::NewReno]
+        .into_iter()
+        .cycle()
+        .take(u16::MAX as usize + 1)
+    {
+        cc.switch_to(algorithm);
+        cc.on_packet_sent(now, 0, Some(false), &rtt_estimator, &mut publisher);
+    }
+    assert_eq!(cubic_info.generation, cc.generation);
+    assert_eq!(Algorithm::NewReno, cc.algorithm());
+    assert_eq!(1000, cc.bytes_in_flight());
+
+    // The packet is accounted against the carried over bytes rather than panicking
+    cc.on_ack(
+        now,
+        1000,
+        cubic_info,
+        &rtt_estimator,
+        random,
+        now,
+        &mut publisher,
+    );
+    assert_eq!(0, cc.bytes_in_flight());
+
+    let lost_info = cc.on_packet_sent(now, 1000, Some(false), &rtt_estimator, &mut publisher);
+    cc.on_packet_lost(1000, lost_info, false, true, random, now, &mut publisher);
+    assert_eq!(0, cc.bytes_in_flight());
+}
+
+#[test]
+fn mtu_update_test() {
+    let mut cc = new_controller(Algorithm::NewReno);
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+
+    cc.on_mtu_update(9000, &mut publisher);
+    cc.switch_to(Algorithm::Prague);
+    cc.on_packet_sent(now, 1000, Some(false), &rtt_estimator, &mut publisher);
+
+    // The new congestion controller uses the updated max datagram size
+    assert_eq!(
+        PragueCongestionController::new(9000, Default::default()).congestion_window(),
+        cc.congestion_window()
+    );
+    assert!(cc.is_l4s_capable());
+}
+
+#[test]
+fn l4s_switch_test() {
+    let mut cc = new_controller(Algorithm::Cubic);
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+    assert!(!cc.is_l4s_capable());
+
+    // A pending switch is reflected before the next packet is sent, so the packet that
+    // applies the switch is marked with the codepoint of the new algorithm
+    cc.switch_to(Algorithm::Prague);
+    assert!(cc.is_l4s_capable());
+    cc.on_packet_sent(now, 1000, Some(false), &rtt_estimator, &mut publisher);
+    assert!(cc.is_l4s_capable());
+
+    cc.switch_to(Algorithm::Bbr);
+    assert!(!cc.is_l4s_capable());
+    cc.on_packet_sent(now, 1000, Some(false), &rtt_estimator, &mut publisher);
+    assert!(!cc.is_l4s_capable());
+}
@@ -11,10 +11,13 @@ pub mod bandwidth;
 pub mod bbr;
 pub mod congestion_controller;
 pub mod cubic;
+pub mod dynamic;
 mod hybrid_slow_start;
 pub mod loss;
-mod pacing;
+pub mod new_reno;
+pub mod pacing;
 pub mod persistent_congestion;
+pub mod prague;
 mod pto;
 mod rtt_estimator;
 mod sent_packets;
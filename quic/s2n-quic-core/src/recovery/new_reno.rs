@@ -0,0 +1,434 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    counter::Counter,
+    event::builder::SlowStartExitCause,
+    random,
+    recovery::{
+        congestion_controller::{self, CongestionController, Publisher},
+        new_reno::{FastRetransmission::*, State::*},
+        pacing::Pacer,
+        RttEstimator,
+    },
+    time::Timestamp,
+};
+use core::cmp::{max, min};
+
+//= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.2
+//# On entering a recovery period, a sender MUST set the slow start
+//# threshold to half the value of the congestion window when loss is
+//# detected.
+const LOSS_REDUCTION_FACTOR: f32 = 0.5;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum State {
+    SlowStart,
+    /// The congestion window was reduced in response to congestion at the given time.
+    ///
+    /// Further congestion signals are not acted on until a packet sent after that time
+    /// is acknowledged, limiting the reduction to once per round trip.
+    Recovery(Timestamp, FastRetransmission),
+    CongestionAvoidance,
+}
+
+impl State {
+    /// Returns true if the state is `SlowStart`
+    fn is_slow_start(&self) -> bool {
+        matches!(self, SlowStart)
+    }
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.2
+//# If the congestion window is reduced immediately, a
+//# single packet can be sent prior to reduction.  This speeds up loss
+//# recovery if the data in the lost packet is retransmitted and is
+//# similar to TCP as described in Section 5 of [RFC6675].
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum FastRetransmission {
+    Idle,
+    RequiresTransmission,
+}
+
+/// A congestion controller that implements the NewReno congestion control algorithm
+/// described in <https://www.rfc-editor.org/rfc/rfc9002#section-7>.
+///
+/// This follows the example implementation in <https://www.rfc-editor.org/rfc/rfc9002#appendix-B>
+/// and is intended as a standards baseline to compare other congestion controllers against.
+#[derive(Clone, Debug)]
+pub struct NewRenoCongestionController {
+    pacer: Pacer,
+    max_datagram_size: u16,
+    congestion_window: f32,
+    slow_start_threshold: f32,
+    state: State,
+    bytes_in_flight: BytesInFlight,
+    under_utilized: bool,
+}
+
+type BytesInFlight = Counter<u32>;
+
+impl CongestionController for NewRenoCongestionController {
+    type PacketInfo = ();
+
+    #[inline]
+    fn congestion_window(&self) -> u32 {
+        self.congestion_window as u32
+    }
+
+    #[inline]
+    fn bytes_in_flight(&self) -> u32 {
+        *self.bytes_in_flight
+    }
+
+    #[inline]
+    fn is_congestion_limited(&self) -> bool {
+        let available_congestion_window = self
+            .congestion_window()
+            .saturating_sub(*self.bytes_in_flight);
+        available_congestion_window < self.max_datagram_size as u32
+    }
+
+    #[inline]
+    fn requires_fast_retransmission(&self) -> bool {
+        matches!(self.state, Recovery(_, RequiresTransmission))
+    }
+
+    #[inline]
+    fn on_packet_sent<Pub: Publisher>(
+        &mut self,
+        time_sent: Timestamp,
+        bytes_sent: usize,
+        app_limited: Option<bool>,
+        rtt_estimator: &RttEstimator,
+        publisher: &mut Pub,
+    ) {
+        if bytes_sent == 0 {
+            // Packet was not congestion controlled
+            return;
+        }
+
+        self.bytes_in_flight
+            .try_add(bytes_sent)
+            .expect("bytes sent should not exceed u32::MAX");
+
+        // The window is considered under utilized if the application did not have enough
+        // data to send and less than half of the window is in use. `app_limited` is not
+        // determined for Initial and Handshake packets so only the utilization is considered.
+        self.under_utilized =
+            app_limited != Some(false) && *self.bytes_in_flight < self.congestion_window() / 2;
+
+        if let Recovery(recovery_start_time, RequiresTransmission) = self.state {
+            // A packet has been sent since we entered recovery (fast retransmission)
+            // so flip the state back to idle.
+            self.state = Recovery(recovery_start_time, Idle);
+        }
+
+        self.pacer.on_packet_sent(
+            time_sent,
+            bytes_sent,
+            rtt_estimator,
+            self.congestion_window(),
+            self.max_datagram_size,
+            self.state.is_slow_start(),
+            publisher,
+        );
+    }
+
+    #[inline]
+    fn on_rtt_update<Pub: Publisher>(
+        &mut self,
+        _time_sent: Timestamp,
+        _now: Timestamp,
+        _rtt_estimator: &RttEstimator,
+        _publisher: &mut Pub,
+    ) {
+        // NewReno does not use the RTT to determine the congestion window
+    }
+
+    #[inline]
+    fn on_ack<Pub: Publisher>(
+        &mut self,
+        newest_acked_time_sent: Timestamp,
+        bytes_acknowledged: usize,
+        _newest_acked_packet_info: Self::PacketInfo,
+        _rtt_estimator: &RttEstimator,
+        _random_generator: &mut dyn random::Generator,
+        _ack_receive_time: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        self.bytes_in_flight
+            .try_sub(bytes_acknowledged)
+            .expect("bytes_acknowledged should not exceed u32::MAX");
+
+        // Check if this ack causes the controller to exit recovery
+        if let Recovery(recovery_start_time, _) = self.state {
+            if newest_acked_time_sent > recovery_start_time {
+                //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.2
+                //# A recovery period ends and the sender enters congestion avoidance
+                //# when a packet sent during the recovery period is acknowledged.
+                self.state = CongestionAvoidance;
+            }
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.8
+        //# When bytes in flight is smaller than the congestion window and
+        //# sending is not pacing limited, the congestion window is
+        //# underutilized.  This can happen due to insufficient application data
+        //# or flow control limits.  When this occurs, the congestion window
+        //# SHOULD NOT be increased in either slow start or congestion avoidance.
+        if self.under_utilized {
+            return;
+        }
+
+        match self.state {
+            SlowStart => {
+                //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.1
+                //# While a sender is in slow start, the congestion window increases by
+                //# the number of bytes acknowledged when each acknowledgment is
+                //# processed.  This results in exponential growth of the congestion
+                //# window.
+                self.congestion_window += bytes_acknowledged as f32;
+
+                //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.3
+                //# A NewReno sender is in congestion avoidance any time the congestion
+                //# window is at or above the slow start threshold and not in a recovery
+                //# period.
+                if self.congestion_window >= self.slow_start_threshold {
+                    self.state = CongestionAvoidance;
+                    publisher
+                        .on_slow_start_exited(SlowStartExitCause::Other, self.congestion_window());
+                }
+            }
+            Recovery(_, _) => {
+                // Don't increase the congestion window while in recovery
+            }
+            CongestionAvoidance => {
+                //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.3
+                //# A sender in congestion avoidance uses an Additive Increase
+                //# Multiplicative Decrease (AIMD) approach that MUST limit the increase
+                //# to the congestion window to at most one maximum datagram size for
+                //# each congestion window that is acknowledged.
+                self.congestion_window += self.max_datagram_size as f32 * bytes_acknowledged as f32
+                    / self.congestion_window;
+            }
+        }
+    }
+
+    #[inline]
+    fn on_packet_lost<Pub: Publisher>(
+        &mut self,
+        lost_bytes: u32,
+        _packet_info: Self::PacketInfo,
+        persistent_congestion: bool,
+        _new_loss_burst: bool,
+        _random_generator: &mut dyn random::Generator,
+        timestamp: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        debug_assert!(lost_bytes > 0);
+
+        self.bytes_in_flight -= lost_bytes;
+
+        if self.state.is_slow_start() && !persistent_congestion {
+            publisher
+                .on_slow_start_exited(SlowStartExitCause::PacketLoss, self.congestion_window());
+        }
+
+        self.on_congestion_event(timestamp, RequiresTransmission);
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.6.2
+        //# When persistent congestion is declared, the sender's congestion
+        //# window MUST be reduced to the minimum congestion window
+        //# (kMinimumWindow), similar to a TCP sender's response on an RTO
+        //# [RFC5681].
+        if persistent_congestion {
+            self.congestion_window = self.minimum_window();
+
+            //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.1
+            //# A sender reenters slow start any time the congestion window is less
+            //# than the slow start threshold, which only occurs after persistent
+            //# congestion is declared.
+            if self.congestion_window < self.slow_start_threshold {
+                self.state = SlowStart;
+            }
+        }
+    }
+
+    #[inline]
+    fn on_explicit_congestion<Pub: Publisher>(
+        &mut self,
+        _ce_count: u64,
+        event_time: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        if self.state.is_slow_start() {
+            publisher.on_slow_start_exited(SlowStartExitCause::Ecn, self.congestion_window());
+        }
+
+        self.on_congestion_event(event_time, Idle);
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-7.2
+    //# If the maximum datagram size is decreased in order to complete the
+    //# handshake, the congestion window SHOULD be set to the new initial
+    //# congestion window.
+    #[inline]
+    fn on_mtu_update<Pub: Publisher>(&mut self, max_datagram_size: u16, _publisher: &mut Pub) {
+        let old_max_datagram_size = self.max_datagram_size;
+        self.max_datagram_size = max_datagram_size;
+
+        let congestion_window =
+            (self.congestion_window / old_max_datagram_size as f32) * max_datagram_size as f32;
+        let initial_window = Self::initial_window(max_datagram_size, &Default::default());
+
+        self.congestion_window = max(congestion_window as u32, initial_window) as f32;
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-6.4
+    //# The sender MUST discard all recovery state
+    //# associated with those packets and MUST remove them from the count of
+    //# bytes in flight.
+    #[inline]
+    fn on_packet_discarded<Pub: Publisher>(&mut self, bytes_sent: usize, _publisher: &mut Pub) {
+        self.bytes_in_flight
+            .try_sub(bytes_sent)
+            .expect("bytes sent should not exceed u32::MAX");
+
+        if let Recovery(recovery_start_time, RequiresTransmission) = self.state {
+            // If any of the discarded packets were lost, they will no longer be retransmitted
+            // so flip the Recovery status back to Idle so it is not waiting for a
+            // retransmission that may never come.
+            self.state = Recovery(recovery_start_time, Idle);
+        }
+    }
+
+    #[inline]
+    fn earliest_departure_time(&self) -> Option<Timestamp> {
+        self.pacer.earliest_departure_time()
+    }
+}
+
+impl NewRenoCongestionController {
+    // max_datagram_size is the current max_datagram_size, and is
+    // expected to be 1200 when the congestion controller is created.
+    pub fn new(max_datagram_size: u16, app_settings: ApplicationSettings) -> Self {
+        Self {
+            pacer: Pacer::default(),
+            max_datagram_size,
+            congestion_window: Self::initial_window(max_datagram_size, &app_settings) as f32,
+            //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.1
+            //# A sender begins in slow start
+            //# because the slow start threshold is initialized to an infinite value.
+            slow_start_threshold: f32::INFINITY,
+            state: SlowStart,
+            bytes_in_flight: Counter::new(0),
+            under_utilized: true,
+        }
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-7.2
+    //# Endpoints SHOULD use an initial congestion
+    //# window of ten times the maximum datagram size (max_datagram_size),
+    //# while limiting the window to the larger of 14,720 bytes or twice the
+    //# maximum datagram size.
+    #[inline]
+    fn initial_window(max_datagram_size: u16, app_settings: &ApplicationSettings) -> u32 {
+        const INITIAL_WINDOW_LIMIT: u32 = 14720;
+        let default = min(
+            10 * max_datagram_size as u32,
+            max(INITIAL_WINDOW_LIMIT, 2 * max_datagram_size as u32),
+        );
+        let initial_window = app_settings.initial_congestion_window.unwrap_or(default);
+
+        max(initial_window, 2 * max_datagram_size as u32)
+    }
+
+    /// Enters a recovery period and reduces the congestion window, at most once per round trip
+    #[inline]
+    fn on_congestion_event(
+        &mut self,
+        event_time: Timestamp,
+        fast_retransmission: FastRetransmission,
+    ) {
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.2
+        //# A
+        //# sender that is already in a recovery period stays in it and does not
+        //# reenter it.
+        if matches!(self.state, Recovery(_, _)) {
+            return;
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.1
+        //# The sender MUST exit slow start and enter a recovery period when a
+        //# packet is lost or when the ECN-CE count reported by its peer
+        //# increases.
+        self.state = Recovery(event_time, fast_retransmission);
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.2
+        //# The congestion window MUST be set to the reduced value of
+        //# the slow start threshold before exiting the recovery period.
+        self.slow_start_threshold = self.congestion_window * LOSS_REDUCTION_FACTOR;
+        self.congestion_window = self.slow_start_threshold.max(self.minimum_window());
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-7.2
+    //# The minimum congestion window is the smallest value the congestion
+    //# window can attain in response to loss, an increase in the peer-
+    //# reported ECN-CE count, or persistent congestion.  The RECOMMENDED
+    //# value is 2 * max_datagram_size.
+    #[inline]
+    fn minimum_window(&self) -> f32 {
+        2.0 * self.max_datagram_size as f32
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ApplicationSettings {
+    initial_congestion_window: Option<u32>,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Default)]
+pub struct Endpoint {
+    pub(super) app_settings: ApplicationSettings,
+}
+
+impl congestion_controller::Endpoint for Endpoint {
+    type CongestionController = NewRenoCongestionController;
+
+    fn new_congestion_controller(
+        &mut self,
+        path_info: congestion_controller::PathInfo,
+    ) -> Self::CongestionController {
+        NewRenoCongestionController::new(path_info.max_datagram_size, self.app_settings)
+    }
+}
+
+pub mod builder {
+    use super::{ApplicationSettings, Endpoint};
+
+    /// Build the congestion controller endpoint with application provided overrides
+    #[derive(Default)]
+    pub struct Builder {
+        initial_congestion_window: Option<u32>,
+    }
+
+    impl Builder {
+        /// Set the initial congestion window in bytes.
+        pub fn with_initial_congestion_window(mut self, initial_congestion_window: u32) -> Self {
+            self.initial_congestion_window = Some(initial_congestion_window);
+            self
+        }
+
+        pub fn build(self) -> Endpoint {
+            let app_settings = ApplicationSettings {
+                initial_congestion_window: self.initial_congestion_window,
+            };
+            Endpoint { app_settings }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,241 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    assert_delta, event, path,
+    recovery::congestion_controller::PathPublisher,
+    time::{Clock, NoopClock},
+};
+use core::time::Duration;
+
+const MAX_DATAGRAM_SIZE: u16 = 1000;
+
+fn new_controller() -> NewRenoCongestionController {
+    NewRenoCongestionController::new(MAX_DATAGRAM_SIZE, Default::default())
+}
+
+#[test]
+fn initial_window() {
+    let cc = new_controller();
+    assert_eq!(10_000, cc.congestion_window());
+    assert!(cc.state.is_slow_start());
+    assert_eq!(f32::INFINITY, cc.slow_start_threshold);
+
+    let cc = NewRenoCongestionController::new(
+        MAX_DATAGRAM_SIZE,
+        ApplicationSettings {
+            initial_congestion_window: Some(50_000),
+        },
+    );
+    assert_eq!(50_000, cc.congestion_window());
+}
+
+#[test]
+fn on_packet_lost() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let random = &mut random::testing::Generator::default();
+    let now = NoopClock.get_time();
+
+    cc.congestion_window = 100_000.0;
+    cc.bytes_in_flight = BytesInFlight::new(10_000);
+
+    cc.on_packet_lost(1000, (), false, false, random, now, &mut publisher);
+
+    assert_eq!(9000, cc.bytes_in_flight());
+    assert_delta!(50_000.0, cc.slow_start_threshold, 0.001);
+    assert_delta!(50_000.0, cc.congestion_window, 0.001);
+    assert_eq!(Recovery(now, RequiresTransmission), cc.state);
+    assert!(cc.requires_fast_retransmission());
+
+    // Losses during the recovery period do not reduce the window further
+    cc.on_packet_lost(
+        1000,
+        (),
+        false,
+        false,
+        random,
+        now + Duration::from_millis(1),
+        &mut publisher,
+    );
+    assert_delta!(50_000.0, cc.congestion_window, 0.001);
+}
+
+#[test]
+fn on_packet_lost_persistent_congestion() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let random = &mut random::testing::Generator::default();
+    let now = NoopClock.get_time();
+
+    cc.congestion_window = 100_000.0;
+    cc.bytes_in_flight = BytesInFlight::new(10_000);
+
+    cc.on_packet_lost(1000, (), true, false, random, now, &mut publisher);
+
+    // The window is reset to the minimum and slow start is used until
+    // the window reaches the slow start threshold again
+    assert_delta!(cc.minimum_window(), cc.congestion_window, 0.001);
+    assert_delta!(50_000.0, cc.slow_start_threshold, 0.001);
+    assert!(cc.state.is_slow_start());
+}
+
+#[test]
+fn on_packet_lost_minimum_window() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let random = &mut random::testing::Generator::default();
+    let now = NoopClock.get_time();
+
+    cc.congestion_window = 2500.0;
+    cc.bytes_in_flight = BytesInFlight::new(2500);
+
+    cc.on_packet_lost(1000, (), false, false, random, now, &mut publisher);
+
+    assert_delta!(1250.0, cc.slow_start_threshold, 0.001);
+    assert_delta!(cc.minimum_window(), cc.congestion_window, 0.001);
+}
+
+#[test]
+fn on_explicit_congestion() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let now = NoopClock.get_time();
+
+    cc.congestion_window = 100_000.0;
+
+    cc.on_explicit_congestion(1, now, &mut publisher);
+
+    // ECN-CE is responded to in the same way as loss, without fast retransmission
+    assert_delta!(50_000.0, cc.congestion_window, 0.001);
+    assert_eq!(Recovery(now, Idle), cc.state);
+    assert!(!cc.requires_fast_retransmission());
+}
+
+#[test]
+fn on_ack() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let random = &mut random::testing::Generator::default();
+    let rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+
+    // Fill the window so it is not under utilized
+    for _ in 0..10 {
+        cc.on_packet_sent(now, 1000, Some(false), &rtt_estimator, &mut publisher);
+    }
+    assert!(!cc.under_utilized);
+
+    // Slow start grows the window by the acknowledged bytes
+    cc.on_ack(now, 1000, (), &rtt_estimator, random, now, &mut publisher);
+    assert_delta!(11_000.0, cc.congestion_window, 0.001);
+
+    // Congestion moves the controller into recovery
+    let recovery_time = now + Duration::from_millis(1);
+    cc.on_explicit_congestion(1, recovery_time, &mut publisher);
+    assert_delta!(5_500.0, cc.congestion_window, 0.001);
+    cc.on_packet_sent(
+        recovery_time,
+        1000,
+        Some(false),
+        &rtt_estimator,
+        &mut publisher,
+    );
+
+    // The window doesn't grow while in recovery
+    cc.on_ack(now, 1000, (), &rtt_estimator, random, now, &mut publisher);
+    assert_delta!(5_500.0, cc.congestion_window, 0.001);
+
+    // Acknowledging a packet sent after recovery started enters congestion avoidance,
+    // where the window grows by one max datagram size per window acknowledged
+    let ack_time = recovery_time + Duration::from_millis(10);
+    cc.on_ack(
+        recovery_time + Duration::from_millis(1),
+        1000,
+        (),
+        &rtt_estimator,
+        random,
+        ack_time,
+        &mut publisher,
+    );
+    assert_eq!(CongestionAvoidance, cc.state);
+    assert_delta!(
+        5_500.0 + 1000.0 * 1000.0 / 5_500.0,
+        cc.congestion_window,
+        0.001
+    );
+}
+
+#[test]
+fn on_ack_exits_slow_start_at_threshold() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let random = &mut random::testing::Generator::default();
+    let rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+
+    cc.slow_start_threshold = 11_500.0;
+    for _ in 0..10 {
+        cc.on_packet_sent(now, 1000, Some(false), &rtt_estimator, &mut publisher);
+    }
+
+    cc.on_ack(now, 1000, (), &rtt_estimator, random, now, &mut publisher);
+    assert!(cc.state.is_slow_start());
+    assert_delta!(11_000.0, cc.congestion_window, 0.001);
+
+    cc.on_ack(now, 1000, (), &rtt_estimator, random, now, &mut publisher);
+    assert_eq!(CongestionAvoidance, cc.state);
+    assert_delta!(12_000.0, cc.congestion_window, 0.001);
+}
+
+#[test]
+fn on_ack_under_utilized() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let random = &mut random::testing::Generator::default();
+    let rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+
+    cc.on_packet_sent(now, 1000, Some(true), &rtt_estimator, &mut publisher);
+    assert!(cc.under_utilized);
+
+    cc.on_ack(now, 1000, (), &rtt_estimator, random, now, &mut publisher);
+    assert_eq!(10_000, cc.congestion_window());
+}
+
+#[test]
+fn on_packet_discarded() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let now = NoopClock.get_time();
+
+    cc.bytes_in_flight = BytesInFlight::new(10_000);
+    cc.state = Recovery(now, RequiresTransmission);
+
+    cc.on_packet_discarded(1000, &mut publisher);
+
+    assert_eq!(9000, cc.bytes_in_flight());
+    assert_eq!(Recovery(now, Idle), cc.state);
+}
+
+#[test]
+fn on_mtu_update() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+
+    cc.congestion_window = 100_000.0;
+    cc.on_mtu_update(2000, &mut publisher);
+
+    assert_eq!(2000, cc.max_datagram_size);
+    assert_eq!(200_000, cc.congestion_window());
+}
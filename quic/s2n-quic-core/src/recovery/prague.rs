@@ -0,0 +1,510 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    counter::Counter,
+    event::builder::SlowStartExitCause,
+    random,
+    recovery::{
+        congestion_controller::{self, CongestionController, Publisher},
+        pacing::Pacer,
+        prague::{FastRetransmission::*, State::*},
+        RttEstimator,
+    },
+    time::Timestamp,
+};
+use core::cmp::{max, min};
+
+// Gain applied to each sample of the fraction of CE marked bytes when updating alpha
+// See https://www.rfc-editor.org/rfc/rfc8257#section-3.3
+const ALPHA_GAIN: f32 = 1.0 / 16.0;
+
+// Prague responds to packet loss in the same way as Reno, halving the congestion window
+// See https://datatracker.ietf.org/doc/html/draft-briscoe-iccrg-prague-congestion-control#section-2.4.5
+const LOSS_REDUCTION_FACTOR: f32 = 0.5;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum State {
+    SlowStart,
+    /// The congestion window was reduced in response to congestion at the given time.
+    ///
+    /// Further congestion signals are not acted on until a packet sent after that time
+    /// is acknowledged, limiting the reduction to once per round trip.
+    Recovery(Timestamp, FastRetransmission),
+    CongestionAvoidance,
+}
+
+impl State {
+    /// Returns true if the state is `SlowStart`
+    fn is_slow_start(&self) -> bool {
+        matches!(self, SlowStart)
+    }
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.2
+//# If the congestion window is reduced immediately, a
+//# single packet can be sent prior to reduction.  This speeds up loss
+//# recovery if the data in the lost packet is retransmitted and is
+//# similar to TCP as described in Section 5 of [RFC6675].
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum FastRetransmission {
+    Idle,
+    RequiresTransmission,
+}
+
+/// Estimates the fraction of bytes that experienced congestion over each round trip
+#[derive(Clone, Debug)]
+struct CeFraction {
+    /// Weighted average of the fraction of CE marked bytes with `ALPHA_GAIN` applied
+    alpha: f32,
+    /// The round ends when a packet sent at or after this time is acknowledged
+    round_end: Option<Timestamp>,
+    /// The amount of bytes acknowledged in the current round
+    acked_bytes: u64,
+    /// The amount of packets reported as CE marked in the current round
+    ce_count: u64,
+}
+
+impl Default for CeFraction {
+    fn default() -> Self {
+        Self {
+            // Starting at 1 means the first congestion experienced reduces the
+            // window by half, similar to a classic congestion controller.
+            alpha: 1.0,
+            round_end: None,
+            acked_bytes: 0,
+            ce_count: 0,
+        }
+    }
+}
+
+impl CeFraction {
+    /// Called each time packets are acknowledged
+    #[inline]
+    fn on_ack(
+        &mut self,
+        newest_acked_time_sent: Timestamp,
+        bytes_acknowledged: usize,
+        time_of_last_sent_packet: Option<Timestamp>,
+        max_datagram_size: u16,
+    ) {
+        self.acked_bytes = self.acked_bytes.saturating_add(bytes_acknowledged as u64);
+
+        match self.round_end {
+            Some(round_end) if newest_acked_time_sent < round_end => return,
+            Some(_) => self.on_round_end(max_datagram_size),
+            None => {}
+        }
+
+        // Start the next round, which lasts until the packets that are currently in
+        // flight have been acknowledged
+        self.round_end = time_of_last_sent_packet;
+        self.acked_bytes = 0;
+        self.ce_count = 0;
+    }
+
+    /// Called each time the peer reports an increase in CE marked packets
+    #[inline]
+    fn on_explicit_congestion(&mut self, ce_count: u64) {
+        self.ce_count = self.ce_count.saturating_add(ce_count);
+    }
+
+    /// Updates alpha with the fraction of CE marked bytes in the round that just ended
+    ///
+    /// `alpha = alpha * (1 - g) + g * F`, see <https://www.rfc-editor.org/rfc/rfc8257#section-3.3>
+    #[inline]
+    fn on_round_end(&mut self, max_datagram_size: u16) {
+        if self.acked_bytes == 0 {
+            return;
+        }
+
+        // ACK frames only report the number of CE marked packets, so estimate the number
+        // of CE marked bytes by assuming each of the packets was full sized.
+        let ce_bytes = self.ce_count.saturating_mul(max_datagram_size as u64);
+        let fraction = (ce_bytes as f32 / self.acked_bytes as f32).min(1.0);
+
+        self.alpha = (self.alpha * (1.0 - ALPHA_GAIN) + ALPHA_GAIN * fraction).min(1.0);
+    }
+}
+
+/// A congestion controller for L4S that implements the TCP Prague congestion control
+/// algorithm described in <https://datatracker.ietf.org/doc/html/draft-briscoe-iccrg-prague-congestion-control>.
+///
+/// Rather than treating each ECN-CE mark as a loss-like signal, the congestion window is
+/// reduced in proportion to the fraction of bytes that experienced congestion, in the same
+/// manner as DCTCP (<https://www.rfc-editor.org/rfc/rfc8257>). Packets are sent with the
+/// ECT(1) codepoint to identify them as L4S traffic to the network.
+#[derive(Clone, Debug)]
+pub struct PragueCongestionController {
+    pacer: Pacer,
+    max_datagram_size: u16,
+    congestion_window: f32,
+    state: State,
+    ce_fraction: CeFraction,
+    bytes_in_flight: BytesInFlight,
+    time_of_last_sent_packet: Option<Timestamp>,
+    under_utilized: bool,
+}
+
+type BytesInFlight = Counter<u32>;
+
+impl CongestionController for PragueCongestionController {
+    type PacketInfo = ();
+
+    #[inline]
+    fn congestion_window(&self) -> u32 {
+        self.congestion_window as u32
+    }
+
+    #[inline]
+    fn bytes_in_flight(&self) -> u32 {
+        *self.bytes_in_flight
+    }
+
+    #[inline]
+    fn is_congestion_limited(&self) -> bool {
+        let available_congestion_window = self
+            .congestion_window()
+            .saturating_sub(*self.bytes_in_flight);
+        available_congestion_window < self.max_datagram_size as u32
+    }
+
+    #[inline]
+    fn requires_fast_retransmission(&self) -> bool {
+        matches!(self.state, Recovery(_, RequiresTransmission))
+    }
+
+    #[inline]
+    fn on_packet_sent<Pub: Publisher>(
+        &mut self,
+        time_sent: Timestamp,
+        bytes_sent: usize,
+        app_limited: Option<bool>,
+        rtt_estimator: &RttEstimator,
+        publisher: &mut Pub,
+    ) {
+        if bytes_sent == 0 {
+            // Packet was not congestion controlled
+            return;
+        }
+
+        self.bytes_in_flight
+            .try_add(bytes_sent)
+            .expect("bytes sent should not exceed u32::MAX");
+
+        // The window is considered under utilized if the application did not have enough
+        // data to send and less than half of the window is in use. `app_limited` is not
+        // determined for Initial and Handshake packets so only the utilization is considered.
+        self.under_utilized =
+            app_limited != Some(false) && *self.bytes_in_flight < self.congestion_window() / 2;
+
+        if let Recovery(recovery_start_time, RequiresTransmission) = self.state {
+            // A packet has been sent since we entered recovery (fast retransmission)
+            // so flip the state back to idle.
+            self.state = Recovery(recovery_start_time, Idle);
+        }
+
+        self.time_of_last_sent_packet = Some(time_sent);
+
+        self.pacer.on_packet_sent(
+            time_sent,
+            bytes_sent,
+            rtt_estimator,
+            self.congestion_window(),
+            self.max_datagram_size,
+            self.state.is_slow_start(),
+            publisher,
+        );
+    }
+
+    #[inline]
+    fn on_rtt_update<Pub: Publisher>(
+        &mut self,
+        _time_sent: Timestamp,
+        _now: Timestamp,
+        _rtt_estimator: &RttEstimator,
+        _publisher: &mut Pub,
+    ) {
+        // Round trips are tracked using the send times of acknowledged packets
+    }
+
+    #[inline]
+    fn on_ack<Pub: Publisher>(
+        &mut self,
+        newest_acked_time_sent: Timestamp,
+        bytes_acknowledged: usize,
+        _newest_acked_packet_info: Self::PacketInfo,
+        _rtt_estimator: &RttEstimator,
+        _random_generator: &mut dyn random::Generator,
+        _ack_receive_time: Timestamp,
+        _publisher: &mut Pub,
+    ) {
+        self.bytes_in_flight
+            .try_sub(bytes_acknowledged)
+            .expect("bytes_acknowledged should not exceed u32::MAX");
+
+        self.ce_fraction.on_ack(
+            newest_acked_time_sent,
+            bytes_acknowledged,
+            self.time_of_last_sent_packet,
+            self.max_datagram_size,
+        );
+
+        // Check if this ack causes the controller to exit recovery
+        if let Recovery(recovery_start_time, _) = self.state {
+            if newest_acked_time_sent > recovery_start_time {
+                //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.2
+                //# A recovery period ends and the sender enters congestion avoidance
+                //# when a packet sent during the recovery period is acknowledged.
+                self.state = CongestionAvoidance;
+            }
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.8
+        //# When bytes in flight is smaller than the congestion window and
+        //# sending is not pacing limited, the congestion window is
+        //# underutilized.  This can happen due to insufficient application data
+        //# or flow control limits.  When this occurs, the congestion window
+        //# SHOULD NOT be increased in either slow start or congestion avoidance.
+        if self.under_utilized {
+            return;
+        }
+
+        match self.state {
+            SlowStart => {
+                //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.1
+                //# While a sender is in slow start, the congestion window increases by
+                //# the number of bytes acknowledged when each acknowledgment is
+                //# processed.  This results in exponential growth of the congestion
+                //# window.
+                self.congestion_window += bytes_acknowledged as f32;
+            }
+            Recovery(_, _) => {
+                // Don't increase the congestion window while in recovery
+            }
+            CongestionAvoidance => {
+                //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.3
+                //# A sender in congestion avoidance uses an Additive Increase
+                //# Multiplicative Decrease (AIMD) approach that MUST limit the increase
+                //# to the congestion window to at most one maximum datagram size for
+                //# each congestion window that is acknowledged.
+                self.congestion_window += self.max_datagram_size as f32 * bytes_acknowledged as f32
+                    / self.congestion_window;
+            }
+        }
+    }
+
+    #[inline]
+    fn on_packet_lost<Pub: Publisher>(
+        &mut self,
+        lost_bytes: u32,
+        _packet_info: Self::PacketInfo,
+        persistent_congestion: bool,
+        _new_loss_burst: bool,
+        _random_generator: &mut dyn random::Generator,
+        timestamp: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        debug_assert!(lost_bytes > 0);
+
+        self.bytes_in_flight -= lost_bytes;
+
+        if self.state.is_slow_start() && !persistent_congestion {
+            publisher
+                .on_slow_start_exited(SlowStartExitCause::PacketLoss, self.congestion_window());
+        }
+
+        self.on_congestion_event(timestamp, LOSS_REDUCTION_FACTOR, RequiresTransmission);
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.6.2
+        //# When persistent congestion is declared, the sender's congestion
+        //# window MUST be reduced to the minimum congestion window
+        //# (kMinimumWindow), similar to a TCP sender's response on an RTO
+        //# [RFC5681].
+        if persistent_congestion {
+            self.congestion_window = self.minimum_window();
+            self.state = SlowStart;
+        }
+    }
+
+    #[inline]
+    fn on_explicit_congestion<Pub: Publisher>(
+        &mut self,
+        ce_count: u64,
+        event_time: Timestamp,
+        publisher: &mut Pub,
+    ) {
+        self.ce_fraction.on_explicit_congestion(ce_count);
+
+        if self.state.is_slow_start() {
+            publisher.on_slow_start_exited(SlowStartExitCause::Ecn, self.congestion_window());
+        }
+
+        // Rather than the fixed reduction in response to loss, the window is reduced by
+        // `cwnd = cwnd * (1 - alpha / 2)`
+        // See https://www.rfc-editor.org/rfc/rfc8257#section-3.3
+        let reduction_factor = self.ce_fraction.alpha / 2.0;
+        self.on_congestion_event(event_time, reduction_factor, Idle);
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-7.2
+    //# If the maximum datagram size is decreased in order to complete the
+    //# handshake, the congestion window SHOULD be set to the new initial
+    //# congestion window.
+    #[inline]
+    fn on_mtu_update<Pub: Publisher>(&mut self, max_datagram_size: u16, _publisher: &mut Pub) {
+        let old_max_datagram_size = self.max_datagram_size;
+        self.max_datagram_size = max_datagram_size;
+
+        let congestion_window =
+            (self.congestion_window / old_max_datagram_size as f32) * max_datagram_size as f32;
+        let initial_window = Self::initial_window(max_datagram_size, &Default::default());
+
+        self.congestion_window = max(congestion_window as u32, initial_window) as f32;
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-6.4
+    //# The sender MUST discard all recovery state
+    //# associated with those packets and MUST remove them from the count of
+    //# bytes in flight.
+    #[inline]
+    fn on_packet_discarded<Pub: Publisher>(&mut self, bytes_sent: usize, _publisher: &mut Pub) {
+        self.bytes_in_flight
+            .try_sub(bytes_sent)
+            .expect("bytes sent should not exceed u32::MAX");
+
+        if let Recovery(recovery_start_time, RequiresTransmission) = self.state {
+            // If any of the discarded packets were lost, they will no longer be retransmitted
+            // so flip the Recovery status back to Idle so it is not waiting for a
+            // retransmission that may never come.
+            self.state = Recovery(recovery_start_time, Idle);
+        }
+    }
+
+    #[inline]
+    fn earliest_departure_time(&self) -> Option<Timestamp> {
+        self.pacer.earliest_departure_time()
+    }
+
+    #[inline]
+    fn is_l4s_capable(&self) -> bool {
+        true
+    }
+}
+
+impl PragueCongestionController {
+    // max_datagram_size is the current max_datagram_size, and is
+    // expected to be 1200 when the congestion controller is created.
+    pub fn new(max_datagram_size: u16, app_settings: ApplicationSettings) -> Self {
+        Self {
+            pacer: Pacer::default(),
+            max_datagram_size,
+            congestion_window: Self::initial_window(max_datagram_size, &app_settings) as f32,
+            state: SlowStart,
+            ce_fraction: CeFraction::default(),
+            bytes_in_flight: Counter::new(0),
+            time_of_last_sent_packet: None,
+            under_utilized: true,
+        }
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-7.2
+    //# Endpoints SHOULD use an initial congestion
+    //# window of ten times the maximum datagram size (max_datagram_size),
+    //# while limiting the window to the larger of 14,720 bytes or twice the
+    //# maximum datagram size.
+    #[inline]
+    fn initial_window(max_datagram_size: u16, app_settings: &ApplicationSettings) -> u32 {
+        const INITIAL_WINDOW_LIMIT: u32 = 14720;
+        let default = min(
+            10 * max_datagram_size as u32,
+            max(INITIAL_WINDOW_LIMIT, 2 * max_datagram_size as u32),
+        );
+        let initial_window = app_settings.initial_congestion_window.unwrap_or(default);
+
+        max(initial_window, 2 * max_datagram_size as u32)
+    }
+
+    /// Reduces the congestion window by the given `reduction_factor`, at most once per round trip
+    #[inline]
+    fn on_congestion_event(
+        &mut self,
+        event_time: Timestamp,
+        reduction_factor: f32,
+        fast_retransmission: FastRetransmission,
+    ) {
+        // No reaction if already in a recovery period.
+        if matches!(self.state, Recovery(_, _)) {
+            return;
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.1
+        //# The sender MUST exit slow start and enter a recovery period when a
+        //# packet is lost or when the ECN-CE count reported by its peer
+        //# increases.
+        self.state = Recovery(event_time, fast_retransmission);
+
+        self.congestion_window =
+            (self.congestion_window * (1.0 - reduction_factor)).max(self.minimum_window());
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-7.2
+    //# The minimum congestion window is the smallest value the congestion
+    //# window can attain in response to loss, an increase in the peer-
+    //# reported ECN-CE count, or persistent congestion.  The RECOMMENDED
+    //# value is 2 * max_datagram_size.
+    #[inline]
+    fn minimum_window(&self) -> f32 {
+        2.0 * self.max_datagram_size as f32
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ApplicationSettings {
+    initial_congestion_window: Option<u32>,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Default)]
+pub struct Endpoint {
+    pub(super) app_settings: ApplicationSettings,
+}
+
+impl congestion_controller::Endpoint for Endpoint {
+    type CongestionController = PragueCongestionController;
+
+    fn new_congestion_controller(
+        &mut self,
+        path_info: congestion_controller::PathInfo,
+    ) -> Self::CongestionController {
+        PragueCongestionController::new(path_info.max_datagram_size, self.app_settings)
+    }
+}
+
+pub mod builder {
+    use super::{ApplicationSettings, Endpoint};
+
+    /// Build the congestion controller endpoint with application provided overrides
+    #[derive(Default)]
+    pub struct Builder {
+        initial_congestion_window: Option<u32>,
+    }
+
+    impl Builder {
+        /// Set the initial congestion window in bytes.
+        pub fn with_initial_congestion_window(mut self, initial_congestion_window: u32) -> Self {
+            self.initial_congestion_window = Some(initial_congestion_window);
+            self
+        }
+
+        pub fn build(self) -> Endpoint {
+            let app_settings = ApplicationSettings {
+                initial_congestion_window: self.initial_congestion_window,
+            };
+            Endpoint { app_settings }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,224 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    assert_delta, event, path,
+    recovery::congestion_controller::PathPublisher,
+    time::{Clock, NoopClock},
+};
+use core::time::Duration;
+
+const MAX_DATAGRAM_SIZE: u16 = 1000;
+
+fn new_controller() -> PragueCongestionController {
+    PragueCongestionController::new(MAX_DATAGRAM_SIZE, Default::default())
+}
+
+#[test]
+fn l4s_capable() {
+    assert!(new_controller().is_l4s_capable());
+}
+
+#[test]
+fn initial_window() {
+    let cc = new_controller();
+    assert_eq!(10_000, cc.congestion_window());
+
+    let cc = PragueCongestionController::new(
+        MAX_DATAGRAM_SIZE,
+        ApplicationSettings {
+            initial_congestion_window: Some(50_000),
+        },
+    );
+    assert_eq!(50_000, cc.congestion_window());
+}
+
+#[test]
+fn ce_fraction() {
+    let now = NoopClock.get_time();
+    let mut ce_fraction = CeFraction::default();
+    assert_delta!(1.0, ce_fraction.alpha, 0.0001);
+
+    // The first ACK starts the first round
+    ce_fraction.on_ack(now, 1000, Some(now + Duration::from_millis(10)), 1000);
+    assert_eq!(Some(now + Duration::from_millis(10)), ce_fraction.round_end);
+    assert_eq!(0, ce_fraction.acked_bytes);
+
+    // 20 packets acknowledged, 5 of which were CE marked
+    ce_fraction.on_explicit_congestion(5);
+    ce_fraction.on_ack(now + Duration::from_millis(5), 19_000, None, 1000);
+    assert_delta!(1.0, ce_fraction.alpha, 0.0001);
+    ce_fraction.on_ack(
+        now + Duration::from_millis(10),
+        1000,
+        Some(now + Duration::from_millis(20)),
+        1000,
+    );
+
+    assert_delta!(
+        (1.0 - ALPHA_GAIN) + ALPHA_GAIN * 0.25,
+        ce_fraction.alpha,
+        0.0001
+    );
+    assert_eq!(Some(now + Duration::from_millis(20)), ce_fraction.round_end);
+    assert_eq!(0, ce_fraction.acked_bytes);
+    assert_eq!(0, ce_fraction.ce_count);
+
+    // A round without congestion decays alpha
+    let alpha = ce_fraction.alpha;
+    ce_fraction.on_ack(
+        now + Duration::from_millis(20),
+        10_000,
+        Some(now + Duration::from_millis(30)),
+        1000,
+    );
+    assert_delta!(alpha * (1.0 - ALPHA_GAIN), ce_fraction.alpha, 0.0001);
+}
+
+#[test]
+fn on_explicit_congestion() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let now = NoopClock.get_time();
+
+    cc.congestion_window = 100_000.0;
+    cc.ce_fraction.alpha = 0.5;
+
+    cc.on_explicit_congestion(1, now, &mut publisher);
+
+    // The window is reduced by alpha / 2
+    assert_delta!(75_000.0, cc.congestion_window, 0.001);
+    assert_eq!(Recovery(now, Idle), cc.state);
+    assert!(!cc.requires_fast_retransmission());
+    assert_eq!(1, cc.ce_fraction.ce_count);
+
+    // The window is only reduced once per round trip
+    cc.on_explicit_congestion(1, now + Duration::from_millis(1), &mut publisher);
+    assert_delta!(75_000.0, cc.congestion_window, 0.001);
+    assert_eq!(2, cc.ce_fraction.ce_count);
+}
+
+#[test]
+fn on_explicit_congestion_minimum_window() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let now = NoopClock.get_time();
+
+    cc.congestion_window = 2500.0;
+
+    cc.on_explicit_congestion(1, now, &mut publisher);
+
+    assert_delta!(cc.minimum_window(), cc.congestion_window, 0.001);
+}
+
+#[test]
+fn on_packet_lost() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let random = &mut random::testing::Generator::default();
+    let now = NoopClock.get_time();
+
+    cc.congestion_window = 100_000.0;
+    cc.bytes_in_flight = BytesInFlight::new(10_000);
+    // Loss is responded to with a fixed reduction regardless of alpha
+    cc.ce_fraction.alpha = 0.1;
+
+    cc.on_packet_lost(1000, (), false, false, random, now, &mut publisher);
+
+    assert_eq!(9000, cc.bytes_in_flight());
+    assert_delta!(50_000.0, cc.congestion_window, 0.001);
+    assert!(cc.requires_fast_retransmission());
+
+    // Persistent congestion resets the window to the minimum
+    cc.on_packet_lost(1000, (), true, false, random, now, &mut publisher);
+    assert_delta!(cc.minimum_window(), cc.congestion_window, 0.001);
+    assert!(cc.state.is_slow_start());
+}
+
+#[test]
+fn on_ack() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let random = &mut random::testing::Generator::default();
+    let rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+
+    // Fill the window so it is not under utilized
+    for _ in 0..10 {
+        cc.on_packet_sent(now, 1000, Some(false), &rtt_estimator, &mut publisher);
+    }
+    assert!(!cc.under_utilized);
+
+    // Slow start grows the window by the acknowledged bytes
+    cc.on_ack(now, 1000, (), &rtt_estimator, random, now, &mut publisher);
+    assert_delta!(11_000.0, cc.congestion_window, 0.001);
+
+    // Congestion experienced moves the controller into recovery
+    let recovery_time = now + Duration::from_millis(1);
+    cc.on_explicit_congestion(1, recovery_time, &mut publisher);
+    assert_delta!(5_500.0, cc.congestion_window, 0.001);
+    cc.on_packet_sent(
+        recovery_time,
+        1000,
+        Some(false),
+        &rtt_estimator,
+        &mut publisher,
+    );
+
+    // The window doesn't grow while in recovery
+    cc.on_ack(now, 1000, (), &rtt_estimator, random, now, &mut publisher);
+    assert_delta!(5_500.0, cc.congestion_window, 0.001);
+
+    // Acknowledging a packet sent after recovery started enters congestion avoidance,
+    // where the window grows by one max datagram size per window acknowledged
+    let ack_time = recovery_time + Duration::from_millis(10);
+    cc.on_ack(
+        recovery_time + Duration::from_millis(1),
+        1000,
+        (),
+        &rtt_estimator,
+        random,
+        ack_time,
+        &mut publisher,
+    );
+    assert_eq!(CongestionAvoidance, cc.state);
+    assert_delta!(
+        5_500.0 + 1000.0 * 1000.0 / 5_500.0,
+        cc.congestion_window,
+        0.001
+    );
+}
+
+#[test]
+fn on_ack_under_utilized() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+    let random = &mut random::testing::Generator::default();
+    let rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+
+    cc.on_packet_sent(now, 1000, Some(true), &rtt_estimator, &mut publisher);
+    assert!(cc.under_utilized);
+
+    cc.on_ack(now, 1000, (), &rtt_estimator, random, now, &mut publisher);
+    assert_eq!(10_000, cc.congestion_window());
+}
+
+#[test]
+fn on_mtu_update() {
+    let mut cc = new_controller();
+    let mut publisher = event::testing::Publisher::no_snapshot();
+    let mut publisher = PathPublisher::new(&mut publisher, path::Id::test_id());
+
+    cc.congestion_window = 100_000.0;
+    cc.on_mtu_update(2000, &mut publisher);
+
+    assert_eq!(2000, cc.max_datagram_size);
+    assert_eq!(200_000, cc.congestion_window());
+}
@@ -33,6 +33,11 @@ pub struct SentPacketInfo<PacketInfo> {
     pub transmission_mode: transmission::Mode,
     /// Additional packet metadata dictated by the congestion controller
     pub cc_packet_info: PacketInfo,
+    /// The position of the packet in the sequence of packets sent on its path
+    ///
+    /// This is only set when packets sent on several paths share a packet number space, as
+    /// is the case with the multipath extension.
+    pub path_sequence: u64,
 }
 
 impl<PacketInfo> SentPacketInfo<PacketInfo> {
@@ -63,6 +68,7 @@ impl<PacketInfo> SentPacketInfo<PacketInfo> {
             ecn,
             transmission_mode,
             cc_packet_info,
+            path_sequence: 0,
         }
     }
 }
@@ -8,8 +8,8 @@ use crate::{
     path::MINIMUM_MAX_DATAGRAM_SIZE,
     random,
     recovery::{
-        congestion_controller::PathPublisher, CongestionController, CubicCongestionController,
-        RttEstimator,
+        congestion_controller::PathPublisher, new_reno::NewRenoCongestionController,
+        CongestionController, CubicCongestionController, RttEstimator,
     },
     time::{Clock, NoopClock, Timestamp},
 };
@@ -34,6 +34,10 @@ fn slow_start_unlimited_test() {
     let cc = CubicCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
 
     slow_start_unlimited(cc, 12).finish();
+
+    let cc = NewRenoCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
+
+    slow_start_unlimited(cc, 12).finish();
 }
 
 #[test]
@@ -42,6 +46,10 @@ fn loss_at_3mb_test() {
     let cc = CubicCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
 
     loss_at_3mb(cc, 135).finish();
+
+    let cc = NewRenoCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
+
+    loss_at_3mb(cc, 135).finish();
 }
 
 #[test]
@@ -50,6 +58,10 @@ fn app_limited_1mb_test() {
     let cc = CubicCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
 
     app_limited_1mb(cc, 120).finish();
+
+    let cc = NewRenoCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
+
+    app_limited_1mb(cc, 120).finish();
 }
 
 #[test]
@@ -58,6 +70,10 @@ fn minimum_window_test() {
     let cc = CubicCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
 
     minimum_window(cc, 10).finish();
+
+    let cc = NewRenoCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
+
+    minimum_window(cc, 10).finish();
 }
 
 #[test]
@@ -66,6 +82,10 @@ fn loss_at_3mb_and_2_75mb_test() {
     let cc = CubicCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
 
     loss_at_3mb_and_2_75mb(cc, 120).finish();
+
+    let cc = NewRenoCongestionController::new(MINIMUM_MAX_DATAGRAM_SIZE, Default::default());
+
+    loss_at_3mb_and_2_75mb(cc, 120).finish();
 }
 
 #[derive(Debug)]
@@ -66,6 +66,14 @@ pub enum StreamError {
     NonEmptyOutput {
         source: &'static panic::Location<'static>,
     },
+    /// The stream was opened with early data (0-RTT), which the peer rejected
+    ///
+    /// The application can open a new stream and send the data again once the
+    /// handle to the rejected stream was released.
+    #[non_exhaustive]
+    EarlyDataRejected {
+        source: &'static panic::Location<'static>,
+    },
 }
 
 #[cfg(feature = "std")]
@@ -98,6 +106,10 @@ impl fmt::Display for StreamError {
                 f,
                 "The stream was provided a non-empty placeholder buffer for receiving data."
             ),
+            Self::EarlyDataRejected { .. } => write!(
+                f,
+                "The stream was opened with early data, which was rejected by the peer"
+            ),
         }
     }
 }
@@ -115,6 +127,7 @@ impl StreamError {
             StreamError::NonWritable { source } => source,
             StreamError::SendingBlocked { source } => source,
             StreamError::NonEmptyOutput { source } => source,
+            StreamError::EarlyDataRejected { source } => source,
         }
     }
 
@@ -181,6 +194,14 @@ impl StreamError {
         let source = panic::Location::caller();
         StreamError::NonEmptyOutput { source }
     }
+
+    #[track_caller]
+    #[inline]
+    #[doc(hidden)]
+    pub fn early_data_rejected() -> StreamError {
+        let source = panic::Location::caller();
+        StreamError::EarlyDataRejected { source }
+    }
 }
 
 impl application::error::TryInto for StreamError {
@@ -237,6 +258,7 @@ impl From<StreamError> for std::io::ErrorKind {
             StreamError::NonWritable { .. } => ErrorKind::Other,
             StreamError::SendingBlocked { .. } => ErrorKind::WouldBlock,
             StreamError::NonEmptyOutput { .. } => ErrorKind::InvalidInput,
+            StreamError::EarlyDataRejected { .. } => ErrorKind::ConnectionRefused,
         }
     }
 }
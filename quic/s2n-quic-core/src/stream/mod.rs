@@ -7,12 +7,14 @@ pub mod iter;
 pub mod limits;
 #[cfg(feature = "alloc")]
 pub mod ops;
+mod priority;
 pub mod state;
 mod type_;
 
 pub use error::*;
 pub use id::*;
 pub use limits::Limits;
+pub use priority::Priority;
 pub use type_::*;
 
 #[cfg(any(test, feature = "testing"))]
@@ -28,7 +28,7 @@
 //!     .await?;
 //! ```
 
-use crate::{application, stream};
+use crate::{application, stream, varint::VarInt};
 use core::task::Poll;
 
 /// A request made on a stream
@@ -54,6 +54,15 @@ impl<'a> Request<'a> {
         self
     }
 
+    /// Resets the tx stream with an error code, while still delivering the data up to
+    /// `reliable_size` to the peer
+    pub fn reset_at(&mut self, error: application::Error, reliable_size: VarInt) -> &mut Self {
+        let tx = self.tx_mut();
+        tx.reset = Some(error);
+        tx.reliable_size = Some(reliable_size);
+        self
+    }
+
     /// Flushes any pending tx data to be ACKed before unblocking
     pub fn flush(&mut self) -> &mut Self {
         self.tx_mut().flush = true;
@@ -113,6 +122,12 @@ impl<'a> Request<'a> {
         self
     }
 
+    /// Sets the scheduling priority of the tx stream
+    pub fn with_priority(&mut self, priority: stream::Priority) -> &mut Self {
+        self.tx_mut().priority = Some(priority);
+        self
+    }
+
     pub fn detach_tx(&mut self) -> &mut Self {
         let tx = self.tx_mut();
         tx.detached = true;
@@ -187,6 +202,11 @@ pub mod tx {
         /// Optionally reset the stream with an error
         pub reset: Option<application::Error>,
 
+        /// The amount of data which is delivered to the peer before the stream is reset
+        ///
+        /// This is only used in combination with `reset`.
+        pub reliable_size: Option<VarInt>,
+
         /// Waits for an ACK on resets and finishes
         pub flush: bool,
 
@@ -196,6 +216,9 @@ pub mod tx {
         /// Marks the tx stream as detached, which makes the stream make progress, regardless of
         /// application observations.
         pub detached: bool,
+
+        /// Optionally updates the scheduling priority of the tx stream
+        pub priority: Option<stream::Priority>,
     }
 
     /// The result of a tx request
@@ -511,7 +534,9 @@ mod tests {
                     finish: true,
                     flush: true,
                     reset: Some(reset),
+                    reliable_size: None,
                     detached: false,
+                    priority: None,
                 }),
                 rx: Some(rx::Request {
                     chunks: Some(rx_chunks),
@@ -0,0 +1,117 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Send stream scheduling priorities
+//!
+//! The model follows the [Extensible Prioritization Scheme for HTTP](https://www.rfc-editor.org/rfc/rfc9218)
+//! so applications can map HTTP priority signals directly onto a stream.
+
+/// The scheduling priority of a send stream
+///
+/// Streams with a lower `urgency` value are always given the opportunity to transmit before
+/// streams with a higher value. Streams that share an urgency are scheduled according to the
+/// `incremental` flag:
+///
+/// * Incremental streams take turns, each writing a bounded number of frames before yielding
+///   to the next stream of the same urgency.
+/// * Non-incremental streams are sent sequentially in the order they became ready, which lets
+///   the peer process each one as a whole.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Priority {
+    urgency: u8,
+    incremental: bool,
+}
+
+impl Default for Priority {
+    /// Returns the default priority
+    ///
+    /// Unlike the HTTP default, streams are incremental by default, which preserves the
+    /// round-robin scheduling used by streams that don't specify a priority.
+    #[inline]
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_URGENCY, true)
+    }
+}
+
+impl Priority {
+    /// The most urgent value a stream can have
+    pub const MIN_URGENCY: u8 = 0;
+
+    /// The least urgent value a stream can have
+    pub const MAX_URGENCY: u8 = 7;
+
+    /// The urgency assigned to streams that don't specify one
+    pub const DEFAULT_URGENCY: u8 = 3;
+
+    /// Creates a new `Priority`
+    ///
+    /// Urgency values larger than [`Self::MAX_URGENCY`] are clamped.
+    #[inline]
+    pub const fn new(urgency: u8, incremental: bool) -> Self {
+        let urgency = if urgency > Self::MAX_URGENCY {
+            Self::MAX_URGENCY
+        } else {
+            urgency
+        };
+
+        Self {
+            urgency,
+            incremental,
+        }
+    }
+
+    /// Returns the urgency of the stream, where `0` is the most urgent
+    #[inline]
+    pub const fn urgency(&self) -> u8 {
+        self.urgency
+    }
+
+    /// Returns `true` if the stream data can be interleaved with other streams of the same urgency
+    #[inline]
+    pub const fn is_incremental(&self) -> bool {
+        self.incremental
+    }
+
+    /// Returns a copy of the priority with the given urgency
+    #[inline]
+    #[must_use]
+    pub const fn with_urgency(self, urgency: u8) -> Self {
+        Self::new(urgency, self.incremental)
+    }
+
+    /// Returns a copy of the priority with the given incremental flag
+    #[inline]
+    #[must_use]
+    pub const fn with_incremental(self, incremental: bool) -> Self {
+        Self::new(self.urgency, incremental)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_test() {
+        let priority = Priority::default();
+        assert_eq!(priority.urgency(), Priority::DEFAULT_URGENCY);
+        assert!(priority.is_incremental());
+    }
+
+    #[test]
+    fn clamp_test() {
+        for urgency in 0..=u8::MAX {
+            let priority = Priority::new(urgency, false);
+            assert_eq!(priority.urgency(), urgency.min(Priority::MAX_URGENCY));
+            assert!(!priority.is_incremental());
+        }
+    }
+
+    #[test]
+    fn builder_test() {
+        let priority = Priority::default()
+            .with_urgency(u8::MAX)
+            .with_incremental(false);
+        assert_eq!(priority, Priority::new(Priority::MAX_URGENCY, false));
+    }
+}
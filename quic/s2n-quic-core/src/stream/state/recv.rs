@@ -36,6 +36,12 @@ use crate::state::{event, is};
 //#   | Read  |                   | Read  |
 //#   +-------+                   +-------+
 
+// The reliable stream reset extension
+// (https://datatracker.ietf.org/doc/draft-ietf-quic-reliable-stream-reset/) adds a
+// RESET_STREAM_AT frame, which only surfaces the reset to the application after it has read
+// the data up to the Reliable Size. A RESET_STREAM, or a RESET_STREAM_AT with a Reliable Size
+// of 0, abandons any of the remaining reliable data.
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub enum Receiver {
     #[default]
@@ -43,6 +49,9 @@ pub enum Receiver {
     SizeKnown,
     DataRecvd,
     DataRead,
+    /// A RESET_STREAM_AT was received and the data up to the Reliable Size is still being
+    /// delivered to the application
+    ResetAtRecvd,
     ResetRecvd,
     ResetRead,
 }
@@ -52,6 +61,7 @@ impl Receiver {
     is!(is_size_known, SizeKnown);
     is!(is_data_received, DataRecvd);
     is!(is_data_read, DataRead);
+    is!(is_reset_at_received, ResetAtRecvd);
     is!(is_reset_received, ResetRecvd);
     is!(is_reset_read, ResetRead);
     is!(is_terminal, DataRead | ResetRead);
@@ -61,7 +71,9 @@ impl Receiver {
         on_receive_all_data(SizeKnown => DataRecvd);
         on_app_read_all_data(DataRecvd => DataRead);
 
-        on_reset(Recv | SizeKnown => ResetRecvd);
+        on_reset(Recv | SizeKnown | ResetAtRecvd => ResetRecvd);
+        on_reset_at(Recv | SizeKnown => ResetAtRecvd);
+        on_app_read_reliable_data(ResetAtRecvd => ResetRecvd);
         on_app_read_reset(ResetRecvd => ResetRead);
     }
 }
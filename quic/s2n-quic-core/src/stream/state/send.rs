@@ -35,6 +35,13 @@ use crate::state::{event, is};
 //#   | Recvd |                   | Recvd |
 //#   +-------+                   +-------+
 
+// The reliable stream reset extension
+// (https://datatracker.ietf.org/doc/draft-ietf-quic-reliable-stream-reset/) adds a
+// RESET_STREAM_AT frame, which resets the stream while still delivering the data up to a
+// Reliable Size. The stream reaches Reset Recvd once the frame and all of the data up to the
+// Reliable Size have been acknowledged. A RESET_STREAM can still be sent afterwards, which
+// abandons the remaining reliable data.
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub enum Sender {
     #[default]
@@ -46,6 +53,8 @@ pub enum Sender {
     /// sending it
     ResetQueued,
     ResetSent,
+    /// A RESET_STREAM_AT was sent and the data up to the Reliable Size is still being delivered
+    ResetAtSent,
     ResetRecvd,
 }
 
@@ -56,6 +65,7 @@ impl Sender {
     is!(is_data_received, DataRecvd);
     is!(is_reset_queued, ResetQueued);
     is!(is_reset_sent, ResetSent);
+    is!(is_reset_at_sent, ResetAtSent);
     is!(is_reset_received, ResetRecvd);
     is!(is_terminal, DataRecvd | ResetRecvd);
 
@@ -67,8 +77,11 @@ impl Sender {
         on_recv_all_acks(DataSent | ResetQueued => DataRecvd);
 
         on_queue_reset(Ready | Send | DataSent => ResetQueued);
-        on_send_reset(Ready | Send | DataSent | ResetQueued => ResetSent);
+        on_send_reset(Ready | Send | DataSent | ResetQueued | ResetAtSent => ResetSent);
+        on_send_reset_at(Ready | Send | DataSent | ResetQueued => ResetAtSent);
         on_recv_reset_ack(ResetSent => ResetRecvd);
+        // called once the RESET_STREAM_AT and the data up to the Reliable Size are acknowledged
+        on_recv_reset_at_ack(ResetAtSent => ResetRecvd);
     }
 }
 
@@ -859,6 +859,73 @@ impl TransportParameterValidator for MaxAckDelay {
     }
 }
 
+// The min_ack_delay transport parameter (0xff04de1b) is defined in
+// https://datatracker.ietf.org/doc/draft-ietf-quic-ack-frequency/. It is an integer
+// value indicating the minimum amount of time in microseconds by which the endpoint
+// is able to delay sending acknowledgments. Sending the parameter indicates that the
+// endpoint supports receiving ACK_FREQUENCY and IMMEDIATE_ACK frames. Values of 2^24
+// or greater are invalid, as are values greater than the max_ack_delay; the latter is
+// validated once all of the peer's transport parameters are known.
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord)]
+pub struct MinAckDelay(VarInt);
+
+impl MinAckDelay {
+    /// Creates a `MinAckDelay` from the given `Duration`
+    pub fn new(value: Duration) -> Option<Self> {
+        value.try_into().ok()
+    }
+
+    /// Convert min_ack_delay into a `core::time::Duration`
+    pub const fn as_duration(self) -> Duration {
+        Duration::from_micros(self.0.as_u64())
+    }
+}
+
+impl TransportParameter for MinAckDelay {
+    const ID: TransportParameterId = TransportParameterId::from_u32(0xff04de1b);
+    type CodecValue = VarInt;
+
+    fn from_codec_value(value: Self::CodecValue) -> Self {
+        Self(value)
+    }
+
+    fn try_into_codec_value(&self) -> Option<&Self::CodecValue> {
+        Some(&self.0)
+    }
+
+    fn default_value() -> Self {
+        unimplemented!("MinAckDelay is an optional transport parameter, so the default is None")
+    }
+}
+
+optional_transport_parameter!(MinAckDelay);
+
+impl TransportParameterValidator for MinAckDelay {
+    fn validate(self) -> Result<Self, DecoderError> {
+        decoder_invariant!(
+            *self.0 < 2u64.pow(24),
+            "min_ack_delay must be less than 2^24"
+        );
+        Ok(self)
+    }
+}
+
+impl TryFrom<Duration> for MinAckDelay {
+    type Error = ValidationError;
+
+    fn try_from(value: Duration) -> Result<Self, Self::Error> {
+        let value: VarInt = value.as_micros().try_into()?;
+        Self(value).validate().map_err(|err| err.into())
+    }
+}
+
+impl From<MinAckDelay> for Duration {
+    fn from(value: MinAckDelay) -> Self {
+        value.as_duration()
+    }
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
 //# disable_active_migration (0x0c): The disable active migration
 //#    transport parameter is included if the endpoint does not support
@@ -900,6 +967,108 @@ impl TransportParameter for MigrationSupport {
 
 impl TransportParameterValidator for MigrationSupport {}
 
+// The reset_stream_at transport parameter (0x17f7586d2cb571) is defined in
+// https://datatracker.ietf.org/doc/draft-ietf-quic-reliable-stream-reset/. It has an
+// empty value and indicates that the endpoint supports receiving RESET_STREAM_AT frames.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ResetStreamAtSupport {
+    Enabled,
+    #[default]
+    Disabled,
+}
+
+impl ResetStreamAtSupport {
+    pub const DEFAULT: Self = Self::Disabled;
+
+    /// Returns `true` if RESET_STREAM_AT frames are supported
+    #[inline]
+    pub fn is_enabled(self) -> bool {
+        matches!(self, Self::Enabled)
+    }
+}
+
+impl TransportParameter for ResetStreamAtSupport {
+    type CodecValue = ();
+
+    // Safety: the value is less than VarInt::MAX
+    const ID: TransportParameterId =
+        unsafe { TransportParameterId::new_unchecked(0x17f7586d2cb571) };
+
+    fn from_codec_value(_value: ()) -> Self {
+        ResetStreamAtSupport::Enabled
+    }
+
+    fn try_into_codec_value(&self) -> Option<&()> {
+        if let ResetStreamAtSupport::Enabled = self {
+            Some(&())
+        } else {
+            None
+        }
+    }
+
+    fn default_value() -> Self {
+        Self::default()
+    }
+}
+
+impl TransportParameterValidator for ResetStreamAtSupport {}
+
+// The initial_max_path_id transport parameter (0x0f739bbc1b666d0c) is defined in
+// https://datatracker.ietf.org/doc/draft-ietf-quic-multipath/. It is an integer value
+// indicating the maximum path identifier the endpoint is willing to maintain at connection
+// initiation. Sending the parameter indicates that the endpoint supports the multipath
+// extension. Values of 2^32 or greater are invalid.
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord)]
+pub struct InitialMaxPathId(VarInt);
+
+impl InitialMaxPathId {
+    /// Creates an `InitialMaxPathId` from the given path identifier
+    pub fn new(value: u32) -> Self {
+        Self(value.into())
+    }
+
+    /// Returns the maximum path identifier
+    pub fn as_u32(self) -> u32 {
+        // the value was validated to fit in a u32
+        self.0.as_u64() as u32
+    }
+}
+
+impl TransportParameter for InitialMaxPathId {
+    // Safety: the value is less than VarInt::MAX
+    const ID: TransportParameterId =
+        unsafe { TransportParameterId::new_unchecked(0x0f739bbc1b666d0c) };
+    type CodecValue = VarInt;
+
+    fn from_codec_value(value: Self::CodecValue) -> Self {
+        Self(value)
+    }
+
+    fn try_into_codec_value(&self) -> Option<&Self::CodecValue> {
+        Some(&self.0)
+    }
+
+    fn default_value() -> Self {
+        unimplemented!(
+            "InitialMaxPathId is an optional transport parameter, so the default is None"
+        )
+    }
+}
+
+optional_transport_parameter!(InitialMaxPathId);
+
+impl TransportParameterValidator for InitialMaxPathId {
+    fn validate(self) -> Result<Self, DecoderError> {
+        decoder_invariant!(
+            *self.0 <= u32::MAX as u64,
+            "initial_max_path_id must be less than 2^32"
+        );
+        Ok(self)
+    }
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
 //# preferred_address (0x0d):  The server's preferred address is used to
 //#    effect a change in server address at the end of the handshake, as
@@ -972,6 +1141,16 @@ impl TransportParameterValidator for PreferredAddress {
             !self.is_unspecified(),
             "at least one address needs to be specified"
         );
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
+        //# Similarly, a server MUST NOT include a zero-
+        //# length connection ID in this transport parameter.  A client MUST
+        //# treat a violation of these requirements as a connection error of
+        //# type TRANSPORT_PARAMETER_ERROR.
+        decoder_invariant!(
+            !self.connection_id.is_empty(),
+            "the connection ID must not be empty"
+        );
         Ok(self)
     }
 }
@@ -1075,6 +1254,142 @@ impl From<connection::id::LocalId> for InitialSourceConnectionId {
 connection_id_parameter!(RetrySourceConnectionId, LocalId, 0x10);
 optional_transport_parameter!(RetrySourceConnectionId);
 
+/// Carries the versions used in compatible version negotiation
+///
+/// The `version_information` transport parameter is defined in
+/// [RFC 9368](https://www.rfc-editor.org/rfc/rfc9368#section-3) as:
+///
+/// ```text
+/// Version Information {
+///   Chosen Version (32),
+///   Available Versions (32) ...,
+/// }
+/// ```
+///
+/// The Chosen Version is the version in use by the sender. The Available Versions are
+/// the versions the sender is willing to use, in order of preference for clients.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VersionInformation {
+    chosen_version: u32,
+    len: u8,
+    available_versions: [u32; VERSION_INFORMATION_MAX_LEN as usize],
+}
+
+// The maximum number of available versions that are stored from a peer's
+// `VersionInformation` transport parameter. Any additional versions are ignored.
+const VERSION_INFORMATION_MAX_LEN: u8 = 8;
+
+impl VersionInformation {
+    /// Creates a `VersionInformation` for the `chosen_version` and `available_versions`
+    pub fn new<I: IntoIterator<Item = u32>>(chosen_version: u32, available_versions: I) -> Self {
+        let mut value = Self {
+            chosen_version,
+            ..Default::default()
+        };
+
+        for version in available_versions {
+            debug_assert!(
+                value.len < VERSION_INFORMATION_MAX_LEN,
+                "Only {VERSION_INFORMATION_MAX_LEN} available versions are supported"
+            );
+            ensure!(value.len < VERSION_INFORMATION_MAX_LEN, break);
+            value.available_versions[value.len as usize] = version;
+            value.len += 1;
+        }
+
+        value
+    }
+
+    /// The version in use by the sender
+    #[inline]
+    pub fn chosen_version(&self) -> u32 {
+        self.chosen_version
+    }
+
+    /// The versions the sender is willing to use
+    #[inline]
+    pub fn available_versions(&self) -> &[u32] {
+        &self.available_versions[..self.len as usize]
+    }
+
+    /// Sets the version in use by the sender
+    #[inline]
+    pub fn set_chosen_version(&mut self, chosen_version: u32) {
+        self.chosen_version = chosen_version;
+    }
+}
+
+impl TransportParameter for VersionInformation {
+    const ID: TransportParameterId = TransportParameterId::from_u8(0x11);
+    type CodecValue = Self;
+
+    fn from_codec_value(value: Self::CodecValue) -> Self {
+        value
+    }
+
+    fn try_into_codec_value(&self) -> Option<&Self::CodecValue> {
+        Some(self)
+    }
+
+    fn default_value() -> Self {
+        unimplemented!(
+            "VersionInformation is an optional transport parameter, so the default is None"
+        )
+    }
+}
+
+optional_transport_parameter!(VersionInformation);
+
+impl EncoderValue for VersionInformation {
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&self.chosen_version);
+        for version in self.available_versions() {
+            buffer.encode(version);
+        }
+    }
+}
+
+decoder_value!(
+    impl<'a> VersionInformation {
+        fn decode(buffer: Buffer) -> Result<Self> {
+            let (chosen_version, mut buffer) = buffer.decode::<u32>()?;
+            let mut value = Self {
+                chosen_version,
+                ..Default::default()
+            };
+
+            while !buffer.is_empty() {
+                let (version, remaining) = buffer.decode::<u32>()?;
+                buffer = remaining;
+
+                // Versions are still decoded past the maximum in order to validate them
+                if value.len < VERSION_INFORMATION_MAX_LEN {
+                    value.available_versions[value.len as usize] = version;
+                    value.len += 1;
+                } else {
+                    decoder_invariant!(version != 0, "available version cannot be 0");
+                }
+            }
+
+            Ok((value, buffer))
+        }
+    }
+);
+
+impl TransportParameterValidator for VersionInformation {
+    fn validate(self) -> Result<Self, DecoderError> {
+        // An endpoint receiving a Chosen Version equal to zero, or any Available
+        // Version equal to zero, MUST treat it as a parsing failure.
+        // See <https://www.rfc-editor.org/rfc/rfc9368#section-3>
+        decoder_invariant!(self.chosen_version != 0, "chosen version cannot be 0");
+        decoder_invariant!(
+            !self.available_versions().contains(&0),
+            "available version cannot be 0"
+        );
+        Ok(self)
+    }
+}
+
 /// Used by the client to indicate which versions of s2n-quic-dc it supports
 /// and by the server to indicate which version it is using
 #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Eq, Ord)]
@@ -1566,7 +1881,11 @@ impl_transport_parameters!(
         preferred_address: PreferredAddress,
         initial_source_connection_id: Option<InitialSourceConnectionId>,
         retry_source_connection_id: RetrySourceConnectionId,
+        version_information: Option<VersionInformation>,
         dc_supported_versions: DcSupportedVersions,
+        min_ack_delay: Option<MinAckDelay>,
+        reset_stream_at_support: ResetStreamAtSupport,
+        initial_max_path_id: Option<InitialMaxPathId>,
     }
 );
 
@@ -1615,5 +1934,8 @@ impl<
         load!(max_active_connection_ids, active_connection_id_limit);
         load!(max_datagram_frame_size, max_datagram_frame_size);
         load!(migration_support, migration_support);
+        self.min_ack_delay = limits.min_ack_delay();
+        load!(reset_stream_at_support, reset_stream_at_support);
+        load!(initial_max_path_id, initial_max_path_id);
     }
 }
@@ -80,10 +80,14 @@ fn server_transport_parameters() -> ServerTransportParameters {
         }),
         initial_source_connection_id: Some([1, 2, 3, 4][..].try_into().unwrap()),
         retry_source_connection_id: Some([1, 2, 3, 4][..].try_into().unwrap()),
+        version_information: Some(VersionInformation::new(1, [1, 2])),
         dc_supported_versions: DcSupportedVersions {
             len: 1,
             versions: [3, 0, 0, 0],
         },
+        min_ack_delay: MinAckDelay::new(Duration::from_millis(1)),
+        reset_stream_at_support: ResetStreamAtSupport::Enabled,
+        initial_max_path_id: Some(InitialMaxPathId::new(3)),
     }
 }
 
@@ -121,10 +125,14 @@ fn client_transport_parameters() -> ClientTransportParameters {
         preferred_address: Default::default(),
         initial_source_connection_id: Some([1, 2, 3, 4][..].try_into().unwrap()),
         retry_source_connection_id: Default::default(),
+        version_information: Some(VersionInformation::new(2, [2, 1])),
         dc_supported_versions: DcSupportedVersions {
             len: 4,
             versions: [1, 2, 3, 4],
         },
+        min_ack_delay: MinAckDelay::new(Duration::from_millis(1)),
+        reset_stream_at_support: ResetStreamAtSupport::Enabled,
+        initial_max_path_id: Some(InitialMaxPathId::new(3)),
     }
 }
 
@@ -188,6 +196,43 @@ fn ignore_unknown_parameter() {
     assert_eq!(0, remaining.len());
 }
 
+#[test]
+fn version_information_test() {
+    fn decode(bytes: &[u8]) -> Result<VersionInformation, DecoderError> {
+        let (value, remaining) = DecoderBuffer::new(bytes).decode::<VersionInformation>()?;
+        assert!(remaining.is_empty());
+        value.validate()
+    }
+
+    let value = decode(&[0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 2]).unwrap();
+    assert_eq!(value.chosen_version(), 1);
+    assert_eq!(value.available_versions(), &[1, 2]);
+
+    let value = decode(&[0, 0, 0, 1]).unwrap();
+    assert!(value.available_versions().is_empty());
+
+    // the chosen version is required
+    assert!(decode(&[]).is_err());
+    // versions are 32 bits
+    assert!(decode(&[0, 0, 0, 1, 0, 0]).is_err());
+    // zero is not a valid chosen or available version
+    assert!(decode(&[0, 0, 0, 0, 0, 0, 0, 1]).is_err());
+    assert!(decode(&[0, 0, 0, 1, 0, 0, 0, 0]).is_err());
+
+    // available versions past the maximum are validated and ignored
+    let mut bytes = vec![0, 0, 0, 1];
+    for _ in 0..=VERSION_INFORMATION_MAX_LEN {
+        bytes.extend_from_slice(&[0, 0, 0, 1]);
+    }
+    let value = decode(&bytes).unwrap();
+    assert_eq!(
+        value.available_versions().len(),
+        VERSION_INFORMATION_MAX_LEN as usize
+    );
+    bytes.extend_from_slice(&[0, 0, 0, 0]);
+    assert!(decode(&bytes).is_err());
+}
+
 #[test]
 fn compute_data_window_test() {
     assert_eq!(
@@ -334,3 +379,24 @@ fn dc_selected_version() {
         .selected_version()
         .is_err());
 }
+
+#[test]
+fn preferred_address_empty_connection_id() {
+    use s2n_codec::EncoderBuffer;
+
+    let mut value = server_transport_parameters();
+    value.preferred_address = Some(PreferredAddress {
+        ipv4_address: Some(SocketAddressV4::new([127, 0, 0, 1], 1337)),
+        ipv6_address: None,
+        connection_id: [][..].try_into().unwrap(),
+        stateless_reset_token: [1; 16].into(),
+    });
+
+    let mut buffer = vec![0; 32 * 1024];
+    let mut encoder = EncoderBuffer::new(&mut buffer);
+    encoder.encode(&value);
+
+    let (encoded, _) = encoder.split_off();
+    let decoder = DecoderBuffer::new(encoded);
+    assert!(ServerTransportParameters::decode(decoder).is_err());
+}
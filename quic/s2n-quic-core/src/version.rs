@@ -0,0 +1,287 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! QUIC version numbers and the configuration of versions enabled on an endpoint
+
+use core::fmt;
+
+pub type Version = u32;
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-15
+//# This version of the specification is identified by the number
+//# 0x00000001.
+pub const V1: Version = 0x0000_0001;
+
+/// QUIC Version 2, as defined in [RFC 9369](https://www.rfc-editor.org/rfc/rfc9369#section-3.1)
+pub const V2: Version = 0x6b33_43cf;
+
+/// All of the QUIC versions implemented, in order of preference
+pub const SUPPORTED_VERSIONS: [Version; 2] = [V1, V2];
+
+/// Returns `true` if the version is implemented
+#[inline]
+pub fn is_supported(version: Version) -> bool {
+    SUPPORTED_VERSIONS.contains(&version)
+}
+
+/// Returns `true` if a connection attempt using the `original` version can be
+/// switched to the `negotiated` version without an additional round trip
+///
+/// QUIC version 1 and QUIC version 2 are compatible with each other, as described
+/// in [RFC 9369](https://www.rfc-editor.org/rfc/rfc9369#section-4).
+#[inline]
+pub fn is_compatible(original: Version, negotiated: Version) -> bool {
+    original == negotiated || (is_supported(original) && is_supported(negotiated))
+}
+
+/// The QUIC versions enabled on an endpoint, in order of preference
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    versions: [Version; SUPPORTED_VERSIONS.len()],
+    len: u8,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            versions: SUPPORTED_VERSIONS,
+            len: SUPPORTED_VERSIONS.len() as _,
+        }
+    }
+}
+
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Config")
+            .field("versions", &self.versions())
+            .finish()
+    }
+}
+
+impl Config {
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// The version used by clients when initiating a connection
+    #[inline]
+    pub fn initial_version(&self) -> Version {
+        self.versions[0]
+    }
+
+    /// The enabled versions, in order of preference
+    #[inline]
+    pub fn versions(&self) -> &[Version] {
+        &self.versions[..self.len as usize]
+    }
+
+    /// Returns `true` if the version is enabled
+    #[inline]
+    pub fn contains(&self, version: Version) -> bool {
+        self.versions().contains(&version)
+    }
+
+    /// Selects the version for a connection attempt using the `original` version
+    ///
+    /// The most preferred enabled version that is compatible with the `original` version
+    /// and included in the peer's `available_versions` is returned. If no such version exists,
+    /// the `original` version is returned.
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc9368#section-2.3>
+    #[inline]
+    pub fn select_compatible(&self, original: Version, available_versions: &[Version]) -> Version {
+        self.versions()
+            .iter()
+            .copied()
+            .find(|version| {
+                is_compatible(original, *version) && available_versions.contains(version)
+            })
+            .unwrap_or(original)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Builder {
+    versions: Option<Config>,
+}
+
+impl Builder {
+    /// Sets the QUIC versions enabled on the endpoint, in order of preference
+    /// (default: [`V1`], [`V2`])
+    ///
+    /// Clients initiate connections with the first version in the list. Servers accept
+    /// connection attempts for any of the listed versions and respond to all others with
+    /// a Version Negotiation packet.
+    pub fn with_versions(mut self, versions: &[Version]) -> Result<Self, Error> {
+        let mut config = Config {
+            versions: [0; SUPPORTED_VERSIONS.len()],
+            len: 0,
+        };
+
+        for &version in versions {
+            ensure!(is_supported(version), Err(Error::Unsupported(version)));
+            ensure!(!config.contains(version), Err(Error::Duplicate(version)));
+            config.versions[config.len as usize] = version;
+            config.len += 1;
+        }
+
+        ensure!(config.len > 0, Err(Error::Empty));
+
+        self.versions = Some(config);
+        Ok(self)
+    }
+
+    /// Sets the version used by clients when initiating a connection (default: [`V1`])
+    ///
+    /// The version is moved to the front of the enabled versions, and enabled if it
+    /// wasn't already.
+    pub fn with_initial_version(mut self, version: Version) -> Result<Self, Error> {
+        ensure!(is_supported(version), Err(Error::Unsupported(version)));
+
+        let mut config = self.versions.unwrap_or_default();
+        let len = config.len as usize;
+
+        if let Some(index) = config.versions().iter().position(|v| *v == version) {
+            config.versions[..=index].rotate_right(1);
+        } else {
+            config.versions.copy_within(..len, 1);
+            config.versions[0] = version;
+            config.len += 1;
+        }
+
+        self.versions = Some(config);
+        Ok(self)
+    }
+
+    pub fn build(self) -> Result<Config, Error> {
+        Ok(self.versions.unwrap_or_default())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The version is not implemented
+    Unsupported(Version),
+    /// The version was specified more than once
+    Duplicate(Version),
+    /// No versions were specified
+    Empty,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported(version) => write!(f, "unsupported QUIC version {version:#010x}"),
+            Self::Duplicate(version) => write!(f, "duplicate QUIC version {version:#010x}"),
+            Self::Empty => write!(f, "at least one QUIC version must be enabled"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_test() {
+        let config = Config::default();
+        assert_eq!(config.initial_version(), V1);
+        assert_eq!(config.versions(), &[V1, V2]);
+        assert_eq!(Config::builder().build().unwrap(), config);
+    }
+
+    #[test]
+    fn with_versions_test() {
+        let config = Config::builder()
+            .with_versions(&[V2])
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(config.initial_version(), V2);
+        assert_eq!(config.versions(), &[V2]);
+        assert!(!config.contains(V1));
+
+        let config = Config::builder()
+            .with_versions(&[V2, V1])
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(config.versions(), &[V2, V1]);
+
+        assert_eq!(
+            Config::builder().with_versions(&[]).unwrap_err(),
+            Error::Empty
+        );
+        assert_eq!(
+            Config::builder().with_versions(&[V1, V1]).unwrap_err(),
+            Error::Duplicate(V1)
+        );
+        assert_eq!(
+            Config::builder().with_versions(&[0xff00_001d]).unwrap_err(),
+            Error::Unsupported(0xff00_001d)
+        );
+    }
+
+    #[test]
+    fn select_compatible_test() {
+        let config = Config::default();
+        assert_eq!(config.select_compatible(V1, &[V1, V2]), V1);
+        assert_eq!(config.select_compatible(V2, &[V2, V1]), V1);
+        assert_eq!(config.select_compatible(V2, &[V2]), V2);
+        assert_eq!(config.select_compatible(V1, &[]), V1);
+
+        let config = Config::builder()
+            .with_versions(&[V2, V1])
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(config.select_compatible(V1, &[V1, V2]), V2);
+        assert_eq!(config.select_compatible(V1, &[V1]), V1);
+        // unknown versions are not compatible
+        assert_eq!(
+            config.select_compatible(0xff00_001d, &[V1, V2]),
+            0xff00_001d
+        );
+
+        assert!(is_compatible(V1, V2));
+        assert!(is_compatible(V2, V1));
+        assert!(!is_compatible(V1, 0xff00_001d));
+    }
+
+    #[test]
+    fn with_initial_version_test() {
+        let config = Config::builder()
+            .with_initial_version(V2)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(config.versions(), &[V2, V1]);
+
+        let config = Config::builder()
+            .with_versions(&[V1])
+            .unwrap()
+            .with_initial_version(V2)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(config.versions(), &[V2, V1]);
+
+        let config = Config::builder()
+            .with_versions(&[V2, V1])
+            .unwrap()
+            .with_initial_version(V2)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(config.versions(), &[V2, V1]);
+
+        assert_eq!(
+            Config::builder().with_initial_version(0).unwrap_err(),
+            Error::Unsupported(0)
+        );
+    }
+}
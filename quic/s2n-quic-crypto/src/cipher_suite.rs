@@ -6,6 +6,7 @@ use core::fmt;
 use s2n_quic_core::{
     assume,
     crypto::{label, packet_protection, scatter},
+    version,
 };
 use zeroize::{Zeroize, Zeroizing};
 
@@ -23,10 +24,8 @@ macro_rules! impl_cipher_suite {
         $cipher:path,
         $cipher_key_len:expr,
         $header_protection:path,
-        $key_label:expr,
-        $iv_label:expr,
-        $hp_label:expr,
-        $key_update_label:expr,
+        [$key_label:expr, $iv_label:expr, $hp_label:expr, $key_update_label:expr $(,)?],
+        [$v2_key_label:expr, $v2_iv_label:expr, $v2_hp_label:expr, $v2_key_update_label:expr $(,)?],
         $confidentiality_limit:expr,
         $integrity_limit:expr,
         $test_name:ident
@@ -40,24 +39,61 @@ macro_rules! impl_cipher_suite {
 
             type Key = platform::$lower::Key;
 
+            /// The labels used to derive keys from a secret for a QUIC version
+            struct Labels {
+                key: &'static [u8],
+                iv: &'static [u8],
+                hp: &'static [u8],
+                key_update: &'static [u8],
+            }
+
+            static V1_LABELS: Labels = Labels {
+                key: &$key_label,
+                iv: &$iv_label,
+                hp: &$hp_label,
+                key_update: &$key_update_label,
+            };
+
+            /// See <https://www.rfc-editor.org/rfc/rfc9369#section-3.3.2>
+            static V2_LABELS: Labels = Labels {
+                key: &$v2_key_label,
+                iv: &$v2_iv_label,
+                hp: &$v2_hp_label,
+                key_update: &$v2_key_update_label,
+            };
+
             // ignore casing warnings in order to preserve the IANA name
             #[allow(non_camel_case_types, clippy::all)]
             pub struct $name {
                 secret: hkdf::Prk,
                 iv: iv::Iv,
                 key: Key,
+                labels: &'static Labels,
             }
 
             impl $name {
-                pub fn new(secret: hkdf::Prk) -> (Self, HeaderKey) {
-                    let iv = Self::new_iv(&secret);
+                /// Creates a cipher_suite with the given secret, using the key derivation
+                /// labels of the provided QUIC version
+                pub fn new(version: version::Version, secret: hkdf::Prk) -> (Self, HeaderKey) {
+                    let labels = if version == version::V2 {
+                        &V2_LABELS
+                    } else {
+                        &V1_LABELS
+                    };
+
+                    let iv = Self::new_iv(&secret, labels);
                     let key = {
-                        let secret = Self::new_key_secret(&secret);
+                        let secret = Self::new_key_secret(&secret, labels);
                         Key::new(&*secret)
                     };
-                    let header_key = Self::new_header_key(&secret);
+                    let header_key = Self::new_header_key(&secret, labels);
 
-                    let key = Self { secret, iv, key };
+                    let key = Self {
+                        secret,
+                        iv,
+                        key,
+                        labels,
+                    };
 
                     (key, header_key)
                 }
@@ -68,25 +104,31 @@ macro_rules! impl_cipher_suite {
                 pub fn update(&self) -> Self {
                     let secret: hkdf::Prk = self
                         .secret
-                        .expand(&[&$key_update_label], $digest)
+                        .expand(&[self.labels.key_update], $digest)
                         .expect("label size verified")
                         .into();
 
-                    let iv = Self::new_iv(&secret);
+                    let labels = self.labels;
+                    let iv = Self::new_iv(&secret, labels);
                     let key = {
-                        let key = Self::new_key_secret(&secret);
+                        let key = Self::new_key_secret(&secret, labels);
                         // ask the existing key to derive the next one so it can persist any
                         // configuration
                         self.key.update(&*key)
                     };
-                    Self { secret, iv, key }
+                    Self {
+                        secret,
+                        iv,
+                        key,
+                        labels,
+                    }
                 }
 
-                fn new_key_secret(secret: &hkdf::Prk) -> Zeroizing<[u8; KEY_LEN]> {
+                fn new_key_secret(secret: &hkdf::Prk, labels: &Labels) -> Zeroizing<[u8; KEY_LEN]> {
                     let mut key = Zeroizing::new([0u8; KEY_LEN]);
 
                     secret
-                        .expand(&[&$key_label], &$cipher)
+                        .expand(&[labels.key], &$cipher)
                         .expect("label size verified")
                         .fill(&mut key.as_mut())
                         .expect("fill size verified");
@@ -94,12 +136,12 @@ macro_rules! impl_cipher_suite {
                     key
                 }
 
-                fn new_iv(secret: &hkdf::Prk) -> iv::Iv {
-                    iv::Iv::new(secret, &$iv_label)
+                fn new_iv(secret: &hkdf::Prk, labels: &Labels) -> iv::Iv {
+                    iv::Iv::new(secret, labels.iv)
                 }
 
-                fn new_header_key(secret: &hkdf::Prk) -> HeaderKey {
-                    HeaderKey::new::<{ KEY_LEN }>(secret, &$hp_label, &$header_protection)
+                fn new_header_key(secret: &hkdf::Prk, labels: &Labels) -> HeaderKey {
+                    HeaderKey::new::<{ KEY_LEN }>(secret, labels.hp, &$header_protection)
                 }
             }
 
@@ -118,7 +160,28 @@ macro_rules! impl_cipher_suite {
                     header: &[u8],
                     payload: &mut [u8],
                 ) -> Result<(), packet_protection::Error> {
-                    let nonce = self.iv.nonce(packet_number);
+                    self.decrypt_for_path(0, packet_number, header, payload)
+                }
+
+                #[inline]
+                fn encrypt(
+                    &mut self,
+                    packet_number: u64,
+                    header: &[u8],
+                    payload: &mut scatter::Buffer,
+                ) -> Result<(), packet_protection::Error> {
+                    self.encrypt_for_path(0, packet_number, header, payload)
+                }
+
+                #[inline]
+                fn decrypt_for_path(
+                    &self,
+                    path_id: u32,
+                    packet_number: u64,
+                    header: &[u8],
+                    payload: &mut [u8],
+                ) -> Result<(), packet_protection::Error> {
+                    let nonce = self.iv.nonce(path_id, packet_number);
 
                     let payload_len = payload
                         .len()
@@ -141,13 +204,14 @@ macro_rules! impl_cipher_suite {
                 }
 
                 #[inline]
-                fn encrypt(
+                fn encrypt_for_path(
                     &mut self,
+                    path_id: u32,
                     packet_number: u64,
                     header: &[u8],
                     payload: &mut scatter::Buffer,
                 ) -> Result<(), packet_protection::Error> {
-                    let nonce = self.iv.nonce(packet_number);
+                    let nonce = self.iv.nonce(path_id, packet_number);
                     self.key.encrypt(&nonce, header, payload)?;
                     Ok(())
                 }
@@ -234,6 +298,33 @@ macro_rules! impl_cipher_suite {
                     $key_update_label,
                     "key update label mismatch"
                 );
+
+                assert_eq!(
+                    compute_vec_label($cipher.key_len(), b"quicv2 key"),
+                    $v2_key_label,
+                    "v2 key label mismatch"
+                );
+
+                assert_eq!(
+                    compute_vec_label(iv::NONCE_LEN, b"quicv2 iv"),
+                    $v2_iv_label,
+                    "v2 iv label mismatch"
+                );
+
+                assert_eq!(
+                    compute_vec_label($header_protection.key_len(), b"quicv2 hp"),
+                    $v2_hp_label,
+                    "v2 hp label mismatch"
+                );
+
+                assert_eq!(
+                    compute_vec_label(
+                        $digest.hmac_algorithm().digest_algorithm().output_len(),
+                        b"quicv2 ku"
+                    ),
+                    $v2_key_update_label,
+                    "v2 key update label mismatch"
+                );
             }
         }
 
@@ -251,10 +342,18 @@ impl_cipher_suite!(
     aead::AES_256_GCM,
     256 / 8, // 256-bit key
     aead::quic::AES_256,
-    label::QUIC_KEY_32,
-    label::QUIC_IV_12,
-    label::QUIC_HP_32,
-    label::QUIC_KU_48,
+    [
+        label::QUIC_KEY_32,
+        label::QUIC_IV_12,
+        label::QUIC_HP_32,
+        label::QUIC_KU_48,
+    ],
+    [
+        label::QUICV2_KEY_32,
+        label::QUICV2_IV_12,
+        label::QUICV2_HP_32,
+        label::QUICV2_KU_48,
+    ],
     u64::pow(2, 23), // Confidentiality limit
     u64::pow(2, 52), // Integrity limit
     tls_aes_256_gcm_sha384_test
@@ -271,10 +370,18 @@ impl_cipher_suite!(
     aead::CHACHA20_POLY1305,
     256 / 8, // 256-bit key
     aead::quic::CHACHA20,
-    label::QUIC_KEY_32,
-    label::QUIC_IV_12,
-    label::QUIC_HP_32,
-    label::QUIC_KU_32,
+    [
+        label::QUIC_KEY_32,
+        label::QUIC_IV_12,
+        label::QUIC_HP_32,
+        label::QUIC_KU_32,
+    ],
+    [
+        label::QUICV2_KEY_32,
+        label::QUICV2_IV_12,
+        label::QUICV2_HP_32,
+        label::QUICV2_KU_32,
+    ],
     u64::pow(2, 62), // Confidentiality limit even though specification notes it can be disregarded
     u64::pow(2, 36), // Integrity limit
     tls_chacha20_poly1305_sha256_test
@@ -288,10 +395,18 @@ impl_cipher_suite!(
     aead::AES_128_GCM,
     128 / 8, // 128-bit key
     aead::quic::AES_128,
-    label::QUIC_KEY_16,
-    label::QUIC_IV_12,
-    label::QUIC_HP_16,
-    label::QUIC_KU_32,
+    [
+        label::QUIC_KEY_16,
+        label::QUIC_IV_12,
+        label::QUIC_HP_16,
+        label::QUIC_KU_32,
+    ],
+    [
+        label::QUICV2_KEY_16,
+        label::QUICV2_IV_12,
+        label::QUICV2_HP_16,
+        label::QUICV2_KU_32,
+    ],
     u64::pow(2, 23), // Confidentiality limit
     u64::pow(2, 52), // Integrity limit
     tls_aes_128_gcm_sha256_test
@@ -8,7 +8,10 @@ use crate::{
     hkdf,
 };
 use core::fmt;
-use s2n_quic_core::crypto::{self, packet_protection, scatter};
+use s2n_quic_core::{
+    crypto::{self, packet_protection, scatter},
+    version,
+};
 
 // ignore casing warnings in order to preserve the IANA name
 #[allow(non_camel_case_types, clippy::all)]
@@ -48,19 +51,23 @@ impl From<TLS_AES_128_GCM_SHA256> for NegotiatedCipherSuite {
 }
 
 impl NegotiatedCipherSuite {
-    /// Create a cipher_suite with a given negotiated algorithm and secret
-    pub fn new(algorithm: &aead::Algorithm, secret: hkdf::Prk) -> Option<(Self, HeaderKey)> {
+    /// Create a cipher_suite for a QUIC version with a given negotiated algorithm and secret
+    pub fn new(
+        version: version::Version,
+        algorithm: &aead::Algorithm,
+        secret: hkdf::Prk,
+    ) -> Option<(Self, HeaderKey)> {
         Some(match algorithm {
             _ if algorithm == &aead::AES_256_GCM => {
-                let (cipher_suite, header_key) = TLS_AES_256_GCM_SHA384::new(secret);
+                let (cipher_suite, header_key) = TLS_AES_256_GCM_SHA384::new(version, secret);
                 (cipher_suite.into(), header_key)
             }
             _ if algorithm == &aead::CHACHA20_POLY1305 => {
-                let (cipher_suite, header_key) = TLS_CHACHA20_POLY1305_SHA256::new(secret);
+                let (cipher_suite, header_key) = TLS_CHACHA20_POLY1305_SHA256::new(version, secret);
                 (cipher_suite.into(), header_key)
             }
             _ if algorithm == &aead::AES_128_GCM => {
-                let (cipher_suite, header_key) = TLS_AES_128_GCM_SHA256::new(secret);
+                let (cipher_suite, header_key) = TLS_AES_128_GCM_SHA256::new(version, secret);
                 (cipher_suite.into(), header_key)
             }
             _ => return None,
@@ -103,6 +110,38 @@ impl crypto::Key for NegotiatedCipherSuite {
         ))
     }
 
+    #[inline]
+    fn decrypt_for_path(
+        &self,
+        path_id: u32,
+        packet_number: u64,
+        header: &[u8],
+        payload: &mut [u8],
+    ) -> Result<(), packet_protection::Error> {
+        dispatch!(self, |cipher| cipher.decrypt_for_path(
+            path_id,
+            packet_number,
+            header,
+            payload
+        ))
+    }
+
+    #[inline]
+    fn encrypt_for_path(
+        &mut self,
+        path_id: u32,
+        packet_number: u64,
+        header: &[u8],
+        payload: &mut scatter::Buffer,
+    ) -> Result<(), packet_protection::Error> {
+        dispatch!(self, |cipher| cipher.encrypt_for_path(
+            path_id,
+            packet_number,
+            header,
+            payload
+        ))
+    }
+
     #[inline]
     fn tag_len(&self) -> usize {
         dispatch!(self, |cipher| cipher.tag_len())
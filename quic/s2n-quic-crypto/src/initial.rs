@@ -6,9 +6,9 @@ use s2n_quic_core::{
     crypto::{
         self,
         label::{CLIENT_IN, SERVER_IN},
-        packet_protection, scatter, Key, INITIAL_SALT,
+        packet_protection, scatter, Key, INITIAL_SALT, INITIAL_SALT_V2,
     },
-    endpoint,
+    endpoint, version,
 };
 
 header_key!(InitialHeaderKey);
@@ -24,12 +24,24 @@ pub struct InitialKey {
 lazy_static::lazy_static! {
     /// Compute the Initial salt once, as the seed is constant
     static ref INITIAL_SIGNING_KEY: hkdf::Salt = hkdf::Salt::new(hkdf::HKDF_SHA256, &INITIAL_SALT);
+
+    /// Compute the QUIC version 2 Initial salt once, as the seed is constant
+    static ref INITIAL_SIGNING_KEY_V2: hkdf::Salt = hkdf::Salt::new(hkdf::HKDF_SHA256, &INITIAL_SALT_V2);
 }
 
 impl InitialKey {
-    fn new(endpoint: endpoint::Type, connection_id: &[u8]) -> (Self, InitialHeaderKey) {
-        let initial_secret = INITIAL_SIGNING_KEY.extract(connection_id);
-        let digest = INITIAL_SIGNING_KEY.algorithm();
+    fn new(
+        endpoint: endpoint::Type,
+        version: version::Version,
+        connection_id: &[u8],
+    ) -> (Self, InitialHeaderKey) {
+        let signing_key: &hkdf::Salt = if version == version::V2 {
+            &INITIAL_SIGNING_KEY_V2
+        } else {
+            &INITIAL_SIGNING_KEY
+        };
+        let initial_secret = signing_key.extract(connection_id);
+        let digest = signing_key.algorithm();
 
         let client_secret = initial_secret
             .expand(&[&CLIENT_IN], digest)
@@ -43,12 +55,12 @@ impl InitialKey {
 
         let (sealer, opener) = match endpoint {
             endpoint::Type::Client => (
-                CipherSuite::new(client_secret),
-                CipherSuite::new(server_secret),
+                CipherSuite::new(version, client_secret),
+                CipherSuite::new(version, server_secret),
             ),
             endpoint::Type::Server => (
-                CipherSuite::new(server_secret),
-                CipherSuite::new(client_secret),
+                CipherSuite::new(version, server_secret),
+                CipherSuite::new(version, client_secret),
             ),
         };
 
@@ -70,12 +82,12 @@ impl InitialKey {
 impl crypto::InitialKey for InitialKey {
     type HeaderKey = InitialHeaderKey;
 
-    fn new_server(connection_id: &[u8]) -> (Self, Self::HeaderKey) {
-        Self::new(endpoint::Type::Server, connection_id)
+    fn new_server(version: u32, connection_id: &[u8]) -> (Self, Self::HeaderKey) {
+        Self::new(endpoint::Type::Server, version, connection_id)
     }
 
-    fn new_client(connection_id: &[u8]) -> (Self, Self::HeaderKey) {
-        Self::new(endpoint::Type::Client, connection_id)
+    fn new_client(version: u32, connection_id: &[u8]) -> (Self, Self::HeaderKey) {
+        Self::new(endpoint::Type::Client, version, connection_id)
     }
 }
 
@@ -124,14 +136,14 @@ impl Key for InitialKey {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use s2n_codec::{DecoderBufferMut, EncoderBuffer};
+    use s2n_codec::{DecoderBufferMut, Encoder as _, EncoderBuffer};
     use s2n_quic_core::{
         connection::id::ConnectionInfo,
         crypto::{
             initial::{
                 EXAMPLE_CLIENT_INITIAL_PAYLOAD, EXAMPLE_CLIENT_INITIAL_PROTECTED_PACKET,
-                EXAMPLE_DCID, EXAMPLE_SERVER_INITIAL_PAYLOAD,
-                EXAMPLE_SERVER_INITIAL_PROTECTED_PACKET,
+                EXAMPLE_CLIENT_INITIAL_SECRET_V2, EXAMPLE_DCID, EXAMPLE_SERVER_INITIAL_PAYLOAD,
+                EXAMPLE_SERVER_INITIAL_PROTECTED_PACKET, EXAMPLE_SERVER_INITIAL_SECRET_V2,
             },
             InitialKey as _,
         },
@@ -142,8 +154,8 @@ mod tests {
     #[test]
     fn rfc_example_server_test() {
         test_round_trip(
-            &mut InitialKey::new_client(&EXAMPLE_DCID),
-            &InitialKey::new_server(&EXAMPLE_DCID),
+            &mut InitialKey::new_client(version::V1, &EXAMPLE_DCID),
+            &InitialKey::new_server(version::V1, &EXAMPLE_DCID),
             &EXAMPLE_CLIENT_INITIAL_PROTECTED_PACKET,
             &EXAMPLE_CLIENT_INITIAL_PAYLOAD,
         );
@@ -152,13 +164,54 @@ mod tests {
     #[test]
     fn rfc_example_client_test() {
         test_round_trip(
-            &mut InitialKey::new_server(&EXAMPLE_DCID),
-            &InitialKey::new_client(&EXAMPLE_DCID),
+            &mut InitialKey::new_server(version::V1, &EXAMPLE_DCID),
+            &InitialKey::new_client(version::V1, &EXAMPLE_DCID),
             &EXAMPLE_SERVER_INITIAL_PROTECTED_PACKET,
             &EXAMPLE_SERVER_INITIAL_PAYLOAD,
         );
     }
 
+    #[test]
+    fn rfc_example_v2_secrets_test() {
+        let initial_secret = INITIAL_SIGNING_KEY_V2.extract(&EXAMPLE_DCID);
+        let digest = INITIAL_SIGNING_KEY_V2.algorithm();
+
+        for (label, expected) in [
+            (&CLIENT_IN, &EXAMPLE_CLIENT_INITIAL_SECRET_V2),
+            (&SERVER_IN, &EXAMPLE_SERVER_INITIAL_SECRET_V2),
+        ] {
+            let mut secret = [0u8; 32];
+            initial_secret
+                .expand(&[label], digest)
+                .unwrap()
+                .fill(&mut secret)
+                .unwrap();
+            assert_eq!(&secret, expected);
+        }
+    }
+
+    #[test]
+    fn v2_keys_test() {
+        let (mut client_key, _) = InitialKey::new_client(version::V2, &EXAMPLE_DCID);
+        let (server_key, _) = InitialKey::new_server(version::V2, &EXAMPLE_DCID);
+        let (v1_server_key, _) = InitialKey::new_server(version::V1, &EXAMPLE_DCID);
+
+        let header = [1u8, 2, 3];
+        let mut payload = [0u8; 32];
+        {
+            let mut payload = EncoderBuffer::new(&mut payload);
+            payload.write_repeated(16, 1);
+            let mut payload = scatter::Buffer::new(payload);
+            client_key.encrypt(0, &header, &mut payload).unwrap();
+        }
+
+        // the QUIC version 1 keys should not be able to open version 2 packets
+        assert!(v1_server_key
+            .decrypt(0, &header, &mut payload.clone())
+            .is_err());
+        assert!(server_key.decrypt(0, &header, &mut payload).is_ok());
+    }
+
     fn test_round_trip(
         sealer: &mut (InitialKey, InitialHeaderKey),
         opener: &(InitialKey, InitialHeaderKey),
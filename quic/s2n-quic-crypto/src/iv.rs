@@ -23,12 +23,17 @@ impl Iv {
         Self(bytes)
     }
 
+    /// Computes the nonce for a packet in the packet number space of the given path
+    ///
+    /// The 32-bit path identifier is encoded in front of the 64-bit packet number, which leaves
+    /// the nonce unchanged for the initial path. See
+    /// <https://datatracker.ietf.org/doc/draft-ietf-quic-multipath/>
     #[inline]
-    pub fn nonce(&self, packet_number: u64) -> [u8; NONCE_LEN] {
+    pub fn nonce(&self, path_id: u32, packet_number: u64) -> [u8; NONCE_LEN] {
         let mut nonce = [0; NONCE_LEN];
         let mut encoder = EncoderBuffer::new(&mut nonce);
 
-        encoder.encode(&0u32);
+        encoder.encode(&path_id);
         encoder.encode(&packet_number);
 
         for (a, b) in nonce.iter_mut().zip(self.0.iter()) {
@@ -7,7 +7,7 @@ use crate::{
 };
 use s2n_quic_core::{
     crypto::{packet_protection, scatter, Key},
-    endpoint,
+    endpoint, version,
 };
 
 #[derive(Debug)]
@@ -19,6 +19,7 @@ pub struct KeyPair {
 impl KeyPair {
     pub fn new(
         endpoint: endpoint::Type,
+        version: version::Version,
         algorithm: &Algorithm,
         secrets: SecretPair,
     ) -> Option<(Self, HeaderKeyPair)> {
@@ -27,8 +28,8 @@ impl KeyPair {
             endpoint::Type::Server => (secrets.server, secrets.client),
         };
 
-        let (sealer, header_sealer) = CipherSuite::new(algorithm, sealer_secret)?;
-        let (opener, header_opener) = CipherSuite::new(algorithm, opener_secret)?;
+        let (sealer, header_sealer) = CipherSuite::new(version, algorithm, sealer_secret)?;
+        let (opener, header_opener) = CipherSuite::new(version, algorithm, opener_secret)?;
 
         let key = Self { sealer, opener };
         let header_key = HeaderKeyPair {
@@ -71,6 +72,30 @@ impl Key for KeyPair {
         self.sealer.encrypt(packet_number, header, payload)
     }
 
+    #[inline]
+    fn decrypt_for_path(
+        &self,
+        path_id: u32,
+        packet_number: u64,
+        header: &[u8],
+        payload: &mut [u8],
+    ) -> Result<(), packet_protection::Error> {
+        self.opener
+            .decrypt_for_path(path_id, packet_number, header, payload)
+    }
+
+    #[inline]
+    fn encrypt_for_path(
+        &mut self,
+        path_id: u32,
+        packet_number: u64,
+        header: &[u8],
+        payload: &mut scatter::Buffer,
+    ) -> Result<(), packet_protection::Error> {
+        self.sealer
+            .encrypt_for_path(path_id, packet_number, header, payload)
+    }
+
     #[inline]
     fn tag_len(&self) -> usize {
         self.sealer.tag_len()
@@ -119,9 +144,20 @@ macro_rules! negotiated_crypto {
                 endpoint: s2n_quic_core::endpoint::Type,
                 algorithm: &$crate::aws_lc_aead::Algorithm,
                 secrets: $crate::SecretPair,
+            ) -> Option<(Self, $header_key)> {
+                Self::new_with_version(endpoint, s2n_quic_core::version::V1, algorithm, secrets)
+            }
+
+            /// Create a cipher_suite for an endpoint type and QUIC version with a given
+            /// negotiated algorithm and secret
+            pub fn new_with_version(
+                endpoint: s2n_quic_core::endpoint::Type,
+                version: s2n_quic_core::version::Version,
+                algorithm: &$crate::aws_lc_aead::Algorithm,
+                secrets: $crate::SecretPair,
             ) -> Option<(Self, $header_key)> {
                 let (key, header_key) =
-                    crate::negotiated::KeyPair::new(endpoint, algorithm, secrets)?;
+                    crate::negotiated::KeyPair::new(endpoint, version, algorithm, secrets)?;
 
                 let key = Self(key);
                 let header_key = $header_key::from(header_key);
@@ -158,6 +194,30 @@ macro_rules! negotiated_crypto {
                 self.0.encrypt(packet_number, header, payload)
             }
 
+            #[inline]
+            fn decrypt_for_path(
+                &self,
+                path_id: u32,
+                packet_number: u64,
+                header: &[u8],
+                payload: &mut [u8],
+            ) -> Result<(), s2n_quic_core::crypto::packet_protection::Error> {
+                self.0
+                    .decrypt_for_path(path_id, packet_number, header, payload)
+            }
+
+            #[inline]
+            fn encrypt_for_path(
+                &mut self,
+                path_id: u32,
+                packet_number: u64,
+                header: &[u8],
+                payload: &mut s2n_quic_core::crypto::scatter::Buffer,
+            ) -> Result<(), s2n_quic_core::crypto::packet_protection::Error> {
+                self.0
+                    .encrypt_for_path(path_id, packet_number, header, payload)
+            }
+
             #[inline]
             fn tag_len(&self) -> usize {
                 self.0.tag_len()
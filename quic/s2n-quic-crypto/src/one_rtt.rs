@@ -21,7 +21,7 @@ mod tests {
     use crate::{cipher_suite::TLS_CHACHA20_POLY1305_SHA256, hkdf};
     use hex_literal::hex;
     use s2n_codec::{encoder::scatter, EncoderBuffer};
-    use s2n_quic_core::crypto::Key;
+    use s2n_quic_core::{crypto::Key, version};
 
     //= https://www.rfc-editor.org/rfc/rfc9001#appendix-A.5
     //# In this example, TLS produces an application write secret from which
@@ -52,6 +52,14 @@ mod tests {
     const KU_SECRET: [u8; 32] =
         hex!("1223504755036d556342ee9361d253421a826c9ecdf3c7148684b36b714881f9");
 
+    // See <https://www.rfc-editor.org/rfc/rfc9369#appendix-A.5>
+    //
+    // ku  = HKDF-Expand-Label(secret, "quicv2 ku", "", 32)
+    //     = c69374c49e3d2a9466fa689e49d476db
+    //       5d0dfbc87d32ceeaa6343fd0ae4c7d88
+    const KU_SECRET_V2: [u8; 32] =
+        hex!("c69374c49e3d2a9466fa689e49d476db5d0dfbc87d32ceeaa6343fd0ae4c7d88");
+
     // Prevent trivial success
     const INVALID_SECRET: [u8; 32] =
         hex!("0000000000000000000000000000000000000000000000000000000000000000");
@@ -60,19 +68,20 @@ mod tests {
     /// implementations don't have RFC values we can test.
     /// This is not exhaustive, but it does show that we are using the KDF and label correctly.
     fn generate_ciphers(
+        version: version::Version,
         secret: &[u8],
         next_secret: &[u8],
     ) -> (TLS_CHACHA20_POLY1305_SHA256, TLS_CHACHA20_POLY1305_SHA256) {
         // Create a cipher based on the initial secret
         let key = hkdf::Prk::new_less_safe(hkdf::HKDF_SHA256, secret);
-        let cipher = TLS_CHACHA20_POLY1305_SHA256::new(key);
+        let cipher = TLS_CHACHA20_POLY1305_SHA256::new(version, key);
 
         // Create the cipher after a Key Update has occurred
         let next_cipher = cipher.0.update();
 
         // Create a cipher based on the expected post-update secret
         let next_key = hkdf::Prk::new_less_safe(hkdf::HKDF_SHA256, next_secret);
-        let expected_next_cipher = TLS_CHACHA20_POLY1305_SHA256::new(next_key);
+        let expected_next_cipher = TLS_CHACHA20_POLY1305_SHA256::new(version, next_key);
 
         (next_cipher, expected_next_cipher.0)
     }
@@ -80,12 +89,17 @@ mod tests {
     #[test]
     fn test_key_update() {
         let tests = [
-            (&SECRET, &KU_SECRET, true),
-            (&INVALID_SECRET, &KU_SECRET, false),
+            (version::V1, &SECRET, &KU_SECRET, true),
+            (version::V1, &INVALID_SECRET, &KU_SECRET, false),
+            (version::V2, &SECRET, &KU_SECRET_V2, true),
+            (version::V2, &INVALID_SECRET, &KU_SECRET_V2, false),
+            // the key update label differs between versions
+            (version::V2, &SECRET, &KU_SECRET, false),
         ];
 
-        for (secret, ku_secret, should_match) in tests {
-            let (mut next_cipher, mut expected_next_cipher) = generate_ciphers(secret, ku_secret);
+        for (version, secret, ku_secret, should_match) in tests {
+            let (mut next_cipher, mut expected_next_cipher) =
+                generate_ciphers(version, secret, ku_secret);
 
             // Encrypt two empty blocks to verify the ciphers are the same
             let mut next_cipher_output = [0; 32];
@@ -2,9 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{aws_lc_aead as aead, constant_time};
-use s2n_quic_core::crypto::{
-    self, packet_protection,
-    retry::{IntegrityTag, NONCE_BYTES, SECRET_KEY_BYTES},
+use s2n_quic_core::{
+    crypto::{
+        self, packet_protection,
+        retry::{IntegrityTag, NONCE_BYTES, NONCE_BYTES_V2, SECRET_KEY_BYTES, SECRET_KEY_BYTES_V2},
+    },
+    version,
 };
 
 lazy_static::lazy_static! {
@@ -12,15 +15,25 @@ lazy_static::lazy_static! {
     static ref SECRET_KEY: aead::LessSafeKey = aead::LessSafeKey::new(
         aead::UnboundKey::new(&aead::AES_128_GCM, &SECRET_KEY_BYTES).unwrap(),
     );
+
+    /// Compute the QUIC version 2 secret key once, as the seed is constant
+    static ref SECRET_KEY_V2: aead::LessSafeKey = aead::LessSafeKey::new(
+        aead::UnboundKey::new(&aead::AES_128_GCM, &SECRET_KEY_BYTES_V2).unwrap(),
+    );
 }
 
 #[derive(Debug)]
 pub struct RetryKey;
 
 impl crypto::RetryKey for RetryKey {
-    fn generate_tag(pseudo_packet: &[u8]) -> IntegrityTag {
-        let nonce = aead::Nonce::assume_unique_for_key(NONCE_BYTES);
-        let tag = SECRET_KEY
+    fn generate_tag(version: u32, pseudo_packet: &[u8]) -> IntegrityTag {
+        let (key, nonce): (&aead::LessSafeKey, _) = if version == version::V2 {
+            (&SECRET_KEY_V2, NONCE_BYTES_V2)
+        } else {
+            (&SECRET_KEY, NONCE_BYTES)
+        };
+        let nonce = aead::Nonce::assume_unique_for_key(nonce);
+        let tag = key
             .seal_in_place_separate_tag(nonce, aead::Aad::from(pseudo_packet), &mut [])
             .expect("in_out len is 0 and should always be less than the nonce max bytes");
 
@@ -29,8 +42,12 @@ impl crypto::RetryKey for RetryKey {
             .expect("AES_128_GCM tag len should always be 128 bits")
     }
 
-    fn validate(pseudo_packet: &[u8], tag: IntegrityTag) -> Result<(), packet_protection::Error> {
-        let expected = Self::generate_tag(pseudo_packet);
+    fn validate(
+        version: u32,
+        pseudo_packet: &[u8],
+        tag: IntegrityTag,
+    ) -> Result<(), packet_protection::Error> {
+        let expected = Self::generate_tag(version, pseudo_packet);
 
         constant_time::verify_slices_are_equal(&expected, &tag)
             .map_err(|_| packet_protection::Error::DECRYPT_ERROR)
@@ -56,11 +73,33 @@ mod tests {
     fn test_tag_validation() {
         let invalid_tag: [u8; 16] = hex!("00112233445566778899aabbccddeeff");
 
+        assert!(RetryKey::validate(
+            version::V1,
+            &retry::example::PSEUDO_PACKET,
+            retry::example::EXPECTED_TAG
+        )
+        .is_ok());
         assert!(
-            RetryKey::validate(&retry::example::PSEUDO_PACKET, retry::example::EXPECTED_TAG)
-                .is_ok()
+            RetryKey::validate(version::V1, &retry::example::PSEUDO_PACKET, invalid_tag).is_err()
         );
-        assert!(RetryKey::validate(&retry::example::PSEUDO_PACKET, invalid_tag).is_err());
+    }
+
+    #[test]
+    fn test_tag_validation_v2() {
+        assert!(RetryKey::validate(
+            version::V2,
+            &retry::example_v2::PSEUDO_PACKET,
+            retry::example_v2::EXPECTED_TAG
+        )
+        .is_ok());
+
+        // the tag is computed with a different key for each version
+        assert!(RetryKey::validate(
+            version::V1,
+            &retry::example_v2::PSEUDO_PACKET,
+            retry::example_v2::EXPECTED_TAG
+        )
+        .is_err());
     }
 
     fn pn(space: PacketNumberSpace) -> TruncatedPacketNumber {
@@ -70,13 +109,22 @@ mod tests {
 
     #[test]
     fn test_packet_encode() {
+        check_packet_encode(version::V1, &retry::example::PACKET);
+    }
+
+    #[test]
+    fn test_packet_encode_v2() {
+        check_packet_encode(version::V2, &retry::example_v2::PACKET);
+    }
+
+    fn check_packet_encode(version: u32, expected: &[u8]) {
         let remote_address = inet::ip::SocketAddress::default();
         let mut token_format = token::testing::Format::new();
         // Values are taken from the retry packet example. Since this is the Initial packet that
         // creates the retry, source_connection_id of the Initial is set to the destination
         // connection id of the retry.
         let packet = packet::initial::Initial {
-            version: 0x01,
+            version,
             destination_connection_id: &retry::example::ODCID[..],
             source_connection_id: &retry::example::DCID[..],
             token: &retry::example::TOKEN[..],
@@ -107,7 +155,7 @@ mod tests {
                 &mut token_format,
                 &mut output_buf,
             ) {
-                assert_eq!(&output_buf[range], &retry::example::PACKET[..]);
+                assert_eq!(&output_buf[range], expected);
             }
         }
     }
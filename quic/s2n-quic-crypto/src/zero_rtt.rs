@@ -10,7 +10,15 @@ pub struct ZeroRttKey(CipherSuite);
 impl ZeroRttKey {
     /// Create a ZeroRTT cipher suite with a given secret
     pub fn new(secret: crate::Prk) -> (Self, ZeroRttHeaderKey) {
-        let (key, header_key) = CipherSuite::new(secret);
+        Self::new_with_version(s2n_quic_core::version::V1, secret)
+    }
+
+    /// Create a ZeroRTT cipher suite for a QUIC version with a given secret
+    pub fn new_with_version(
+        version: s2n_quic_core::version::Version,
+        secret: crate::Prk,
+    ) -> (Self, ZeroRttHeaderKey) {
+        let (key, header_key) = CipherSuite::new(version, secret);
         let key = Self(key);
         let header_key = ZeroRttHeaderKey(header_key);
         (key, header_key)
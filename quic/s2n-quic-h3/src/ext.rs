@@ -0,0 +1,58 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Configuration for [HTTP Datagrams](https://www.rfc-editor.org/rfc/rfc9297) and
+//! [Extended CONNECT](https://www.rfc-editor.org/rfc/rfc9220)
+//!
+//! Together these allow building CONNECT-UDP proxies and WebTransport sessions on top of
+//! s2n-quic. HTTP Datagrams are carried in QUIC DATAGRAM frames, so the QUIC endpoints need to
+//! be configured with a datagram provider from [`datagram_provider`] in addition to the HTTP/3
+//! settings from [`server_builder`] and [`client_builder`].
+
+use s2n_quic::provider::datagram::default::{BuilderError, Endpoint};
+
+/// The default number of datagrams buffered for sending and receiving on each connection
+pub const DEFAULT_DATAGRAM_CAPACITY: usize = 256;
+
+/// Returns a datagram provider for carrying HTTP Datagrams
+///
+/// The provider should be passed to `with_datagram` on both the s2n-quic `Server` and `Client`
+/// builders, which advertises support for QUIC DATAGRAM frames to the peer.
+pub fn datagram_provider() -> Endpoint {
+    datagram_provider_with_capacity(DEFAULT_DATAGRAM_CAPACITY)
+        .expect("default capacity is non-zero")
+}
+
+/// Returns a datagram provider for carrying HTTP Datagrams, buffering up to `capacity`
+/// datagrams for sending and receiving on each connection
+pub fn datagram_provider_with_capacity(capacity: usize) -> Result<Endpoint, BuilderError> {
+    let endpoint = Endpoint::builder()
+        .with_send_capacity(capacity)?
+        .with_recv_capacity(capacity)?
+        .build()
+        .unwrap_or_else(|never| match never {});
+    Ok(endpoint)
+}
+
+/// Returns an HTTP/3 server builder that negotiates Extended CONNECT and HTTP Datagrams
+///
+/// The server advertises `SETTINGS_ENABLE_CONNECT_PROTOCOL` and `SETTINGS_H3_DATAGRAM`, which
+/// allows clients to send requests with a `:protocol` pseudo-header, such as `connect-udp`.
+pub fn server_builder() -> h3::server::Builder {
+    let mut builder = h3::server::builder();
+    builder.enable_extended_connect(true).enable_datagram(true);
+    builder
+}
+
+/// Returns an HTTP/3 client builder that negotiates Extended CONNECT and HTTP Datagrams
+///
+/// This is the client counterpart of [`server_builder`]. Extended CONNECT requests can only be
+/// sent once the server has advertised `SETTINGS_ENABLE_CONNECT_PROTOCOL`.
+pub fn client_builder() -> h3::client::Builder {
+    let mut builder = h3::client::builder();
+    builder.enable_extended_connect(true).enable_datagram(true);
+    builder
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,117 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::Connection;
+use bytes::Bytes;
+use core::future::Future;
+use futures::future::{self, Either};
+use h3::ext::Protocol;
+use h3_datagram::datagram_traits::HandleDatagramsExt;
+use http::{Method, Request, Response, StatusCode};
+use s2n_quic::{
+    client::Connect,
+    provider::io::testing::{primary, spawn, test, Handle, Model, Result},
+    Client, Server,
+};
+use s2n_quic_core::crypto::tls::testing::certificates;
+use std::net::SocketAddr;
+
+/// Starts a server that accepts a CONNECT-UDP request and echoes a datagram on it
+fn start_server(handle: &Handle) -> Result<SocketAddr> {
+    let mut server = Server::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls((certificates::CERT_PEM, certificates::KEY_PEM))?
+        .with_datagram(datagram_provider())?
+        .start()?;
+    let server_addr = server.local_addr()?;
+
+    spawn(async move {
+        let connection = server.accept().await.unwrap();
+        let mut conn = server_builder()
+            .build::<_, Bytes>(Connection::new(connection))
+            .await
+            .unwrap();
+
+        let (request, mut stream) = conn.accept().await.unwrap().unwrap();
+
+        // the `:protocol` pseudo-header is only sent once Extended CONNECT is negotiated
+        assert_eq!(request.method(), Method::CONNECT);
+        assert_eq!(
+            request.extensions().get::<Protocol>(),
+            Some(&Protocol::CONNECT_UDP)
+        );
+
+        let response = Response::builder().status(StatusCode::OK).body(()).unwrap();
+        stream.send_response(response).await.unwrap();
+
+        let datagram = conn.read_datagram().await.unwrap().unwrap();
+        assert_eq!(datagram.stream_id(), stream.id());
+        conn.send_datagram(stream.id(), datagram.payload().clone())
+            .unwrap();
+
+        // keep the request open until the client closes the connection
+        while let Ok(Some(_)) = conn.accept().await {}
+    });
+
+    Ok(server_addr)
+}
+
+/// Polls `f` to completion while driving the client connection
+async fn drive<F: Future>(
+    driver: &mut h3::client::Connection<Connection, Bytes>,
+    f: F,
+) -> F::Output {
+    let close = future::poll_fn(|cx| driver.poll_close(cx));
+    match future::select(core::pin::pin!(f), core::pin::pin!(close)).await {
+        Either::Left((output, _)) => output,
+        Either::Right((result, _)) => panic!("connection closed: {result:?}"),
+    }
+}
+
+#[test]
+fn datagram_round_trip() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server_addr = start_server(handle)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_datagram(datagram_provider())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+            let (mut driver, mut send_request) = client_builder()
+                .build::<_, _, Bytes>(Connection::new(connection))
+                .await
+                .unwrap();
+
+            let mut request = Request::builder()
+                .method(Method::CONNECT)
+                .uri("https://localhost/.well-known/masque/udp/192.0.2.6/443/")
+                .body(())
+                .unwrap();
+            request.extensions_mut().insert(Protocol::CONNECT_UDP);
+
+            let mut stream = drive(&mut driver, send_request.send_request(request))
+                .await
+                .unwrap();
+            let response = drive(&mut driver, stream.recv_response()).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            driver
+                .send_datagram(stream.id(), Bytes::from_static(b"hello datagram"))
+                .unwrap();
+            let datagram = driver.read_datagram().await.unwrap().unwrap();
+            assert_eq!(datagram.stream_id(), stream.id());
+            assert_eq!(&datagram.payload()[..], b"hello datagram");
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
@@ -1,7 +1,12 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod ext;
+mod priority;
 mod s2n_quic;
+pub mod server;
+pub mod webtransport;
 
-pub use self::s2n_quic::*;
+pub use self::{priority::*, s2n_quic::*};
 pub use h3;
+pub use h3_datagram;
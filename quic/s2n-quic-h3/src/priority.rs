@@ -0,0 +1,104 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Maps [HTTP priority signals](https://www.rfc-editor.org/rfc/rfc9218) onto QUIC stream
+//! priorities
+
+use s2n_quic::stream::Priority;
+
+/// The urgency of a request without an urgency parameter, as defined in RFC 9218 section 4.1
+const DEFAULT_URGENCY: u8 = 3;
+
+/// Requests are not incremental by default, as defined in RFC 9218 section 4.2
+const DEFAULT_INCREMENTAL: bool = false;
+
+/// Returns the stream priority for a request without any priority signals
+pub fn default_priority() -> Priority {
+    Priority::new(DEFAULT_URGENCY, DEFAULT_INCREMENTAL)
+}
+
+/// Parses the value of a `Priority` header field or a `PRIORITY_UPDATE` frame
+///
+/// Parameters that are unknown or carry invalid values are ignored, in which case the default
+/// is used for the parameter.
+pub fn parse_priority(value: &[u8]) -> Priority {
+    let mut urgency = DEFAULT_URGENCY;
+    let mut incremental = DEFAULT_INCREMENTAL;
+
+    for member in value.split(|b| *b == b',') {
+        // drop any parameters attached to the dictionary member
+        let member = member.split(|b| *b == b';').next().unwrap_or_default();
+        let member = trim(member);
+
+        let (key, value) = match member.iter().position(|b| *b == b'=') {
+            Some(idx) => (&member[..idx], Some(&member[idx + 1..])),
+            None => (member, None),
+        };
+
+        match key {
+            b"u" => {
+                // values outside of the urgency range are ignored
+                if let Some(value) = value
+                    .and_then(|value| core::str::from_utf8(value).ok())
+                    .and_then(|value| value.parse::<u8>().ok())
+                    .filter(|value| *value <= Priority::MAX_URGENCY)
+                {
+                    urgency = value;
+                }
+            }
+            b"i" => match value {
+                // a bare key is a boolean `true` in structured fields
+                None | Some(b"?1") => incremental = true,
+                Some(b"?0") => incremental = false,
+                // values that aren't booleans are ignored
+                _ => {}
+            },
+            // unknown parameters are ignored
+            _ => {}
+        }
+    }
+
+    Priority::new(urgency, incremental)
+}
+
+fn trim(mut value: &[u8]) -> &[u8] {
+    while let [first, rest @ ..] = value {
+        if !first.is_ascii_whitespace() {
+            break;
+        }
+        value = rest;
+    }
+
+    while let [rest @ .., last] = value {
+        if !last.is_ascii_whitespace() {
+            break;
+        }
+        value = rest;
+    }
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        for (value, urgency, incremental) in [
+            ("", 3, false),
+            ("u=0", 0, false),
+            ("u=7, i", 7, true),
+            ("i=?1,u=1", 1, true),
+            ("u=5;foo=bar, i=?0", 5, false),
+            ("u=8, i=1", 3, false),
+            ("u=-1, x=?1", 3, false),
+        ] {
+            assert_eq!(
+                parse_priority(value.as_bytes()),
+                Priority::new(urgency, incremental),
+                "{value:?}"
+            );
+        }
+    }
+}
@@ -1,12 +1,15 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use bytes::{Buf, Bytes};
+use crate::webtransport;
+use bytes::{Buf, Bytes, BytesMut};
 use core::task::ready;
 use h3::quic::{self, Error, StreamId, WriteBuf};
+use h3_datagram::{datagram::Datagram, quic_traits as datagram};
 use s2n_quic::{
     application,
-    stream::{BidirectionalStream, ReceiveStream},
+    provider::datagram::default::{DatagramError as ProviderDatagramError, Sender},
+    stream::{BidirectionalStream, Priority, ReceiveStream},
 };
 use std::{
     convert::TryInto,
@@ -20,8 +23,15 @@ use tracing::instrument;
 
 pub struct Connection {
     conn: s2n_quic::connection::Handle,
-    bidi_acceptor: s2n_quic::connection::BidirectionalStreamAcceptor,
-    recv_acceptor: s2n_quic::connection::ReceiveStreamAcceptor,
+    acceptor: Acceptor,
+}
+
+enum Acceptor {
+    Direct {
+        bidi: s2n_quic::connection::BidirectionalStreamAcceptor,
+        recv: s2n_quic::connection::ReceiveStreamAcceptor,
+    },
+    WebTransport(webtransport::Router),
 }
 
 impl Connection {
@@ -31,10 +41,27 @@ impl Connection {
 
         Self {
             conn: handle,
-            bidi_acceptor: bidi,
-            recv_acceptor: recv,
+            acceptor: Acceptor::Direct { bidi, recv },
         }
     }
+
+    /// Creates a connection that routes WebTransport streams and datagrams to sessions
+    ///
+    /// Incoming streams are only handed to h3 once they're known not to belong to a
+    /// WebTransport session. Sessions are established with the returned
+    /// [`WebTransport`](crate::webtransport::WebTransport) handle.
+    pub fn with_webtransport(new_conn: s2n_quic::Connection) -> (Self, webtransport::WebTransport) {
+        let (handle, acceptor) = new_conn.split();
+        let (bidi, recv) = acceptor.split();
+        let router = webtransport::Router::new(handle.clone(), bidi, recv);
+
+        let conn = Self {
+            conn: handle.clone(),
+            acceptor: Acceptor::WebTransport(router.clone()),
+        };
+
+        (conn, webtransport::WebTransport::new(handle, router))
+    }
 }
 
 #[derive(Debug)]
@@ -80,11 +107,17 @@ where
         &mut self,
         cx: &mut task::Context<'_>,
     ) -> Poll<Result<Option<Self::RecvStream>, Self::AcceptError>> {
-        let recv = match ready!(self.recv_acceptor.poll_accept_receive_stream(cx))? {
-            Some(x) => x,
-            None => return Poll::Ready(Ok(None)),
+        let recv = match &mut self.acceptor {
+            Acceptor::Direct { recv, .. } => match ready!(recv.poll_accept_receive_stream(cx))? {
+                Some(x) => x.into(),
+                None => return Poll::Ready(Ok(None)),
+            },
+            Acceptor::WebTransport(router) => match ready!(router.poll_accept_h3_recv(cx))? {
+                Some((recv, prefix)) => Self::RecvStream::with_prefix(recv, prefix),
+                None => return Poll::Ready(Ok(None)),
+            },
         };
-        Poll::Ready(Ok(Some(Self::RecvStream::new(recv))))
+        Poll::Ready(Ok(Some(recv)))
     }
 
     #[cfg_attr(feature = "tracing", instrument(skip_all, level = "trace"))]
@@ -92,13 +125,22 @@ where
         &mut self,
         cx: &mut task::Context<'_>,
     ) -> Poll<Result<Option<Self::BidiStream>, Self::AcceptError>> {
-        let (recv, send) = match ready!(self.bidi_acceptor.poll_accept_bidirectional_stream(cx))? {
-            Some(x) => x.split(),
-            None => return Poll::Ready(Ok(None)),
+        let (stream, prefix) = match &mut self.acceptor {
+            Acceptor::Direct { bidi, .. } => {
+                match ready!(bidi.poll_accept_bidirectional_stream(cx))? {
+                    Some(x) => (x, Bytes::new()),
+                    None => return Poll::Ready(Ok(None)),
+                }
+            }
+            Acceptor::WebTransport(router) => match ready!(router.poll_accept_h3_bidi(cx))? {
+                Some(x) => x,
+                None => return Poll::Ready(Ok(None)),
+            },
         };
+        let (recv, send) = stream.split();
         Poll::Ready(Ok(Some(Self::BidiStream {
             send: Self::SendStream::new(send),
-            recv: Self::RecvStream::new(recv),
+            recv: Self::RecvStream::with_prefix(recv, prefix),
         })))
     }
 
@@ -145,6 +187,53 @@ where
     }
 }
 
+impl<B> datagram::SendDatagramExt<B> for Connection
+where
+    B: Buf,
+{
+    type Error = DatagramError;
+
+    /// Enqueues an HTTP Datagram on the connection's datagram provider
+    ///
+    /// The connection must be configured with the
+    /// [`default`](s2n_quic::provider::datagram::default) datagram provider. An error is returned
+    /// if its send queue is full.
+    #[cfg_attr(feature = "tracing", instrument(skip_all, level = "trace"))]
+    fn send_datagram(&mut self, data: Datagram<B>) -> Result<(), Self::Error> {
+        // HTTP Datagrams are prefixed with the quarter stream id of the associated request
+        let mut buf = BytesMut::new();
+        data.encode(&mut buf);
+        let buf = buf.freeze();
+
+        self.conn
+            .datagram_mut(|sender: &mut Sender| sender.send_datagram(buf))
+            .map_err(ProviderDatagramError::from)??;
+
+        Ok(())
+    }
+}
+
+impl datagram::RecvDatagramExt for Connection {
+    type Buf = Bytes;
+    type Error = DatagramError;
+
+    #[cfg_attr(feature = "tracing", instrument(skip_all, level = "trace"))]
+    fn poll_accept_datagram(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Option<Self::Buf>, Self::Error>> {
+        match ready!(self.conn.poll_recv_datagram(cx)) {
+            Ok(datagram) => Poll::Ready(Ok(Some(datagram))),
+            // no more datagrams will be received once the connection is closed
+            Err(ProviderDatagramError::ConnectionError {
+                error: s2n_quic::connection::Error::Closed { .. },
+                ..
+            }) => Poll::Ready(Ok(None)),
+            Err(error) => Poll::Ready(Err(error.into())),
+        }
+    }
+}
+
 pub struct OpenStreams {
     conn: s2n_quic::connection::Handle,
 }
@@ -201,6 +290,19 @@ where
     recv: RecvStream,
 }
 
+impl<B> BidiStream<B>
+where
+    B: Buf,
+{
+    /// Sets the scheduling priority of the sending half of the stream
+    ///
+    /// See [`parse_priority`](crate::parse_priority) for deriving the priority from HTTP
+    /// priority signals.
+    pub fn set_priority(&mut self, priority: Priority) -> Result<(), SendStreamError> {
+        self.send.set_priority(priority)
+    }
+}
+
 impl<B> quic::BidiStream<B> for BidiStream<B>
 where
     B: Buf,
@@ -278,11 +380,23 @@ where
 
 pub struct RecvStream {
     stream: s2n_quic::stream::ReceiveStream,
+    /// Data that was read from the stream before it was handed to h3
+    prefix: Option<Bytes>,
 }
 
 impl RecvStream {
     fn new(stream: s2n_quic::stream::ReceiveStream) -> Self {
-        Self { stream }
+        Self {
+            stream,
+            prefix: None,
+        }
+    }
+
+    fn with_prefix(stream: s2n_quic::stream::ReceiveStream, prefix: Bytes) -> Self {
+        Self {
+            stream,
+            prefix: Some(prefix).filter(|prefix| !prefix.is_empty()),
+        }
     }
 }
 
@@ -295,6 +409,10 @@ impl quic::RecvStream for RecvStream {
         &mut self,
         cx: &mut task::Context<'_>,
     ) -> Poll<Result<Option<Self::Buf>, Self::Error>> {
+        if let Some(prefix) = self.prefix.take() {
+            return Ok(Some(prefix)).into();
+        }
+
         let buf = ready!(self.stream.poll_receive(cx))?;
         Ok(buf).into()
     }
@@ -383,6 +501,15 @@ where
             buf: Default::default(),
         }
     }
+
+    /// Sets the scheduling priority of the stream
+    ///
+    /// See [`parse_priority`](crate::parse_priority) for deriving the priority from HTTP
+    /// priority signals.
+    pub fn set_priority(&mut self, priority: Priority) -> Result<(), SendStreamError> {
+        self.stream.set_priority(priority)?;
+        Ok(())
+    }
 }
 
 impl<B> quic::SendStream<B> for SendStream<B>
@@ -531,3 +658,54 @@ impl From<SendStreamError> for Arc<dyn Error> {
         Arc::new(e)
     }
 }
+
+#[derive(Debug)]
+pub struct DatagramError(ProviderDatagramError);
+
+impl std::error::Error for DatagramError {}
+
+impl fmt::Display for DatagramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl From<ProviderDatagramError> for DatagramError {
+    fn from(e: ProviderDatagramError) -> Self {
+        Self(e)
+    }
+}
+
+impl Error for DatagramError {
+    fn is_timeout(&self) -> bool {
+        matches!(
+            self.0,
+            ProviderDatagramError::ConnectionError {
+                error: s2n_quic::connection::Error::IdleTimerExpired { .. },
+                ..
+            }
+        )
+    }
+
+    fn err_code(&self) -> Option<u64> {
+        match self.0 {
+            ProviderDatagramError::ConnectionError {
+                error: s2n_quic::connection::Error::Application { error, .. },
+                ..
+            } => Some(error.into()),
+            _ => None,
+        }
+    }
+}
+
+impl From<DatagramError> for Arc<dyn Error> {
+    fn from(e: DatagramError) -> Self {
+        Arc::new(e)
+    }
+}
+
+impl From<DatagramError> for Box<dyn Error> {
+    fn from(e: DatagramError) -> Self {
+        Box::new(e)
+    }
+}
@@ -0,0 +1,495 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Serves HTTP/3 requests on an [`s2n_quic::Server`] with a [`tower_service::Service`]
+//!
+//! Each request is passed to the service with a streaming [`RequestBody`]. The response body is
+//! streamed back to the client, including any trailers it produces.
+//!
+//! ```ignore
+//! let server = s2n_quic::Server::builder()
+//!     .with_tls((cert, key))?
+//!     .with_io("127.0.0.1:4433")?
+//!     .start()?;
+//!
+//! s2n_quic_h3::server::Server::builder()
+//!     .build(service)
+//!     .serve_with_shutdown(server, tokio::signal::ctrl_c().map(|_| ()))
+//!     .await;
+//! ```
+//!
+//! By default, the futures returned by [`Server::serve`] and [`Server::serve_with_shutdown`] drive
+//! all of the connections and requests, so no executor is required. Since that runs every
+//! connection on a single task, a slow [`Service::call`] delays all of the other connections.
+//! Configuring an executor with [`Builder::with_executor`] spawns each connection and request as
+//! its own task instead:
+//!
+//! ```ignore
+//! s2n_quic_h3::server::Server::builder()
+//!     .with_executor(|future| {
+//!         tokio::spawn(future);
+//!     })
+//!     .build(service)
+//!     .serve(server)
+//!     .await;
+//! ```
+
+use crate::Connection;
+use bytes::{Buf, Bytes};
+use core::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use futures::{
+    channel::oneshot,
+    future::{BoxFuture, FutureExt, Shared},
+    stream::{FuturesUnordered, StreamExt},
+};
+use h3::{error::Code, server::RequestStream};
+use http::{HeaderMap, Request, Response, StatusCode};
+use http_body::{Body, Frame};
+use std::sync::Arc;
+use tower_service::Service;
+
+#[cfg(test)]
+mod tests;
+
+type RecvStream = RequestStream<crate::RecvStream, Bytes>;
+type SendStream = RequestStream<crate::SendStream<Bytes>, Bytes>;
+type ShutdownSignal = Shared<BoxFuture<'static, ()>>;
+
+/// Configures the HTTP/3 settings of a [`Server`]
+///
+/// # QPACK
+///
+/// The QPACK dynamic table can't be configured. h3 (including the latest 0.0.8 release) only
+/// encodes and decodes field sections with the static table. It never sends the QPACK settings,
+/// so peers use their defaults of a zero capacity dynamic table and no blocked streams.
+/// Advertising a non-zero capacity without decoder support would make requests that reference
+/// the dynamic table fail, so no setting is exposed until h3 implements it.
+#[derive(Clone, Debug, Default)]
+pub struct Builder {
+    max_field_section_size: Option<u64>,
+    send_grease: Option<bool>,
+    enable_extended_connect: bool,
+    enable_datagram: bool,
+    executor: Option<Executor>,
+}
+
+impl Builder {
+    /// Sets the maximum size of a field section the server accepts from clients
+    pub fn with_max_field_section_size(mut self, value: u64) -> Self {
+        self.max_field_section_size = Some(value);
+        self
+    }
+
+    /// Sets whether the server sends reserved settings, frames and streams
+    pub fn with_grease(mut self, enabled: bool) -> Self {
+        self.send_grease = Some(enabled);
+        self
+    }
+
+    /// Sets whether clients can send Extended CONNECT requests
+    pub fn with_extended_connect(mut self, enabled: bool) -> Self {
+        self.enable_extended_connect = enabled;
+        self
+    }
+
+    /// Sets whether the server negotiates HTTP Datagrams
+    pub fn with_datagram(mut self, enabled: bool) -> Self {
+        self.enable_datagram = enabled;
+        self
+    }
+
+    /// Sets the executor that connections and requests are spawned on
+    ///
+    /// Without an executor, all of the connections and requests are driven by the future
+    /// returned by [`Server::serve`].
+    pub fn with_executor<E>(mut self, executor: E) -> Self
+    where
+        E: Fn(BoxFuture<'static, ()>) + Send + Sync + 'static,
+    {
+        self.executor = Some(Executor(Arc::new(executor)));
+        self
+    }
+
+    /// Builds a server which handles requests with `service`
+    pub fn build<S>(self, service: S) -> Server<S> {
+        Server {
+            config: self,
+            service,
+        }
+    }
+
+    fn h3(&self) -> h3::server::Builder {
+        let mut builder = h3::server::builder();
+        if let Some(value) = self.max_field_section_size {
+            builder.max_field_section_size(value);
+        }
+        if let Some(value) = self.send_grease {
+            builder.send_grease(value);
+        }
+        builder
+            .enable_extended_connect(self.enable_extended_connect)
+            .enable_datagram(self.enable_datagram);
+        builder
+    }
+}
+
+/// Spawns futures on the executor from [`Builder::with_executor`]
+#[derive(Clone)]
+struct Executor(Arc<dyn Fn(BoxFuture<'static, ()>) + Send + Sync>);
+
+impl fmt::Debug for Executor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Executor").finish_non_exhaustive()
+    }
+}
+
+/// Spawns `future` if an executor is configured
+///
+/// The returned future completes once `future` does, so the caller can wait for spawned tasks.
+fn spawn<F>(executor: &Option<Executor>, future: F) -> BoxFuture<'static, ()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let Some(executor) = executor else {
+        return future.boxed();
+    };
+
+    let (done, is_done) = oneshot::channel();
+    (executor.0)(
+        async move {
+            future.await;
+            let _ = done.send(());
+        }
+        .boxed(),
+    );
+    // the task is also done if the executor drops it
+    is_done.map(|_| ()).boxed()
+}
+
+/// Serves HTTP/3 requests with a [`Service`]
+pub struct Server<S> {
+    config: Builder,
+    service: S,
+}
+
+impl Server<()> {
+    /// Returns a builder for the HTTP/3 settings of the server
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+}
+
+impl<S, ResBody> Server<S>
+where
+    S: Service<Request<RequestBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    S::Error: fmt::Display,
+    ResBody: Body + Send,
+    ResBody::Data: Send,
+    ResBody::Error: fmt::Display,
+{
+    /// Serves requests on all connections accepted by `server` until it's closed
+    pub async fn serve(self, server: s2n_quic::Server) {
+        self.serve_with_shutdown(server, futures::future::pending())
+            .await
+    }
+
+    /// Serves requests on all connections accepted by `server` until `signal` completes
+    ///
+    /// Once `signal` completes, no more connections are accepted and a GOAWAY frame is sent on
+    /// every connection. The returned future completes after the requests that were already
+    /// accepted are handled.
+    pub async fn serve_with_shutdown<F>(self, mut server: s2n_quic::Server, signal: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let shutdown: ShutdownSignal = signal.boxed().shared();
+        let mut connections = FuturesUnordered::new();
+        let mut is_accepting = true;
+
+        futures::future::poll_fn(|cx| {
+            if is_accepting && shutdown.poll_unpin(cx).is_ready() {
+                is_accepting = false;
+            }
+
+            while is_accepting {
+                match server.poll_accept(cx) {
+                    Poll::Ready(Some(connection)) => {
+                        let connection = serve_connection(
+                            connection,
+                            self.config.h3(),
+                            self.config.executor.clone(),
+                            self.service.clone(),
+                            shutdown.clone(),
+                        );
+                        connections.push(spawn(&self.config.executor, connection));
+                    }
+                    Poll::Ready(None) => is_accepting = false,
+                    Poll::Pending => break,
+                }
+            }
+
+            loop {
+                match connections.poll_next_unpin(cx) {
+                    Poll::Ready(Some(())) => continue,
+                    Poll::Ready(None) if !is_accepting => return Poll::Ready(()),
+                    Poll::Ready(None) | Poll::Pending => return Poll::Pending,
+                }
+            }
+        })
+        .await
+    }
+}
+
+enum Event<T> {
+    Accept(T),
+    Shutdown,
+}
+
+async fn serve_connection<S, ResBody>(
+    connection: s2n_quic::Connection,
+    builder: h3::server::Builder,
+    executor: Option<Executor>,
+    service: S,
+    shutdown: ShutdownSignal,
+) where
+    S: Service<Request<RequestBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    S::Error: fmt::Display,
+    ResBody: Body + Send,
+    ResBody::Data: Send,
+    ResBody::Error: fmt::Display,
+{
+    let Ok(mut conn) = builder.build(Connection::new(connection)).await else {
+        return;
+    };
+
+    let mut requests = FuturesUnordered::new();
+    let mut shutdown = Some(shutdown);
+
+    loop {
+        let event = {
+            let mut accept = core::pin::pin!(conn.accept());
+            futures::future::poll_fn(|cx| {
+                // make progress on the in-flight requests while waiting for the next one
+                while let Poll::Ready(Some(())) = requests.poll_next_unpin(cx) {}
+
+                if let Some(signal) = shutdown.as_mut() {
+                    if signal.poll_unpin(cx).is_ready() {
+                        return Poll::Ready(Event::Shutdown);
+                    }
+                }
+
+                accept.as_mut().poll(cx).map(Event::Accept)
+            })
+            .await
+        };
+
+        match event {
+            Event::Shutdown => {
+                shutdown = None;
+                // tell the client that no more requests will be processed
+                if conn.shutdown(0).await.is_err() {
+                    break;
+                }
+            }
+            Event::Accept(Ok(Some((request, stream)))) => {
+                let request = handle_request(service.clone(), request, stream);
+                requests.push(spawn(&executor, request));
+            }
+            Event::Accept(Ok(None)) | Event::Accept(Err(_)) => break,
+        }
+    }
+
+    // finish handling the requests that were already accepted
+    while requests.next().await.is_some() {}
+}
+
+async fn handle_request<S, ResBody>(
+    mut service: S,
+    request: Request<()>,
+    stream: RequestStream<crate::BidiStream<Bytes>, Bytes>,
+) where
+    S: Service<Request<RequestBody>, Response = Response<ResBody>>,
+    S::Error: fmt::Display,
+    ResBody: Body,
+    ResBody::Error: fmt::Display,
+{
+    let (mut send, recv) = stream.split();
+    let request = request.map(|()| RequestBody::new(recv));
+
+    let response = match futures::future::poll_fn(|cx| service.poll_ready(cx)).await {
+        Ok(()) => service.call(request).await,
+        Err(error) => Err(error),
+    };
+
+    let response = match response {
+        Ok(response) => Some(response),
+        Err(_error) => {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(error = %_error, "service failed to handle the request");
+            None
+        }
+    };
+
+    let response = match response {
+        Some(response) => response,
+        None => {
+            let response = Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(())
+                .expect("response is valid");
+            if send.send_response(response).await.is_ok() {
+                let _ = send.finish().await;
+            }
+            return;
+        }
+    };
+
+    let (parts, body) = response.into_parts();
+    if send
+        .send_response(Response::from_parts(parts, ()))
+        .await
+        .is_err()
+    {
+        return;
+    }
+
+    if send_body(&mut send, body).await.is_ok() {
+        let _ = send.finish().await;
+    }
+}
+
+/// Streams the response body, including its trailers
+async fn send_body<B>(send: &mut SendStream, body: B) -> Result<(), ()>
+where
+    B: Body,
+    B::Error: fmt::Display,
+{
+    let mut body = core::pin::pin!(body);
+
+    while let Some(frame) = futures::future::poll_fn(|cx| body.as_mut().poll_frame(cx)).await {
+        let frame = match frame {
+            Ok(frame) => frame,
+            Err(_error) => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(error = %_error, "response body failed");
+
+                send.stop_stream(Code::H3_INTERNAL_ERROR);
+                return Err(());
+            }
+        };
+
+        let result = match frame.into_data() {
+            Ok(mut data) => send.send_data(data.copy_to_bytes(data.remaining())).await,
+            Err(frame) => match frame.into_trailers() {
+                Ok(trailers) => send.send_trailers(trailers).await,
+                // unknown frame types are skipped
+                Err(_frame) => Ok(()),
+            },
+        };
+
+        if result.is_err() {
+            return Err(());
+        }
+    }
+
+    Ok(())
+}
+
+/// The streaming body of a request
+pub struct RequestBody {
+    stream: Option<RecvStream>,
+    pending: Option<BoxFuture<'static, (RecvStream, Result<Option<Frame<Bytes>>, h3::Error>)>>,
+    is_end_stream: bool,
+}
+
+impl RequestBody {
+    fn new(stream: RecvStream) -> Self {
+        Self {
+            stream: Some(stream),
+            pending: None,
+            is_end_stream: false,
+        }
+    }
+
+    async fn next_frame(
+        mut stream: RecvStream,
+    ) -> (RecvStream, Result<Option<Frame<Bytes>>, h3::Error>) {
+        let result = match stream.recv_data().await {
+            Ok(Some(mut data)) => Ok(Some(Frame::data(data.copy_to_bytes(data.remaining())))),
+            Ok(None) => stream
+                .recv_trailers()
+                .await
+                .map(|trailers| trailers.map(Frame::trailers)),
+            Err(error) => Err(error),
+        };
+        (stream, result)
+    }
+
+    /// Receives the trailers of the request, discarding any remaining data
+    pub async fn trailers(mut self) -> Result<Option<HeaderMap>, h3::Error> {
+        while let Some(frame) = futures::future::poll_fn(|cx| Pin::new(&mut self).poll_frame(cx))
+            .await
+            .transpose()?
+        {
+            if let Ok(trailers) = frame.into_trailers() {
+                return Ok(Some(trailers));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Body for RequestBody {
+    type Data = Bytes;
+    type Error = h3::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+
+        if this.is_end_stream {
+            return Poll::Ready(None);
+        }
+
+        let pending = match this.pending.as_mut() {
+            Some(pending) => pending,
+            None => {
+                let stream = this
+                    .stream
+                    .take()
+                    .expect("stream is only taken while pending");
+                this.pending.insert(Self::next_frame(stream).boxed())
+            }
+        };
+
+        let (stream, result) = ready!(pending.poll_unpin(cx));
+        this.pending = None;
+        this.stream = Some(stream);
+
+        let frame = result.transpose();
+        // the body ends with the trailers, the end of the stream or an error
+        this.is_end_stream = !matches!(&frame, Some(Ok(frame)) if frame.is_data());
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.is_end_stream
+    }
+}
+
+impl fmt::Debug for RequestBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestBody")
+            .field("is_end_stream", &self.is_end_stream)
+            .finish()
+    }
+}
@@ -0,0 +1,198 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use core::convert::Infallible;
+use futures::channel::oneshot;
+use http::{HeaderValue, Method};
+use s2n_quic::{
+    client::Connect,
+    provider::io::testing::{primary, spawn, test, Handle, Model, Result},
+    Client,
+};
+use s2n_quic_core::crypto::tls::testing::certificates;
+use std::{
+    net::SocketAddr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Echoes the request body and trailers back to the client
+#[derive(Clone)]
+struct Echo;
+
+impl Service<Request<RequestBody>> for Echo {
+    type Response = Response<EchoBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<RequestBody>) -> Self::Future {
+        async move {
+            let mut body = request.into_body();
+            let mut frames = vec![];
+            while let Some(frame) =
+                futures::future::poll_fn(|cx| Pin::new(&mut body).poll_frame(cx)).await
+            {
+                frames.push(frame.unwrap());
+            }
+
+            Ok(Response::new(EchoBody(frames.into())))
+        }
+        .boxed()
+    }
+}
+
+struct EchoBody(std::collections::VecDeque<Frame<Bytes>>);
+
+impl Body for EchoBody {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        Poll::Ready(self.get_mut().0.pop_front().map(Ok))
+    }
+}
+
+fn start_client(
+    handle: &Handle,
+    server_addr: SocketAddr,
+    on_response: impl FnOnce() + Send + 'static,
+) -> Result {
+    let client = Client::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(certificates::CERT_PEM)?
+        .start()?;
+
+    primary::spawn(async move {
+        let connect = Connect::new(server_addr).with_server_name("localhost");
+        let connection = client.connect(connect).await.unwrap();
+        let (mut driver, mut send_request) =
+            h3::client::new(Connection::new(connection)).await.unwrap();
+
+        let request = async {
+            let request = Request::builder()
+                .method(Method::POST)
+                .uri("https://localhost/echo")
+                .body(())
+                .unwrap();
+            let mut stream = send_request.send_request(request).await.unwrap();
+            stream
+                .send_data(Bytes::from_static(b"hello "))
+                .await
+                .unwrap();
+            stream
+                .send_data(Bytes::from_static(b"world"))
+                .await
+                .unwrap();
+            let mut trailers = HeaderMap::new();
+            trailers.insert("checksum", HeaderValue::from_static("abc"));
+            stream.send_trailers(trailers.clone()).await.unwrap();
+            stream.finish().await.unwrap();
+
+            let response = stream.recv_response().await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let mut data = vec![];
+            while let Some(mut chunk) = stream.recv_data().await.unwrap() {
+                data.extend_from_slice(&chunk.copy_to_bytes(chunk.remaining()));
+            }
+            assert_eq!(data, b"hello world");
+            assert_eq!(stream.recv_trailers().await.unwrap(), Some(trailers));
+
+            on_response();
+        };
+        let close = futures::future::poll_fn(|cx| driver.poll_close(cx));
+
+        // the driver needs to be polled while the request is in flight
+        futures::future::select(core::pin::pin!(request), core::pin::pin!(close)).await;
+
+        // dropping the connection closes it, which lets the server finish
+    });
+
+    Ok(())
+}
+
+fn build_server(handle: &Handle) -> Result<(s2n_quic::Server, SocketAddr)> {
+    let server = s2n_quic::Server::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls((certificates::CERT_PEM, certificates::KEY_PEM))?
+        .start()?;
+    let server_addr = server.local_addr()?;
+    Ok((server, server_addr))
+}
+
+#[test]
+fn echo_with_trailers() {
+    test(Model::default(), |handle| {
+        let (server, server_addr) = build_server(handle)?;
+        spawn(Server::builder().build(Echo).serve(server));
+
+        start_client(handle, server_addr, || {})?;
+        Ok(server_addr)
+    })
+    .unwrap();
+}
+
+/// Ensures the server future completes after a graceful shutdown
+#[test]
+fn graceful_shutdown() {
+    test(Model::default(), |handle| {
+        let (server, server_addr) = build_server(handle)?;
+        let (shutdown, signal) = oneshot::channel::<()>();
+
+        // the test only completes if the server finishes serving
+        primary::spawn(
+            Server::builder()
+                .build(Echo)
+                .serve_with_shutdown(server, signal.map(|_| ())),
+        );
+
+        start_client(handle, server_addr, move || {
+            let _ = shutdown.send(());
+        })?;
+        Ok(server_addr)
+    })
+    .unwrap();
+}
+
+/// Ensures connections and requests run on the executor and the server still waits for them
+/// during a graceful shutdown
+#[test]
+fn graceful_shutdown_with_executor() {
+    test(Model::default(), |handle| {
+        let (server, server_addr) = build_server(handle)?;
+        let (shutdown, signal) = oneshot::channel::<()>();
+        let spawned = Arc::new(AtomicUsize::new(0));
+
+        let executor = {
+            let spawned = spawned.clone();
+            move |future| {
+                spawned.fetch_add(1, Ordering::Relaxed);
+                spawn(future);
+            }
+        };
+
+        primary::spawn(async move {
+            Server::builder()
+                .with_executor(executor)
+                .build(Echo)
+                .serve_with_shutdown(server, signal.map(|_| ()))
+                .await;
+
+            // the connection and its request were both spawned
+            assert_eq!(spawned.load(Ordering::Relaxed), 2);
+        });
+
+        start_client(handle, server_addr, move || {
+            let _ = shutdown.send(());
+        })?;
+        Ok(server_addr)
+    })
+    .unwrap();
+}
@@ -0,0 +1,969 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! [WebTransport over HTTP/3](https://datatracker.ietf.org/doc/draft-ietf-webtrans-http3/)
+//!
+//! A session is established with an Extended CONNECT request using the `webtransport`
+//! protocol. Streams and datagrams belonging to a session carry the session id, which is the id
+//! of the CONNECT request stream, so they can be told apart from HTTP/3 traffic. A
+//! [`Connection`](crate::Connection) created with
+//! [`Connection::with_webtransport`](crate::Connection::with_webtransport) routes them to the
+//! matching [`Session`].
+//!
+//! ```ignore
+//! let (conn, webtransport) = s2n_quic_h3::Connection::with_webtransport(connection);
+//! let mut conn = s2n_quic_h3::webtransport::server_builder().build(conn).await?;
+//!
+//! while let Some((request, stream)) = conn.accept().await? {
+//!     if s2n_quic_h3::webtransport::is_webtransport_request(&request) {
+//!         let session = webtransport.accept_session(&request, stream).await?;
+//!         // handle the session
+//!     }
+//! }
+//! ```
+
+use crate::{BidiStream as H3BidiStream, DatagramError};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use core::{
+    fmt,
+    task::{ready, Context, Poll, Waker},
+};
+use h3::ext::Protocol;
+use http::{Method, Request, Response, StatusCode};
+use s2n_codec::{DecoderBuffer, EncoderValue};
+use s2n_quic::{
+    application,
+    connection::{self, BidirectionalStreamAcceptor, Handle, ReceiveStreamAcceptor},
+    provider::datagram::default::{DatagramError as ProviderDatagramError, Sender},
+    stream::{BidirectionalStream, ReceiveStream},
+};
+use s2n_quic_core::{
+    endpoint,
+    stream::{StreamId, StreamType},
+    varint::VarInt,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    task::Wake,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The stream type of unidirectional WebTransport streams
+const UNI_STREAM_TYPE: VarInt = VarInt::from_u8(0x54);
+/// The signal value that starts bidirectional WebTransport streams
+const BIDI_STREAM_SIGNAL: VarInt = VarInt::from_u8(0x41);
+/// The type of the capsule that closes a session
+const CLOSE_SESSION_CAPSULE_TYPE: VarInt = VarInt::from_u32(0x2843);
+/// The `WEBTRANSPORT_BUFFERED_STREAM_REJECTED` error code, used to reject streams that can't be
+/// buffered
+const BUFFERED_STREAM_REJECTED: u32 = 0x3994_bd84;
+/// The maximum number of datagrams buffered for each session
+const SESSION_DATAGRAM_CAPACITY: usize = 256;
+/// The maximum number of sessions that streams and datagrams are buffered for before the
+/// session is accepted
+const MAX_PENDING_SESSIONS: usize = DEFAULT_MAX_SESSIONS as usize;
+/// The number of closed sessions that are remembered, so late streams and datagrams for them
+/// are rejected
+const CLOSED_SESSION_CAPACITY: usize = 4 * DEFAULT_MAX_SESSIONS as usize;
+/// The maximum number of sessions a peer can establish on a connection
+pub const DEFAULT_MAX_SESSIONS: u64 = 16;
+/// The maximum length of the reason a session is closed with
+pub const MAX_CLOSE_REASON_LEN: usize = 1024;
+
+/// The id of a WebTransport session, which is the id of its CONNECT request stream
+pub type SessionId = u64;
+
+type ServerConnectStream = h3::server::RequestStream<H3BidiStream<Bytes>, Bytes>;
+type ClientConnectStream = h3::client::RequestStream<H3BidiStream<Bytes>, Bytes>;
+
+/// Returns an HTTP/3 server builder that negotiates WebTransport sessions
+///
+/// In addition to the settings from [`ext::server_builder`](crate::ext::server_builder), the
+/// server advertises support for up to [`DEFAULT_MAX_SESSIONS`] WebTransport sessions.
+pub fn server_builder() -> h3::server::Builder {
+    let mut builder = crate::ext::server_builder();
+    builder
+        .enable_webtransport(true)
+        .max_webtransport_sessions(DEFAULT_MAX_SESSIONS);
+    builder
+}
+
+/// Returns `true` if the request is an Extended CONNECT request for a WebTransport session
+pub fn is_webtransport_request(request: &Request<()>) -> bool {
+    request.method() == Method::CONNECT
+        && request.extensions().get::<Protocol>() == Some(&Protocol::WEB_TRANSPORT)
+}
+
+/// Establishes WebTransport sessions on a connection
+///
+/// Created with [`Connection::with_webtransport`](crate::Connection::with_webtransport).
+#[derive(Clone)]
+pub struct WebTransport {
+    conn: Handle,
+    router: Router,
+}
+
+impl WebTransport {
+    pub(crate) fn new(conn: Handle, router: Router) -> Self {
+        Self { conn, router }
+    }
+
+    /// Accepts a WebTransport session requested by the peer
+    ///
+    /// A `200` response is sent on the CONNECT request stream. An error is returned without
+    /// responding if the request isn't a WebTransport request.
+    pub async fn accept_session(
+        &self,
+        request: &Request<()>,
+        mut stream: ServerConnectStream,
+    ) -> Result<Session, Error> {
+        if !is_webtransport_request(request) {
+            return Err(Error::InvalidRequest);
+        }
+
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            // browsers require the draft version the server implements
+            .header("sec-webtransport-http3-draft", "draft02")
+            .body(())
+            .expect("response is valid");
+        stream.send_response(response).await?;
+
+        let id = stream.id().into_inner();
+        Ok(self.new_session(id, ConnectStream::Server(stream)))
+    }
+
+    /// Creates a session on the CONNECT request stream of a WebTransport request
+    ///
+    /// The stream must have received a successful response from the server.
+    pub fn connect_session(&self, stream: ClientConnectStream) -> Session {
+        let id = stream.id().into_inner();
+        self.new_session(id, ConnectStream::Client(stream))
+    }
+
+    fn new_session(&self, id: SessionId, connect_stream: ConnectStream) -> Session {
+        self.router.open_session(id);
+        Session {
+            id,
+            conn: self.conn.clone(),
+            router: self.router.clone(),
+            connect_stream: Some(connect_stream),
+        }
+    }
+}
+
+/// An established WebTransport session
+pub struct Session {
+    id: SessionId,
+    conn: Handle,
+    router: Router,
+    connect_stream: Option<ConnectStream>,
+}
+
+impl Session {
+    /// Returns the id of the session
+    pub fn id(&self) -> SessionId {
+        self.id
+    }
+
+    /// Accepts a bidirectional stream opened by the peer
+    ///
+    /// Returns `Ok(None)` once the connection is closed.
+    pub async fn accept_bi(&self) -> Result<Option<BidiStream>, Error> {
+        futures::future::poll_fn(|cx| self.poll_accept_bi(cx)).await
+    }
+
+    /// Polls for a bidirectional stream opened by the peer
+    pub fn poll_accept_bi(&self, cx: &mut Context) -> Poll<Result<Option<BidiStream>, Error>> {
+        self.router
+            .poll_session(self.id, cx, |session| session.bidi.pop_front())
+    }
+
+    /// Accepts a unidirectional stream opened by the peer
+    ///
+    /// Returns `Ok(None)` once the connection is closed.
+    pub async fn accept_uni(&self) -> Result<Option<RecvStream>, Error> {
+        futures::future::poll_fn(|cx| self.poll_accept_uni(cx)).await
+    }
+
+    /// Polls for a unidirectional stream opened by the peer
+    pub fn poll_accept_uni(&self, cx: &mut Context) -> Poll<Result<Option<RecvStream>, Error>> {
+        self.router
+            .poll_session(self.id, cx, |session| session.uni.pop_front())
+    }
+
+    /// Opens a bidirectional stream associated with the session
+    pub async fn open_bi(&self) -> Result<BidiStream, Error> {
+        let mut stream = self.conn.clone().open_bidirectional_stream().await?;
+        stream
+            .send(stream_header(BIDI_STREAM_SIGNAL, self.id))
+            .await?;
+        Ok(BidiStream::new(stream, Bytes::new()))
+    }
+
+    /// Opens a unidirectional stream associated with the session
+    pub async fn open_uni(&self) -> Result<SendStream, Error> {
+        let mut stream = self.conn.clone().open_send_stream().await?;
+        stream.send(stream_header(UNI_STREAM_TYPE, self.id)).await?;
+        Ok(stream)
+    }
+
+    /// Enqueues a datagram associated with the session
+    ///
+    /// The connection must be configured with the
+    /// [`default`](s2n_quic::provider::datagram::default) datagram provider. An error is returned
+    /// if its send queue is full.
+    pub fn send_datagram(&self, data: Bytes) -> Result<(), Error> {
+        // datagrams are prefixed with the quarter stream id of the CONNECT request stream
+        let quarter_stream_id = VarInt::new(self.id / 4).expect("session ids are valid varints");
+        let buf = Bytes::from((quarter_stream_id, data).encode_to_vec());
+
+        self.conn
+            .datagram_mut(|sender: &mut Sender| sender.send_datagram(buf))
+            .map_err(ProviderDatagramError::from)
+            .and_then(|result| result)
+            .map_err(DatagramError::from)?;
+
+        Ok(())
+    }
+
+    /// Receives a datagram associated with the session
+    ///
+    /// Returns `Ok(None)` once the connection is closed.
+    pub async fn recv_datagram(&self) -> Result<Option<Bytes>, Error> {
+        futures::future::poll_fn(|cx| self.poll_recv_datagram(cx)).await
+    }
+
+    /// Polls for a datagram associated with the session
+    pub fn poll_recv_datagram(&self, cx: &mut Context) -> Poll<Result<Option<Bytes>, Error>> {
+        self.router
+            .poll_session(self.id, cx, |session| session.datagrams.pop_front())
+    }
+
+    /// Closes the session with an application error code and reason
+    ///
+    /// A `CLOSE_WEBTRANSPORT_SESSION` capsule is sent to the peer and the CONNECT request
+    /// stream is finished. Streams and datagrams that arrive for the session afterwards are
+    /// discarded. An error is returned if the reason is longer than [`MAX_CLOSE_REASON_LEN`]
+    /// bytes.
+    pub async fn close(mut self, code: u32, reason: &str) -> Result<(), Error> {
+        if reason.len() > MAX_CLOSE_REASON_LEN {
+            return Err(Error::InvalidCloseReason);
+        }
+
+        let len = VarInt::try_from(4 + reason.len()).expect("the reason length is bounded");
+        let value = (code, Bytes::copy_from_slice(reason.as_bytes()));
+        let capsule = (CLOSE_SESSION_CAPSULE_TYPE, (len, value)).encode_to_vec();
+
+        let mut stream = self
+            .connect_stream
+            .take()
+            .expect("the CONNECT stream is only taken on close");
+        self.router.close_session(self.id);
+
+        stream.send_data(capsule.into()).await?;
+        stream.finish().await?;
+        Ok(())
+    }
+
+    /// Waits for the peer to close the session
+    ///
+    /// Returns the error code and reason from the peer's `CLOSE_WEBTRANSPORT_SESSION` capsule,
+    /// or a code of `0` if the peer finished the CONNECT request stream without one.
+    pub async fn closed(&mut self) -> Result<(u32, Bytes), Error> {
+        let stream = self
+            .connect_stream
+            .as_mut()
+            .expect("the CONNECT stream is only taken on close");
+
+        let mut buf = BytesMut::new();
+        loop {
+            if let Some(capsule) = decode_close_capsule(&mut buf)? {
+                self.router.close_session(self.id);
+                return Ok(capsule);
+            }
+
+            match stream.recv_data().await? {
+                Some(mut data) => {
+                    while data.has_remaining() {
+                        let chunk = data.chunk();
+                        buf.put_slice(chunk);
+                        let len = chunk.len();
+                        data.advance(len);
+                    }
+                }
+                None => {
+                    self.router.close_session(self.id);
+                    return Ok((0, Bytes::new()));
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        self.router.close_session(self.id);
+    }
+}
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Session").field("id", &self.id).finish()
+    }
+}
+
+/// The CONNECT request stream of a session
+enum ConnectStream {
+    Server(ServerConnectStream),
+    Client(ClientConnectStream),
+}
+
+impl ConnectStream {
+    async fn send_data(&mut self, data: Bytes) -> Result<(), h3::Error> {
+        match self {
+            Self::Server(stream) => stream.send_data(data).await,
+            Self::Client(stream) => stream.send_data(data).await,
+        }
+    }
+
+    async fn finish(&mut self) -> Result<(), h3::Error> {
+        match self {
+            Self::Server(stream) => stream.finish().await,
+            Self::Client(stream) => stream.finish().await,
+        }
+    }
+
+    async fn recv_data(&mut self) -> Result<Option<Bytes>, h3::Error> {
+        let data = match self {
+            Self::Server(stream) => stream
+                .recv_data()
+                .await?
+                .map(|mut data| data.copy_to_bytes(data.remaining())),
+            Self::Client(stream) => stream
+                .recv_data()
+                .await?
+                .map(|mut data| data.copy_to_bytes(data.remaining())),
+        };
+        Ok(data)
+    }
+}
+
+/// The sending half of a WebTransport stream
+pub type SendStream = s2n_quic::stream::SendStream;
+
+/// The receiving half of a WebTransport stream
+pub struct RecvStream {
+    stream: ReceiveStream,
+    /// Data that was read while routing the stream to its session
+    prefix: Option<Bytes>,
+}
+
+impl RecvStream {
+    fn new(stream: ReceiveStream, prefix: Bytes) -> Self {
+        Self {
+            stream,
+            prefix: Some(prefix).filter(|prefix| !prefix.is_empty()),
+        }
+    }
+
+    /// Returns the id of the underlying QUIC stream
+    pub fn id(&self) -> u64 {
+        self.stream.id()
+    }
+
+    /// Receives a chunk of data from the stream
+    ///
+    /// Returns `Ok(None)` once the stream is finished and all of the data was received.
+    pub async fn receive(&mut self) -> s2n_quic::stream::Result<Option<Bytes>> {
+        futures::future::poll_fn(|cx| self.poll_receive(cx)).await
+    }
+
+    /// Polls for a chunk of data from the stream
+    pub fn poll_receive(
+        &mut self,
+        cx: &mut Context,
+    ) -> Poll<s2n_quic::stream::Result<Option<Bytes>>> {
+        if let Some(prefix) = self.prefix.take() {
+            return Poll::Ready(Ok(Some(prefix)));
+        }
+
+        self.stream.poll_receive(cx)
+    }
+
+    /// Requests the peer to stop sending data on the stream
+    pub fn stop_sending(
+        &mut self,
+        error_code: s2n_quic::application::Error,
+    ) -> s2n_quic::stream::Result<()> {
+        self.stream.stop_sending(error_code)
+    }
+}
+
+/// A bidirectional WebTransport stream
+pub struct BidiStream {
+    recv: RecvStream,
+    send: SendStream,
+}
+
+impl BidiStream {
+    fn new(stream: BidirectionalStream, prefix: Bytes) -> Self {
+        let (recv, send) = stream.split();
+        Self {
+            recv: RecvStream::new(recv, prefix),
+            send,
+        }
+    }
+
+    /// Returns the id of the underlying QUIC stream
+    pub fn id(&self) -> u64 {
+        self.send.id()
+    }
+
+    /// Receives a chunk of data from the stream
+    pub async fn receive(&mut self) -> s2n_quic::stream::Result<Option<Bytes>> {
+        self.recv.receive().await
+    }
+
+    /// Sends a chunk of data on the stream
+    pub async fn send(&mut self, data: Bytes) -> s2n_quic::stream::Result<()> {
+        self.send.send(data).await
+    }
+
+    /// Finishes the sending half of the stream
+    pub fn finish(&mut self) -> s2n_quic::stream::Result<()> {
+        self.send.finish()
+    }
+
+    /// Splits the stream into its receiving and sending halves
+    pub fn split(self) -> (RecvStream, SendStream) {
+        (self.recv, self.send)
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// The request isn't a WebTransport request
+    InvalidRequest,
+    /// The close reason is longer than [`MAX_CLOSE_REASON_LEN`] bytes
+    InvalidCloseReason,
+    Connection(connection::Error),
+    Stream(s2n_quic::stream::Error),
+    Datagram(DatagramError),
+    Http(h3::Error),
+    /// The peer sent a malformed capsule on the CONNECT request stream
+    MalformedCapsule,
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRequest => write!(f, "the request isn't a WebTransport request"),
+            Self::InvalidCloseReason => write!(
+                f,
+                "the close reason is longer than {MAX_CLOSE_REASON_LEN} bytes"
+            ),
+            Self::Connection(error) => error.fmt(f),
+            Self::Stream(error) => error.fmt(f),
+            Self::Datagram(error) => error.fmt(f),
+            Self::Http(error) => error.fmt(f),
+            Self::MalformedCapsule => write!(f, "the peer sent a malformed capsule"),
+        }
+    }
+}
+
+impl From<connection::Error> for Error {
+    fn from(error: connection::Error) -> Self {
+        Self::Connection(error)
+    }
+}
+
+impl From<s2n_quic::stream::Error> for Error {
+    fn from(error: s2n_quic::stream::Error) -> Self {
+        Self::Stream(error)
+    }
+}
+
+impl From<DatagramError> for Error {
+    fn from(error: DatagramError) -> Self {
+        Self::Datagram(error)
+    }
+}
+
+impl From<h3::Error> for Error {
+    fn from(error: h3::Error) -> Self {
+        Self::Http(error)
+    }
+}
+
+/// Routes incoming streams and datagrams between h3 and WebTransport sessions
+///
+/// The router is shared by the h3 connection and all of its sessions. Whichever of them polls
+/// first accepts new streams and reads their headers to find out where they belong.
+#[derive(Clone)]
+pub(crate) struct Router {
+    state: Arc<Mutex<State>>,
+    wakers: Arc<Wakers>,
+}
+
+impl Router {
+    pub(crate) fn new(
+        conn: Handle,
+        bidi: BidirectionalStreamAcceptor,
+        recv: ReceiveStreamAcceptor,
+    ) -> Self {
+        let state = State {
+            conn,
+            bidi: Some(bidi),
+            recv: Some(recv),
+            error: None,
+            can_recv_datagrams: true,
+            pending_bidi: vec![],
+            pending_recv: vec![],
+            h3_bidi: VecDeque::new(),
+            h3_recv: VecDeque::new(),
+            sessions: HashMap::new(),
+            closed_sessions: VecDeque::new(),
+        };
+
+        Self {
+            state: Arc::new(Mutex::new(state)),
+            wakers: Default::default(),
+        }
+    }
+
+    pub(crate) fn poll_accept_h3_bidi(
+        &self,
+        cx: &mut Context,
+    ) -> Poll<Result<Option<(BidirectionalStream, Bytes)>, connection::Error>> {
+        self.poll(cx, |state| {
+            state
+                .h3_bidi
+                .pop_front()
+                .map(Some)
+                .map(Ok)
+                .or_else(|| state.closed_result())
+        })
+    }
+
+    pub(crate) fn poll_accept_h3_recv(
+        &self,
+        cx: &mut Context,
+    ) -> Poll<Result<Option<(ReceiveStream, Bytes)>, connection::Error>> {
+        self.poll(cx, |state| {
+            state
+                .h3_recv
+                .pop_front()
+                .map(Some)
+                .map(Ok)
+                .or_else(|| state.closed_result())
+        })
+    }
+
+    fn poll_session<T>(
+        &self,
+        id: SessionId,
+        cx: &mut Context,
+        pop: impl Fn(&mut SessionState) -> Option<T>,
+    ) -> Poll<Result<Option<T>, Error>> {
+        let result = ready!(self.poll(cx, |state| {
+            match state.sessions.get_mut(&id) {
+                Some(session) if session.is_accepted => {
+                    if let Some(value) = pop(session) {
+                        return Some(Ok(Some(value)));
+                    }
+                }
+                // the session was accepted before polling, so it must have been closed
+                _ => return Some(Ok(None)),
+            }
+            state.closed_result()
+        }));
+        Poll::Ready(result.map_err(Error::from))
+    }
+
+    fn open_session(&self, id: SessionId) {
+        let mut state = self.state.lock().unwrap();
+        state.closed_sessions.retain(|closed| *closed != id);
+        state.sessions.entry(id).or_default().is_accepted = true;
+    }
+
+    fn close_session(&self, id: SessionId) {
+        let mut state = self.state.lock().unwrap();
+        // dropping the queued streams stops the peer from sending on them
+        state.sessions.remove(&id);
+        if !state.closed_sessions.contains(&id) {
+            if state.closed_sessions.len() == CLOSED_SESSION_CAPACITY {
+                state.closed_sessions.pop_front();
+            }
+            state.closed_sessions.push_back(id);
+        }
+    }
+
+    /// Makes progress on routing and returns the output of `ready`, if any
+    fn poll<T>(&self, cx: &mut Context, mut ready: impl FnMut(&mut State) -> Option<T>) -> Poll<T> {
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(value) = ready(&mut state) {
+            return Poll::Ready(value);
+        }
+
+        // all of the tasks waiting on the router are woken, since a new stream may belong to
+        // any of them
+        self.wakers.register(cx.waker());
+        let waker = Waker::from(self.wakers.clone());
+        let mut router_cx = Context::from_waker(&waker);
+
+        if state.route(&mut router_cx) {
+            self.wakers.wake_all();
+        }
+
+        match ready(&mut state) {
+            Some(value) => Poll::Ready(value),
+            None => Poll::Pending,
+        }
+    }
+}
+
+struct State {
+    conn: Handle,
+    bidi: Option<BidirectionalStreamAcceptor>,
+    recv: Option<ReceiveStreamAcceptor>,
+    error: Option<connection::Error>,
+    can_recv_datagrams: bool,
+    pending_bidi: Vec<PendingStream<BidirectionalStream>>,
+    pending_recv: Vec<PendingStream<ReceiveStream>>,
+    h3_bidi: VecDeque<(BidirectionalStream, Bytes)>,
+    h3_recv: VecDeque<(ReceiveStream, Bytes)>,
+    sessions: HashMap<SessionId, SessionState>,
+    /// The most recently closed sessions, oldest first
+    closed_sessions: VecDeque<SessionId>,
+}
+
+impl State {
+    /// Returns the result for pollers once no more streams will be accepted
+    fn closed_result<T>(&self) -> Option<Result<Option<T>, connection::Error>> {
+        if let Some(error) = self.error {
+            return Some(Err(error));
+        }
+
+        let is_closed = self.bidi.is_none() && self.recv.is_none();
+        let is_routing = !self.pending_bidi.is_empty() || !self.pending_recv.is_empty();
+        (is_closed && !is_routing).then_some(Ok(None))
+    }
+
+    /// Routes newly accepted streams and datagrams, returning `true` if any were routed
+    fn route(&mut self, cx: &mut Context) -> bool {
+        let mut has_routed = false;
+
+        if let Some(acceptor) = self.bidi.as_mut() {
+            loop {
+                match acceptor.poll_accept_bidirectional_stream(cx) {
+                    Poll::Ready(Ok(Some(stream))) => {
+                        self.pending_bidi.push(PendingStream::new(stream))
+                    }
+                    Poll::Ready(Ok(None)) => {
+                        self.bidi = None;
+                        has_routed = true;
+                        break;
+                    }
+                    Poll::Ready(Err(error)) => {
+                        self.bidi = None;
+                        self.error = Some(error);
+                        has_routed = true;
+                        break;
+                    }
+                    Poll::Pending => break,
+                }
+            }
+        }
+
+        if let Some(acceptor) = self.recv.as_mut() {
+            loop {
+                match acceptor.poll_accept_receive_stream(cx) {
+                    Poll::Ready(Ok(Some(stream))) => {
+                        self.pending_recv.push(PendingStream::new(stream))
+                    }
+                    Poll::Ready(Ok(None)) => {
+                        self.recv = None;
+                        has_routed = true;
+                        break;
+                    }
+                    Poll::Ready(Err(error)) => {
+                        self.recv = None;
+                        self.error = Some(error);
+                        has_routed = true;
+                        break;
+                    }
+                    Poll::Pending => break,
+                }
+            }
+        }
+
+        let mut idx = 0;
+        while idx < self.pending_bidi.len() {
+            let pending = &mut self.pending_bidi[idx];
+            let Poll::Ready(header) = pending.poll_header(BIDI_STREAM_SIGNAL, cx) else {
+                idx += 1;
+                continue;
+            };
+            let pending = self.pending_bidi.swap_remove(idx);
+            has_routed = true;
+
+            match header {
+                Some(session_id) => match self.session(session_id) {
+                    Some(session) => session
+                        .bidi
+                        .push_back(BidiStream::new(pending.stream, pending.buf.freeze())),
+                    None => {
+                        let mut stream = pending.stream;
+                        let error = application::Error::from(BUFFERED_STREAM_REJECTED);
+                        let _ = stream.stop_sending(error);
+                        let _ = stream.reset(error);
+                    }
+                },
+                None => self
+                    .h3_bidi
+                    .push_back((pending.stream, pending.buf.freeze())),
+            }
+        }
+
+        let mut idx = 0;
+        while idx < self.pending_recv.len() {
+            let pending = &mut self.pending_recv[idx];
+            let Poll::Ready(header) = pending.poll_header(UNI_STREAM_TYPE, cx) else {
+                idx += 1;
+                continue;
+            };
+            let pending = self.pending_recv.swap_remove(idx);
+            has_routed = true;
+
+            match header {
+                Some(session_id) => match self.session(session_id) {
+                    Some(session) => session
+                        .uni
+                        .push_back(RecvStream::new(pending.stream, pending.buf.freeze())),
+                    None => {
+                        let mut stream = pending.stream;
+                        let error = application::Error::from(BUFFERED_STREAM_REJECTED);
+                        let _ = stream.stop_sending(error);
+                    }
+                },
+                None => self
+                    .h3_recv
+                    .push_back((pending.stream, pending.buf.freeze())),
+            }
+        }
+
+        while self.can_recv_datagrams {
+            match self.conn.poll_recv_datagram(cx) {
+                Poll::Ready(Ok(mut datagram)) => {
+                    let buffer = DecoderBuffer::new(&datagram);
+                    let Ok((quarter_stream_id, payload)) = buffer.decode::<VarInt>() else {
+                        continue;
+                    };
+                    let header_len = datagram.len() - payload.len();
+                    let Some(session_id) = quarter_stream_id.checked_mul(VarInt::from_u8(4)) else {
+                        continue;
+                    };
+                    let Some(session) = self.session(*session_id) else {
+                        continue;
+                    };
+                    datagram.advance(header_len);
+                    // the oldest datagram is dropped if the application isn't keeping up
+                    if session.datagrams.len() == SESSION_DATAGRAM_CAPACITY {
+                        session.datagrams.pop_front();
+                    }
+                    session.datagrams.push_back(datagram);
+                    has_routed = true;
+                }
+                // the connection isn't configured to receive datagrams or is closed
+                Poll::Ready(Err(_)) => self.can_recv_datagrams = false,
+                Poll::Pending => break,
+            }
+        }
+
+        has_routed
+    }
+
+    /// Returns the state of the session that a stream or datagram belongs to
+    ///
+    /// Streams and datagrams can arrive before the CONNECT request was accepted, so they're
+    /// buffered for up to [`MAX_PENDING_SESSIONS`] sessions that weren't accepted yet. `None` is
+    /// returned if the id can't belong to a session, the session was closed or too many sessions
+    /// are pending.
+    fn session(&mut self, id: SessionId) -> Option<&mut SessionState> {
+        // sessions are only established on client-initiated bidirectional streams
+        let stream_id = StreamId::from_varint(VarInt::new(id).ok()?);
+        if stream_id.initiator() != endpoint::Type::Client
+            || stream_id.stream_type() != StreamType::Bidirectional
+        {
+            return None;
+        }
+
+        if self.closed_sessions.contains(&id) {
+            return None;
+        }
+
+        if !self.sessions.contains_key(&id) {
+            let pending = self.sessions.values().filter(|s| !s.is_accepted).count();
+            if pending >= MAX_PENDING_SESSIONS {
+                return None;
+            }
+        }
+
+        Some(self.sessions.entry(id).or_default())
+    }
+}
+
+#[derive(Default)]
+struct SessionState {
+    /// Set once the CONNECT request was accepted or the session was connected
+    is_accepted: bool,
+    bidi: VecDeque<BidiStream>,
+    uni: VecDeque<RecvStream>,
+    datagrams: VecDeque<Bytes>,
+}
+
+/// A stream whose header hasn't been read yet
+struct PendingStream<S> {
+    stream: S,
+    buf: BytesMut,
+}
+
+impl<S: ReceiveStreamExt> PendingStream<S> {
+    fn new(stream: S) -> Self {
+        Self {
+            stream,
+            buf: BytesMut::new(),
+        }
+    }
+
+    /// Reads the header of the stream
+    ///
+    /// Returns the session id if the stream starts with `session_type`, or `None` if the stream
+    /// belongs to h3. The remaining buffered data is left in `buf`.
+    fn poll_header(&mut self, session_type: VarInt, cx: &mut Context) -> Poll<Option<SessionId>> {
+        loop {
+            let buffer = DecoderBuffer::new(&self.buf);
+            match buffer.decode::<VarInt>() {
+                Ok((value, _)) if value != session_type => return Poll::Ready(None),
+                Ok((_, buffer)) => {
+                    if let Ok((session_id, buffer)) = buffer.decode::<VarInt>() {
+                        let len = self.buf.len() - buffer.len();
+                        self.buf.advance(len);
+                        return Poll::Ready(Some(*session_id));
+                    }
+                }
+                // the header isn't complete yet
+                Err(_) => {}
+            }
+
+            match ready!(self.stream.poll_receive_chunk(cx)) {
+                Ok(Some(chunk)) => self.buf.put(chunk),
+                // let h3 observe the end of the stream or the error
+                Ok(None) | Err(_) => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+trait ReceiveStreamExt {
+    fn poll_receive_chunk(
+        &mut self,
+        cx: &mut Context,
+    ) -> Poll<s2n_quic::stream::Result<Option<Bytes>>>;
+}
+
+impl ReceiveStreamExt for BidirectionalStream {
+    fn poll_receive_chunk(
+        &mut self,
+        cx: &mut Context,
+    ) -> Poll<s2n_quic::stream::Result<Option<Bytes>>> {
+        self.poll_receive(cx)
+    }
+}
+
+impl ReceiveStreamExt for ReceiveStream {
+    fn poll_receive_chunk(
+        &mut self,
+        cx: &mut Context,
+    ) -> Poll<s2n_quic::stream::Result<Option<Bytes>>> {
+        self.poll_receive(cx)
+    }
+}
+
+/// Wakes all of the tasks waiting on the router
+#[derive(Default)]
+struct Wakers(Mutex<Vec<Waker>>);
+
+impl Wakers {
+    fn register(&self, waker: &Waker) {
+        let mut wakers = self.0.lock().unwrap();
+        if !wakers.iter().any(|w| w.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+    }
+
+    fn wake_all(&self) {
+        let wakers = core::mem::take(&mut *self.0.lock().unwrap());
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+impl Wake for Wakers {
+    fn wake(self: Arc<Self>) {
+        self.wake_all();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.wake_all();
+    }
+}
+
+fn stream_header(ty: VarInt, session_id: SessionId) -> Bytes {
+    let session_id = VarInt::new(session_id).expect("session ids are valid varints");
+    (ty, session_id).encode_to_vec().into()
+}
+
+/// Decodes the `CLOSE_WEBTRANSPORT_SESSION` capsule at the start of `buf`, skipping any other
+/// capsules
+fn decode_close_capsule(buf: &mut BytesMut) -> Result<Option<(u32, Bytes)>, Error> {
+    loop {
+        let buffer = DecoderBuffer::new(&buf[..]);
+        let Ok((ty, buffer)) = buffer.decode::<VarInt>() else {
+            return Ok(None);
+        };
+        let Ok((len, buffer)) = buffer.decode::<VarInt>() else {
+            return Ok(None);
+        };
+        let Ok(len) = usize::try_from(*len) else {
+            return Err(Error::MalformedCapsule);
+        };
+
+        let is_close = ty == CLOSE_SESSION_CAPSULE_TYPE;
+        // the capsule contains the error code followed by the reason
+        if is_close && !(4..=4 + MAX_CLOSE_REASON_LEN).contains(&len) {
+            return Err(Error::MalformedCapsule);
+        }
+
+        if buffer.len() < len {
+            return Ok(None);
+        }
+
+        let header_len = buf.len() - buffer.len();
+        buf.advance(header_len);
+        let mut value = buf.split_to(len).freeze();
+
+        if is_close {
+            let code = value.get_u32();
+            return Ok(Some((code, value)));
+        }
+    }
+}
@@ -0,0 +1,243 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::Connection;
+use s2n_quic::{
+    client::Connect,
+    provider::io::testing::{primary, spawn, test, time::delay, Handle, Model, Result},
+    Client, Server,
+};
+use s2n_quic_core::crypto::tls::testing::certificates;
+use std::{net::SocketAddr, time::Duration};
+
+const CLOSE_CODE: u32 = 42;
+
+/// Starts a server that echoes everything sent on a WebTransport session
+fn start_server(handle: &Handle) -> Result<SocketAddr> {
+    let mut server = Server::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls((certificates::CERT_PEM, certificates::KEY_PEM))?
+        .with_datagram(crate::ext::datagram_provider())?
+        .start()?;
+    let server_addr = server.local_addr()?;
+
+    spawn(async move {
+        let connection = server.accept().await.unwrap();
+        let (conn, webtransport) = Connection::with_webtransport(connection);
+        let mut conn = server_builder().build(conn).await.unwrap();
+
+        while let Ok(Some((request, stream))) = conn.accept().await {
+            assert!(is_webtransport_request(&request));
+            let session = webtransport.accept_session(&request, stream).await.unwrap();
+            spawn(echo_session(session));
+        }
+    });
+
+    Ok(server_addr)
+}
+
+async fn echo_session(session: Session) {
+    // echo a bidirectional stream on the same stream
+    let mut stream = session.accept_bi().await.unwrap().unwrap();
+    while let Some(chunk) = stream.receive().await.unwrap() {
+        stream.send(chunk).await.unwrap();
+    }
+    stream.finish().unwrap();
+
+    // echo a unidirectional stream on a new stream
+    let mut recv = session.accept_uni().await.unwrap().unwrap();
+    let mut send = session.open_uni().await.unwrap();
+    while let Some(chunk) = recv.receive().await.unwrap() {
+        send.send(chunk).await.unwrap();
+    }
+    send.finish().unwrap();
+
+    // echo a datagram
+    let datagram = session.recv_datagram().await.unwrap().unwrap();
+    session.send_datagram(datagram).unwrap();
+
+    session.close(CLOSE_CODE, "done").await.unwrap();
+}
+
+async fn receive_all(stream: &mut RecvStream) -> Vec<u8> {
+    let mut data = vec![];
+    while let Some(chunk) = stream.receive().await.unwrap() {
+        data.extend_from_slice(&chunk);
+    }
+    data
+}
+
+#[test]
+fn session_round_trip() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server_addr = start_server(handle)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_datagram(crate::ext::datagram_provider())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+            let (conn, webtransport) = Connection::with_webtransport(connection);
+            let (mut driver, mut send_request) = h3::client::new(conn).await.unwrap();
+            spawn(async move {
+                let _ = futures::future::poll_fn(|cx| driver.poll_close(cx)).await;
+            });
+
+            let mut request = Request::builder()
+                .method(Method::CONNECT)
+                .uri("https://localhost/session")
+                .body(())
+                .unwrap();
+            request.extensions_mut().insert(Protocol::WEB_TRANSPORT);
+
+            let mut stream = send_request.send_request(request).await.unwrap();
+            let response = stream.recv_response().await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let mut session = webtransport.connect_session(stream);
+            assert_eq!(session.id(), 0);
+
+            let mut bidi = session.open_bi().await.unwrap();
+            bidi.send(Bytes::from_static(b"hello bidi")).await.unwrap();
+            bidi.finish().unwrap();
+            let (mut recv, _send) = bidi.split();
+            assert_eq!(receive_all(&mut recv).await, b"hello bidi");
+
+            let mut send = session.open_uni().await.unwrap();
+            send.send(Bytes::from_static(b"hello uni")).await.unwrap();
+            send.finish().unwrap();
+            let mut recv = session.accept_uni().await.unwrap().unwrap();
+            assert_eq!(receive_all(&mut recv).await, b"hello uni");
+
+            session
+                .send_datagram(Bytes::from_static(b"hello datagram"))
+                .unwrap();
+            let datagram = session.recv_datagram().await.unwrap().unwrap();
+            assert_eq!(&datagram[..], b"hello datagram");
+
+            let (code, reason) = session.closed().await.unwrap();
+            assert_eq!(code, CLOSE_CODE);
+            assert_eq!(&reason[..], b"done");
+
+            // the session doesn't accept streams once it's closed
+            assert!(session.accept_bi().await.unwrap().is_none());
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
+
+/// Ensures a peer can't grow the state of the connection by sending streams and datagrams for
+/// sessions that were never requested
+#[test]
+fn unknown_session_flood() {
+    const FLOOD_SESSIONS: u64 = 2 * DEFAULT_MAX_SESSIONS;
+    let model = Model::default();
+
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls((certificates::CERT_PEM, certificates::KEY_PEM))?
+            .with_datagram(crate::ext::datagram_provider())?
+            .start()?;
+        let server_addr = server.local_addr()?;
+
+        primary::spawn(async move {
+            let connection = server.accept().await.unwrap();
+            let (conn, webtransport) = Connection::with_webtransport(connection);
+            let mut conn = server_builder().build(conn).await.unwrap();
+
+            // accepting requests drives the routing of streams and datagrams
+            while let Ok(Some(_)) = conn.accept().await {}
+
+            let state = webtransport.router.state.lock().unwrap();
+            assert_eq!(state.sessions.len(), MAX_PENDING_SESSIONS);
+            for session in state.sessions.values() {
+                assert!(!session.is_accepted);
+                assert!(session.datagrams.len() <= SESSION_DATAGRAM_CAPACITY);
+            }
+        });
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_datagram(crate::ext::datagram_provider())?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            for idx in 0..FLOOD_SESSIONS {
+                // the id of a server-initiated stream never belongs to a session
+                for session_id in [idx * 4, idx * 4 + 1] {
+                    let mut stream = connection.open_send_stream().await.unwrap();
+                    let _ = stream
+                        .send(stream_header(UNI_STREAM_TYPE, session_id))
+                        .await;
+                    let _ = stream.finish();
+                }
+
+                let quarter_stream_id = VarInt::new(FLOOD_SESSIONS + idx).unwrap();
+                let datagram = (quarter_stream_id, Bytes::from_static(b"flood")).encode_to_vec();
+                connection
+                    .datagram_mut(|sender: &mut Sender| sender.send_datagram(datagram.into()))
+                    .unwrap()
+                    .unwrap();
+            }
+
+            // give the server time to route everything before closing the connection
+            delay(Duration::from_secs(1)).await;
+            connection.close(0u32.into());
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+}
+
+#[test]
+fn close_capsule() {
+    let mut buf = BytesMut::new();
+    // an unknown capsule is skipped
+    let unknown = (VarInt::from_u32(0x1234), (VarInt::from_u8(2), 0x0102u16));
+    buf.put_slice(&unknown.encode_to_vec());
+    // a partial close capsule isn't decoded yet
+    let header = (CLOSE_SESSION_CAPSULE_TYPE, (VarInt::from_u8(6), 7u32));
+    buf.put_slice(&header.encode_to_vec());
+    assert_eq!(decode_close_capsule(&mut buf).unwrap(), None);
+
+    buf.put_slice(b"ok");
+    assert_eq!(
+        decode_close_capsule(&mut buf).unwrap(),
+        Some((7, Bytes::from_static(b"ok")))
+    );
+}
+
+#[test]
+fn close_capsule_reason_limit() {
+    // the reason is limited to 1024 bytes, which is checked before the capsule is buffered
+    let len = VarInt::try_from(4 + MAX_CLOSE_REASON_LEN + 1).unwrap();
+    let header = (CLOSE_SESSION_CAPSULE_TYPE, (len, 7u32));
+    let mut buf = BytesMut::from(&header.encode_to_vec()[..]);
+    assert!(matches!(
+        decode_close_capsule(&mut buf),
+        Err(Error::MalformedCapsule)
+    ));
+
+    // the code is required
+    let header = (CLOSE_SESSION_CAPSULE_TYPE, (VarInt::from_u8(2), 7u16));
+    let mut buf = BytesMut::from(&header.encode_to_vec()[..]);
+    assert!(matches!(
+        decode_close_capsule(&mut buf),
+        Err(Error::MalformedCapsule)
+    ));
+}
@@ -14,6 +14,8 @@ enum Feature {
     GenericReceiveOffload,
     PacketInfo,
     TypeOfService,
+    RecvError,
+    TransmitTime,
 }
 
 impl Feature {
@@ -27,6 +29,8 @@ impl Feature {
             GenericReceiveOffload => "gro",
             PacketInfo => "pktinfo",
             TypeOfService => "tos",
+            RecvError => "recverr",
+            TransmitTime => "txtime",
         }
     }
 }
@@ -43,7 +47,7 @@ impl From<&str> for Feature {
     }
 }
 
-const ALL_FEATURES: [Feature; 8] = [
+const ALL_FEATURES: [Feature; 10] = [
     ControlMessage,
     SocketMessage,
     SocketMultiMessage,
@@ -52,6 +56,8 @@ const ALL_FEATURES: [Feature; 8] = [
     GenericReceiveOffload,
     PacketInfo,
     TypeOfService,
+    RecvError,
+    TransmitTime,
 ];
 
 fn main() -> Result<(), Error> {
@@ -95,6 +101,8 @@ fn main() -> Result<(), Error> {
             features.insert(GenericReceiveOffload);
             features.insert(PacketInfo);
             features.insert(TypeOfService);
+            features.insert(RecvError);
+            features.insert(TransmitTime);
         }
         "macos" => {
             // miri doesn't support the way we detect syscall support so override it
@@ -143,6 +151,8 @@ impl Features {
             GenericReceiveOffload,
             PacketInfo,
             TypeOfService,
+            RecvError,
+            TransmitTime,
         ]
         .contains(&feature)
             && !self.supports(ControlMessage)
@@ -11,6 +11,7 @@ pub mod ancillary;
 pub mod cbpf;
 pub mod ebpf;
 mod program;
+pub mod shard;
 
 pub use cbpf::Cbpf;
 pub use ebpf::Ebpf;
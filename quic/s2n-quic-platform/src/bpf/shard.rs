@@ -0,0 +1,308 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generates a `SO_REUSEPORT` program which steers packets to the socket of the shard encoded in
+//! the destination connection ID
+//!
+//! The program returns the shard index, which the kernel uses to select the socket in the reuseport
+//! group. The sockets must be added to the group in shard order for the indexes to match.
+//!
+//! The program is generated as classic BPF with [`Program`] and as eBPF with [`EbpfProgram`]. The
+//! eBPF program is JIT compiled by the kernel, but loading it requires `CAP_BPF` unless
+//! unprivileged eBPF is enabled, while the classic program can be attached by any process.
+
+use super::cbpf::{self, *};
+use core::{fmt, mem::size_of};
+use s2n_quic_core::connection::id::shard::{
+    Encoding, LONG_HEADER_LEN_OFFSET, LONG_HEADER_OFFSET, SHORT_HEADER_OFFSET,
+};
+use std::{
+    io,
+    os::fd::{AsRawFd, FromRawFd, OwnedFd},
+};
+
+#[cfg(all(test, not(miri)))]
+mod tests;
+
+const LEN: usize = 10;
+
+/// A program which steers packets to the socket of the shard that owns the connection
+#[derive(Clone, Copy)]
+pub struct Program {
+    instructions: [cbpf::Instruction; LEN],
+}
+
+impl Program {
+    #[inline]
+    pub const fn new(encoding: Encoding) -> Self {
+        let offset = encoding.offset() as u32;
+        let count = encoding.count() as u32;
+
+        let instructions = [
+            // load the first byte of the packet
+            ldb(abs(0)),
+            // IF:
+            // the header form bit is set
+            jset(0x80, 0, 4),
+            // THEN:
+            // load the destination connection ID length of the long header
+            ldb(abs(LONG_HEADER_LEN_OFFSET as u32)),
+            // IF:
+            // the connection ID is long enough to contain the shard byte
+            jgt(offset, 0, 5),
+            // THEN:
+            // load the shard byte from the long header connection ID
+            ldb(abs(LONG_HEADER_OFFSET as u32 + offset)),
+            ja(1),
+            // ELSE:
+            // load the shard byte from the short header connection ID
+            ldb(abs(SHORT_HEADER_OFFSET as u32 + offset)),
+            // compute the shard index from the byte
+            rem(count),
+            // return the index of the socket in the reuseport group
+            ret_a(),
+            // the long header connection ID is too short so return an invalid index, which makes
+            // the kernel fall back to selecting a socket by hash
+            ret(count),
+        ];
+
+        Self { instructions }
+    }
+
+    #[inline]
+    pub fn program(&self) -> cbpf::Program {
+        cbpf::Program::new(&self.instructions)
+    }
+
+    /// Attaches the program to the reuseport group of `socket`
+    #[inline]
+    pub fn attach<S: AsRawFd>(&self, socket: &S) -> io::Result<()> {
+        self.program().attach(socket)
+    }
+}
+
+impl fmt::Debug for Program {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.program(), f)
+    }
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.program(), f)
+    }
+}
+
+const EBPF_LEN: usize = 12;
+
+/// The eBPF version of [`Program`]
+///
+/// The program is loaded as a socket filter, which is run with the same packet offsets and return
+/// value semantics as the classic program.
+#[derive(Clone, Copy)]
+pub struct EbpfProgram {
+    instructions: [ebpf::Instruction; EBPF_LEN],
+}
+
+impl EbpfProgram {
+    #[inline]
+    pub const fn new(encoding: Encoding) -> Self {
+        use ebpf::*;
+
+        let offset = encoding.offset() as i32;
+        let count = encoding.count() as i32;
+
+        let instructions = [
+            // packet loads read from the socket buffer in r6
+            mov64_reg(R6, R1),
+            // load the first byte of the packet
+            ldb_abs(0),
+            // IF:
+            // the header form bit is set
+            jset(0x80, 2),
+            // ELSE:
+            // load the shard byte from the short header connection ID
+            ldb_abs(SHORT_HEADER_OFFSET as i32 + offset),
+            ja(5),
+            // THEN:
+            // load the destination connection ID length of the long header
+            ldb_abs(LONG_HEADER_LEN_OFFSET as i32),
+            // IF:
+            // the connection ID is long enough to contain the shard byte
+            jgt(offset, 2),
+            // ELSE:
+            // return an invalid index, which makes the kernel fall back to selecting a socket by
+            // hash
+            mov64_imm(R0, count),
+            exit(),
+            // THEN:
+            // load the shard byte from the long header connection ID
+            ldb_abs(LONG_HEADER_OFFSET as i32 + offset),
+            // compute the shard index from the byte
+            mod_imm(count),
+            // return the index of the socket in the reuseport group
+            exit(),
+        ];
+
+        Self { instructions }
+    }
+
+    /// Loads the program and attaches it to the reuseport group of `socket`
+    pub fn attach<S: AsRawFd>(&self, socket: &S) -> io::Result<()> {
+        let program = self.load()?;
+        let fd = program.as_raw_fd();
+
+        let ret = unsafe {
+            libc::setsockopt(
+                socket.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_ATTACH_REUSEPORT_EBPF,
+                &fd as *const _ as *const _,
+                size_of::<libc::c_int>() as _,
+            )
+        };
+
+        // the socket holds its own reference to the program, so the descriptor is closed either
+        // way
+        if ret < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn load(&self) -> io::Result<OwnedFd> {
+        const LICENSE: &[u8] = b"Apache-2.0\0";
+
+        let attr = ebpf::ProgLoadAttr {
+            prog_type: ebpf::PROG_TYPE_SOCKET_FILTER,
+            insn_cnt: EBPF_LEN as _,
+            insns: self.instructions.as_ptr() as u64,
+            license: LICENSE.as_ptr() as u64,
+            log_level: 0,
+            log_size: 0,
+            log_buf: 0,
+            kern_version: 0,
+            prog_flags: 0,
+        };
+
+        let fd = unsafe {
+            libc::syscall(
+                libc::SYS_bpf,
+                ebpf::PROG_LOAD,
+                &attr as *const ebpf::ProgLoadAttr,
+                size_of::<ebpf::ProgLoadAttr>(),
+            )
+        };
+
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(unsafe { OwnedFd::from_raw_fd(fd as _) })
+    }
+}
+
+impl fmt::Debug for EbpfProgram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EbpfProgram")
+            .field("instructions", &self.instructions)
+            .finish()
+    }
+}
+
+/// The subset of eBPF used by [`EbpfProgram`]
+///
+/// See <https://www.kernel.org/doc/html/latest/bpf/standardization/instruction-set.html>
+mod ebpf {
+    pub const R0: u8 = 0;
+    pub const R1: u8 = 1;
+    pub const R6: u8 = 6;
+
+    pub const PROG_LOAD: libc::c_long = 5;
+    pub const PROG_TYPE_SOCKET_FILTER: u32 = 1;
+
+    /// The `BPF_PROG_LOAD` variant of `union bpf_attr`
+    ///
+    /// The kernel treats the fields after `prog_flags` as zero since they aren't passed.
+    #[repr(C)]
+    pub struct ProgLoadAttr {
+        pub prog_type: u32,
+        pub insn_cnt: u32,
+        pub insns: u64,
+        pub license: u64,
+        pub log_level: u32,
+        pub log_size: u32,
+        pub log_buf: u64,
+        pub kern_version: u32,
+        pub prog_flags: u32,
+    }
+
+    /// `struct bpf_insn`
+    #[derive(Clone, Copy, Debug)]
+    #[repr(C)]
+    pub struct Instruction {
+        code: u8,
+        /// The destination register in the low nibble and the source register in the high nibble
+        regs: u8,
+        off: i16,
+        imm: i32,
+    }
+
+    const fn insn(code: u8, dst: u8, src: u8, off: i16, imm: i32) -> Instruction {
+        Instruction {
+            code,
+            regs: dst | (src << 4),
+            off,
+            imm,
+        }
+    }
+
+    /// `dst = src`
+    pub const fn mov64_reg(dst: u8, src: u8) -> Instruction {
+        // BPF_ALU64 | BPF_MOV | BPF_X
+        insn(0xbf, dst, src, 0, 0)
+    }
+
+    /// `dst = imm`
+    pub const fn mov64_imm(dst: u8, imm: i32) -> Instruction {
+        // BPF_ALU64 | BPF_MOV | BPF_K
+        insn(0xb7, dst, 0, 0, imm)
+    }
+
+    /// `r0 = packet[offset]`
+    pub const fn ldb_abs(offset: i32) -> Instruction {
+        // BPF_LD | BPF_ABS | BPF_B
+        insn(0x30, 0, 0, 0, offset)
+    }
+
+    /// `r0 %= imm`
+    pub const fn mod_imm(imm: i32) -> Instruction {
+        // BPF_ALU | BPF_MOD | BPF_K
+        insn(0x94, R0, 0, 0, imm)
+    }
+
+    /// Skips `off` instructions if `r0 & imm` is non-zero
+    pub const fn jset(imm: i32, off: i16) -> Instruction {
+        // BPF_JMP | BPF_JSET | BPF_K
+        insn(0x45, R0, 0, off, imm)
+    }
+
+    /// Skips `off` instructions if `r0 > imm`
+    pub const fn jgt(imm: i32, off: i16) -> Instruction {
+        // BPF_JMP | BPF_JGT | BPF_K
+        insn(0x25, R0, 0, off, imm)
+    }
+
+    /// Skips `off` instructions
+    pub const fn ja(off: i16) -> Instruction {
+        // BPF_JMP | BPF_JA
+        insn(0x05, 0, 0, off, 0)
+    }
+
+    /// Returns `r0`
+    pub const fn exit() -> Instruction {
+        // BPF_JMP | BPF_EXIT
+        insn(0x95, 0, 0, 0, 0)
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::socket::options::{Options, ReusePort};
+use std::{net::UdpSocket, time::Duration};
+
+const SHARDS: u16 = 4;
+
+/// Binds a reuseport group with a socket for each shard
+fn reuseport_group(
+    attach: impl FnOnce(&UdpSocket) -> io::Result<()>,
+) -> io::Result<Vec<UdpSocket>> {
+    let mut options = Options {
+        addr: "127.0.0.1:0".parse().unwrap(),
+        gro: false,
+        blocking: true,
+        // set the reuse port option after binding to avoid port collisions
+        reuse_port: ReusePort::AfterBind,
+        ..Default::default()
+    };
+
+    let first = options.build_udp()?;
+    attach(&first)?;
+
+    // bind the rest of the sockets to the same address
+    options.addr = first.local_addr()?;
+    options.reuse_port = ReusePort::BeforeBind;
+
+    let mut sockets = vec![first];
+    for _ in 1..SHARDS {
+        sockets.push(options.build_udp()?);
+    }
+
+    for socket in &sockets {
+        socket.set_read_timeout(Some(Duration::from_secs(1)))?;
+    }
+
+    Ok(sockets)
+}
+
+#[test]
+fn steering() {
+    let encoding = Encoding::new(SHARDS).unwrap().with_offset(2).unwrap();
+    let program = Program::new(encoding);
+
+    insta::assert_snapshot!(program);
+
+    let Ok(sockets) = reuseport_group(|socket| program.attach(socket)) else {
+        eprintln!("skipping tests due to environment not supporting bpf programs");
+        return;
+    };
+
+    assert_steering(encoding, &sockets);
+}
+
+#[test]
+fn ebpf_steering() {
+    let encoding = Encoding::new(SHARDS).unwrap().with_offset(2).unwrap();
+    let program = EbpfProgram::new(encoding);
+
+    let Ok(sockets) = reuseport_group(|socket| program.attach(socket)) else {
+        eprintln!("skipping tests due to environment not supporting loading eBPF programs");
+        return;
+    };
+
+    assert_steering(encoding, &sockets);
+}
+
+/// Sends short and long header packets for each shard and checks they arrive at its socket
+fn assert_steering(encoding: Encoding, sockets: &[UdpSocket]) {
+    let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let server_addr = sockets[0].local_addr().unwrap();
+
+    for index in 0..SHARDS {
+        let mut id = [0xa5; 8];
+        encoding.encode(index, &mut id).unwrap();
+
+        // short header
+        let mut short = vec![0x40];
+        short.extend_from_slice(&id);
+
+        // long header
+        let mut long = vec![0xc0, 0, 0, 0, 1, id.len() as u8];
+        long.extend_from_slice(&id);
+
+        for packet in [short, long] {
+            client.send_to(&packet, server_addr).unwrap();
+
+            let mut buf = [0u8; 64];
+            let (len, _) = sockets[index as usize].recv_from(&mut buf).unwrap();
+            assert_eq!(&buf[..len], &packet[..]);
+        }
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::c_int;
+use s2n_quic_core::inet::icmp;
+
+#[cfg(s2n_quic_platform_recverr)]
+mod recverr_enabled {
+    use super::*;
+    use libc::{IPPROTO_IP, IPPROTO_IPV6, IPV6_RECVERR, IP_RECVERR};
+
+    pub const SOCKOPT_V4: Option<(c_int, c_int)> = Some((IPPROTO_IP as _, IP_RECVERR as _));
+    pub const SOCKOPT_V6: Option<(c_int, c_int)> = Some((IPPROTO_IPV6 as _, IPV6_RECVERR as _));
+    // the kernel includes the address of the node that generated the error after the
+    // extended error
+    pub const CMSG_SPACE: usize =
+        crate::message::cmsg::size_of_cmsg::<Cmsg>() + core::mem::size_of::<libc::sockaddr_in6>();
+    /// The flag passed to `recvmsg` to read messages from the socket error queue
+    pub const MSG_ERRQUEUE: c_int = libc::MSG_ERRQUEUE as _;
+
+    #[inline]
+    pub const fn is_match(level: c_int, ty: c_int) -> bool {
+        (level == IPPROTO_IP as c_int && ty == IP_RECVERR as c_int)
+            || (level == IPPROTO_IPV6 as c_int && ty == IPV6_RECVERR as c_int)
+    }
+
+    #[inline]
+    pub fn decode(bytes: &[u8]) -> Option<icmp::Error> {
+        use libc::{EMSGSIZE, SO_EE_ORIGIN_ICMP, SO_EE_ORIGIN_ICMP6};
+
+        if bytes.len() < core::mem::size_of::<Cmsg>() {
+            return None;
+        }
+
+        // the control message data isn't guaranteed to be aligned
+        let err = unsafe { (bytes.as_ptr() as *const Cmsg).read_unaligned() };
+
+        let is_icmp = err.ee_origin == SO_EE_ORIGIN_ICMP || err.ee_origin == SO_EE_ORIGIN_ICMP6;
+
+        if is_icmp && err.ee_errno == EMSGSIZE as u32 {
+            // The MTU is reported in `ee_info` for both ICMP Fragmentation Needed and
+            // ICMPv6 Packet Too Big messages
+            let mtu = err.ee_info.try_into().unwrap_or(u16::MAX);
+            Some(icmp::Error::PacketTooBig { mtu })
+        } else {
+            Some(icmp::Error::Other)
+        }
+    }
+
+    pub type Cmsg = libc::sock_extended_err;
+}
+
+#[cfg(any(not(s2n_quic_platform_recverr), test))]
+mod recverr_disabled {
+    #![cfg_attr(test, allow(dead_code))]
+    use super::*;
+
+    pub const SOCKOPT_V4: Option<(c_int, c_int)> = None;
+    pub const SOCKOPT_V6: Option<(c_int, c_int)> = None;
+    pub const CMSG_SPACE: usize = 0;
+    pub const MSG_ERRQUEUE: c_int = 0;
+
+    #[inline]
+    pub const fn is_match(level: c_int, ty: c_int) -> bool {
+        let _ = level;
+        let _ = ty;
+        false
+    }
+
+    #[inline]
+    pub fn decode(bytes: &[u8]) -> Option<icmp::Error> {
+        let _ = bytes;
+        None
+    }
+}
+
+mod recverr_impl {
+    #[cfg(not(s2n_quic_platform_recverr))]
+    pub use super::recverr_disabled::*;
+    #[cfg(s2n_quic_platform_recverr)]
+    pub use super::recverr_enabled::*;
+}
+
+pub use recverr_impl::*;
+pub const IS_SUPPORTED: bool = cfg!(s2n_quic_platform_recverr);
+
+#[cfg(all(test, s2n_quic_platform_recverr))]
+mod tests {
+    use super::*;
+    use libc::{
+        sock_extended_err, ECONNREFUSED, EMSGSIZE, SO_EE_ORIGIN_ICMP, SO_EE_ORIGIN_ICMP6,
+        SO_EE_ORIGIN_LOCAL,
+    };
+
+    fn extended_err(origin: u8, errno: i32, ty: u8, code: u8, info: u32) -> sock_extended_err {
+        let mut err: sock_extended_err = unsafe { core::mem::zeroed() };
+        err.ee_origin = origin;
+        err.ee_errno = errno as _;
+        err.ee_type = ty;
+        err.ee_code = code;
+        err.ee_info = info;
+        err
+    }
+
+    /// Encodes the error at an unaligned offset, as control message data isn't guaranteed to be
+    /// aligned, followed by the address of the node that generated it
+    fn decode_err(err: sock_extended_err) -> Option<icmp::Error> {
+        let len = core::mem::size_of::<sock_extended_err>();
+        let mut bytes = vec![0u8; 1 + CMSG_SPACE];
+        unsafe { (bytes[1..].as_mut_ptr() as *mut sock_extended_err).write_unaligned(err) };
+        let error = decode(&bytes[1..]);
+
+        // the error is decoded without the trailing address
+        assert_eq!(decode(&bytes[1..1 + len]), error);
+
+        error
+    }
+
+    #[test]
+    fn packet_too_big_test() {
+        // ICMP Destination Unreachable (Fragmentation Needed)
+        let err = extended_err(SO_EE_ORIGIN_ICMP, EMSGSIZE, 3, 4, 1400);
+        assert_eq!(
+            decode_err(err),
+            Some(icmp::Error::PacketTooBig { mtu: 1400 })
+        );
+
+        // ICMPv6 Packet Too Big
+        let err = extended_err(SO_EE_ORIGIN_ICMP6, EMSGSIZE, 2, 0, 1280);
+        assert_eq!(
+            decode_err(err),
+            Some(icmp::Error::PacketTooBig { mtu: 1280 })
+        );
+
+        // MTUs that don't fit in a `u16` are saturated
+        let err = extended_err(SO_EE_ORIGIN_ICMP6, EMSGSIZE, 2, 0, 100_000);
+        assert_eq!(
+            decode_err(err),
+            Some(icmp::Error::PacketTooBig { mtu: u16::MAX })
+        );
+    }
+
+    #[test]
+    fn other_error_test() {
+        // ICMP Destination Unreachable (Port Unreachable)
+        let err = extended_err(SO_EE_ORIGIN_ICMP, ECONNREFUSED, 3, 3, 0);
+        assert_eq!(decode_err(err), Some(icmp::Error::Other));
+
+        // the local stack reports `EMSGSIZE` for datagrams that exceed the MTU it already knows
+        // about, which doesn't come from an ICMP message
+        let err = extended_err(SO_EE_ORIGIN_LOCAL, EMSGSIZE, 0, 0, 1400);
+        assert_eq!(decode_err(err), Some(icmp::Error::Other));
+    }
+
+    #[test]
+    fn truncated_test() {
+        let err = extended_err(SO_EE_ORIGIN_ICMP, EMSGSIZE, 3, 4, 1400);
+        let len = core::mem::size_of::<sock_extended_err>();
+        let mut bytes = vec![0u8; len];
+        unsafe { (bytes.as_mut_ptr() as *mut sock_extended_err).write_unaligned(err) };
+
+        assert_eq!(decode(&bytes[..len - 1]), None);
+        assert_eq!(decode(&[]), None);
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Transmit times for outgoing datagrams with `SO_TXTIME`
+//!
+//! Each datagram can be given the time it should leave the host with an `SCM_TXTIME` control
+//! message. The times are only honored by a qdisc which supports them, such as `fq` or `etf`.
+//! Otherwise the datagrams are sent immediately.
+
+use super::c_int;
+use core::time::Duration;
+
+/// The maximum amount of time before its transmit time a datagram is handed to the kernel
+///
+/// This is kept small so changes to the pacing rate take effect quickly, while still allowing
+/// several bursts to be sent by the endpoint in a single wakeup.
+pub const MAX_DELAY: Duration = Duration::from_millis(5);
+
+#[cfg(s2n_quic_platform_txtime)]
+mod txtime_enabled {
+    use super::*;
+    use libc::{CLOCK_MONOTONIC, SCM_TXTIME, SOL_SOCKET, SO_TXTIME};
+
+    pub const LEVEL: Option<c_int> = Some(SOL_SOCKET as _);
+    pub const TYPE: Option<c_int> = Some(SCM_TXTIME as _);
+    pub const SOCKOPT: Option<(c_int, c_int)> = Some((SOL_SOCKET as _, SO_TXTIME as _));
+    pub const CMSG_SPACE: usize = crate::message::cmsg::size_of_cmsg::<super::Cmsg>();
+
+    #[inline]
+    pub const fn is_match(level: c_int, ty: c_int) -> bool {
+        level == SOL_SOCKET as c_int && ty == SCM_TXTIME as c_int
+    }
+
+    /// The value of the `SO_TXTIME` socket option
+    ///
+    /// See `struct sock_txtime` in `include/uapi/linux/net_tstamp.h`
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug)]
+    pub struct SockOpt {
+        clockid: libc::clockid_t,
+        flags: u32,
+    }
+
+    /// Transmit times are measured with the same clock as the tokio runtime
+    pub const SOCKOPT_VALUE: SockOpt = SockOpt {
+        clockid: CLOCK_MONOTONIC,
+        flags: 0,
+    };
+
+    /// Returns the transmit time of a datagram which should be sent after `delay`
+    #[inline]
+    pub fn transmit_time(delay: Duration) -> super::Cmsg {
+        let mut now = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        // Safety: the timespec is valid for writes and CLOCK_MONOTONIC is always supported
+        unsafe { libc::clock_gettime(CLOCK_MONOTONIC, &mut now) };
+
+        let now = now.tv_sec as u64 * 1_000_000_000 + now.tv_nsec as u64;
+        now + delay.as_nanos() as u64
+    }
+}
+
+#[cfg(any(not(s2n_quic_platform_txtime), test))]
+mod txtime_disabled {
+    #![cfg_attr(test, allow(dead_code))]
+    use super::*;
+
+    pub const LEVEL: Option<c_int> = None;
+    pub const TYPE: Option<c_int> = None;
+    pub const SOCKOPT: Option<(c_int, c_int)> = None;
+    pub const CMSG_SPACE: usize = 0;
+
+    #[inline]
+    pub const fn is_match(level: c_int, ty: c_int) -> bool {
+        let _ = level;
+        let _ = ty;
+        false
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    pub struct SockOpt(());
+
+    pub const SOCKOPT_VALUE: SockOpt = SockOpt(());
+
+    #[inline]
+    pub fn transmit_time(delay: Duration) -> super::Cmsg {
+        let _ = delay;
+        0
+    }
+}
+
+mod txtime_impl {
+    #[cfg(not(s2n_quic_platform_txtime))]
+    pub use super::txtime_disabled::*;
+    #[cfg(s2n_quic_platform_txtime)]
+    pub use super::txtime_enabled::*;
+}
+
+pub use txtime_impl::*;
+pub type Cmsg = u64;
+
+pub const IS_SUPPORTED: bool = cfg!(s2n_quic_platform_txtime);
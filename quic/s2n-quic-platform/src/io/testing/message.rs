@@ -55,6 +55,7 @@ impl MessageTrait for Message {
     const SUPPORTS_GSO: bool = false;
     const SUPPORTS_ECN: bool = true;
     const SUPPORTS_FLOW_LABELS: bool = false;
+    const SUPPORTS_TXTIME: bool = false;
 
     #[inline]
     fn alloc(entries: u32, payload_len: u32, offset: usize) -> message::Storage {
@@ -102,6 +103,7 @@ impl MessageTrait for Message {
             header,
             segment_size: payload.len(),
             payload,
+            icmp_error: None,
         };
 
         Some(message)
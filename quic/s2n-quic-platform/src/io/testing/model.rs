@@ -9,6 +9,7 @@ use core::time::Duration;
 use s2n_quic_core::{havoc, path::MaxMtu};
 use std::{
     borrow::Cow,
+    net::{IpAddr, SocketAddr},
     sync::{
         atomic::{AtomicU16, AtomicU64, Ordering},
         Arc, Mutex,
@@ -79,6 +80,25 @@ impl Model {
         self
     }
 
+    pub fn address_delay(&self, addr: IpAddr) -> Duration {
+        let delays = self.0.address_delays.lock().unwrap();
+        delays
+            .iter()
+            .find(|(delay_addr, _)| *delay_addr == addr)
+            .map_or(Duration::ZERO, |(_, delay)| *delay)
+    }
+
+    /// The amount of time packets sent from or to the given IP address are delayed in addition
+    /// to the network delay
+    ///
+    /// This can be used to give the paths of a connection different round-trip times.
+    pub fn set_address_delay(&self, addr: IpAddr, value: Duration) -> &Self {
+        let mut delays = self.0.address_delays.lock().unwrap();
+        delays.retain(|(delay_addr, _)| *delay_addr != addr);
+        delays.push((addr, value));
+        self
+    }
+
     pub fn transmit_rate(&self) -> u64 {
         self.0.transmit_rate.load(Ordering::SeqCst)
     }
@@ -204,6 +224,7 @@ struct State {
     inflight_delay: AtomicU64,
     inflight_delay_threshold: AtomicU64,
     current_inflight: AtomicU64,
+    address_delays: Mutex<Vec<(IpAddr, Duration)>>,
 }
 
 impl Default for State {
@@ -221,6 +242,7 @@ impl Default for State {
             inflight_delay: AtomicU64::new(0),
             inflight_delay_threshold: AtomicU64::new(u64::MAX),
             current_inflight: AtomicU64::new(0),
+            address_delays: Mutex::new(Vec::new()),
         }
     }
 }
@@ -296,6 +318,13 @@ impl Network for Model {
                 transmit_time += gen_jitter(network_jitter);
             }
 
+            for addr in [
+                SocketAddr::from(packet.path.local_address.0),
+                SocketAddr::from(packet.path.remote_address.0),
+            ] {
+                transmit_time += self.address_delay(addr.ip());
+            }
+
             let model = self.clone();
             let current_inflight = model.0.current_inflight.fetch_add(1, Ordering::SeqCst);
 
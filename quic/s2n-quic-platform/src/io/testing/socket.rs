@@ -89,6 +89,13 @@ impl Socket {
         self.0.buffers.rebind(self.0.host, addr);
     }
 
+    /// Adds an additional address the socket can send from and receive on
+    ///
+    /// The address can use a different port, which models an additional socket bound to it.
+    pub fn add_address(&self, addr: std::net::SocketAddr) {
+        self.0.buffers.add_address(self.0.host, addr);
+    }
+
     /// Sends a packet to the provided destination
     pub fn send_to(
         &self,
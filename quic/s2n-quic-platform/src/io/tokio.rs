@@ -16,6 +16,7 @@ use tokio::runtime::Handle;
 
 mod builder;
 mod clock;
+pub mod shard;
 pub(crate) mod task;
 #[cfg(test)]
 mod tests;
@@ -50,6 +51,8 @@ impl Io {
             tx_socket,
             recv_addr,
             send_addr,
+            additional_addrs,
+            additional_sockets,
             socket_recv_buffer_size,
             socket_send_buffer_size,
             queue_recv_buffer_size,
@@ -60,8 +63,16 @@ impl Io {
             reuse_address,
             reuse_port,
             only_v6,
+            io_uring,
+            txtime,
+            shard,
         } = self.builder;
 
+        let (router, alive) = match shard {
+            Some(builder::Attached { router, alive }) => (Some(router), Some(alive)),
+            None => (None, None),
+        };
+
         let clock = Clock::default();
 
         let mut publisher = event::EndpointPublisherSubscriber::new(
@@ -102,14 +113,17 @@ impl Io {
 
         let rx_addr = convert_addr_to_std(rx_socket.local_addr()?)?;
 
-        let tx_socket = if let Some(tx_socket) = tx_socket {
-            tx_socket
+        let (tx_socket, tx_socket_is_separate) = if let Some(tx_socket) = tx_socket {
+            (tx_socket, true)
         } else if let Some(send_addr) = send_addr {
-            syscall::bind_udp(send_addr, reuse_address, reuse_port, only_v6)?
+            (
+                syscall::bind_udp(send_addr, reuse_address, reuse_port, only_v6)?,
+                true,
+            )
         } else {
             // No tx_socket or send address was specified, so the tx socket
             // will be a handle to the rx socket.
-            rx_socket.try_clone()?
+            (rx_socket.try_clone()?, false)
         };
 
         if let Some(size) = socket_send_buffer_size {
@@ -120,13 +134,40 @@ impl Io {
             rx_socket.set_recv_buffer_size(size)?;
         }
 
+        // bind the sockets for the additional addresses that connections can migrate to. Each
+        // socket is used for both sending and receiving.
+        let additional_sockets = additional_addrs
+            .into_iter()
+            .map(|addr| syscall::bind_udp(addr, reuse_address, reuse_port, only_v6))
+            .chain(additional_sockets.into_iter().map(Ok))
+            .map(|socket| {
+                let socket = socket?;
+
+                if let Some(size) = socket_send_buffer_size {
+                    socket.set_send_buffer_size(size)?;
+                }
+
+                if let Some(size) = socket_recv_buffer_size {
+                    socket.set_recv_buffer_size(size)?;
+                }
+
+                let addr: inet::SocketAddress = convert_addr_to_std(socket.local_addr()?)?.into();
+                Ok((socket, addr))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
         let mut mtu_config = mtu_config_builder
             .build()
             .map_err(|err| io::Error::new(ErrorKind::InvalidInput, format!("{err}")))?;
         let original_max_mtu = mtu_config.max_mtu();
 
         // Configure MTU discovery
-        if !syscall::configure_mtu_disc(&tx_socket) {
+        let mut mtu_disc_enabled = syscall::configure_mtu_disc(&tx_socket);
+        for (socket, _) in &additional_sockets {
+            mtu_disc_enabled &= syscall::configure_mtu_disc(socket);
+        }
+
+        if !mtu_disc_enabled {
             // disable MTU probing if we can't prevent fragmentation
             mtu_config = mtu::Config::MIN;
         }
@@ -161,9 +202,30 @@ impl Io {
         // Configure packet info CMSG
         syscall::configure_pktinfo(&rx_socket);
 
+        // Configure ICMP errors to be queued so Packet Too Big messages can lower the path MTU
+        syscall::configure_recverr(&rx_socket);
+
+        // ICMP errors are queued on the socket that sent the datagram, so a separate tx socket
+        // also needs to queue them
+        let tx_recverr_enabled = tx_socket_is_separate && syscall::configure_recverr(&tx_socket);
+
+        // Configure the tx socket to accept transmit times, which hands pacing off to the kernel
+        let mut txtime_enabled = txtime && syscall::configure_txtime(&tx_socket);
+
         // Configure TOS/ECN
         let tos_enabled = syscall::configure_tos(&rx_socket);
 
+        // Configure the additional sockets with the same features
+        for (socket, _) in &additional_sockets {
+            if gro_enabled {
+                syscall::configure_gro(socket);
+            }
+            syscall::configure_pktinfo(socket);
+            syscall::configure_recverr(socket);
+            syscall::configure_tos(socket);
+            txtime_enabled = txtime_enabled && syscall::configure_txtime(socket);
+        }
+
         publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
             configuration: event::builder::PlatformFeatureConfiguration::Ecn {
                 enabled: tos_enabled,
@@ -199,34 +261,83 @@ impl Io {
             // complete
             let rx_cooldown = cooldown("RX");
 
+            // read the error queue of the separate tx socket as well. Otherwise the queued ICMP
+            // errors would never reach the endpoint. Only the error queue is read, so datagrams
+            // arriving on the tx socket are left unread.
+            #[cfg(s2n_quic_platform_recverr)]
+            if tx_recverr_enabled {
+                let (producer, consumer) = socket::ring::pair(entries, payload_len);
+                consumers.push(consumer);
+                handle.spawn(task::rx_errors(
+                    tx_socket.try_clone()?,
+                    producer,
+                    rx_cooldown.clone(),
+                    stats_sender.clone(),
+                ));
+            }
+            #[cfg(not(s2n_quic_platform_recverr))]
+            let _ = tx_recverr_enabled;
+
+            let mut additional_consumers = vec![];
+            for (socket, addr) in &additional_sockets {
+                let (producer, consumer) = socket::ring::pair(entries, payload_len);
+                additional_consumers.push((consumer, *addr));
+                spawn_rx(
+                    &handle,
+                    socket.try_clone()?,
+                    producer,
+                    payload_len,
+                    io_uring,
+                    rx_cooldown.clone(),
+                    stats_sender.clone(),
+                )?;
+            }
+
             for idx in 0usize..rx_socket_count {
                 let (producer, consumer) = socket::ring::pair(entries, payload_len);
                 consumers.push(consumer);
 
                 // spawn a task that actually reads from the socket into the ring buffer
                 if idx + 1 == rx_socket_count {
-                    handle.spawn(task::rx(
+                    spawn_rx(
+                        &handle,
                         rx_socket,
                         producer,
+                        payload_len,
+                        io_uring,
                         rx_cooldown,
                         stats_sender.clone(),
-                    ));
+                    )?;
                     break;
                 } else {
                     let rx_socket = rx_socket.try_clone()?;
-                    handle.spawn(task::rx(
+                    spawn_rx(
+                        &handle,
                         rx_socket,
                         producer,
+                        payload_len,
+                        io_uring,
                         rx_cooldown.clone(),
                         stats_sender.clone(),
-                    ));
+                    )?;
                 }
             }
 
             // construct the RX side for the endpoint event loop
             let max_mtu = MaxMtu::try_from(payload_len as u16).unwrap();
             let addr: inet::SocketAddress = rx_addr.into();
-            socket::io::rx::Rx::new(consumers, max_mtu, addr.into())
+            let mut rx = socket::io::rx::Rx::new(consumers, max_mtu, addr.into());
+
+            for (consumer, addr) in additional_consumers {
+                rx = rx.with_channel(consumer, addr.into());
+            }
+
+            // forward packets for connections owned by other shards
+            if let Some(router) = router {
+                rx.with_router(router)
+            } else {
+                rx
+            }
         };
 
         let tx = {
@@ -256,50 +367,92 @@ impl Io {
             // complete
             let tx_cooldown = cooldown("TX");
 
+            let mut routes = vec![];
+            for (socket, addr) in &additional_sockets {
+                let (producer, consumer) = socket::ring::pair(entries, payload_len);
+                spawn_tx(
+                    &handle,
+                    socket.try_clone()?,
+                    consumer,
+                    gso.clone(),
+                    io_uring,
+                    tx_cooldown.clone(),
+                    stats_sender.clone(),
+                )?;
+
+                let route =
+                    socket::io::tx::Tx::new(vec![producer], gso.clone(), mtu_config.max_mtu());
+                let route = if txtime_enabled {
+                    route.with_transmit_time()
+                } else {
+                    route
+                };
+                routes.push((*addr, route));
+            }
+
             for idx in 0usize..tx_socket_count {
                 let (producer, consumer) = socket::ring::pair(entries, payload_len);
                 producers.push(producer);
 
                 // spawn a task that actually flushes the ring buffer to the socket
                 if idx + 1 == tx_socket_count {
-                    handle.spawn(task::tx(
+                    spawn_tx(
+                        &handle,
                         tx_socket,
                         consumer,
                         gso.clone(),
+                        io_uring,
                         tx_cooldown,
                         stats_sender.clone(),
-                    ));
+                    )?;
                     break;
                 } else {
                     let tx_socket = tx_socket.try_clone()?;
-                    handle.spawn(task::tx(
+                    spawn_tx(
+                        &handle,
                         tx_socket,
                         consumer,
                         gso.clone(),
+                        io_uring,
                         tx_cooldown.clone(),
                         stats_sender.clone(),
-                    ));
+                    )?;
                 }
             }
 
             // construct the TX side for the endpoint event loop
-            socket::io::tx::Tx::new(producers, gso, mtu_config.max_mtu())
+            let mut tx = socket::io::tx::Tx::new(producers, gso, mtu_config.max_mtu());
+
+            // send messages from the additional addresses on the socket bound to them
+            for (addr, route) in routes {
+                tx = tx.with_route(addr, route);
+            }
+
+            if txtime_enabled {
+                tx.with_transmit_time()
+            } else {
+                tx
+            }
         };
 
         // Notify the endpoint of the MTU that we chose
         endpoint.set_mtu_config(mtu_config);
 
-        let task = handle.spawn(
-            EventLoop {
-                endpoint,
-                clock,
-                rx,
-                tx,
-                cooldown: cooldown("ENDPOINT"),
-                stats: stats_recv,
-            }
-            .start(rx_addr.into()),
-        );
+        let event_loop = EventLoop {
+            endpoint,
+            clock,
+            rx,
+            tx,
+            cooldown: cooldown("ENDPOINT"),
+            stats: stats_recv,
+        }
+        .start(rx_addr.into());
+
+        let task = handle.spawn(async move {
+            event_loop.await;
+            // let the shard thread exit now that the endpoint is closed
+            drop(alive);
+        });
 
         drop(guard);
 
@@ -307,6 +460,57 @@ impl Io {
     }
 }
 
+/// Spawns a task that reads from the socket into the ring buffer
+fn spawn_rx(
+    handle: &Handle,
+    socket: socket2::Socket,
+    producer: socket::ring::Producer<message::Message>,
+    payload_len: u32,
+    io_uring: bool,
+    cooldown: Cooldown,
+    stats: socket::stats::Sender,
+) -> io::Result<()> {
+    #[cfg(all(feature = "uring", target_os = "linux", s2n_quic_platform_socket_mmsg))]
+    if io_uring {
+        // create the ring before spawning the task so kernels without multishot `recvmsg` fall
+        // back to the `recvmmsg` task
+        match task::uring::RxSocket::new(socket.try_clone()?, payload_len) {
+            Ok(socket) => {
+                handle.spawn(task::uring::rx(socket, producer, cooldown, stats));
+                return Ok(());
+            }
+            Err(err) if err.kind() == ErrorKind::Unsupported => {}
+            Err(err) => return Err(err),
+        }
+    }
+
+    let _ = (io_uring, payload_len);
+    handle.spawn(task::rx(socket, producer, cooldown, stats));
+    Ok(())
+}
+
+/// Spawns a task that flushes the ring buffer to the socket
+fn spawn_tx(
+    handle: &Handle,
+    socket: socket2::Socket,
+    consumer: socket::ring::Consumer<message::Message>,
+    gso: gso::Gso,
+    io_uring: bool,
+    cooldown: Cooldown,
+    stats: socket::stats::Sender,
+) -> io::Result<()> {
+    #[cfg(all(feature = "uring", target_os = "linux", s2n_quic_platform_socket_mmsg))]
+    if io_uring {
+        let socket = task::uring::TxSocket::new(socket)?;
+        handle.spawn(task::uring::tx(socket, consumer, gso, cooldown, stats));
+        return Ok(());
+    }
+
+    let _ = io_uring;
+    handle.spawn(task::tx(socket, consumer, gso, cooldown, stats));
+    Ok(())
+}
+
 fn convert_addr_to_std(addr: socket2::SockAddr) -> io::Result<std::net::SocketAddr> {
     addr.as_socket()
         .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid domain for socket"))
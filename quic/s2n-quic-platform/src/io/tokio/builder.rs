@@ -1,7 +1,10 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+use super::shard::Shard;
 use super::*;
+use crate::socket::io::shard::Router;
+use core::fmt;
 
 #[derive(Debug, Default)]
 pub struct Builder {
@@ -10,6 +13,8 @@ pub struct Builder {
     pub(super) tx_socket: Option<socket2::Socket>,
     pub(super) recv_addr: Option<std::net::SocketAddr>,
     pub(super) send_addr: Option<std::net::SocketAddr>,
+    pub(super) additional_addrs: Vec<std::net::SocketAddr>,
+    pub(super) additional_sockets: Vec<socket2::Socket>,
     pub(super) socket_recv_buffer_size: Option<usize>,
     pub(super) socket_send_buffer_size: Option<usize>,
     pub(super) queue_recv_buffer_size: Option<u32>,
@@ -20,6 +25,23 @@ pub struct Builder {
     pub(super) reuse_address: bool,
     pub(super) reuse_port: bool,
     pub(super) only_v6: bool,
+    pub(super) io_uring: bool,
+    pub(super) txtime: bool,
+    pub(super) shard: Option<Attached>,
+}
+
+/// The parts of a [`Shard`] used after the sockets are configured
+pub(super) struct Attached {
+    pub(super) router: Router<PathHandle>,
+    pub(super) alive: tokio::sync::oneshot::Sender<()>,
+}
+
+impl fmt::Debug for Attached {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Shard")
+            .field("index", &self.router.index())
+            .finish()
+    }
 }
 
 impl Builder {
@@ -75,6 +97,24 @@ impl Builder {
         Ok(self)
     }
 
+    /// Binds an additional socket for the runtime to send from and receive on
+    ///
+    /// Client connections can migrate to the local address of the socket, for example to fail
+    /// over to another network interface. The method can be called multiple times to bind
+    /// several sockets.
+    pub fn with_additional_address(mut self, addr: std::net::SocketAddr) -> io::Result<Self> {
+        self.additional_addrs.push(addr);
+        Ok(self)
+    }
+
+    /// Adds an additional socket for the runtime to send from and receive on
+    ///
+    /// This is the same as `with_additional_address`, except the socket is already bound.
+    pub fn with_additional_socket(mut self, socket: std::net::UdpSocket) -> io::Result<Self> {
+        self.additional_sockets.push(socket.into());
+        Ok(self)
+    }
+
     /// Sets the size of the operating system’s send buffer associated with the tx socket
     pub fn with_send_buffer_size(mut self, send_buffer_size: usize) -> io::Result<Self> {
         self.socket_send_buffer_size = Some(send_buffer_size);
@@ -219,6 +259,47 @@ impl Builder {
         }
     }
 
+    /// Configures the socket tasks to use `io_uring` (default: disabled)
+    ///
+    /// Packets are received with a multishot `recvmsg` into buffers registered with the kernel
+    /// and sent with a `sendmsg` request per message, so the receive task doesn't make a syscall
+    /// for each batch of packets. This requires the `uring` feature. Receiving requires Linux 6.0
+    /// or later, and packets are received with `recvmmsg` on older kernels instead. If the kernel
+    /// doesn't support `io_uring`, starting the provider fails.
+    pub fn with_io_uring(mut self, enabled: bool) -> io::Result<Self> {
+        if enabled
+            && !cfg!(all(
+                feature = "uring",
+                target_os = "linux",
+                s2n_quic_platform_socket_mmsg
+            ))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "io_uring is not supported on the current platform",
+            ));
+        }
+        self.io_uring = enabled;
+        Ok(self)
+    }
+
+    /// Configures the kernel to pace packets with `SO_TXTIME` (default: disabled)
+    ///
+    /// Each packet is given the time it should be transmitted, which lets the endpoint send
+    /// several paced bursts in a single wakeup instead of waiting on a timer for each one. The
+    /// transmit times are only honored by a qdisc which supports them, such as `fq`, on the
+    /// egress interface. Otherwise, the packets are sent without pacing.
+    pub fn with_txtime(mut self, enabled: bool) -> io::Result<Self> {
+        if enabled && !crate::features::txtime::IS_SUPPORTED {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "SO_TXTIME is not supported on the current platform",
+            ));
+        }
+        self.txtime = enabled;
+        Ok(self)
+    }
+
     /// Enables the address reuse (SO_REUSEADDR) socket option
     pub fn with_reuse_address(mut self, enabled: bool) -> io::Result<Self> {
         self.reuse_address = enabled;
@@ -250,6 +331,29 @@ impl Builder {
         Ok(self)
     }
 
+    /// Runs the endpoint on the thread of the `shard`, using the shard's socket
+    ///
+    /// Packets received for connections owned by other shards are forwarded to them.
+    ///
+    /// NOTE: this method is mutually exclusive with `with_receive_address`, `with_rx_socket` and
+    /// `with_handle`
+    pub fn with_shard(mut self, shard: Shard) -> io::Result<Self> {
+        debug_assert!(
+            self.recv_addr.is_none() && self.rx_socket.is_none(),
+            "receive socket has already been set"
+        );
+        let Shard {
+            socket,
+            handle,
+            router,
+            alive,
+        } = shard;
+        self.rx_socket = Some(socket);
+        self.handle = Some(handle);
+        self.shard = Some(Attached { router, alive });
+        Ok(self)
+    }
+
     pub fn build(self) -> io::Result<Io> {
         Ok(Io { builder: self })
     }
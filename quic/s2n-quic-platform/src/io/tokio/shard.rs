@@ -0,0 +1,228 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Runs an endpoint per shard, with each shard on its own thread and `SO_REUSEPORT` socket
+//!
+//! Each shard encodes its index in the connection IDs it issues. On Linux, a reuseport program
+//! steers packets to the socket of the shard encoded in the destination connection ID. Any packets
+//! which still arrive at the wrong shard are forwarded to the owning shard by the IO provider.
+//!
+//! The endpoint of each shard needs to be configured with a connection ID format which encodes the
+//! shard [`index`](Shard::index) with the shard [`encoding`](Shard::encoding). `s2n_quic`'s sharded
+//! server fails to start if a shard's format doesn't.
+
+use super::{convert_addr_to_std, PathHandle};
+use crate::{socket::io::shard::Router, syscall};
+use s2n_quic_core::connection::id::shard::{Encoding, MAX_COUNT};
+use std::{fmt, io};
+use tokio::{runtime::Handle, sync::oneshot};
+
+#[derive(Debug)]
+pub struct Builder {
+    addr: Option<std::net::SocketAddr>,
+    count: Option<u16>,
+    connection_id_offset: u8,
+    steering: bool,
+    cpu_affinity: bool,
+    only_v6: bool,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            addr: None,
+            count: None,
+            connection_id_offset: 0,
+            steering: true,
+            cpu_affinity: true,
+            only_v6: false,
+        }
+    }
+}
+
+impl Builder {
+    /// Sets the local address the shards listen on
+    pub fn with_address(mut self, addr: std::net::SocketAddr) -> io::Result<Self> {
+        self.addr = Some(addr);
+        Ok(self)
+    }
+
+    /// Sets the number of shards
+    ///
+    /// By default, a shard is created for each CPU available to the process.
+    pub fn with_shard_count(mut self, count: u16) -> io::Result<Self> {
+        Encoding::new(count).map_err(invalid_input)?;
+        self.count = Some(count);
+        Ok(self)
+    }
+
+    /// Sets the offset of the byte in each connection ID which stores the shard index
+    pub fn with_connection_id_offset(mut self, offset: u8) -> io::Result<Self> {
+        Encoding::new(1)
+            .and_then(|encoding| encoding.with_offset(offset))
+            .map_err(invalid_input)?;
+        self.connection_id_offset = offset;
+        Ok(self)
+    }
+
+    /// Enables/disables steering packets to shards in the kernel (default: enabled)
+    ///
+    /// Steering is only supported on Linux. If it's disabled or can't be configured, packets are
+    /// forwarded between the shards instead.
+    pub fn with_steering(mut self, enabled: bool) -> io::Result<Self> {
+        self.steering = enabled;
+        Ok(self)
+    }
+
+    /// Enables/disables pinning each shard thread to a CPU (default: enabled)
+    ///
+    /// Shard threads are assigned to the CPUs available to the process in shard order. Pinning is
+    /// only supported on Linux and is skipped on other platforms.
+    pub fn with_cpu_affinity(mut self, enabled: bool) -> io::Result<Self> {
+        self.cpu_affinity = enabled;
+        Ok(self)
+    }
+
+    /// Set the value for the IPV6_V6ONLY socket option of the shard sockets
+    pub fn with_only_v6(mut self, only_v6: bool) -> io::Result<Self> {
+        self.only_v6 = only_v6;
+        Ok(self)
+    }
+
+    /// Binds the shard sockets and starts a thread for each shard
+    pub fn build(self) -> io::Result<Vec<Shard>> {
+        if !cfg!(unix) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "sharding is not supported on the current platform",
+            ));
+        }
+
+        let mut addr = self
+            .addr
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing bind address"))?;
+
+        let count = match self.count {
+            Some(count) => count,
+            None => std::thread::available_parallelism()
+                .map_or(1, |count| count.get().min(MAX_COUNT as usize) as u16),
+        };
+
+        let encoding = Encoding::new(count)
+            .and_then(|encoding| encoding.with_offset(self.connection_id_offset))
+            .map_err(invalid_input)?;
+
+        // The sockets are added to the reuseport group in shard order, which makes the socket
+        // indexes used by the steering program match the shard indexes.
+        let mut sockets = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let socket = if addr.port() == 0 {
+                // enable port reuse after binding so the OS doesn't pick a port in use by another
+                // reuseport group
+                let socket = syscall::bind_udp(addr, false, false, self.only_v6)?;
+                #[cfg(unix)]
+                socket.set_reuse_port(true)?;
+                socket
+            } else {
+                syscall::bind_udp(addr, false, true, self.only_v6)?
+            };
+
+            // the rest of the sockets bind to the port chosen for the first one
+            addr = convert_addr_to_std(socket.local_addr()?)?;
+            sockets.push(socket);
+        }
+
+        #[cfg(target_os = "linux")]
+        if self.steering {
+            use crate::bpf::shard::{EbpfProgram, Program};
+
+            // the program is shared by all of the sockets in the reuseport group. The eBPF
+            // program is preferred, since it's JIT compiled, but loading it requires privileges
+            // so the classic program is used otherwise. If neither can be attached, the shards
+            // forward misrouted packets to each other instead.
+            let _ = EbpfProgram::new(encoding)
+                .attach(&sockets[0])
+                .or_else(|_| Program::new(encoding).attach(&sockets[0]));
+        }
+
+        sockets
+            .into_iter()
+            .zip(Router::group(encoding))
+            .map(|(socket, router)| Shard::new(socket, router, self.cpu_affinity))
+            .collect()
+    }
+}
+
+/// A single endpoint shard
+///
+/// The shard is passed to the IO provider with
+/// [`Builder::with_shard`](super::Builder::with_shard), which runs the endpoint on the shard's
+/// thread. The thread exits once the endpoint closes, or when the shard is dropped without being
+/// started. Unless disabled with [`Builder::with_cpu_affinity`], the thread is pinned to a CPU.
+pub struct Shard {
+    pub(super) socket: socket2::Socket,
+    pub(super) handle: Handle,
+    pub(super) router: Router<PathHandle>,
+    pub(super) alive: oneshot::Sender<()>,
+}
+
+impl fmt::Debug for Shard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Shard")
+            .field("index", &self.index())
+            .field("encoding", &self.encoding())
+            .field("local_addr", &self.socket.local_addr().ok())
+            .finish()
+    }
+}
+
+impl Shard {
+    fn new(
+        socket: socket2::Socket,
+        router: Router<PathHandle>,
+        cpu_affinity: bool,
+    ) -> io::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let handle = runtime.handle().clone();
+        let (alive, closed) = oneshot::channel();
+        let index = router.index();
+
+        std::thread::Builder::new()
+            .name(format!("s2n-quic-shard-{index}"))
+            .spawn(move || {
+                if cpu_affinity {
+                    // keep each shard on its own CPU so the shards don't compete with each other
+                    // or migrate away from their socket's cache
+                    let _ = syscall::pin_thread_to_cpu(index as usize);
+                }
+
+                // drive the endpoint tasks until the endpoint closes
+                runtime.block_on(async move {
+                    let _ = closed.await;
+                });
+            })?;
+
+        Ok(Self {
+            socket,
+            handle,
+            router,
+            alive,
+        })
+    }
+
+    /// Returns the index of the shard, which its connection IDs need to encode
+    pub fn index(&self) -> u16 {
+        self.router.index()
+    }
+
+    /// Returns the encoding of the shard index in connection IDs
+    pub fn encoding(&self) -> Encoding {
+        self.router.encoding()
+    }
+}
+
+fn invalid_input<E: fmt::Display>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, error.to_string())
+}
@@ -7,12 +7,18 @@
 mod simple;
 #[cfg(unix)]
 mod unix;
+#[cfg(all(feature = "uring", target_os = "linux", s2n_quic_platform_socket_mmsg))]
+pub mod uring;
 
 cfg_if::cfg_if! {
     if #[cfg(s2n_quic_platform_socket_mmsg)] {
         pub use mmsg::{rx, tx};
+        #[cfg(s2n_quic_platform_recverr)]
+        pub use mmsg::rx_errors;
     } else if #[cfg(s2n_quic_platform_socket_msg)] {
         pub use msg::{rx, tx};
+        #[cfg(s2n_quic_platform_recverr)]
+        pub use msg::rx_errors;
     } else {
         pub use simple::{rx, tx};
     }
@@ -39,6 +45,16 @@ macro_rules! libc_msg {
                 unix::rx(socket, producer, cooldown, stats).await
             }
 
+            #[cfg(s2n_quic_platform_recverr)]
+            pub async fn rx_errors<S: Into<std::net::UdpSocket>>(
+                socket: S,
+                producer: ring::Producer<Message>,
+                cooldown: Cooldown,
+                stats: stats::Sender,
+            ) -> std::io::Result<()> {
+                unix::rx_errors(socket, producer, cooldown, stats).await
+            }
+
             pub async fn tx<S: Into<std::net::UdpSocket>>(
                 socket: S,
                 consumer: ring::Consumer<Message>,
@@ -14,6 +14,9 @@ use s2n_quic_core::task::cooldown::Cooldown;
 use std::{io, os::unix::io::AsRawFd};
 use tokio::io::unix::AsyncFd;
 
+#[cfg(s2n_quic_platform_recverr)]
+pub use error_queue::rx_errors;
+
 pub async fn rx<S: Into<std::net::UdpSocket>, M: UnixMessage + Unpin>(
     socket: S,
     producer: ring::Producer<M>,
@@ -156,3 +159,100 @@ impl<S: AsRawFd, M: UnixMessage> rx::Socket<M> for AsyncFd<S> {
         Ok(())
     }
 }
+
+#[cfg(s2n_quic_platform_recverr)]
+mod error_queue {
+    use super::*;
+    use core::{future::Future, pin::Pin};
+    use std::sync::Arc;
+    use tokio::io::Interest;
+
+    /// Reads the ICMP errors queued on a socket which is only used to transmit datagrams
+    ///
+    /// Datagrams that arrive on the socket are never read, so they stay in its receive buffer.
+    pub async fn rx_errors<S: Into<std::net::UdpSocket>, M: UnixMessage + Unpin>(
+        socket: S,
+        producer: ring::Producer<M>,
+        cooldown: Cooldown,
+        stats: stats::Sender,
+    ) -> io::Result<()> {
+        let socket = socket.into();
+        socket.set_nonblocking(true).unwrap();
+
+        // Queued errors are signaled with `EPOLLERR` rather than making the socket readable
+        let socket = AsyncFd::with_interest(socket, Interest::ERROR)?;
+        let socket = ErrorQueue {
+            socket: Arc::new(socket),
+            ready: None,
+        };
+        let result = rx::Receiver::new(producer, socket, cooldown, stats).await;
+        if let Some(err) = result {
+            Err(err)
+        } else {
+            Ok(())
+        }
+    }
+
+    type Ready = Pin<Box<dyn Future<Output = io::Result<()>> + Send>>;
+
+    struct ErrorQueue<S> {
+        socket: Arc<AsyncFd<S>>,
+        /// Resolves once the socket has a queued error
+        ///
+        /// `AsyncFd` can only wait for error readiness with a future, which borrows the socket,
+        /// so the future holds its own reference to the socket.
+        ready: Option<Ready>,
+    }
+
+    impl<S: AsRawFd + Send + Sync + 'static, M: UnixMessage> rx::Socket<M> for ErrorQueue<S> {
+        type Error = io::Error;
+
+        #[inline]
+        fn recv(
+            &mut self,
+            cx: &mut Context,
+            entries: &mut [M],
+            events: &mut rx::Events,
+            stats: &stats::Sender,
+        ) -> io::Result<()> {
+            M::recv(
+                self.socket.get_ref().as_raw_fd(),
+                SocketType::ErrorQueue,
+                entries,
+                events,
+                stats,
+            );
+
+            // yield back if we weren't blocked
+            if !events.is_blocked() {
+                self.ready = None;
+                return Ok(());
+            }
+
+            if self.ready.is_none() {
+                // The error queue was drained so clear any readiness left over from the errors
+                // that were just read
+                let ready = core::pin::pin!(self.socket.ready(Interest::ERROR));
+                if let Poll::Ready(guard) = ready.poll(cx) {
+                    guard?.clear_ready();
+                }
+
+                let socket = self.socket.clone();
+                self.ready = Some(Box::pin(async move {
+                    socket.ready(Interest::ERROR).await.map(|_guard| ())
+                }));
+            }
+
+            if let Some(ready) = self.ready.as_mut() {
+                if let Poll::Ready(result) = ready.as_mut().poll(cx) {
+                    self.ready = None;
+                    result?;
+                    // an error was queued so have the caller try again
+                    events.take_blocked();
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,773 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Socket tasks which use `io_uring` to move messages between the sockets and the message queues
+//!
+//! Packets are received with a multishot `recvmsg` request into a ring of buffers registered with
+//! the kernel, so a single request keeps receiving packets until it's canceled. Each packet is
+//! copied into a free message in the queue along with its address and control messages (GRO, ECN,
+//! packet info). Messages are sent with a `sendmsg` request for each message in the queue, which
+//! is only released once its request completes.
+//!
+//! Provided buffer rings require Linux 5.19 and multishot `recvmsg` requires Linux 6.0. Both are
+//! checked when the receiver is created, so callers can fall back to the `recvmmsg` task on older
+//! kernels. No special hardware or kernel configuration is needed.
+
+use crate::{
+    features::{recverr, Gso},
+    message::{cmsg, mmsg::Message},
+    socket::{
+        ring, stats,
+        task::{rx, tx},
+    },
+    syscall::SocketEvents,
+};
+use core::{
+    mem::size_of,
+    ptr::NonNull,
+    sync::atomic::{AtomicU16, Ordering},
+    task::{Context, Poll},
+};
+use io_uring::{cqueue, opcode, squeue, types, IoUring};
+use s2n_quic_core::task::cooldown::Cooldown;
+use std::{
+    alloc::{self, Layout},
+    collections::VecDeque,
+    io,
+    net::UdpSocket,
+    os::unix::io::AsRawFd,
+};
+use tokio::io::{unix::AsyncFd, Interest};
+
+#[cfg(test)]
+mod tests;
+
+/// The number of entries in the submission queue of each ring
+const QUEUE_DEPTH: u32 = 256;
+
+/// The number of buffers the kernel can fill with received packets before they're copied out
+const BUFFER_COUNT: u16 = 64;
+
+/// The buffer group ID of the receive buffers
+const BUFFER_GROUP: u16 = 0;
+
+/// The size of the `io_uring_recvmsg_out` header the kernel writes at the start of each buffer
+const RECVMSG_OUT_LEN: usize = 16;
+
+/// The user data of the multishot `recvmsg` request
+const RECV_USER_DATA: u64 = 0;
+
+/// The user data of requests that cancel in-flight requests
+const CANCEL_USER_DATA: u64 = u64::MAX;
+
+/// The user data of requests that wait for the tx socket to become writable
+const POLL_USER_DATA: u64 = u64::MAX - 1;
+
+pub async fn rx(
+    socket: RxSocket,
+    producer: ring::Producer<Message>,
+    cooldown: Cooldown,
+    stats: stats::Sender,
+) -> io::Result<()> {
+    let result = rx::Receiver::new(producer, socket, cooldown, stats).await;
+    if let Some(err) = result {
+        Err(err)
+    } else {
+        Ok(())
+    }
+}
+
+pub async fn tx(
+    socket: TxSocket,
+    consumer: ring::Consumer<Message>,
+    gso: Gso,
+    cooldown: Cooldown,
+    stats: stats::Sender,
+) -> io::Result<()> {
+    let result = tx::Sender::new(consumer, socket, gso, cooldown, stats).await;
+    if let Some(err) = result {
+        Err(err)
+    } else {
+        Ok(())
+    }
+}
+
+/// Creates a ring which wakes the tokio reactor when completions are available
+fn new_ring() -> io::Result<AsyncFd<IoUring>> {
+    let ring = IoUring::new(QUEUE_DEPTH)?;
+    AsyncFd::with_interest(ring, Interest::READABLE)
+}
+
+/// Waits for the ring to have completions
+///
+/// Returns `true` if the caller should check the completion queue again. The readiness is
+/// cleared before returning `true` so completions posted after the caller's next check still wake
+/// the task.
+#[inline]
+fn poll_completions(ring: &AsyncFd<IoUring>, cx: &mut Context) -> io::Result<bool> {
+    match ring.poll_read_ready(cx) {
+        Poll::Ready(guard) => {
+            guard?.clear_ready();
+            Ok(true)
+        }
+        Poll::Pending => Ok(false),
+    }
+}
+
+/// Checks that the kernel accepts the `entry` request
+///
+/// Requests which aren't supported by the kernel are rejected as soon as they're submitted, so the
+/// entry should be for a request that doesn't complete on its own. It's canceled once accepted.
+///
+/// # Safety
+///
+/// The memory referenced by the request must be valid until this function returns
+unsafe fn probe(ring: &mut IoUring, entry: squeue::Entry) -> io::Result<()> {
+    let entry = entry.user_data(RECV_USER_DATA);
+    ring.submission()
+        .push(&entry)
+        .map_err(|_| io::Error::new(io::ErrorKind::Other, "submission queue is full"))?;
+    ring.submit()?;
+
+    let mut in_flight = 1;
+    if let Some(cqe) = ring.completion().next() {
+        let result = cqe.result();
+        if result < 0 {
+            let error = io::Error::from_raw_os_error(-result);
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("io_uring request is not supported: {error}"),
+            ));
+        }
+
+        if !cqueue::more(cqe.flags()) {
+            in_flight = 0;
+        }
+    }
+
+    cancel_all(ring, in_flight);
+
+    Ok(())
+}
+
+/// Cancels all of the in-flight requests on the ring and waits for them to complete
+///
+/// The requests reference memory owned by the tasks, so they need to complete before the memory
+/// is freed.
+fn cancel_all(ring: &mut IoUring, mut in_flight: usize) {
+    let cancel = opcode::AsyncCancel2::new(types::CancelBuilder::any())
+        .build()
+        .user_data(CANCEL_USER_DATA);
+
+    // Safety: the cancel request doesn't reference any memory
+    if unsafe { ring.submission().push(&cancel) }.is_err() {
+        let _ = ring.submit();
+        if unsafe { ring.submission().push(&cancel) }.is_err() {
+            return;
+        }
+    }
+
+    let mut is_cancel_pending = true;
+    while in_flight > 0 || is_cancel_pending {
+        if ring.submit_and_wait(1).is_err() {
+            // the kernel cancels the remaining requests when the ring is closed
+            return;
+        }
+
+        for cqe in ring.completion() {
+            if cqe.user_data() == CANCEL_USER_DATA {
+                is_cancel_pending = false;
+                continue;
+            }
+
+            // multishot requests are only done when they stop producing completions
+            if !cqueue::more(cqe.flags()) {
+                in_flight = in_flight.saturating_sub(1);
+            }
+        }
+    }
+}
+
+/// A ring of buffers the kernel fills with received packets
+struct Buffers {
+    /// The ring entries shared with the kernel
+    ring: NonNull<types::BufRingEntry>,
+    layout: Layout,
+    data: Box<[u8]>,
+    buffer_len: usize,
+    tail: u16,
+}
+
+impl Buffers {
+    fn new(buffer_len: usize) -> io::Result<Self> {
+        // the kernel requires the ring to be page aligned
+        let layout = Layout::from_size_align(
+            size_of::<types::BufRingEntry>() * BUFFER_COUNT as usize,
+            4096,
+        )
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+
+        // Safety: the layout has a non-zero size
+        let ring = unsafe { alloc::alloc_zeroed(layout) } as *mut types::BufRingEntry;
+        let ring = NonNull::new(ring).ok_or_else(|| io::Error::from(io::ErrorKind::OutOfMemory))?;
+
+        let mut buffers = Self {
+            ring,
+            layout,
+            data: vec![0; buffer_len * BUFFER_COUNT as usize].into_boxed_slice(),
+            buffer_len,
+            tail: 0,
+        };
+
+        for id in 0..BUFFER_COUNT {
+            buffers.push(id);
+        }
+        buffers.publish();
+
+        Ok(buffers)
+    }
+
+    /// Returns the filled part of the buffer with the given ID
+    #[inline]
+    fn get(&self, id: u16, len: usize) -> &[u8] {
+        let start = id as usize * self.buffer_len;
+        &self.data[start..start + len.min(self.buffer_len)]
+    }
+
+    /// Gives the buffer with the given ID back to the kernel
+    ///
+    /// The buffer isn't visible to the kernel until [`Self::publish`] is called.
+    #[inline]
+    fn push(&mut self, id: u16) {
+        debug_assert!(id < BUFFER_COUNT);
+        let index = (self.tail & (BUFFER_COUNT - 1)) as usize;
+        let addr = self.data[id as usize * self.buffer_len..].as_mut_ptr();
+
+        // Safety: the index is within the bounds of the ring
+        let entry = unsafe { &mut *self.ring.as_ptr().add(index) };
+        entry.set_addr(addr as u64);
+        entry.set_len(self.buffer_len as u32);
+        entry.set_bid(id);
+
+        self.tail = self.tail.wrapping_add(1);
+    }
+
+    /// Makes the pushed buffers visible to the kernel
+    #[inline]
+    fn publish(&self) {
+        // Safety: the tail is stored in the first ring entry, which is aligned for atomic access
+        unsafe {
+            let tail = types::BufRingEntry::tail(self.ring.as_ptr()) as *const AtomicU16;
+            (*tail).store(self.tail, Ordering::Release);
+        }
+    }
+}
+
+impl Drop for Buffers {
+    fn drop(&mut self) {
+        // Safety: the ring was allocated with the same layout
+        unsafe { alloc::dealloc(self.ring.as_ptr() as *mut u8, self.layout) }
+    }
+}
+
+/// Receives packets with a multishot `recvmsg` request
+pub struct RxSocket {
+    ring: AsyncFd<IoUring>,
+    buffers: Buffers,
+    /// Describes how the kernel splits each buffer between the address, control messages and
+    /// payload
+    msghdr: Box<libc::msghdr>,
+    is_armed: bool,
+    // the socket is dropped after the ring so it's open while requests are canceled
+    socket: UdpSocket,
+}
+
+// Safety: the raw pointers are owned by the socket and only accessed by the task it's moved to
+unsafe impl Send for RxSocket {}
+
+impl RxSocket {
+    /// Creates an `io_uring` receiver for packets with up to `payload_len` bytes
+    ///
+    /// An error of kind [`io::ErrorKind::Unsupported`] is returned if the kernel doesn't support
+    /// provided buffer rings or multishot `recvmsg`.
+    pub fn new<S: Into<UdpSocket>>(socket: S, payload_len: u32) -> io::Result<Self> {
+        let socket = socket.into();
+        socket.set_nonblocking(true)?;
+
+        let mut ring = new_ring()?;
+
+        // Safety: an all-zero `msghdr` is valid
+        let mut msghdr: Box<libc::msghdr> = Box::new(unsafe { core::mem::zeroed() });
+        msghdr.msg_namelen = size_of::<libc::sockaddr_in6>() as _;
+        msghdr.msg_controllen = cmsg::MAX_LEN as _;
+
+        let buffer_len = RECVMSG_OUT_LEN
+            + msghdr.msg_namelen as usize
+            + msghdr.msg_controllen as usize
+            + payload_len as usize;
+        let buffers = Buffers::new(buffer_len)?;
+
+        // Safety: the buffers are valid until the ring is dropped, which cancels the receive
+        //         request first
+        unsafe {
+            ring.get_ref().submitter().register_buf_ring_with_flags(
+                buffers.ring.as_ptr() as u64,
+                BUFFER_COUNT,
+                BUFFER_GROUP,
+                0,
+            )
+        }
+        .map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("io_uring provided buffer rings are not supported: {err}"),
+            )
+        })?;
+
+        // Linux 5.19 supports provided buffer rings but not multishot `recvmsg`, so the request
+        // is probed on a socket that isn't bound and never receives any packets
+        let probe_socket = socket2::Socket::new(socket2::Domain::IPV4, socket2::Type::DGRAM, None)?;
+        let entry = recv_entry(probe_socket.as_raw_fd(), &msghdr).build();
+        // Safety: the probe socket, `msghdr` and buffers outlive the request, which is canceled
+        //         before returning
+        unsafe { probe(ring.get_mut(), entry) }.map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!("io_uring multishot recvmsg is not supported: {err}"),
+            )
+        })?;
+
+        Ok(Self {
+            ring,
+            buffers,
+            msghdr,
+            is_armed: false,
+            socket,
+        })
+    }
+
+    /// Submits the multishot `recvmsg` request, if it isn't already in flight
+    #[inline]
+    fn arm(&mut self) -> io::Result<()> {
+        if self.is_armed {
+            return Ok(());
+        }
+
+        let entry = recv_entry(self.socket.as_raw_fd(), &self.msghdr)
+            .build()
+            .user_data(RECV_USER_DATA);
+
+        let ring = self.ring.get_mut();
+        // Safety: the `msghdr` and buffers outlive the request
+        unsafe { ring.submission().push(&entry) }
+            .map_err(|_| io::Error::new(io::ErrorKind::Other, "submission queue is full"))?;
+        ring.submit()?;
+        self.is_armed = true;
+
+        Ok(())
+    }
+
+    /// Copies the received packets into `entries` and returns the number of filled entries
+    #[inline]
+    fn drain(&mut self, entries: &mut [Message], stats: &stats::Sender) -> io::Result<usize> {
+        let mut count = 0;
+        let fd = self.socket.as_raw_fd();
+        let ring = self.ring.get_mut();
+
+        while count < entries.len() {
+            let Some(cqe) = ring.completion().next() else {
+                break;
+            };
+
+            if !cqueue::more(cqe.flags()) {
+                // the request stopped receiving, either from an error or running out of buffers
+                self.is_armed = false;
+            }
+
+            let result = cqe.result();
+
+            let Some(id) = cqueue::buffer_select(cqe.flags()) else {
+                if result >= 0 {
+                    continue;
+                }
+
+                let error = io::Error::from_raw_os_error(-result);
+
+                match -result {
+                    libc::ENOBUFS | libc::EINTR | libc::EAGAIN | libc::ECANCELED => {}
+                    libc::EINVAL | libc::EOPNOTSUPP => {
+                        return Err(io::Error::new(
+                            error.kind(),
+                            format!("io_uring multishot recvmsg is not supported: {error}"),
+                        ));
+                    }
+                    errno => {
+                        stats.recv().on_error(errno);
+
+                        // a pending error was reported so read it from the socket error queue
+                        if recverr::IS_SUPPORTED && read_error_queue(fd, &mut entries[count]) {
+                            count += 1;
+                        }
+                    }
+                }
+
+                continue;
+            };
+
+            let buffer = self.buffers.get(id, result.max(0) as usize);
+            if let Ok(out) = types::RecvMsgOut::parse(buffer, &self.msghdr) {
+                // Safety: the message was allocated by `msg::alloc` and reset for receiving
+                if unsafe { copy_message(&out, &mut entries[count]) } {
+                    count += 1;
+                }
+            }
+
+            self.buffers.push(id);
+        }
+
+        self.buffers.publish();
+
+        Ok(count)
+    }
+}
+
+impl Drop for RxSocket {
+    fn drop(&mut self) {
+        if self.is_armed {
+            cancel_all(self.ring.get_mut(), 1);
+        }
+    }
+}
+
+impl rx::Socket<Message> for RxSocket {
+    type Error = io::Error;
+
+    #[inline]
+    fn recv(
+        &mut self,
+        cx: &mut Context,
+        entries: &mut [Message],
+        events: &mut rx::Events,
+        stats: &stats::Sender,
+    ) -> io::Result<()> {
+        loop {
+            self.arm()?;
+
+            let count = self.drain(entries, stats)?;
+
+            if count > 0 {
+                stats.recv().on_operation_ready(count);
+                let _ = events.on_complete(count);
+                return Ok(());
+            }
+
+            // the request needs to be re-armed before waiting for more packets
+            if !self.is_armed {
+                continue;
+            }
+
+            if !poll_completions(&self.ring, cx)? {
+                stats.recv().on_operation_pending();
+                events.blocked();
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Returns a multishot `recvmsg` request for the socket, which selects buffers from the ring
+#[inline]
+fn recv_entry(fd: i32, msghdr: &libc::msghdr) -> opcode::RecvMsgMulti {
+    opcode::RecvMsgMulti::new(types::Fd(fd), msghdr as *const _, BUFFER_GROUP)
+}
+
+/// Copies a packet received by the multishot request into a message
+///
+/// Returns `false` if the packet doesn't fit in the message.
+///
+/// # Safety
+///
+/// The message must have been allocated by `msg::alloc` and reset for receiving
+#[inline]
+unsafe fn copy_message(out: &types::RecvMsgOut, message: &mut Message) -> bool {
+    if out.is_payload_truncated() || out.is_name_data_truncated() {
+        return false;
+    }
+
+    let msg = &mut message.msg_hdr;
+    let payload = out.payload_data();
+
+    if payload.len() > (*msg.msg_iov).iov_len {
+        return false;
+    }
+
+    let name = out.name_data();
+    debug_assert!(name.len() <= size_of::<libc::sockaddr_in6>());
+    core::ptr::copy_nonoverlapping(name.as_ptr(), msg.msg_name as *mut u8, name.len());
+    msg.msg_namelen = name.len() as _;
+
+    // truncated control messages are still passed along since they're validated when decoded
+    let control = out.control_data();
+    let control_len = control.len().min(cmsg::MAX_LEN);
+    core::ptr::copy_nonoverlapping(control.as_ptr(), msg.msg_control as *mut u8, control_len);
+    msg.msg_controllen = control_len as _;
+
+    msg.msg_flags = out.flags() as _;
+
+    core::ptr::copy_nonoverlapping(
+        payload.as_ptr(),
+        (*msg.msg_iov).iov_base as *mut u8,
+        payload.len(),
+    );
+    crate::message::Message::set_payload_len(message, payload.len());
+
+    true
+}
+
+/// Reads a message from the socket error queue into `message`
+///
+/// The multishot request doesn't read the error queue so it's read with a non-blocking syscall
+/// instead.
+#[inline]
+fn read_error_queue(fd: std::os::unix::io::RawFd, message: &mut Message) -> bool {
+    let flags = recverr::MSG_ERRQUEUE | libc::MSG_DONTWAIT;
+    let res = unsafe { libc::recvmsg(fd, &mut message.msg_hdr, flags) };
+
+    if res < 0 {
+        return false;
+    }
+
+    message.msg_len = res as _;
+    true
+}
+
+/// The state of the `sendmsg` request for a message in the queue
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Request {
+    /// The request was submitted and hasn't completed yet
+    InFlight,
+    /// The request completed with the given result
+    Complete(i32),
+    /// The socket send buffer was full so the message is sent again once the socket is writable
+    Retry,
+}
+
+/// Sends the messages in the queue with a `sendmsg` request for each message
+///
+/// The socket is non-blocking, so requests fail with `EAGAIN` instead of waiting when the socket
+/// send buffer is full. Those messages are kept in the queue and resubmitted once a `POLLOUT`
+/// request reports the socket as writable.
+pub struct TxSocket {
+    ring: AsyncFd<IoUring>,
+    /// The requests for the messages in the queue, in the order of the messages
+    ///
+    /// Messages are only released once all of the requests for the messages before them
+    /// complete, since the kernel reads them until their request completes.
+    in_flight: VecDeque<Request>,
+    /// The user data of the request for the first message in the queue
+    sequence: u64,
+    /// The number of messages waiting for the socket to become writable
+    retries: usize,
+    /// Set if a `POLLOUT` request is in flight
+    is_polling: bool,
+    /// Set once the `POLLOUT` request completes, after which the retries are resubmitted
+    is_writable: bool,
+    // the socket is dropped after the ring so it's open while requests are canceled
+    socket: UdpSocket,
+}
+
+impl TxSocket {
+    pub fn new<S: Into<UdpSocket>>(socket: S) -> io::Result<Self> {
+        let socket = socket.into();
+        socket.set_nonblocking(true)?;
+
+        Ok(Self {
+            ring: new_ring()?,
+            in_flight: VecDeque::with_capacity(QUEUE_DEPTH as usize),
+            sequence: 0,
+            retries: 0,
+            is_polling: false,
+            is_writable: false,
+            socket,
+        })
+    }
+
+    /// Submits a request for each message that doesn't have one yet
+    ///
+    /// Messages waiting for the socket to become writable are resubmitted once the `POLLOUT`
+    /// request completes, which is submitted first if it isn't already in flight.
+    #[inline]
+    fn submit(&mut self, entries: &mut [Message], stats: &stats::Sender) -> io::Result<()> {
+        let fd = types::Fd(self.socket.as_raw_fd());
+        let ring = self.ring.get_mut();
+        let mut submitted = 0;
+
+        {
+            let mut submission = ring.submission();
+
+            if self.retries > 0 && self.is_writable {
+                for (index, request) in self.in_flight.iter_mut().enumerate() {
+                    if *request != Request::Retry {
+                        continue;
+                    }
+
+                    let entry = opcode::SendMsg::new(fd, &entries[index].msg_hdr as *const _)
+                        .build()
+                        .user_data(self.sequence.wrapping_add(index as u64));
+
+                    // Safety: the message isn't released until the request completes
+                    if unsafe { submission.push(&entry) }.is_err() {
+                        break;
+                    }
+
+                    *request = Request::InFlight;
+                    self.retries -= 1;
+                    submitted += 1;
+                }
+
+                self.is_writable = self.retries > 0;
+            }
+
+            if self.retries > 0 && !self.is_polling && !self.is_writable {
+                let entry = opcode::PollAdd::new(fd, libc::POLLOUT as _)
+                    .build()
+                    .user_data(POLL_USER_DATA);
+
+                // Safety: the poll request doesn't reference any memory
+                if unsafe { submission.push(&entry) }.is_ok() {
+                    self.is_polling = true;
+                    submitted += 1;
+                }
+            }
+
+            // bound the number of in-flight requests so the completion queue can't overflow
+            while self.in_flight.len() < entries.len().min(QUEUE_DEPTH as usize) {
+                let index = self.in_flight.len();
+                let entry = opcode::SendMsg::new(fd, &entries[index].msg_hdr as *const _)
+                    .build()
+                    .user_data(self.sequence.wrapping_add(index as u64));
+
+                // Safety: the message isn't released until the request completes
+                if unsafe { submission.push(&entry) }.is_err() {
+                    break;
+                }
+
+                self.in_flight.push_back(Request::InFlight);
+                submitted += 1;
+            }
+        }
+
+        if submitted > 0 {
+            let res = ring.submit();
+            if res.is_err() {
+                stats.send().on_operation_result(&res, |_| 0);
+            }
+            res?;
+        }
+
+        Ok(())
+    }
+
+    /// Records the results of the completed requests
+    #[inline]
+    fn reap(&mut self) {
+        for cqe in self.ring.get_mut().completion() {
+            if cqe.user_data() == POLL_USER_DATA {
+                // the messages are resubmitted even if the poll failed, in which case the
+                // requests report the socket error
+                self.is_polling = false;
+                self.is_writable = true;
+                continue;
+            }
+
+            let index = cqe.user_data().wrapping_sub(self.sequence) as usize;
+            let Some(request) = self.in_flight.get_mut(index) else {
+                continue;
+            };
+
+            *request = match -cqe.result() {
+                libc::EAGAIN | libc::EINTR => {
+                    self.retries += 1;
+                    Request::Retry
+                }
+                _ => Request::Complete(cqe.result()),
+            };
+        }
+    }
+
+    /// Releases the messages with completed requests at the front of the queue
+    #[inline]
+    fn release(&mut self, events: &mut tx::Events, stats: &stats::Sender) -> usize {
+        let mut count = 0;
+
+        while let Some(Request::Complete(result)) = self.in_flight.front().copied() {
+            self.in_flight.pop_front();
+            self.sequence = self.sequence.wrapping_add(1);
+            count += 1;
+
+            if result >= 0 {
+                let _ = events.on_complete(1);
+                continue;
+            }
+
+            stats.send().on_error(-result);
+
+            // let the GSO settings know about the error, which may disable it
+            let _ = events.on_error(io::Error::from_raw_os_error(-result));
+        }
+
+        if count > 0 {
+            stats.send().on_operation_ready(count);
+        }
+
+        count
+    }
+}
+
+impl Drop for TxSocket {
+    fn drop(&mut self) {
+        let in_flight = self
+            .in_flight
+            .iter()
+            .filter(|request| **request == Request::InFlight)
+            .count()
+            + self.is_polling as usize;
+        if in_flight > 0 {
+            cancel_all(self.ring.get_mut(), in_flight);
+        }
+    }
+}
+
+impl tx::Socket<Message> for TxSocket {
+    type Error = io::Error;
+
+    #[inline]
+    fn send(
+        &mut self,
+        cx: &mut Context,
+        entries: &mut [Message],
+        events: &mut tx::Events,
+        stats: &stats::Sender,
+    ) -> io::Result<()> {
+        loop {
+            // reap before submitting so messages which need to be retried wait for the socket to
+            // become writable before the task waits for completions
+            self.reap();
+            self.submit(entries, stats)?;
+
+            if self.release(events, stats) > 0 {
+                return Ok(());
+            }
+
+            if self.in_flight.is_empty() {
+                return Ok(());
+            }
+
+            if !poll_completions(&self.ring, cx)? {
+                stats.send().on_operation_pending();
+                events.blocked();
+                return Ok(());
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// Ensures requests rejected by the kernel are reported as unsupported, which makes the provider
+/// fall back to receiving with `recvmmsg`
+#[test]
+#[cfg_attr(miri, ignore)]
+fn probe_rejected_test() {
+    let Ok(mut ring) = IoUring::new(8) else {
+        eprintln!("The current environment does not support io_uring; skipping");
+        return;
+    };
+
+    let socket = socket2::Socket::new(socket2::Domain::IPV4, socket2::Type::DGRAM, None).unwrap();
+    // Safety: an all-zero `msghdr` is valid
+    let msghdr: libc::msghdr = unsafe { core::mem::zeroed() };
+
+    // kernels before 6.0 reject the multishot flag, which is simulated with a flag that no kernel
+    // accepts
+    let entry = recv_entry(socket.as_raw_fd(), &msghdr)
+        .ioprio(1 << 15)
+        .build();
+
+    // Safety: the request is rejected before it references any memory
+    let error = unsafe { probe(&mut ring, entry) }.unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::Unsupported);
+}
+
+/// Ensures the receiver is created on kernels with multishot `recvmsg` and otherwise fails with
+/// an unsupported error
+#[test]
+#[cfg_attr(miri, ignore)]
+fn rx_socket_test() {
+    if IoUring::new(8).is_err() {
+        eprintln!("The current environment does not support io_uring; skipping");
+        return;
+    }
+
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    match RxSocket::new(socket, 1500) {
+        Ok(socket) => assert!(!socket.is_armed),
+        Err(error) => assert_eq!(error.kind(), io::ErrorKind::Unsupported, "{error}"),
+    }
+}
@@ -183,6 +183,14 @@ async fn test<A: ToSocketAddrs>(
     let (server_io, server_addr) = runtime(server_rx_addr, server_tx_addr).await?;
     let (client_io, client_addr) = runtime(client_rx_addr, client_tx_addr).await?;
 
+    run((server_io, server_addr), (client_io, client_addr)).await
+}
+
+/// Runs a client and server endpoint until the client has finished exchanging packets
+async fn run(
+    (server_io, server_addr): (super::Io, SocketAddress),
+    (client_io, client_addr): (super::Io, SocketAddress),
+) -> io::Result<()> {
     let server_endpoint = {
         let mut handle = PathHandle::from_remote_address(client_addr.into());
         handle.local_address = server_addr.into();
@@ -263,6 +271,115 @@ async fn ipv6_two_socket_test() -> io::Result<()> {
     }
 }
 
+/// Returns `true` if `io_uring` is available
+///
+/// Kernels without multishot `recvmsg`, which was added in Linux 6.0, are still supported since
+/// the provider receives packets with `recvmmsg` on them instead.
+#[cfg(all(feature = "uring", target_os = "linux", s2n_quic_platform_socket_mmsg))]
+fn supports_io_uring() -> bool {
+    io_uring::IoUring::new(8).is_ok()
+}
+
+#[cfg(all(feature = "uring", target_os = "linux", s2n_quic_platform_socket_mmsg))]
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn io_uring_test() -> io::Result<()> {
+    if !supports_io_uring() {
+        eprintln!("The current environment does not support io_uring; skipping");
+        return Ok(());
+    }
+
+    let mut server = runtime(IPV4_LOCALHOST, None).await?;
+    server.0.builder = server.0.builder.with_io_uring(true)?;
+    let mut client = runtime(IPV4_LOCALHOST, None).await?;
+    client.0.builder = client.0.builder.with_io_uring(true)?;
+
+    run(server, client).await
+}
+
+/// Ensures messages which fail with `EAGAIN` because the socket send buffer is full are sent
+/// once the socket is writable again instead of being dropped
+#[cfg(all(feature = "uring", target_os = "linux", s2n_quic_platform_socket_mmsg))]
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn io_uring_full_send_buffer_test() -> io::Result<()> {
+    use crate::message::Message as _;
+    use std::os::unix::net::UnixDatagram;
+
+    if !supports_io_uring() {
+        eprintln!("The current environment does not support io_uring; skipping");
+        return Ok(());
+    }
+
+    const MESSAGES: usize = 2048;
+    const PAYLOAD_LEN: usize = 1200;
+
+    // the queue of a unix datagram socket fills up after a few messages, after which sends fail
+    // with `EAGAIN` until the peer reads from it
+    let (tx_socket, rx_socket) = UnixDatagram::pair()?;
+    let tx_socket = task::uring::TxSocket::new(std::os::fd::OwnedFd::from(tx_socket))?;
+
+    let (mut producer, consumer) = socket::ring::pair::<message::Message>(256, PAYLOAD_LEN as _);
+    let (stats, _stats) = socket::stats::channel();
+    let task = tokio::spawn(task::uring::tx(
+        tx_socket,
+        consumer,
+        Default::default(),
+        Cooldown::default(),
+        stats,
+    ));
+
+    let producer = tokio::spawn(async move {
+        let mut sent = 0;
+        while sent < MESSAGES {
+            let count = core::future::poll_fn(|cx| producer.poll_acquire(1, cx)).await as usize;
+            let count = count.min(MESSAGES - sent);
+
+            for (index, entry) in producer.data()[..count].iter_mut().enumerate() {
+                // the socket is connected, so the messages don't need an address
+                entry.msg_hdr.msg_namelen = 0;
+                entry.msg_hdr.msg_controllen = 0;
+                unsafe {
+                    *((*entry.msg_hdr.msg_iov).iov_base as *mut u32) = (sent + index) as u32;
+                    entry.set_payload_len(PAYLOAD_LEN);
+                }
+            }
+
+            producer.release(count as _);
+            sent += count;
+        }
+
+        producer
+    });
+
+    // give the task a chance to fill the send buffer before reading anything
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let received = tokio::task::spawn_blocking(move || -> io::Result<Vec<u32>> {
+        rx_socket.set_read_timeout(Some(Duration::from_secs(5)))?;
+        let mut received = vec![];
+        let mut payload = [0; PAYLOAD_LEN];
+        while received.len() < MESSAGES {
+            let len = rx_socket.recv(&mut payload)?;
+            assert_eq!(len, PAYLOAD_LEN);
+            received.push(u32::from_ne_bytes(payload[..4].try_into().unwrap()));
+        }
+        Ok(received)
+    })
+    .await
+    .unwrap()?;
+
+    // every message should be delivered exactly once
+    let mut received = received;
+    received.sort_unstable();
+    assert_eq!(received, (0..MESSAGES as u32).collect::<Vec<_>>());
+
+    drop(producer.await.unwrap());
+    task.await.unwrap()?;
+
+    Ok(())
+}
+
 #[cfg(unix)]
 #[tokio::test]
 #[cfg_attr(miri, ignore)]
@@ -282,3 +399,386 @@ async fn only_v6_test() -> io::Result<()> {
 
     Ok(())
 }
+
+/// Records the shard encoded in each packet received by a shard endpoint
+struct ShardEndpoint {
+    index: u16,
+    encoding: s2n_quic_core::connection::id::shard::Encoding,
+    received: std::sync::Arc<std::sync::Mutex<Vec<(u16, Option<u16>)>>>,
+    subscriber: NoopSubscriber,
+}
+
+impl Endpoint for ShardEndpoint {
+    type PathHandle = PathHandle;
+    type Subscriber = NoopSubscriber;
+
+    const ENDPOINT_TYPE: endpoint::Type = endpoint::Type::Server;
+
+    fn transmit<Tx: tx::Queue<Handle = PathHandle>, C: Clock>(
+        &mut self,
+        _queue: &mut Tx,
+        _clock: &C,
+    ) {
+    }
+
+    fn receive<Rx: rx::Queue<Handle = PathHandle>, C: Clock>(
+        &mut self,
+        queue: &mut Rx,
+        _clock: &C,
+    ) {
+        let mut received = self.received.lock().unwrap();
+        queue.for_each(|_header, payload| {
+            received.push((self.index, self.encoding.decode_packet(payload)));
+        });
+    }
+
+    fn poll_wakeups<C: Clock>(
+        &mut self,
+        _cx: &mut Context<'_>,
+        _clock: &C,
+    ) -> Poll<Result<usize, CloseError>> {
+        Poll::Pending
+    }
+
+    fn timeout(&self) -> Option<Timestamp> {
+        None
+    }
+
+    fn set_mtu_config(&mut self, _mtu_config: mtu::Config) {
+        // noop
+    }
+
+    fn subscriber(&mut self) -> &mut Self::Subscriber {
+        &mut self.subscriber
+    }
+}
+
+/// Ensures packets are handled by the shard encoded in the connection ID, even if they're
+/// received by the socket of another shard
+#[cfg(unix)]
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn shard_forwarding_test() -> io::Result<()> {
+    const SHARDS: u16 = 4;
+    const CLIENTS: usize = 8;
+
+    let shards = shard::Builder::default()
+        .with_address(IPV4_LOCALHOST.parse().unwrap())?
+        .with_shard_count(SHARDS)?
+        // rely on forwarding instead of the kernel
+        .with_steering(false)?
+        .build()?;
+    let encoding = shards[0].encoding();
+    let server_addr = convert_addr_to_std(shards[0].socket.local_addr()?)?;
+
+    let received = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+
+    for shard in shards {
+        let endpoint = ShardEndpoint {
+            index: shard.index(),
+            encoding,
+            received: received.clone(),
+            subscriber: Default::default(),
+        };
+        Io::builder().with_shard(shard)?.build()?.start(endpoint)?;
+    }
+
+    // send from several clients so the kernel spreads the packets across the sockets
+    for _ in 0..CLIENTS {
+        let client = std::net::UdpSocket::bind(IPV4_LOCALHOST)?;
+        for index in 0..SHARDS {
+            let mut packet = [0x40; 9];
+            encoding.encode(index, &mut packet[1..]).unwrap();
+            client.send_to(&packet, server_addr)?;
+        }
+    }
+
+    let expected = CLIENTS * SHARDS as usize;
+    tokio::time::timeout(core::time::Duration::from_secs(10), async {
+        while received.lock().unwrap().len() < expected {
+            tokio::time::sleep(core::time::Duration::from_millis(10)).await;
+        }
+    })
+    .await?;
+
+    for (shard, encoded) in received.lock().unwrap().iter() {
+        assert_eq!(Some(*shard), *encoded);
+    }
+
+    Ok(())
+}
+
+/// A message which should be transmitted after a delay
+#[cfg(all(s2n_quic_platform_txtime, s2n_quic_platform_socket_mmsg))]
+struct DelayedMessage {
+    handle: PathHandle,
+    delay: Duration,
+}
+
+#[cfg(all(s2n_quic_platform_txtime, s2n_quic_platform_socket_mmsg))]
+impl tx::Message for DelayedMessage {
+    type Handle = PathHandle;
+
+    fn path_handle(&self) -> &Self::Handle {
+        &self.handle
+    }
+
+    fn ecn(&mut self) -> ExplicitCongestionNotification {
+        Default::default()
+    }
+
+    fn delay(&mut self) -> Duration {
+        self.delay
+    }
+
+    fn ipv6_flow_label(&mut self) -> u32 {
+        0
+    }
+
+    fn can_gso(&self, segment_len: usize, _segment_count: usize) -> bool {
+        segment_len >= 3
+    }
+
+    fn write_payload(
+        &mut self,
+        mut buffer: tx::PayloadBuffer,
+        _gso_offset: usize,
+    ) -> Result<usize, tx::Error> {
+        buffer.write(&[1, 2, 3])
+    }
+}
+
+/// Ensures messages are handed to the kernel with an `SCM_TXTIME` of the time they were pushed
+/// plus their delay, and that the kernel accepts it
+#[cfg(all(s2n_quic_platform_txtime, s2n_quic_platform_socket_mmsg))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn transmit_time_test() -> io::Result<()> {
+    use crate::{
+        features::txtime,
+        message::{cmsg, mmsg},
+    };
+    use s2n_quic_core::io::tx::{Queue as _, Tx as _};
+    use std::os::unix::io::AsRawFd;
+
+    const DELAY: Duration = Duration::from_millis(2);
+
+    let rx_socket = syscall::bind_udp(IPV4_LOCALHOST, false, false, false)?;
+    let tx_socket = syscall::bind_udp(IPV4_LOCALHOST, false, false, false)?;
+    if !syscall::configure_txtime(&tx_socket) {
+        eprintln!("The current environment does not support SO_TXTIME; skipping");
+        return Ok(());
+    }
+    let rx_socket: std::net::UdpSocket = rx_socket.into();
+    rx_socket.set_read_timeout(Some(Duration::from_secs(1)))?;
+
+    let rx_addr: SocketAddress = rx_socket.local_addr()?.into();
+    let handle = PathHandle::from_remote_address(rx_addr.into());
+
+    let (producer, mut consumer) = socket::ring::pair::<mmsg::Message>(16, 1500);
+    let mut tx =
+        socket::io::tx::Tx::new(vec![producer], Default::default(), 1500).with_transmit_time();
+
+    let before = txtime::transmit_time(Duration::ZERO);
+    tx.queue(|queue| {
+        queue
+            .push(DelayedMessage {
+                handle,
+                delay: DELAY,
+            })
+            .unwrap();
+    });
+    let after = txtime::transmit_time(Duration::ZERO);
+
+    assert_eq!(consumer.acquire(u32::MAX), 1);
+    let message = &consumer.data()[0];
+
+    let transmit_time = unsafe { cmsg::decode::Iter::from_msghdr(&message.msg_hdr) }
+        .find(|(cmsg, _)| txtime::is_match(cmsg.cmsg_level, cmsg.cmsg_type))
+        .and_then(|(_, value)| unsafe { cmsg::decode::value_from_bytes::<txtime::Cmsg>(value) })
+        .expect("missing SCM_TXTIME");
+
+    // the transmit time is relative to when the message was pushed, not when the endpoint
+    // started transmitting
+    let delay = DELAY.as_nanos() as u64;
+    assert!(
+        (before + delay..=after + delay).contains(&transmit_time),
+        "{transmit_time} should be within [{}, {}]",
+        before + delay,
+        after + delay,
+    );
+
+    // the kernel accepts the transmit time and sends the message
+    let res = unsafe { libc::sendmsg(tx_socket.as_raw_fd(), &message.msg_hdr, 0) };
+    assert!(res >= 0, "{}", io::Error::last_os_error());
+
+    let mut payload = [0; 16];
+    let len = rx_socket.recv(&mut payload)?;
+    assert_eq!(&payload[..len], &[1, 2, 3]);
+
+    consumer.release(1);
+
+    Ok(())
+}
+
+/// Ensures messages with the same departure time are coalesced into a single GSO payload, while
+/// messages with a different departure time start a new one
+#[cfg(all(
+    s2n_quic_platform_txtime,
+    s2n_quic_platform_gso,
+    s2n_quic_platform_socket_mmsg
+))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn transmit_time_gso_test() {
+    use crate::{message::mmsg, message::Message as _};
+    use s2n_quic_core::io::tx::{Queue as _, Tx as _};
+
+    const DELAY: Duration = Duration::from_millis(2);
+
+    let handle = PathHandle::from_remote_address(SocketAddress::default().into());
+
+    let (producer, mut consumer) = socket::ring::pair::<mmsg::Message>(16, u16::MAX as _);
+    let mut tx =
+        socket::io::tx::Tx::new(vec![producer], Default::default(), 1500).with_transmit_time();
+
+    tx.queue(|queue| {
+        // the messages in a pacer burst share a departure time
+        for delay in [DELAY, DELAY, DELAY + Duration::from_millis(1)] {
+            queue.push(DelayedMessage { handle, delay }).unwrap();
+        }
+    });
+
+    assert_eq!(consumer.acquire(u32::MAX), 2);
+    let payload_lens: Vec<_> = consumer.data().iter().map(|m| m.payload_len()).collect();
+    assert_eq!(payload_lens, [6, 3]);
+    consumer.release(2);
+}
+
+/// Ensures the ICMP errors queued on a separate tx socket are read without reading the
+/// datagrams that arrive on it
+#[cfg(s2n_quic_platform_recverr)]
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn tx_socket_error_queue_test() -> io::Result<()> {
+    use crate::message::Message as _;
+    use s2n_quic_core::inet::icmp;
+
+    let tx_socket = syscall::bind_udp(IPV4_LOCALHOST, false, false, false)?;
+    if !syscall::configure_recverr(&tx_socket) {
+        eprintln!("The current environment does not support IP_RECVERR; skipping");
+        return Ok(());
+    }
+    let tx_socket: std::net::UdpSocket = tx_socket.into();
+    let tx_addr = tx_socket.local_addr()?;
+
+    // find a port that nothing is listening on
+    let closed_addr = std::net::UdpSocket::bind(IPV4_LOCALHOST)?.local_addr()?;
+
+    // a peer sends a datagram to the tx socket, which should stay in its receive buffer
+    let peer = std::net::UdpSocket::bind(IPV4_LOCALHOST)?;
+    peer.send_to(b"peer", tx_addr)?;
+
+    let (producer, mut consumer) = socket::ring::pair::<message::Message>(16, 1500);
+    let (stats, _stats) = socket::stats::channel();
+    tokio::spawn(task::rx_errors(
+        tx_socket.try_clone()?,
+        producer,
+        Cooldown::new(0),
+        stats,
+    ));
+
+    // the closed port responds with an ICMP Port Unreachable message, which is queued on the
+    // socket that sent the datagram
+    tx_socket.send_to(b"hello", closed_addr)?;
+
+    let count = tokio::time::timeout(
+        Duration::from_secs(5),
+        core::future::poll_fn(|cx| consumer.poll_acquire(1, cx)),
+    )
+    .await
+    .expect("the ICMP error should be read from the error queue");
+    assert_eq!(count, 1);
+
+    let local_address: path::LocalAddress = SocketAddress::from(tx_addr).into();
+    let message = consumer.data()[0]
+        .rx_read(&local_address)
+        .expect("the error should include the datagram that was sent");
+    assert_eq!(message.icmp_error, Some(icmp::Error::Other));
+    assert_eq!(
+        message.header.path.remote_address,
+        SocketAddress::from(closed_addr).into()
+    );
+    assert_eq!(message.payload, b"hello");
+    consumer.release(1);
+
+    // the datagram from the peer was left for the application to read
+    tx_socket.set_read_timeout(Some(Duration::from_secs(1)))?;
+    let mut payload = [0; 16];
+    let (len, addr) = tx_socket.recv_from(&mut payload)?;
+    assert_eq!(&payload[..len], b"peer");
+    assert_eq!(addr, peer.local_addr()?);
+
+    Ok(())
+}
+
+/// Ensures the rx socket reads both the queued ICMP errors and the datagrams that arrive on it
+#[cfg(s2n_quic_platform_recverr)]
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn rx_socket_error_queue_test() -> io::Result<()> {
+    use crate::message::Message as _;
+    use s2n_quic_core::inet::icmp;
+
+    let rx_socket = syscall::bind_udp(IPV4_LOCALHOST, false, false, false)?;
+    if !syscall::configure_recverr(&rx_socket) {
+        eprintln!("The current environment does not support IP_RECVERR; skipping");
+        return Ok(());
+    }
+    let rx_socket: std::net::UdpSocket = rx_socket.into();
+    let rx_addr = rx_socket.local_addr()?;
+    let closed_addr = std::net::UdpSocket::bind(IPV4_LOCALHOST)?.local_addr()?;
+
+    // queue an error on the socket and then receive a datagram from a peer
+    rx_socket.send_to(b"hello", closed_addr)?;
+    let peer = std::net::UdpSocket::bind(IPV4_LOCALHOST)?;
+    peer.send_to(b"peer", rx_addr)?;
+
+    let (producer, mut consumer) = socket::ring::pair::<message::Message>(16, 1500);
+    let (stats, _stats) = socket::stats::channel();
+    tokio::spawn(task::rx(
+        rx_socket.try_clone()?,
+        producer,
+        Cooldown::new(0),
+        stats,
+    ));
+
+    let local_address: path::LocalAddress = SocketAddress::from(rx_addr).into();
+    let mut received = vec![];
+
+    while received.len() < 2 {
+        let count = tokio::time::timeout(
+            Duration::from_secs(5),
+            core::future::poll_fn(|cx| consumer.poll_acquire(1, cx)),
+        )
+        .await
+        .expect("both the error and the datagram should be read");
+
+        for message in &mut consumer.data()[..count as usize] {
+            let message = message.rx_read(&local_address).unwrap();
+            received.push((message.icmp_error, message.payload.to_vec()));
+        }
+        consumer.release(count);
+    }
+
+    received.sort_by_key(|(icmp_error, _)| icmp_error.is_some());
+    assert_eq!(
+        received,
+        [
+            (None, b"peer".to_vec()),
+            (Some(icmp::Error::Other), b"hello".to_vec()),
+        ]
+    );
+
+    Ok(())
+}
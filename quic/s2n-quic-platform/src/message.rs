@@ -2,7 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use core::{alloc::Layout, ptr::NonNull};
-use s2n_quic_core::{inet::datagram, io::tx, path};
+use s2n_quic_core::{
+    inet::{datagram, icmp},
+    io::tx,
+    path,
+};
 
 #[cfg(s2n_quic_platform_cmsg)]
 pub mod cmsg;
@@ -80,6 +84,7 @@ pub trait Message: 'static + Copy {
     const SUPPORTS_GSO: bool;
     const SUPPORTS_ECN: bool;
     const SUPPORTS_FLOW_LABELS: bool;
+    const SUPPORTS_TXTIME: bool;
 
     /// Allocates `entries` messages, each with `payload_len` bytes
     fn alloc(entries: u32, payload_len: u32, offset: usize) -> Storage;
@@ -115,6 +120,11 @@ pub trait Message: 'static + Copy {
         panic!("cannot use GSO on the current platform");
     }
 
+    /// Sets the time the message should be transmitted, in nanoseconds of the monotonic clock
+    fn set_transmit_time(&mut self, _time: u64) {
+        panic!("cannot use SO_TXTIME on the current platform");
+    }
+
     /// Resets the message for future use
     ///
     /// # Safety
@@ -138,6 +148,11 @@ pub struct RxMessage<'a, Handle: Copy> {
     pub segment_size: usize,
     /// The full payload of the message
     pub payload: &'a mut [u8],
+    /// The ICMP error reported for the message, if it was read from the socket error queue
+    ///
+    /// In this case, the payload is the beginning of a datagram that was previously sent
+    /// and the header contains its destination.
+    pub icmp_error: Option<icmp::Error>,
 }
 
 impl<Handle: Copy> RxMessage<'_, Handle> {
@@ -177,6 +192,7 @@ mod tests {
                     header: datagram::Header { path, ecn },
                     segment_size,
                     payload: &mut payload,
+                    icmp_error: None,
                 };
 
                 rx_message.for_each(|header, segment| {
@@ -88,6 +88,9 @@ unsafe fn collect_item(data: &mut AncillaryData, cmsg: &cmsghdr, value: &[u8]) {
         (level, ty) if features::gso::is_match(level, ty) => {
             // ignore GSO settings when reading
         }
+        (level, ty) if features::txtime::is_match(level, ty) => {
+            // ignore transmit times when reading
+        }
         (level, ty) if features::gro::is_match(level, ty) => {
             if let Some(segment_size) = value_from_bytes::<features::gro::Cmsg>(value) {
                 data.segment_size = segment_size as _;
@@ -95,6 +98,13 @@ unsafe fn collect_item(data: &mut AncillaryData, cmsg: &cmsghdr, value: &[u8]) {
                 decode_error!("invalid gro value");
             }
         }
+        (level, ty) if features::recverr::is_match(level, ty) => {
+            if let Some(icmp_error) = features::recverr::decode(value) {
+                data.icmp_error = Some(icmp_error);
+            } else {
+                decode_error!("invalid recverr value");
+            }
+        }
         _ => {
             decode_error!("unexpected cmsghdr");
         }
@@ -59,6 +59,16 @@ pub trait Encoder {
         }
     }
 
+    /// Encodes the transmit time of the message into the cmsg encoder
+    #[inline]
+    fn encode_transmit_time(&mut self, time: features::txtime::Cmsg) -> Result<usize, Error> {
+        if let (Some(level), Some(ty)) = (features::txtime::LEVEL, features::txtime::TYPE) {
+            self.encode_cmsg(level, ty, time)
+        } else {
+            panic!("platform does not support SO_TXTIME");
+        }
+    }
+
     #[inline]
     fn encode_local_address(&mut self, address: &SocketAddress) -> Result<usize, Error> {
         use s2n_quic_core::inet::Unspecified;
@@ -14,6 +14,7 @@ impl MessageTrait for mmsghdr {
     const SUPPORTS_GSO: bool = libc::msghdr::SUPPORTS_GSO;
     const SUPPORTS_ECN: bool = libc::msghdr::SUPPORTS_ECN;
     const SUPPORTS_FLOW_LABELS: bool = libc::msghdr::SUPPORTS_FLOW_LABELS;
+    const SUPPORTS_TXTIME: bool = libc::msghdr::SUPPORTS_TXTIME;
 
     #[inline]
     fn alloc(entries: u32, payload_len: u32, offset: usize) -> super::Storage {
@@ -44,6 +45,11 @@ impl MessageTrait for mmsghdr {
         self.msg_hdr.set_segment_size(size)
     }
 
+    #[inline]
+    fn set_transmit_time(&mut self, time: u64) {
+        self.msg_hdr.set_transmit_time(time)
+    }
+
     #[inline]
     unsafe fn reset(&mut self, mtu: usize) {
         self.set_payload_len(mtu);
@@ -34,6 +34,7 @@ impl MessageTrait for msghdr {
     const SUPPORTS_GSO: bool = features::gso::IS_SUPPORTED;
     const SUPPORTS_ECN: bool = features::tos::IS_SUPPORTED;
     const SUPPORTS_FLOW_LABELS: bool = true;
+    const SUPPORTS_TXTIME: bool = features::txtime::IS_SUPPORTED;
 
     #[inline]
     fn alloc(entries: u32, payload_len: u32, offset: usize) -> super::Storage {
@@ -61,6 +62,11 @@ impl MessageTrait for msghdr {
         self.cmsg_encoder().encode_gso(size as _).unwrap();
     }
 
+    #[inline]
+    fn set_transmit_time(&mut self, time: u64) {
+        self.cmsg_encoder().encode_transmit_time(time).unwrap();
+    }
+
     #[inline]
     unsafe fn reset(&mut self, mtu: usize) {
         // reset the payload
@@ -158,6 +164,7 @@ impl MessageTrait for msghdr {
             header,
             segment_size,
             payload,
+            icmp_error: cmsg.icmp_error,
         };
 
         Some(message)
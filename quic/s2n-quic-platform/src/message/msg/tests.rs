@@ -109,3 +109,31 @@ fn handle_get_set_test() {
             });
         });
 }
+
+#[test]
+fn transmit_time_test() {
+    if !features::txtime::IS_SUPPORTED {
+        return;
+    }
+
+    test_msghdr(|message| {
+        // messages clear the control messages before writing them
+        message.msg_controllen = 0;
+        message.set_transmit_time(1234);
+
+        let mut iter = unsafe { cmsg::decode::Iter::from_msghdr(message) };
+        let (cmsg, value) = iter.next().unwrap();
+        assert!(features::txtime::is_match(cmsg.cmsg_level, cmsg.cmsg_type));
+        let time = unsafe { cmsg::decode::value_from_bytes::<features::txtime::Cmsg>(value) };
+        assert_eq!(time, Some(1234));
+        assert!(iter.next().is_none());
+
+        // the transmit time is ignored when reading
+        assert!(message.header().is_some());
+
+        // reset the message and ensure the transmit time is cleared
+        unsafe {
+            message.reset(0);
+        }
+    });
+}
@@ -1,13 +1,16 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{message::Message, socket::ring::Consumer};
+use crate::{
+    message::Message,
+    socket::{io::shard::Router, ring::Consumer},
+};
 use core::task::{Context, Poll};
 use s2n_quic_core::{
     event,
-    inet::datagram,
+    inet::{datagram, icmp},
     io::rx,
-    path::{LocalAddress, MaxMtu},
+    path::{Handle as _, LocalAddress, MaxMtu},
     task::waker,
 };
 
@@ -15,18 +18,39 @@ use s2n_quic_core::{
 pub struct Rx<T: Message> {
     channels: Vec<Consumer<T>>,
     max_mtu: MaxMtu,
-    local_address: LocalAddress,
+    /// The local address of the socket each channel receives on
+    local_addresses: Vec<LocalAddress>,
+    packet_too_big: Vec<icmp::PacketTooBig>,
+    router: Option<Router<T::Handle>>,
 }
 
 impl<T: Message> Rx<T> {
     #[inline]
     pub fn new(channels: Vec<Consumer<T>>, max_mtu: MaxMtu, local_address: LocalAddress) -> Self {
+        let local_addresses = vec![local_address; channels.len()];
         Self {
             channels,
             max_mtu,
-            local_address,
+            local_addresses,
+            packet_too_big: Vec::new(),
+            router: None,
         }
     }
+
+    /// Receives messages from a `channel` for a socket bound to an additional `local_address`
+    #[inline]
+    pub fn with_channel(mut self, channel: Consumer<T>, local_address: LocalAddress) -> Self {
+        self.channels.push(channel);
+        self.local_addresses.push(local_address);
+        self
+    }
+
+    /// Forwards packets for connections owned by other shards with the `router`
+    #[inline]
+    pub fn with_router(mut self, router: Router<T::Handle>) -> Self {
+        self.router = Some(router);
+        self
+    }
 }
 
 impl<T: Message> rx::Rx for Rx<T> {
@@ -53,6 +77,11 @@ impl<T: Message> rx::Rx for Rx<T> {
                 }
             }
 
+            // check for any packets forwarded from other shards
+            if let Some(router) = &self.router {
+                is_any_ready |= router.poll_forwarded(cx);
+            }
+
             // if all of the channels are closed then shut down the task
             if is_all_closed {
                 return Err(()).into();
@@ -89,7 +118,9 @@ impl<T: Message> rx::Rx for Rx<T> {
         let mut queue = RxQueue {
             channels: &mut this.channels,
             max_mtu: this.max_mtu,
-            local_address: &this.local_address,
+            local_addresses: &this.local_addresses,
+            packet_too_big: &mut this.packet_too_big,
+            router: this.router.as_ref(),
         };
 
         f(&mut queue);
@@ -106,7 +137,9 @@ impl<T: Message> rx::Rx for Rx<T> {
 pub struct RxQueue<'a, T: Message> {
     channels: &'a mut [Consumer<T>],
     max_mtu: MaxMtu,
-    local_address: &'a LocalAddress,
+    local_addresses: &'a [LocalAddress],
+    packet_too_big: &'a mut Vec<icmp::PacketTooBig>,
+    router: Option<&'a Router<T::Handle>>,
 }
 
 impl<T: Message> rx::Queue for RxQueue<'_, T> {
@@ -114,7 +147,25 @@ impl<T: Message> rx::Queue for RxQueue<'_, T> {
 
     #[inline]
     fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(&mut self, mut on_packet: F) {
-        for channel in self.channels.iter_mut() {
+        let router = self.router;
+
+        // process the packets other shards received on our behalf
+        if let Some(router) = router {
+            router.for_each_forwarded(&mut on_packet);
+        }
+
+        let mut on_packet = |header: datagram::Header<Self::Handle>, payload: &mut [u8]| {
+            // hand off packets for connections owned by other shards
+            if let Some(router) = router {
+                if router.forward(header, payload) {
+                    return;
+                }
+            }
+
+            on_packet(header, payload)
+        };
+
+        for (channel, local_address) in self.channels.iter_mut().zip(self.local_addresses) {
             // one last effort to acquire items if some were received since we last polled
             let len = channel.acquire(u32::MAX);
 
@@ -126,8 +177,20 @@ impl<T: Message> rx::Queue for RxQueue<'_, T> {
                 //
                 // NOTE: it's important that we process all of the messages in the queue as the
                 //       channel is completely drained here.
-                if let Some(message) = message.rx_read(self.local_address) {
-                    message.for_each(&mut on_packet);
+                if let Some(message) = message.rx_read(local_address) {
+                    match message.icmp_error {
+                        None => message.for_each(&mut on_packet),
+                        Some(icmp::Error::PacketTooBig { mtu }) => {
+                            // hold on to the message until the endpoint asks for it, since it
+                            // needs to be validated by the connection the quoted datagram
+                            // belongs to
+                            let remote_address = message.header.path.remote_address().0;
+                            let ptb = icmp::PacketTooBig::new(remote_address, mtu, message.payload);
+                            self.packet_too_big.push(ptb);
+                        }
+                        // other ICMP errors aren't used by the endpoint
+                        Some(icmp::Error::Other) => {}
+                    }
                 }
 
                 unsafe {
@@ -141,6 +204,13 @@ impl<T: Message> rx::Queue for RxQueue<'_, T> {
         }
     }
 
+    #[inline]
+    fn for_each_packet_too_big<F: FnMut(&icmp::PacketTooBig)>(&mut self, mut on_packet_too_big: F) {
+        for ptb in self.packet_too_big.drain(..) {
+            on_packet_too_big(&ptb);
+        }
+    }
+
     #[inline]
     fn is_empty(&self) -> bool {
         false
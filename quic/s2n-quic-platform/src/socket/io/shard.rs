@@ -0,0 +1,166 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Forwards packets between endpoint shards
+//!
+//! Without a kernel steering program, or while the reuseport group is changing, packets can be
+//! received by the socket of a shard that doesn't own the connection. The [`Router`] of each shard
+//! hands those packets to the owning shard, which processes them as if they were received on its
+//! own socket.
+
+use core::task::{Context, Waker};
+use s2n_quic_core::{connection::id::shard::Encoding, inet::datagram};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+/// The maximum number of forwarded packets queued for a shard before packets are dropped
+const MAX_QUEUED: usize = 4096;
+
+struct Inbox<H> {
+    queue: VecDeque<(datagram::Header<H>, Vec<u8>)>,
+    waker: Option<Waker>,
+}
+
+/// Routes packets received by a shard to the shard that owns the connection
+pub struct Router<H> {
+    index: u16,
+    encoding: Encoding,
+    inboxes: Arc<[Mutex<Inbox<H>>]>,
+}
+
+impl<H: Copy> Router<H> {
+    /// Creates a router for each of the shards in the `encoding`
+    pub fn group(encoding: Encoding) -> Vec<Self> {
+        let inboxes: Arc<[_]> = (0..encoding.count())
+            .map(|_| {
+                Mutex::new(Inbox {
+                    queue: VecDeque::new(),
+                    waker: None,
+                })
+            })
+            .collect();
+
+        (0..encoding.count())
+            .map(|index| Self {
+                index,
+                encoding,
+                inboxes: inboxes.clone(),
+            })
+            .collect()
+    }
+
+    /// Returns the index of the shard
+    #[inline]
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    /// Returns the connection ID encoding used to route packets
+    #[inline]
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Forwards the packet if it belongs to another shard
+    ///
+    /// Returns `true` if the packet was taken by another shard.
+    #[inline]
+    pub fn forward(&self, header: datagram::Header<H>, payload: &[u8]) -> bool {
+        let Some(index) = self.encoding.decode_packet(payload) else {
+            return false;
+        };
+
+        if index == self.index {
+            return false;
+        }
+
+        let Ok(mut inbox) = self.inboxes[index as usize].lock() else {
+            // the shard has panicked so drop the packet
+            return true;
+        };
+
+        // drop the packet if the shard isn't keeping up
+        if inbox.queue.len() < MAX_QUEUED {
+            inbox.queue.push_back((header, payload.to_vec()));
+            if let Some(waker) = inbox.waker.take() {
+                waker.wake();
+            }
+        }
+
+        true
+    }
+
+    /// Returns `true` if packets were forwarded to the shard
+    ///
+    /// If there aren't any, the waker is notified once a packet is forwarded.
+    #[inline]
+    pub fn poll_forwarded(&self, cx: &mut Context) -> bool {
+        let Ok(mut inbox) = self.inboxes[self.index as usize].lock() else {
+            return false;
+        };
+
+        if inbox.queue.is_empty() {
+            inbox.waker = Some(cx.waker().clone());
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Calls `on_packet` for each packet forwarded to the shard
+    #[inline]
+    pub fn for_each_forwarded<F: FnMut(datagram::Header<H>, &mut [u8])>(&self, mut on_packet: F) {
+        let queue = match self.inboxes[self.index as usize].lock() {
+            Ok(mut inbox) => core::mem::take(&mut inbox.queue),
+            Err(_) => return,
+        };
+
+        // the lock isn't held while processing so other shards can keep forwarding packets
+        for (header, mut payload) in queue {
+            on_packet(header, &mut payload);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::inet::ExplicitCongestionNotification;
+
+    fn header() -> datagram::Header<u8> {
+        datagram::Header {
+            path: 0,
+            ecn: ExplicitCongestionNotification::default(),
+        }
+    }
+
+    #[test]
+    fn forward_test() {
+        let encoding = Encoding::new(2).unwrap();
+        let routers = Router::group(encoding);
+
+        let mut id = [0u8; 8];
+        encoding.encode(1, &mut id).unwrap();
+        let mut packet = vec![0x40];
+        packet.extend_from_slice(&id);
+
+        // the packet belongs to the second shard
+        assert!(!routers[1].forward(header(), &packet));
+        assert!(routers[0].forward(header(), &packet));
+
+        // packets without a connection ID aren't forwarded
+        assert!(!routers[0].forward(header(), &[0x40]));
+
+        let waker = futures::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        assert!(!routers[0].poll_forwarded(&mut cx));
+        assert!(routers[1].poll_forwarded(&mut cx));
+
+        let mut received = vec![];
+        routers[1].for_each_forwarded(|_header, payload| received.push(payload.to_vec()));
+        assert_eq!(received, vec![packet]);
+        assert!(!routers[1].poll_forwarded(&mut cx));
+    }
+}
@@ -1,11 +1,18 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{features::Gso, message::Message, socket::ring::Producer};
-use core::task::{Context, Poll};
+use crate::{
+    features::{txtime, Gso},
+    message::Message,
+    socket::ring::Producer,
+};
+use core::{
+    task::{Context, Poll},
+    time::Duration,
+};
 use s2n_quic_core::{
     event,
-    inet::ExplicitCongestionNotification,
+    inet::{ExplicitCongestionNotification, SocketAddress, Unspecified as _},
     io::tx,
     path::{Handle as _, MaxMtu},
     task::waker,
@@ -16,7 +23,15 @@ pub struct Tx<T: Message> {
     channels: Vec<Producer<T>>,
     gso: Gso,
     max_mtu: usize,
+    max_delay: Duration,
     is_full: bool,
+    routes: Vec<Route<T>>,
+}
+
+/// The channels of a socket bound to an additional local address
+struct Route<T: Message> {
+    local_address: SocketAddress,
+    tx: Tx<T>,
 }
 
 impl<T: Message> Tx<T> {
@@ -26,18 +41,36 @@ impl<T: Message> Tx<T> {
             channels,
             gso,
             max_mtu: max_mtu.into(),
+            max_delay: Duration::ZERO,
             is_full: true,
+            routes: Vec::new(),
         }
     }
-}
 
-impl<T: Message> tx::Tx for Tx<T> {
-    type PathHandle = T::Handle;
-    type Queue = TxQueue<'static, T>;
-    type Error = ();
+    /// Sends messages from `local_address` with the channels of `tx`
+    ///
+    /// This is used for sockets bound to an additional local address, which connections can
+    /// migrate to. Messages sent from any other address use the channels of `self`.
+    #[inline]
+    pub fn with_route(mut self, local_address: SocketAddress, tx: Self) -> Self {
+        self.routes.push(Route { local_address, tx });
+        self
+    }
 
+    /// Stamps each message with the time it should be transmitted
+    ///
+    /// This hands pacing off to the kernel, which requires the sockets to be configured with
+    /// `SO_TXTIME`.
     #[inline]
-    fn poll_ready(&mut self, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+    pub fn with_transmit_time(mut self) -> Self {
+        if T::SUPPORTS_TXTIME {
+            self.max_delay = txtime::MAX_DELAY;
+        }
+        self
+    }
+
+    #[inline]
+    fn poll_capacity(&mut self, cx: &mut Context) -> Poll<Result<(), ()>> {
         // We only need to poll for capacity if we completely filled up all of the channels.
         // If we always polled, this would cause the endpoint to spin since most of the time it has
         // capacity for sending.
@@ -77,28 +110,12 @@ impl<T: Message> tx::Tx for Tx<T> {
         })
     }
 
+    /// Acquires the free slots in the channels and returns a queue to write messages into
     #[inline]
-    fn queue<F: FnOnce(&mut Self::Queue)>(&mut self, f: F) {
-        let this: &'static mut Self = unsafe {
-            // Safety: As noted in the [transmute examples](https://doc.rust-lang.org/std/mem/fn.transmute.html#examples)
-            // it can be used to temporarily extend the lifetime of a reference. In this case, we
-            // don't want to use GATs until the MSRV is >=1.65.0, which means `Self::Queue` is not
-            // allowed to take generic lifetimes.
-            //
-            // We are left with using a `'static` lifetime here and encapsulating it in a private
-            // field. The `Self::Queue` struct is then borrowed for the lifetime of the `F`
-            // function. This will prevent the value from escaping beyond the lifetime of `&mut
-            // self`.
-            //
-            // See https://play.rust-lang.org/?version=stable&mode=debug&edition=2021&gist=9a32abe85c666f36fb2ec86496cc41b4
-            //
-            // Once https://github.com/aws/s2n-quic/issues/1742 is resolved this code can go away
-            core::mem::transmute(self)
-        };
-
+    fn new_queue(&mut self) -> TxQueue<'_, T> {
         let mut capacity = 0;
         let mut first_with_free_slots = None;
-        for (idx, channel) in this.channels.iter_mut().enumerate() {
+        for (idx, channel) in self.channels.iter_mut().enumerate() {
             // try to make one more effort to acquire capacity for sending
             let count = channel.acquire(u32::MAX) as usize;
 
@@ -111,30 +128,82 @@ impl<T: Message> tx::Tx for Tx<T> {
         }
 
         // mark that we're still full so we need to poll and wake up next iteration
-        this.is_full = capacity == 0;
+        self.is_full = capacity == 0;
 
         // start with the first queue that has free slots, otherwise set the index to the length,
         // which will return an AtCapacity error immediately.
-        let channel_index = first_with_free_slots.unwrap_or(this.channels.len());
+        let channel_index = first_with_free_slots.unwrap_or(self.channels.len());
 
         // query the maximum number of segments we can fill at this point in time
         //
         // NOTE: this value could be lowered in the case the TX task encounters an error with GSO
         //       so we do need to query it each iteration.
-        let max_segments = this.gso.max_segments();
+        let max_segments = self.gso.max_segments();
+
+        let routes = self
+            .routes
+            .iter_mut()
+            .map(|route| (route.local_address, route.tx.new_queue()))
+            .collect();
 
-        let mut queue = TxQueue {
-            channels: &mut this.channels,
+        TxQueue {
+            channels: &mut self.channels,
             channel_index,
             message_index: 0,
             pending_release: 0,
             gso_segment: None,
             max_segments,
-            max_mtu: this.max_mtu,
+            max_mtu: self.max_mtu,
+            max_delay: self.max_delay,
             capacity,
-            is_full: &mut this.is_full,
+            is_full: &mut self.is_full,
+            routes,
+        }
+    }
+}
+
+impl<T: Message> tx::Tx for Tx<T> {
+    type PathHandle = T::Handle;
+    type Queue = TxQueue<'static, T>;
+    type Error = ();
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        let mut outcome = self.poll_capacity(cx);
+
+        // the sockets bound to additional addresses can also wake the endpoint up, but only
+        // the primary channels closing shuts the task down
+        for route in &mut self.routes {
+            let is_ready = matches!(route.tx.poll_capacity(cx), Poll::Ready(Ok(())));
+            if is_ready && outcome.is_pending() {
+                outcome = Poll::Ready(Ok(()));
+            }
+        }
+
+        outcome
+    }
+
+    #[inline]
+    fn queue<F: FnOnce(&mut Self::Queue)>(&mut self, f: F) {
+        let this: &'static mut Self = unsafe {
+            // Safety: As noted in the [transmute examples](https://doc.rust-lang.org/std/mem/fn.transmute.html#examples)
+            // it can be used to temporarily extend the lifetime of a reference. In this case, we
+            // don't want to use GATs until the MSRV is >=1.65.0, which means `Self::Queue` is not
+            // allowed to take generic lifetimes.
+            //
+            // We are left with using a `'static` lifetime here and encapsulating it in a private
+            // field. The `Self::Queue` struct is then borrowed for the lifetime of the `F`
+            // function. This will prevent the value from escaping beyond the lifetime of `&mut
+            // self`.
+            //
+            // See https://play.rust-lang.org/?version=stable&mode=debug&edition=2021&gist=9a32abe85c666f36fb2ec86496cc41b4
+            //
+            // Once https://github.com/aws/s2n-quic/issues/1742 is resolved this code can go away
+            core::mem::transmute(self)
         };
 
+        let mut queue = this.new_queue();
+
         f(&mut queue);
     }
 
@@ -159,6 +228,11 @@ pub struct GsoSegment<Handle> {
     /// This is used to determine if future messages should be included in this payload or need a
     /// separate packet.
     ecn: ExplicitCongestionNotification,
+    /// The delay before the current GSO segment is transmitted
+    ///
+    /// All of the segments are transmitted at the same time, so messages with a different delay
+    /// need a separate packet.
+    delay: Duration,
     /// The number of segments that have been written
     count: usize,
     /// The size of each segment.
@@ -189,14 +263,46 @@ pub struct TxQueue<'a, T: Message> {
     max_segments: usize,
     /// The maximum MTU for any given packet
     max_mtu: usize,
+    /// The maximum delay before a message is transmitted, if transmit times are enabled
+    max_delay: Duration,
     /// The maximum number of packets that can be sent in the current iteration
     capacity: usize,
     /// Used to track if we have filled up the producer queue and waiting on free slots to be
     /// released by the consumer.
     is_full: &'a mut bool,
+    /// The queues for the sockets bound to additional local addresses
+    routes: Vec<(SocketAddress, TxQueue<'a, T>)>,
 }
 
-impl<T: Message> TxQueue<'_, T> {
+impl<'a, T: Message> TxQueue<'a, T> {
+    /// Returns the queue of the socket bound to the local address of the message, if any
+    #[inline]
+    fn route(&mut self, handle: &T::Handle) -> Option<&mut TxQueue<'a, T>> {
+        if self.routes.is_empty() {
+            return None;
+        }
+
+        let local_address = handle.local_address().0;
+        self.routes
+            .iter_mut()
+            .find(|(route, _)| {
+                // a socket bound to an unspecified IP can send from any of the host's addresses
+                route.port() == local_address.port()
+                    && (route.ip().is_unspecified() || route.ip().unmapped_eq(&local_address.ip()))
+            })
+            .map(|(_, queue)| queue)
+    }
+
+    /// Returns the delay before the message should be transmitted
+    #[inline]
+    fn delay<M: tx::Message<Handle = T::Handle>>(&self, message: &mut M) -> Duration {
+        if T::SUPPORTS_TXTIME && !self.max_delay.is_zero() {
+            message.delay().min(self.max_delay)
+        } else {
+            Duration::ZERO
+        }
+    }
+
     /// Tries to send a message as a GSO segment
     ///
     /// Returns the Err(Message) if it was not able to. Otherwise, the index of the GSO'd message is returned.
@@ -204,6 +310,7 @@ impl<T: Message> TxQueue<'_, T> {
     fn try_gso<M: tx::Message<Handle = T::Handle>>(
         &mut self,
         mut message: M,
+        delay: Duration,
     ) -> Result<Result<tx::Outcome, M>, tx::Error> {
         // the message doesn't support GSO to return it
         if !T::SUPPORTS_GSO {
@@ -227,7 +334,8 @@ impl<T: Message> TxQueue<'_, T> {
         // GSO payload as the previous message
         let can_gso = message.can_gso(gso.size, gso.count)
             && message.path_handle().strict_eq(&gso.handle)
-            && message.ecn() == gso.ecn;
+            && message.ecn() == gso.ecn
+            && delay == gso.delay;
 
         // if we can't use GSO then flush the current message
         if !can_gso {
@@ -386,15 +494,25 @@ impl<T: Message> tx::Queue for TxQueue<'_, T> {
     type Handle = T::Handle;
 
     const SUPPORTS_ECN: bool = T::SUPPORTS_ECN;
+    const SUPPORTS_PACING: bool = T::SUPPORTS_TXTIME;
     const SUPPORTS_FLOW_LABELS: bool = T::SUPPORTS_FLOW_LABELS;
 
     #[inline]
-    fn push<M>(&mut self, message: M) -> Result<tx::Outcome, tx::Error>
+    fn push<M>(&mut self, mut message: M) -> Result<tx::Outcome, tx::Error>
     where
         M: tx::Message<Handle = Self::Handle>,
     {
+        // messages sent from an additional address go out on the socket bound to it
+        if let Some(queue) = self.route(message.path_handle()) {
+            return queue.push(message);
+        }
+
+        // query the delay before any payloads are written, which can move the departure time of
+        // the next message
+        let delay = self.delay(&mut message);
+
         // first try to write a GSO payload, if supported
-        let mut message = match self.try_gso(message)? {
+        let mut message = match self.try_gso(message, delay)? {
             Ok(outcome) => return Ok(outcome),
             Err(message) => message,
         };
@@ -429,12 +547,17 @@ impl<T: Message> tx::Queue for TxQueue<'_, T> {
         // write the message to the entry
         let payload_len = entry.tx_write(message)?;
 
+        if !delay.is_zero() {
+            entry.set_transmit_time(txtime::transmit_time(delay));
+        }
+
         // if GSO is supported and we are allowed to have additional segments, store the GSO state
         // for another potential message to be written later
         if T::SUPPORTS_GSO && self.max_segments > 1 && can_gso {
             self.gso_segment = Some(GsoSegment {
                 handle,
                 ecn,
+                delay,
                 count: 1,
                 size: payload_len,
             });
@@ -456,11 +579,25 @@ impl<T: Message> tx::Queue for TxQueue<'_, T> {
     fn flush(&mut self) {
         // flush GSO segments between connections
         self.flush_gso();
+
+        for (_, queue) in &mut self.routes {
+            queue.flush();
+        }
     }
 
     #[inline]
     fn capacity(&self) -> usize {
         self.capacity
+            + self
+                .routes
+                .iter()
+                .map(|(_, queue)| queue.capacity)
+                .sum::<usize>()
+    }
+
+    #[inline]
+    fn max_delay(&self) -> Duration {
+        self.max_delay
     }
 }
 
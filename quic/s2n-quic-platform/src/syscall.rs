@@ -31,6 +31,8 @@ pub mod msg;
 pub enum SocketType {
     Blocking,
     NonBlocking,
+    /// A non-blocking socket of which only the error queue is read
+    ErrorQueue,
 }
 
 pub trait SocketEvents {
@@ -49,6 +51,19 @@ pub trait SocketEvents {
     fn on_error(&mut self, error: io::Error) -> ControlFlow<(), ()>;
 }
 
+/// Returns true if the error was reported for a datagram previously sent on the socket
+///
+/// If `IP_RECVERR` is enabled, the details of the error can be read from the socket error queue.
+#[cfg(unix)]
+#[inline]
+#[allow(dead_code)] // this isn't used on some platforms
+fn is_pending_error(error: &io::Error) -> bool {
+    !matches!(
+        error.kind(),
+        io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted
+    )
+}
+
 #[cfg(unix)]
 pub trait UnixMessage: crate::message::Message {
     fn send<E: SocketEvents>(
@@ -245,6 +260,67 @@ pub fn configure_pktinfo(rx_socket: &Socket) -> bool {
     success
 }
 
+/// Configures the socket to queue ICMP errors for sent datagrams so they can be read
+/// from the socket error queue
+pub fn configure_recverr(rx_socket: &Socket) -> bool {
+    let mut success = false;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::io::AsRawFd;
+        let enabled: libc::c_int = 1;
+
+        if let Some((level, ty)) = crate::features::recverr::SOCKOPT_V4 {
+            success |= libc!(setsockopt(
+                rx_socket.as_raw_fd(),
+                level,
+                ty,
+                &enabled as *const _ as _,
+                core::mem::size_of_val(&enabled) as _,
+            ))
+            .is_ok();
+        }
+
+        if let Some((level, ty)) = crate::features::recverr::SOCKOPT_V6 {
+            success |= libc!(setsockopt(
+                rx_socket.as_raw_fd(),
+                level,
+                ty,
+                &enabled as *const _ as _,
+                core::mem::size_of_val(&enabled) as _,
+            ))
+            .is_ok();
+        }
+    }
+
+    success
+}
+
+/// Configures the socket to accept transmit times with each message
+///
+/// The times are measured with the monotonic clock and only honored if the egress interface uses
+/// a qdisc which supports them, such as `fq`.
+pub fn configure_txtime(tx_socket: &Socket) -> bool {
+    let mut success = false;
+
+    #[cfg(unix)]
+    if let Some((level, ty)) = crate::features::txtime::SOCKOPT {
+        use std::os::unix::io::AsRawFd;
+        let value = crate::features::txtime::SOCKOPT_VALUE;
+
+        success |= libc!(setsockopt(
+            tx_socket.as_raw_fd(),
+            level as _,
+            ty as _,
+            &value as *const _ as _,
+            core::mem::size_of_val(&value) as _
+        ))
+        .is_ok();
+    }
+
+    success
+}
+
 pub fn configure_gro(rx_socket: &Socket) -> bool {
     let mut success = false;
 
@@ -265,3 +341,38 @@ pub fn configure_gro(rx_socket: &Socket) -> bool {
 
     success
 }
+
+/// Pins the calling thread to one of the CPUs the process is allowed to run on
+///
+/// The CPU is chosen by `index`, wrapping around the allowed set. Pinning is only supported on
+/// Linux; `false` is returned on other platforms or if the affinity can't be set.
+pub fn pin_thread_to_cpu(index: usize) -> bool {
+    let mut success = false;
+
+    #[cfg(target_os = "linux")]
+    {
+        let size = core::mem::size_of::<libc::cpu_set_t>();
+        // SAFETY: `cpu_set_t` is a plain bitmask which is valid when zeroed
+        let mut allowed: libc::cpu_set_t = unsafe { core::mem::zeroed() };
+
+        if libc!(sched_getaffinity(0, size, &mut allowed)).is_err() {
+            return false;
+        }
+
+        // find the `index`th allowed CPU, wrapping around the number of allowed CPUs
+        let allowed: Vec<usize> = (0..libc::CPU_SETSIZE as usize)
+            .filter(|cpu| unsafe { libc::CPU_ISSET(*cpu, &allowed) })
+            .collect();
+
+        if let Some(cpu) = allowed.get(index.checked_rem(allowed.len()).unwrap_or(0)) {
+            let mut set: libc::cpu_set_t = unsafe { core::mem::zeroed() };
+            unsafe { libc::CPU_SET(*cpu, &mut set) };
+            success = libc!(sched_setaffinity(0, size, &set)).is_ok();
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    let _ = index;
+
+    success
+}
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{SocketEvents, SocketType, UnixMessage};
-use crate::socket::stats;
+use crate::{features, socket::stats};
 use libc::mmsghdr;
 use std::os::unix::io::{AsRawFd, RawFd};
 
@@ -127,11 +127,12 @@ pub fn recv<Sock: AsRawFd, E: SocketEvents>(
     let flags = match socket_type {
         SocketType::Blocking => libc::MSG_WAITFORONE,
         SocketType::NonBlocking => libc::MSG_DONTWAIT,
+        SocketType::ErrorQueue => libc::MSG_DONTWAIT | features::recverr::MSG_ERRQUEUE,
     };
 
     // some platforms have a mismatch in types for the flag values and the actual parameter so cast
     // it to whatever type the syscall expects.
-    let flags = flags as _;
+    let syscall_flags = flags as _;
 
     // > The timeout argument points to a struct timespec defining a timeout
     // > (seconds plus nanoseconds) for the receive operation.
@@ -158,7 +159,17 @@ pub fn recv<Sock: AsRawFd, E: SocketEvents>(
     // > On success, recvmmsg() returns the number of messages received in
     // > msgvec; on error, -1 is returned, and errno is set to indicate the error.
 
-    let res = libc!(recvmmsg(sockfd, msgvec, vlen, flags, timeout));
+    let mut res = libc!(recvmmsg(sockfd, msgvec, vlen, syscall_flags, timeout));
+
+    // > If IP_RECVERR is enabled, all errors are stored in the socket error queue,
+    // > and can be received by recvmsg(2) with the MSG_ERRQUEUE flag set.
+    //
+    // A pending error is reported by failing the call, so read the error queue instead. Reading
+    // the error queue never blocks.
+    if features::recverr::IS_SUPPORTED && matches!(&res, Err(err) if super::is_pending_error(err)) {
+        let syscall_flags = (flags | features::recverr::MSG_ERRQUEUE) as _;
+        res = libc!(recvmmsg(sockfd, msgvec, vlen, syscall_flags, timeout));
+    }
 
     stats.recv().on_operation_result(&res, |count| *count as _);
 
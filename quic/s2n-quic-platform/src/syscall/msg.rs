@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{SocketEvents, SocketType, UnixMessage};
-use crate::{message::Message as _, socket::stats};
+use crate::{features, message::Message as _, socket::stats};
 use libc::msghdr;
 use std::os::unix::io::{AsRawFd, RawFd};
 
@@ -117,6 +117,7 @@ pub fn recv<'a, Sock: AsRawFd, P: IntoIterator<Item = &'a mut msghdr>, E: Socket
     let mut flags = match socket_type {
         SocketType::Blocking => Default::default(),
         SocketType::NonBlocking => libc::MSG_DONTWAIT,
+        SocketType::ErrorQueue => libc::MSG_DONTWAIT | features::recverr::MSG_ERRQUEUE,
     };
 
     for packet in packets {
@@ -154,7 +155,31 @@ pub fn recv<'a, Sock: AsRawFd, P: IntoIterator<Item = &'a mut msghdr>, E: Socket
         // > discarded depending on the type of socket the message is received from.
         //
         // > These calls return the number of bytes received, or -1 if an error occurred.
-        let result = libc!(recvmsg(sockfd, msg, flags));
+        let mut result = libc!(recvmsg(sockfd, msg, flags));
+
+        if features::recverr::IS_SUPPORTED {
+            let errqueue = features::recverr::MSG_ERRQUEUE;
+
+            // > If IP_RECVERR is enabled, all errors are stored in the socket error queue,
+            // > and can be received by recvmsg(2) with the MSG_ERRQUEUE flag set.
+            //
+            // A pending error is reported by failing the call, so start reading the error queue
+            if flags & errqueue == 0 && matches!(&result, Err(err) if super::is_pending_error(err))
+            {
+                flags |= errqueue;
+                result = libc!(recvmsg(sockfd, msg, flags));
+            }
+
+            // Reading the error queue never blocks, so go back to reading datagrams once it's
+            // been drained, unless only the error queue is being read
+            if flags & errqueue != 0
+                && socket_type != SocketType::ErrorQueue
+                && matches!(&result, Err(err) if err.kind() == std::io::ErrorKind::WouldBlock)
+            {
+                flags &= !errqueue;
+                result = libc!(recvmsg(sockfd, msg, flags));
+            }
+        }
 
         #[cfg(debug_assertions)]
         {
@@ -182,6 +207,6 @@ pub fn recv<'a, Sock: AsRawFd, P: IntoIterator<Item = &'a mut msghdr>, E: Socket
         }
 
         // don't block the follow-up calls
-        flags = libc::MSG_DONTWAIT;
+        flags |= libc::MSG_DONTWAIT;
     }
 }
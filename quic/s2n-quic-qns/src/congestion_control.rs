@@ -2,33 +2,42 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use core::str::FromStr;
+use s2n_quic_core::recovery::congestion_controller::registry;
 use std::io;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 pub struct CongestionControl {
     /// The congestion controller to use
-    #[structopt(long = "cc", default_value = "bbr", possible_values = &["cubic","bbr"])]
+    #[structopt(long = "cc", default_value = "bbr", possible_values = registry::NAMES)]
     pub congestion_controller: CongestionController,
 }
 
+/// The name of a congestion controller in the registry
 #[derive(Copy, Clone, Debug)]
-pub enum CongestionController {
-    Cubic,
-    Bbr,
+pub struct CongestionController(&'static str);
+
+impl CongestionController {
+    /// Calls the `visitor` with the selected congestion controller endpoint
+    pub fn visit<V: registry::Visitor>(self, visitor: V) -> V::Output {
+        registry::visit(self.0, visitor).expect("the name was validated when parsed")
+    }
 }
 
 impl FromStr for CongestionController {
     type Err = crate::Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "cubic" => Ok(Self::Cubic),
-            "bbr" => Ok(Self::Bbr),
-            _ => Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                format!("Unsupported congestion controller: {s}"),
-            )
-            .into()),
-        }
+        registry::NAMES
+            .iter()
+            .copied()
+            .find(|name| *name == s)
+            .map(Self)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Unsupported congestion controller: {s}"),
+                )
+                .into()
+            })
     }
 }
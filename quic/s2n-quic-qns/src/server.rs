@@ -11,17 +11,9 @@ mod unstable;
 pub use interop::Interop;
 pub use perf::Perf;
 
-use crate::{
-    congestion_control::{CongestionControl, CongestionController::*},
-    tls,
-    tls::TlsProviders::*,
-    Result,
-};
-use s2n_quic::{
-    provider::congestion_controller::{Bbr, Cubic},
-    server,
-    server::ServerProviders,
-};
+use crate::{congestion_control::CongestionControl, tls, tls::TlsProviders::*, Result};
+use s2n_quic::{server, server::ServerProviders};
+use s2n_quic_core::recovery::congestion_controller::{registry::Visitor, Endpoint};
 
 /// Build and start a server with the given TLS configuration and Congestion Controller
 pub fn build(
@@ -30,30 +22,48 @@ pub fn build(
     tls_server: &tls::Server,
     congestion_control: &CongestionControl,
 ) -> Result<s2n_quic::Server> {
-    macro_rules! build {
-        ($build_tls:ident, $cc:ident $(, $alpns:ident)?) => {
-            {
-                let tls = tls_server.$build_tls($($alpns)?)?;
-
-                builder
-                    .with_tls(tls)?
-                    .with_congestion_controller($cc::default())?
-                    .start()
-                    .unwrap()
+    congestion_control.congestion_controller.visit(Build {
+        builder,
+        alpns,
+        tls_server,
+    })
+}
+
+struct Build<'a, Providers> {
+    builder: server::Builder<Providers>,
+    alpns: &'a [String],
+    tls_server: &'a tls::Server,
+}
+
+impl<Providers: ServerProviders> Visitor for Build<'_, Providers> {
+    type Output = Result<s2n_quic::Server>;
+
+    fn visit<E: Endpoint>(self, congestion_controller: E) -> Self::Output {
+        let Self {
+            builder,
+            alpns,
+            tls_server,
+        } = self;
+        let builder = builder.with_congestion_controller(congestion_controller)?;
+
+        macro_rules! build {
+            ($build_tls:ident $(, $alpns:ident)?) => {
+                {
+                    let tls = tls_server.$build_tls($($alpns)?)?;
+
+                    builder
+                        .with_tls(tls)?
+                        .start()
+                        .unwrap()
+                }
             }
         }
-    }
 
-    Ok(
-        match (tls_server.tls, congestion_control.congestion_controller) {
+        Ok(match tls_server.tls {
             #[cfg(unix)]
-            (S2N, Cubic) => build!(build_s2n_tls, Cubic, alpns),
-            #[cfg(unix)]
-            (S2N, Bbr) => build!(build_s2n_tls, Bbr, alpns),
-            (Rustls, Cubic) => build!(build_rustls, Cubic, alpns),
-            (Rustls, Bbr) => build!(build_rustls, Bbr, alpns),
-            (Null, Cubic) => build!(build_null, Cubic),
-            (Null, Bbr) => build!(build_null, Bbr),
-        },
-    )
+            S2N => build!(build_s2n_tls, alpns),
+            Rustls => build!(build_rustls, alpns),
+            Null => build!(build_null),
+        })
+    }
 }
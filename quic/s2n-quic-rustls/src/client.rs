@@ -87,26 +87,37 @@ impl tls::Endpoint for Client {
         let rustls_server_name = rustls::pki_types::ServerName::try_from(server_name.to_string())
             .expect("invalid server name");
 
-        let session = rustls::quic::ClientConnection::new(
-            self.config.clone(),
-            crate::QUIC_VERSION,
-            rustls_server_name,
-            transport_parameters,
-        )
-        .expect("could not create rustls client session");
-
-        Session::new(session.into(), Some(server_name))
+        let config = self.config.clone();
+        let connection_init = Box::new(move |quic_version| {
+            rustls::quic::ClientConnection::new(
+                config,
+                quic_version,
+                rustls_server_name,
+                transport_parameters,
+            )
+            .expect("could not create rustls client session")
+            .into()
+        });
+
+        Session::new(connection_init, Some(server_name))
     }
 
     fn max_tag_length(&self) -> usize {
         s2n_quic_crypto::MAX_TAG_LEN
     }
+
+    fn supports_compatible_version_negotiation(&self) -> bool {
+        // rustls derives the handshake keys with the QUIC version the connection was
+        // created with, so it isn't able to switch versions after the ClientHello.
+        false
+    }
 }
 
 pub struct Builder {
     cert_store: rustls::RootCertStore,
     application_protocols: Vec<Vec<u8>>,
     key_log: Option<Arc<dyn rustls::KeyLog>>,
+    early_data: bool,
 }
 
 impl Default for Builder {
@@ -121,6 +132,7 @@ impl Builder {
             cert_store: rustls::RootCertStore::empty(),
             application_protocols: vec![b"h3".to_vec()],
             key_log: None,
+            early_data: false,
         }
     }
 
@@ -157,6 +169,16 @@ impl Builder {
         Ok(self)
     }
 
+    /// If enabled, the client sends 0-RTT early data when resuming a session with a
+    /// server that allows it (default: false)
+    ///
+    /// Early data can be replayed by an attacker, so applications should only send
+    /// idempotent requests before the handshake completes.
+    pub fn with_early_data(mut self, enabled: bool) -> Result<Self, Error> {
+        self.early_data = enabled;
+        Ok(self)
+    }
+
     pub fn build(self) -> Result<Client, Error> {
         // TODO load system root store?
         if self.cert_store.is_empty() {
@@ -173,6 +195,7 @@ impl Builder {
 
         config.max_fragment_size = None;
         config.alpn_protocols = self.application_protocols;
+        config.enable_early_data = self.early_data;
 
         if let Some(key_log) = self.key_log {
             config.key_log = key_log;
@@ -0,0 +1,92 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Server-side policy for accepting 0-RTT early data
+
+use core::fmt;
+use rustls::server::{ServerSessionMemoryCache, StoresServerSessions};
+use std::sync::Arc;
+
+/// The number of sessions stored by the server for resumption
+const SESSION_CACHE_SIZE: usize = 256;
+
+//= https://www.rfc-editor.org/rfc/rfc9001#section-9.2
+//# Disabling 0-RTT entirely is the most effective
+//# defense against replay attack.
+
+/// Decides if a client resuming a session is allowed to send early data
+///
+/// Early data is not protected against replay by the TLS handshake. Each session ticket
+/// can only be used once by a single server, but applications running several servers
+/// need to share the state of used tickets between them to prevent replays.
+pub trait AntiReplay: 'static + Send + Sync {
+    /// Called when a client presents a session ticket
+    ///
+    /// Returning `false` rejects the ticket, which causes the client to fall back to a full
+    /// handshake and to retransmit any early data it sent once the handshake completes.
+    fn on_ticket(&self, ticket_id: &[u8]) -> bool;
+}
+
+impl<F: 'static + Send + Sync + Fn(&[u8]) -> bool> AntiReplay for F {
+    #[inline]
+    fn on_ticket(&self, ticket_id: &[u8]) -> bool {
+        (self)(ticket_id)
+    }
+}
+
+/// Only relies on the single-use session tickets of the local server to prevent replays
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SingleUseTickets;
+
+impl AntiReplay for SingleUseTickets {
+    #[inline]
+    fn on_ticket(&self, _ticket_id: &[u8]) -> bool {
+        true
+    }
+}
+
+/// Stores sessions for resumption and consults the [`AntiReplay`] policy before handing
+/// out a session
+pub(crate) struct SessionStore {
+    sessions: Arc<ServerSessionMemoryCache>,
+    anti_replay: Box<dyn AntiReplay>,
+}
+
+impl SessionStore {
+    pub(crate) fn new(anti_replay: Box<dyn AntiReplay>) -> Self {
+        Self {
+            sessions: ServerSessionMemoryCache::new(SESSION_CACHE_SIZE),
+            anti_replay,
+        }
+    }
+}
+
+impl fmt::Debug for SessionStore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SessionStore")
+            .field("sessions", &self.sessions)
+            .finish()
+    }
+}
+
+impl StoresServerSessions for SessionStore {
+    fn put(&self, key: Vec<u8>, value: Vec<u8>) -> bool {
+        self.sessions.put(key, value)
+    }
+
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.sessions.get(key)
+    }
+
+    fn take(&self, key: &[u8]) -> Option<Vec<u8>> {
+        if !self.anti_replay.on_ticket(key) {
+            return None;
+        }
+
+        self.sessions.take(key)
+    }
+
+    fn can_cache(&self) -> bool {
+        self.sessions.can_cache()
+    }
+}
@@ -29,6 +29,7 @@ mod session;
 
 pub mod certificate;
 pub mod client;
+pub mod early_data;
 pub mod server;
 
 pub use client::Client;
@@ -38,8 +39,14 @@ pub use server::Server;
 //# Clients MUST NOT offer TLS versions older than 1.3.
 static PROTOCOL_VERSIONS: &[&rustls::SupportedProtocolVersion] = &[&rustls::version::TLS13];
 
-/// The supported version of quic
-const QUIC_VERSION: rustls::quic::Version = rustls::quic::Version::V1;
+/// Maps the QUIC version of a connection to the rustls version
+fn quic_version(version: u32) -> rustls::quic::Version {
+    if version == s2n_quic_core::version::V2 {
+        rustls::quic::Version::V2
+    } else {
+        rustls::quic::Version::V1
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -1,7 +1,9 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{certificate, cipher_suite::default_crypto_provider, session::Session, Error};
+use crate::{
+    certificate, cipher_suite::default_crypto_provider, early_data, session::Session, Error,
+};
 use rustls::{crypto::aws_lc_rs, ConfigBuilder, ServerConfig, WantsVerifier};
 use s2n_codec::EncoderValue;
 use s2n_quic_core::{application::ServerName, crypto::tls};
@@ -74,14 +76,14 @@ impl tls::Endpoint for Server {
         //# Endpoints MUST send the quic_transport_parameters extension;
         let transport_parameters = transport_parameters.encode_to_vec();
 
-        let session = rustls::quic::ServerConnection::new(
-            self.config.clone(),
-            crate::QUIC_VERSION,
-            transport_parameters,
-        )
-        .expect("could not create rustls server session");
+        let config = self.config.clone();
+        let connection_init = Box::new(move |quic_version| {
+            rustls::quic::ServerConnection::new(config, quic_version, transport_parameters)
+                .expect("could not create rustls server session")
+                .into()
+        });
 
-        Session::new(session.into(), None)
+        Session::new(connection_init, None)
     }
 
     fn new_client_session<Params: EncoderValue>(
@@ -95,6 +97,12 @@ impl tls::Endpoint for Server {
     fn max_tag_length(&self) -> usize {
         s2n_quic_crypto::MAX_TAG_LEN
     }
+
+    fn supports_compatible_version_negotiation(&self) -> bool {
+        // rustls derives the handshake keys with the QUIC version the connection was
+        // created with, so it isn't able to switch versions after the ClientHello.
+        false
+    }
 }
 
 pub struct Builder {
@@ -102,6 +110,7 @@ pub struct Builder {
     application_protocols: Vec<Vec<u8>>,
     key_log: Option<Arc<dyn rustls::KeyLog>>,
     prefer_server_cipher_suite_order: bool,
+    anti_replay: Option<Box<dyn early_data::AntiReplay>>,
 }
 
 impl Default for Builder {
@@ -117,6 +126,7 @@ impl Builder {
             application_protocols: vec![b"h3".to_vec()],
             key_log: None,
             prefer_server_cipher_suite_order: true,
+            anti_replay: None,
         }
     }
 
@@ -162,6 +172,19 @@ impl Builder {
         Ok(self)
     }
 
+    /// Enables accepting 0-RTT early data from clients resuming a session (default: disabled)
+    ///
+    /// Early data can be replayed by an attacker, so the `anti_replay` policy is consulted
+    /// before a session ticket is accepted. [`early_data::SingleUseTickets`] is sufficient
+    /// for applications running a single server.
+    pub fn with_early_data<A: early_data::AntiReplay>(
+        mut self,
+        anti_replay: A,
+    ) -> Result<Self, Error> {
+        self.anti_replay = Some(Box::new(anti_replay));
+        Ok(self)
+    }
+
     pub fn build(self) -> Result<Server, Error> {
         let builder = default_config_builder()?.with_no_client_auth();
 
@@ -182,6 +205,15 @@ impl Builder {
             config.key_log = key_log;
         }
 
+        if let Some(anti_replay) = self.anti_replay {
+            //= https://www.rfc-editor.org/rfc/rfc9001#section-4.6.1
+            //# Servers MUST NOT send
+            //# the early_data extension with a max_early_data_size field set to any
+            //# value other than 0xffffffff.
+            config.max_early_data_size = u32::MAX;
+            config.session_storage = Arc::new(early_data::SessionStore::new(anti_replay));
+        }
+
         #[allow(deprecated)]
         Ok(Server::new(config))
     }
@@ -16,8 +16,12 @@ use s2n_quic_core::{
     transport,
 };
 
+/// Creates the rustls connection once the QUIC version of the connection is known
+pub type ConnectionInit = Box<dyn FnOnce(quic::Version) -> Connection + Send>;
+
 pub struct Session {
-    connection: Connection,
+    connection: Option<Connection>,
+    connection_init: Option<ConnectionInit>,
     rx_phase: HandshakePhase,
     tx_phase: HandshakePhase,
     emitted_zero_rtt_keys: bool,
@@ -35,17 +39,22 @@ impl tls::TlsSession for Session {
         context: &[u8],
         output: &mut [u8],
     ) -> Result<(), tls::TlsExportError> {
-        match self
+        let connection = self
             .connection
-            .export_keying_material(output, label, Some(context))
-        {
+            .as_ref()
+            .ok_or_else(tls::TlsExportError::failure)?;
+        match connection.export_keying_material(output, label, Some(context)) {
             Ok(_) => Ok(()),
             Err(_) => Err(tls::TlsExportError::failure()),
         }
     }
 
     fn cipher_suite(&self) -> CipherSuite {
-        if let Some(rustls_cipher_suite) = self.connection.negotiated_cipher_suite() {
+        let cipher_suite = self
+            .connection
+            .as_ref()
+            .and_then(|connection| connection.negotiated_cipher_suite());
+        if let Some(rustls_cipher_suite) = cipher_suite {
             match rustls_cipher_suite.suite() {
                 rustls::CipherSuite::TLS13_AES_128_GCM_SHA256 => {
                     CipherSuite::TLS_AES_128_GCM_SHA256
@@ -64,11 +73,13 @@ impl tls::TlsSession for Session {
     }
 
     fn peer_cert_chain_der(&self) -> Result<Vec<Vec<u8>>, tls::ChainError> {
-        let err = tls::ChainError::failure();
+        let err = tls::ChainError::failure;
         Ok(self
             .connection
+            .as_ref()
+            .ok_or_else(err)?
             .peer_certificates()
-            .ok_or(err)?
+            .ok_or_else(err)?
             .iter()
             .map(|v| v.to_vec())
             .collect())
@@ -85,9 +96,10 @@ impl fmt::Debug for Session {
 }
 
 impl Session {
-    pub fn new(connection: Connection, server_name: Option<ServerName>) -> Self {
+    pub fn new(connection_init: ConnectionInit, server_name: Option<ServerName>) -> Self {
         Self {
-            connection,
+            connection: None,
+            connection_init: Some(connection_init),
             rx_phase: Default::default(),
             tx_phase: Default::default(),
             emitted_zero_rtt_keys: false,
@@ -99,8 +111,32 @@ impl Session {
         }
     }
 
-    fn receive(&mut self, crypto_data: &[u8]) -> Result<(), transport::Error> {
+    /// Returns the rustls connection, creating it for the QUIC version in use if needed
+    ///
+    /// The rustls connection derives packet protection keys with version-specific
+    /// labels, so it can't be created until the version is known.
+    fn connection_mut(&mut self, quic_version: u32) -> &mut Connection {
+        let connection_init = &mut self.connection_init;
+        self.connection.get_or_insert_with(|| {
+            let connection_init = connection_init
+                .take()
+                .expect("connection should only be initialized once");
+            connection_init(crate::quic_version(quic_version))
+        })
+    }
+
+    fn connection(&self) -> &Connection {
         self.connection
+            .as_ref()
+            .expect("connection is initialized on the first poll")
+    }
+
+    fn receive(&mut self, crypto_data: &[u8]) -> Result<(), transport::Error> {
+        let connection = self
+            .connection
+            .as_mut()
+            .expect("connection is initialized on the first poll");
+        connection
             .read_hs(crypto_data)
             .map_err(crate::error::reason)
             .map_err(|reason| {
@@ -116,7 +152,7 @@ impl Session {
                 // > https://docs.rs/rustls/0.19.0/rustls/quic/trait.QuicExt#tymethod.get_alert
                 // > Emit the TLS description code of a fatal alert, if one has arisen.
 
-                self.connection
+                connection
                     .alert()
                     .map(|alert| {
                         // Explicitly annotate the type to detect if rustls starts
@@ -138,9 +174,11 @@ impl Session {
         //# error of type 0x16d (equivalent to a fatal TLS missing_extension
         //# alert, see Section 4.8).
         let transport_parameters =
-            self.connection.quic_transport_parameters().ok_or_else(|| {
-                tls::Error::MISSING_EXTENSION.with_reason("Missing QUIC transport parameters")
-            })?;
+            self.connection()
+                .quic_transport_parameters()
+                .ok_or_else(|| {
+                    tls::Error::MISSING_EXTENSION.with_reason("Missing QUIC transport parameters")
+                })?;
 
         Ok(tls::ApplicationParameters {
             transport_parameters,
@@ -164,11 +202,11 @@ impl Session {
     //#    Finished                        -------->
     //#    Application Data                <------->       Application Data
     fn application_protocol(&self) -> Option<&[u8]> {
-        self.connection.alpn_protocol()
+        self.connection.as_ref()?.alpn_protocol()
     }
 
     fn server_name(&self) -> Option<ServerName> {
-        match &self.connection {
+        match self.connection.as_ref()? {
             Connection::Client(_) => self.server_name.clone(),
             Connection::Server(server) => {
                 server.server_name().map(|server_name| server_name.into())
@@ -179,6 +217,7 @@ impl Session {
     fn key_exchange_group(&self) -> Option<NamedGroup> {
         let group = self
             .connection
+            .as_ref()?
             .negotiated_key_exchange_group()
             .and_then(|group| group.name().as_str())?;
 
@@ -195,11 +234,21 @@ impl Session {
             return None;
         }
 
-        let keys = self.connection.zero_rtt_keys()?;
+        let keys = self.connection().zero_rtt_keys()?;
         self.emitted_zero_rtt_keys = true;
         Some(keys)
     }
 
+    /// Returns `true` if the client sent early data and the server rejected it
+    fn is_zero_rtt_rejected(&self) -> bool {
+        match self.connection() {
+            Connection::Client(connection) => {
+                self.emitted_zero_rtt_keys && !connection.is_early_data_accepted()
+            }
+            Connection::Server(_) => false,
+        }
+    }
+
     /// Check and process TLS handshake complete.
     ///
     /// Upon TLS handshake complete, emit an event to notify the transport layer.
@@ -207,7 +256,7 @@ impl Session {
         &mut self,
         context: &mut C,
     ) -> Poll<Result<(), transport::Error>> {
-        if self.tx_phase == HandshakePhase::Application && !self.connection.is_handshaking() {
+        if self.tx_phase == HandshakePhase::Application && !self.connection().is_handshaking() {
             // attempt to emit server_name and application_protocol events prior to completing the
             // handshake
             self.emit_events(context)?;
@@ -233,6 +282,8 @@ impl Session {
         &mut self,
         context: &mut C,
     ) -> Poll<Result<(), transport::Error>> {
+        self.connection_mut(context.quic_version());
+
         // Tracks if we have attempted to receive data at least once
         let mut has_tried_receive = false;
 
@@ -261,10 +312,9 @@ impl Session {
 
             // try to pull out the early secrets, if any
             if let Some(keys) = self.zero_rtt_keys() {
-                let (key, header_key) = PacketKey::new(
-                    keys,
-                    s2n_quic_core::crypto::tls::CipherSuite::TLS_AES_128_GCM_SHA256,
-                );
+                // the cipher suite is the one used by the session being resumed
+                let cipher_suite = tls::TlsSession::cipher_suite(self);
+                let (key, header_key) = PacketKey::new(keys, cipher_suite);
                 context.on_zero_rtt_keys(key, header_key, self.application_parameters()?)?;
             }
 
@@ -282,7 +332,9 @@ impl Session {
 
                 let mut transmission_buffer = vec![];
 
-                let key_change = self.connection.write_hs(&mut transmission_buffer);
+                let key_change = self
+                    .connection_mut(context.quic_version())
+                    .write_hs(&mut transmission_buffer);
 
                 // if we didn't upgrade the key or transmit anything then we're waiting for
                 // more reads
@@ -301,7 +353,7 @@ impl Session {
 
                 if let Some(key_change) = key_change {
                     let cipher_suite = self
-                        .connection
+                        .connection()
                         .negotiated_cipher_suite()
                         .expect("cipher_suite should be negotiated")
                         .suite();
@@ -318,6 +370,12 @@ impl Session {
                         quic::KeyChange::OneRtt { keys, next } => {
                             let (key, header_key) = OneRttKey::new(keys, next, cipher_suite);
 
+                            // the server's flight has been processed so we know if it
+                            // accepted the early data
+                            if self.is_zero_rtt_rejected() {
+                                context.on_zero_rtt_rejected()?;
+                            }
+
                             // at this point we should have both SNI and ALPN values
                             self.emit_events(context)?;
 
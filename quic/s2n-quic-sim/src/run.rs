@@ -53,7 +53,11 @@ impl Run {
 
                 let mut servers = vec![];
                 for _ in 0..server_len {
-                    servers.push(endpoint::server(handle, events.clone())?);
+                    servers.push(endpoint::server(
+                        handle,
+                        events.clone(),
+                        &self.congestion_controller,
+                    )?);
                 }
 
                 for _ in 0..client_len {
@@ -64,6 +68,7 @@ impl Run {
                     endpoint::client(
                         handle,
                         events.clone(),
+                        &self.congestion_controller,
                         &servers,
                         count,
                         delay,
@@ -115,6 +115,10 @@ config!(
         #[default = "4096"]
         stream_data: CliRange<u64>,
 
+        #[name = "congestion_controller"]
+        #[default = "cubic"]
+        congestion_controller: String,
+
         #[name = "iterations"]
         #[default = "10000"]
         iterations: u64,
@@ -10,15 +10,74 @@ use s2n_quic::{
     },
     Client, Server,
 };
-use s2n_quic_core::{crypto::tls::testing::certificates, stream::testing::Data};
+use s2n_quic_core::{
+    crypto::tls::testing::certificates,
+    recovery::congestion_controller::{registry, Endpoint},
+    stream::testing::Data,
+};
 use std::net::SocketAddr;
 
-pub fn server(handle: &Handle, events: events::Events) -> Result<SocketAddr> {
-    let mut server = Server::builder()
-        .with_io(handle.builder().build().unwrap())?
-        .with_tls((certificates::CERT_PEM, certificates::KEY_PEM))?
-        .with_event((events, Tracing::default()))?
-        .start()?;
+/// Calls the `visitor` with the congestion controller registered with the given `name`
+fn with_congestion_controller<T, V>(name: &str, visitor: V) -> Result<T>
+where
+    V: registry::Visitor<Output = Result<T>>,
+{
+    registry::visit(name, visitor).unwrap_or_else(|| {
+        Err(format!(
+            "unsupported congestion controller {name:?}; expected one of {:?}",
+            registry::NAMES
+        )
+        .into())
+    })
+}
+
+struct StartServer<'a> {
+    handle: &'a Handle,
+    events: events::Events,
+}
+
+impl registry::Visitor for StartServer<'_> {
+    type Output = Result<Server>;
+
+    fn visit<E: Endpoint>(self, congestion_controller: E) -> Self::Output {
+        let server = Server::builder()
+            .with_io(self.handle.builder().build().unwrap())?
+            .with_tls((certificates::CERT_PEM, certificates::KEY_PEM))?
+            .with_event((self.events, Tracing::default()))?
+            .with_congestion_controller(congestion_controller)?
+            .start()?;
+
+        Ok(server)
+    }
+}
+
+struct StartClient<'a> {
+    handle: &'a Handle,
+    events: events::Events,
+}
+
+impl registry::Visitor for StartClient<'_> {
+    type Output = Result<Client>;
+
+    fn visit<E: Endpoint>(self, congestion_controller: E) -> Self::Output {
+        let client = Client::builder()
+            .with_io(self.handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((self.events, Tracing::default()))?
+            .with_congestion_controller(congestion_controller)?
+            .start()?;
+
+        Ok(client)
+    }
+}
+
+pub fn server(
+    handle: &Handle,
+    events: events::Events,
+    congestion_controller: &str,
+) -> Result<SocketAddr> {
+    let mut server =
+        with_congestion_controller(congestion_controller, StartServer { handle, events })?;
     let server_addr = server.local_addr()?;
 
     // accept connections and echo back
@@ -39,20 +98,18 @@ pub fn server(handle: &Handle, events: events::Events) -> Result<SocketAddr> {
     Ok(server_addr)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn client(
     handle: &Handle,
     events: events::Events,
+    congestion_controller: &str,
     servers: &[SocketAddr],
     count: usize,
     delay: CliRange<jiff::SignedDuration>,
     streams: CliRange<u32>,
     stream_data: CliRange<u64>,
 ) -> Result {
-    let client = Client::builder()
-        .with_io(handle.builder().build().unwrap())?
-        .with_tls(certificates::CERT_PEM)?
-        .with_event((events, Tracing::default()))?
-        .start()?;
+    let client = with_congestion_controller(congestion_controller, StartClient { handle, events })?;
 
     let mut total_delay = core::time::Duration::ZERO;
 
@@ -157,6 +157,12 @@ where
     ) -> Result<(), transport::Error> {
         match core::mem::replace(&mut self.state.secrets, Secrets::Waiting) {
             Secrets::Waiting => {
+                // s2n-tls doesn't support early data with QUIC: early data can only be
+                // requested with `s2n_send_early_data`/`s2n_recv_early_data`, which fail on QUIC
+                // connections, and an accepted handshake still exchanges the EndOfEarlyData
+                // message, which QUIC forbids. The secret is only emitted if a client offers
+                // early data, which s2n-tls never does, so the 0-RTT keys are never handed to the
+                // connection and clients wait for the handshake to complete.
                 if id == s2n_secret_type_t::CLIENT_EARLY_TRAFFIC_SECRET {
                     return Ok(());
                 }
@@ -211,9 +217,6 @@ where
 
                 match self.state.tx_phase {
                     HandshakePhase::Initial => {
-                        let (key, header_key) = HandshakeKey::new(self.endpoint, aead_algo, pair)
-                            .expect("invalid cipher");
-
                         if !self.server_params.is_empty() {
                             debug_assert!(self.endpoint.is_server());
 
@@ -244,13 +247,28 @@ where
                             self.server_params.clear();
                         }
 
+                        // The handshake keys are derived after the client params are processed,
+                        // since the server may have switched to a compatible QUIC version.
+                        let (key, header_key) = HandshakeKey::new_with_version(
+                            self.endpoint,
+                            self.context.quic_version(),
+                            aead_algo,
+                            pair,
+                        )
+                        .expect("invalid cipher");
+
                         self.context.on_handshake_keys(key, header_key)?;
                         self.state.tx_phase.transition();
                         self.state.rx_phase.transition();
                     }
                     _ => {
-                        let (key, header_key) =
-                            OneRttKey::new(self.endpoint, aead_algo, pair).expect("invalid cipher");
+                        let (key, header_key) = OneRttKey::new_with_version(
+                            self.endpoint,
+                            self.context.quic_version(),
+                            aead_algo,
+                            pair,
+                        )
+                        .expect("invalid cipher");
                         // At this point the server is done writing Handshake messages
                         if self.endpoint.is_server() {
                             self.state.tx_phase.transition();
@@ -220,4 +220,8 @@ impl<L: ConfigLoader> tls::Endpoint for Client<L> {
     fn max_tag_length(&self) -> usize {
         s2n_quic_crypto::MAX_TAG_LEN
     }
+
+    fn supports_compatible_version_negotiation(&self) -> bool {
+        true
+    }
 }
@@ -253,4 +253,8 @@ impl<L: ConfigLoader> tls::Endpoint for Server<L> {
     fn max_tag_length(&self) -> usize {
         s2n_quic_crypto::MAX_TAG_LEN
     }
+
+    fn supports_compatible_version_negotiation(&self) -> bool {
+        true
+    }
 }
@@ -14,7 +14,7 @@ use s2n_quic_core::{
         testing::certificates::{CERT_PEM, KEY_PEM, UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM},
         Endpoint,
     },
-    transport,
+    transport, version,
 };
 #[cfg(any(test, feature = "unstable_client_hello"))]
 use s2n_tls::callbacks::ClientHelloCallback;
@@ -382,6 +382,15 @@ fn s2n_client_rustls_server_test() {
     run(&mut server_endpoint, &mut client_endpoint, None);
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn quic_v2_test() {
+    // exercise the key derivation across providers to ensure they agree on the v2 labels
+    run_with_version(&mut s2n_server(), &mut s2n_client(), version::V2);
+    run_with_version(&mut rustls_server(), &mut s2n_client(), version::V2);
+    run_with_version(&mut s2n_server(), &mut rustls_client(), version::V2);
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn s2n_client_s2n_server_client_auth_test() {
@@ -490,6 +499,18 @@ fn run_result<S: Endpoint, C: Endpoint>(
     Ok(pair)
 }
 
+/// Executes the handshake to completion with the given QUIC version
+fn run_with_version<S: Endpoint, C: Endpoint>(server: &mut S, client: &mut C, version: u32) {
+    let mut pair =
+        tls::testing::Pair::new_with_version(server, client, "localhost".into(), version);
+
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+
+    pair.finish();
+}
+
 /// Executes the handshake to completion
 fn run<S: Endpoint, C: Endpoint>(
     server: &mut S,
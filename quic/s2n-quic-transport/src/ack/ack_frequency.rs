@@ -0,0 +1,334 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Support for the QUIC Acknowledgment Frequency extension
+//!
+//! The extension is defined in <https://datatracker.ietf.org/doc/draft-ietf-quic-ack-frequency/>.
+//! An endpoint that advertises the `min_ack_delay` transport parameter lets its peer send
+//! ACK_FREQUENCY frames to control how often acknowledgments are sent.
+
+use crate::{
+    contexts::WriteContext, endpoint, path::Path, recovery::CongestionController, transmission,
+};
+use core::time::Duration;
+use s2n_quic_core::{
+    ack, frame::AckFrequency, packet::number::PacketNumber, recovery::K_PACKET_THRESHOLD,
+    transport, varint::VarInt,
+};
+
+/// The number of ack-eliciting packets that can be received before an ACK is sent, unless
+/// the peer requests otherwise
+///
+/// An ACK frame is sent for at least every 10th ack-eliciting packet.
+const DEFAULT_ACK_ELICITING_THRESHOLD: u8 = 9;
+
+/// The amount of packet reordering that causes an immediate ACK, unless the peer requests
+/// otherwise
+///
+/// A value of `1` matches the behavior described in RFC 9000, which acknowledges any
+/// out-of-order packet immediately.
+const DEFAULT_REORDERING_THRESHOLD: u8 = 1;
+
+/// Tracks the acknowledgment frequency requested by the peer
+#[derive(Clone, Copy, Debug)]
+pub struct AckFrequencyReceiver {
+    /// The min_ack_delay advertised to the peer, or `None` if the extension isn't enabled
+    min_ack_delay: Option<Duration>,
+    /// The smallest sequence number that will be applied from an ACK_FREQUENCY frame
+    next_sequence_number: VarInt,
+    /// The number of ack-eliciting packets that can be received without sending an ACK
+    ack_eliciting_threshold: u8,
+    /// The packet reordering that causes an immediate ACK; `0` disables the behavior
+    reordering_threshold: u8,
+}
+
+impl Default for AckFrequencyReceiver {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl AckFrequencyReceiver {
+    pub fn new(min_ack_delay: Option<Duration>) -> Self {
+        Self {
+            min_ack_delay,
+            next_sequence_number: VarInt::from_u8(0),
+            ack_eliciting_threshold: DEFAULT_ACK_ELICITING_THRESHOLD,
+            reordering_threshold: DEFAULT_REORDERING_THRESHOLD,
+        }
+    }
+
+    /// Returns `true` if the min_ack_delay transport parameter was sent to the peer
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.min_ack_delay.is_some()
+    }
+
+    /// Returns the number of ack-eliciting packets that can be received without sending an ACK
+    #[inline]
+    pub fn ack_eliciting_threshold(&self) -> u8 {
+        self.ack_eliciting_threshold
+    }
+
+    /// Returns the packet reordering that causes an immediate ACK
+    #[inline]
+    pub fn reordering_threshold(&self) -> u8 {
+        self.reordering_threshold
+    }
+
+    /// Called when an ACK_FREQUENCY frame is received
+    ///
+    /// Returns the max_ack_delay requested by the peer if the frame is the most recent one
+    /// that has been received.
+    pub fn on_ack_frequency_frame(
+        &mut self,
+        frame: &AckFrequency,
+    ) -> Result<Option<Duration>, transport::Error> {
+        // An endpoint that didn't advertise min_ack_delay can't receive ACK_FREQUENCY frames
+        let min_ack_delay = self.min_ack_delay.ok_or(
+            transport::Error::PROTOCOL_VIOLATION
+                .with_reason("ACK_FREQUENCY frame received without sending min_ack_delay"),
+        )?;
+
+        let max_ack_delay = frame.request_max_ack_delay();
+
+        if max_ack_delay < min_ack_delay {
+            return Err(transport::Error::PROTOCOL_VIOLATION
+                .with_reason("requested max ack delay is less than min_ack_delay"));
+        }
+
+        // ACK_FREQUENCY frames can arrive out of order so only the latest one is applied
+        if frame.sequence_number < self.next_sequence_number {
+            return Ok(None);
+        }
+
+        self.next_sequence_number = frame.sequence_number.saturating_add(VarInt::from_u8(1));
+        self.ack_eliciting_threshold = frame
+            .ack_eliciting_threshold
+            .as_u64()
+            .try_into()
+            .unwrap_or(u8::MAX);
+        self.reordering_threshold = frame
+            .reordering_threshold
+            .as_u64()
+            .try_into()
+            .unwrap_or(u8::MAX);
+
+        Ok(Some(max_ack_delay))
+    }
+}
+
+/// The number of ACKs the peer is asked to send per congestion window
+const ACKS_PER_CONGESTION_WINDOW: u32 = 4;
+
+/// The largest ack-eliciting threshold that will be requested from the peer
+const MAX_ACK_ELICITING_THRESHOLD: u32 = 63;
+
+/// Requests an acknowledgment frequency from the peer based on the state of the active path
+///
+/// The peer is asked to acknowledge a few times per congestion window and round trip, rather
+/// than every other packet, which reduces the cost of processing ACKs on high bandwidth links.
+#[derive(Debug, Default)]
+pub struct AckFrequencySender {
+    /// The min_ack_delay sent by the peer, or `None` if the peer doesn't support the extension
+    peer_min_ack_delay: Option<Duration>,
+    /// The max_ack_delay sent by the peer
+    peer_max_ack_delay: Duration,
+    /// The sequence number of the next ACK_FREQUENCY frame
+    next_sequence_number: VarInt,
+    /// The most recent ACK_FREQUENCY frame
+    latest: Option<AckFrequency>,
+    /// The transmission state of the most recent frame
+    state: State,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum State {
+    #[default]
+    Idle,
+    Requested,
+    InFlight(PacketNumber),
+    Lost,
+}
+
+impl AckFrequencySender {
+    /// Called when the transport parameters of the peer are received
+    pub fn on_peer_params(&mut self, min_ack_delay: Option<Duration>, max_ack_delay: Duration) {
+        self.peer_min_ack_delay = min_ack_delay;
+        self.peer_max_ack_delay = max_ack_delay;
+    }
+
+    /// Called when an outgoing packet is being assembled
+    pub fn on_transmit<Config: endpoint::Config, W: WriteContext>(
+        &mut self,
+        path: &Path<Config>,
+        context: &mut W,
+    ) {
+        self.update(path);
+
+        let constraint = context.transmission_constraint();
+        let can_transmit = match self.state {
+            State::Requested => constraint.can_transmit(),
+            State::Lost => constraint.can_retransmit(),
+            State::Idle | State::InFlight(_) => false,
+        };
+
+        if !can_transmit {
+            return;
+        }
+
+        if let Some(frame) = self.latest.as_ref() {
+            if let Some(packet_number) = context.write_frame(frame) {
+                self.state = State::InFlight(packet_number);
+            }
+        }
+    }
+
+    /// Called when a set of packets was acknowledged
+    pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
+        if let State::InFlight(packet_number) = self.state {
+            if ack_set.contains(packet_number) {
+                self.state = State::Idle;
+            }
+        }
+    }
+
+    /// Called when a set of packets was reported lost
+    pub fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) {
+        if let State::InFlight(packet_number) = self.state {
+            if ack_set.contains(packet_number) {
+                self.state = State::Lost;
+            }
+        }
+    }
+
+    /// Queues a new ACK_FREQUENCY frame if the path has changed enough since the last request
+    fn update<Config: endpoint::Config>(&mut self, path: &Path<Config>) {
+        let Some(min_ack_delay) = self.peer_min_ack_delay else {
+            return;
+        };
+
+        // Wait until the path has an RTT estimate before changing the peer's behavior
+        if path.rtt_estimator.first_rtt_sample().is_none() {
+            return;
+        }
+
+        let max_datagram_size = (path.mtu_controller.max_datagram_size() as u32).max(1);
+        let packets_per_window = path.congestion_controller.congestion_window() / max_datagram_size;
+        let packets_per_ack = (packets_per_window / ACKS_PER_CONGESTION_WINDOW).max(1);
+        // Round down to a power of two so the threshold only changes when the congestion
+        // window changes significantly
+        let packets_per_ack = 1 << (u32::BITS - 1 - packets_per_ack.leading_zeros());
+        let ack_eliciting_threshold = (packets_per_ack - 1).clamp(1, MAX_ACK_ELICITING_THRESHOLD);
+
+        // Ask for an ACK at least every quarter of a round trip. The delay is capped by the
+        // max_ack_delay of the peer so the probe timeout doesn't need to be adjusted.
+        let max_ack_delay = (path.rtt_estimator.smoothed_rtt() / 4)
+            .min(self.peer_max_ack_delay)
+            .max(min_ack_delay);
+
+        if let Some(latest) = self.latest.as_ref() {
+            let latest_delay = latest.request_max_ack_delay();
+            let delay_change = if max_ack_delay > latest_delay {
+                max_ack_delay - latest_delay
+            } else {
+                latest_delay - max_ack_delay
+            };
+
+            if latest.ack_eliciting_threshold == VarInt::from_u32(ack_eliciting_threshold)
+                && delay_change <= latest_delay / 4
+            {
+                return;
+            }
+        }
+
+        let Ok(request_max_ack_delay) = VarInt::try_from(max_ack_delay.as_micros()) else {
+            return;
+        };
+
+        self.latest = Some(AckFrequency {
+            sequence_number: self.next_sequence_number,
+            ack_eliciting_threshold: VarInt::from_u32(ack_eliciting_threshold),
+            request_max_ack_delay,
+            // Packets are declared lost once K_PACKET_THRESHOLD later packets have been
+            // acknowledged, so the peer only needs to report reordering beyond that
+            reordering_threshold: VarInt::from_u8(K_PACKET_THRESHOLD as u8),
+        });
+        self.next_sequence_number = self.next_sequence_number.saturating_add(VarInt::from_u8(1));
+        self.state = State::Requested;
+    }
+}
+
+impl transmission::interest::Provider for AckFrequencySender {
+    #[inline]
+    fn transmission_interest<Q: transmission::interest::Query>(
+        &self,
+        query: &mut Q,
+    ) -> transmission::interest::Result {
+        match self.state {
+            State::Requested => query.on_new_data(),
+            State::Lost => query.on_lost_data(),
+            State::Idle | State::InFlight(_) => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path::testing::helper_path_server;
+    use s2n_quic_core::{packet::number::PacketNumberSpace, time::clock::testing as time};
+
+    #[test]
+    fn sender_test() {
+        let mut sender = AckFrequencySender::default();
+        let mut path = helper_path_server();
+
+        // nothing is requested until the path has an RTT sample
+        sender.on_peer_params(Some(Duration::from_millis(1)), Duration::from_millis(25));
+        sender.update(&path);
+        assert!(sender.latest.is_none());
+
+        path.rtt_estimator.update_rtt(
+            Duration::ZERO,
+            Duration::from_millis(40),
+            time::now(),
+            true,
+            PacketNumberSpace::ApplicationData,
+        );
+        sender.update(&path);
+
+        let frame = sender.latest.expect("a frame should be requested");
+        assert_eq!(frame.sequence_number, VarInt::from_u8(0));
+        assert_eq!(frame.request_max_ack_delay(), Duration::from_millis(10));
+        assert_eq!(frame.reordering_threshold, VarInt::from_u8(3));
+        assert_eq!(sender.state, State::Requested);
+
+        // the request isn't repeated if the path hasn't changed
+        sender.state = State::InFlight(
+            PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(1)),
+        );
+        sender.update(&path);
+        assert_eq!(sender.latest, Some(frame));
+        assert!(matches!(sender.state, State::InFlight(_)));
+    }
+
+    #[test]
+    fn disabled_sender_test() {
+        let mut sender = AckFrequencySender::default();
+        let mut path = helper_path_server();
+        path.rtt_estimator.update_rtt(
+            Duration::ZERO,
+            Duration::from_millis(40),
+            time::now(),
+            true,
+            PacketNumberSpace::ApplicationData,
+        );
+
+        // the peer didn't send min_ack_delay
+        sender.on_peer_params(None, Duration::from_millis(25));
+        sender.update(&path);
+        assert!(sender.latest.is_none());
+        assert_eq!(sender.state, State::Idle);
+    }
+}
@@ -2,9 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ack::ack_transmission_state::AckTransmissionState, contexts::WriteContext,
-    processed_packet::ProcessedPacket, transmission,
+    ack::{ack_transmission_state::AckTransmissionState, AckFrequencyReceiver},
+    contexts::WriteContext,
+    processed_packet::ProcessedPacket,
+    transmission,
 };
+use core::time::Duration;
 use s2n_quic_core::{
     ack,
     counter::{Counter, Saturating},
@@ -13,9 +16,10 @@ use s2n_quic_core::{
         builder::{AckAction, AckProcessed},
         IntoEvent as _,
     },
-    frame::{ack::EcnCounts, Ack, Ping},
+    frame::{ack::EcnCounts, Ack, AckFrequency, PathAck, Ping},
     packet::number::{PacketNumber, PacketNumberSpace},
     time::{timer, Timer, Timestamp},
+    transport,
     varint::VarInt,
 };
 
@@ -66,6 +70,15 @@ pub struct AckManager {
 
     /// Explicit Congestion Notification counts from processed packets
     ecn_counts: EcnCounts,
+
+    /// The acknowledgment frequency requested by the peer
+    ack_frequency: AckFrequencyReceiver,
+
+    /// The multipath path id of the acknowledged packet number space
+    ///
+    /// Packets received on paths other than the initial path are acknowledged with
+    /// PATH_ACK frames instead of ACK frames.
+    path_id: Option<VarInt>,
 }
 
 impl AckManager {
@@ -82,9 +95,41 @@ impl AckManager {
             transmissions_since_elicitation: Counter::new(0),
             transmission_state: AckTransmissionState::default(),
             ecn_counts: EcnCounts::default(),
+            ack_frequency: AckFrequencyReceiver::default(),
+            path_id: None,
         }
     }
 
+    /// Creates an `AckManager` for the packet number space of a multipath path
+    pub fn for_path(path_id: VarInt, ack_settings: ack::Settings) -> Self {
+        Self {
+            path_id: Some(path_id),
+            ..Self::new(PacketNumberSpace::ApplicationData, ack_settings)
+        }
+    }
+
+    /// Enables the peer to change the acknowledgment frequency with ACK_FREQUENCY frames
+    ///
+    /// `min_ack_delay` is the value of the min_ack_delay transport parameter sent to the peer.
+    pub fn enable_ack_frequency(&mut self, min_ack_delay: Duration) {
+        self.ack_frequency = AckFrequencyReceiver::new(Some(min_ack_delay));
+    }
+
+    /// Returns `true` if the peer is allowed to send ACK_FREQUENCY and IMMEDIATE_ACK frames
+    #[inline]
+    pub fn is_ack_frequency_enabled(&self) -> bool {
+        self.ack_frequency.is_enabled()
+    }
+
+    /// Called when an ACK_FREQUENCY frame is received
+    pub fn on_ack_frequency_frame(&mut self, frame: &AckFrequency) -> Result<(), transport::Error> {
+        if let Some(max_ack_delay) = self.ack_frequency.on_ack_frequency_frame(frame)? {
+            self.ack_settings.max_ack_delay = max_ack_delay;
+        }
+
+        Ok(())
+    }
+
     /// Called when an outgoing packet is being assembled
     pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) -> bool {
         let constraint = context.transmission_constraint();
@@ -104,13 +149,17 @@ impl AckManager {
         //# Even if an endpoint does not set an ECT field on packets it sends,
         //# the endpoint MUST provide feedback about ECN markings it receives, if
         //# these are accessible.
-        context
-            .write_ack_frame(&Ack {
-                ack_delay,
-                ack_ranges: &self.ack_ranges,
-                ecn_counts: self.ecn_counts.as_option(),
-            })
-            .is_some()
+        let ack = Ack {
+            ack_delay,
+            ack_ranges: &self.ack_ranges,
+            ecn_counts: self.ecn_counts.as_option(),
+        };
+
+        if let Some(path_id) = self.path_id {
+            context.write_frame(&PathAck { path_id, ack }).is_some()
+        } else {
+            context.write_ack_frame(&ack).is_some()
+        }
     }
 
     /// Called after an outgoing packet is assembled and `on_transmit` returned `true`
@@ -295,14 +344,12 @@ impl AckManager {
             //# *  when the received packet has a packet number less than another
             //#    ack-eliciting packet that has been received, or
 
-            should_activate |= !is_largest;
-
             //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2.1
             //# *  when the packet has a packet number larger than the highest-
             //#    numbered ack-eliciting packet that has been received and there are
             //#    missing packets between that packet and this packet.
 
-            should_activate |= !is_ordered;
+            should_activate |= self.is_reordering_exceeded(is_ordered, is_largest);
 
             //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2.1
             //# Similarly, packets marked with the ECN Congestion Experienced (CE)
@@ -310,15 +357,15 @@ impl AckManager {
             //# reduce the peer's response time to congestion events.
             should_activate |= processed_packet.datagram.ecn.congestion_experienced();
 
-            // TODO update to draft link after published
-            // https://github.com/quicwg/base-drafts/pull/3623
-            // An ACK frame SHOULD be generated for at least every 10th ack-eliciting packet
+            // An ACK frame is sent once more ack-eliciting packets than the threshold
+            // have been received. The threshold can be changed by the peer with an
+            // ACK_FREQUENCY frame.
+            let ack_eliciting_threshold = self.ack_frequency.ack_eliciting_threshold();
 
-            // TODO support delayed ack proposal
-            // https://tools.ietf.org/html/draft-iyengar-quic-delayed-ack-00
-            let packet_tolerance = 10;
+            should_activate |= self.processed_packets_since_transmission > ack_eliciting_threshold;
 
-            should_activate |= self.processed_packets_since_transmission >= packet_tolerance;
+            // The peer can request an ACK without delay with an IMMEDIATE_ACK frame
+            should_activate |= processed_packet.immediate_ack_requested;
 
             //= https://www.rfc-editor.org/rfc/rfc9000#section-9.3.3
             //# An endpoint that receives a PATH_CHALLENGE on an active path SHOULD
@@ -360,6 +407,45 @@ impl AckManager {
         self.largest_received_packet_number_acked
     }
 
+    /// Returns `true` if an out-of-order packet should be acknowledged immediately
+    ///
+    /// Must be called after the packet number has been inserted into `ack_ranges`.
+    fn is_reordering_exceeded(&self, is_ordered: bool, is_largest: bool) -> bool {
+        match self.ack_frequency.reordering_threshold() {
+            // the peer has disabled immediate ACKs for out-of-order packets
+            0 => false,
+            // acknowledge any out-of-order packet, as described in RFC 9000
+            1 => !is_largest || !is_ordered,
+            threshold => {
+                // The peer waits for `threshold` packets before declaring a packet lost so
+                // late arrivals don't need to be acknowledged immediately
+                if !is_largest {
+                    return false;
+                }
+
+                let mut intervals = self.ack_ranges.intervals().rev();
+                let (Some(largest), Some(_)) = (intervals.next(), intervals.next()) else {
+                    // there are no missing packets
+                    return false;
+                };
+
+                let Some(largest_missing) = largest.start_inclusive().prev() else {
+                    return false;
+                };
+
+                // the missing packet was already reported in a previous ACK frame
+                if largest_missing < self.largest_received_packet_number_acked {
+                    return false;
+                }
+
+                largest
+                    .end_inclusive()
+                    .checked_distance(largest_missing)
+                    .is_some_and(|distance| distance >= threshold as u64)
+            }
+        }
+    }
+
     /// Computes the ack_delay field for the current state
     fn ack_delay(&self, now: Timestamp) -> VarInt {
         let ack_delay = self
@@ -519,6 +605,146 @@ mod tests {
         assert_eq!(1, manager.ecn_counts.ce_count.as_u64());
     }
 
+    #[test]
+    fn ack_frequency_frame_test() {
+        let mut manager =
+            AckManager::new(PacketNumberSpace::ApplicationData, ack::Settings::default());
+        let frame = AckFrequency {
+            sequence_number: VarInt::from_u8(1),
+            ack_eliciting_threshold: VarInt::from_u8(2),
+            request_max_ack_delay: VarInt::from_u32(10_000),
+            reordering_threshold: VarInt::from_u8(3),
+        };
+
+        // the frame is rejected if min_ack_delay wasn't sent to the peer
+        assert!(manager.on_ack_frequency_frame(&frame).is_err());
+
+        manager.enable_ack_frequency(Duration::from_millis(1));
+
+        // the requested max_ack_delay can't be less than min_ack_delay
+        assert!(manager
+            .on_ack_frequency_frame(&AckFrequency {
+                request_max_ack_delay: VarInt::from_u16(999),
+                ..frame
+            })
+            .is_err());
+
+        assert!(manager.on_ack_frequency_frame(&frame).is_ok());
+        assert_eq!(
+            manager.ack_settings.max_ack_delay,
+            Duration::from_millis(10)
+        );
+        assert_eq!(manager.ack_frequency.ack_eliciting_threshold(), 2);
+        assert_eq!(manager.ack_frequency.reordering_threshold(), 3);
+
+        // frames with an older sequence number are ignored
+        assert!(manager
+            .on_ack_frequency_frame(&AckFrequency {
+                sequence_number: VarInt::from_u8(0),
+                ack_eliciting_threshold: VarInt::from_u8(5),
+                request_max_ack_delay: VarInt::from_u32(20_000),
+                ..frame
+            })
+            .is_ok());
+        assert_eq!(
+            manager.ack_settings.max_ack_delay,
+            Duration::from_millis(10)
+        );
+        assert_eq!(manager.ack_frequency.ack_eliciting_threshold(), 2);
+    }
+
+    #[test]
+    fn ack_eliciting_threshold_test() {
+        let mut manager =
+            AckManager::new(PacketNumberSpace::ApplicationData, ack::Settings::default());
+        manager.enable_ack_frequency(Duration::from_millis(1));
+        manager
+            .on_ack_frequency_frame(&AckFrequency {
+                sequence_number: VarInt::from_u8(0),
+                ack_eliciting_threshold: VarInt::from_u8(2),
+                request_max_ack_delay: VarInt::from_u32(25_000),
+                reordering_threshold: VarInt::from_u8(3),
+            })
+            .unwrap();
+
+        let path = helper_path_server();
+        let path_id = path::Id::test_id();
+        let mut publisher = Publisher::snapshot();
+        let datagram = helper_datagram_info(ExplicitCongestionNotification::NotEct);
+
+        for pn in 1..=3 {
+            assert!(!manager.transmission_state.is_active());
+
+            let pn = PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(pn));
+            let mut processed_packet = ProcessedPacket::new(pn, &datagram);
+            processed_packet.ack_elicitation = AckElicitation::Eliciting;
+            manager.on_processed_packet(
+                &processed_packet,
+                path_event!(path, path_id),
+                &mut publisher,
+            );
+        }
+
+        // an ACK is sent once the threshold is exceeded
+        assert!(manager.transmission_state.is_active());
+    }
+
+    #[test]
+    fn reordering_threshold_test() {
+        let mut manager =
+            AckManager::new(PacketNumberSpace::ApplicationData, ack::Settings::default());
+        manager.enable_ack_frequency(Duration::from_millis(1));
+        manager
+            .on_ack_frequency_frame(&AckFrequency {
+                sequence_number: VarInt::from_u8(0),
+                ack_eliciting_threshold: VarInt::from_u8(10),
+                request_max_ack_delay: VarInt::from_u32(25_000),
+                reordering_threshold: VarInt::from_u8(3),
+            })
+            .unwrap();
+
+        let path = helper_path_server();
+        let path_id = path::Id::test_id();
+        let mut publisher = Publisher::snapshot();
+        let datagram = helper_datagram_info(ExplicitCongestionNotification::NotEct);
+
+        // packet 2 is missing, which is only reported once packet 5 arrives
+        for (pn, is_active) in [(1, false), (3, false), (4, false), (5, true)] {
+            let pn = PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(pn));
+            let mut processed_packet = ProcessedPacket::new(pn, &datagram);
+            processed_packet.ack_elicitation = AckElicitation::Eliciting;
+            manager.on_processed_packet(
+                &processed_packet,
+                path_event!(path, path_id),
+                &mut publisher,
+            );
+
+            assert_eq!(manager.transmission_state.is_active(), is_active);
+        }
+    }
+
+    #[test]
+    fn immediate_ack_test() {
+        let mut manager =
+            AckManager::new(PacketNumberSpace::ApplicationData, ack::Settings::default());
+
+        let pn = PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(1));
+        let datagram = helper_datagram_info(ExplicitCongestionNotification::NotEct);
+        let mut processed_packet = ProcessedPacket::new(pn, &datagram);
+        processed_packet.ack_elicitation = AckElicitation::Eliciting;
+        processed_packet.immediate_ack_requested = true;
+
+        let path = helper_path_server();
+        let path_id = path::Id::test_id();
+        manager.on_processed_packet(
+            &processed_packet,
+            path_event!(path, path_id),
+            &mut Publisher::snapshot(),
+        );
+
+        assert!(manager.transmission_state.is_active());
+    }
+
     /// Helper function to construct `DatagramInfo` with the given `ExplicitCongestionNotification`
     fn helper_datagram_info(ecn: ExplicitCongestionNotification) -> DatagramInfo {
         DatagramInfo {
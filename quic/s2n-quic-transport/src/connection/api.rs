@@ -17,7 +17,9 @@ use core::{
 use s2n_quic_core::{
     application,
     application::ServerName,
+    crypto::tls::EarlyDataStatus,
     inet::SocketAddress,
+    path::migration,
     query::{Query, QueryMut},
     stream::StreamType,
 };
@@ -175,6 +177,12 @@ impl Connection {
     pub fn application_protocol(&self) -> Result<Bytes, connection::Error> {
         self.api.application_protocol()
     }
+
+    #[inline]
+    pub fn early_data_status(&self) -> Result<EarlyDataStatus, connection::Error> {
+        self.api.early_data_status()
+    }
+
     #[inline]
     pub fn take_tls_context(&self) -> Option<Box<dyn Any + Send>> {
         self.api.take_tls_context()
@@ -198,11 +206,33 @@ impl Connection {
         self.api.local_address()
     }
 
+    /// Migrates the connection to the provided local address
+    ///
+    /// The connection switches to the new address once the path has been validated.
+    #[inline]
+    pub fn migrate(&self, local_address: SocketAddress) -> Result<(), migration::Error> {
+        self.api.migrate(local_address)
+    }
+
     #[inline]
     pub fn remote_address(&self) -> Result<SocketAddress, connection::Error> {
         self.api.remote_address()
     }
 
+    /// Returns the maximum QUIC datagram size currently validated on the active path
+    #[inline]
+    pub fn mtu(&self) -> Result<u16, connection::Error> {
+        self.api.mtu()
+    }
+
+    /// Restarts the search for a larger MTU on the active path
+    ///
+    /// The search is started the next time the endpoint wakes up the connection.
+    #[inline]
+    pub fn probe_mtu(&self) -> Result<(), connection::Error> {
+        self.api.probe_mtu()
+    }
+
     #[inline]
     pub fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error> {
         self.api.query_event_context(query)
@@ -220,4 +250,12 @@ impl Connection {
     pub fn datagram_mut(&self, query: &mut dyn QueryMut) -> Result<(), connection::Error> {
         self.api.datagram_mut(query)
     }
+
+    #[inline]
+    pub fn congestion_controller_mut(
+        &self,
+        query: &mut dyn QueryMut,
+    ) -> Result<(), connection::Error> {
+        self.api.congestion_controller_mut(query)
+    }
 }
@@ -18,7 +18,9 @@ use core::{
 use s2n_quic_core::{
     application,
     application::ServerName,
+    crypto::tls::EarlyDataStatus,
     inet::SocketAddress,
+    path::migration,
     query::{Query, QueryMut},
     stream::{ops, StreamId, StreamType},
 };
@@ -59,6 +61,8 @@ pub(crate) trait ConnectionApiProvider: Sync + Send {
 
     fn application_protocol(&self) -> Result<Bytes, connection::Error>;
 
+    fn early_data_status(&self) -> Result<EarlyDataStatus, connection::Error>;
+
     fn take_tls_context(&self) -> Option<Box<dyn Any + Send>>;
 
     fn id(&self) -> u64;
@@ -69,11 +73,19 @@ pub(crate) trait ConnectionApiProvider: Sync + Send {
 
     fn local_address(&self) -> Result<SocketAddress, connection::Error>;
 
+    fn migrate(&self, local_address: SocketAddress) -> Result<(), migration::Error>;
+
     fn remote_address(&self) -> Result<SocketAddress, connection::Error>;
 
+    fn mtu(&self) -> Result<u16, connection::Error>;
+
+    fn probe_mtu(&self) -> Result<(), connection::Error>;
+
     fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error>;
 
     fn query_event_context_mut(&self, query: &mut dyn QueryMut) -> Result<(), connection::Error>;
 
     fn datagram_mut(&self, query: &mut dyn QueryMut) -> Result<(), connection::Error>;
+
+    fn congestion_controller_mut(&self, query: &mut dyn QueryMut) -> Result<(), connection::Error>;
 }
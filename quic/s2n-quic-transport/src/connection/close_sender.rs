@@ -141,9 +141,7 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> tx::Message
 
     #[inline]
     fn ecn(&mut self) -> ExplicitCongestionNotification {
-        self.path
-            .ecn_controller
-            .ecn(transmission::Mode::Normal, self.timestamp)
+        self.path.ecn(transmission::Mode::Normal, self.timestamp)
     }
 
     #[inline]
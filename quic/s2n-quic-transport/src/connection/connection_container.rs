@@ -34,8 +34,10 @@ use intrusive_collections::{
 use s2n_quic_core::{
     application,
     application::ServerName,
+    crypto::tls::EarlyDataStatus,
     event::supervisor,
     inet::SocketAddress,
+    path::migration,
     query::{Query, QueryMut},
     recovery::K_GRANULARITY,
     time::Timestamp,
@@ -310,6 +312,11 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
     fn application_protocol(&self) -> Result<Bytes, connection::Error> {
         self.api_read_call(|conn| Ok(conn.application_protocol()))
     }
+
+    fn early_data_status(&self) -> Result<EarlyDataStatus, connection::Error> {
+        self.api_read_call(|conn| Ok(conn.early_data_status()))
+    }
+
     fn take_tls_context(&self) -> Option<Box<dyn Any + Send>> {
         self.api_write_call(|conn| Ok::<_, connection::Error>(conn.take_tls_context()))
             .ok()
@@ -332,10 +339,22 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_read_call(|conn| conn.local_address())
     }
 
+    fn migrate(&self, local_address: SocketAddress) -> Result<(), migration::Error> {
+        self.api_write_call(|conn| conn.migrate(local_address))
+    }
+
     fn remote_address(&self) -> Result<SocketAddress, connection::Error> {
         self.api_read_call(|conn| conn.remote_address())
     }
 
+    fn mtu(&self) -> Result<u16, connection::Error> {
+        self.api_read_call(|conn| conn.mtu())
+    }
+
+    fn probe_mtu(&self) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.probe_mtu())
+    }
+
     #[inline]
     fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error> {
         self.api_read_call(|conn| {
@@ -359,6 +378,14 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
             Ok(())
         })
     }
+
+    #[inline]
+    fn congestion_controller_mut(&self, query: &mut dyn QueryMut) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| {
+            conn.congestion_controller_mut(query);
+            Ok(())
+        })
+    }
 }
 
 /// Contains all secondary lists of Connections.
@@ -20,7 +20,7 @@ use core::{
 use s2n_quic_core::{
     application, event,
     event::builder::DatagramDropReason,
-    inet::{DatagramInfo, SocketAddress},
+    inet::{icmp, DatagramInfo, SocketAddress},
     io::tx,
     packet::{
         handshake::ProtectedHandshake,
@@ -29,10 +29,11 @@ use s2n_quic_core::{
         short::ProtectedShort,
         version_negotiation::ProtectedVersionNegotiation,
         zero_rtt::ProtectedZeroRtt,
+        ProtectedPacket,
     },
-    path::mtu,
+    path::{migration, mtu},
     query,
-    time::{Timer, Timestamp},
+    time::{Clock, Timer, Timestamp},
 };
 use std::sync::Mutex;
 
@@ -107,10 +108,11 @@ impl connection::Trait for TestConnection {
         Ok(())
     }
 
-    fn on_transmit<Tx: tx::Queue>(
+    fn on_transmit<Tx: tx::Queue, C: Clock>(
         &mut self,
         _queue: &mut Tx,
         _timestamp: Timestamp,
+        _clock: &C,
         _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
         _packet_interceptor: &mut <Self::Config as endpoint::Config>::PacketInterceptor,
     ) -> Result<(), crate::contexts::ConnectionOnTransmitError> {
@@ -138,6 +140,9 @@ impl connection::Trait for TestConnection {
         _datagram: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
         _dc_endpoint: &mut <Self::Config as endpoint::Config>::DcEndpoint,
         _conn_limits_endpoint: &mut <Self::Config as endpoint::Config>::ConnectionLimits,
+        _congestion_controller_endpoint: &mut <Self::Config as endpoint::Config>::CongestionControllerEndpoint,
+        _mtu: &mut mtu::Manager<<Self::Config as endpoint::Config>::Mtu>,
+        _random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
     ) -> Result<(), connection::Error> {
         Ok(())
     }
@@ -223,6 +228,7 @@ impl connection::Trait for TestConnection {
         _datagram: &DatagramInfo,
         _path_id: path::Id,
         _packet: ProtectedZeroRtt,
+        _random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
         _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
         _packet_interceptor: &mut <Self::Config as endpoint::Config>::PacketInterceptor,
     ) -> Result<(), ProcessingError> {
@@ -254,6 +260,15 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn on_packet_too_big(
+        &mut self,
+        _packet_too_big: &icmp::PacketTooBig,
+        _timestamp: Timestamp,
+        _subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+    ) -> bool {
+        todo!()
+    }
+
     /// Returns the Connections interests
     fn interests(&self) -> ConnectionInterests {
         self.interests
@@ -264,6 +279,10 @@ impl connection::Trait for TestConnection {
         123
     }
 
+    fn is_compatible_version(&self, _packet: &ProtectedPacket) -> bool {
+        false
+    }
+
     fn poll_stream_request(
         &mut self,
         _stream_id: stream::StreamId,
@@ -302,6 +321,10 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn early_data_status(&self) -> EarlyDataStatus {
+        todo!()
+    }
+
     fn ping(&mut self) -> Result<(), connection::Error> {
         todo!()
     }
@@ -314,10 +337,22 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn migrate(&mut self, _local_address: SocketAddress) -> Result<(), migration::Error> {
+        todo!()
+    }
+
     fn remote_address(&self) -> Result<SocketAddress, connection::Error> {
         Ok(SocketAddress::default())
     }
 
+    fn mtu(&self) -> Result<u16, connection::Error> {
+        todo!()
+    }
+
+    fn probe_mtu(&mut self) -> Result<(), connection::Error> {
+        todo!()
+    }
+
     fn error(&self) -> Option<connection::Error> {
         None
     }
@@ -334,6 +369,10 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn congestion_controller_mut(&mut self, _query: &mut dyn query::QueryMut) {
+        todo!()
+    }
+
     fn with_event_publisher<F>(
         &mut self,
         _timestamp: Timestamp,
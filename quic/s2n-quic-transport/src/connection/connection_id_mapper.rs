@@ -84,6 +84,54 @@ impl StatelessResetMap {
     }
 }
 
+#[derive(Debug)]
+pub(crate) struct PeerIdMap {
+    /// Maps from peer connection IDs in use to internal connection IDs
+    map: HashMap<connection::PeerId, InternalConnectionId, HashState>,
+}
+
+impl PeerIdMap {
+    /// Constructs a new `PeerIdMap`
+    fn new(hash_state: HashState) -> Self {
+        Self {
+            map: HashMap::with_hasher(hash_state),
+        }
+    }
+
+    /// Gets the `InternalConnectionId` (if any) associated with the given peer id
+    pub(crate) fn get(&self, peer_id: &connection::PeerId) -> Option<InternalConnectionId> {
+        self.map.get(peer_id).copied()
+    }
+
+    /// Inserts the given `PeerId` into the map
+    ///
+    /// Peer connection IDs are chosen by the peer, so they aren't guaranteed to be unique
+    /// across connections. The most recent connection to use the ID replaces any existing entry.
+    pub(crate) fn insert(
+        &mut self,
+        peer_id: connection::PeerId,
+        internal_id: InternalConnectionId,
+    ) {
+        // Zero-length connection IDs can't identify a connection
+        if !peer_id.is_empty() {
+            self.map.insert(peer_id, internal_id);
+        }
+    }
+
+    /// Removes the given `PeerId` from the map if it is associated with the given internal ID
+    pub(crate) fn remove(
+        &mut self,
+        peer_id: &connection::PeerId,
+        internal_id: InternalConnectionId,
+    ) {
+        if let Entry::Occupied(entry) = self.map.entry(*peer_id) {
+            if *entry.get() == internal_id {
+                entry.remove();
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct LocalIdMap {
     /// Maps from external to internal connection IDs
@@ -209,6 +257,8 @@ pub(crate) struct ConnectionIdMapperState {
     pub(crate) local_id_map: LocalIdMap,
     /// Maps from a hash of peer stateless reset token to internal connection IDs
     pub(crate) stateless_reset_map: StatelessResetMap,
+    /// Maps from peer connection IDs in use to internal connection IDs
+    pub(crate) peer_id_map: PeerIdMap,
     /// Maps from initial id to internal connection IDs
     pub(crate) initial_id_map: InitialIdMap,
     /// Maps from connection open request to internal connection IDs
@@ -222,6 +272,7 @@ impl ConnectionIdMapperState {
         Self {
             local_id_map: LocalIdMap::new(HashState::new(random_generator)),
             stateless_reset_map: StatelessResetMap::new(HashState::new(random_generator)),
+            peer_id_map: PeerIdMap::new(HashState::new(random_generator)),
             initial_id_map: InitialIdMap::new(
                 HashState::new(random_generator),
                 HashState::new(random_generator),
@@ -279,6 +330,20 @@ impl ConnectionIdMapper {
             })
     }
 
+    /// Looks up the internal Connection ID which is using the given peer connection ID
+    ///
+    /// This is used to route ICMP messages, which quote a datagram that was sent to the peer.
+    pub fn lookup_internal_connection_id_by_peer_id(
+        &self,
+        peer_id: &connection::PeerId,
+    ) -> Option<InternalConnectionId> {
+        self.state
+            .lock()
+            .expect("should succeed unless the lock is poisoned")
+            .peer_id_map
+            .get(peer_id)
+    }
+
     /// Inserts the given `InitialId` into the map if it is not already in the map,
     /// otherwise returns an Err
     pub fn try_insert_initial_id(
@@ -482,6 +547,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn peer_id_map() {
+        let mut random_generator = random::testing::Generator(123);
+        let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Server);
+        let mut id_generator = InternalConnectionIdGenerator::new();
+        let internal_id_1 = id_generator.generate_id();
+        let internal_id_2 = id_generator.generate_id();
+        let peer_id = id(b"id01");
+
+        assert_eq!(
+            None,
+            mapper.lookup_internal_connection_id_by_peer_id(&peer_id)
+        );
+
+        let registry_1 = mapper.create_server_peer_id_registry(internal_id_1, peer_id, true);
+        assert_eq!(
+            Some(internal_id_1),
+            mapper.lookup_internal_connection_id_by_peer_id(&peer_id)
+        );
+
+        // The peer ID isn't unique, so the most recent connection to use it is returned
+        let registry_2 = mapper.create_server_peer_id_registry(internal_id_2, peer_id, true);
+        assert_eq!(
+            Some(internal_id_2),
+            mapper.lookup_internal_connection_id_by_peer_id(&peer_id)
+        );
+
+        // Dropping the registry of the other connection doesn't remove the mapping
+        drop(registry_1);
+        assert_eq!(
+            Some(internal_id_2),
+            mapper.lookup_internal_connection_id_by_peer_id(&peer_id)
+        );
+
+        drop(registry_2);
+        assert_eq!(
+            None,
+            mapper.lookup_internal_connection_id_by_peer_id(&peer_id)
+        );
+
+        // Zero-length connection IDs can't identify a connection
+        let empty_id = id(&[]);
+        let _registry = mapper.create_server_peer_id_registry(internal_id_1, empty_id, true);
+        assert_eq!(
+            None,
+            mapper.lookup_internal_connection_id_by_peer_id(&empty_id)
+        );
+    }
+
     #[test]
     fn initial_id_map() {
         let mut random_generator = random::testing::Generator(123);
@@ -16,10 +16,10 @@ use crate::{
     },
     contexts::{ConnectionApiCallContext, ConnectionOnTransmitError},
     endpoint,
-    path::{self, path_event},
+    path::{self, path_event, scheduler::Endpoint as _},
     processed_packet::ProcessedPacket,
     recovery::{recovery_event, RttEstimator},
-    space::{PacketSpace, PacketSpaceManager},
+    space::{PacketSpace, PacketSpaceManager, ZeroRttSpace},
     stream::{self, Manager as _},
     transmission,
     transmission::interest::Provider as _,
@@ -36,14 +36,17 @@ use s2n_quic_core::{
     application,
     application::ServerName,
     connection::{error::Error, id::Generator as _, InitialId, PeerId},
-    crypto::{tls, CryptoSuite},
+    crypto::{
+        tls::{self, EarlyDataStatus},
+        CryptoSuite,
+    },
     datagram::{Receiver, Sender},
     event::{
         self,
         builder::{DatagramDropReason, MtuUpdatedCause, RxStreamProgress, TxStreamProgress},
         supervisor, ConnectionPublisher as _, IntoEvent as _, Subscriber,
     },
-    inet::{DatagramInfo, SocketAddress},
+    inet::{icmp, DatagramInfo, SocketAddress},
     io::tx,
     packet::{
         handshake::ProtectedHandshake,
@@ -53,12 +56,13 @@ use s2n_quic_core::{
         short::ProtectedShort,
         version_negotiation::ProtectedVersionNegotiation,
         zero_rtt::ProtectedZeroRtt,
+        ProtectedPacket,
     },
-    path::{mtu, Handle as _},
+    path::{migration, mtu, Handle as _, LocalAddress},
     query,
     recovery::CongestionController,
     stateless_reset::token::Generator as _,
-    time::{timer, Timestamp},
+    time::{timer, Clock, Timestamp},
     transport,
 };
 
@@ -181,6 +185,19 @@ pub struct ConnectionImpl<Config: endpoint::Config> {
     wakeup_handle: Arc<WakeupHandle<InternalConnectionId>>,
     /// A Waker to the connection.
     waker: Waker,
+    /// The local address the application requested to migrate the connection to
+    ///
+    /// The migration is started the next time the endpoint wakes up the connection.
+    migration_request: Option<LocalAddress>,
+    /// Set when the application requested to restart the search for a larger MTU
+    ///
+    /// The search is restarted the next time the endpoint wakes up the connection.
+    mtu_probe_requested: bool,
+    /// Set once the handshake completes so the congestion controllers are updated with the
+    /// negotiated parameters
+    ///
+    /// The congestion controllers are updated the next time the endpoint wakes up the connection.
+    congestion_controller_update_pending: bool,
     event_context: EventContext<Config>,
 }
 
@@ -247,9 +264,7 @@ macro_rules! transmission_context {
         $packet_interceptor:expr,
         $(,)?
     ) => {{
-        let ecn = $self.path_manager[$path_id]
-            .ecn_controller
-            .ecn($transmission_mode, $timestamp);
+        let ecn = $self.path_manager[$path_id].ecn($transmission_mode, $timestamp);
 
         ConnectionTransmissionContext {
             quic_version: $self.event_context.quic_version,
@@ -279,7 +294,7 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         let mut publisher = self.event_context.publisher(timestamp, subscriber);
         let space_manager = &mut self.space_manager;
 
-        match space_manager.poll_crypto(
+        let result = space_manager.poll_crypto(
             &mut self.path_manager,
             &mut self.local_id_registry,
             &mut self.limits,
@@ -289,7 +304,22 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
             datagram,
             dc,
             limits,
-        ) {
+        );
+
+        // The server may have switched the connection to a compatible version while
+        // processing the client's transport parameters
+        self.event_context.quic_version = space_manager.version_negotiation().quic_version();
+
+        // A client sending early data is handed over to the application as soon as the
+        // 0-RTT keys are available so it can open streams before the handshake completes
+        if Config::ENDPOINT_TYPE.is_client()
+            && self.accept_state == AcceptState::Handshaking
+            && space_manager.early_data_status() == EarlyDataStatus::Pending
+        {
+            self.accept_state = AcceptState::HandshakeCompleted;
+        }
+
+        match result {
             Poll::Ready(Ok(())) => {}
             // use `from` instead of `into` so the location is correctly captured
             Poll::Ready(Err(err)) => return Err(connection::Error::from(err)),
@@ -323,13 +353,23 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         {
             // Move into the HandshakeCompleted state. This will signal the
             // necessary interest to hand over the connection to the application.
-            self.accept_state = AcceptState::HandshakeCompleted;
+            //
+            // Clients sending early data have already been handed over.
+            if self.accept_state == AcceptState::Handshaking {
+                self.accept_state = AcceptState::HandshakeCompleted;
+            }
             // Move the connection into the active state.
             self.state = ConnectionState::Active;
 
             // Cancel the max handshake duration timer as the handshake has completed in time
             self.timers.max_handshake_duration_timer.cancel();
 
+            // The congestion controller endpoint isn't available while processing packets, so
+            // the congestion controllers are updated with the negotiated application protocol
+            // and server name when the connection is woken up
+            self.congestion_controller_update_pending = true;
+            self.wakeup_handle.wakeup();
+
             // We don't expect any further initial packets on this connection, so start
             // a timer to remove the mapping from the initial ID to the internal connection ID
             // to give time for any delayed initial packets to arrive.
@@ -399,6 +439,17 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
                 .discard_handshake(&mut self.path_manager, &mut publisher);
         }
 
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.6.1
+        //# Once the handshake is confirmed, the client SHOULD select one of the
+        //# two addresses provided by the server and initiate path validation
+        //# (see Section 8.2).
+        if self.space_manager.is_handshake_confirmed()
+            && self.path_manager.can_migrate_to_preferred_address()
+        {
+            // The new path is created on wakeup, where the endpoint providers are available
+            self.wakeup_handle.wakeup();
+        }
+
         // check to see if we're flushing and should now close the connection
         if self.poll_flush().is_ready() {
             self.error?;
@@ -438,6 +489,7 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         &mut self,
         queue: &mut Tx,
         timestamp: Timestamp,
+        now: Timestamp,
         outcome: &'a mut transmission::Outcome,
         subscriber: &mut Config::EventSubscriber,
         packet_interceptor: &'a mut Config::PacketInterceptor,
@@ -455,9 +507,7 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
             }
 
             let transmission_mode = transmission::Mode::PathValidationOnly;
-            let ecn = path_manager[path_id]
-                .ecn_controller
-                .ecn(transmission_mode, timestamp);
+            let ecn = path_manager[path_id].ecn(transmission_mode, timestamp);
 
             if queue
                 .push(ConnectionTransmission {
@@ -475,6 +525,7 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
                         packet_interceptor,
                     },
                     space_manager: &mut self.space_manager,
+                    now,
                 })
                 .is_ok()
             {
@@ -609,7 +660,19 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             parameters.limits.anti_amplification_multiplier(),
         );
 
-        let path_manager = path::Manager::new(initial_path, parameters.peer_id_registry);
+        let mut path_manager = path::Manager::new(initial_path, parameters.peer_id_registry);
+
+        if parameters.limits.initial_max_path_id().is_some() {
+            let scheduler = parameters.path_scheduler_endpoint.new_scheduler();
+            path_manager.set_scheduler(Box::new(scheduler));
+        }
+
+        if Self::Config::ENDPOINT_TYPE.is_server() {
+            let (ipv4_address, ipv6_address) = parameters.limits.preferred_address();
+            if ipv4_address.is_some() || ipv6_address.is_some() {
+                path_manager.on_preferred_address_advertised();
+            }
+        }
 
         let mut publisher =
             event_context.publisher(parameters.timestamp, parameters.event_subscriber);
@@ -653,6 +716,9 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             space_manager: parameters.space_manager,
             wakeup_handle,
             waker,
+            migration_request: None,
+            mtu_probe_requested: false,
+            congestion_controller_update_pending: false,
             event_context,
         };
 
@@ -714,6 +780,25 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         self.event_context.quic_version
     }
 
+    fn is_compatible_version(&self, packet: &ProtectedPacket) -> bool {
+        let (ProtectedPacket::Initial(_), Some(version)) = (packet, packet.version()) else {
+            return false;
+        };
+
+        match Config::ENDPOINT_TYPE {
+            // Clients switch to a compatible version selected by the server
+            endpoint::Type::Client => self
+                .space_manager
+                .version_negotiation()
+                .is_compatible(version),
+            // Servers keep accepting the client's original version until the client switches
+            endpoint::Type::Server => self
+                .space_manager
+                .initial()
+                .map_or(false, |space| space.accepts_original_version(version)),
+        }
+    }
+
     /// Initiates closing the connection as described in
     /// https://www.rfc-editor.org/rfc/rfc9000#section-10
     fn close(
@@ -859,15 +944,20 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
     }
 
     /// Queries the connection for outgoing packets
-    fn on_transmit<Tx: tx::Queue<Handle = Config::PathHandle>>(
+    fn on_transmit<Tx: tx::Queue<Handle = Config::PathHandle>, C: Clock>(
         &mut self,
         queue: &mut Tx,
         timestamp: Timestamp,
+        clock: &C,
         subscriber: &mut Config::EventSubscriber,
         packet_interceptor: &mut Config::PacketInterceptor,
     ) -> Result<(), ConnectionOnTransmitError> {
         let mut count = 0;
 
+        // Departure delays are measured from a single sample of the clock, so packets paced with
+        // the same departure time get the same delay and can be coalesced into a GSO segment
+        let now = clock.get_time();
+
         debug_assert!(
             !self.path_manager.is_amplification_limited(),
             "connection should not express transmission interest if amplification limited"
@@ -878,11 +968,23 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 let mut outcome = transmission::Outcome::default();
                 let path_id = self.path_manager.active_path_id();
 
+                // Queues that pace transmissions hold each packet until its departure time, so
+                // packets can be pushed ahead of the pacer by up to the queue's maximum delay.
+                let max_delay = if Tx::SUPPORTS_PACING {
+                    queue.max_delay()
+                } else {
+                    Duration::ZERO
+                };
+                let pacing_timestamp = timestamp + max_delay;
+
                 // Send an MTU probe if necessary and the handshake has been confirmed
                 // MTU probes are prioritized over other data so they are not blocked by the
                 // congestion controller, as they are critical to achieving maximum throughput.
                 if self.state == ConnectionState::Active
-                    && self.path_manager.active_path().can_transmit(timestamp)
+                    && self
+                        .path_manager
+                        .active_path()
+                        .can_transmit(pacing_timestamp)
                     && self.space_manager.is_handshake_confirmed()
                     && self
                         .path_manager
@@ -901,15 +1003,18 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                                 packet_interceptor,
                             ),
                             space_manager: &mut self.space_manager,
+                            now,
                         })
                         .is_ok()
                 {
                     count += 1;
                 }
 
-                // Send all other data for the active path
-                while self.path_manager.active_path().can_transmit(timestamp)
-                    && queue
+                // Send all other data for the active path. If multipath was negotiated, the
+                // scheduler selects a validated path for each packet instead.
+                while let Some(path_id) = self.path_manager.next_transmission_path(pacing_timestamp)
+                {
+                    if queue
                         .push(ConnectionTransmission {
                             context: transmission_context!(
                                 self,
@@ -921,9 +1026,13 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                                 packet_interceptor,
                             ),
                             space_manager: &mut self.space_manager,
+                            now,
                         })
-                        .is_ok()
-                {
+                        .is_err()
+                    {
+                        break;
+                    }
+
                     count += 1;
                 }
 
@@ -937,9 +1046,10 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                     .congestion_controller
                     .earliest_departure_time()
                 {
-                    if !edt.has_elapsed(timestamp) {
+                    if !edt.has_elapsed(pacing_timestamp) {
                         // We can't transmit more until a future time, so arm the pacing
                         // timer to pause transmission until the earliest departure time.
+                        // Queues that pace transmissions can be given packets early.
 
                         //= https://www.rfc-editor.org/rfc/rfc9002#section-7.7
                         //# A sender SHOULD pace sending of all in-flight packets based on input
@@ -947,7 +1057,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
 
                         //= https://www.rfc-editor.org/rfc/rfc9002#section-7.7
                         //# Senders MUST either use pacing or limit such bursts.
-                        self.timers.pacing_timer.set(edt);
+                        self.timers.pacing_timer.set(edt - max_delay);
                     }
                 }
 
@@ -969,6 +1079,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 count += self.path_validation_only_transmission(
                     queue,
                     timestamp,
+                    now,
                     &mut outcome,
                     subscriber,
                     packet_interceptor,
@@ -1134,6 +1245,9 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         datagram: &mut Config::DatagramEndpoint,
         dc: &mut Config::DcEndpoint,
         conn_limits: &mut Config::ConnectionLimits,
+        congestion_controller_endpoint: &mut Config::CongestionControllerEndpoint,
+        mtu: &mut mtu::Manager<Config::Mtu>,
+        random_generator: &mut Config::RandomGenerator,
     ) -> Result<(), connection::Error> {
         // reset the queued state first so that new wakeup request are not missed
         self.wakeup_handle.wakeup_handled();
@@ -1144,6 +1258,62 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         // return an error if the application set one
         self.error?;
 
+        if let Some(local_address) = self.migration_request.take() {
+            let mut publisher = self.event_context.publisher(timestamp, subscriber);
+
+            // The request was checked when it was made, so this only fails if the
+            // connection state changed in the meantime. In that case the connection
+            // keeps using the current path and the application is notified with an event.
+            if self
+                .path_manager
+                .migrate(
+                    local_address,
+                    congestion_controller_endpoint,
+                    mtu,
+                    &self.limits,
+                    random_generator,
+                    &mut publisher,
+                )
+                .is_err()
+            {
+                publisher.on_active_migration_updated(event::builder::ActiveMigrationUpdated {
+                    status: event::builder::ActiveMigrationStatus::Aborted,
+                    local_addr: local_address.into_event(),
+                });
+            }
+        }
+
+        if core::mem::take(&mut self.congestion_controller_update_pending) {
+            self.path_manager.on_handshake_complete(
+                self.space_manager.application_protocol.clone(),
+                self.space_manager.server_name.clone(),
+                congestion_controller_endpoint,
+                mtu,
+            );
+        }
+
+        if core::mem::take(&mut self.mtu_probe_requested) {
+            let mut publisher = self.event_context.publisher(timestamp, subscriber);
+            let path_id = self.path_manager.active_path_id();
+
+            self.path_manager
+                .active_path_mut()
+                .mtu_controller
+                .request_probe(path_id, &mut publisher);
+        }
+
+        if self.space_manager.is_handshake_confirmed() {
+            let mut publisher = self.event_context.publisher(timestamp, subscriber);
+
+            self.path_manager.migrate_to_preferred_address(
+                congestion_controller_endpoint,
+                mtu,
+                &self.limits,
+                random_generator,
+                &mut publisher,
+            );
+        }
+
         Ok(())
     }
 
@@ -1215,6 +1385,29 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         Ok(id)
     }
 
+    fn on_packet_too_big(
+        &mut self,
+        packet_too_big: &icmp::PacketTooBig,
+        timestamp: Timestamp,
+        subscriber: &mut Config::EventSubscriber,
+    ) -> bool {
+        let mut publisher = self.event_context.publisher(timestamp, subscriber);
+
+        match self
+            .path_manager
+            .on_packet_too_big(packet_too_big, timestamp, &mut publisher)
+        {
+            Some(mtu::MtuResult::MtuUpdated(max_datagram_size)) => {
+                if let Some((space, _)) = self.space_manager.application_mut() {
+                    space.dc_manager.on_mtu_updated(max_datagram_size);
+                }
+                true
+            }
+            Some(mtu::MtuResult::NoChange) => true,
+            None => false,
+        }
+    }
+
     /// Is called when a initial packet had been received
     fn handle_initial_packet(
         &mut self,
@@ -1242,15 +1435,46 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         //# subsequent Initial packets include a different Source Connection ID,
         //# they MUST be discarded.
 
+        // The server's first Initial packet may use a compatible version, which requires
+        // switching to the Initial keys for that version
+        let version = packet.version;
+        let compatible_keys = if Config::ENDPOINT_TYPE.is_client() && version != self.quic_version()
+        {
+            Some(
+                self.space_manager
+                    .version_negotiation()
+                    .initial_keys(Config::ENDPOINT_TYPE, version),
+            )
+        } else {
+            None
+        };
+
         if let Some((space, _status)) = self.space_manager.initial_mut() {
             let mut publisher = self.event_context.publisher(datagram.timestamp, subscriber);
 
-            let packet = space.validate_and_decrypt_packet(
+            let previous_keys = compatible_keys.map(|(key, header_key)| {
+                (
+                    core::mem::replace(&mut space.key, key),
+                    core::mem::replace(&mut space.header_key, header_key),
+                )
+            });
+
+            let packet = match space.validate_and_decrypt_packet(
                 packet,
                 path_id,
                 &self.path_manager[path_id],
                 &mut publisher,
-            )?;
+            ) {
+                Ok(packet) => packet,
+                Err(err) => {
+                    // The packet wasn't sent by the server so keep using the original version
+                    if let Some((key, header_key)) = previous_keys {
+                        space.key = key;
+                        space.header_key = header_key;
+                    }
+                    return Err(err);
+                }
+            };
 
             publisher.on_packet_received(event::builder::PacketReceived {
                 packet_header: event::builder::PacketHeader::new(
@@ -1259,6 +1483,20 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 ),
             });
 
+            if previous_keys.is_some() {
+                self.space_manager
+                    .version_negotiation_mut()
+                    .on_version_negotiated(version);
+                self.event_context.quic_version = version;
+            } else if Config::ENDPOINT_TYPE.is_server()
+                && version == self.event_context.quic_version
+            {
+                // Servers retain the keys for the client's original version until the client
+                // starts using the negotiated version.
+                // See https://www.rfc-editor.org/rfc/rfc9368#section-2.3
+                space.on_negotiated_version_packet();
+            }
+
             self.handle_cleartext_initial_packet(
                 datagram,
                 path_id,
@@ -1270,6 +1508,14 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 dc_endpoint,
                 connection_limits_endpoint,
             )?;
+
+            // Once an Initial packet from the server has been processed, the version can no
+            // longer change
+            if Config::ENDPOINT_TYPE.is_client() {
+                self.space_manager
+                    .version_negotiation_mut()
+                    .on_packet_processed();
+            }
         }
 
         Ok(())
@@ -1523,10 +1769,17 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             return Err(ProcessingError::Other);
         }
 
+        // With multipath, each connection ID used by the peer selects a packet number space
+        let rx_path_id = self.path_manager.rx_path_id(
+            self.local_id_registry
+                .sequence_number(&datagram.destination_connection_id),
+        );
+
         if let Some((space, handshake_status)) = self.space_manager.application_mut() {
             let packet = space.validate_and_decrypt_packet(
                 packet,
                 datagram,
+                rx_path_id,
                 path_id,
                 &self.path_manager[path_id],
                 &mut publisher,
@@ -1647,20 +1900,14 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
     fn handle_zero_rtt_packet(
         &mut self,
         datagram: &DatagramInfo,
-        _path_id: path::Id,
-        _packet: ProtectedZeroRtt,
+        path_id: path::Id,
+        packet: ProtectedZeroRtt,
+        random_generator: &mut Config::RandomGenerator,
         subscriber: &mut Config::EventSubscriber,
-        _packet_interceptor: &mut Config::PacketInterceptor,
+        packet_interceptor: &mut Config::PacketInterceptor,
     ) -> Result<(), ProcessingError> {
         let mut publisher = self.event_context.publisher(datagram.timestamp, subscriber);
 
-        publisher.on_packet_received(event::builder::PacketReceived {
-            packet_header: event::builder::PacketHeader::ZeroRtt {
-                // FIXME: replace with PacketHeader::new when we support zero-rtt.
-                number: 0,
-                version: publisher.quic_version(),
-            },
-        });
         //= https://www.rfc-editor.org/rfc/rfc9000#section-5.2.2
         //= type=TODO
         //= tracking-issue=339
@@ -1668,7 +1915,57 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         //# number of these packets in anticipation of a late-arriving Initial
         //# packet.
 
-        // TODO
+        // 0-RTT packets are dropped if they arrive before the application space is created, after
+        // early data was rejected, or after the 0-RTT keys have been discarded. Clients never
+        // have keys to process 0-RTT packets, since servers don't send them.
+        let decrypted = match self.space_manager.application_mut() {
+            Some((space, handshake_status)) if Config::ENDPOINT_TYPE.is_server() => space
+                .validate_and_decrypt_zero_rtt_packet(
+                    packet,
+                    datagram,
+                    path_id,
+                    &self.path_manager[path_id],
+                    &mut publisher,
+                )
+                .map(|packet| (packet, space, handshake_status)),
+            _ => None,
+        };
+
+        let Some((packet, space, handshake_status)) = decrypted else {
+            let path = &self.path_manager[path_id];
+            publisher.on_packet_dropped(event::builder::PacketDropped {
+                reason: event::builder::PacketDropReason::UnprotectFailed {
+                    space: event::builder::KeySpace::ZeroRtt,
+                    path: path_event!(path, path_id),
+                },
+            });
+            return Ok(());
+        };
+        let packet = packet?;
+
+        publisher.on_packet_received(event::builder::PacketReceived {
+            packet_header: event::builder::PacketHeader::ZeroRtt {
+                number: packet.packet_number.into_event(),
+                version: publisher.quic_version(),
+            },
+        });
+
+        let processed_packet = ZeroRttSpace::new(space).handle_cleartext_payload(
+            packet.packet_number,
+            packet.payload,
+            datagram,
+            path_id,
+            &mut self.path_manager,
+            handshake_status,
+            &mut self.local_id_registry,
+            random_generator,
+            &mut publisher,
+            packet_interceptor,
+        )?;
+
+        // notify the connection a packet was processed
+        self.on_processed_packet(&processed_packet, subscriber)?;
+
         Ok(())
     }
 
@@ -1958,6 +2255,10 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         self.space_manager.application_protocol.clone()
     }
 
+    fn early_data_status(&self) -> EarlyDataStatus {
+        self.space_manager.early_data_status()
+    }
+
     fn take_tls_context(&mut self) -> Option<Box<dyn Any + Send>> {
         self.space_manager.tls_context.take()
     }
@@ -2002,10 +2303,51 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         Ok(*self.path_manager.active_path().handle.local_address())
     }
 
+    fn migrate(&mut self, local_address: SocketAddress) -> Result<(), migration::Error> {
+        self.error?;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+        //# An endpoint MUST NOT initiate
+        //# connection migration before the handshake is confirmed, as defined
+        //# in section 4.1.2 of [QUIC-TLS].
+        if !self.space_manager.is_handshake_confirmed() {
+            return Err(migration::Error::HandshakeNotConfirmed);
+        }
+
+        let local_address = local_address.into();
+
+        if self.migration_request.is_some() {
+            return Err(migration::Error::InProgress);
+        }
+
+        self.path_manager.can_migrate(&local_address)?;
+
+        self.migration_request = Some(local_address);
+        self.wakeup_handle.wakeup();
+
+        Ok(())
+    }
+
     fn remote_address(&self) -> Result<SocketAddress, connection::Error> {
         Ok(*self.path_manager.active_path().handle.remote_address())
     }
 
+    fn mtu(&self) -> Result<u16, connection::Error> {
+        self.error?;
+
+        let mtu_controller = &self.path_manager.active_path().mtu_controller;
+        Ok(mtu_controller.max_datagram_size() as u16)
+    }
+
+    fn probe_mtu(&mut self) -> Result<(), connection::Error> {
+        self.error?;
+
+        self.mtu_probe_requested = true;
+        self.wakeup_handle.wakeup();
+
+        Ok(())
+    }
+
     fn error(&self) -> Option<connection::Error> {
         self.error.err()
     }
@@ -2032,6 +2374,16 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         }
     }
 
+    #[inline]
+    fn congestion_controller_mut(&mut self, query: &mut dyn query::QueryMut) {
+        let path = self.path_manager.active_path_mut();
+        if let query::ControlFlow::Break = query.execute_mut(&mut path.congestion_controller) {
+            // The query may have changed the state of the congestion controller, so wake up
+            // the connection to re-evaluate its transmission interest
+            self.wakeup_handle.wakeup();
+        }
+    }
+
     fn with_event_publisher<F>(
         &mut self,
         timestamp: Timestamp,
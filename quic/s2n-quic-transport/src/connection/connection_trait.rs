@@ -23,8 +23,9 @@ use s2n_codec::DecoderBufferMut;
 use s2n_quic_core::{
     application,
     application::ServerName,
+    crypto::tls::EarlyDataStatus,
     event::{self, builder::DatagramDropReason, supervisor, ConnectionPublisher, IntoEvent},
-    inet::{DatagramInfo, SocketAddress},
+    inet::{icmp, DatagramInfo, SocketAddress},
     io::tx,
     packet::{
         handshake::ProtectedHandshake,
@@ -35,9 +36,9 @@ use s2n_quic_core::{
         zero_rtt::ProtectedZeroRtt,
         ProtectedPacket,
     },
-    path::{mtu, Handle as _},
+    path::{migration, mtu, Handle as _},
     query,
-    time::Timestamp,
+    time::{Clock, Timestamp},
 };
 
 /// A trait which represents an internally used `Connection`
@@ -85,15 +86,20 @@ pub trait ConnectionTrait: 'static + Send + Sized {
     ) -> Result<(), LocalIdRegistrationError>;
 
     /// Queries the connection for outgoing packets
-    fn on_transmit<Tx>(
+    ///
+    /// `timestamp` is the time the endpoint started transmitting, while `clock` is sampled when
+    /// the connection starts transmitting to compute the departure delays of its packets.
+    fn on_transmit<Tx, C>(
         &mut self,
         queue: &mut Tx,
         timestamp: Timestamp,
+        clock: &C,
         subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
         packet_interceptor: &mut <Self::Config as endpoint::Config>::PacketInterceptor,
     ) -> Result<(), ConnectionOnTransmitError>
     where
-        Tx: tx::Queue<Handle = <Self::Config as endpoint::Config>::PathHandle>;
+        Tx: tx::Queue<Handle = <Self::Config as endpoint::Config>::PathHandle>,
+        C: Clock;
 
     /// Handles all timeouts on the `Connection`.
     ///
@@ -115,6 +121,9 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         datagram: &mut <Self::Config as endpoint::Config>::DatagramEndpoint,
         dc_endpoint: &mut <Self::Config as endpoint::Config>::DcEndpoint,
         conn_limits: &mut <Self::Config as endpoint::Config>::ConnectionLimits,
+        congestion_controller_endpoint: &mut <Self::Config as endpoint::Config>::CongestionControllerEndpoint,
+        mtu: &mut mtu::Manager<<Self::Config as endpoint::Config>::Mtu>,
+        random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
     ) -> Result<(), connection::Error>;
 
     // Packet handling
@@ -191,6 +200,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         datagram: &DatagramInfo,
         path_id: path::Id,
         packet: ProtectedZeroRtt,
+        random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
         subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
         packet_interceptor: &mut <Self::Config as endpoint::Config>::PacketInterceptor,
     ) -> Result<(), ProcessingError>;
@@ -216,12 +226,28 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
     ) -> Result<path::Id, DatagramDropReason>;
 
+    /// Notifies a connection it has received an ICMP Packet Too Big message
+    ///
+    /// Returns `true` if the message quoted a datagram that was sent by the connection.
+    fn on_packet_too_big(
+        &mut self,
+        packet_too_big: &icmp::PacketTooBig,
+        timestamp: Timestamp,
+        subscriber: &mut <Self::Config as endpoint::Config>::EventSubscriber,
+    ) -> bool;
+
     /// Returns the Connections interests
     fn interests(&self) -> ConnectionInterests;
 
     /// Returns the QUIC version selected for the current connection
     fn quic_version(&self) -> u32;
 
+    /// Returns `true` if the connection can switch to the version of the given packet
+    ///
+    /// Clients switch to a compatible version selected by the server when processing
+    /// the server's first Initial packet.
+    fn is_compatible_version(&self, packet: &ProtectedPacket) -> bool;
+
     /// Handles reception of a single QUIC packet
     fn handle_packet(
         &mut self,
@@ -254,7 +280,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         //# If a client receives a packet that uses a different version than it
         //# initially selected, it MUST discard that packet.
         if let Some(version) = packet.version() {
-            if version != self.quic_version() {
+            if version != self.quic_version() && !self.is_compatible_version(&packet) {
                 emit_drop_reason!(|path| event::builder::PacketDropReason::VersionMismatch {
                     version,
                     path: path_event!(path, path_id),
@@ -319,6 +345,7 @@ pub trait ConnectionTrait: 'static + Send + Sized {
                 datagram,
                 path_id,
                 packet,
+                random_generator,
                 subscriber,
                 packet_interceptor,
             ),
@@ -490,14 +517,22 @@ pub trait ConnectionTrait: 'static + Send + Sized {
 
     fn application_protocol(&self) -> Bytes;
 
+    fn early_data_status(&self) -> EarlyDataStatus;
+
     fn ping(&mut self) -> Result<(), connection::Error>;
 
     fn keep_alive(&mut self, enabled: bool) -> Result<(), connection::Error>;
 
     fn local_address(&self) -> Result<SocketAddress, connection::Error>;
 
+    fn migrate(&mut self, local_address: SocketAddress) -> Result<(), migration::Error>;
+
     fn remote_address(&self) -> Result<SocketAddress, connection::Error>;
 
+    fn mtu(&self) -> Result<u16, connection::Error>;
+
+    fn probe_mtu(&mut self) -> Result<(), connection::Error>;
+
     fn error(&self) -> Option<connection::Error>;
 
     fn query_event_context(&self, query: &mut dyn query::Query);
@@ -506,6 +541,8 @@ pub trait ConnectionTrait: 'static + Send + Sized {
 
     fn datagram_mut(&mut self, query: &mut dyn query::QueryMut);
 
+    fn congestion_controller_mut(&mut self, query: &mut dyn query::QueryMut);
+
     fn with_event_publisher<F>(
         &mut self,
         timestamp: Timestamp,
@@ -317,6 +317,14 @@ impl LocalIdRegistry {
         self.internal_id
     }
 
+    /// Returns the sequence number of the given connection ID, if it is registered
+    pub fn sequence_number(&self, id: &connection::LocalId) -> Option<u32> {
+        self.registered_ids
+            .iter()
+            .find(|id_info| id_info.id == *id)
+            .map(|id_info| id_info.sequence_number)
+    }
+
     /// Sets the active connection id limit
     pub fn set_active_connection_id_limit(&mut self, active_connection_id_limit: u64) {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
@@ -385,6 +393,45 @@ impl LocalIdRegistry {
         Ok(())
     }
 
+    /// Registers the connection ID sent in the `preferred_address` transport parameter
+    ///
+    /// The connection ID is issued to the peer in the transport parameters, so it
+    /// starts in the `Active` status and is never sent in a NEW_CONNECTION_ID frame.
+    pub fn register_preferred_address_connection_id(
+        &mut self,
+        id: &connection::LocalId,
+        expiration: Option<Timestamp>,
+        stateless_reset_token: stateless_reset::Token,
+    ) -> Result<(), LocalIdRegistrationError> {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
+        //# If the preferred_address transport
+        //# parameter is sent, the sequence number of the supplied connection ID
+        //# is 1.
+        if self.next_sequence_number != 1 {
+            return Err(LocalIdRegistrationError::InvalidSequenceNumber);
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
+        //# The value of the
+        //# active_connection_id_limit parameter MUST be at least 2.
+        self.active_connection_id_limit = self.active_connection_id_limit.max(2);
+
+        self.register_connection_id(id, expiration, stateless_reset_token)?;
+
+        let id_info = self
+            .registered_ids
+            .iter_mut()
+            .find(|id_info| id_info.sequence_number == 1)
+            .expect("preferred address id added above");
+
+        id_info.status = Active;
+        self.transmission_interest.clear();
+
+        self.check_consistency();
+
+        Ok(())
+    }
+
     /// Unregisters connection IDs that have expired
     fn unregister_expired_ids(&mut self, timestamp: Timestamp) {
         {
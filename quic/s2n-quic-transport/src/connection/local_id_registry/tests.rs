@@ -956,3 +956,49 @@ fn on_handshake_confirmed_rotate_handshake_connection_id_disabled() {
         assert_eq!(None, id_info.retirement_time);
     }
 }
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
+//= type=test
+//# If the preferred_address transport
+//# parameter is sent, the sequence number of the supplied connection ID
+//# is 1.
+#[test]
+fn preferred_address_connection_id() {
+    let ext_id_1 = id(b"id01");
+    let ext_id_2 = id(b"id02");
+    let ext_id_3 = id(b"id03");
+
+    let (_, mut reg1) = mapper(ext_id_1, None, TEST_TOKEN_1);
+
+    assert!(reg1
+        .register_preferred_address_connection_id(&ext_id_2, None, TEST_TOKEN_2)
+        .is_ok());
+    assert_eq!(Some(1), reg1.sequence_number(&ext_id_2));
+
+    // The connection ID was issued in the transport parameters, so it is not
+    // sent in a NEW_CONNECTION_ID frame
+    assert_eq!(
+        Active,
+        reg1.get_connection_id_info(&ext_id_2).unwrap().status
+    );
+    assert_eq!(
+        transmission::Interest::None,
+        reg1.get_transmission_interest()
+    );
+    assert_eq!(
+        connection::id::Interest::None,
+        reg1.connection_id_interest()
+    );
+
+    // Only the first connection ID after the handshake connection ID can be used
+    assert_eq!(
+        Err(LocalIdRegistrationError::InvalidSequenceNumber),
+        reg1.register_preferred_address_connection_id(&ext_id_3, None, TEST_TOKEN_3)
+    );
+
+    reg1.set_active_connection_id_limit(3);
+    assert_eq!(
+        connection::id::Interest::New(1),
+        reg1.connection_id_interest()
+    );
+}
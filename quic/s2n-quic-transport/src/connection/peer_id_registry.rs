@@ -239,6 +239,11 @@ impl Drop for PeerIdRegistry {
             {
                 guard.stateless_reset_map.remove(&token);
             }
+
+            // Stop routing ICMP messages for the connection IDs
+            for id_info in self.registered_ids.iter() {
+                guard.peer_id_map.remove(&id_info.id, self.internal_id);
+            }
         }
     }
 }
@@ -304,6 +309,12 @@ impl PeerIdRegistry {
             status,
         });
 
+        self.state
+            .lock()
+            .expect("should succeed unless the lock is poisoned")
+            .peer_id_map
+            .insert(peer_id, self.internal_id);
+
         self.check_consistency();
     }
 
@@ -492,6 +503,11 @@ impl PeerIdRegistry {
                         mapper_state.stateless_reset_map.remove(&token);
                     }
 
+                    // The peer won't receive any more packets with the connection ID
+                    mapper_state
+                        .peer_id_map
+                        .remove(&id_info.id, self.internal_id);
+
                     self.ack_interest.clear();
 
                     //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.2
@@ -528,6 +544,21 @@ impl PeerIdRegistry {
         self.check_consistency();
     }
 
+    /// Returns the sequence number of the given peer_id, if it is registered
+    pub fn sequence_number(&self, peer_id: &connection::PeerId) -> Option<u32> {
+        self.registered_ids
+            .iter()
+            .find(|id_info| id_info.id == *peer_id)
+            .map(|id_info| id_info.sequence_number)
+    }
+
+    /// Returns true if a peer_id is available to be consumed for a new path
+    pub fn has_new_id(&self) -> bool {
+        self.registered_ids
+            .iter()
+            .any(|id_info| id_info.status == New)
+    }
+
     /// Checks if the peer_id exists and if it is active.
     pub fn is_active(&self, peer_id: &connection::PeerId) -> bool {
         self.registered_ids
@@ -541,19 +572,27 @@ impl PeerIdRegistry {
     fn consume_new_id_inner(&mut self) -> Option<connection::PeerId> {
         for id_info in self.registered_ids.iter_mut() {
             if id_info.status == New {
+                let mut mapper_state = self
+                    .state
+                    .lock()
+                    .expect("should succeed unless the lock is poisoned");
+
                 // Start tracking the stateless reset token
                 //= https://www.rfc-editor.org/rfc/rfc9000#section-10.3.1
                 //# An endpoint MUST NOT check for any stateless reset tokens associated
                 //# with connection IDs it has not used or for connection IDs that have
                 //# been retired.
                 if let Some(token) = id_info.stateless_reset_token {
-                    self.state
-                        .lock()
-                        .expect("should succeed unless the lock is poisoned")
+                    mapper_state
                         .stateless_reset_map
                         .insert(token, self.internal_id);
                 }
 
+                // Route ICMP messages quoting packets sent with the connection ID
+                mapper_state
+                    .peer_id_map
+                    .insert(id_info.id, self.internal_id);
+
                 // Consume the new id
                 id_info.status = InUse;
                 return Some(id_info.id);
@@ -45,6 +45,9 @@ impl<Config: endpoint::Config> ConnectionTransmissionContext<'_, '_, Config> {
 pub struct ConnectionTransmission<'a, 'sub, Config: endpoint::Config> {
     pub context: ConnectionTransmissionContext<'a, 'sub, Config>,
     pub space_manager: &'a mut PacketSpaceManager<Config>,
+    /// The time the connection started pushing packets to the queue, which is sampled after
+    /// `context.timestamp` and used to compute departure delays
+    pub now: Timestamp,
 }
 
 impl<Config: endpoint::Config> tx::Message for ConnectionTransmission<'_, '_, Config> {
@@ -62,8 +65,19 @@ impl<Config: endpoint::Config> tx::Message for ConnectionTransmission<'_, '_, Co
 
     #[inline]
     fn delay(&mut self) -> Duration {
-        // TODO return delay from pacer
-        Default::default()
+        // The departure time is only in the future if the queue accepts packets ahead of the
+        // pacer, in which case the queue holds the packet until it's due.
+        self.context
+            .path()
+            .congestion_controller
+            .earliest_departure_time()
+            .map_or(Duration::ZERO, |edt| {
+                // `context.timestamp` was sampled before the endpoint started transmitting, so
+                // the delay is computed against the time this connection started transmitting
+                // instead. Otherwise, the time spent on other connections would be added to the
+                // departure time.
+                edt.saturating_duration_since(self.now)
+            })
     }
 
     #[inline]
@@ -3,10 +3,10 @@
 
 //! Configuration parameters for `Endpoint`s
 
-use crate::{connection, stream};
+use crate::{connection, path::scheduler, stream};
 use s2n_quic_core::{
     crypto::tls, datagram, dc, endpoint, event, packet, path, path::mtu, random,
-    recovery::congestion_controller, stateless_reset,
+    recovery::congestion_controller, stateless_reset, version,
 };
 
 /// Configuration parameters for a QUIC endpoint
@@ -42,6 +42,8 @@ pub trait Config: 'static + Send + Sized + core::fmt::Debug {
     type PathHandle: path::Handle;
     /// The path migration validator for the endpoint
     type PathMigrationValidator: path::migration::Validator;
+    /// The multipath scheduler for the endpoint
+    type PathScheduler: scheduler::Endpoint;
     /// The packet_interceptor implementation for the endpoint
     type PacketInterceptor: packet::interceptor::Interceptor;
     /// The datagram implementation for the endpoint
@@ -92,9 +94,14 @@ pub struct Context<'a, Cfg: Config> {
 
     pub path_migration: &'a mut Cfg::PathMigrationValidator,
 
+    pub path_scheduler: &'a mut Cfg::PathScheduler,
+
     pub packet_interceptor: &'a mut Cfg::PacketInterceptor,
 
     pub datagram: &'a mut Cfg::DatagramEndpoint,
 
     pub dc: &'a mut Cfg::DcEndpoint,
+
+    /// The QUIC versions enabled on the endpoint
+    pub version: &'a version::Config,
 }
@@ -10,7 +10,7 @@ use crate::{
     },
     endpoint,
     recovery::congestion_controller::{self, Endpoint as _},
-    space::PacketSpaceManager,
+    space::{PacketSpaceManager, VersionNegotiation},
 };
 use core::convert::TryInto;
 use s2n_codec::DecoderBufferMut;
@@ -22,7 +22,10 @@ use s2n_quic_core::{
     packet::initial::ProtectedInitial,
     path::Handle as _,
     stateless_reset::token::Generator as _,
-    transport::{self, parameters::ServerTransportParameters},
+    transport::{
+        self,
+        parameters::{PreferredAddress, ServerTransportParameters},
+    },
 };
 
 impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
@@ -106,6 +109,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
         //# a change to the keys used to protect the Initial packet.
         let (initial_key, initial_header_key) =
             <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey::new_server(
+                packet.version,
                 datagram.destination_connection_id.as_bytes(),
             );
 
@@ -138,7 +142,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             .stateless_reset_token_generator
             .generate(initial_connection_id.as_bytes());
 
-        let local_id_registry = self.connection_id_mapper.create_local_id_registry(
+        let mut local_id_registry = self.connection_id_mapper.create_local_id_registry(
             internal_connection_id,
             &initial_connection_id,
             initial_connection_id_expiration_time,
@@ -172,6 +176,39 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
 
         transport_parameters.load_limits(&limits);
 
+        let (ipv4_address, ipv6_address) = limits.preferred_address();
+        if ipv4_address.is_some() || ipv6_address.is_some() {
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-9.6.1
+            //# A server conveys a preferred address by including the
+            //# preferred_address transport parameter in the TLS handshake.
+            let context = self.config.context();
+            let connection_id = context
+                .connection_id_format
+                .generate(&ConnectionInfo::new(&remote_address));
+            let expiration = context
+                .connection_id_format
+                .lifetime()
+                .map(|duration| datagram.timestamp + duration);
+            let stateless_reset_token = context
+                .stateless_reset_token_generator
+                .generate(connection_id.as_bytes());
+
+            local_id_registry
+                .register_preferred_address_connection_id(
+                    &connection_id,
+                    expiration,
+                    stateless_reset_token,
+                )
+                .map_err(|err| transport::Error::INTERNAL_ERROR.with_reason(err.message()))?;
+
+            transport_parameters.preferred_address = Some(PreferredAddress {
+                ipv4_address,
+                ipv6_address,
+                connection_id: connection_id.into(),
+                stateless_reset_token,
+            });
+        }
+
         //= https://www.rfc-editor.org/rfc/rfc9000#section-7.3
         //# A server includes the Destination Connection ID field from the first
         //# Initial packet it received from the client in the
@@ -226,12 +263,33 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             .try_into()
             .expect("Failed to convert max_datagram_frame_size");
 
+        let quic_version = packet.version;
+
+        // Only switch to a compatible version if the TLS provider is able to derive the
+        // handshake keys for the version selected after the ClientHello is processed
+        let versions = if endpoint_context
+            .tls
+            .supports_compatible_version_negotiation()
+        {
+            *endpoint_context.version
+        } else {
+            s2n_quic_core::version::Config::builder()
+                .with_versions(&[quic_version])
+                .and_then(|builder| builder.build())
+                .expect("packet version is supported")
+        };
+        // The version_information transport parameter is appended to the server's transport
+        // parameters once the version is selected in `on_client_application_params`.
+        let version_negotiation = VersionNegotiation::new(
+            versions,
+            quic_version,
+            datagram.destination_connection_id.as_bytes(),
+        );
+
         let tls_session = endpoint_context
             .tls
             .new_server_session(&transport_parameters);
 
-        let quic_version = packet.version;
-
         let meta = event::builder::ConnectionMeta {
             endpoint_type: Config::ENDPOINT_TYPE,
             id: internal_connection_id.into(),
@@ -247,7 +305,11 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
 
         let mut event_context = endpoint_context.event_subscriber.create_connection_context(
             &meta.clone().into_event(),
-            &event::builder::ConnectionInfo {}.into_event(),
+            &event::builder::ConnectionInfo {
+                original_destination_connection_id: (&original_destination_connection_id)
+                    .into_event(),
+            }
+            .into_event(),
         );
 
         let mut endpoint_publisher = event::EndpointPublisherSubscriber::new(
@@ -288,6 +350,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             tls_session,
             initial_key,
             initial_header_key,
+            version_negotiation,
             datagram.timestamp,
             &mut publisher,
         );
@@ -316,6 +379,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             dc_endpoint: endpoint_context.dc,
             open_registry: None,
             limits_endpoint: endpoint_context.connection_limits,
+            path_scheduler_endpoint: endpoint_context.path_scheduler,
         };
 
         let mut connection = <Config as endpoint::Config>::Connection::new(connection_parameters)?;
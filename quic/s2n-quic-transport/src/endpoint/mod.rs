@@ -13,7 +13,7 @@ use crate::{
     endpoint,
     endpoint::close::CloseHandle,
     recovery::congestion_controller::{self, Endpoint as _},
-    space::PacketSpaceManager,
+    space::{PacketSpaceManager, VersionNegotiation},
     wakeup_queue::WakeupQueue,
 };
 use alloc::collections::VecDeque;
@@ -35,7 +35,7 @@ use s2n_quic_core::{
     event::{
         self, supervisor, ConnectionPublisher, EndpointPublisher as _, IntoEvent, Subscriber as _,
     },
-    inet::{datagram, DatagramInfo},
+    inet::{datagram, icmp, DatagramInfo},
     io::{rx, tx},
     packet::{initial::ProtectedInitial, interceptor::Interceptor, ProtectedPacket},
     path,
@@ -87,6 +87,8 @@ pub struct Endpoint<Cfg: Config> {
     retry_dispatch: retry::Dispatch<Cfg::PathHandle>,
     stateless_reset_dispatch: stateless_reset::Dispatch<Cfg::PathHandle>,
     close_packet_buffer: packet_buffer::Buffer,
+    /// Limits the number of ICMP Packet Too Big messages that are processed
+    packet_too_big_limiter: icmp::RateLimiter,
 }
 
 impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
@@ -113,6 +115,18 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
 
             self.receive_datagram(&mut header, payload, timestamp)
         });
+
+        queue.for_each_packet_too_big(|packet_too_big| {
+            let timestamp = match now {
+                Some(time) => time,
+                None => {
+                    now = Some(clock.get_time());
+                    now.expect("value should be set")
+                }
+            };
+
+            self.on_packet_too_big(packet_too_big, timestamp)
+        });
     }
 
     fn transmit<Tx, C>(&mut self, queue: &mut Tx, clock: &C)
@@ -143,6 +157,7 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
             let _ = connection.on_transmit(
                 queue,
                 timestamp,
+                clock,
                 endpoint_context.event_subscriber,
                 endpoint_context.packet_interceptor,
             );
@@ -215,6 +230,9 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
                     endpoint_context.datagram,
                     endpoint_context.dc,
                     endpoint_context.connection_limits,
+                    endpoint_context.congestion_controller,
+                    endpoint_context.mtu,
+                    endpoint_context.random_generator,
                 ) {
                     conn.close(
                         error,
@@ -303,6 +321,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
 
         let connection_id_mapper =
             ConnectionIdMapper::new(config.context().random_generator, Cfg::ENDPOINT_TYPE);
+        let version_negotiator = version::Negotiator::new(*config.context().version);
 
         let endpoint = Self {
             config,
@@ -312,10 +331,11 @@ impl<Cfg: Config> Endpoint<Cfg> {
             wakeup_queue: WakeupQueue::new(),
             close_handle,
             dequeued_wakeups: VecDeque::new(),
-            version_negotiator: version::Negotiator::default(),
+            version_negotiator,
             retry_dispatch: retry::Dispatch::default(),
             stateless_reset_dispatch: stateless_reset::Dispatch::default(),
             close_packet_buffer: Default::default(),
+            packet_too_big_limiter: Default::default(),
         };
 
         (endpoint, handle)
@@ -415,6 +435,35 @@ impl<Cfg: Config> Endpoint<Cfg> {
         }
     }
 
+    /// Forwards an ICMP Packet Too Big message to the connection that sent the quoted datagram
+    fn on_packet_too_big(&mut self, packet_too_big: &icmp::PacketTooBig, timestamp: Timestamp) {
+        // ICMP messages aren't authenticated, so limit the work that forged messages can cause
+        if !self.packet_too_big_limiter.on_message(timestamp) {
+            return;
+        }
+
+        let endpoint_context = self.config.context();
+
+        // The quoted datagram contains the peer's connection ID rather than one of ours, so
+        // the connection is looked up by the peer connection IDs it is using
+        for peer_id in packet_too_big.destination_connection_ids() {
+            let Some(internal_id) = self
+                .connection_id_mapper
+                .lookup_internal_connection_id_by_peer_id(&peer_id)
+            else {
+                continue;
+            };
+
+            let is_quoted = self.connections.with_connection(internal_id, |conn| {
+                conn.on_packet_too_big(packet_too_big, timestamp, endpoint_context.event_subscriber)
+            });
+
+            if matches!(is_quoted, Some((true, _))) {
+                break;
+            }
+        }
+    }
+
     /// Ingests a single datagram
     fn receive_datagram(
         &mut self,
@@ -1090,10 +1139,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
             .connection_id_mapper
             .create_client_peer_id_registry(internal_connection_id, rotate_handshake_connection_id);
 
-        //= https://www.rfc-editor.org/rfc/rfc9000#section-15
-        //# This version of the specification is identified by the number
-        //# 0x00000001.
-        let quic_version = 0x00000001;
+        // Clients initiate connections with the most preferred version
+        let quic_version = endpoint_context.version.initial_version();
 
         let meta = event::builder::ConnectionMeta {
             endpoint_type: Cfg::ENDPOINT_TYPE,
@@ -1108,7 +1155,11 @@ impl<Cfg: Config> Endpoint<Cfg> {
         );
         let mut event_context = endpoint_context.event_subscriber.create_connection_context(
             &meta.clone().into_event(),
-            &event::builder::ConnectionInfo {}.into_event(),
+            &event::builder::ConnectionInfo {
+                original_destination_connection_id: (&original_destination_connection_id)
+                    .into_event(),
+            }
+            .into_event(),
         );
 
         let mut transport_parameters = ClientTransportParameters {
@@ -1174,6 +1225,26 @@ impl<Cfg: Config> Endpoint<Cfg> {
                 DcSupportedVersions::for_client(dc::SUPPORTED_VERSIONS);
         }
 
+        // Only advertise the other enabled versions if the TLS provider is able to switch
+        // to a version selected by the server
+        let versions = if endpoint_context
+            .tls
+            .supports_compatible_version_negotiation()
+        {
+            *endpoint_context.version
+        } else {
+            s2n_quic_core::version::Config::builder()
+                .with_versions(&[quic_version])
+                .and_then(|builder| builder.build())
+                .expect("initial version is supported")
+        };
+        let version_negotiation = VersionNegotiation::new(
+            versions,
+            quic_version,
+            original_destination_connection_id.as_bytes(),
+        );
+        transport_parameters.version_information = Some(version_negotiation.version_information());
+
         //= https://www.rfc-editor.org/rfc/rfc9000#section-7.2
         //# The Destination Connection ID field from the first Initial packet
         //# sent by a client is used to determine packet protection keys for
@@ -1183,6 +1254,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
         // protection keys.
         let (initial_key, initial_header_key) =
             <<Cfg::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey::new_client(
+                quic_version,
                 original_destination_connection_id.as_bytes(),
             );
         let tls_session = endpoint_context
@@ -1198,6 +1270,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
             tls_session,
             initial_key,
             initial_header_key,
+            version_negotiation,
             timestamp,
             &mut publisher,
         );
@@ -1232,6 +1305,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
             dc_endpoint: endpoint_context.dc,
             open_registry,
             limits_endpoint: endpoint_context.connection_limits,
+            path_scheduler_endpoint: endpoint_context.path_scheduler,
         };
         let connection = <Cfg as crate::endpoint::Config>::Connection::new(connection_parameters)?;
         self.connections
@@ -1266,6 +1340,7 @@ pub mod testing {
         type ConnectionCloseFormatter = s2n_quic_core::connection::close::Development;
         type EventSubscriber = Subscriber;
         type PathMigrationValidator = path::migration::allow_all::Validator;
+        type PathScheduler = crate::path::scheduler::MinRtt;
         type PacketInterceptor = s2n_quic_core::packet::interceptor::Disabled;
         type DatagramEndpoint = s2n_quic_core::datagram::Disabled;
         type DcEndpoint = s2n_quic_core::dc::testing::MockDcEndpoint;
@@ -1298,6 +1373,7 @@ pub mod testing {
         type ConnectionCloseFormatter = s2n_quic_core::connection::close::Development;
         type EventSubscriber = Subscriber;
         type PathMigrationValidator = path::migration::allow_all::Validator;
+        type PathScheduler = crate::path::scheduler::MinRtt;
         type PacketInterceptor = s2n_quic_core::packet::interceptor::Disabled;
         type DatagramEndpoint = s2n_quic_core::datagram::Disabled;
         type DcEndpoint = s2n_quic_core::dc::testing::MockDcEndpoint;
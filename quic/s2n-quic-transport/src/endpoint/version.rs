@@ -12,36 +12,57 @@ use s2n_quic_core::{
     packet,
     packet::ProtectedPacket,
     path::{self, MINIMUM_MAX_DATAGRAM_SIZE},
+    version,
 };
 
 #[derive(Debug)]
 pub struct Negotiator<Config: endpoint::Config> {
     transmissions: VecDeque<Transmission<Config::PathHandle>>,
     max_peers: usize,
+    versions: version::Config,
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct Error;
 
-const SUPPORTED_VERSIONS: &[u32] = &[
-    0x1, // Draft 34 / Version 1 (https://github.com/quicwg/base-drafts/wiki/21st-Implementation-Draft)
-];
+impl<Config: endpoint::Config> Default for Negotiator<Config> {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
 
-macro_rules! is_supported {
-    ($packet:ident, $publisher:ident) => {{
-        let supported = SUPPORTED_VERSIONS
-            .iter()
-            .cloned()
-            .any(|v| v == $packet.version);
+impl<Config: endpoint::Config> Negotiator<Config> {
+    pub fn new(versions: version::Config) -> Self {
+        Self::with_max_peers(versions, endpoint::DEFAULT_MAX_PEERS)
+    }
+
+    pub fn with_max_peers(versions: version::Config, max_peers: usize) -> Self {
+        Self {
+            transmissions: if Config::ENDPOINT_TYPE.is_server() {
+                VecDeque::with_capacity(max_peers)
+            } else {
+                VecDeque::new()
+            },
+            max_peers,
+            versions,
+        }
+    }
+
+    fn is_supported<Pub: event::EndpointPublisher>(
+        &self,
+        version: version::Version,
+        publisher: &mut Pub,
+    ) -> bool {
+        let supported = self.versions.contains(version);
 
         if supported {
             //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.3.1
             //# Upon receiving a client initial with a supported version, the
             //# server logs this event with server_versions and chosen_version set
-            $publisher.on_version_information(event::builder::VersionInformation {
-                server_versions: &SUPPORTED_VERSIONS,
+            publisher.on_version_information(event::builder::VersionInformation {
+                server_versions: self.versions.versions(),
                 client_versions: &[],
-                chosen_version: Some($packet.version),
+                chosen_version: Some(version),
             });
         } else {
             //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.3.1
@@ -50,33 +71,14 @@ macro_rules! is_supported {
             //# client_versions to the single-element array containing the
             //# client's attempted version.  The absence of chosen_version implies
             //# no overlap was found.
-            $publisher.on_version_information(event::builder::VersionInformation {
-                server_versions: &SUPPORTED_VERSIONS,
-                client_versions: &[$packet.version],
+            publisher.on_version_information(event::builder::VersionInformation {
+                server_versions: self.versions.versions(),
+                client_versions: &[version],
                 chosen_version: None,
             });
         }
 
         supported
-    }};
-}
-
-impl<Config: endpoint::Config> Default for Negotiator<Config> {
-    fn default() -> Self {
-        Self::new(endpoint::DEFAULT_MAX_PEERS)
-    }
-}
-
-impl<Config: endpoint::Config> Negotiator<Config> {
-    pub fn new(max_peers: usize) -> Self {
-        Self {
-            transmissions: if Config::ENDPOINT_TYPE.is_server() {
-                VecDeque::with_capacity(max_peers)
-            } else {
-                VecDeque::new()
-            },
-            max_peers,
-        }
     }
 
     pub fn on_packet<Pub: event::EndpointPublisher>(
@@ -93,13 +95,13 @@ impl<Config: endpoint::Config> Negotiator<Config> {
 
         let packet = match packet {
             ProtectedPacket::Initial(packet) => {
-                if is_supported!(packet, publisher) {
+                if self.is_supported(packet.version, publisher) {
                     return Ok(());
                 }
                 packet
             }
             ProtectedPacket::ZeroRtt(packet) => {
-                if is_supported!(packet, publisher) {
+                if self.is_supported(packet.version, publisher) {
                     return Ok(());
                 }
 
@@ -151,7 +153,7 @@ impl<Config: endpoint::Config> Negotiator<Config> {
                 //# Servers SHOULD respond with a Version
                 //# Negotiation packet, provided that the datagram is sufficiently long.
                 self.transmissions
-                    .push_back(Transmission::new(*path, packet));
+                    .push_back(Transmission::new(*path, packet, self.versions));
             }
         }
 
@@ -211,11 +213,15 @@ impl<Path: path::Handle> core::fmt::Debug for Transmission<Path> {
 }
 
 impl<Path: path::Handle> Transmission<Path> {
-    pub fn new(path: Path, initial_packet: &packet::initial::ProtectedInitial) -> Self {
+    pub fn new(
+        path: Path,
+        initial_packet: &packet::initial::ProtectedInitial,
+        versions: version::Config,
+    ) -> Self {
         let mut packet_buf = [0u8; MINIMUM_MAX_DATAGRAM_SIZE as usize];
         let version_packet = packet::version_negotiation::VersionNegotiation::from_initial(
             initial_packet,
-            SupportedVersions,
+            SupportedVersions(versions),
         );
 
         let mut buffer = EncoderBuffer::new(&mut packet_buf);
@@ -274,11 +280,11 @@ impl<Path: path::Handle> tx::Message for &Transmission<Path> {
 }
 
 #[derive(Clone, Copy, Debug)]
-pub struct SupportedVersions;
+pub struct SupportedVersions(version::Config);
 
 impl EncoderValue for SupportedVersions {
     fn encode<E: Encoder>(&self, encoder: &mut E) {
-        for version in SUPPORTED_VERSIONS {
+        for version in self.0.versions() {
             encoder.encode(version);
         }
 
@@ -477,7 +483,7 @@ mod tests {
                 tag: 0,
                 destination_connection_id: &[1u8, 2, 3][..],
                 source_connection_id: &[4u8, 5, 6][..],
-                supported_versions: SupportedVersions,
+                supported_versions: SupportedVersions(Default::default()),
             }
         )
     }
@@ -567,6 +573,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn server_enabled_versions_test() {
+        let mut publisher = Publisher::snapshot();
+
+        let mut server = Server::default();
+        for version in [version::V1, version::V2] {
+            assert_eq!(
+                on_initial_packet(datagram_info(1200), version, &mut server, &mut publisher),
+                Ok(()),
+                "server implementations should accept enabled versions"
+            );
+        }
+
+        let versions = version::Config::builder()
+            .with_versions(&[version::V1])
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut server = Server::new(versions);
+
+        assert_eq!(
+            on_initial_packet(
+                datagram_info(1200),
+                version::V2,
+                &mut server,
+                &mut publisher
+            ),
+            Err(Error),
+            "server implementations should error on disabled versions"
+        );
+
+        let transmission = server.transmissions.pop_front().unwrap();
+        let mut buffer = transmission.as_ref().to_vec();
+        let decoder = DecoderBufferMut::new(&mut buffer);
+        let remote_address = SocketAddress::default();
+        let connection_info = ConnectionInfo::new(&remote_address);
+        let (packet, _) = ProtectedPacket::decode(decoder, &connection_info, &3).unwrap();
+        let ProtectedPacket::VersionNegotiation(packet) = packet else {
+            panic!("expected a version negotiation packet");
+        };
+        let versions: Vec<u32> = packet.iter().collect();
+        assert_eq!(
+            versions,
+            [version::V1, 0xdadadada],
+            "only the enabled versions should be advertised"
+        );
+    }
+
     #[test]
     fn server_future_version_initial_test() {
         let mut server = Server::default();
@@ -635,7 +689,7 @@ mod tests {
 
     #[test]
     fn server_max_peers_test() {
-        let mut server = Server::new(2);
+        let mut server = Server::with_max_peers(Default::default(), 2);
         let mut publisher = Publisher::snapshot();
 
         for _ in 0..5 {
@@ -4,14 +4,17 @@
 //! This module contains the Manager implementation
 
 use crate::{
-    connection::PeerIdRegistry,
+    connection::{LocalIdRegistry, PeerIdRegistry},
     endpoint, path,
-    path::{challenge, Path},
+    path::{challenge, multipath, scheduler, Path},
     transmission,
 };
+use bytes::Bytes;
 use s2n_quic_core::{
     ack,
+    application::ServerName,
     connection::{self, Limits, PeerId},
+    ensure,
     event::{
         self,
         builder::{DatagramDropReason, MtuUpdatedCause},
@@ -19,17 +22,18 @@ use s2n_quic_core::{
     },
     frame,
     frame::path_validation,
-    inet::DatagramInfo,
+    inet::{icmp, DatagramInfo, SocketAddress},
     packet::number::PacketNumberSpace,
     path::{
         migration::{self, Validator as _},
-        mtu, Handle as _, Id,
+        mtu, remote_port_blocked, Handle as _, Id, LocalAddress, RemoteAddress,
     },
     random,
-    recovery::congestion_controller::{self, Endpoint as _},
+    recovery::congestion_controller::{self, CongestionController as _, Endpoint as _},
     stateless_reset,
     time::{timer, Timestamp},
-    transport,
+    transport::{self, parameters::PreferredAddress},
+    varint::VarInt,
 };
 use smallvec::SmallVec;
 
@@ -69,6 +73,36 @@ pub struct Manager<Config: endpoint::Config> {
     /// The `paths` data structure will need to be enhanced to include garbage collection
     /// of old paths to overcome this limitation.
     pending_packet_authentication: Option<u8>,
+
+    /// Multipath state, set once both endpoints have negotiated the multipath extension
+    multipath: Option<multipath::State>,
+
+    /// The scheduler used once the multipath extension is negotiated
+    scheduler: Option<Box<dyn scheduler::Scheduler>>,
+
+    /// The index of the path a client is migrating to, while it is being validated
+    pending_migration: Option<u8>,
+
+    /// Set when the peer sent the `disable_active_migration` transport parameter
+    active_migration_disabled: bool,
+
+    /// The server's preferred address that a client migrates to once the handshake is confirmed
+    preferred_address: Option<RemoteAddress>,
+
+    /// Set while the pending migration is a migration to the server's preferred address
+    preferred_address_migration: bool,
+
+    /// Set when a server sent the `preferred_address` transport parameter
+    ///
+    /// The connection can then receive packets on more than one local address, so paths
+    /// are matched by both the local and remote address.
+    preferred_address_advertised: bool,
+
+    /// The application protocol and server name negotiated in the handshake
+    ///
+    /// These are passed to the congestion controller endpoint for each path created after the
+    /// handshake completes.
+    handshake_info: Option<(Bytes, Option<ServerName>)>,
 }
 
 impl<Config: endpoint::Config> Manager<Config> {
@@ -79,6 +113,14 @@ impl<Config: endpoint::Config> Manager<Config> {
             active: 0,
             last_known_active_validated_path: None,
             pending_packet_authentication: None,
+            multipath: None,
+            scheduler: None,
+            pending_migration: None,
+            active_migration_disabled: false,
+            preferred_address: None,
+            preferred_address_migration: false,
+            preferred_address_advertised: false,
+            handshake_info: None,
         };
         manager.paths[0].activated = true;
         manager.paths[0].is_active = true;
@@ -204,21 +246,90 @@ impl<Config: endpoint::Config> Manager<Config> {
     /// Returns the Path for the provided address if the PathManager knows about it
     #[inline]
     pub fn path(&self, handle: &Config::PathHandle) -> Option<(Id, &Path<Config>)> {
-        self.paths
-            .iter()
-            .enumerate()
-            .find(|(_id, path)| Path::eq_by_handle(path, handle))
-            .map(|(id, path)| (path_id(id as u8), path))
+        let id = self.path_id_by_handle(handle)?;
+        Some((id, &self[id]))
     }
 
     /// Returns the Path for the provided address if the PathManager knows about it
     #[inline]
     pub fn path_mut(&mut self, handle: &Config::PathHandle) -> Option<(Id, &mut Path<Config>)> {
+        let id = self.path_id_by_handle(handle)?;
+        Some((id, &mut self[id]))
+    }
+
+    #[inline]
+    fn path_id_by_handle(&self, handle: &Config::PathHandle) -> Option<Id> {
+        // Packets sent to the server's preferred address belong to a different path than
+        // packets sent to its original address, even if the client address is the same.
+        if self.preferred_address_advertised {
+            return self
+                .paths
+                .iter()
+                .position(|path| path.handle.unmapped_eq(handle))
+                .map(|idx| path_id(idx as u8));
+        }
+
+        // A client that migrated to a new local address has multiple paths to the same server
+        // address, so the local address is used to tell them apart. If none of the paths match
+        // exactly, the local address changed without the client's involvement (e.g. a NAT
+        // rebinding) and the packet is associated with the active path.
+        if Config::ENDPOINT_TYPE.is_client() && self.paths.len() > 1 {
+            if let Some(idx) = self
+                .paths
+                .iter()
+                .position(|path| path.handle.unmapped_eq(handle))
+            {
+                return Some(path_id(idx as u8));
+            }
+
+            if self.active_path().eq_by_handle(handle) {
+                return Some(self.active_path_id());
+            }
+        }
+
         self.paths
-            .iter_mut()
-            .enumerate()
-            .find(|(_id, path)| Path::eq_by_handle(path, handle))
-            .map(|(id, path)| (path_id(id as u8), path))
+            .iter()
+            .position(|path| path.eq_by_handle(handle))
+            .map(|idx| path_id(idx as u8))
+    }
+
+    /// Returns the information passed to the congestion controller endpoint for a new path
+    #[inline]
+    fn congestion_controller_path_info<'a>(
+        &'a self,
+        mtu_config: &mtu::Config,
+        remote_address: &'a SocketAddress,
+    ) -> congestion_controller::PathInfo<'a> {
+        let path_info = congestion_controller::PathInfo::new(mtu_config, remote_address);
+
+        if let Some((application_protocol, server_name)) = &self.handshake_info {
+            path_info.with_handshake(application_protocol, server_name.as_ref())
+        } else {
+            path_info
+        }
+    }
+
+    /// Notifies the congestion controller endpoint of the parameters negotiated in the
+    /// handshake for each path
+    pub fn on_handshake_complete(
+        &mut self,
+        application_protocol: Bytes,
+        server_name: Option<ServerName>,
+        congestion_controller_endpoint: &mut Config::CongestionControllerEndpoint,
+        mtu: &mut mtu::Manager<Config::Mtu>,
+    ) {
+        for path in self.paths.iter_mut() {
+            let remote_address = path.remote_address();
+            let mtu_config = mtu
+                .config(&remote_address)
+                .unwrap_or_else(|_| *mtu.endpoint_config());
+            let path_info = congestion_controller::PathInfo::new(&mtu_config, &remote_address)
+                .with_handshake(&application_protocol, server_name.as_ref());
+            congestion_controller_endpoint
+                .on_handshake_complete(path_info, &mut path.congestion_controller);
+        }
+
+        self.handshake_info = Some((application_protocol, server_name));
     }
 
     /// Returns an iterator over all paths pending path_challenge or path_response
@@ -422,7 +533,7 @@ impl<Config: endpoint::Config> Manager<Config> {
             }
         })?;
 
-        let path_info = congestion_controller::PathInfo::new(&mtu_config, &remote_address);
+        let path_info = self.congestion_controller_path_info(&mtu_config, &remote_address);
         let cc = congestion_controller_endpoint.new_congestion_controller(path_info);
 
         let peer_connection_id = {
@@ -550,7 +661,11 @@ impl<Config: endpoint::Config> Manager<Config> {
     /// Writes any frames the path manager wishes to transmit to the given context
     #[inline]
     pub fn on_transmit<W: transmission::WriteContext>(&mut self, context: &mut W) {
-        self.peer_id_registry.on_transmit(context)
+        self.peer_id_registry.on_transmit(context);
+
+        if let Some(multipath) = self.multipath.as_mut() {
+            multipath.on_transmit(context);
+        }
 
         // TODO Add in per-path constraints based on whether a Challenge needs to be
         // transmitted.
@@ -560,12 +675,20 @@ impl<Config: endpoint::Config> Manager<Config> {
     #[inline]
     pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
         self.peer_id_registry.on_packet_ack(ack_set);
+
+        if let Some(multipath) = self.multipath.as_mut() {
+            multipath.on_packet_ack(ack_set);
+        }
     }
 
     /// Called when packets are lost
     #[inline]
     pub fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) {
         self.peer_id_registry.on_packet_loss(ack_set);
+
+        if let Some(multipath) = self.multipath.as_mut() {
+            multipath.on_packet_loss(ack_set);
+        }
     }
 
     #[inline]
@@ -606,6 +729,8 @@ impl<Config: endpoint::Config> Manager<Config> {
         //# A PATH_RESPONSE frame received on any network path validates the path
         //# on which the PATH_CHALLENGE was sent.
 
+        let mut validated = None;
+
         for (id, path) in self.paths.iter_mut().enumerate() {
             let was_amplification_limited = path.at_amplification_limit();
             if path.on_path_response(response.data) {
@@ -623,14 +748,43 @@ impl<Config: endpoint::Config> Manager<Config> {
                 // The path is now validated, so it is unblocked if it was
                 // previously amplification limited
                 debug_assert!(!path.at_amplification_limit());
-                return match (was_amplification_limited, path.is_active()) {
+                let amplification_outcome = match (was_amplification_limited, path.is_active()) {
                     (true, true) => AmplificationOutcome::ActivePathUnblocked,
                     (true, false) => AmplificationOutcome::InactivePathUnblocked,
                     _ => AmplificationOutcome::Unchanged,
                 };
+                validated = Some((id as u8, amplification_outcome));
+                break;
+            }
+        }
+
+        let Some((id, amplification_outcome)) = validated else {
+            return AmplificationOutcome::Unchanged;
+        };
+
+        if self.pending_migration == Some(id) {
+            self.pending_migration = None;
+            self.on_migration_validated(path_id(id), publisher);
+
+            let path = &self.paths[id as usize];
+            if core::mem::take(&mut self.preferred_address_migration) {
+                let id = path_id(id);
+                publisher.on_preferred_address_migration_updated(
+                    event::builder::PreferredAddressMigrationUpdated {
+                        status: event::builder::PreferredAddressMigrationStatus::Migrated,
+                        path: path_event!(path, id),
+                    },
+                );
+            } else {
+                let local_address = path.local_address();
+                publisher.on_active_migration_updated(event::builder::ActiveMigrationUpdated {
+                    status: event::builder::ActiveMigrationStatus::Migrated,
+                    local_addr: local_address.into_event(),
+                });
             }
         }
-        AmplificationOutcome::Unchanged
+
+        amplification_outcome
     }
 
     /// Process a packet and update internal state.
@@ -681,7 +835,20 @@ impl<Config: endpoint::Config> Manager<Config> {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-9.2
         //# An endpoint can migrate a connection to a new local address by
         //# sending packets containing non-probing frames from that address.
-        if !path_validation_probing.is_probing() && self.active_path_id() != path_id {
+        // When the multipath extension is negotiated, non-probing packets received on a
+        // validated path do not indicate a migration since all validated paths are in use.
+        // See https://datatracker.ietf.org/doc/draft-ietf-quic-multipath#section-3.1
+        let is_multipath_path = self.is_multipath_enabled()
+            && self[path_id].is_validated()
+            && !self[path_id].is_abandoned();
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+        //# Clients are responsible for initiating all migrations.
+        if Config::ENDPOINT_TYPE.is_server()
+            && !path_validation_probing.is_probing()
+            && self.active_path_id() != path_id
+            && !is_multipath_path
+        {
             amplification_outcome =
                 self.update_active_path(path_id, random_generator, publisher)?;
             //= https://www.rfc-editor.org/rfc/rfc9000#section-9.3
@@ -764,6 +931,544 @@ impl<Config: endpoint::Config> Manager<Config> {
         Ok(())
     }
 
+    /// Called when the peer sent the `disable_active_migration` transport parameter
+    #[inline]
+    pub fn on_active_migration_disabled(&mut self) {
+        self.active_migration_disabled = true;
+    }
+
+    /// Called when a client receives the server's `preferred_address` transport parameter
+    ///
+    /// The connection ID in the transport parameter is registered and the address matching
+    /// the address family of the active path is stored to migrate to once the handshake is
+    /// confirmed.
+    pub fn on_preferred_address<Pub: event::ConnectionPublisher>(
+        &mut self,
+        preferred_address: &PreferredAddress,
+        publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+
+        let connection_id = PeerId::try_from_bytes(preferred_address.connection_id.as_bytes())
+            .ok_or_else(|| {
+                transport::Error::TRANSPORT_PARAMETER_ERROR
+                    .with_reason("invalid preferred_address connection ID")
+            })?;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
+        //# If the preferred_address transport
+        //# parameter is sent, the sequence number of the supplied connection ID
+        //# is 1.
+        self.on_new_connection_id(
+            &connection_id,
+            1,
+            0,
+            &preferred_address.stateless_reset_token,
+            publisher,
+        )?;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.6.1
+        //# Servers MAY communicate a preferred address of each address family
+        //# (IPv4 and IPv6) to allow clients to pick the one most suited to their
+        //# network attachment.
+        let active_remote_address = self.active_path().remote_address().0;
+        let ipv4_address = preferred_address.ipv4_address;
+        let ipv6_address = preferred_address.ipv6_address;
+        let address: Option<SocketAddress> = match active_remote_address {
+            SocketAddress::IpV4(_) => ipv4_address.map(Into::into),
+            SocketAddress::IpV6(address) => match address.unmap() {
+                // Keep the IPv4-mapped format used by the socket
+                SocketAddress::IpV4(_) => {
+                    ipv4_address.map(|address| address.to_ipv6_mapped().into())
+                }
+                SocketAddress::IpV6(_) => ipv6_address.map(Into::into),
+            },
+        };
+
+        // Ignore preferred addresses that the client would not migrate to if the server
+        // was using them during the handshake
+        self.preferred_address = address
+            .filter(|address| !remote_port_blocked(address.port()))
+            .filter(|address| {
+                let scope = address.unicast_scope();
+                scope.is_some() && scope == active_remote_address.unicast_scope()
+            })
+            .map(RemoteAddress::from);
+
+        Ok(())
+    }
+
+    /// Called when a server sent the `preferred_address` transport parameter
+    #[inline]
+    pub fn on_preferred_address_advertised(&mut self) {
+        debug_assert!(Config::ENDPOINT_TYPE.is_server());
+        self.preferred_address_advertised = true;
+    }
+
+    /// Returns the index of the path a client is migrating to, if a migration is in progress
+    #[inline]
+    pub fn pending_migration(&self) -> Option<Id> {
+        self.pending_migration.map(path_id)
+    }
+
+    /// Checks if a client is able to migrate the connection to the provided local address
+    pub fn can_migrate(&self, local_address: &LocalAddress) -> Result<(), migration::Error> {
+        ensure!(
+            Config::ENDPOINT_TYPE.is_client(),
+            Err(migration::Error::ServerInitiated)
+        );
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+        //# If the peer sent the disable_active_migration transport parameter, an endpoint also MUST NOT send
+        //# packets (including probing packets; see Section 9.1) from a different local address to the address
+        //# the peer used during the handshake, unless the endpoint has acted on a preferred_address transport
+        //# parameter from the peer.
+        ensure!(
+            !self.active_migration_disabled,
+            Err(migration::Error::Disabled)
+        );
+        ensure!(
+            self.pending_migration.is_none(),
+            Err(migration::Error::InProgress)
+        );
+        ensure!(
+            !self
+                .active_path()
+                .local_address()
+                .unmapped_eq(local_address),
+            Err(migration::Error::AlreadyActive)
+        );
+        ensure!(
+            self.paths.len() < MAX_ALLOWED_PATHS,
+            Err(migration::Error::PathLimitExceeded)
+        );
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.2
+        //# An endpoint uses a new connection ID for probes sent from a new local
+        //# address; see Section 9.5.
+        ensure!(
+            self.peer_id_registry.has_new_id(),
+            Err(migration::Error::NoAvailableConnectionId)
+        );
+
+        Ok(())
+    }
+
+    /// Starts a client-initiated migration to the provided local address
+    ///
+    /// A new path from the local address to the current peer address is created and
+    /// probed with a PATH_CHALLENGE. The client switches to the new path once it is
+    /// validated and keeps using the current active path if validation fails.
+    pub fn migrate<Pub: event::ConnectionPublisher>(
+        &mut self,
+        local_address: LocalAddress,
+        congestion_controller_endpoint: &mut Config::CongestionControllerEndpoint,
+        mtu: &mut mtu::Manager<Config::Mtu>,
+        limits: &Limits,
+        random_generator: &mut dyn random::Generator,
+        publisher: &mut Pub,
+    ) -> Result<Id, migration::Error> {
+        self.can_migrate(&local_address)?;
+
+        let mut handle = self.active_path().handle;
+        handle.set_local_address(local_address);
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.1
+        //# An endpoint MAY probe for peer reachability from a new local address
+        //# using path validation (Section 8.2) prior to migrating the connection
+        //# to the new local address.
+        let new_path_id = self.probe_new_path(
+            handle,
+            congestion_controller_endpoint,
+            mtu,
+            limits,
+            random_generator,
+            publisher,
+        )?;
+
+        publisher.on_active_migration_updated(event::builder::ActiveMigrationUpdated {
+            status: event::builder::ActiveMigrationStatus::Started,
+            local_addr: local_address.into_event(),
+        });
+
+        Ok(new_path_id)
+    }
+
+    /// Returns true if a client is able to start migrating to the server's preferred address
+    pub fn can_migrate_to_preferred_address(&self) -> bool {
+        Config::ENDPOINT_TYPE.is_client()
+            && self.preferred_address.is_some()
+            && self.pending_migration.is_none()
+            && self.paths.len() < MAX_ALLOWED_PATHS
+            && self.peer_id_registry.has_new_id()
+    }
+
+    /// Starts a client migration to the server's preferred address
+    ///
+    /// A new path from the active local address to the preferred address is created and
+    /// probed with a PATH_CHALLENGE. The client switches to the new path once it is
+    /// validated and keeps using the server's original address if validation fails.
+    pub fn migrate_to_preferred_address<Pub: event::ConnectionPublisher>(
+        &mut self,
+        congestion_controller_endpoint: &mut Config::CongestionControllerEndpoint,
+        mtu: &mut mtu::Manager<Config::Mtu>,
+        limits: &Limits,
+        random_generator: &mut dyn random::Generator,
+        publisher: &mut Pub,
+    ) -> Option<Id> {
+        ensure!(self.can_migrate_to_preferred_address(), None);
+
+        let mut handle = self.active_path().handle;
+        handle.set_remote_address(self.preferred_address.take()?);
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.6.2
+        //# A client that migrates to a preferred address MUST validate the
+        //# address it chooses before migrating; see Section 21.5.3.
+        let new_path_id = self
+            .probe_new_path(
+                handle,
+                congestion_controller_endpoint,
+                mtu,
+                limits,
+                random_generator,
+                publisher,
+            )
+            .ok()?;
+        self.preferred_address_migration = true;
+
+        let path = &self[new_path_id];
+        publisher.on_preferred_address_migration_updated(
+            event::builder::PreferredAddressMigrationUpdated {
+                status: event::builder::PreferredAddressMigrationStatus::Started,
+                path: path_event!(path, new_path_id),
+            },
+        );
+
+        Some(new_path_id)
+    }
+
+    /// Creates a new path with the provided handle and starts validating it
+    ///
+    /// The path becomes the pending migration and is activated once it is validated.
+    fn probe_new_path<Pub: event::ConnectionPublisher>(
+        &mut self,
+        handle: Config::PathHandle,
+        congestion_controller_endpoint: &mut Config::CongestionControllerEndpoint,
+        mtu: &mut mtu::Manager<Config::Mtu>,
+        limits: &Limits,
+        random_generator: &mut dyn random::Generator,
+        publisher: &mut Pub,
+    ) -> Result<Id, migration::Error> {
+        let peer_connection_id = self
+            .peer_id_registry
+            .consume_new_id_for_new_path()
+            .ok_or(migration::Error::NoAvailableConnectionId)?;
+
+        let remote_address = handle.remote_address();
+
+        let new_path_idx = self.paths.len();
+        let new_path_id = path_id(new_path_idx as u8);
+
+        let rtt = self
+            .active_path()
+            .rtt_estimator
+            .for_new_path(limits.initial_round_trip_time());
+
+        // Fall back to the endpoint configuration if the provider rejects the peer address
+        let mtu_config = mtu
+            .config(&remote_address)
+            .unwrap_or_else(|_| *mtu.endpoint_config());
+
+        let path_info = self.congestion_controller_path_info(&mtu_config, &remote_address);
+        let cc = congestion_controller_endpoint.new_congestion_controller(path_info);
+
+        // The server validated the client's address during the handshake and will
+        // validate the new address with its own PATH_CHALLENGE
+        let path = Path::new(
+            handle,
+            peer_connection_id,
+            self.active_path().local_connection_id,
+            rtt,
+            cc,
+            true,
+            mtu_config,
+            limits.anti_amplification_multiplier(),
+        );
+
+        let active_path = self.active_path();
+        let active_path_id = self.active_path_id();
+        publisher.on_path_created(event::builder::PathCreated {
+            active: path_event!(active_path, active_path_id),
+            new: path_event!(path, new_path_id),
+        });
+
+        publisher.on_mtu_updated(event::builder::MtuUpdated {
+            path_id: new_path_id.into_event(),
+            mtu: path.mtu_controller.max_datagram_size() as u16,
+            cause: MtuUpdatedCause::NewPath,
+            search_complete: path.mtu_controller.is_search_completed(),
+        });
+
+        self.paths.push(path);
+        self.pending_migration = Some(new_path_idx as u8);
+        self.set_challenge(new_path_id, random_generator);
+
+        Ok(new_path_id)
+    }
+
+    /// Switches to the path of a client-initiated migration once it has been validated
+    fn on_migration_validated<Pub: event::ConnectionPublisher>(
+        &mut self,
+        new_path_id: Id,
+        publisher: &mut Pub,
+    ) {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+
+        if new_path_id == self.active_path_id() {
+            return;
+        }
+
+        if self.active_path().is_validated() {
+            self.last_known_active_validated_path = Some(self.active);
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9.2
+        //# An endpoint can migrate a connection to a new local address by
+        //# sending packets containing non-probing frames from that address.
+        let prev_path_id = self.active_path_id();
+        let amplification_outcome = self.activate_path(publisher, prev_path_id, new_path_id);
+        debug_assert!(
+            amplification_outcome.is_unchanged(),
+            "client paths are not amplification limited"
+        );
+
+        // Restart ECN validation to check that the new path supports ECN
+        let path = self.active_path_mut();
+        path.ecn_controller
+            .restart(path_event!(path, new_path_id), publisher);
+    }
+
+    /// Called when both endpoints have negotiated the multipath extension
+    ///
+    /// `local_max_path_id` and `peer_max_path_id` are the `initial_max_path_id` transport
+    /// parameters sent by the local endpoint and the peer, respectively.
+    pub fn on_multipath_negotiated(&mut self, local_max_path_id: u32, peer_max_path_id: u32) {
+        debug_assert!(self.multipath.is_none(), "multipath negotiated twice");
+        let mut multipath = multipath::State::new(local_max_path_id, peer_max_path_id);
+        if let Some(scheduler) = self.scheduler.take() {
+            multipath.set_scheduler(scheduler);
+        }
+        self.multipath = Some(multipath);
+    }
+
+    /// Returns `true` if the multipath extension was negotiated
+    #[inline]
+    pub fn is_multipath_enabled(&self) -> bool {
+        self.multipath.is_some()
+    }
+
+    /// Replaces the scheduler used to select the path for each packet
+    ///
+    /// The scheduler is only used once multipath has been negotiated.
+    pub fn set_scheduler(&mut self, scheduler: Box<dyn scheduler::Scheduler>) {
+        if let Some(multipath) = self.multipath.as_mut() {
+            multipath.set_scheduler(scheduler);
+        } else {
+            self.scheduler = Some(scheduler);
+        }
+    }
+
+    /// Returns the multipath path id of packets sent on the given path
+    ///
+    /// Returns 0, the initial packet number space, if multipath is not enabled.
+    #[inline]
+    pub fn tx_path_id(&self, id: Id) -> u32 {
+        self.multipath.as_ref().map_or(0, |multipath| {
+            let sequence_number = self
+                .peer_id_registry
+                .sequence_number(&self[id].peer_connection_id);
+            multipath.tx_path_id(sequence_number)
+        })
+    }
+
+    /// Returns the multipath path id of packets received with a local connection ID of the
+    /// given sequence number
+    ///
+    /// Returns 0, the initial packet number space, if multipath is not enabled.
+    #[inline]
+    pub fn rx_path_id(&self, sequence_number: Option<u32>) -> u32 {
+        self.multipath
+            .as_ref()
+            .map_or(0, |multipath| multipath.rx_path_id(sequence_number))
+    }
+
+    /// Returns the path which sends packets with the given multipath path id
+    #[inline]
+    pub fn path_id_for_tx_path_id(&self, tx_path_id: u32) -> Option<Id> {
+        self.multipath.as_ref()?;
+
+        (0..self.paths.len() as u8)
+            .map(path_id)
+            .find(|&id| self.tx_path_id(id) == tx_path_id)
+    }
+
+    /// Returns the path which should carry the next packet of application data
+    ///
+    /// Without multipath all packets are sent on the active path. Otherwise the scheduler
+    /// selects between all of the validated paths that are able to transmit. `None` is
+    /// returned if no path is able to transmit at the given timestamp.
+    pub fn next_transmission_path(&mut self, timestamp: Timestamp) -> Option<Id> {
+        let active_path_id = self.active_path_id();
+        let active_path_can_transmit = self.active_path().can_transmit(timestamp);
+
+        let multipath = match self.multipath.as_mut() {
+            Some(multipath) => multipath,
+            None => return Some(active_path_id).filter(|_| active_path_can_transmit),
+        };
+
+        let mut candidates = SmallVec::<[scheduler::Candidate; MAX_ALLOWED_PATHS]>::new();
+
+        for (idx, path) in self.paths.iter().enumerate() {
+            if !path.is_validated()
+                || path.is_abandoned()
+                || !path.can_transmit(timestamp)
+                || !path.transmission_constraint().can_transmit()
+            {
+                continue;
+            }
+
+            candidates.push(scheduler::Candidate {
+                id: path_id(idx as u8),
+                smoothed_rtt: path.rtt_estimator.smoothed_rtt(),
+                congestion_window: path.congestion_controller.congestion_window(),
+                bytes_in_flight: path.congestion_controller.bytes_in_flight(),
+            });
+        }
+
+        multipath
+            .scheduler_mut()
+            .select(&candidates)
+            .filter(|id| candidates.iter().any(|candidate| candidate.id == *id))
+            .or_else(|| Some(active_path_id).filter(|_| active_path_can_transmit))
+    }
+
+    /// Stops using the given path and notifies the peer with a PATH_ABANDON frame
+    pub fn abandon_path<Pub: event::ConnectionPublisher>(
+        &mut self,
+        id: Id,
+        error_code: VarInt,
+        publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        let tx_path_id = self.tx_path_id(id);
+
+        let multipath = self
+            .multipath
+            .as_mut()
+            .ok_or(transport::Error::INTERNAL_ERROR.with_reason("multipath was not negotiated"))?;
+
+        // PATH_ABANDON frames with the path ID of the initial path are not sent since
+        // the initial path can only be closed by closing the connection.
+        // See https://datatracker.ietf.org/doc/draft-ietf-quic-multipath#section-3.3
+        if tx_path_id == 0 {
+            return Err(transport::Error::INTERNAL_ERROR
+                .with_reason("the initial path cannot be abandoned"));
+        }
+
+        multipath.abandon(tx_path_id, error_code);
+        self.on_path_abandoned(id, publisher)
+    }
+
+    /// Called when a PATH_ABANDON frame is received from the peer
+    pub fn on_path_abandon<Pub: event::ConnectionPublisher>(
+        &mut self,
+        frame: &frame::PathAbandon,
+        local_id_registry: &LocalIdRegistry,
+        publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        let multipath = self.multipath.as_ref().ok_or(
+            transport::Error::PROTOCOL_VIOLATION
+                .with_reason("PATH_ABANDON received without negotiating multipath"),
+        )?;
+
+        // If a PATH_ABANDON frame is received for the initial path, the receiver
+        // MUST close the connection with a PROTOCOL_VIOLATION.
+        // See https://datatracker.ietf.org/doc/draft-ietf-quic-multipath#section-3.3
+        if frame.path_id == VarInt::from_u8(0) {
+            return Err(transport::Error::PROTOCOL_VIOLATION
+                .with_reason("PATH_ABANDON received for the initial path"));
+        }
+
+        // The peer identifies the path by the packet number space it uses to send on the path,
+        // which is the sequence number of the local connection id.
+        let id = self.paths.iter().position(|path| {
+            let sequence_number = local_id_registry.sequence_number(&path.local_connection_id);
+            VarInt::from_u32(multipath.rx_path_id(sequence_number)) == frame.path_id
+        });
+
+        // The path may have already been abandoned and removed
+        let id = match id {
+            Some(id) => path_id(id as u8),
+            None => return Ok(()),
+        };
+
+        if self[id].is_abandoned() {
+            return Ok(());
+        }
+
+        // When an endpoint receives a PATH_ABANDON frame, it MUST respond by sending its own
+        // PATH_ABANDON frame for the same path, unless it has already done so.
+        // See https://datatracker.ietf.org/doc/draft-ietf-quic-multipath#section-3.3
+        let tx_path_id = self.tx_path_id(id);
+        if let Some(multipath) = self.multipath.as_mut() {
+            if tx_path_id != 0 {
+                multipath.abandon(tx_path_id, frame.error_code);
+            }
+        }
+
+        self.on_path_abandoned(id, publisher)
+    }
+
+    /// Marks the path as abandoned and moves off of it if it was the active path
+    fn on_path_abandoned<Pub: event::ConnectionPublisher>(
+        &mut self,
+        id: Id,
+        publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        self[id].on_abandoned();
+
+        if self.active_path_id() != id {
+            return Ok(());
+        }
+
+        let new_path_id = self
+            .paths
+            .iter()
+            .position(|path| path.is_validated() && !path.is_abandoned())
+            .map(|idx| path_id(idx as u8))
+            .ok_or(
+                transport::Error::PROTOCOL_VIOLATION
+                    .with_reason("the last usable path was abandoned"),
+            )?;
+
+        self.activate_path(publisher, id, new_path_id);
+
+        Ok(())
+    }
+
+    /// Called when an ICMP Packet Too Big message is received for a datagram sent to the peer
+    ///
+    /// Returns `None` if the message doesn't quote a datagram that was sent on any of the paths.
+    pub fn on_packet_too_big<Pub: event::ConnectionPublisher>(
+        &mut self,
+        packet_too_big: &icmp::PacketTooBig,
+        timestamp: Timestamp,
+        publisher: &mut Pub,
+    ) -> Option<mtu::MtuResult> {
+        self.paths.iter_mut().enumerate().find_map(|(id, path)| {
+            path.on_packet_too_big(packet_too_big, timestamp, path_id(id as u8), publisher)
+        })
+    }
+
     /// Called when the connection timer expired
     ///
     /// Returns `Ok(true)` if the timeout caused the active path to change from a path
@@ -779,6 +1484,35 @@ impl<Config: endpoint::Config> Manager<Config> {
             path.on_timeout(timestamp, path_id(id as u8), random_generator, publisher);
         }
 
+        if let Some(idx) = self.pending_migration {
+            // The PATH_CHALLENGE was abandoned without a response, so the client keeps
+            // using the current active path
+            if !self[path_id(idx)].is_challenge_pending() {
+                self.pending_migration = None;
+
+                //= https://www.rfc-editor.org/rfc/rfc9000#section-9.6.1
+                //# If path validation
+                //# fails, the client MUST continue sending all future packets to the
+                //# server's original IP address.
+                let id = path_id(idx);
+                let path = &self[id];
+                if core::mem::take(&mut self.preferred_address_migration) {
+                    publisher.on_preferred_address_migration_updated(
+                        event::builder::PreferredAddressMigrationUpdated {
+                            status: event::builder::PreferredAddressMigrationStatus::Failed,
+                            path: path_event!(path, id),
+                        },
+                    );
+                } else {
+                    let local_address = path.local_address();
+                    publisher.on_active_migration_updated(event::builder::ActiveMigrationUpdated {
+                        status: event::builder::ActiveMigrationStatus::Failed,
+                        local_addr: local_address.into_event(),
+                    });
+                }
+            }
+        }
+
         let mut amplification_outcome = AmplificationOutcome::Unchanged;
 
         if self.active_path().failed_validation() {
@@ -917,6 +1651,10 @@ impl<Config: endpoint::Config> transmission::interest::Provider for Manager<Conf
     ) -> transmission::interest::Result {
         self.peer_id_registry.transmission_interest(query)?;
 
+        if let Some(multipath) = self.multipath.as_ref() {
+            multipath.transmission_interest(query)?;
+        }
+
         for path in self.paths.iter() {
             // query PATH_CHALLENGE and PATH_RESPONSE interest for each path
             path.transmission_interest(query)?;
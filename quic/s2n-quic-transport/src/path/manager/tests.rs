@@ -15,7 +15,10 @@ use core::time::Duration;
 use s2n_quic_core::{
     connection::limits::ANTI_AMPLIFICATION_MULTIPLIER,
     event::testing::Publisher,
-    inet::{DatagramInfo, ExplicitCongestionNotification, SocketAddress},
+    inet::{
+        DatagramInfo, ExplicitCongestionNotification, SocketAddress, SocketAddressV4,
+        SocketAddressV6,
+    },
     path::{migration, RemoteAddress},
     random::{self, Generator},
     recovery::RttEstimator,
@@ -1861,6 +1864,365 @@ fn last_known_validated_path_should_update_on_path_response() {
     assert_eq!(manager.last_known_active_validated_path, None);
 }
 
+fn helper_migration_client() -> ClientManager {
+    let zero_conn_id = connection::PeerId::try_from_bytes(&[0]).unwrap();
+    let first_conn_id = connection::PeerId::try_from_bytes(&[1]).unwrap();
+    let mut zero_path = ClientPath::new(
+        Default::default(),
+        zero_conn_id,
+        connection::LocalId::TEST_ID,
+        RttEstimator::new(Duration::from_millis(30)),
+        Default::default(),
+        false,
+        mtu::Config::default(),
+        ANTI_AMPLIFICATION_MULTIPLIER,
+    );
+    zero_path.on_handshake_packet();
+
+    let mut random_generator = random::testing::Generator(123);
+    let mut peer_id_registry =
+        ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Client)
+            .create_client_peer_id_registry(
+                InternalConnectionIdGenerator::new().generate_id(),
+                false,
+            );
+    peer_id_registry.register_initial_connection_id(zero_conn_id);
+    assert!(peer_id_registry
+        .on_new_connection_id(&first_conn_id, 1, 0, &TEST_TOKEN_1)
+        .is_ok());
+
+    ClientManager::new(zero_path, peer_id_registry)
+}
+
+#[test]
+fn client_migration_test() {
+    let mut publisher = Publisher::no_snapshot();
+    let mut manager = helper_migration_client();
+    let zero_path_id = path_id(0);
+    let new_addr: SocketAddr = "127.0.0.2:1".parse().unwrap();
+    let new_addr = LocalAddress::from(SocketAddress::from(new_addr));
+
+    assert!(manager.can_migrate(&new_addr).is_ok());
+    let new_path_id = manager
+        .migrate(
+            new_addr,
+            &mut Default::default(),
+            &mut mtu::Manager::new(mtu::Config::default()),
+            &Limits::default(),
+            &mut random::testing::Generator(123),
+            &mut publisher,
+        )
+        .unwrap();
+
+    // the client keeps using the active path until the new path is validated
+    assert_eq!(new_path_id, path_id(1));
+    assert_eq!(manager.pending_migration(), Some(new_path_id));
+    assert_eq!(manager.active_path_id(), zero_path_id);
+    assert!(manager[new_path_id].is_challenge_pending());
+    assert_eq!(
+        manager.can_migrate(&new_addr),
+        Err(migration::Error::InProgress)
+    );
+
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    let mut context = MockWriteContext::new(
+        NoopClock {}.get_time(),
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::PathValidationOnly,
+        endpoint::Type::Client,
+    );
+    manager[new_path_id].on_transmit(&mut context);
+
+    let data: challenge::Data = manager[new_path_id]
+        .challenge
+        .challenge_data()
+        .try_into()
+        .unwrap();
+    let frame = s2n_quic_core::frame::PathResponse { data: &data };
+    let amplification_outcome = manager.on_path_response(&frame, &mut publisher);
+
+    // the client switches to the new path once it is validated
+    assert!(amplification_outcome.is_unchanged());
+    assert_eq!(manager.pending_migration(), None);
+    assert_eq!(manager.active_path_id(), new_path_id);
+    assert!(manager[new_path_id].is_validated());
+    assert_eq!(
+        manager.last_known_active_validated_path,
+        Some(zero_path_id.as_u8())
+    );
+}
+
+#[test]
+fn client_migration_failure_test() {
+    let mut publisher = Publisher::no_snapshot();
+    let mut manager = helper_migration_client();
+    let zero_path_id = path_id(0);
+    let new_addr: SocketAddr = "127.0.0.2:1".parse().unwrap();
+    let new_addr = LocalAddress::from(SocketAddress::from(new_addr));
+    let now = NoopClock {}.get_time();
+
+    let new_path_id = manager
+        .migrate(
+            new_addr,
+            &mut Default::default(),
+            &mut mtu::Manager::new(mtu::Config::default()),
+            &Limits::default(),
+            &mut random::testing::Generator(123),
+            &mut publisher,
+        )
+        .unwrap();
+
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    let mut context = MockWriteContext::new(
+        now,
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::PathValidationOnly,
+        endpoint::Type::Client,
+    );
+    manager[new_path_id].on_transmit(&mut context);
+
+    // the challenge is abandoned without a response
+    manager
+        .on_timeout(
+            now + Duration::from_secs(60),
+            &mut random::testing::Generator(123),
+            &mut publisher,
+        )
+        .unwrap();
+
+    assert_eq!(manager.pending_migration(), None);
+    assert_eq!(manager.active_path_id(), zero_path_id);
+    assert!(!manager[new_path_id].is_validated());
+}
+
+#[test]
+fn client_migration_errors_test() {
+    let new_addr: SocketAddr = "127.0.0.2:1".parse().unwrap();
+    let new_addr = LocalAddress::from(SocketAddress::from(new_addr));
+
+    let mut manager = helper_migration_client();
+    manager.on_active_migration_disabled();
+    assert_eq!(
+        manager.can_migrate(&new_addr),
+        Err(migration::Error::Disabled)
+    );
+
+    let manager = manager_client(ClientPath::new(
+        Default::default(),
+        connection::PeerId::try_from_bytes(&[0]).unwrap(),
+        connection::LocalId::TEST_ID,
+        RttEstimator::default(),
+        Default::default(),
+        false,
+        mtu::Config::default(),
+        ANTI_AMPLIFICATION_MULTIPLIER,
+    ));
+    assert_eq!(
+        manager.can_migrate(&new_addr),
+        Err(migration::Error::NoAvailableConnectionId)
+    );
+
+    let manager = manager_server(helper_path(
+        connection::PeerId::try_from_bytes(&[0]).unwrap(),
+    ));
+    assert_eq!(
+        manager.can_migrate(&new_addr),
+        Err(migration::Error::ServerInitiated)
+    );
+}
+
+fn helper_preferred_address_client() -> ClientManager {
+    let zero_conn_id = connection::PeerId::try_from_bytes(&[0]).unwrap();
+    let remote_addr: SocketAddr = "127.0.0.1:443".parse().unwrap();
+    let mut zero_path = ClientPath::new(
+        RemoteAddress::from(SocketAddress::from(remote_addr)),
+        zero_conn_id,
+        connection::LocalId::TEST_ID,
+        RttEstimator::new(Duration::from_millis(30)),
+        Default::default(),
+        false,
+        mtu::Config::default(),
+        ANTI_AMPLIFICATION_MULTIPLIER,
+    );
+    zero_path.on_handshake_packet();
+
+    let mut random_generator = random::testing::Generator(123);
+    let mut peer_id_registry =
+        ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Client)
+            .create_client_peer_id_registry(
+                InternalConnectionIdGenerator::new().generate_id(),
+                false,
+            );
+    peer_id_registry.register_initial_connection_id(zero_conn_id);
+
+    ClientManager::new(zero_path, peer_id_registry)
+}
+
+fn helper_preferred_address(
+    ipv4_address: Option<SocketAddressV4>,
+    ipv6_address: Option<SocketAddressV6>,
+) -> PreferredAddress {
+    PreferredAddress {
+        ipv4_address,
+        ipv6_address,
+        connection_id: connection::UnboundedId::try_from_bytes(&[1]).unwrap(),
+        stateless_reset_token: TEST_TOKEN_1,
+    }
+}
+
+#[test]
+fn client_preferred_address_test() {
+    let mut publisher = Publisher::no_snapshot();
+    let mut manager = helper_preferred_address_client();
+    let zero_path_id = path_id(0);
+    let preferred_addr = SocketAddressV4::new([127, 0, 0, 2], 4433);
+
+    assert!(!manager.can_migrate_to_preferred_address());
+    manager
+        .on_preferred_address(
+            &helper_preferred_address(Some(preferred_addr), None),
+            &mut publisher,
+        )
+        .unwrap();
+    assert!(manager.can_migrate_to_preferred_address());
+
+    let new_path_id = manager
+        .migrate_to_preferred_address(
+            &mut Default::default(),
+            &mut mtu::Manager::new(mtu::Config::default()),
+            &Limits::default(),
+            &mut random::testing::Generator(123),
+            &mut publisher,
+        )
+        .unwrap();
+
+    // the client keeps using the original server address until the new path is validated
+    assert_eq!(new_path_id, path_id(1));
+    assert_eq!(manager.pending_migration(), Some(new_path_id));
+    assert_eq!(manager.active_path_id(), zero_path_id);
+    assert_eq!(
+        manager[new_path_id].remote_address(),
+        RemoteAddress::from(preferred_addr)
+    );
+    assert_eq!(
+        manager[new_path_id].peer_connection_id,
+        connection::PeerId::try_from_bytes(&[1]).unwrap()
+    );
+    assert!(manager[new_path_id].is_challenge_pending());
+    // the client only migrates to the preferred address once
+    assert!(!manager.can_migrate_to_preferred_address());
+
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    let mut context = MockWriteContext::new(
+        NoopClock {}.get_time(),
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::PathValidationOnly,
+        endpoint::Type::Client,
+    );
+    manager[new_path_id].on_transmit(&mut context);
+
+    let data: challenge::Data = manager[new_path_id]
+        .challenge
+        .challenge_data()
+        .try_into()
+        .unwrap();
+    let frame = s2n_quic_core::frame::PathResponse { data: &data };
+    manager.on_path_response(&frame, &mut publisher);
+
+    // the client switches to the preferred address once it is validated
+    assert_eq!(manager.pending_migration(), None);
+    assert_eq!(manager.active_path_id(), new_path_id);
+    assert!(!manager.preferred_address_migration);
+}
+
+#[test]
+fn client_preferred_address_failure_test() {
+    let mut publisher = Publisher::no_snapshot();
+    let mut manager = helper_preferred_address_client();
+    let zero_path_id = path_id(0);
+    let now = NoopClock {}.get_time();
+
+    manager
+        .on_preferred_address(
+            &helper_preferred_address(Some(SocketAddressV4::new([127, 0, 0, 2], 4433)), None),
+            &mut publisher,
+        )
+        .unwrap();
+    let new_path_id = manager
+        .migrate_to_preferred_address(
+            &mut Default::default(),
+            &mut mtu::Manager::new(mtu::Config::default()),
+            &Limits::default(),
+            &mut random::testing::Generator(123),
+            &mut publisher,
+        )
+        .unwrap();
+
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    let mut context = MockWriteContext::new(
+        now,
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::PathValidationOnly,
+        endpoint::Type::Client,
+    );
+    manager[new_path_id].on_transmit(&mut context);
+
+    // the challenge is abandoned without a response
+    manager
+        .on_timeout(
+            now + Duration::from_secs(60),
+            &mut random::testing::Generator(123),
+            &mut publisher,
+        )
+        .unwrap();
+
+    // the client keeps using the server's original address
+    assert_eq!(manager.pending_migration(), None);
+    assert_eq!(manager.active_path_id(), zero_path_id);
+    assert!(!manager.preferred_address_migration);
+    assert!(!manager.can_migrate_to_preferred_address());
+}
+
+#[test]
+fn client_preferred_address_selection_test() {
+    let mut publisher = Publisher::no_snapshot();
+    let ipv6_addr = SocketAddressV6::new([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1], 4433);
+
+    // only an address of the same family as the active path is used
+    let mut manager = helper_preferred_address_client();
+    manager
+        .on_preferred_address(
+            &helper_preferred_address(None, Some(ipv6_addr)),
+            &mut publisher,
+        )
+        .unwrap();
+    assert!(!manager.can_migrate_to_preferred_address());
+
+    // addresses in a different scope than the original server address are ignored
+    let mut manager = helper_preferred_address_client();
+    manager
+        .on_preferred_address(
+            &helper_preferred_address(Some(SocketAddressV4::new([10, 0, 0, 1], 4433)), None),
+            &mut publisher,
+        )
+        .unwrap();
+    assert!(!manager.can_migrate_to_preferred_address());
+
+    // addresses with a blocked port are ignored
+    let mut manager = helper_preferred_address_client();
+    manager
+        .on_preferred_address(
+            &helper_preferred_address(Some(SocketAddressV4::new([127, 0, 0, 2], 53)), None),
+            &mut publisher,
+        )
+        .unwrap();
+    assert!(!manager.can_migrate_to_preferred_address());
+}
+
 // creates a test path_manager. also check out `helper_manager_with_paths`
 // which calls this helper with preset options
 pub fn helper_manager_with_paths_base(
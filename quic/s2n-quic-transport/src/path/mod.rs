@@ -15,13 +15,15 @@ use s2n_quic_core::{
     counter::{Counter, Saturating},
     event::{self, builder::DatagramDropReason, IntoEvent},
     frame,
-    inet::DatagramInfo,
+    inet::{icmp, DatagramInfo, ExplicitCongestionNotification},
     packet, random,
     time::{timer, Timestamp},
 };
 
 mod challenge;
 mod manager;
+mod multipath;
+pub mod scheduler;
 
 pub use challenge::Challenge;
 pub use manager::*;
@@ -80,6 +82,10 @@ pub struct Path<Config: endpoint::Config> {
 
     /// True if the path is currently active
     is_active: bool,
+
+    /// True if the path was abandoned with a PATH_ABANDON frame and no longer carries
+    /// application data
+    abandoned: bool,
     anti_amplification_multiplier: u8,
 }
 
@@ -100,6 +106,7 @@ impl<Config: endpoint::Config> Clone for Path<Config> {
             response_data: self.response_data,
             activated: self.activated,
             is_active: self.is_active,
+            abandoned: self.abandoned,
             anti_amplification_multiplier: self.anti_amplification_multiplier,
         }
     }
@@ -133,6 +140,11 @@ impl<Config: endpoint::Config> Path<Config> {
             Type::Client => State::Validated,
         };
         let peer_socket_address = handle.remote_address();
+        let ecn_controller = if congestion_controller.is_l4s_capable() {
+            ecn::Controller::l4s()
+        } else {
+            ecn::Controller::default()
+        };
         Path {
             handle,
             peer_connection_id,
@@ -142,12 +154,13 @@ impl<Config: endpoint::Config> Path<Config> {
             pto_backoff: INITIAL_PTO_BACKOFF,
             state,
             mtu_controller: mtu::Controller::new(mtu_config, &peer_socket_address),
-            ecn_controller: ecn::Controller::default(),
+            ecn_controller,
             peer_validated,
             challenge: Challenge::disabled(),
             response_data: None,
             activated: false,
             is_active: false,
+            abandoned: false,
             anti_amplification_multiplier,
         }
     }
@@ -162,6 +175,21 @@ impl<Config: endpoint::Config> Path<Config> {
         self.handle.local_address()
     }
 
+    /// Returns the ECN marking to use on packets sent on the path
+    ///
+    /// The ECT codepoint follows the congestion controller, which may switch between L4S and
+    /// classic congestion control on a live connection.
+    #[inline]
+    pub fn ecn(
+        &mut self,
+        transmission_mode: Mode,
+        now: Timestamp,
+    ) -> ExplicitCongestionNotification {
+        self.ecn_controller
+            .set_l4s(self.congestion_controller.is_l4s_capable());
+        self.ecn_controller.ecn(transmission_mode, now)
+    }
+
     #[inline]
     pub fn set_challenge(&mut self, challenge: Challenge) {
         self.challenge = challenge;
@@ -182,6 +210,18 @@ impl<Config: endpoint::Config> Path<Config> {
         self.is_active
     }
 
+    /// Returns `true` if the path was abandoned by either endpoint
+    #[inline]
+    pub fn is_abandoned(&self) -> bool {
+        self.abandoned
+    }
+
+    /// Marks the path as abandoned so it no longer carries application data
+    #[inline]
+    pub fn on_abandoned(&mut self) {
+        self.abandoned = true;
+    }
+
     /// Called when bytes have been transmitted on this path
     #[inline]
     pub fn on_bytes_transmitted(&mut self, bytes: usize) {
@@ -289,7 +329,8 @@ impl<Config: endpoint::Config> Path<Config> {
     ) {
         self.challenge
             .on_timeout(timestamp, publisher, path_event!(self, path_id));
-        self.mtu_controller.on_timeout(timestamp);
+        self.mtu_controller
+            .on_timeout(timestamp, path_id, publisher);
         self.ecn_controller.on_timeout(
             timestamp,
             path_event!(self, path_id),
@@ -299,6 +340,51 @@ impl<Config: endpoint::Config> Path<Config> {
         );
     }
 
+    /// Called when an ICMP Packet Too Big message is received
+    ///
+    /// Returns `None` if the message doesn't quote a datagram that was sent on this path.
+    #[inline]
+    pub fn on_packet_too_big<Pub: event::ConnectionPublisher>(
+        &mut self,
+        packet_too_big: &icmp::PacketTooBig,
+        timestamp: Timestamp,
+        path_id: Id,
+        publisher: &mut Pub,
+    ) -> Option<mtu::MtuResult> {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-14.2.1
+        //# The endpoint SHOULD ignore all ICMP messages that fail validation.
+        if !self.is_quoted_by(packet_too_big) {
+            return None;
+        }
+
+        let result = self.mtu_controller.on_packet_too_big(
+            packet_too_big.max_udp_payload(),
+            timestamp,
+            &mut self.congestion_controller,
+            path_id,
+            publisher,
+        );
+
+        Some(result)
+    }
+
+    /// Returns true if the ICMP message quotes a datagram that was sent on this path
+    ///
+    /// An off-path attacker would need to guess the peer's connection ID to pass the validation.
+    /// Messages for paths using a zero-length connection ID are never validated, since there
+    /// is nothing to guess.
+    #[inline]
+    fn is_quoted_by(&self, packet_too_big: &icmp::PacketTooBig) -> bool {
+        let remote_address = RemoteAddress(*packet_too_big.remote_address());
+        if !self.remote_address().unmapped_eq(&remote_address) {
+            return false;
+        }
+
+        packet_too_big
+            .destination_connection_ids()
+            .any(|id| id == self.peer_connection_id)
+    }
+
     /// Returns true if this path is able to transmit packets at the given timestamp
     #[inline]
     pub fn can_transmit(&self, timestamp: Timestamp) -> bool {
@@ -686,6 +772,7 @@ mod tests {
         connection::limits::ANTI_AMPLIFICATION_MULTIPLIER,
         endpoint,
         event::testing::Publisher,
+        inet::{SocketAddress, SocketAddressV4},
         path::MINIMUM_MAX_DATAGRAM_SIZE,
         recovery::{CongestionController, RttEstimator},
         time::{Clock, NoopClock},
@@ -1264,6 +1351,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn on_packet_too_big_test() {
+        let mut path = testing::helper_path_server();
+        path.peer_connection_id = connection::PeerId::try_from_bytes(&[1, 2, 3, 4]).unwrap();
+        path.mtu_controller = mtu::testing::test_controller(1472, 1500);
+        let remote_address = path.remote_address().0;
+        let other_address = SocketAddress::IpV4(SocketAddressV4::new([10, 0, 0, 1], 443));
+        let mut publisher = Publisher::no_snapshot();
+        let now = NoopClock.get_time();
+        let path_id = path::Id::test_id();
+
+        // The quoted datagram was sent to a different address
+        let ptb = icmp::PacketTooBig::new(other_address, 1300, &[0x40, 1, 2, 3, 4]);
+        assert_eq!(
+            None,
+            path.on_packet_too_big(&ptb, now, path_id, &mut publisher)
+        );
+
+        // The quoted short header packet was sent to a different connection ID
+        let ptb = icmp::PacketTooBig::new(remote_address, 1300, &[0x40, 1, 2, 3, 5]);
+        assert_eq!(
+            None,
+            path.on_packet_too_big(&ptb, now, path_id, &mut publisher)
+        );
+
+        // The quoted datagram is truncated before the end of the connection ID
+        let ptb = icmp::PacketTooBig::new(remote_address, 1300, &[0x40, 1, 2]);
+        assert_eq!(
+            None,
+            path.on_packet_too_big(&ptb, now, path_id, &mut publisher)
+        );
+        assert_eq!(1472, path.mtu_controller.max_datagram_size());
+
+        // The quoted short header packet was sent on the path
+        let ptb = icmp::PacketTooBig::new(remote_address, 1300, &[0x40, 1, 2, 3, 4]);
+        assert_eq!(
+            Some(mtu::MtuResult::MtuUpdated(1272)),
+            path.on_packet_too_big(&ptb, now, path_id, &mut publisher)
+        );
+        assert_eq!(1272, path.mtu_controller.max_datagram_size());
+
+        // The quoted long header packet was sent on the path
+        let ptb = icmp::PacketTooBig::new(remote_address, 1250, &[0xc0, 0, 0, 0, 1, 4, 1, 2, 3, 4]);
+        assert_eq!(
+            Some(mtu::MtuResult::MtuUpdated(1222)),
+            path.on_packet_too_big(&ptb, now, path_id, &mut publisher)
+        );
+        assert_eq!(1222, path.mtu_controller.max_datagram_size());
+    }
+
     #[test]
     fn is_congestion_limited() {
         let mut path = testing::helper_path_client();
@@ -0,0 +1,208 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Connection state for the multipath extension
+//!
+//! Each path has its own packet number space, identified by the sequence number of the
+//! connection ID used on the path. Connection IDs with a sequence number of 0 or above the
+//! maximum path id advertised by the receiver use the initial packet number space (path id 0).
+//! See <https://datatracker.ietf.org/doc/draft-ietf-quic-multipath/>
+
+use crate::{
+    path::scheduler::{self, Scheduler},
+    transmission::{self, WriteContext},
+};
+use s2n_quic_core::{ack, frame::PathAbandon, packet::number::PacketNumber, varint::VarInt};
+use smallvec::SmallVec;
+
+#[derive(Debug)]
+pub struct State {
+    /// The largest path id the local endpoint accepts packets for
+    local_max_path_id: u32,
+    /// The largest path id the peer accepts packets for
+    peer_max_path_id: u32,
+    /// Selects the path each packet is sent on
+    scheduler: Box<dyn Scheduler>,
+    /// PATH_ABANDON frames that were queued for transmission
+    abandons: SmallVec<[PendingAbandon; 2]>,
+}
+
+#[derive(Debug)]
+struct PendingAbandon {
+    frame: PathAbandon,
+    status: Status,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Status {
+    /// The frame needs to be transmitted
+    Pending,
+    /// The frame was sent in the given packet and is waiting on an acknowledgement
+    Sent(PacketNumber),
+    /// The frame was acknowledged by the peer
+    Acknowledged,
+}
+
+impl PendingAbandon {
+    #[inline]
+    fn is_sent_in<A: ack::Set>(&self, ack_set: &A) -> bool {
+        matches!(self.status, Status::Sent(packet_number) if ack_set.contains(packet_number))
+    }
+}
+
+impl State {
+    pub fn new(local_max_path_id: u32, peer_max_path_id: u32) -> Self {
+        Self {
+            local_max_path_id,
+            peer_max_path_id,
+            scheduler: Box::<scheduler::MinRtt>::default(),
+            abandons: SmallVec::new(),
+        }
+    }
+
+    /// Replaces the scheduler used to select the path for each packet
+    pub fn set_scheduler(&mut self, scheduler: Box<dyn Scheduler>) {
+        self.scheduler = scheduler;
+    }
+
+    #[inline]
+    pub fn scheduler_mut(&mut self) -> &mut dyn Scheduler {
+        &mut *self.scheduler
+    }
+
+    /// Returns the path id of packets received with a local connection ID of the given
+    /// sequence number
+    #[inline]
+    pub fn rx_path_id(&self, sequence_number: Option<u32>) -> u32 {
+        Self::path_id(sequence_number, self.local_max_path_id)
+    }
+
+    /// Returns the path id of packets sent with a peer connection ID of the given sequence number
+    #[inline]
+    pub fn tx_path_id(&self, sequence_number: Option<u32>) -> u32 {
+        Self::path_id(sequence_number, self.peer_max_path_id)
+    }
+
+    #[inline]
+    fn path_id(sequence_number: Option<u32>, max_path_id: u32) -> u32 {
+        match sequence_number {
+            Some(sequence_number) if sequence_number <= max_path_id => sequence_number,
+            _ => 0,
+        }
+    }
+
+    /// Queues a PATH_ABANDON frame for the given path id
+    ///
+    /// Returns `false` if the path was already abandoned.
+    pub fn abandon(&mut self, path_id: u32, error_code: VarInt) -> bool {
+        let path_id = VarInt::from_u32(path_id);
+
+        if self
+            .abandons
+            .iter()
+            .any(|pending| pending.frame.path_id == path_id)
+        {
+            return false;
+        }
+
+        self.abandons.push(PendingAbandon {
+            frame: PathAbandon {
+                path_id,
+                error_code,
+            },
+            status: Status::Pending,
+        });
+
+        true
+    }
+
+    /// Writes any pending PATH_ABANDON frames to the given context
+    pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) {
+        if !context.transmission_constraint().can_transmit() {
+            return;
+        }
+
+        for pending in self
+            .abandons
+            .iter_mut()
+            .filter(|pending| pending.status == Status::Pending)
+        {
+            if let Some(packet_number) = context.write_frame(&pending.frame) {
+                pending.status = Status::Sent(packet_number);
+            }
+        }
+    }
+
+    /// Marks PATH_ABANDON frames as acknowledged
+    ///
+    /// Abandoned path ids are never reused so acknowledged frames are retained to detect
+    /// duplicate abandonment.
+    pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
+        for pending in self.abandons.iter_mut() {
+            if pending.is_sent_in(ack_set) {
+                pending.status = Status::Acknowledged;
+            }
+        }
+    }
+
+    /// Schedules lost PATH_ABANDON frames for retransmission
+    pub fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) {
+        for pending in self.abandons.iter_mut() {
+            if pending.is_sent_in(ack_set) {
+                pending.status = Status::Pending;
+            }
+        }
+    }
+}
+
+impl transmission::interest::Provider for State {
+    #[inline]
+    fn transmission_interest<Q: transmission::interest::Query>(
+        &self,
+        query: &mut Q,
+    ) -> transmission::interest::Result {
+        if self
+            .abandons
+            .iter()
+            .any(|pending| pending.status == Status::Pending)
+        {
+            query.on_new_data()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_id_mapping_test() {
+        let state = State::new(2, 4);
+
+        // the initial connection ID always uses the initial packet number space
+        assert_eq!(state.rx_path_id(Some(0)), 0);
+        assert_eq!(state.tx_path_id(Some(0)), 0);
+
+        assert_eq!(state.rx_path_id(Some(2)), 2);
+        assert_eq!(state.tx_path_id(Some(2)), 2);
+
+        // sequence numbers above the receiver's limit fall back to the initial space
+        assert_eq!(state.rx_path_id(Some(3)), 0);
+        assert_eq!(state.tx_path_id(Some(3)), 3);
+        assert_eq!(state.tx_path_id(Some(5)), 0);
+
+        assert_eq!(state.rx_path_id(None), 0);
+    }
+
+    #[test]
+    fn abandon_test() {
+        let mut state = State::new(2, 2);
+
+        assert!(state.abandon(1, VarInt::from_u8(0)));
+        assert!(!state.abandon(1, VarInt::from_u8(3)));
+        assert!(state.abandon(2, VarInt::from_u8(0)));
+        assert_eq!(state.abandons.len(), 2);
+    }
+}
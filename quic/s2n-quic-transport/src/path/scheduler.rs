@@ -0,0 +1,135 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Packet schedulers for connections using the multipath extension
+//!
+//! When multipath is enabled, every validated path can carry application data. The scheduler
+//! decides which of those paths is used for each outgoing packet. See
+//! <https://datatracker.ietf.org/doc/draft-ietf-quic-multipath/>
+
+use core::{fmt, time::Duration};
+use s2n_quic_core::path::Id;
+
+/// A path that is able to carry the next packet
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Candidate {
+    /// The id of the path in the path manager
+    pub id: Id,
+    /// The smoothed round-trip time of the path
+    pub smoothed_rtt: Duration,
+    /// The congestion window of the path, in bytes
+    pub congestion_window: u32,
+    /// The number of bytes currently in flight on the path
+    pub bytes_in_flight: u32,
+}
+
+/// Selects the path used to transmit each packet
+pub trait Scheduler: 'static + Send + fmt::Debug {
+    /// Returns the id of the path that should carry the next packet
+    ///
+    /// `candidates` only contains paths that are not limited by congestion control or
+    /// amplification limits and is ordered by path id. Returning `None` sends the packet on the
+    /// active path.
+    fn select(&mut self, candidates: &[Candidate]) -> Option<Id>;
+}
+
+/// Creates the scheduler for each connection that enables the multipath extension
+pub trait Endpoint: 'static + Send {
+    type Scheduler: Scheduler;
+
+    /// Returns a new scheduler for a connection
+    fn new_scheduler(&mut self) -> Self::Scheduler;
+}
+
+/// Every connection starts with a copy of the configured scheduler
+impl<S: Scheduler + Clone> Endpoint for S {
+    type Scheduler = S;
+
+    #[inline]
+    fn new_scheduler(&mut self) -> Self::Scheduler {
+        self.clone()
+    }
+}
+
+/// Sends each packet on the path with the lowest smoothed RTT
+///
+/// Once the congestion window of the fastest path is full, packets spill over to the next
+/// fastest path. This is the default scheduler.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MinRtt;
+
+impl Scheduler for MinRtt {
+    #[inline]
+    fn select(&mut self, candidates: &[Candidate]) -> Option<Id> {
+        candidates
+            .iter()
+            .min_by_key(|candidate| candidate.smoothed_rtt)
+            .map(|candidate| candidate.id)
+    }
+}
+
+/// Alternates between all of the available paths
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RoundRobin {
+    previous: Option<Id>,
+}
+
+impl Scheduler for RoundRobin {
+    #[inline]
+    fn select(&mut self, candidates: &[Candidate]) -> Option<Id> {
+        let next = self
+            .previous
+            .and_then(|previous| candidates.iter().find(|candidate| candidate.id > previous))
+            .or_else(|| candidates.first())
+            .map(|candidate| candidate.id)?;
+
+        self.previous = Some(next);
+        Some(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(id: u8, rtt_ms: u64) -> Candidate {
+        Candidate {
+            id: unsafe { Id::new(id) },
+            smoothed_rtt: Duration::from_millis(rtt_ms),
+            congestion_window: 12_000,
+            bytes_in_flight: 0,
+        }
+    }
+
+    #[test]
+    fn min_rtt_test() {
+        let mut scheduler = MinRtt;
+
+        assert_eq!(scheduler.select(&[]), None);
+
+        let candidates = [candidate(0, 40), candidate(1, 10), candidate(2, 25)];
+        assert_eq!(scheduler.select(&candidates), Some(candidates[1].id));
+
+        // the fastest path is congestion limited so the next fastest is used
+        let candidates = [candidate(0, 40), candidate(2, 25)];
+        assert_eq!(scheduler.select(&candidates), Some(candidates[1].id));
+    }
+
+    #[test]
+    fn round_robin_test() {
+        let mut scheduler = RoundRobin::default();
+
+        assert_eq!(scheduler.select(&[]), None);
+
+        let candidates = [candidate(0, 40), candidate(1, 10), candidate(3, 25)];
+        let selected: Vec<_> = (0..5)
+            .map(|_| scheduler.select(&candidates).unwrap().as_u8())
+            .collect();
+        assert_eq!(selected, [0, 1, 3, 0, 1]);
+
+        // a path becoming unavailable is skipped
+        let candidates = [candidate(0, 40), candidate(3, 25)];
+        assert_eq!(scheduler.select(&candidates).unwrap().as_u8(), 3);
+        assert_eq!(scheduler.select(&candidates).unwrap().as_u8(), 0);
+    }
+}
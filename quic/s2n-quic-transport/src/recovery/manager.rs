@@ -18,6 +18,7 @@ use s2n_quic_core::{
     recovery::{congestion_controller, persistent_congestion, CongestionController, Pto},
     time::{timer, timer::Provider, Timer, Timestamp},
     transport,
+    varint::VarInt,
 };
 use smallvec::SmallVec;
 
@@ -66,6 +67,22 @@ pub struct Manager<Config: endpoint::Config> {
     //
     // Used for updating the PTO timer at the end of a transmission burst.
     pto_update_pending: bool,
+
+    // The loss detection state of each path when the multipath extension is negotiated.
+    //
+    // Packets sent on every path are tracked by their application space packet number, so
+    // each packet is only compared with the packets sent on the same path to detect loss.
+    multipath: Option<SmallVec<[PathLossState; 4]>>,
+}
+
+/// The loss detection state of a path sharing the packet number space with other paths
+#[derive(Clone, Copy, Debug)]
+struct PathLossState {
+    path_id: path::Id,
+    /// The number of packets sent on the path
+    sent_packets: u64,
+    /// The `path_sequence` of the largest packet acknowledged on the path
+    largest_acked: Option<u64>,
 }
 
 /// Initial capacity of the SmallVec used for keeping track of packets
@@ -124,9 +141,18 @@ impl<Config: endpoint::Config> Manager<Config> {
             baseline_ecn_counts: EcnCounts::default(),
             sent_packet_ecn_counts: EcnCounts::default(),
             pto_update_pending: false,
+            multipath: None,
         }
     }
 
+    /// Detects loss separately for each path the packets are sent on
+    ///
+    /// This is called once the multipath extension is negotiated.
+    pub fn enable_multipath(&mut self) {
+        debug_assert_eq!(self.space, PacketNumberSpace::ApplicationData);
+        self.multipath.get_or_insert_with(SmallVec::new);
+    }
+
     /// Invoked when the Client processes a Retry packet.
     ///
     /// Reset congestion controller state by discarding sent bytes and replacing recovery
@@ -247,19 +273,32 @@ impl<Config: endpoint::Config> Manager<Config> {
             &mut congestion_controller::PathPublisher::new(publisher, path_id),
         );
 
-        self.sent_packets.insert(
-            packet_number,
-            SentPacketInfo::new(
-                outcome.is_congestion_controlled,
-                congestion_controlled_bytes,
-                time_sent,
-                outcome.ack_elicitation,
-                path_id,
-                ecn,
-                transmission_mode,
-                cc_packet_info,
-            ),
+        let mut sent_info = SentPacketInfo::new(
+            outcome.is_congestion_controlled,
+            congestion_controlled_bytes,
+            time_sent,
+            outcome.ack_elicitation,
+            path_id,
+            ecn,
+            transmission_mode,
+            cc_packet_info,
         );
+        if let Some(paths) = self.multipath.as_mut() {
+            let state = match paths.iter_mut().position(|state| state.path_id == path_id) {
+                Some(index) => &mut paths[index],
+                None => {
+                    paths.push(PathLossState {
+                        path_id,
+                        sent_packets: 0,
+                        largest_acked: None,
+                    });
+                    paths.last_mut().expect("a path was just pushed")
+                }
+            };
+            sent_info.path_sequence = state.sent_packets;
+            state.sent_packets += 1;
+        }
+        self.sent_packets.insert(packet_number, sent_info);
         path.ecn_controller
             .on_packet_sent(ecn, path_event!(path, path_id), publisher);
         self.sent_packet_ecn_counts.increment(ecn);
@@ -410,6 +449,39 @@ impl<Config: endpoint::Config> Manager<Config> {
         Ok(())
     }
 
+    /// Process PATH_ACK frame of the multipath extension.
+    ///
+    /// The acknowledged ranges were already mapped from the packet number space of the path
+    /// to the packet numbers the sent packets are tracked with.
+    pub fn on_path_ack<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
+        &mut self,
+        timestamp: Timestamp,
+        ranges: &[PacketNumberRange],
+        largest_acked_packet_number: PacketNumber,
+        ack_delay: Duration,
+        ecn_counts: Option<EcnCounts>,
+        packet_number: PacketNumber,
+        random_generator: &mut Config::RandomGenerator,
+        context: &mut Ctx,
+        publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        self.process_acks(
+            timestamp,
+            ranges.iter().copied(),
+            largest_acked_packet_number,
+            ack_delay,
+            ecn_counts,
+            packet_number,
+            random_generator,
+            context,
+            publisher,
+        )?;
+
+        self.check_consistency(context.active_path(), context.is_handshake_confirmed());
+
+        Ok(())
+    }
+
     /// Generic interface for processing ACK ranges.
     fn process_acks<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
         &mut self,
@@ -536,6 +608,20 @@ impl<Config: endpoint::Config> Manager<Config> {
 
                 includes_ack_eliciting |= acked_packet_info.ack_elicitation.is_ack_eliciting();
 
+                if let Some(state) = self.multipath.as_mut().and_then(|paths| {
+                    paths
+                        .iter_mut()
+                        .find(|state| state.path_id == acked_packet_info.path_id)
+                }) {
+                    let path_sequence = acked_packet_info.path_sequence;
+                    if state
+                        .largest_acked
+                        .map_or(true, |largest| path_sequence > largest)
+                    {
+                        state.largest_acked = Some(path_sequence);
+                    }
+                }
+
                 let path = context.path_mut_by_id(acked_packet_info.path_id);
                 path.ecn_controller
                     .on_packet_ack(acked_packet_info.time_sent, acked_packet_info.ecn);
@@ -783,6 +869,38 @@ impl<Config: endpoint::Config> Manager<Config> {
         self.pto.has_transmission_interest()
     }
 
+    /// Invoked when the client learns that the server rejected early data
+    ///
+    /// All of the packets sent with 0-RTT keys are removed from flight without being treated
+    /// as a congestion event and the frames they carried are declared lost so they are
+    /// retransmitted in 1-RTT packets.
+    pub fn on_zero_rtt_rejected<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
+        &mut self,
+        context: &mut Ctx,
+        publisher: &mut Pub,
+    ) {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+        debug_assert_eq!(self.space, PacketNumberSpace::ApplicationData);
+
+        if self.sent_packets.is_empty() {
+            return;
+        }
+
+        let lost_packets = self.sent_packets.get_range();
+        for (_packet_number, sent_info) in self.sent_packets.remove_range(lost_packets) {
+            let path = context.path_mut_by_id(sent_info.path_id);
+            path.congestion_controller.on_packet_discarded(
+                sent_info.sent_bytes as usize,
+                &mut congestion_controller::PathPublisher::new(publisher, sent_info.path_id),
+            );
+        }
+
+        context.on_packet_loss(&lost_packets, publisher);
+
+        self.time_of_last_ack_eliciting_packet = None;
+        self.loss_timer.cancel();
+    }
+
     //= https://www.rfc-editor.org/rfc/rfc9002#appendix-B.9
     //# When Initial or Handshake keys are discarded, packets sent in that
     //# space no longer count toward bytes in flight.
@@ -832,11 +950,11 @@ impl<Config: endpoint::Config> Manager<Config> {
         let (persistent_congestion_duration, lost_packets) =
             self.detect_lost_packets(now, context, publisher);
 
-        if let Some(lost_packets) = lost_packets {
+        if !lost_packets.is_empty() {
             self.remove_lost_packets(
                 now,
                 persistent_congestion_duration,
-                lost_packets,
+                &lost_packets,
                 random_generator,
                 context,
                 publisher,
@@ -849,7 +967,7 @@ impl<Config: endpoint::Config> Manager<Config> {
         now: Timestamp,
         context: &mut Ctx,
         publisher: &mut Pub,
-    ) -> (Duration, Option<PacketNumberRange>) {
+    ) -> (Duration, SmallVec<[PacketNumberRange; 1]>) {
         let largest_acked_packet = self
             .largest_acked_packet
             .expect("This function is only called after an ack has been received");
@@ -859,8 +977,12 @@ impl<Config: endpoint::Config> Manager<Config> {
             context.path_id(),
         );
 
-        let mut smallest_lost_packet = None;
-        let mut largest_lost_packet = None;
+        let mut lost_packets = SmallVec::new();
+        let mut lost_range: Option<PacketNumberRange> = None;
+        let mut lost_time: Option<Timestamp> = None;
+        // The paths that have packets that can't be declared lost yet
+        let mut pending_paths = SmallVec::<[path::Id; 4]>::new();
+
         for (unacked_packet_number, unacked_sent_info) in self.sent_packets.iter() {
             if unacked_packet_number > largest_acked_packet {
                 // sent_packets is ordered by packet number, so all remaining packets will be larger
@@ -868,6 +990,38 @@ impl<Config: endpoint::Config> Manager<Config> {
             }
 
             let unacked_path_id = unacked_sent_info.path_id;
+
+            // With multipath, a packet is only compared with the packets sent on the same path
+            let (packet_number, largest_acked_packet_number) = match self.multipath.as_ref() {
+                None => (unacked_packet_number, largest_acked_packet),
+                Some(paths) => {
+                    let largest_acked = paths
+                        .iter()
+                        .find(|state| state.path_id == unacked_path_id)
+                        .and_then(|state| state.largest_acked)
+                        .filter(|largest_acked| *largest_acked > unacked_sent_info.path_sequence);
+
+                    match largest_acked {
+                        Some(largest_acked) if !pending_paths.contains(&unacked_path_id) => {
+                            let sequence = |sequence| {
+                                self.space
+                                    .new_packet_number(VarInt::new(sequence).unwrap_or(VarInt::MAX))
+                            };
+                            (
+                                sequence(unacked_sent_info.path_sequence),
+                                sequence(largest_acked),
+                            )
+                        }
+                        _ => {
+                            // The packet isn't lost, so packets sent on other paths after it are
+                            // removed separately
+                            lost_packets.extend(lost_range.take());
+                            continue;
+                        }
+                    }
+                }
+            };
+
             let path = &context.path_by_id(unacked_path_id);
             // Calculate how long we wait until a packet is declared lost
             let time_threshold = path.rtt_estimator.loss_time_threshold();
@@ -876,17 +1030,19 @@ impl<Config: endpoint::Config> Manager<Config> {
                 time_threshold,
                 unacked_sent_info.time_sent,
                 loss::K_PACKET_THRESHOLD,
-                unacked_packet_number,
-                largest_acked_packet,
+                packet_number,
+                largest_acked_packet_number,
                 now,
             );
 
             match loss_outcome {
                 loss::Outcome::Lost => {
-                    if smallest_lost_packet.is_none() {
-                        smallest_lost_packet = Some(unacked_packet_number);
-                    }
-                    largest_lost_packet = Some(unacked_packet_number);
+                    lost_range = Some(match lost_range {
+                        Some(range) => PacketNumberRange::new(range.start(), unacked_packet_number),
+                        None => {
+                            PacketNumberRange::new(unacked_packet_number, unacked_packet_number)
+                        }
+                    });
 
                     // TODO merge contiguous packet numbers
                     let range =
@@ -894,43 +1050,51 @@ impl<Config: endpoint::Config> Manager<Config> {
                     context.on_packet_loss(&range, publisher);
 
                     persistent_congestion_calculator
-                        .on_lost_packet(unacked_packet_number, unacked_sent_info);
+                        .on_lost_packet(packet_number, unacked_sent_info);
                 }
-                loss::Outcome::NotLostYet { lost_time } => {
-                    //= https://www.rfc-editor.org/rfc/rfc9002#section-6.1.2
-                    //# If packets sent prior to the largest acknowledged packet cannot yet
-                    //# be declared lost, then a timer SHOULD be set for the remaining time.
-                    self.loss_timer.set(lost_time);
+                loss::Outcome::NotLostYet {
+                    lost_time: packet_lost_time,
+                } => {
                     debug_assert!(
-                        !self.loss_timer.is_expired(now),
+                        !packet_lost_time.has_elapsed(now),
                         "loss timer was not armed in the future; now: {now}, threshold: {time_threshold:?}\nmanager: {self:#?}"
                     );
+                    lost_time = Some(lost_time.map_or(packet_lost_time, |lost_time| {
+                        lost_time.min(packet_lost_time)
+                    }));
+
+                    if self.multipath.is_none() {
+                        // assuming sent_packets is ordered by packet number and sent time, all remaining
+                        // packets will have a larger packet number and sent time, and are thus not lost.
+                        break;
+                    }
 
-                    //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.1
-                    //# The PTO timer MUST NOT be set if a timer is set for time threshold
-                    //# loss detection; see Section 6.1.2.  A timer that is set for time
-                    //# threshold loss detection will expire earlier than the PTO timer in
-                    //# most cases and is less likely to spuriously retransmit data.
-                    self.pto.cancel();
-
-                    // assuming sent_packets is ordered by packet number and sent time, all remaining
-                    // packets will have a larger packet number and sent time, and are thus not lost.
-                    break;
+                    // The remaining packets sent on this path are not lost either
+                    pending_paths.push(unacked_path_id);
+                    lost_packets.extend(lost_range.take());
                 }
             }
         }
 
-        let sent_packets_to_remove = {
-            if let (Some(start), Some(end)) = (smallest_lost_packet, largest_lost_packet) {
-                Some(PacketNumberRange::new(start, end))
-            } else {
-                None
-            }
-        };
+        lost_packets.extend(lost_range);
+
+        if let Some(lost_time) = lost_time {
+            //= https://www.rfc-editor.org/rfc/rfc9002#section-6.1.2
+            //# If packets sent prior to the largest acknowledged packet cannot yet
+            //# be declared lost, then a timer SHOULD be set for the remaining time.
+            self.loss_timer.set(lost_time);
+
+            //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.1
+            //# The PTO timer MUST NOT be set if a timer is set for time threshold
+            //# loss detection; see Section 6.1.2.  A timer that is set for time
+            //# threshold loss detection will expire earlier than the PTO timer in
+            //# most cases and is less likely to spuriously retransmit data.
+            self.pto.cancel();
+        }
 
         (
             persistent_congestion_calculator.persistent_congestion_duration(),
-            sent_packets_to_remove,
+            lost_packets,
         )
     }
 
@@ -938,7 +1102,7 @@ impl<Config: endpoint::Config> Manager<Config> {
         &mut self,
         now: Timestamp,
         persistent_congestion_duration: Duration,
-        lost_packets: PacketNumberRange,
+        lost_packets: &[PacketNumberRange],
         random_generator: &mut Config::RandomGenerator,
         context: &mut Ctx,
         publisher: &mut Pub,
@@ -948,97 +1112,105 @@ impl<Config: endpoint::Config> Manager<Config> {
         let mut prev_lost_packet_number = None;
 
         // Remove the lost packets and account for the bytes on the proper congestion controller
-        for (packet_number, sent_info) in self.sent_packets.remove_range(lost_packets) {
-            let path = context.path_mut_by_id(sent_info.path_id);
-
-            //= https://www.rfc-editor.org/rfc/rfc9002#section-7.6.2
-            //# A sender that does not have state for all packet
-            //# number spaces or an implementation that cannot compare send times
-            //# across packet number spaces MAY use state for just the packet number
-            //# space that was acknowledged.
-            let persistent_congestion = persistent_congestion_duration
+        for lost_packets in lost_packets {
+            for (packet_number, sent_info) in self.sent_packets.remove_range(*lost_packets) {
+                let path = context.path_mut_by_id(sent_info.path_id);
+
+                //= https://www.rfc-editor.org/rfc/rfc9002#section-7.6.2
+                //# A sender that does not have state for all packet
+                //# number spaces or an implementation that cannot compare send times
+                //# across packet number spaces MAY use state for just the packet number
+                //# space that was acknowledged.
+                let persistent_congestion = persistent_congestion_duration
                 > path.rtt_estimator.persistent_congestion_threshold()
                 // Check that the packet was sent on this path
                 && sent_info.path_id == current_path_id;
 
-            let new_loss_burst = prev_lost_packet_number.map_or(true, |prev: PacketNumber| {
-                packet_number.checked_distance(prev) != Some(1)
-            });
-
-            if sent_info.transmission_mode.is_mtu_probing() {
-                //= https://www.rfc-editor.org/rfc/rfc9000#section-14.4
-                //# Loss of a QUIC packet that is carried in a PMTU probe is therefore not a
-                //# reliable indication of congestion and SHOULD NOT trigger a congestion
-                //# control reaction; see Item 7 in Section 3 of [DPLPMTUD].
-
-                //= https://www.rfc-editor.org/rfc/rfc8899#section-3
-                //# Loss of a probe packet SHOULD NOT be treated as an
-                //# indication of congestion and SHOULD NOT trigger a congestion
-                //# control reaction [RFC4821] because this could result in
-                //# unnecessary reduction of the sending rate.
-                path.congestion_controller.on_packet_discarded(
-                    sent_info.sent_bytes as usize,
-                    &mut congestion_controller::PathPublisher::new(publisher, sent_info.path_id),
-                );
-            } else if sent_info.sent_bytes > 0 {
-                path.congestion_controller.on_packet_lost(
-                    sent_info.sent_bytes as u32,
-                    sent_info.cc_packet_info,
-                    persistent_congestion,
-                    new_loss_burst,
-                    random_generator,
-                    now,
-                    &mut congestion_controller::PathPublisher::new(publisher, sent_info.path_id),
-                );
-                is_congestion_event = true;
-            }
+                let new_loss_burst = prev_lost_packet_number.map_or(true, |prev: PacketNumber| {
+                    packet_number.checked_distance(prev) != Some(1)
+                });
+
+                if sent_info.transmission_mode.is_mtu_probing() {
+                    //= https://www.rfc-editor.org/rfc/rfc9000#section-14.4
+                    //# Loss of a QUIC packet that is carried in a PMTU probe is therefore not a
+                    //# reliable indication of congestion and SHOULD NOT trigger a congestion
+                    //# control reaction; see Item 7 in Section 3 of [DPLPMTUD].
+
+                    //= https://www.rfc-editor.org/rfc/rfc8899#section-3
+                    //# Loss of a probe packet SHOULD NOT be treated as an
+                    //# indication of congestion and SHOULD NOT trigger a congestion
+                    //# control reaction [RFC4821] because this could result in
+                    //# unnecessary reduction of the sending rate.
+                    path.congestion_controller.on_packet_discarded(
+                        sent_info.sent_bytes as usize,
+                        &mut congestion_controller::PathPublisher::new(
+                            publisher,
+                            sent_info.path_id,
+                        ),
+                    );
+                } else if sent_info.sent_bytes > 0 {
+                    path.congestion_controller.on_packet_lost(
+                        sent_info.sent_bytes as u32,
+                        sent_info.cc_packet_info,
+                        persistent_congestion,
+                        new_loss_burst,
+                        random_generator,
+                        now,
+                        &mut congestion_controller::PathPublisher::new(
+                            publisher,
+                            sent_info.path_id,
+                        ),
+                    );
+                    is_congestion_event = true;
+                }
 
-            publisher.on_packet_lost(event::builder::PacketLost {
-                packet_header: event::builder::PacketHeader::new(
-                    packet_number,
-                    publisher.quic_version(),
-                ),
-                path: path_event!(path, current_path_id),
-                bytes_lost: sent_info.sent_bytes,
-                is_mtu_probe: sent_info.transmission_mode.is_mtu_probing(),
-            });
+                publisher.on_packet_lost(event::builder::PacketLost {
+                    packet_header: event::builder::PacketHeader::new(
+                        packet_number,
+                        publisher.quic_version(),
+                    ),
+                    path: path_event!(path, current_path_id),
+                    bytes_lost: sent_info.sent_bytes,
+                    is_mtu_probe: sent_info.transmission_mode.is_mtu_probing(),
+                });
 
-            let path_id = sent_info.path_id;
+                let path_id = sent_info.path_id;
 
-            // Notify the ECN controller of packet loss for blackhole detection.
-            path.ecn_controller.on_packet_loss(
-                sent_info.time_sent,
-                sent_info.ecn,
-                now,
-                path_event!(path, path_id),
-                publisher,
-            );
+                // Notify the ECN controller of packet loss for blackhole detection.
+                path.ecn_controller.on_packet_loss(
+                    sent_info.time_sent,
+                    sent_info.ecn,
+                    now,
+                    path_event!(path, path_id),
+                    publisher,
+                );
 
-            if persistent_congestion {
-                //= https://www.rfc-editor.org/rfc/rfc9002#section-5.2
-                //# Endpoints SHOULD set the min_rtt to the newest RTT sample after
-                //# persistent congestion is established.
-                path.rtt_estimator.on_persistent_congestion();
-            }
+                if persistent_congestion {
+                    //= https://www.rfc-editor.org/rfc/rfc9002#section-5.2
+                    //# Endpoints SHOULD set the min_rtt to the newest RTT sample after
+                    //# persistent congestion is established.
+                    path.rtt_estimator.on_persistent_congestion();
+                }
 
-            // Notify the MTU controller of packet loss even if it wasn't a probe since it uses
-            // that information for blackhole detection.
-            match path.mtu_controller.on_packet_loss(
-                packet_number,
-                sent_info.sent_bytes,
-                new_loss_burst,
-                now,
-                &mut path.congestion_controller,
-                sent_info.path_id,
-                publisher,
-            ) {
-                MtuResult::MtuUpdated(max_datagram_size) => {
-                    context.on_mtu_update(max_datagram_size)
+                // Notify the MTU controller of packet loss even if it wasn't a probe since it uses
+                // that information for blackhole detection.
+                match path.mtu_controller.on_packet_loss(
+                    packet_number,
+                    sent_info.sent_bytes,
+                    new_loss_burst,
+                    now,
+                    &mut path.congestion_controller,
+                    sent_info.path_id,
+                    publisher,
+                ) {
+                    MtuResult::MtuUpdated(max_datagram_size) => {
+                        context.on_mtu_update(max_datagram_size)
+                    }
+                    MtuResult::NoChange => {}
                 }
-                MtuResult::NoChange => {}
-            }
 
-            prev_lost_packet_number = Some(packet_number);
+                prev_lost_packet_number = Some(packet_number);
+            }
         }
 
         if is_congestion_event {
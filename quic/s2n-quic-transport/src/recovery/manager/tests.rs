@@ -2013,6 +2013,131 @@ fn remove_lost_packets_persistent_congestion_path_aware() {
         .is_none());
 }
 
+// Setup:
+// - multipath is enabled
+// - packet 0 is sent on the first path and packets 1-4 on the second path
+//
+// Trigger 1:
+// - packets 1-4 are acked on the second path
+//
+// Expectation 1:
+// - packet 0 is not lost, since no later packet on the first path was acked
+//
+// Trigger 2:
+// - packets 5-7 are sent on the first path and packet 7 is acked
+//
+// Expectation 2:
+// - only packet 0 is lost, since packets 5 and 6 were sent on the first path within
+//   K_PACKET_THRESHOLD packets of packet 7
+#[test]
+fn detect_lost_packets_multipath() {
+    // Setup:
+    let space = PacketNumberSpace::ApplicationData;
+    let mut publisher = Publisher::no_snapshot();
+    let (first_addr, first_path_id, second_addr, second_path_id, mut manager, mut path_manager) =
+        helper_generate_multi_path_manager(space, &mut publisher);
+    let ecn = ExplicitCongestionNotification::default();
+    let mut context = MockContext::new(&mut path_manager);
+    manager.enable_multipath();
+
+    let packet_bytes = 128;
+    let outcome = transmission::Outcome {
+        ack_elicitation: AckElicitation::Eliciting,
+        is_congestion_controlled: true,
+        bytes_sent: packet_bytes,
+        bytes_progressed: 0,
+    };
+    let time_sent = time::now() + Duration::from_secs(10);
+
+    for packet_number in 0..=4 {
+        let path_id = if packet_number == 0 {
+            first_path_id
+        } else {
+            second_path_id
+        };
+        context.set_path_id(path_id);
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(packet_number)),
+            outcome,
+            time_sent,
+            ecn,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+
+    // Trigger 1:
+    helper_ack_packets_on_path(
+        1..=4,
+        time_sent + Duration::from_millis(10),
+        &mut context,
+        &mut manager,
+        second_addr,
+        None,
+        &mut publisher,
+    );
+
+    // Expectation 1:
+    assert!(manager
+        .sent_packets
+        .get(space.new_packet_number(VarInt::from_u8(0)))
+        .is_some());
+    assert_eq!(
+        context
+            .path_by_id(first_path_id)
+            .congestion_controller
+            .lost_bytes,
+        0
+    );
+    assert!(!manager.loss_timer.is_armed());
+
+    // Trigger 2:
+    context.set_path_id(first_path_id);
+    for packet_number in 5..=7 {
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(packet_number)),
+            outcome,
+            time_sent,
+            ecn,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+    helper_ack_packets_on_path(
+        7..=7,
+        time_sent + Duration::from_millis(20),
+        &mut context,
+        &mut manager,
+        first_addr,
+        None,
+        &mut publisher,
+    );
+
+    // Expectation 2:
+    assert!(manager
+        .sent_packets
+        .get(space.new_packet_number(VarInt::from_u8(0)))
+        .is_none());
+    for packet_number in 5..=6 {
+        assert!(manager
+            .sent_packets
+            .get(space.new_packet_number(VarInt::from_u8(packet_number)))
+            .is_some());
+    }
+    assert_eq!(
+        context
+            .path_by_id(first_path_id)
+            .congestion_controller
+            .lost_bytes,
+        packet_bytes as u32
+    );
+    assert!(manager.loss_timer.is_armed());
+}
+
 #[test]
 fn detect_and_remove_lost_packets_nothing_lost() {
     let space = PacketNumberSpace::ApplicationData;
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ack::AckManager,
+    ack::{AckFrequencySender, AckManager},
     connection::{self, ConnectionTransmissionContext, ProcessingError},
     dc, endpoint, path,
     path::{path_event, Path},
@@ -10,8 +10,8 @@ use crate::{
     recovery,
     recovery::CongestionController,
     space::{
-        datagram, keep_alive::KeepAlive, CryptoStream, HandshakeStatus, PacketSpace,
-        TxPacketNumbers,
+        datagram, keep_alive::KeepAlive, CryptoStream, HandshakeStatus, PacketSpace, ReceiveSpaces,
+        TransmitSpaces, TxPacketNumbers,
     },
     stream::Manager as _,
     sync::flag,
@@ -22,35 +22,47 @@ use core::{convert::TryInto, fmt, marker::PhantomData};
 use s2n_codec::EncoderBuffer;
 use s2n_quic_core::{
     counter::{Counter, Saturating},
-    crypto::{application::KeySet, limited, tls, CryptoSuite},
+    crypto::{application::KeySet, limited, tls, CryptoSuite, PathKey},
     dc::Endpoint as _,
     event::{self, ConnectionPublisher as _, IntoEvent},
     frame::{
         ack::AckRanges, crypto::CryptoRef, datagram::DatagramRef, stream::StreamRef, Ack,
-        ConnectionClose, DataBlocked, DcStatelessResetTokens, HandshakeDone, MaxData,
-        MaxStreamData, MaxStreams, NewConnectionId, NewToken, PathChallenge, PathResponse,
-        ResetStream, RetireConnectionId, StopSending, StreamDataBlocked, StreamsBlocked,
+        AckFrequency, ConnectionClose, DataBlocked, DcStatelessResetTokens, HandshakeDone,
+        ImmediateAck, MaxData, MaxStreamData, MaxStreams, NewConnectionId, NewToken, PathAbandon,
+        PathAck, PathChallenge, PathResponse, ResetStream, ResetStreamAt, RetireConnectionId,
+        StopSending, StreamDataBlocked, StreamsBlocked,
     },
     inet::DatagramInfo,
     packet::{
         encoding::{PacketEncoder, PacketEncodingError},
         number::{PacketNumber, PacketNumberRange, PacketNumberSpace, SlidingWindow},
         short::{CleartextShort, ProtectedShort, Short, SpinBit},
+        zero_rtt::{CleartextZeroRtt, ProtectedZeroRtt, ZeroRtt},
     },
     random::Generator,
     recovery::MAX_BURST_PACKETS,
     time::{timer, Timestamp},
     transport,
 };
+use smallvec::SmallVec;
 
 // Ensure there is a gap between skipped packet numbers
 const MIN_SKIP_COUNTER_VALUE: u32 = MAX_BURST_PACKETS * 3;
 
+type OneRttKey<Config> =
+    <<<Config as endpoint::Config>::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey;
+type OneRttHeaderKey<Config> = <<<Config as endpoint::Config>::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttHeaderKey;
+type ZeroRttKey<Config> =
+    <<<Config as endpoint::Config>::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey;
+type ZeroRttHeaderKey<Config> = <<<Config as endpoint::Config>::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttHeaderKey;
+
 pub struct ApplicationSpace<Config: endpoint::Config> {
     /// Transmission Packet numbers
     pub tx_packet_numbers: TxPacketNumbers,
     /// Ack manager
     pub ack_manager: AckManager,
+    /// Requests an acknowledgment frequency from the peer
+    pub ack_frequency: AckFrequencySender,
     /// All streams that are managed through this connection
     pub stream_manager: Config::StreamManager,
     /// The current state of the Spin bit
@@ -65,13 +77,27 @@ pub struct ApplicationSpace<Config: endpoint::Config> {
     //# An endpoint MUST NOT initiate a key update prior to having confirmed
     //# the handshake (Section 4.1.2).
     /// The crypto suite for application data
-    /// TODO: What about ZeroRtt?
-    key_set: KeySet<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey>,
-    header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttHeaderKey,
+    ///
+    /// This is `None` while a client is sending early data and is still waiting on 1-RTT keys.
+    one_rtt_crypto: Option<(KeySet<OneRttKey<Config>>, OneRttHeaderKey<Config>)>,
+    /// The crypto suite for early data
+    ///
+    /// 0-RTT and 1-RTT packets share the application data packet number space.
+    zero_rtt_crypto: Option<Box<(ZeroRttKey<Config>, ZeroRttHeaderKey<Config>)>>,
 
     ping: flag::Ping,
     keep_alive: KeepAlive,
     processed_packet_numbers: SlidingWindow,
+    /// Receive state for the packet number spaces of multipath paths
+    ///
+    /// This is `None` unless the multipath extension was negotiated.
+    multipath: Option<ReceiveSpaces>,
+    /// Send state for the packet number spaces of multipath paths
+    ///
+    /// This is `None` unless the multipath extension was negotiated.
+    multipath_tx: Option<TransmitSpaces>,
+    /// The multipath path id of the packet currently being processed
+    rx_path_id: u32,
     recovery_manager: recovery::Manager<Config>,
     pub datagram_manager: datagram::Manager<Config>,
     pub dc_manager: dc::Manager<Config>,
@@ -96,9 +122,11 @@ impl<Config: endpoint::Config> fmt::Debug for ApplicationSpace<Config> {
 }
 
 impl<Config: endpoint::Config> ApplicationSpace<Config> {
+    /// Creates an application space without any packet protection keys
+    ///
+    /// Either [`Self::on_zero_rtt_keys`] or [`Self::on_one_rtt_keys`] must be called before any
+    /// packets can be sent.
     pub fn new(
-        key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey,
-        header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttHeaderKey,
         now: Timestamp,
         stream_manager: Config::StreamManager,
         ack_manager: AckManager,
@@ -106,19 +134,21 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         datagram_manager: datagram::Manager<Config>,
         dc_manager: dc::Manager<Config>,
     ) -> Self {
-        let key_set = KeySet::new(key, Self::key_limits());
-
         Self {
             tx_packet_numbers: TxPacketNumbers::new(PacketNumberSpace::ApplicationData, now),
             ack_manager,
+            ack_frequency: AckFrequencySender::default(),
             spin_bit: SpinBit::Zero,
             stream_manager,
             crypto_stream: CryptoStream::new(),
-            key_set,
-            header_key,
+            one_rtt_crypto: None,
+            zero_rtt_crypto: None,
             ping: flag::Ping::default(),
             keep_alive,
             processed_packet_numbers: SlidingWindow::default(),
+            multipath: None,
+            multipath_tx: None,
+            rx_path_id: 0,
             recovery_manager: recovery::Manager::new(PacketNumberSpace::ApplicationData),
             datagram_manager,
             dc_manager,
@@ -127,6 +157,72 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         }
     }
 
+    /// Installs the keys used to protect early data
+    pub fn on_zero_rtt_keys(
+        &mut self,
+        key: ZeroRttKey<Config>,
+        header_key: ZeroRttHeaderKey<Config>,
+    ) {
+        debug_assert!(self.one_rtt_crypto.is_none());
+        self.zero_rtt_crypto = Some(Box::new((key, header_key)));
+    }
+
+    /// Installs the keys used to protect 1-RTT packets
+    pub fn on_one_rtt_keys(&mut self, key: OneRttKey<Config>, header_key: OneRttHeaderKey<Config>) {
+        debug_assert!(self.one_rtt_crypto.is_none());
+        let key_set = KeySet::new(key, Self::key_limits());
+        self.one_rtt_crypto = Some((key_set, header_key));
+
+        if Config::ENDPOINT_TYPE.is_client() {
+            //= https://www.rfc-editor.org/rfc/rfc9001#section-4.9.3
+            //# Therefore, a client SHOULD discard 0-RTT keys as soon as it installs
+            //# 1-RTT keys as they have no use after that moment.
+            self.discard_zero_rtt_keys();
+        }
+    }
+
+    /// Discards the early data keys, if any
+    pub fn discard_zero_rtt_keys(&mut self) {
+        self.zero_rtt_crypto = None;
+    }
+
+    /// Returns `true` if the space is able to protect and remove protection from 1-RTT packets
+    pub fn has_one_rtt_keys(&self) -> bool {
+        self.one_rtt_crypto.is_some()
+    }
+
+    /// Starts tracking a packet number space for each path of the multipath extension
+    pub fn enable_multipath(&mut self) {
+        debug_assert!(self.multipath.is_none());
+        self.multipath = Some(ReceiveSpaces::new(self.ack_manager.ack_settings));
+        self.multipath_tx = Some(TransmitSpaces::default());
+        self.recovery_manager.enable_multipath();
+    }
+
+    /// Called by the client when the server rejected early data
+    ///
+    /// All of the packets sent with 0-RTT keys are declared lost and the streams that were
+    /// opened are failed back to the application, since the server never received them.
+    pub fn on_zero_rtt_rejected<Pub: event::ConnectionPublisher>(
+        &mut self,
+        path_manager: &mut path::Manager<Config>,
+        handshake_status: &mut HandshakeStatus,
+        local_id_registry: &mut connection::LocalIdRegistry,
+        publisher: &mut Pub,
+    ) {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+        debug_assert!(self.one_rtt_crypto.is_none());
+
+        self.discard_zero_rtt_keys();
+
+        let path_id = path_manager.active_path_id();
+        let (recovery_manager, mut context) =
+            self.recovery(handshake_status, local_id_registry, path_id, path_manager);
+        recovery_manager.on_zero_rtt_rejected(&mut context, publisher);
+
+        self.stream_manager.on_zero_rtt_rejected();
+    }
+
     /// Returns true if the packet number has already been processed
     pub fn is_duplicate<Pub: event::ConnectionPublisher>(
         &self,
@@ -135,7 +231,10 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         path: &path::Path<Config>,
         publisher: &mut Pub,
     ) -> bool {
-        let packet_check = self.processed_packet_numbers.check(packet_number);
+        let packet_check = match (self.rx_path_id, self.multipath.as_ref()) {
+            (0, _) | (_, None) => self.processed_packet_numbers.check(packet_number),
+            (rx_path_id, Some(multipath)) => multipath.check(rx_path_id, packet_number),
+        };
         if let Err(error) = packet_check {
             publisher.on_duplicate_packet(event::builder::DuplicatePacket {
                 packet_header: event::builder::PacketHeader::new(
@@ -159,6 +258,13 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         handshake_status: &mut HandshakeStatus,
         buffer: EncoderBuffer<'a>,
     ) -> Result<(transmission::Outcome, EncoderBuffer<'a>), PacketEncodingError<'a>> {
+        // Early data is only used for application data; MTU probes wait for 1-RTT keys
+        if self.one_rtt_crypto.is_none()
+            && (self.zero_rtt_crypto.is_none() || context.transmission_mode.is_mtu_probing())
+        {
+            return Err(PacketEncodingError::EmptyPayload(buffer));
+        }
+
         let mut packet_number = self.tx_packet_numbers.next();
 
         // This function can return early and not transmit a packet for various reasons
@@ -199,10 +305,14 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             }
         }
 
-        let packet_number_encoder = self.packet_number_encoder();
+        let tx_path_id = context.path_manager.tx_path_id(context.path_id);
+        let (path_packet_number, packet_number_encoder) =
+            self.path_packet_number(tx_path_id, packet_number);
         let mut outcome = transmission::Outcome::default();
 
         let destination_connection_id = context.path().peer_connection_id;
+        let source_connection_id = context.path().local_connection_id;
+        let quic_version = context.quic_version;
         let transmission_mode = context.transmission_mode;
         let min_packet_len = context.min_packet_len;
         let bytes_progressed = self.stream_manager.outgoing_bytes_progressed();
@@ -217,6 +327,8 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
                 context.local_id_registry,
                 context.transmission_mode,
                 &mut self.ack_manager,
+                self.multipath.as_mut(),
+                &mut self.ack_frequency,
                 handshake_status,
                 &mut self.ping,
                 &mut self.stream_manager,
@@ -235,29 +347,57 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         };
 
         let spin_bit = self.spin_bit;
-        let header_key = &self.header_key;
         let (_protected_packet, buffer) =
-            self.key_set
-                .encrypt_packet(buffer, |buffer, key, key_phase| {
+            if let Some((key_set, header_key)) = self.one_rtt_crypto.as_mut() {
+                let header_key = &*header_key;
+                key_set.encrypt_packet(buffer, |buffer, key, key_phase| {
                     let packet = Short {
                         spin_bit,
                         key_phase,
                         destination_connection_id,
-                        packet_number,
+                        packet_number: path_packet_number,
                         payload,
                     };
                     packet.encode_packet(
-                        key,
+                        &mut PathKey::new(key, tx_path_id),
                         header_key,
                         packet_number_encoder,
                         min_packet_len,
                         buffer,
                     )
-                })?;
+                })?
+            } else if let Some((key, header_key)) = self.zero_rtt_crypto.as_deref_mut() {
+                //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.3
+                //# A client MUST NOT send 0-RTT packets once it starts processing 1-RTT
+                //# packets from the server.
+
+                // 0-RTT keys are discarded as soon as 1-RTT keys are installed, which happens prior
+                // to processing any 1-RTT packets.
+                let packet = ZeroRtt {
+                    version: quic_version,
+                    destination_connection_id,
+                    source_connection_id,
+                    packet_number,
+                    payload,
+                };
+                packet.encode_packet(
+                    key,
+                    &*header_key,
+                    packet_number_encoder,
+                    min_packet_len,
+                    buffer,
+                )?
+            } else {
+                return Err(PacketEncodingError::EmptyPayload(buffer));
+            };
 
         outcome.bytes_progressed +=
             (self.stream_manager.outgoing_bytes_progressed() - bytes_progressed).as_u64() as usize;
 
+        if let (1.., Some(multipath)) = (tx_path_id, self.multipath_tx.as_mut()) {
+            multipath.on_packet_sent(tx_path_id, path_packet_number, packet_number);
+        }
+
         self.on_packet_sent(
             context,
             packet_number,
@@ -306,13 +446,12 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             *skip_counter -= 1_u32;
         }
 
+        let packet_header =
+            self.sent_packet_header(packet_number, context.publisher.quic_version());
         context
             .publisher
             .on_packet_sent(event::builder::PacketSent {
-                packet_header: event::builder::PacketHeader::new(
-                    packet_number,
-                    context.publisher.quic_version(),
-                ),
+                packet_header,
                 packet_len: outcome.bytes_sent,
             });
 
@@ -335,6 +474,22 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         }
     }
 
+    /// Returns the event header for a packet sent with the currently installed keys
+    fn sent_packet_header(
+        &self,
+        packet_number: PacketNumber,
+        version: u32,
+    ) -> event::builder::PacketHeader {
+        if self.one_rtt_crypto.is_none() && self.zero_rtt_crypto.is_some() {
+            event::builder::PacketHeader::ZeroRtt {
+                number: packet_number.into_event(),
+                version,
+            }
+        } else {
+            event::builder::PacketHeader::new(packet_number, version)
+        }
+    }
+
     fn packet_skipped_event(
         context: &mut ConnectionTransmissionContext<Config>,
         skip_packet_number: PacketNumber,
@@ -370,7 +525,15 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
     ) -> Result<(transmission::Outcome, EncoderBuffer<'a>), PacketEncodingError<'a>> {
         let packet_number = self.tx_packet_numbers.next();
 
-        let packet_number_encoder = self.packet_number_encoder();
+        let tx_path_id = context.path_manager.tx_path_id(context.path_id);
+        let (path_packet_number, packet_number_encoder) =
+            self.path_packet_number(tx_path_id, packet_number);
+
+        // CONNECTION_CLOSE frames are only sent with 1-RTT keys since the server may have
+        // rejected early data
+        let Some((key_set, header_key)) = self.one_rtt_crypto.as_mut() else {
+            return Err(PacketEncodingError::EmptyPayload(buffer));
+        };
 
         let mut outcome = transmission::Outcome::default();
         let destination_connection_id = context.path().peer_connection_id;
@@ -391,25 +554,28 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
 
         let spin_bit = self.spin_bit;
         let min_packet_len = context.min_packet_len;
-        let header_key = &self.header_key;
+        let header_key = &*header_key;
         let (_protected_packet, buffer) =
-            self.key_set
-                .encrypt_packet(buffer, |buffer, key, key_phase| {
-                    let packet = Short {
-                        spin_bit,
-                        key_phase,
-                        destination_connection_id,
-                        packet_number,
-                        payload,
-                    };
-                    packet.encode_packet(
-                        key,
-                        header_key,
-                        packet_number_encoder,
-                        min_packet_len,
-                        buffer,
-                    )
-                })?;
+            key_set.encrypt_packet(buffer, |buffer, key, key_phase| {
+                let packet = Short {
+                    spin_bit,
+                    key_phase,
+                    destination_connection_id,
+                    packet_number: path_packet_number,
+                    payload,
+                };
+                packet.encode_packet(
+                    &mut PathKey::new(key, tx_path_id),
+                    header_key,
+                    packet_number_encoder,
+                    min_packet_len,
+                    buffer,
+                )
+            })?;
+
+        if let (1.., Some(multipath)) = (tx_path_id, self.multipath_tx.as_mut()) {
+            multipath.on_transmit(tx_path_id, path_packet_number);
+        }
 
         context
             .publisher
@@ -463,7 +629,12 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         publisher: &mut Pub,
     ) {
         self.ack_manager.on_timeout(timestamp);
-        self.key_set.on_timeout(timestamp);
+        if let Some(multipath) = self.multipath.as_mut() {
+            multipath.on_timeout(timestamp);
+        }
+        if let Some((key_set, _header_key)) = self.one_rtt_crypto.as_mut() {
+            key_set.on_timeout(timestamp);
+        }
 
         let (recovery_manager, mut context) = self.recovery(
             handshake_status,
@@ -544,9 +715,26 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         self.keep_alive.update(enabled);
     }
 
-    /// Returns the Packet Number to be used when encoding outgoing packets
-    fn packet_number_encoder(&self) -> PacketNumber {
-        self.tx_packet_numbers.largest_sent_packet_number_acked()
+    /// Returns the packet number a packet is sent with on the given multipath path id, along
+    /// with the packet number used to encode it
+    ///
+    /// `packet_number` is the application space packet number the packet is tracked with.
+    fn path_packet_number(
+        &self,
+        tx_path_id: u32,
+        packet_number: PacketNumber,
+    ) -> (PacketNumber, PacketNumber) {
+        match (tx_path_id, self.multipath_tx.as_ref()) {
+            (_, None) => (
+                packet_number,
+                self.tx_packet_numbers.largest_sent_packet_number_acked(),
+            ),
+            (0, Some(multipath)) => (packet_number, multipath.largest_sent_packet_number_acked(0)),
+            (tx_path_id, Some(multipath)) => (
+                multipath.next_packet_number(tx_path_id),
+                multipath.largest_sent_packet_number_acked(tx_path_id),
+            ),
+        }
     }
 
     fn recovery<'a>(
@@ -563,6 +751,9 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             &mut self.recovery_manager,
             RecoveryContext {
                 ack_manager: &mut self.ack_manager,
+                multipath: self.multipath.as_mut(),
+                multipath_tx: self.multipath_tx.as_mut(),
+                ack_frequency: &mut self.ack_frequency,
                 crypto_stream: &mut self.crypto_stream,
                 handshake_status,
                 ping: &mut self.ping,
@@ -572,6 +763,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
                 path_manager,
                 tx_packet_numbers: &mut self.tx_packet_numbers,
                 dc_manager: &mut self.dc_manager,
+                datagram_manager: &mut self.datagram_manager,
             },
         )
     }
@@ -585,17 +777,35 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
     }
 
     /// Validate packets in the Application packet space
+    ///
+    /// `rx_path_id` is the multipath path id of the packet number space the packet was sent in.
     pub fn validate_and_decrypt_packet<'a, Pub: event::ConnectionPublisher>(
         &mut self,
         protected: ProtectedShort<'a>,
         datagram: &DatagramInfo,
+        rx_path_id: u32,
         path_id: path::Id,
         path: &path::Path<Config>,
         publisher: &mut Pub,
     ) -> Result<CleartextShort<'a>, ProcessingError> {
-        let largest_acked = self.ack_manager.largest_received_packet_number_acked();
+        self.rx_path_id = rx_path_id;
+        let largest_acked = match (rx_path_id, self.multipath.as_ref()) {
+            (0, _) | (_, None) => self.ack_manager.largest_received_packet_number_acked(),
+            (rx_path_id, Some(multipath)) => {
+                multipath.largest_received_packet_number_acked(rx_path_id)
+            }
+        };
+        let (key_set, header_key) = self.one_rtt_crypto.as_mut().ok_or_else(|| {
+            publisher.on_packet_dropped(event::builder::PacketDropped {
+                reason: event::builder::PacketDropReason::UnprotectFailed {
+                    space: event::builder::KeySpace::OneRtt,
+                    path: path_event!(path, path_id),
+                },
+            });
+            ProcessingError::Other
+        })?;
         let packet = protected
-            .unprotect(&self.header_key, largest_acked)
+            .unprotect(&*header_key, largest_acked)
             .map_err(|err| {
                 publisher.on_packet_dropped(event::builder::PacketDropped {
                     reason: event::builder::PacketDropReason::UnprotectFailed {
@@ -609,8 +819,9 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         let packet_number = packet.packet_number;
         let packet_header =
             event::builder::PacketHeader::new(packet.packet_number, publisher.quic_version());
-        let decrypted = self.key_set.decrypt_packet(
+        let decrypted = key_set.decrypt_packet_for_path(
             packet,
+            rx_path_id,
             largest_acked,
             //= https://www.rfc-editor.org/rfc/rfc9001#section-6.3
             //# For a short period after a key
@@ -632,7 +843,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             Ok((_, Some(generation))) => {
                 publisher.on_key_update(event::builder::KeyUpdate {
                     key_type: event::builder::KeyType::OneRtt { generation },
-                    cipher_suite: self.key_set.cipher_suite().into_event(),
+                    cipher_suite: key_set.cipher_suite().into_event(),
                 });
             }
             Ok(_) => {}
@@ -665,6 +876,69 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         decrypted.map(|x| x.0)
     }
 
+    /// Validate 0-RTT packets in the Application packet space
+    ///
+    /// Returns `None` if the space doesn't have any 0-RTT keys, which happens if early data was
+    /// rejected or the keys have already been discarded.
+    pub fn validate_and_decrypt_zero_rtt_packet<'a, Pub: event::ConnectionPublisher>(
+        &mut self,
+        protected: ProtectedZeroRtt<'a>,
+        datagram: &DatagramInfo,
+        path_id: path::Id,
+        path: &path::Path<Config>,
+        publisher: &mut Pub,
+    ) -> Option<Result<CleartextZeroRtt<'a>, ProcessingError>> {
+        debug_assert!(Config::ENDPOINT_TYPE.is_server());
+
+        // 0-RTT packets are always sent in the packet number space of the initial path
+        self.rx_path_id = 0;
+
+        let (key, header_key) = self.zero_rtt_crypto.as_deref()?;
+        let largest_acked = self.ack_manager.largest_received_packet_number_acked();
+
+        let packet = match protected.unprotect(header_key, largest_acked) {
+            Ok(packet) => packet,
+            Err(err) => {
+                publisher.on_packet_dropped(event::builder::PacketDropped {
+                    reason: event::builder::PacketDropReason::UnprotectFailed {
+                        space: event::builder::KeySpace::ZeroRtt,
+                        path: path_event!(path, path_id),
+                    },
+                });
+                return Some(Err(err.into()));
+            }
+        };
+
+        let packet_number = packet.packet_number;
+        let packet_header = event::builder::PacketHeader::ZeroRtt {
+            number: packet_number.into_event(),
+            version: publisher.quic_version(),
+        };
+        let decrypted = packet.decrypt(key);
+
+        if decrypted.is_err() {
+            publisher.on_packet_dropped(event::builder::PacketDropped {
+                reason: event::builder::PacketDropReason::DecryptionFailed {
+                    packet_header,
+                    path: path_event!(path, path_id),
+                },
+            });
+        }
+
+        // We perform decryption prior to checking for duplicate to avoid short-circuiting
+        // and maintain constant-time operation.
+        if self.is_duplicate(packet_number, path_id, path, publisher) {
+            return Some(Err(ProcessingError::Other));
+        }
+
+        if decrypted.is_ok() {
+            // reset the keep alive timer after receiving a packet
+            self.keep_alive.reset(datagram.timestamp);
+        }
+
+        Some(decrypted.map_err(ProcessingError::from))
+    }
+
     fn key_limits() -> limited::Limits {
         limited::Limits::default()
     }
@@ -674,8 +948,11 @@ impl<Config: endpoint::Config> timer::Provider for ApplicationSpace<Config> {
     #[inline]
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
         self.ack_manager.timers(query)?;
+        self.multipath.timers(query)?;
         self.recovery_manager.timers(query)?;
-        self.key_set.timers(query)?;
+        if let Some((key_set, _header_key)) = self.one_rtt_crypto.as_ref() {
+            key_set.timers(query)?;
+        }
         self.stream_manager.timers(query)?;
         self.keep_alive.timers(query)?;
 
@@ -690,6 +967,10 @@ impl<Config: endpoint::Config> transmission::interest::Provider for ApplicationS
         query: &mut Q,
     ) -> transmission::interest::Result {
         self.ack_manager.transmission_interest(query)?;
+        if let Some(multipath) = self.multipath.as_ref() {
+            multipath.transmission_interest(query)?;
+        }
+        self.ack_frequency.transmission_interest(query)?;
         self.ping.transmission_interest(query)?;
         self.crypto_stream.transmission_interest(query)?;
         self.recovery_manager.transmission_interest(query)?;
@@ -708,6 +989,9 @@ impl<Config: endpoint::Config> connection::finalization::Provider for Applicatio
 
 struct RecoveryContext<'a, Config: endpoint::Config> {
     ack_manager: &'a mut AckManager,
+    multipath: Option<&'a mut ReceiveSpaces>,
+    multipath_tx: Option<&'a mut TransmitSpaces>,
+    ack_frequency: &'a mut AckFrequencySender,
     handshake_status: &'a mut HandshakeStatus,
     crypto_stream: &'a mut CryptoStream,
     ping: &'a mut flag::Ping,
@@ -717,6 +1001,7 @@ struct RecoveryContext<'a, Config: endpoint::Config> {
     path_manager: &'a mut path::Manager<Config>,
     tx_packet_numbers: &'a mut TxPacketNumbers,
     dc_manager: &'a mut dc::Manager<Config>,
+    datagram_manager: &'a mut datagram::Manager<Config>,
 }
 
 impl<Config: endpoint::Config> recovery::Context<Config> for RecoveryContext<'_, Config> {
@@ -777,15 +1062,23 @@ impl<Config: endpoint::Config> recovery::Context<Config> for RecoveryContext<'_,
         self.dc_manager
             .on_packet_ack(packet_number_range, publisher);
         self.crypto_stream.on_packet_ack(packet_number_range);
+        self.ack_frequency.on_packet_ack(packet_number_range);
         self.ping.on_packet_ack(packet_number_range);
         self.stream_manager.on_packet_ack(packet_number_range);
         self.local_id_registry.on_packet_ack(packet_number_range);
         self.path_manager.on_packet_ack(packet_number_range);
+        self.datagram_manager.on_packet_ack(packet_number_range);
     }
 
     fn on_packet_ack(&mut self, timestamp: Timestamp, packet_number_range: &PacketNumberRange) {
         self.ack_manager
             .on_packet_ack(timestamp, packet_number_range);
+        if let Some(multipath) = self.multipath.as_mut() {
+            multipath.on_packet_ack(timestamp, packet_number_range);
+        }
+        if let Some(multipath) = self.multipath_tx.as_mut() {
+            multipath.on_packet_ack(packet_number_range);
+        }
     }
 
     fn on_packet_loss<Pub: event::ConnectionPublisher>(
@@ -794,14 +1087,22 @@ impl<Config: endpoint::Config> recovery::Context<Config> for RecoveryContext<'_,
         publisher: &mut Pub,
     ) {
         self.ack_manager.on_packet_loss(packet_number_range);
+        if let Some(multipath) = self.multipath.as_mut() {
+            multipath.on_packet_loss(packet_number_range);
+        }
+        if let Some(multipath) = self.multipath_tx.as_mut() {
+            multipath.on_packet_loss(packet_number_range);
+        }
         self.crypto_stream.on_packet_loss(packet_number_range);
         self.handshake_status
             .on_packet_loss(packet_number_range, publisher);
         self.dc_manager.on_packet_loss(packet_number_range);
+        self.ack_frequency.on_packet_loss(packet_number_range);
         self.ping.on_packet_loss(packet_number_range);
         self.stream_manager.on_packet_loss(packet_number_range);
         self.local_id_registry.on_packet_loss(packet_number_range);
         self.path_manager.on_packet_loss(packet_number_range);
+        self.datagram_manager.on_packet_loss(packet_number_range);
     }
 
     fn on_rtt_update(&mut self, now: Timestamp) {
@@ -871,6 +1172,11 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         random_generator: &mut Config::RandomGenerator,
         publisher: &mut Pub,
     ) -> Result<(), transport::Error> {
+        // ACK frames acknowledge the packet number space of the initial path
+        if let Some(multipath) = self.multipath_tx.as_mut() {
+            multipath.on_initial_path_ack(&frame)?;
+        }
+
         let path = &mut path_manager[path_id];
         path.on_peer_validated();
         let (recovery_manager, mut context) =
@@ -886,6 +1192,87 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         )
     }
 
+    fn handle_path_ack_frame<A: AckRanges, Pub: event::ConnectionPublisher>(
+        &mut self,
+        frame: PathAck<A>,
+        timestamp: Timestamp,
+        path_id: path::Id,
+        path_manager: &mut path::Manager<Config>,
+        packet_number: PacketNumber,
+        handshake_status: &mut HandshakeStatus,
+        local_id_registry: &mut connection::LocalIdRegistry,
+        random_generator: &mut Config::RandomGenerator,
+        publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        if self.multipath_tx.is_none() || !path_manager.is_multipath_enabled() {
+            return Err(transport::Error::PROTOCOL_VIOLATION
+                .with_reason("PATH_ACK received without negotiating multipath"));
+        }
+
+        let tx_path_id = u32::try_from(frame.path_id.as_u64()).map_err(|_| {
+            transport::Error::PROTOCOL_VIOLATION
+                .with_reason("received an ACK for a packet that was not sent on the path")
+        })?;
+
+        // Acknowledgements are attributed to the path the packets were sent on, falling back to
+        // the path the frame was received on.
+        let path_id = path_manager
+            .path_id_for_tx_path_id(tx_path_id)
+            .unwrap_or(path_id);
+
+        if tx_path_id == 0 {
+            return self.handle_ack_frame(
+                frame.ack,
+                timestamp,
+                path_id,
+                path_manager,
+                packet_number,
+                handshake_status,
+                local_id_registry,
+                random_generator,
+                publisher,
+            );
+        }
+
+        // The recovery manager tracks the packets by their application space packet number so
+        // the acknowledged ranges are mapped from the packet number space of the path.
+        let multipath = self
+            .multipath_tx
+            .as_mut()
+            .expect("multipath was negotiated");
+        let mut ranges = SmallVec::new();
+        let Some(largest_acked) = multipath.on_path_ack(tx_path_id, &frame.ack, &mut ranges)?
+        else {
+            return Ok(());
+        };
+
+        path_manager[path_id].on_peer_validated();
+        let (recovery_manager, mut context) =
+            self.recovery(handshake_status, local_id_registry, path_id, path_manager);
+
+        recovery_manager.on_path_ack(
+            timestamp,
+            &ranges,
+            largest_acked,
+            frame.ack.ack_delay(),
+            frame.ack.ecn_counts,
+            packet_number,
+            random_generator,
+            &mut context,
+            publisher,
+        )
+    }
+
+    fn handle_path_abandon_frame<Pub: event::ConnectionPublisher>(
+        &mut self,
+        frame: PathAbandon,
+        path_manager: &mut path::Manager<Config>,
+        local_id_registry: &connection::LocalIdRegistry,
+        publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        path_manager.on_path_abandon(&frame, local_id_registry, publisher)
+    }
+
     fn handle_connection_close_frame<Pub: event::ConnectionPublisher>(
         &mut self,
         frame: ConnectionClose,
@@ -932,6 +1319,20 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         Ok(())
     }
 
+    fn handle_ack_frequency_frame(&mut self, frame: AckFrequency) -> Result<(), transport::Error> {
+        self.ack_manager.on_ack_frequency_frame(&frame)
+    }
+
+    fn handle_immediate_ack_frame(&mut self, _frame: ImmediateAck) -> Result<(), transport::Error> {
+        // IMMEDIATE_ACK frames can only be sent by a peer that received min_ack_delay
+        if !self.ack_manager.is_ack_frequency_enabled() {
+            return Err(transport::Error::PROTOCOL_VIOLATION
+                .with_reason("IMMEDIATE_ACK frame received without sending min_ack_delay"));
+        }
+
+        Ok(())
+    }
+
     fn handle_data_blocked_frame(&mut self, frame: DataBlocked) -> Result<(), transport::Error> {
         self.stream_manager.on_data_blocked(frame)
     }
@@ -955,6 +1356,13 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         self.stream_manager.on_reset_stream(&frame)
     }
 
+    fn handle_reset_stream_at_frame(
+        &mut self,
+        frame: ResetStreamAt,
+    ) -> Result<(), transport::Error> {
+        self.stream_manager.on_reset_stream_at(&frame)
+    }
+
     fn handle_stop_sending_frame(&mut self, frame: StopSending) -> Result<(), transport::Error> {
         self.stream_manager.on_stop_sending(&frame)
     }
@@ -1145,6 +1553,16 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         path: &Path<Config>,
         publisher: &mut Pub,
     ) -> Result<(), transport::Error> {
+        if let (rx_path_id @ 1.., Some(multipath)) = (self.rx_path_id, self.multipath.as_mut()) {
+            multipath.on_processed_packet(
+                rx_path_id,
+                &processed_packet,
+                path_event!(path, path_id),
+                publisher,
+            );
+            return Ok(());
+        }
+
         self.ack_manager.on_processed_packet(
             &processed_packet,
             path_event!(path, path_id),
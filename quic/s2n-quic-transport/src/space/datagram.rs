@@ -14,6 +14,7 @@ use s2n_codec::EncoderValue;
 use s2n_quic_core::{
     datagram::{Endpoint, ReceiveContext, Receiver, Sender, WriteError},
     frame::{self, datagram::DatagramRef},
+    packet::number::{PacketNumber, PacketNumberRange},
     query,
     varint::VarInt,
 };
@@ -68,6 +69,18 @@ impl<Config: endpoint::Config> Manager<Config> {
         self.receiver.on_datagram(&context, datagram.data);
     }
 
+    /// Notifies the sender that packets were acknowledged by the peer
+    #[inline]
+    pub fn on_packet_ack(&mut self, packet_number_range: &PacketNumberRange) {
+        self.sender.on_packet_ack(packet_number_range);
+    }
+
+    /// Notifies the sender that packets were declared lost
+    #[inline]
+    pub fn on_packet_loss(&mut self, packet_number_range: &PacketNumberRange) {
+        self.sender.on_packet_loss(packet_number_range);
+    }
+
     pub fn datagram_mut(&mut self, query: &mut dyn query::QueryMut) -> Poll<()> {
         // Try to execute the query on the sender side. If that fails, try the receiver side.
         match query.execute_mut(&mut self.sender) {
@@ -150,4 +163,9 @@ impl<C: WriteContext> s2n_quic_core::datagram::Packet for Packet<'_, C> {
     fn datagrams_prioritized(&self) -> bool {
         self.datagrams_prioritized
     }
+
+    /// Returns the number of the packet the datagrams are written to
+    fn packet_number(&self) -> PacketNumber {
+        self.context.packet_number()
+    }
 }
@@ -29,6 +29,10 @@ use s2n_quic_core::{
 };
 use smallvec::SmallVec;
 
+type SpaceKey<Config> =
+    <<<Config as endpoint::Config>::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey;
+type SpaceHeaderKey<Config> = <<<Config as endpoint::Config>::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialHeaderKey;
+
 pub struct InitialSpace<Config: endpoint::Config> {
     pub ack_manager: AckManager,
     //= https://www.rfc-editor.org/rfc/rfc9001#section-4
@@ -37,6 +41,11 @@ pub struct InitialSpace<Config: endpoint::Config> {
     pub key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey,
     pub header_key:
         <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialHeaderKey,
+    /// The keys for the client's original version, retained by a server that switched to a
+    /// compatible version until the client starts using the negotiated version
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc9368#section-2.3>
+    original_version_keys: Option<(u32, SpaceKey<Config>, SpaceHeaderKey<Config>)>,
     //= https://www.rfc-editor.org/rfc/rfc9001#section-4.9
     //# If packets from a lower encryption level contain
     //# CRYPTO frames, frames that retransmit that data MUST be sent at the
@@ -74,6 +83,7 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
             ack_manager,
             key,
             header_key,
+            original_version_keys: None,
             crypto_stream: CryptoStream::new(),
             tx_packet_numbers: TxPacketNumbers::new(PacketNumberSpace::Initial, now),
             received_hello_message: false,
@@ -103,6 +113,7 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
         //# a change to the keys used to protect the Initial packet.
         let (initial_key, initial_header_key) =
                             <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey::new_client(
+                                publisher.quic_version(),
                                 retry_source_connection_id.as_bytes(),
                             );
 
@@ -122,6 +133,39 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
             .on_retry_packet(path, path_id, publisher);
     }
 
+    /// Called by the server when switching the connection to a compatible version
+    ///
+    /// The keys for the `original_version` are retained for processing Initial packets the
+    /// client sends before it learns about the negotiated version.
+    pub fn on_compatible_version(
+        &mut self,
+        original_version: u32,
+        key: SpaceKey<Config>,
+        header_key: SpaceHeaderKey<Config>,
+    ) {
+        debug_assert!(Config::ENDPOINT_TYPE.is_server());
+
+        let key = core::mem::replace(&mut self.key, key);
+        let header_key = core::mem::replace(&mut self.header_key, header_key);
+        self.original_version_keys = Some((original_version, key, header_key));
+    }
+
+    /// Returns true if Initial packets with the given `version` are still accepted after
+    /// switching to a compatible version
+    #[inline]
+    pub fn accepts_original_version(&self, version: u32) -> bool {
+        matches!(&self.original_version_keys, Some((original_version, ..)) if *original_version == version)
+    }
+
+    /// Called by the server when an Initial packet with the negotiated version is processed
+    ///
+    /// The client has switched to the negotiated version so the keys for the original
+    /// version are no longer needed.
+    #[inline]
+    pub fn on_negotiated_version_packet(&mut self) {
+        self.original_version_keys = None;
+    }
+
     /// Returns true if the packet number has already been processed
     pub fn is_duplicate<Pub: event::ConnectionPublisher>(
         &self,
@@ -392,9 +436,16 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
         path: &path::Path<Config>,
         publisher: &mut Pub,
     ) -> Result<CleartextInitial<'a>, ProcessingError> {
+        let (key, header_key) = match &self.original_version_keys {
+            Some((original_version, key, header_key)) if *original_version == protected.version => {
+                (key, header_key)
+            }
+            _ => (&self.key, &self.header_key),
+        };
+
         let packet_number_decoder = self.packet_number_decoder();
         let packet = protected
-            .unprotect(&self.header_key, packet_number_decoder)
+            .unprotect(header_key, packet_number_decoder)
             .map_err(|err| {
                 publisher.on_packet_dropped(event::builder::PacketDropped {
                     reason: event::builder::PacketDropReason::UnprotectFailed {
@@ -411,7 +462,7 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
 
         let packet_header =
             event::builder::PacketHeader::new(packet.packet_number, publisher.quic_version());
-        let decrypted = packet.decrypt(&self.key).map_err(|err| {
+        let decrypted = packet.decrypt(key).map_err(|err| {
             publisher.on_packet_dropped(event::builder::PacketDropped {
                 reason: event::builder::PacketDropReason::DecryptionFailed {
                     packet_header,
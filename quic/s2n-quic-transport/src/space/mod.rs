@@ -21,13 +21,17 @@ use s2n_codec::DecoderBufferMut;
 use s2n_quic_core::{
     application::ServerName,
     connection::{limits::Limits, InitialId, PeerId},
-    crypto::{tls, tls::Session, CryptoSuite, Key},
+    crypto::{
+        tls::{self, EarlyDataStatus, Session},
+        CryptoSuite, Key,
+    },
     event::{self, IntoEvent},
     frame::{
         ack::AckRanges, crypto::CryptoRef, datagram::DatagramRef, stream::StreamRef, Ack,
-        ConnectionClose, DataBlocked, DcStatelessResetTokens, HandshakeDone, MaxData,
-        MaxStreamData, MaxStreams, NewConnectionId, NewToken, PathChallenge, PathResponse,
-        ResetStream, RetireConnectionId, StopSending, StreamDataBlocked, StreamsBlocked,
+        AckFrequency, ConnectionClose, DataBlocked, DcStatelessResetTokens, HandshakeDone,
+        ImmediateAck, MaxData, MaxStreamData, MaxStreams, NewConnectionId, NewToken, PathAbandon,
+        PathAck, PathChallenge, PathResponse, ResetStream, ResetStreamAt, RetireConnectionId,
+        StopSending, StreamDataBlocked, StreamsBlocked,
     },
     inet::DatagramInfo,
     packet::number::{PacketNumber, PacketNumberSpace},
@@ -43,16 +47,27 @@ mod handshake;
 mod handshake_status;
 mod initial;
 mod keep_alive;
+mod multipath;
 mod session_context;
 mod tx_packet_numbers;
+mod version_negotiation;
+mod zero_rtt;
 
 pub(crate) use application::ApplicationSpace;
 pub(crate) use crypto_stream::CryptoStream;
 pub(crate) use handshake::HandshakeSpace;
 pub(crate) use handshake_status::HandshakeStatus;
 pub(crate) use initial::InitialSpace;
+pub(crate) use multipath::{ReceiveSpaces, TransmitSpaces};
 pub(crate) use session_context::SessionContext;
 pub(crate) use tx_packet_numbers::TxPacketNumbers;
+pub(crate) use version_negotiation::VersionNegotiation;
+pub(crate) use zero_rtt::ZeroRttSpace;
+
+type ZeroRttCrypto<Config> = (
+    <<<Config as endpoint::Config>::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey,
+    <<<Config as endpoint::Config>::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttHeaderKey,
+);
 
 struct SessionInfo<Config: endpoint::Config> {
     session: <Config::TLSEndpoint as tls::Endpoint>::Session,
@@ -62,12 +77,14 @@ struct SessionInfo<Config: endpoint::Config> {
 pub struct PacketSpaceManager<Config: endpoint::Config> {
     session_info: Option<SessionInfo<Config>>,
     retry_cid: Option<Box<PeerId>>,
+    version_negotiation: VersionNegotiation,
     initial: Option<Box<InitialSpace<Config>>>,
     handshake: Option<Box<HandshakeSpace<Config>>>,
     pub tls_context: Option<Box<dyn Any + Send>>,
     application: Option<Box<ApplicationSpace<Config>>>,
-    zero_rtt_crypto:
-        Option<Box<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey>>,
+    /// 0-RTT keys received by the server before the application space is created
+    zero_rtt_crypto: Option<Box<ZeroRttCrypto<Config>>>,
+    early_data_status: EarlyDataStatus,
     handshake_status: HandshakeStatus,
     /// Server Name Indication
     pub server_name: Option<ServerName>,
@@ -112,6 +129,7 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         session: <Config::TLSEndpoint as tls::Endpoint>::Session,
         initial_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey,
         header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialHeaderKey,
+        version_negotiation: VersionNegotiation,
         now: Timestamp,
         publisher: &mut Pub,
     ) -> Self {
@@ -128,6 +146,7 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
             }),
             tls_context: None,
             retry_cid: None,
+            version_negotiation,
             initial: Some(Box::new(InitialSpace::new(
                 initial_key,
                 header_key,
@@ -137,6 +156,7 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
             handshake: None,
             application: None,
             zero_rtt_crypto: None,
+            early_data_status: EarlyDataStatus::default(),
             handshake_status: HandshakeStatus::default(),
             server_name: None,
             application_protocol: Bytes::new(),
@@ -149,11 +169,9 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
 
     packet_space_api!(ApplicationSpace<Config>, application, application_mut);
 
-    #[allow(dead_code)] // 0RTT hasn't been started yet
-    pub fn zero_rtt_crypto(
-        &self,
-    ) -> Option<&<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey> {
-        self.zero_rtt_crypto.as_ref().map(Box::as_ref)
+    /// Returns the status of early data on the connection
+    pub fn early_data_status(&self) -> EarlyDataStatus {
+        self.early_data_status
     }
 
     /// Discard the initial packet space
@@ -239,6 +257,9 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
 
     pub fn discard_zero_rtt_crypto(&mut self) {
         self.zero_rtt_crypto = None;
+        if let Some((space, _handshake_status)) = self.application_mut() {
+            space.discard_zero_rtt_keys();
+        }
     }
 
     pub fn poll_crypto<Pub: event::ConnectionPublisher>(
@@ -258,10 +279,12 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
                 now,
                 initial_cid: &session_info.initial_cid,
                 retry_cid: self.retry_cid.as_deref(),
+                version_negotiation: &mut self.version_negotiation,
                 initial: &mut self.initial,
                 handshake: &mut self.handshake,
                 application: &mut self.application,
                 zero_rtt_crypto: &mut self.zero_rtt_crypto,
+                early_data_status: &mut self.early_data_status,
                 tls_context: &mut self.tls_context,
                 path_manager,
                 handshake_status: &mut self.handshake_status,
@@ -308,11 +331,13 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
                 now,
                 initial_cid: &session_info.initial_cid,
                 retry_cid: self.retry_cid.as_deref(),
+                version_negotiation: &mut self.version_negotiation,
                 initial: &mut self.initial,
                 handshake: &mut self.handshake,
                 tls_context: &mut self.tls_context,
                 application: &mut self.application,
                 zero_rtt_crypto: &mut self.zero_rtt_crypto,
+                early_data_status: &mut self.early_data_status,
                 path_manager,
                 handshake_status: &mut self.handshake_status,
                 local_id_registry,
@@ -594,12 +619,22 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
 
     pub fn on_retry_packet(&mut self, retry_source_connection_id: PeerId) {
         debug_assert!(Config::ENDPOINT_TYPE.is_client());
+        self.version_negotiation
+            .on_retry_packet(&retry_source_connection_id);
         self.retry_cid = Some(Box::new(retry_source_connection_id));
     }
 
     pub fn retry_cid(&self) -> Option<&PeerId> {
         self.retry_cid.as_deref()
     }
+
+    pub fn version_negotiation(&self) -> &VersionNegotiation {
+        &self.version_negotiation
+    }
+
+    pub fn version_negotiation_mut(&mut self) -> &mut VersionNegotiation {
+        &mut self.version_negotiation
+    }
 }
 
 impl<Config: endpoint::Config> timer::Provider for PacketSpaceManager<Config> {
@@ -807,6 +842,41 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
             .with_frame_type(frame.tag()))
     }
 
+    fn handle_ack_frequency_frame(&mut self, frame: AckFrequency) -> Result<(), transport::Error> {
+        Err(transport::Error::PROTOCOL_VIOLATION
+            .with_reason(Self::INVALID_FRAME_ERROR)
+            .with_frame_type(frame.tag()))
+    }
+
+    fn handle_path_ack_frame<A: AckRanges, Pub: event::ConnectionPublisher>(
+        &mut self,
+        frame: PathAck<A>,
+        _timestamp: Timestamp,
+        _path_id: path::Id,
+        _path_manager: &mut path::Manager<Config>,
+        _packet_number: PacketNumber,
+        _handshake_status: &mut HandshakeStatus,
+        _local_id_registry: &mut connection::LocalIdRegistry,
+        _random_generator: &mut Config::RandomGenerator,
+        _publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        Err(transport::Error::PROTOCOL_VIOLATION
+            .with_reason(Self::INVALID_FRAME_ERROR)
+            .with_frame_type(frame.tag()))
+    }
+
+    fn handle_path_abandon_frame<Pub: event::ConnectionPublisher>(
+        &mut self,
+        frame: PathAbandon,
+        _path_manager: &mut path::Manager<Config>,
+        _local_id_registry: &connection::LocalIdRegistry,
+        _publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        Err(transport::Error::PROTOCOL_VIOLATION
+            .with_reason(Self::INVALID_FRAME_ERROR)
+            .with_frame_type(frame.tag()))
+    }
+
     default_frame_handler!(handle_data_blocked_frame, DataBlocked);
     default_frame_handler!(handle_max_data_frame, MaxData);
     default_frame_handler!(handle_max_stream_data_frame, MaxStreamData);
@@ -816,6 +886,8 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
     default_frame_handler!(handle_stream_data_blocked_frame, StreamDataBlocked);
     default_frame_handler!(handle_streams_blocked_frame, StreamsBlocked);
     default_frame_handler!(handle_new_token_frame, NewToken);
+    default_frame_handler!(handle_immediate_ack_frame, ImmediateAck);
+    default_frame_handler!(handle_reset_stream_at_frame, ResetStreamAt);
 
     fn on_processed_packet<Pub: event::ConnectionPublisher>(
         &mut self,
@@ -1071,6 +1143,44 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
                     self.handle_dc_stateless_reset_tokens_frame(frame, publisher)
                         .map_err(on_error)?;
                 }
+                Frame::AckFrequency(frame) => {
+                    let on_error = on_frame_processed!(frame);
+                    self.handle_ack_frequency_frame(frame).map_err(on_error)?;
+                }
+                Frame::ImmediateAck(frame) => {
+                    let on_error = on_frame_processed!(frame);
+                    self.handle_immediate_ack_frame(frame).map_err(on_error)?;
+                    processed_packet.immediate_ack_requested = true;
+                }
+                Frame::ResetStreamAt(frame) => {
+                    let on_error = on_frame_processed!(frame);
+                    self.handle_reset_stream_at_frame(frame).map_err(on_error)?;
+                }
+                Frame::PathAck(frame) => {
+                    let on_error = on_frame_processed!(frame);
+                    self.handle_path_ack_frame(
+                        frame,
+                        datagram.timestamp,
+                        path_id,
+                        path_manager,
+                        packet_number,
+                        handshake_status,
+                        local_id_registry,
+                        random_generator,
+                        publisher,
+                    )
+                    .map_err(on_error)?;
+                }
+                Frame::PathAbandon(frame) => {
+                    let on_error = on_frame_processed!(frame);
+                    self.handle_path_abandon_frame(
+                        frame,
+                        path_manager,
+                        local_id_registry,
+                        publisher,
+                    )
+                    .map_err(on_error)?;
+                }
             }
 
             payload = remaining;
@@ -0,0 +1,602 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-path packet number spaces of the multipath extension
+//!
+//! Packets received on the initial path (path id 0) are tracked by the application space
+//! itself. Every other path id gets its own set of processed packet numbers and its own
+//! [`AckManager`], which acknowledges packets with PATH_ACK frames.
+//!
+//! Likewise, packets sent on the initial path use the packet numbers of the application space
+//! while every other path numbers its packets starting at 0.
+//! See <https://datatracker.ietf.org/doc/draft-ietf-quic-multipath/>
+
+use crate::{
+    ack::AckManager, contexts::WriteContext, processed_packet::ProcessedPacket, transmission,
+};
+use alloc::collections::VecDeque;
+use s2n_quic_core::{
+    ack, event,
+    frame::{ack::AckRanges, Ack},
+    packet::number::{
+        PacketNumber, PacketNumberRange, PacketNumberSpace, SlidingWindow, SlidingWindowError,
+    },
+    time::{timer, Timestamp},
+    transport,
+    varint::VarInt,
+};
+use smallvec::SmallVec;
+
+#[derive(Debug)]
+pub struct ReceiveSpaces {
+    ack_settings: ack::Settings,
+    spaces: SmallVec<[ReceiveSpace; 4]>,
+}
+
+#[derive(Debug)]
+struct ReceiveSpace {
+    path_id: u32,
+    ack_manager: AckManager,
+    processed_packet_numbers: SlidingWindow,
+    /// Set when a PATH_ACK frame was written to the packet currently being assembled
+    ack_transmitted: bool,
+}
+
+impl ReceiveSpace {
+    fn new(path_id: u32, ack_settings: ack::Settings) -> Self {
+        Self {
+            path_id,
+            ack_manager: AckManager::for_path(VarInt::from_u32(path_id), ack_settings),
+            processed_packet_numbers: SlidingWindow::default(),
+            ack_transmitted: false,
+        }
+    }
+}
+
+impl ReceiveSpaces {
+    pub fn new(ack_settings: ack::Settings) -> Self {
+        Self {
+            ack_settings,
+            spaces: SmallVec::new(),
+        }
+    }
+
+    #[inline]
+    fn get(&self, path_id: u32) -> Option<&ReceiveSpace> {
+        self.spaces.iter().find(|space| space.path_id == path_id)
+    }
+
+    #[inline]
+    fn get_or_insert(&mut self, path_id: u32) -> &mut ReceiveSpace {
+        debug_assert_ne!(
+            path_id, 0,
+            "the initial path is tracked by the application space"
+        );
+
+        let index = match self
+            .spaces
+            .iter()
+            .position(|space| space.path_id == path_id)
+        {
+            Some(index) => index,
+            None => {
+                self.spaces
+                    .push(ReceiveSpace::new(path_id, self.ack_settings));
+                self.spaces.len() - 1
+            }
+        };
+
+        &mut self.spaces[index]
+    }
+
+    /// Returns the largest packet number received on the path that has been acknowledged
+    ///
+    /// This is used to decode the packet numbers of packets received on the path.
+    #[inline]
+    pub fn largest_received_packet_number_acked(&self, path_id: u32) -> PacketNumber {
+        self.get(path_id).map_or_else(
+            || PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(0)),
+            |space| space.ack_manager.largest_received_packet_number_acked(),
+        )
+    }
+
+    /// Checks if the packet number has already been processed on the path
+    #[inline]
+    pub fn check(
+        &self,
+        path_id: u32,
+        packet_number: PacketNumber,
+    ) -> Result<(), SlidingWindowError> {
+        self.get(path_id).map_or(Ok(()), |space| {
+            space.processed_packet_numbers.check(packet_number)
+        })
+    }
+
+    /// Called after a packet received on the path has been processed
+    pub fn on_processed_packet<Pub: event::ConnectionPublisher>(
+        &mut self,
+        path_id: u32,
+        processed_packet: &ProcessedPacket,
+        path: event::builder::Path,
+        publisher: &mut Pub,
+    ) {
+        let space = self.get_or_insert(path_id);
+        space
+            .ack_manager
+            .on_processed_packet(processed_packet, path, publisher);
+        space
+            .processed_packet_numbers
+            .insert(processed_packet.packet_number)
+            .expect("packet number was already checked");
+    }
+
+    /// Writes PATH_ACK frames for any paths that need to acknowledge packets
+    pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) {
+        for space in self.spaces.iter_mut() {
+            space.ack_transmitted = space.ack_manager.on_transmit(context);
+        }
+    }
+
+    /// Called after an outgoing packet is assembled
+    pub fn on_transmit_complete<W: WriteContext>(&mut self, context: &mut W) {
+        for space in self.spaces.iter_mut() {
+            if core::mem::take(&mut space.ack_transmitted) {
+                space.ack_manager.on_transmit_complete(context);
+            }
+        }
+    }
+
+    /// Called when a set of packets was acknowledged
+    pub fn on_packet_ack<A: ack::Set>(&mut self, timestamp: Timestamp, ack_set: &A) {
+        for space in self.spaces.iter_mut() {
+            space.ack_manager.on_packet_ack(timestamp, ack_set);
+        }
+    }
+
+    /// Called when a set of packets was reported lost
+    pub fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) {
+        for space in self.spaces.iter_mut() {
+            space.ack_manager.on_packet_loss(ack_set);
+        }
+    }
+
+    /// Called when the connection timer expired
+    pub fn on_timeout(&mut self, timestamp: Timestamp) {
+        for space in self.spaces.iter_mut() {
+            space.ack_manager.on_timeout(timestamp);
+        }
+    }
+}
+
+impl timer::Provider for ReceiveSpaces {
+    #[inline]
+    fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
+        for space in self.spaces.iter() {
+            space.ack_manager.timers(query)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl transmission::interest::Provider for ReceiveSpaces {
+    #[inline]
+    fn transmission_interest<Q: transmission::interest::Query>(
+        &self,
+        query: &mut Q,
+    ) -> transmission::interest::Result {
+        for space in self.spaces.iter() {
+            space.ack_manager.transmission_interest(query)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Send state for the packet number spaces of the paths other than the initial path
+///
+/// The recovery manager tracks every packet by the packet number it was allocated in the
+/// application space. Packets sent on other paths are mapped to that packet number until they
+/// are acknowledged or declared lost so PATH_ACK frames can be processed by the same recovery
+/// manager.
+#[derive(Debug, Default)]
+pub struct TransmitSpaces {
+    /// The largest packet number acknowledged on the initial path
+    initial_largest_acked: Option<PacketNumber>,
+    spaces: SmallVec<[TransmitSpace; 4]>,
+}
+
+#[derive(Debug)]
+struct TransmitSpace {
+    path_id: u32,
+    next: PacketNumber,
+    largest_acked: PacketNumber,
+    /// Packets sent on the path, in packet number order
+    sent_packets: VecDeque<SentPacket>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct SentPacket {
+    /// The packet number the packet was sent with on the path
+    packet_number: PacketNumber,
+    /// The application space packet number the recovery manager tracks the packet with
+    local_packet_number: PacketNumber,
+    /// Set until the packet is acknowledged or declared lost
+    is_pending: bool,
+}
+
+impl TransmitSpace {
+    fn new(path_id: u32) -> Self {
+        let initial_packet_number =
+            PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(0));
+        Self {
+            path_id,
+            next: initial_packet_number,
+            largest_acked: initial_packet_number,
+            sent_packets: VecDeque::new(),
+        }
+    }
+
+    /// Returns the index of the first sent packet with a local packet number of at least `start`
+    #[inline]
+    fn local_position(&self, start: PacketNumber) -> usize {
+        self.sent_packets
+            .partition_point(|sent| sent.local_packet_number < start)
+    }
+}
+
+impl TransmitSpaces {
+    #[inline]
+    fn get(&self, path_id: u32) -> Option<&TransmitSpace> {
+        self.spaces.iter().find(|space| space.path_id == path_id)
+    }
+
+    #[inline]
+    fn get_or_insert(&mut self, path_id: u32) -> &mut TransmitSpace {
+        debug_assert_ne!(
+            path_id, 0,
+            "the initial path is tracked by the application space"
+        );
+
+        let index = match self
+            .spaces
+            .iter()
+            .position(|space| space.path_id == path_id)
+        {
+            Some(index) => index,
+            None => {
+                self.spaces.push(TransmitSpace::new(path_id));
+                self.spaces.len() - 1
+            }
+        };
+
+        &mut self.spaces[index]
+    }
+
+    /// Returns the packet number of the next packet sent on the path
+    #[inline]
+    pub fn next_packet_number(&self, path_id: u32) -> PacketNumber {
+        self.get(path_id).map_or_else(
+            || PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(0)),
+            |space| space.next,
+        )
+    }
+
+    /// Returns the largest packet number sent on the path that was acknowledged by the peer
+    ///
+    /// This is used to encode the packet numbers of packets sent on the path.
+    #[inline]
+    pub fn largest_sent_packet_number_acked(&self, path_id: u32) -> PacketNumber {
+        if path_id == 0 {
+            return self.initial_largest_acked.unwrap_or_else(|| {
+                PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(0))
+            });
+        }
+
+        self.get(path_id).map_or_else(
+            || PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(0)),
+            |space| space.largest_acked,
+        )
+    }
+
+    /// Called after a packet was sent on the path without being tracked by the recovery manager
+    pub fn on_transmit(&mut self, path_id: u32, packet_number: PacketNumber) {
+        let space = self.get_or_insert(path_id);
+        debug_assert_eq!(space.next, packet_number);
+        space.next = packet_number.next().expect("packet number overflowed");
+    }
+
+    /// Called after a packet was sent on the path
+    ///
+    /// `local_packet_number` is the application space packet number the recovery manager
+    /// tracks the packet with.
+    pub fn on_packet_sent(
+        &mut self,
+        path_id: u32,
+        packet_number: PacketNumber,
+        local_packet_number: PacketNumber,
+    ) {
+        self.on_transmit(path_id, packet_number);
+        self.get_or_insert(path_id)
+            .sent_packets
+            .push_back(SentPacket {
+                packet_number,
+                local_packet_number,
+                is_pending: true,
+            });
+    }
+
+    /// Validates an acknowledgement of packets sent on the initial path
+    ///
+    /// Packet numbers of the application space that were used for packets on other paths were
+    /// never sent on the initial path.
+    pub fn on_initial_path_ack<A: AckRanges>(
+        &mut self,
+        ack: &Ack<A>,
+    ) -> Result<(), transport::Error> {
+        let space = PacketNumberSpace::ApplicationData;
+
+        for range in ack.ack_ranges() {
+            let (start, end) = range.into_inner();
+            let start = space.new_packet_number(start);
+            let end = space.new_packet_number(end);
+
+            for path in self.spaces.iter() {
+                //= https://www.rfc-editor.org/rfc/rfc9000#section-13.1
+                //# An endpoint SHOULD treat receipt of an acknowledgment for a packet it
+                //# did not send as a connection error of type PROTOCOL_VIOLATION, if it
+                //# is able to detect the condition.
+                if path
+                    .sent_packets
+                    .range(path.local_position(start)..)
+                    .take_while(|sent| sent.local_packet_number <= end)
+                    .any(|sent| sent.is_pending)
+                {
+                    return Err(transport::Error::PROTOCOL_VIOLATION.with_reason(
+                        "received an ACK for a packet that was not sent on the path",
+                    ));
+                }
+            }
+        }
+
+        let largest = space.new_packet_number(ack.largest_acknowledged());
+        if self
+            .initial_largest_acked
+            .map_or(true, |acked| acked < largest)
+        {
+            self.initial_largest_acked = Some(largest);
+        }
+
+        Ok(())
+    }
+
+    /// Maps the ranges acknowledged by a PATH_ACK frame to the packet numbers tracked by the
+    /// recovery manager
+    ///
+    /// Only packets that were sent on the path and are still pending are pushed to `ranges`.
+    /// Returns the tracked packet number of the largest acknowledged packet, or `None` if the
+    /// frame does not acknowledge any pending packets.
+    pub fn on_path_ack<A: AckRanges>(
+        &mut self,
+        path_id: u32,
+        ack: &Ack<A>,
+        ranges: &mut SmallVec<[PacketNumberRange; 4]>,
+    ) -> Result<Option<PacketNumber>, transport::Error> {
+        let space = PacketNumberSpace::ApplicationData;
+        let largest = space.new_packet_number(ack.largest_acknowledged());
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-13.1
+        //# An endpoint SHOULD treat receipt of an acknowledgment for a packet it
+        //# did not send as a connection error of type PROTOCOL_VIOLATION, if it
+        //# is able to detect the condition.
+        let path = match self
+            .spaces
+            .iter_mut()
+            .find(|space| space.path_id == path_id)
+        {
+            Some(path) if largest < path.next => path,
+            _ => {
+                return Err(transport::Error::PROTOCOL_VIOLATION
+                    .with_reason("received an ACK for a packet that was not sent on the path"))
+            }
+        };
+
+        if path.largest_acked < largest {
+            path.largest_acked = largest;
+        }
+
+        for range in ack.ack_ranges() {
+            let (start, end) = range.into_inner();
+            let start = space.new_packet_number(start);
+            let end = space.new_packet_number(end);
+            let index = path
+                .sent_packets
+                .partition_point(|sent| sent.packet_number < start);
+
+            // packets sent on other paths may interleave with the ones sent on this path so a
+            // contiguous range on the path can map to several tracked ranges
+            let mut current: Option<(PacketNumber, PacketNumber)> = None;
+            for sent in path
+                .sent_packets
+                .range(index..)
+                .take_while(|sent| sent.packet_number <= end)
+                .filter(|sent| sent.is_pending)
+            {
+                let packet_number = sent.local_packet_number;
+                match current.as_mut() {
+                    Some((_, last)) if last.next() == Some(packet_number) => *last = packet_number,
+                    _ => {
+                        if let Some((start, end)) = current.replace((packet_number, packet_number))
+                        {
+                            ranges.push(PacketNumberRange::new(start, end));
+                        }
+                    }
+                }
+            }
+
+            if let Some((start, end)) = current {
+                ranges.push(PacketNumberRange::new(start, end));
+            }
+        }
+
+        let largest_pending = path
+            .sent_packets
+            .binary_search_by_key(&largest, |sent| sent.packet_number)
+            .ok()
+            .map(|index| path.sent_packets[index])
+            .filter(|sent| sent.is_pending)
+            .map(|sent| sent.local_packet_number);
+
+        Ok(largest_pending.or_else(|| ranges.iter().map(|range| range.end()).max()))
+    }
+
+    /// Called when a set of tracked packets was acknowledged
+    pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
+        self.on_packet_removed(ack_set);
+    }
+
+    /// Called when a set of tracked packets was reported lost
+    pub fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) {
+        self.on_packet_removed(ack_set);
+    }
+
+    fn on_packet_removed<A: ack::Set>(&mut self, ack_set: &A) {
+        let end = ack_set.largest();
+
+        for path in self.spaces.iter_mut() {
+            let index = path.local_position(ack_set.smallest());
+            for sent in path
+                .sent_packets
+                .range_mut(index..)
+                .take_while(|sent| sent.local_packet_number <= end)
+            {
+                if ack_set.contains(sent.local_packet_number) {
+                    sent.is_pending = false;
+                }
+            }
+
+            // packets are usually acknowledged in order so they can be popped from the front
+            while path
+                .sent_packets
+                .front()
+                .is_some_and(|sent| !sent.is_pending)
+            {
+                path.sent_packets.pop_front();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn receive_space_test() {
+        let mut spaces = ReceiveSpaces::new(ack::Settings::default());
+        let packet_number =
+            PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(7));
+
+        // unknown paths have not processed any packets
+        assert!(spaces.check(1, packet_number).is_ok());
+        assert_eq!(spaces.largest_received_packet_number_acked(1).as_u64(), 0);
+
+        let space = spaces.get_or_insert(1);
+        space
+            .processed_packet_numbers
+            .insert(packet_number)
+            .unwrap();
+
+        assert!(spaces.check(1, packet_number).is_err());
+        // each path has its own packet number space
+        assert!(spaces.check(2, packet_number).is_ok());
+
+        spaces.get_or_insert(1);
+        assert_eq!(spaces.spaces.len(), 1);
+    }
+
+    fn pn(value: u8) -> PacketNumber {
+        PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(value))
+    }
+
+    fn ack(packet_numbers: &[u8]) -> ack::Ranges {
+        let mut ranges = ack::Ranges::new(packet_numbers.len());
+        for &packet_number in packet_numbers {
+            ranges.insert_packet_number(pn(packet_number)).unwrap();
+        }
+        ranges
+    }
+
+    #[test]
+    fn transmit_space_test() {
+        let mut spaces = TransmitSpaces::default();
+
+        // paths 1 and 2 interleave with application space packet numbers 0..6
+        for (local, path_id) in [(0, 1), (1, 2), (2, 1), (3, 1), (4, 2), (5, 1)] {
+            let packet_number = spaces.next_packet_number(path_id);
+            spaces.on_packet_sent(path_id, packet_number, pn(local));
+        }
+
+        // each path numbers its packets starting at 0
+        assert_eq!(spaces.next_packet_number(1), pn(4));
+        assert_eq!(spaces.next_packet_number(2), pn(2));
+        assert_eq!(spaces.next_packet_number(3), pn(0));
+
+        // packets that were not sent on the path can't be acknowledged
+        let ranges = ack(&[2]);
+        let frame = Ack {
+            ack_delay: VarInt::from_u8(0),
+            ack_ranges: &ranges,
+            ecn_counts: None,
+        };
+        let mut mapped = SmallVec::new();
+        assert!(spaces.on_path_ack(2, &frame, &mut mapped).is_err());
+        assert!(spaces.on_path_ack(3, &frame, &mut mapped).is_err());
+
+        // packets sent on another path can't be acknowledged on the initial path
+        let local = ack(&[4]);
+        let initial_frame = Ack {
+            ack_delay: VarInt::from_u8(0),
+            ack_ranges: &local,
+            ecn_counts: None,
+        };
+        assert!(spaces.on_initial_path_ack(&initial_frame).is_err());
+
+        // path 1 packets 0..=2 were tracked as 0, 2 and 3
+        let ranges = ack(&[0, 1, 2]);
+        let frame = Ack {
+            ack_delay: VarInt::from_u8(0),
+            ack_ranges: &ranges,
+            ecn_counts: None,
+        };
+        assert_eq!(spaces.on_path_ack(1, &frame, &mut mapped), Ok(Some(pn(3))));
+        // the path's contiguous range maps to the packets it was tracked with
+        assert!(
+            mapped[..]
+                == [
+                    PacketNumberRange::new(pn(0), pn(0)),
+                    PacketNumberRange::new(pn(2), pn(3))
+                ]
+        );
+        assert_eq!(spaces.largest_sent_packet_number_acked(1), pn(2));
+        assert_eq!(spaces.largest_sent_packet_number_acked(2), pn(0));
+
+        for range in mapped.drain(..) {
+            spaces.on_packet_ack(&range);
+        }
+
+        // acknowledged packets are no longer mapped
+        assert_eq!(spaces.on_path_ack(1, &frame, &mut mapped), Ok(None));
+        assert!(mapped.is_empty());
+        assert_eq!(spaces.get(1).unwrap().sent_packets.len(), 1);
+
+        // lost packets are no longer tracked either
+        spaces.on_packet_loss(&PacketNumberRange::new(pn(1), pn(5)));
+        assert!(spaces
+            .spaces
+            .iter()
+            .all(|space| space.sent_packets.is_empty()));
+        assert!(spaces.on_initial_path_ack(&initial_frame).is_ok());
+        assert_eq!(spaces.largest_sent_packet_number_acked(0), pn(4));
+    }
+}
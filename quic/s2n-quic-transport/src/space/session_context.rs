@@ -7,12 +7,12 @@ use crate::{
     endpoint, path,
     space::{
         datagram, keep_alive::KeepAlive, ApplicationSpace, HandshakeSpace, HandshakeStatus,
-        InitialSpace,
+        InitialSpace, VersionNegotiation, ZeroRttCrypto,
     },
     stream,
 };
 use bytes::Bytes;
-use core::{any::Any, ops::Not, task::Waker};
+use core::{any::Any, ops::Not, task::Waker, time::Duration};
 use s2n_codec::{DecoderBuffer, DecoderValue};
 use s2n_quic_core::{
     ack,
@@ -23,7 +23,7 @@ use s2n_quic_core::{
     },
     crypto::{
         self,
-        tls::{self, ApplicationParameters, NamedGroup},
+        tls::{self, ApplicationParameters, EarlyDataStatus, NamedGroup},
         CryptoSuite, Key,
     },
     ct::ConstantTimeEq,
@@ -40,8 +40,9 @@ use s2n_quic_core::{
         self,
         parameters::{
             ActiveConnectionIdLimit, ClientTransportParameters, DatagramLimits,
-            DcSupportedVersions, InitialFlowControlLimits, InitialSourceConnectionId, MaxAckDelay,
-            ServerTransportParameters, TransportParameter as _,
+            DcSupportedVersions, InitialFlowControlLimits, InitialMaxPathId,
+            InitialSourceConnectionId, MaxAckDelay, MigrationSupport, MinAckDelay,
+            ResetStreamAtSupport, ServerTransportParameters, TransportParameter as _,
         },
         Error,
     },
@@ -51,13 +52,13 @@ pub struct SessionContext<'a, Config: endpoint::Config, Pub: event::ConnectionPu
     pub now: Timestamp,
     pub initial_cid: &'a InitialId,
     pub retry_cid: Option<&'a PeerId>,
+    pub version_negotiation: &'a mut VersionNegotiation,
     pub path_manager: &'a mut path::Manager<Config>,
     pub initial: &'a mut Option<Box<InitialSpace<Config>>>,
     pub handshake: &'a mut Option<Box<HandshakeSpace<Config>>>,
     pub application: &'a mut Option<Box<ApplicationSpace<Config>>>,
-    pub zero_rtt_crypto: &'a mut Option<
-        Box<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey>,
-    >,
+    pub zero_rtt_crypto: &'a mut Option<Box<ZeroRttCrypto<Config>>>,
+    pub early_data_status: &'a mut EarlyDataStatus,
     pub handshake_status: &'a mut HandshakeStatus,
     pub local_id_registry: &'a mut connection::LocalIdRegistry,
     pub limits: &'a mut Limits,
@@ -82,7 +83,10 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
             ActiveConnectionIdLimit,
             DatagramLimits,
             MaxAckDelay,
+            Option<MinAckDelay>,
             Option<dc::Version>,
+            ResetStreamAtSupport,
+            Option<InitialMaxPathId>,
         ),
         transport::Error,
     > {
@@ -149,6 +153,9 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
             (None, None) => {}
         }
 
+        self.version_negotiation
+            .validate_server_info(peer_parameters.version_information.as_ref())?;
+
         if let Some(peer_value) = peer_parameters.original_destination_connection_id {
             //= https://www.rfc-editor.org/rfc/rfc9000#section-7.3
             //# The values provided by a peer for these transport parameters MUST
@@ -192,10 +199,24 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
         // Load the peer's transport parameters into the connection's limits
         self.limits.load_peer(&peer_parameters);
 
+        if peer_parameters.migration_support == MigrationSupport::Disabled {
+            self.path_manager.on_active_migration_disabled();
+        }
+
+        if let Some(preferred_address) = peer_parameters.preferred_address.as_ref() {
+            self.path_manager
+                .on_preferred_address(preferred_address, self.publisher)?;
+        }
+
         let initial_flow_control_limits = peer_parameters.flow_control_limits();
         let active_connection_id_limit = peer_parameters.active_connection_id_limit;
         let datagram_limits = peer_parameters.datagram_limits();
 
+        let min_ack_delay = Self::validate_min_ack_delay(
+            peer_parameters.min_ack_delay,
+            peer_parameters.max_ack_delay,
+        )?;
+
         let dc_version = if Config::DcEndpoint::ENABLED {
             peer_parameters
                 .dc_supported_versions
@@ -213,10 +234,133 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
             active_connection_id_limit,
             datagram_limits,
             peer_parameters.max_ack_delay,
+            min_ack_delay,
             dc_version,
+            peer_parameters.reset_stream_at_support,
+            peer_parameters.initial_max_path_id,
+        ))
+    }
+
+    // This is called by the client when sending early data
+    //
+    // The parameters are the ones the server sent on the connection that issued the resumption
+    // ticket so they aren't validated against the current connection.
+    fn on_remembered_server_params(
+        &mut self,
+        decoder: DecoderBuffer,
+    ) -> Result<(InitialFlowControlLimits, DatagramLimits), transport::Error> {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+
+        let (peer_parameters, _remaining) =
+            ServerTransportParameters::decode(decoder).map_err(|_| {
+                transport::Error::TRANSPORT_PARAMETER_ERROR
+                    .with_reason("Invalid remembered transport parameters")
+            })?;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-7.4.1
+        //# A client MUST NOT use remembered values for the following parameters:
+        //# ack_delay_exponent, max_ack_delay, initial_source_connection_id,
+        //# original_destination_connection_id, preferred_address,
+        //# retry_source_connection_id, and stateless_reset_token.
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-7.4.1
+        //# A client that attempts to send 0-RTT data MUST remember all other
+        //# transport parameters used by the server that it is able to process.
+        self.limits.load_peer(&peer_parameters);
+
+        Ok((
+            peer_parameters.flow_control_limits(),
+            peer_parameters.datagram_limits(),
         ))
     }
 
+    /// Creates the application space once the connection can start sending application data
+    fn new_application_space(
+        &mut self,
+        peer_flow_control_limits: InitialFlowControlLimits,
+        datagram_limits: DatagramLimits,
+        dc_manager: crate::dc::Manager<Config>,
+    ) -> ApplicationSpace<Config> {
+        let stream_manager = <Config::StreamManager as stream::Manager>::new(
+            self.limits,
+            Config::ENDPOINT_TYPE,
+            self.limits.initial_flow_control_limits(),
+            peer_flow_control_limits,
+            self.path_manager.active_path().rtt_estimator.min_rtt(),
+        );
+
+        let mut ack_manager = AckManager::new(
+            PacketNumberSpace::ApplicationData,
+            self.limits.ack_settings(),
+        );
+
+        if let Some(min_ack_delay) = self.limits.min_ack_delay() {
+            ack_manager.enable_ack_frequency(min_ack_delay.into());
+        }
+
+        let keep_alive = KeepAlive::new(
+            self.limits.max_idle_timeout(),
+            self.limits.max_keep_alive_period(),
+        );
+
+        let conn_info =
+            ConnectionInfo::new(datagram_limits.max_datagram_payload, self.waker.clone());
+        let (datagram_sender, datagram_receiver) = self.datagram.create_connection(&conn_info);
+        let datagram_manager = datagram::Manager::new(
+            datagram_sender,
+            datagram_receiver,
+            datagram_limits.max_datagram_payload,
+        );
+
+        ApplicationSpace::new(
+            self.now,
+            stream_manager,
+            ack_manager,
+            keep_alive,
+            datagram_manager,
+            dc_manager,
+        )
+    }
+
+    fn new_dc_manager(
+        &mut self,
+        dc_version: Option<dc::Version>,
+        peer_flow_control_limits: &InitialFlowControlLimits,
+    ) -> crate::dc::Manager<Config> {
+        if let Some(dc_version) = dc_version {
+            let application_params = dc::ApplicationParams::new(
+                self.path_manager
+                    .active_path()
+                    .mtu_controller
+                    .max_datagram_size() as u16,
+                peer_flow_control_limits,
+                self.limits,
+            );
+            let remote_address = self.path_manager.active_path().remote_address().0;
+            let conn_info = dc::ConnectionInfo::new(
+                &remote_address,
+                dc_version,
+                application_params,
+                Config::ENDPOINT_TYPE.into_event(),
+            );
+            let dc_path = self.dc.new_path(&conn_info);
+
+            // &mut would be ideal but events currently need to be `Clone`, and we're OK with
+            // pushing interior mutability for now. dc is all unstable anyway.
+            self.publisher
+                .on_dc_path_created(DcPathCreated { path: &dc_path });
+
+            crate::dc::Manager::new(dc_path, dc_version, self.publisher)
+        } else {
+            if Config::DcEndpoint::ENABLED {
+                self.publisher.on_dc_state_changed(DcStateChanged {
+                    state: DcState::NoVersionNegotiated,
+                });
+            }
+            crate::dc::Manager::disabled()
+        }
+    }
+
     // This is called by the server
     fn on_client_params(
         &mut self,
@@ -227,7 +371,10 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
             ActiveConnectionIdLimit,
             DatagramLimits,
             MaxAckDelay,
+            Option<MinAckDelay>,
             Option<dc::Version>,
+            ResetStreamAtSupport,
+            Option<InitialMaxPathId>,
         ),
         transport::Error,
     > {
@@ -261,6 +408,10 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
                 .as_bytes(),
         )?;
 
+        if let Some(client_info) = peer_parameters.version_information.as_ref() {
+            self.version_negotiation.validate_client_info(client_info)?;
+        }
+
         // Load the peer's transport parameters into the connection's limits
         self.limits.load_peer(&peer_parameters);
 
@@ -268,6 +419,11 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
         let active_connection_id_limit = peer_parameters.active_connection_id_limit;
         let datagram_limits = peer_parameters.datagram_limits();
 
+        let min_ack_delay = Self::validate_min_ack_delay(
+            peer_parameters.min_ack_delay,
+            peer_parameters.max_ack_delay,
+        )?;
+
         let dc_version = if Config::DcEndpoint::ENABLED {
             dc::select_version(peer_parameters.dc_supported_versions)
         } else {
@@ -279,10 +435,36 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
             active_connection_id_limit,
             datagram_limits,
             peer_parameters.max_ack_delay,
+            min_ack_delay,
             dc_version,
+            peer_parameters.reset_stream_at_support,
+            peer_parameters.initial_max_path_id,
         ))
     }
 
+    // This is called by the server
+    //
+    // Switches the connection to a compatible version before any packets are sent in
+    // response to the client's first flight.
+    fn on_compatible_version(&mut self, version: u32) -> Result<(), transport::Error> {
+        debug_assert!(Config::ENDPOINT_TYPE.is_server());
+
+        // The server's Initial packets are protected with keys for the negotiated version
+        let (key, header_key) = self
+            .version_negotiation
+            .initial_keys(Config::ENDPOINT_TYPE, version);
+
+        let space = self.initial.as_mut().ok_or_else(|| {
+            transport::Error::INTERNAL_ERROR
+                .with_reason("initial space discarded before version negotiation")
+        })?;
+        space.on_compatible_version(self.version_negotiation.original_version(), key, header_key);
+
+        self.version_negotiation.on_version_negotiated(version);
+
+        Ok(())
+    }
+
     //= https://www.rfc-editor.org/rfc/rfc9000#section-7.3
     //# Each endpoint includes the value of the Source Connection ID field
     //# from the first Initial packet it sent in the
@@ -325,6 +507,24 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
 
         Ok(())
     }
+
+    // The min_ack_delay transport parameter can't be validated in isolation since it
+    // can't be larger than the max_ack_delay of the same endpoint.
+    //
+    // See https://datatracker.ietf.org/doc/draft-ietf-quic-ack-frequency/
+    fn validate_min_ack_delay(
+        min_ack_delay: Option<MinAckDelay>,
+        max_ack_delay: MaxAckDelay,
+    ) -> Result<Option<MinAckDelay>, transport::Error> {
+        if let Some(min_ack_delay) = min_ack_delay {
+            if min_ack_delay.as_duration() > max_ack_delay.as_duration() {
+                return Err(transport::Error::TRANSPORT_PARAMETER_ERROR
+                    .with_reason("min_ack_delay is greater than max_ack_delay"));
+            }
+        }
+
+        Ok(min_ack_delay)
+    }
 }
 
 impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
@@ -366,18 +566,53 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
     fn on_zero_rtt_keys(
         &mut self,
         key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey,
-        _header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttHeaderKey,
-        _application_parameters: tls::ApplicationParameters,
+        header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttHeaderKey,
+        application_parameters: tls::ApplicationParameters,
     ) -> Result<(), transport::Error> {
-        if self.zero_rtt_crypto.is_some() {
+        if self.zero_rtt_crypto.is_some()
+            || *self.early_data_status != EarlyDataStatus::NotAttempted
+        {
             return Err(transport::Error::INTERNAL_ERROR
                 .with_reason("zero rtt keys initialized more than once"));
         }
 
+        if self.application.is_some() {
+            return Err(transport::Error::INTERNAL_ERROR
+                .with_reason("zero rtt keys initialized after application keys"));
+        }
+
         let cipher_suite = key.cipher_suite().into_event();
 
-        // TODO: also store the header_key
-        *self.zero_rtt_crypto = Some(Box::new(key));
+        match Config::ENDPOINT_TYPE {
+            endpoint::Type::Client => {
+                // The client is resuming a session and can start sending application data with
+                // the transport parameters the server previously sent
+                let param_decoder = DecoderBuffer::new(application_parameters.transport_parameters);
+                let (peer_flow_control_limits, datagram_limits) =
+                    self.on_remembered_server_params(param_decoder)?;
+
+                //= https://www.rfc-editor.org/rfc/rfc9000#section-7.4.1
+                //# When sending frames in 0-RTT packets, a client MUST only use
+                //# remembered transport parameters; importantly, it MUST NOT use updated
+                //# values that it learns from the server's updated transport parameters
+                //# or from frames received in 1-RTT packets.
+                let mut space = self.new_application_space(
+                    peer_flow_control_limits,
+                    datagram_limits,
+                    // dc paths are only negotiated with the server's current parameters
+                    crate::dc::Manager::disabled(),
+                );
+                space.on_zero_rtt_keys(key, header_key);
+                *self.application = Some(Box::new(space));
+                *self.early_data_status = EarlyDataStatus::Pending;
+            }
+            endpoint::Type::Server => {
+                // The TLS session only derives 0-RTT keys if it accepted early data. The keys are
+                // held until the application space is created with the 1-RTT keys.
+                *self.zero_rtt_crypto = Some(Box::new((key, header_key)));
+                *self.early_data_status = EarlyDataStatus::Accepted;
+            }
+        }
 
         self.publisher.on_key_update(event::builder::KeyUpdate {
             key_type: event::builder::KeyType::ZeroRtt,
@@ -386,25 +621,43 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
         Ok(())
     }
 
+    fn on_zero_rtt_rejected(&mut self) -> Result<(), transport::Error> {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+
+        if *self.early_data_status != EarlyDataStatus::Pending {
+            return Err(transport::Error::INTERNAL_ERROR
+                .with_reason("zero rtt rejected without sending early data"));
+        }
+
+        *self.early_data_status = EarlyDataStatus::Rejected;
+
+        if let Some(space) = self.application.as_mut() {
+            space.on_zero_rtt_rejected(
+                self.path_manager,
+                self.handshake_status,
+                self.local_id_registry,
+                self.publisher,
+            );
+        }
+
+        Ok(())
+    }
+
     fn on_one_rtt_keys(
         &mut self,
         key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey,
         header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttHeaderKey,
         application_parameters: tls::ApplicationParameters,
     ) -> Result<(), transport::Error> {
-        if self.application.is_some() {
+        if self
+            .application
+            .as_ref()
+            .is_some_and(|space| space.has_one_rtt_keys())
+        {
             return Err(transport::Error::INTERNAL_ERROR
                 .with_reason("application keys initialized more than once"));
         }
 
-        if Config::ENDPOINT_TYPE.is_client() {
-            //= https://www.rfc-editor.org/rfc/rfc9001#section-4.9.3
-            //# Therefore, a client SHOULD discard 0-RTT keys as soon as it installs
-            //# 1-RTT keys as they have no use after that moment.
-
-            *self.zero_rtt_crypto = None;
-        }
-
         // Parse transport parameters
         let param_decoder = DecoderBuffer::new(application_parameters.transport_parameters);
         let (
@@ -412,7 +665,10 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
             active_connection_id_limit,
             datagram_limits,
             max_ack_delay,
+            min_ack_delay,
             dc_version,
+            reset_stream_at_support,
+            initial_max_path_id,
         ) = match Config::ENDPOINT_TYPE {
             endpoint::Type::Client => self.on_server_params(param_decoder)?,
             endpoint::Type::Server => self.on_client_params(param_decoder)?,
@@ -431,82 +687,77 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
         self.local_id_registry
             .set_active_connection_id_limit(active_connection_id_limit.as_u64());
 
-        let stream_manager = <Config::StreamManager as stream::Manager>::new(
-            self.limits,
-            Config::ENDPOINT_TYPE,
-            self.limits.initial_flow_control_limits(),
-            peer_flow_control_limits,
-            self.path_manager.active_path().rtt_estimator.min_rtt(),
-        );
-
-        let ack_manager = AckManager::new(
-            PacketNumberSpace::ApplicationData,
-            self.limits.ack_settings(),
-        );
+        let dc_manager = self.new_dc_manager(dc_version, &peer_flow_control_limits);
 
-        let keep_alive = KeepAlive::new(
-            self.limits.max_idle_timeout(),
-            self.limits.max_keep_alive_period(),
-        );
+        self.path_manager
+            .active_path_mut()
+            .rtt_estimator
+            .on_max_ack_delay(max_ack_delay);
 
-        let conn_info =
-            ConnectionInfo::new(datagram_limits.max_datagram_payload, self.waker.clone());
-        let (datagram_sender, datagram_receiver) = self.datagram.create_connection(&conn_info);
-        let datagram_manager = datagram::Manager::new(
-            datagram_sender,
-            datagram_receiver,
-            datagram_limits.max_datagram_payload,
-        );
+        let cipher_suite = key.cipher_suite().into_event();
 
-        let dc_manager = if let Some(dc_version) = dc_version {
-            let application_params = dc::ApplicationParams::new(
-                self.path_manager
-                    .active_path()
-                    .mtu_controller
-                    .max_datagram_size() as u16,
-                &peer_flow_control_limits,
-                self.limits,
+        if let Some(space) = self.application.as_mut() {
+            // The client already created the application space to send early data
+            debug_assert!(Config::ENDPOINT_TYPE.is_client());
+
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-7.4.1
+            //# The client
+            //# MUST use the server's new values in the handshake instead; if the
+            //# server does not provide new values, the default values are used.
+            <Config::StreamManager as stream::Manager>::on_peer_limits(
+                &mut space.stream_manager,
+                peer_flow_control_limits,
+            )?;
+            <Config::StreamManager as stream::Manager>::on_peer_reset_stream_at_support(
+                &mut space.stream_manager,
+                reset_stream_at_support.is_enabled(),
             );
-            let remote_address = self.path_manager.active_path().remote_address().0;
-            let conn_info = dc::ConnectionInfo::new(
-                &remote_address,
-                dc_version,
-                application_params,
-                Config::ENDPOINT_TYPE.into_event(),
+            space.dc_manager = dc_manager;
+            space.ack_frequency.on_peer_params(
+                min_ack_delay.map(Duration::from),
+                max_ack_delay.as_duration(),
             );
-            let dc_path = self.dc.new_path(&conn_info);
+            space.on_one_rtt_keys(key, header_key);
 
-            // &mut would be ideal but events currently need to be `Clone`, and we're OK with
-            // pushing interior mutability for now. dc is all unstable anyway.
-            self.publisher
-                .on_dc_path_created(DcPathCreated { path: &dc_path });
-
-            crate::dc::Manager::new(dc_path, dc_version, self.publisher)
+            if *self.early_data_status == EarlyDataStatus::Pending {
+                *self.early_data_status = EarlyDataStatus::Accepted;
+            }
         } else {
-            if Config::DcEndpoint::ENABLED {
-                self.publisher.on_dc_state_changed(DcStateChanged {
-                    state: DcState::NoVersionNegotiated,
-                });
+            let mut space =
+                self.new_application_space(peer_flow_control_limits, datagram_limits, dc_manager);
+            <Config::StreamManager as stream::Manager>::on_peer_reset_stream_at_support(
+                &mut space.stream_manager,
+                reset_stream_at_support.is_enabled(),
+            );
+
+            if let Some(zero_rtt_crypto) = self.zero_rtt_crypto.take() {
+                debug_assert!(Config::ENDPOINT_TYPE.is_server());
+                let (key, header_key) = *zero_rtt_crypto;
+                space.on_zero_rtt_keys(key, header_key);
             }
-            crate::dc::Manager::disabled()
-        };
 
-        self.path_manager
-            .active_path_mut()
-            .rtt_estimator
-            .on_max_ack_delay(max_ack_delay);
+            space.ack_frequency.on_peer_params(
+                min_ack_delay.map(Duration::from),
+                max_ack_delay.as_duration(),
+            );
+            space.on_one_rtt_keys(key, header_key);
+            *self.application = Some(Box::new(space));
+        }
+
+        // The multipath extension is used only if both endpoints send the
+        // initial_max_path_id transport parameter.
+        // See https://datatracker.ietf.org/doc/draft-ietf-quic-multipath#section-2
+        if let (Some(local_max_path_id), Some(peer_max_path_id)) =
+            (self.limits.initial_max_path_id(), initial_max_path_id)
+        {
+            self.path_manager
+                .on_multipath_negotiated(local_max_path_id.as_u32(), peer_max_path_id.as_u32());
+
+            if let Some(space) = self.application.as_mut() {
+                space.enable_multipath();
+            }
+        }
 
-        let cipher_suite = key.cipher_suite().into_event();
-        *self.application = Some(Box::new(ApplicationSpace::new(
-            key,
-            header_key,
-            self.now,
-            stream_manager,
-            ack_manager,
-            keep_alive,
-            datagram_manager,
-            dc_manager,
-        )));
         self.publisher.on_key_update(event::builder::KeyUpdate {
             key_type: event::builder::KeyType::OneRtt { generation: 0 },
             cipher_suite,
@@ -701,6 +952,10 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
         self.waker
     }
 
+    fn quic_version(&self) -> u32 {
+        self.version_negotiation.quic_version()
+    }
+
     fn on_client_application_params(
         &mut self,
         client_params: ApplicationParameters,
@@ -708,20 +963,33 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
     ) -> Result<(), Error> {
         debug_assert!(Config::ENDPOINT_TYPE.is_server());
 
-        if Config::DcEndpoint::ENABLED {
-            let param_decoder = DecoderBuffer::new(client_params.transport_parameters);
-            let (client_params, remaining) = ClientTransportParameters::decode(param_decoder)
-                .map_err(|_| {
-                    //= https://www.rfc-editor.org/rfc/rfc9000#section-7.4
-                    //# An endpoint SHOULD treat receipt of
-                    //# duplicate transport parameters as a connection error of type
-                    //# TRANSPORT_PARAMETER_ERROR.
-                    transport::Error::TRANSPORT_PARAMETER_ERROR
-                        .with_reason("Invalid transport parameters")
-                })?;
+        let param_decoder = DecoderBuffer::new(client_params.transport_parameters);
+        let (client_params, remaining) =
+            ClientTransportParameters::decode(param_decoder).map_err(|_| {
+                //= https://www.rfc-editor.org/rfc/rfc9000#section-7.4
+                //# An endpoint SHOULD treat receipt of
+                //# duplicate transport parameters as a connection error of type
+                //# TRANSPORT_PARAMETER_ERROR.
+                transport::Error::TRANSPORT_PARAMETER_ERROR
+                    .with_reason("Invalid transport parameters")
+            })?;
+
+        debug_assert_eq!(remaining.len(), 0);
 
-            debug_assert_eq!(remaining.len(), 0);
+        let negotiated_version = self
+            .version_negotiation
+            .select(client_params.version_information.as_ref());
 
+        if negotiated_version != self.version_negotiation.quic_version() {
+            self.on_compatible_version(negotiated_version)?;
+        }
+
+        // The chosen version is only known once the client's version_information is available
+        self.version_negotiation
+            .version_information()
+            .append_to_buffer(server_params);
+
+        if Config::DcEndpoint::ENABLED {
             if let Some(selected_version) = dc::select_version(client_params.dc_supported_versions)
             {
                 DcSupportedVersions::for_server(selected_version).append_to_buffer(server_params)
@@ -0,0 +1,242 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Compatible version negotiation, as defined in [RFC 9368](https://www.rfc-editor.org/rfc/rfc9368)
+//!
+//! A client initiates a connection with its preferred version and advertises the other
+//! versions it supports in the `version_information` transport parameter. A server can
+//! then switch the connection to a compatible version it prefers, without the additional
+//! round trip required by a Version Negotiation packet.
+
+use s2n_quic_core::{
+    connection::{PeerId, UnboundedId},
+    crypto::InitialKey,
+    endpoint,
+    transport::{self, parameters::VersionInformation},
+    version,
+};
+
+#[derive(Debug)]
+pub struct VersionNegotiation {
+    /// The versions the endpoint is willing to switch to
+    versions: version::Config,
+    /// The version of the first Initial packet sent by the client
+    original_version: u32,
+    /// The version currently in use on the connection
+    quic_version: u32,
+    /// The connection ID used to derive the Initial packet protection keys
+    initial_key_id: UnboundedId,
+    /// Set once the version in use can no longer change
+    is_confirmed: bool,
+}
+
+impl VersionNegotiation {
+    pub fn new(versions: version::Config, original_version: u32, initial_key_id: &[u8]) -> Self {
+        Self {
+            versions,
+            original_version,
+            quic_version: original_version,
+            initial_key_id: initial_key_id
+                .try_into()
+                .expect("connection ID already validated"),
+            is_confirmed: false,
+        }
+    }
+
+    /// The version currently in use on the connection
+    #[inline]
+    pub fn quic_version(&self) -> u32 {
+        self.quic_version
+    }
+
+    /// The version of the first Initial packet sent by the client
+    #[inline]
+    pub fn original_version(&self) -> u32 {
+        self.original_version
+    }
+
+    /// Returns the `version_information` transport parameter value sent by the endpoint
+    #[inline]
+    pub fn version_information(&self) -> VersionInformation {
+        VersionInformation::new(self.quic_version, self.versions.versions().iter().copied())
+    }
+
+    /// Called by the client when a Retry packet is processed
+    ///
+    /// Since the Initial keys are derived from the Retry packet's Source Connection ID, any
+    /// subsequent version switch needs to use it as well.
+    pub fn on_retry_packet(&mut self, retry_source_connection_id: &PeerId) {
+        self.initial_key_id = retry_source_connection_id
+            .as_bytes()
+            .try_into()
+            .expect("connection ID already validated");
+    }
+
+    /// Returns `true` if the client can switch to the version of an Initial packet
+    /// received from the server
+    ///
+    /// Clients only switch versions in response to the first Initial packet from the server,
+    /// and only to a version that was advertised in the client's `version_information`.
+    #[inline]
+    pub fn is_compatible(&self, version: u32) -> bool {
+        !self.is_confirmed
+            && self.quic_version == self.original_version
+            && version != self.quic_version
+            && self.versions.contains(version)
+            && version::is_compatible(self.original_version, version)
+    }
+
+    /// Called when a packet from the peer was successfully processed with the current version
+    #[inline]
+    pub fn on_packet_processed(&mut self) {
+        self.is_confirmed = true;
+    }
+
+    /// Derives the Initial keys for switching the connection to the given `version`
+    pub fn initial_keys<K: InitialKey>(
+        &self,
+        endpoint_type: endpoint::Type,
+        version: u32,
+    ) -> (K, K::HeaderKey) {
+        match endpoint_type {
+            endpoint::Type::Client => K::new_client(version, self.initial_key_id.as_bytes()),
+            endpoint::Type::Server => K::new_server(version, self.initial_key_id.as_bytes()),
+        }
+    }
+
+    /// Switches the connection to the negotiated `version`
+    #[inline]
+    pub fn on_version_negotiated(&mut self, version: u32) {
+        debug_assert!(version::is_compatible(self.original_version, version));
+        self.quic_version = version;
+        self.is_confirmed = true;
+    }
+
+    /// Selects the version to use for the connection based on the client's `version_information`
+    ///
+    /// This is called by the server when the client's transport parameters are available.
+    /// The `version_information` is validated separately with [`Self::validate_client_info`],
+    /// once the client's transport parameters are processed.
+    pub fn select(&self, client_info: Option<&VersionInformation>) -> u32 {
+        let Some(client_info) = client_info else {
+            // Clients that don't support compatible version negotiation stay on the original version
+            return self.original_version;
+        };
+
+        self.versions
+            .select_compatible(self.original_version, client_info.available_versions())
+    }
+
+    /// Validates the `version_information` transport parameter received from the client
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc9368#section-4>
+    pub fn validate_client_info(
+        &self,
+        client_info: &VersionInformation,
+    ) -> Result<(), transport::Error> {
+        // The client's Chosen Version is the version of the Initial packets it sent. A mismatch
+        // indicates an attacker modified the version field of the client's first Initial packet.
+        if client_info.chosen_version() != self.original_version {
+            return Err(transport::Error::VERSION_NEGOTIATION_ERROR
+                .with_reason("client chosen_version mismatch"));
+        }
+
+        Ok(())
+    }
+
+    /// Validates the `version_information` transport parameter received from the server
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc9368#section-4>
+    pub fn validate_server_info(
+        &self,
+        server_info: Option<&VersionInformation>,
+    ) -> Result<(), transport::Error> {
+        match server_info {
+            // The server's Chosen Version needs to match the version of the packets it sent
+            Some(server_info) if server_info.chosen_version() != self.quic_version => {
+                Err(transport::Error::VERSION_NEGOTIATION_ERROR
+                    .with_reason("server chosen_version mismatch"))
+            }
+            Some(_) => Ok(()),
+            // A server that switched versions is required to send the version_information
+            None if self.quic_version != self.original_version => {
+                Err(transport::Error::VERSION_NEGOTIATION_ERROR
+                    .with_reason("missing version_information"))
+            }
+            // The server doesn't support compatible version negotiation
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::version::{V1, V2};
+
+    fn negotiation(versions: &[u32], original_version: u32) -> VersionNegotiation {
+        let versions = version::Config::builder()
+            .with_versions(versions)
+            .unwrap()
+            .build()
+            .unwrap();
+        VersionNegotiation::new(versions, original_version, &[1; 8])
+    }
+
+    #[test]
+    fn server_select_test() {
+        let server = negotiation(&[V2, V1], V1);
+
+        // clients without version_information stay on the original version
+        assert_eq!(server.select(None), V1);
+
+        let client_info = VersionInformation::new(V1, [V1, V2]);
+        assert_eq!(server.select(Some(&client_info)), V2);
+        assert!(server.validate_client_info(&client_info).is_ok());
+
+        let client_info = VersionInformation::new(V1, [V1]);
+        assert_eq!(server.select(Some(&client_info)), V1);
+
+        // the chosen version must match the version of the client's Initial packet
+        let client_info = VersionInformation::new(V2, [V1, V2]);
+        assert_eq!(
+            server.validate_client_info(&client_info).unwrap_err().code,
+            transport::Error::VERSION_NEGOTIATION_ERROR.code
+        );
+    }
+
+    #[test]
+    fn client_switch_test() {
+        let mut client = negotiation(&[V1, V2], V1);
+
+        assert!(!client.is_compatible(V1));
+        assert!(client.is_compatible(V2));
+        assert!(!client.is_compatible(0xff00_001d));
+
+        client.on_version_negotiated(V2);
+        assert_eq!(client.quic_version(), V2);
+        // the version can only change once
+        assert!(!client.is_compatible(V1));
+
+        assert!(client.validate_server_info(None).is_err());
+        assert!(client
+            .validate_server_info(Some(&VersionInformation::new(V1, [V1, V2])))
+            .is_err());
+        assert!(client
+            .validate_server_info(Some(&VersionInformation::new(V2, [V1, V2])))
+            .is_ok());
+    }
+
+    #[test]
+    fn client_confirmed_test() {
+        let mut client = negotiation(&[V1, V2], V1);
+        client.on_packet_processed();
+        assert!(!client.is_compatible(V2));
+
+        assert!(client.validate_server_info(None).is_ok());
+
+        // versions not advertised by the client are not accepted
+        let client = negotiation(&[V1], V1);
+        assert!(!client.is_compatible(V2));
+    }
+}
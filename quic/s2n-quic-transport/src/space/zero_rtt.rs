@@ -0,0 +1,214 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    connection, endpoint, path,
+    path::Path,
+    processed_packet::ProcessedPacket,
+    space::{ApplicationSpace, HandshakeStatus, PacketSpace},
+};
+use s2n_quic_core::{
+    event,
+    frame::{
+        ack::AckRanges, crypto::CryptoRef, datagram::DatagramRef, stream::StreamRef, Ack,
+        AckFrequency, ConnectionClose, DataBlocked, ImmediateAck, MaxData, MaxStreamData,
+        MaxStreams, NewConnectionId, PathChallenge, ResetStream, ResetStreamAt, RetireConnectionId,
+        StopSending, StreamDataBlocked, StreamsBlocked,
+    },
+    inet::DatagramInfo,
+    packet::number::PacketNumber,
+    time::Timestamp,
+    transport,
+};
+
+/// Processes the frames in 0-RTT packets received by a server
+///
+/// 0-RTT packets share the application data packet number space but are only permitted to
+/// carry a subset of the frames that 1-RTT packets can.
+//
+//= https://www.rfc-editor.org/rfc/rfc9000#section-12.4
+//# An endpoint MUST treat receipt of a frame in a packet type that is not
+//# permitted as a connection error of type PROTOCOL_VIOLATION.
+pub struct ZeroRttSpace<'a, Config: endpoint::Config> {
+    space: &'a mut ApplicationSpace<Config>,
+}
+
+impl<'a, Config: endpoint::Config> ZeroRttSpace<'a, Config> {
+    pub fn new(space: &'a mut ApplicationSpace<Config>) -> Self {
+        debug_assert!(Config::ENDPOINT_TYPE.is_server());
+        Self { space }
+    }
+}
+
+impl<Config: endpoint::Config> PacketSpace<Config> for ZeroRttSpace<'_, Config> {
+    const INVALID_FRAME_ERROR: &'static str = "invalid frame in 0-RTT packet";
+
+    fn on_amplification_unblocked(
+        &mut self,
+        path_manager: &path::Manager<Config>,
+        timestamp: Timestamp,
+        is_handshake_confirmed: bool,
+    ) {
+        self.space
+            .on_amplification_unblocked(path_manager, timestamp, is_handshake_confirmed)
+    }
+
+    fn handle_crypto_frame<Pub: event::ConnectionPublisher>(
+        &mut self,
+        frame: CryptoRef,
+        _datagram: &DatagramInfo,
+        _path: &mut Path<Config>,
+        _publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        Err(transport::Error::PROTOCOL_VIOLATION
+            .with_reason(Self::INVALID_FRAME_ERROR)
+            .with_frame_type(frame.tag().into()))
+    }
+
+    fn handle_ack_frame<A: AckRanges, Pub: event::ConnectionPublisher>(
+        &mut self,
+        frame: Ack<A>,
+        _timestamp: Timestamp,
+        _path_id: path::Id,
+        _path_manager: &mut path::Manager<Config>,
+        _packet_number: PacketNumber,
+        _handshake_status: &mut HandshakeStatus,
+        _local_id_registry: &mut connection::LocalIdRegistry,
+        _random_generator: &mut Config::RandomGenerator,
+        _publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.3
+        //# An acknowledgment for a 1-RTT
+        //# packet MUST be carried in a 1-RTT packet.
+        Err(transport::Error::PROTOCOL_VIOLATION
+            .with_reason(Self::INVALID_FRAME_ERROR)
+            .with_frame_type(frame.tag().into()))
+    }
+
+    fn handle_connection_close_frame<Pub: event::ConnectionPublisher>(
+        &mut self,
+        frame: ConnectionClose,
+        path_id: path::Id,
+        path: &mut Path<Config>,
+        packet_number: PacketNumber,
+        publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        self.space
+            .handle_connection_close_frame(frame, path_id, path, packet_number, publisher)
+    }
+
+    fn handle_retire_connection_id_frame(
+        &mut self,
+        frame: RetireConnectionId,
+        datagram: &DatagramInfo,
+        path: &mut Path<Config>,
+        local_id_registry: &mut connection::LocalIdRegistry,
+    ) -> Result<(), transport::Error> {
+        self.space
+            .handle_retire_connection_id_frame(frame, datagram, path, local_id_registry)
+    }
+
+    fn handle_new_connection_id_frame<Pub: event::ConnectionPublisher>(
+        &mut self,
+        frame: NewConnectionId,
+        datagram: &DatagramInfo,
+        path_manager: &mut path::Manager<Config>,
+        publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        self.space
+            .handle_new_connection_id_frame(frame, datagram, path_manager, publisher)
+    }
+
+    fn handle_path_challenge_frame(
+        &mut self,
+        frame: PathChallenge,
+        path_id: path::Id,
+        path_manager: &mut path::Manager<Config>,
+    ) -> Result<(), transport::Error> {
+        self.space
+            .handle_path_challenge_frame(frame, path_id, path_manager)
+    }
+
+    fn handle_stream_frame(
+        &mut self,
+        frame: StreamRef,
+        packet: &mut ProcessedPacket,
+    ) -> Result<(), transport::Error> {
+        self.space.handle_stream_frame(frame, packet)
+    }
+
+    fn handle_datagram_frame(
+        &mut self,
+        path: event::api::Path<'_>,
+        frame: DatagramRef,
+    ) -> Result<(), transport::Error> {
+        self.space.handle_datagram_frame(path, frame)
+    }
+
+    fn handle_data_blocked_frame(&mut self, frame: DataBlocked) -> Result<(), transport::Error> {
+        self.space.handle_data_blocked_frame(frame)
+    }
+
+    fn handle_max_data_frame(&mut self, frame: MaxData) -> Result<(), transport::Error> {
+        self.space.handle_max_data_frame(frame)
+    }
+
+    fn handle_max_stream_data_frame(
+        &mut self,
+        frame: MaxStreamData,
+    ) -> Result<(), transport::Error> {
+        self.space.handle_max_stream_data_frame(frame)
+    }
+
+    fn handle_max_streams_frame(&mut self, frame: MaxStreams) -> Result<(), transport::Error> {
+        self.space.handle_max_streams_frame(frame)
+    }
+
+    fn handle_reset_stream_frame(&mut self, frame: ResetStream) -> Result<(), transport::Error> {
+        self.space.handle_reset_stream_frame(frame)
+    }
+
+    fn handle_stop_sending_frame(&mut self, frame: StopSending) -> Result<(), transport::Error> {
+        self.space.handle_stop_sending_frame(frame)
+    }
+
+    fn handle_stream_data_blocked_frame(
+        &mut self,
+        frame: StreamDataBlocked,
+    ) -> Result<(), transport::Error> {
+        self.space.handle_stream_data_blocked_frame(frame)
+    }
+
+    fn handle_streams_blocked_frame(
+        &mut self,
+        frame: StreamsBlocked,
+    ) -> Result<(), transport::Error> {
+        self.space.handle_streams_blocked_frame(frame)
+    }
+
+    fn handle_ack_frequency_frame(&mut self, frame: AckFrequency) -> Result<(), transport::Error> {
+        self.space.handle_ack_frequency_frame(frame)
+    }
+
+    fn handle_immediate_ack_frame(&mut self, frame: ImmediateAck) -> Result<(), transport::Error> {
+        self.space.handle_immediate_ack_frame(frame)
+    }
+
+    fn handle_reset_stream_at_frame(
+        &mut self,
+        frame: ResetStreamAt,
+    ) -> Result<(), transport::Error> {
+        self.space.handle_reset_stream_at_frame(frame)
+    }
+
+    fn on_processed_packet<Pub: event::ConnectionPublisher>(
+        &mut self,
+        processed_packet: ProcessedPacket,
+        path_id: path::Id,
+        path: &Path<Config>,
+        publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        self.space
+            .on_processed_packet(processed_packet, path_id, path, publisher)
+    }
+}
@@ -13,7 +13,8 @@ use core::{
 };
 pub use s2n_quic_core::{
     application,
-    stream::{ops, StreamError, StreamId, StreamType},
+    stream::{ops, Priority, StreamError, StreamId, StreamType},
+    varint::VarInt,
 };
 
 #[derive(Clone)]
@@ -39,8 +40,47 @@ impl State {
         request: &mut ops::Request,
         context: Option<&Context>,
     ) -> Result<ops::Response, StreamError> {
+        if let Some(error) = self.early_data_rejected() {
+            return Err(error);
+        }
+
         let id = self.stream_id;
-        self.connection.poll_request(id, request, context)
+        let result = self.connection.poll_request(id, request, context);
+
+        if let Err(error @ StreamError::EarlyDataRejected { .. }) = result {
+            self.on_early_data_rejected(error);
+        }
+
+        result
+    }
+
+    /// Returns the error if the stream was rejected with early data
+    fn early_data_rejected(&self) -> Option<StreamError> {
+        match (self.rx, self.tx) {
+            (ops::Status::Reset(error @ StreamError::EarlyDataRejected { .. }), _)
+            | (_, ops::Status::Reset(error @ StreamError::EarlyDataRejected { .. })) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Releases the stream after it was rejected with early data
+    ///
+    /// The ID of a rejected stream is used again for a stream that is opened afterwards, so no
+    /// more requests are issued for it.
+    fn on_early_data_rejected(&mut self, error: StreamError) {
+        let mut request = ops::Request::default();
+        if !self.tx.is_closed() {
+            request.detach_tx();
+        }
+        if !self.rx.is_closed() {
+            request.detach_rx();
+        }
+        let _ = self
+            .connection
+            .poll_request(self.stream_id, &mut request, None);
+
+        self.rx = ops::Status::Reset(error);
+        self.tx = ops::Status::Reset(error);
     }
 
     fn request(&mut self) -> Request {
@@ -215,6 +255,31 @@ macro_rules! tx_stream_apis {
             self.tx_request()?.reset(error_code).poll(None)?;
             Ok(())
         }
+
+        /// Initiates a `RESET` on the stream, while still delivering the data up to
+        /// `reliable_size` to the peer.
+        ///
+        /// This will close the stream and notify the peer of the provided `error_code`
+        /// once the data up to `reliable_size` has been received.
+        pub fn reset_at(
+            &mut self,
+            error_code: application::Error,
+            reliable_size: VarInt,
+        ) -> Result<(), StreamError> {
+            self.tx_request()?
+                .reset_at(error_code, reliable_size)
+                .poll(None)?;
+            Ok(())
+        }
+
+        /// Sets the scheduling priority of the stream.
+        ///
+        /// The priority determines the order in which the stream's data is written into
+        /// packets, relative to the other streams on the connection.
+        pub fn set_priority(&mut self, priority: Priority) -> Result<(), StreamError> {
+            self.tx_request()?.with_priority(priority).poll(None)?;
+            Ok(())
+        }
     };
 }
 
@@ -481,10 +546,24 @@ macro_rules! tx_request_apis {
             self
         }
 
+        pub fn reset_at(
+            &mut self,
+            error_code: application::Error,
+            reliable_size: VarInt,
+        ) -> &mut Self {
+            self.request.reset_at(error_code, reliable_size);
+            self
+        }
+
         pub fn flush(&mut self) -> &mut Self {
             self.request.flush();
             self
         }
+
+        pub fn with_priority(&mut self, priority: Priority) -> &mut Self {
+            self.request.with_priority(priority);
+            self
+        }
     };
 }
 
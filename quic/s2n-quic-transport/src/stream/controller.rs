@@ -98,6 +98,31 @@ impl Controller {
         }
     }
 
+    /// This method is called by the client when the server rejected early data
+    ///
+    /// The locally initiated streams that are currently open were rejected and no longer count
+    /// towards the server's limits. Stream capacity is only available once all of the rejected
+    /// streams are closed and the server's limits for the current connection are known.
+    pub fn on_zero_rtt_rejected(&mut self) {
+        self.local_bidi_controller.on_zero_rtt_rejected();
+        self.local_uni_controller.on_zero_rtt_rejected();
+    }
+
+    /// Returns `true` if the given stream was rejected with early data and has not been
+    /// closed yet
+    pub fn is_rejected_stream(&self, stream_id: StreamId) -> bool {
+        // No locally initiated streams can be opened while rejected streams are still open
+        match self.direction(stream_id) {
+            StreamDirection::LocalInitiatedBidirectional => {
+                self.local_bidi_controller.has_rejected_streams()
+            }
+            StreamDirection::LocalInitiatedUnidirectional => {
+                self.local_uni_controller.has_rejected_streams()
+            }
+            _ => false,
+        }
+    }
+
     /// This method is called when the local application wishes to open the next stream
     /// of a type (Bidirectional/Unidirectional).
     ///
@@ -43,6 +43,10 @@ pub(super) struct LocalInitiated<L: LocalLimits, OpenNotify: OpenNotifyBehavior>
     /// peer_stream_limit is a cumulative limit.
     opened_streams: VarInt,
     closed_streams: VarInt,
+    /// The number of streams that were opened with early data which the peer rejected.
+    ///
+    /// The rejected streams don't count towards the peer's limits.
+    rejected_streams: VarInt,
     /// Keeps track of all of the issued open tokens
     token_counter: open_token::Counter,
     /// Keeps track of all of the expired open tokens
@@ -59,6 +63,7 @@ impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> LocalInitiated<L, OpenNotif
             streams_blocked_sync: PeriodicSync::new(),
             opened_streams: VarInt::from_u8(0),
             closed_streams: VarInt::from_u8(0),
+            rejected_streams: VarInt::from_u8(0),
             token_counter: open_token::Counter::new(),
             expired_token: open_token::Token::new(),
             open_notify: Default::default(),
@@ -83,6 +88,27 @@ impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> LocalInitiated<L, OpenNotif
         self.wake_unblocked();
     }
 
+    /// This method is called by the client when the peer rejected early data
+    ///
+    /// All of the streams that are currently open were rejected. The stream limits the
+    /// peer sent on a previous connection no longer apply, so no capacity is available until
+    /// the peer's limits for the current connection are known.
+    pub fn on_zero_rtt_rejected(&mut self) {
+        self.peer_cumulative_stream_limit = VarInt::from_u8(0);
+        self.rejected_streams = self.opened_streams;
+        self.streams_blocked_sync.stop_sync();
+    }
+
+    /// Returns `true` if streams that were rejected with early data are still open
+    ///
+    /// The stream IDs of the rejected streams are used again for the streams that are opened
+    /// afterwards, so no new streams can be opened until all of the rejected streams are closed.
+    #[inline]
+    pub fn has_rejected_streams(&self) -> bool {
+        // Only rejected streams are open until all of them were closed
+        self.closed_streams < self.rejected_streams
+    }
+
     pub fn update_sync_period(&mut self, blocked_sync_period: Duration) {
         self.streams_blocked_sync
             .update_sync_period(blocked_sync_period);
@@ -149,6 +175,10 @@ impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> LocalInitiated<L, OpenNotif
     /// the local concurrent streams limit and the peer's stream limits.
     #[inline]
     pub fn available_stream_capacity(&self) -> VarInt {
+        if self.has_rejected_streams() {
+            return VarInt::from_u8(0);
+        }
+
         let local_capacity = self
             .max_local_limit
             .as_varint()
@@ -160,7 +190,7 @@ impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> LocalInitiated<L, OpenNotif
     #[inline]
     fn peer_capacity(&self) -> VarInt {
         self.peer_cumulative_stream_limit
-            .saturating_sub(self.opened_streams)
+            .saturating_sub(self.opened_streams - self.rejected_streams)
     }
 
     /// Wake all wakers
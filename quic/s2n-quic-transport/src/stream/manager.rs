@@ -28,7 +28,7 @@ use s2n_quic_core::{
     endpoint,
     frame::{
         stream::StreamRef, DataBlocked, MaxData, MaxStreamData, MaxStreams, ResetStream,
-        StopSending, StreamDataBlocked, StreamsBlocked,
+        ResetStreamAt, StopSending, StreamDataBlocked, StreamsBlocked,
     },
     packet::number::PacketNumberSpace,
     stream::{iter::StreamIter, ops, StreamId, StreamType},
@@ -179,6 +179,10 @@ pub struct StreamManagerState<S> {
     /// The initial flow control limits we received from the peer via transport
     /// parameters
     initial_peer_limits: InitialFlowControlLimits,
+    /// Whether `RESET_STREAM_AT` frames can be received from the peer
+    reset_stream_at_enabled: bool,
+    /// Whether the peer supports receiving `RESET_STREAM_AT` frames
+    peer_reset_stream_at_support: bool,
     /// If the `StreamManager` was closed, this contains the error which was
     /// passed to the `close()` call
     close_reason: Option<connection::Error>,
@@ -247,6 +251,7 @@ impl<S: StreamTrait> StreamManagerState<S> {
             desired_flow_control_window: initial_receive_window.as_u64() as u32,
             initial_send_window,
             max_send_buffer_size: self.stream_limits.max_send_buffer_size.as_u32(),
+            reset_stream_at_supported: self.peer_reset_stream_at_support,
         }));
     }
 
@@ -401,6 +406,35 @@ impl<S: StreamTrait> StreamManagerState<S> {
         self.stream_controller.close();
     }
 
+    fn on_zero_rtt_rejected(&mut self) {
+        //= https://www.rfc-editor.org/rfc/rfc9001#section-4.6.2
+        //# When 0-RTT is rejected, all connection characteristics that the
+        //# client assumed might be incorrect.  This includes the choice of
+        //# application protocol, transport parameters, and any application
+        //# configuration.  The client therefore MUST reset the state of all
+        //# streams, including application state bound to those streams.
+        let error = StreamError::early_data_rejected();
+        self.streams
+            .iterate_streams(&mut self.stream_controller, |stream| {
+                let mut events = StreamEvents::new();
+                stream.on_internal_reset(error, &mut events);
+                events.wake_all();
+            });
+
+        self.stream_controller.on_zero_rtt_rejected();
+        self.outgoing_connection_flow_controller
+            .on_zero_rtt_rejected();
+        self.initial_peer_limits = InitialFlowControlLimits::default();
+
+        // The server never learned about the rejected streams so their IDs are used again
+        for stream_type in [StreamType::Bidirectional, StreamType::Unidirectional] {
+            *self
+                .next_stream_ids
+                .get_mut(self.local_endpoint_type, stream_type) =
+                Some(StreamId::initial(self.local_endpoint_type, stream_type));
+        }
+    }
+
     fn flush(&mut self, error: connection::Error) -> Poll<()> {
         self.close(error, true);
 
@@ -598,6 +632,9 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
                 local_endpoint_type,
                 initial_local_limits,
                 initial_peer_limits,
+                reset_stream_at_enabled: connection_limits.reset_stream_at_enabled(),
+                // Support is only assumed once the peer's transport parameters are known
+                peer_reset_stream_at_support: false,
                 close_reason: None,
                 accept_state: AcceptState::new(local_endpoint_type),
                 stream_limits: connection_limits.stream_limits(),
@@ -912,6 +949,20 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         self.handle_stream_frame(stream_id, |stream, events| stream.on_reset(frame, events))
     }
 
+    fn on_reset_stream_at(&mut self, frame: &ResetStreamAt) -> Result<(), transport::Error> {
+        // An endpoint that didn't send the reset_stream_at transport parameter can't
+        // receive RESET_STREAM_AT frames
+        if !self.inner.reset_stream_at_enabled {
+            return Err(transport::Error::PROTOCOL_VIOLATION
+                .with_reason("RESET_STREAM_AT frame received without sending reset_stream_at"));
+        }
+
+        let stream_id = StreamId::from_varint(frame.stream_id);
+        self.handle_stream_frame(stream_id, |stream, events| {
+            stream.on_reset_at(frame, events)
+        })
+    }
+
     fn on_max_stream_data(&mut self, frame: &MaxStreamData) -> Result<(), transport::Error> {
         let stream_id = StreamId::from_varint(frame.stream_id);
         self.handle_stream_frame(stream_id, |stream, events| {
@@ -975,6 +1026,51 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         Ok(())
     }
 
+    fn on_peer_limits(
+        &mut self,
+        peer_limits: InitialFlowControlLimits,
+    ) -> Result<(), transport::Error> {
+        // Streams opened from now on use the new stream limits. Streams that were already
+        // opened with the remembered limits will be issued additional credit by the peer with
+        // MAX_STREAM_DATA frames.
+        self.inner.initial_peer_limits = peer_limits;
+
+        // The connection flow controller and the stream controller ignore any values that are
+        // lower than the ones they already have
+        self.on_max_data(MaxData {
+            maximum_data: peer_limits.max_data,
+        })?;
+
+        for (stream_type, maximum_streams) in [
+            (
+                StreamType::Bidirectional,
+                peer_limits.max_open_remote_bidirectional_streams,
+            ),
+            (
+                StreamType::Unidirectional,
+                peer_limits.max_open_remote_unidirectional_streams,
+            ),
+        ] {
+            self.on_max_streams(&MaxStreams {
+                stream_type,
+                maximum_streams,
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn on_zero_rtt_rejected(&mut self) {
+        self.inner.on_zero_rtt_rejected();
+    }
+
+    fn on_peer_reset_stream_at_support(&mut self, is_supported: bool) {
+        // Only streams opened from now on can send RESET_STREAM_AT frames. Streams that were
+        // already opened fall back to sending a RESET_STREAM frame once the reliable data
+        // has been acknowledged.
+        self.inner.peer_reset_stream_at_support = is_supported;
+    }
+
     fn poll_request(
         &mut self,
         stream_id: StreamId,
@@ -982,6 +1078,27 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         request: &mut ops::Request,
         context: Option<&Context>,
     ) -> Result<ops::Response, StreamError> {
+        // The IDs of the streams that were rejected with early data are used again once all of
+        // them are closed. Until then, the application can only release its handles to them.
+        if self.inner.stream_controller.is_rejected_stream(stream_id) {
+            return self.perform_api_call(
+                stream_id,
+                Err(StreamError::early_data_rejected()),
+                api_call_context,
+                |stream| {
+                    // The receiving side fails the request before the sending side is polled,
+                    // so each side is detached with a separate request
+                    if request.tx.as_ref().is_some_and(|tx| tx.detached) {
+                        let _ = stream.poll_request(ops::Request::default().detach_tx(), None);
+                    }
+                    if request.rx.as_ref().is_some_and(|rx| rx.detached) {
+                        let _ = stream.poll_request(ops::Request::default().detach_rx(), None);
+                    }
+                    Err(StreamError::early_data_rejected())
+                },
+            );
+        }
+
         self.perform_api_call(
             stream_id,
             Err(StreamError::invalid_stream()),
@@ -37,10 +37,10 @@ use s2n_quic_core::{
     application::Error as ApplicationErrorCode,
     frame::{
         stream::StreamRef, DataBlocked, Frame, MaxData, MaxStreamData, MaxStreams, ResetStream,
-        StopSending, Stream as StreamFrame, StreamDataBlocked, StreamsBlocked,
+        ResetStreamAt, StopSending, Stream as StreamFrame, StreamDataBlocked, StreamsBlocked,
     },
     packet::number::{PacketNumberRange, PacketNumberSpace},
-    stream::{ops, StreamId, StreamType},
+    stream::{ops, Priority, StreamId, StreamType},
     time::{
         clock::testing as time,
         timer::{self, Provider as _},
@@ -57,6 +57,7 @@ use s2n_quic_core::{
 struct MockStream {
     config: StreamConfig,
     last_reset: Option<ResetStream>,
+    last_reset_at: Option<ResetStreamAt>,
     last_on_stream_data_blocked: Option<StreamDataBlocked>,
     last_max_stream_data: Option<MaxStreamData>,
     last_stop_sending: Option<StopSending>,
@@ -75,6 +76,7 @@ struct MockStream {
     on_transmit_limit: Option<usize>,
     on_data_count: usize,
     on_reset_count: usize,
+    on_reset_at_count: usize,
     on_stream_data_blocked_count: usize,
     on_stop_sending_count: usize,
     on_max_stream_data_count: usize,
@@ -104,6 +106,7 @@ impl MockStream {
 impl StreamInterestProvider for MockStream {
     fn stream_interests(&self, interests: &mut StreamInterests) {
         interests.merge(&self.interests);
+        interests.priority = self.interests.priority;
 
         interests.connection_flow_control_credits |=
             self.on_connection_window_available_retrieve_window > 0;
@@ -127,6 +130,7 @@ impl StreamTrait for MockStream {
         Self {
             config,
             last_reset: None,
+            last_reset_at: None,
             last_on_stream_data_blocked: None,
             last_max_stream_data: None,
             last_stop_sending: None,
@@ -143,6 +147,7 @@ impl StreamTrait for MockStream {
             on_internal_reset_count: 0,
             on_data_count: 0,
             on_reset_count: 0,
+            on_reset_at_count: 0,
             on_stream_data_blocked_count: 0,
             on_stop_sending_count: 0,
             on_max_stream_data_count: 0,
@@ -212,6 +217,21 @@ impl StreamTrait for MockStream {
         Ok(())
     }
 
+    fn on_reset_at(
+        &mut self,
+        frame: &ResetStreamAt,
+        events: &mut StreamEvents,
+    ) -> Result<(), TransportError> {
+        assert_eq!(self.stream_id(), StreamId::from_varint(frame.stream_id));
+        self.on_reset_at_count += 1;
+        self.last_reset_at = Some(*frame);
+        self.store_wakers(events);
+        if let Some(err) = self.next_packet_error {
+            return Err(err);
+        };
+        Ok(())
+    }
+
     fn on_max_stream_data(
         &mut self,
         frame: &MaxStreamData,
@@ -2943,6 +2963,114 @@ fn forwards_on_reset() {
     assert_eq!(write_wake_counter, 2);
 }
 
+#[test]
+fn forwards_on_reset_at() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+    let stream_1 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+
+    let frame = ResetStreamAt {
+        stream_id: stream_1.into(),
+        application_error_code: VarInt::from_u32(3_928_776),
+        final_size: VarInt::from_u32(44_222_111),
+        reliable_size: VarInt::from_u32(1_000),
+    };
+
+    // RESET_STREAM_AT frames are rejected if the extension wasn't enabled
+    assert_is_transport_error(
+        manager.on_reset_stream_at(&frame),
+        TransportError::PROTOCOL_VIOLATION,
+    );
+    manager.with_asserted_stream(stream_1, |stream| {
+        assert_eq!(stream.on_reset_at_count, 0);
+    });
+
+    manager.inner.reset_stream_at_enabled = true;
+    assert_eq!(Ok(()), manager.on_reset_stream_at(&frame));
+
+    manager.with_asserted_stream(stream_1, |stream| {
+        assert_eq!(stream.on_reset_at_count, 1);
+        assert_eq!(Some(frame), stream.last_reset_at);
+    });
+}
+
+#[test]
+fn peer_reset_stream_at_support_applies_to_new_streams() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+
+    let stream_1 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    manager.on_peer_reset_stream_at_support(true);
+    let stream_2 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+
+    manager.with_asserted_stream(stream_1, |stream| {
+        assert!(!stream.config.reset_stream_at_supported);
+    });
+    manager.with_asserted_stream(stream_2, |stream| {
+        assert!(stream.config.reset_stream_at_supported);
+    });
+}
+
+#[test]
+fn zero_rtt_rejected_resets_streams_and_peer_limits() {
+    let mut manager = create_stream_manager(endpoint::Type::Client);
+    let (_wakeup_queue, wakeup_handle) = create_wakeup_queue_and_handle();
+
+    // Send some early data with the limits remembered from a previous connection
+    let rejected_1 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    let rejected_2 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    assert_eq!(
+        VarInt::from_u32(1000),
+        manager.with_outgoing_connection_flow_controller(
+            |ctrl| ctrl.acquire_window(VarInt::from_u32(1000))
+        )
+    );
+
+    manager.with_asserted_stream(rejected_2, |stream| {
+        stream.set_finalize_on_internal_reset = true;
+    });
+
+    manager.on_zero_rtt_rejected();
+
+    // All of the streams are failed back to the application
+    manager.with_asserted_stream(rejected_1, |stream| {
+        assert_eq!(1, stream.on_internal_reset_count);
+    });
+    assert_eq!([rejected_1], *manager.active_streams());
+    assert!(matches!(
+        manager.poll_request(
+            rejected_1,
+            &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
+            ops::Request::default().send(&mut [Bytes::from_static(b"hello")]),
+            None,
+        ),
+        Err(StreamError::EarlyDataRejected { .. })
+    ));
+
+    // The server's limits for the current connection are lower than the remembered ones
+    let mut peer_limits = create_default_initial_flow_control_limits();
+    peer_limits.max_data = VarInt::from_u32(100);
+    peer_limits.max_open_remote_bidirectional_streams = VarInt::from_u32(1);
+    manager.on_peer_limits(peer_limits).unwrap();
+
+    assert_eq!(
+        VarInt::from_u32(100),
+        manager.with_outgoing_connection_flow_controller(|ctrl| ctrl.available_window())
+    );
+
+    // The stream IDs can't be used again until the rejected streams are released
+    assert!(try_open(&mut manager, StreamType::Bidirectional).is_err());
+    manager.with_asserted_stream(rejected_1, |stream| {
+        stream.interests.retained = false;
+    });
+    assert!(manager.active_streams().is_empty());
+
+    // The server never received the rejected streams, so only a single stream can be opened
+    assert_eq!(
+        Ok(rejected_1),
+        try_open(&mut manager, StreamType::Bidirectional)
+    );
+    assert!(try_open(&mut manager, StreamType::Bidirectional).is_err());
+}
+
 #[test]
 fn forwards_poll_pop() {
     let (mut wakeup_queue, wakeup_handle) = create_wakeup_queue_and_handle();
@@ -3255,6 +3383,75 @@ fn stream_transmission_fairness_test() {
     }
 }
 
+#[test]
+fn stream_transmission_priority_test() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+
+    let streams: Vec<_> = (0..4)
+        .map(|_| try_open(&mut manager, StreamType::Bidirectional).unwrap())
+        .collect();
+
+    for stream_id in &streams {
+        manager.with_asserted_stream(*stream_id, |stream| {
+            stream.on_transmit_try_write_frames = 100;
+            stream.on_transmit_limit = Some(1);
+        });
+    }
+
+    // make sure the order matches creation order
+    assert_eq!(streams, manager.streams_waiting_for_transmission());
+
+    // raise the urgency of the last stream
+    manager.with_asserted_stream(streams[3], |stream| {
+        stream.interests.priority = Priority::new(0, false);
+    });
+
+    // lower the urgency of the first stream
+    manager.with_asserted_stream(streams[0], |stream| {
+        stream.interests.priority = Priority::new(Priority::MAX_URGENCY, true);
+    });
+
+    let expected = vec![streams[3], streams[1], streams[2], streams[0]];
+    assert_eq!(expected, manager.streams_waiting_for_transmission());
+
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    let mut write_context = MockWriteContext::new(
+        time::now(),
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::Normal,
+        endpoint::Type::Server,
+    );
+
+    // The non-incremental stream should remain at the front of the list
+    for _ in 0..4 {
+        let _ = manager.on_transmit(&mut write_context);
+        write_context.frame_buffer.flush();
+        write_context.frame_buffer.set_error_write_after_n_frames(1);
+
+        assert_eq!(expected, manager.streams_waiting_for_transmission());
+    }
+
+    // Once the urgent stream is done, the incremental streams should rotate but always be
+    // scheduled before the less urgent stream
+    manager.with_asserted_stream(streams[3], |stream| {
+        stream.on_transmit_try_write_frames = 0;
+    });
+
+    let mut expected = vec![streams[2], streams[1]];
+    for _ in 0..4 {
+        let _ = manager.on_transmit(&mut write_context);
+        write_context.frame_buffer.flush();
+        write_context.frame_buffer.set_error_write_after_n_frames(1);
+
+        let mut actual = manager.streams_waiting_for_transmission();
+        assert_eq!(actual.pop(), Some(streams[0]));
+        assert_eq!(expected, actual);
+
+        expected.rotate_left(1);
+    }
+}
+
 #[test]
 fn stream_batching_test() {
     for batch_size in 1..=10 {
@@ -16,7 +16,7 @@ use s2n_quic_core::{
     ack, endpoint,
     frame::{
         stream::StreamRef, DataBlocked, MaxData, MaxStreamData, MaxStreams, ResetStream,
-        StopSending, StreamDataBlocked, StreamsBlocked,
+        ResetStreamAt, StopSending, StreamDataBlocked, StreamsBlocked,
     },
     stream::{ops, StreamId, StreamType},
     time::{timer, Timestamp},
@@ -113,6 +113,10 @@ pub trait Manager:
     /// a stream
     fn on_reset_stream(&mut self, frame: &ResetStream) -> Result<(), transport::Error>;
 
+    /// This is called when a `RESET_STREAM_AT` frame had been received for
+    /// a stream
+    fn on_reset_stream_at(&mut self, frame: &ResetStreamAt) -> Result<(), transport::Error>;
+
     /// This is called when a `MAX_STREAM_DATA` frame had been received for
     /// a stream
     fn on_max_stream_data(&mut self, frame: &MaxStreamData) -> Result<(), transport::Error>;
@@ -130,6 +134,30 @@ pub trait Manager:
     /// This is called when a `MAX_STREAMS` frame had been received
     fn on_max_streams(&mut self, frame: &MaxStreams) -> Result<(), transport::Error>;
 
+    /// This is called by a client that sent early data once it receives the
+    /// server's transport parameters for the current connection
+    ///
+    /// The stream manager was created with the limits the server sent on a previous
+    /// connection. Any limits that were increased take effect immediately. If the server
+    /// rejected the early data, [`Self::on_zero_rtt_rejected`] was called beforehand and
+    /// the server's current limits are applied as they are.
+    fn on_peer_limits(
+        &mut self,
+        peer_limits: InitialFlowControlLimits,
+    ) -> Result<(), transport::Error>;
+
+    /// This is called by a client when the server rejected early data
+    ///
+    /// All of the streams that were opened are failed with [`StreamError::EarlyDataRejected`]
+    /// and the limits the server sent on a previous connection are discarded.
+    fn on_zero_rtt_rejected(&mut self);
+
+    /// This is called once the peer's transport parameters are known
+    ///
+    /// Streams opened after this call will send `RESET_STREAM_AT` frames if the peer
+    /// supports them.
+    fn on_peer_reset_stream_at_support(&mut self, is_supported: bool);
+
     // User APIs
 
     fn poll_request(
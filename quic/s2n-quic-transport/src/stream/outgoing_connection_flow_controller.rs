@@ -154,6 +154,15 @@ impl OutgoingConnectionFlowController {
         self.inner.borrow_mut().on_max_data(frame)
     }
 
+    /// This method is called by the client when the peer rejected early data
+    ///
+    /// The peer didn't receive any of the data that was sent, and the window the peer sent on a
+    /// previous connection no longer applies. No window is available until the peer's limits
+    /// for the current connection are known.
+    pub fn on_zero_rtt_rejected(&mut self) {
+        *self.inner.borrow_mut() = OutgoingConnectionFlowControllerImpl::new(VarInt::from_u8(0));
+    }
+
     /// This method is called when a packet delivery got acknowledged
     pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
         self.inner
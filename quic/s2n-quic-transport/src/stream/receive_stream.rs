@@ -19,7 +19,10 @@ use core::{
 use s2n_quic_core::{
     ack, application,
     buffer::{self, Reassembler},
-    frame::{stream::StreamRef, MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
+    frame::{
+        stream::StreamRef, MaxStreamData, ResetStream, ResetStreamAt, StopSending,
+        StreamDataBlocked,
+    },
     packet::number::PacketNumber,
     stream::{ops, StreamId},
     transport,
@@ -343,6 +346,10 @@ pub struct ReceiveStream {
     /// The handle of a task that is currently waiting on new incoming data, along with the low
     /// watermark value.
     pub(super) read_waiter: Option<(Waker, usize)>,
+    /// The error and reliable size of a `RESET_STREAM_AT` frame which was received
+    /// from the peer. The reset is applied once the application has consumed all
+    /// of the data up to the reliable size.
+    pub(super) reliable_reset: Option<(StreamError, VarInt)>,
     /// Whether the final state had already been observed by the application
     final_state_observed: bool,
     /// Marks the stream as detached from the application
@@ -374,6 +381,7 @@ impl ReceiveStream {
             ),
             stop_sending_sync: OnceSync::new(),
             read_waiter: None,
+            reliable_reset: None,
             final_state_observed: is_closed,
             detached: is_closed,
         };
@@ -596,6 +604,93 @@ impl ReceiveStream {
         Ok(())
     }
 
+    /// This is called when a `RESET_STREAM_AT` frame had been received for
+    /// this stream
+    pub fn on_reset_at(
+        &mut self,
+        frame: &ResetStreamAt,
+        events: &mut StreamEvents,
+    ) -> Result<(), transport::Error> {
+        let error = StreamError::stream_reset(frame.application_error_code.into());
+
+        // A RESET_STREAM_AT frame with a reliable size of 0 is equivalent to a RESET_STREAM
+        // frame. The stream is also reset immediately if the application isn't interested
+        // in the data anymore.
+        if frame.reliable_size == VarInt::from_u8(0)
+            || !matches!(self.state, ReceiveStreamState::Receiving)
+        {
+            self.init_reset(error, Some(frame.final_size), Some(frame.tag()))?;
+            self.stop_sending_sync.stop_sync();
+            self.wake(events);
+            return Ok(());
+        }
+
+        // The frame indicates the final size of the stream in the same way as a STREAM
+        // frame with the FIN bit would, so the buffer validates it against the data that
+        // has already been received.
+        if self.receive_buffer.final_size().is_none() {
+            self.flow_controller
+                .acquire_window_up_to(frame.final_size, Some(frame.tag()))?;
+        }
+
+        self.receive_buffer
+            .write_at_fin(frame.final_size, &[])
+            .map_err(|error| {
+                match error {
+                    buffer::Error::OutOfRange => transport::Error::FLOW_CONTROL_ERROR,
+                    buffer::Error::InvalidFin => transport::Error::FINAL_SIZE_ERROR,
+                    buffer::Error::ReaderError(_) => {
+                        unreachable!("reader is infallible")
+                    }
+                }
+                .with_reason("Final size in reset frame did not match previous final size")
+                .with_frame_type(frame.tag().into())
+            })?;
+
+        // We don't have to send MAX_STREAM_DATA frames anymore since the final size is known
+        self.flow_controller.stop_sync();
+
+        // A later frame can only reduce the amount of data which is delivered
+        let reliable_size = match self.reliable_reset {
+            Some((_, reliable_size)) => reliable_size.min(frame.reliable_size),
+            None => frame.reliable_size,
+        };
+        self.reliable_reset = Some((error, reliable_size));
+
+        self.try_apply_reliable_reset();
+
+        // Return the waker to wake up potential users of the stream
+        self.wake(events);
+
+        Ok(())
+    }
+
+    /// Resets the stream if a `RESET_STREAM_AT` frame was received and the application
+    /// has consumed all of the data up to the reliable size
+    fn try_apply_reliable_reset(&mut self) {
+        if let Some((error, reliable_size)) = self.reliable_reset {
+            if self.receive_buffer.consumed_len() >= reliable_size.as_u64() {
+                // The final size was already validated when the frame was received
+                let reset_result = self.init_reset(error, None, None);
+                debug_assert!(reset_result.is_ok());
+
+                // The reliable size was consumed so the reset has been applied and is no
+                // longer pending
+                self.reliable_reset = None;
+            }
+        }
+    }
+
+    /// Returns the amount of data that can still be consumed by the application
+    /// before a pending `RESET_STREAM_AT` is applied
+    fn remaining_reliable_len(&self) -> Option<usize> {
+        let (_, reliable_size) = self.reliable_reset?;
+        let remaining = reliable_size
+            .as_u64()
+            .saturating_sub(self.receive_buffer.consumed_len());
+        Some(usize::try_from(remaining).unwrap_or(usize::MAX))
+    }
+
     /// Starts the reset procedure if the Stream has not been in a RESET state
     /// before.
     fn init_reset(
@@ -809,6 +904,8 @@ impl ReceiveStream {
         // Do some state checks here. Only read data when the client is still
         // allowed to read (not reset).
 
+        self.try_apply_reliable_reset();
+
         let total_size = match self.state {
             ReceiveStreamState::Reset(error) => {
                 // The reset is now known to have been read by the client.
@@ -831,6 +928,12 @@ impl ReceiveStream {
             ReceiveStreamState::Receiving => self.receive_buffer.final_size(),
         };
 
+        // Data beyond the reliable size of a pending `RESET_STREAM_AT` isn't delivered
+        if let Some(remaining) = self.remaining_reliable_len() {
+            request.low_watermark = request.low_watermark.min(remaining);
+            request.high_watermark = request.high_watermark.min(remaining);
+        }
+
         let low_watermark = &mut request.low_watermark;
         let high_watermark = &mut request.high_watermark;
         let mut should_wake = false;
@@ -12,7 +12,7 @@ use core::{convert::TryFrom, task::Poll};
 use s2n_quic_core::{
     application::Error as ApplicationErrorCode,
     connection, endpoint,
-    frame::{Frame, MaxData, MaxStreamData, ResetStream, StopSending},
+    frame::{Frame, MaxData, MaxStreamData, ResetStream, ResetStreamAt, StopSending},
     stream::{ops, StreamError, StreamType},
     transport::Error as TransportError,
     varint::VarInt,
@@ -612,6 +612,100 @@ fn reset_has_no_impact_if_all_data_had_been_received() {
     }
 }
 
+#[test]
+fn reset_at_delivers_data_up_to_reliable_size() {
+    let mut test_env = setup_receive_only_test_env();
+
+    test_env.feed_data(VarInt::from_u32(0), 800);
+
+    let reset_frame = ResetStreamAt {
+        stream_id: test_env.stream.stream_id.into(),
+        application_error_code: VarInt::from_u8(1),
+        final_size: VarInt::from_u32(1000),
+        reliable_size: VarInt::from_u32(400),
+    };
+
+    let mut events = StreamEvents::new();
+    assert!(test_env
+        .stream
+        .on_reset_at(&reset_frame, &mut events)
+        .is_ok());
+
+    // Only the data up to the reliable size is returned to the application
+    let mut received = 0;
+    let result = loop {
+        match test_env.poll_pop() {
+            Poll::Ready(Ok(Some(data))) => received += data.len(),
+            result => break result,
+        }
+    };
+    assert_eq!(400, received);
+    assert_matches!(result, Poll::Ready(Err(StreamError::StreamReset { .. })));
+    assert_eq!(
+        stream_interests(&["fin"]),
+        test_env.stream.get_stream_interests()
+    );
+}
+
+#[test]
+fn reset_at_waits_for_reliable_data() {
+    let mut test_env = setup_receive_only_test_env();
+
+    let reset_frame = ResetStreamAt {
+        stream_id: test_env.stream.stream_id.into(),
+        application_error_code: VarInt::from_u8(1),
+        final_size: VarInt::from_u32(1000),
+        reliable_size: VarInt::from_u32(400),
+    };
+
+    let mut events = StreamEvents::new();
+    assert!(test_env
+        .stream
+        .on_reset_at(&reset_frame, &mut events)
+        .is_ok());
+
+    // The reset is not surfaced before the reliable data was received
+    test_env.assert_no_read_data();
+
+    test_env.feed_data(VarInt::from_u32(0), 400);
+    assert_eq!(
+        Poll::Ready(Ok(Some(Bytes::from(vec![0u8; 400])))),
+        test_env.poll_pop()
+    );
+    test_env.assert_pop_error();
+}
+
+#[test]
+fn reset_at_errors_if_final_size_contradicts_fin_size() {
+    for final_size in &[400, 799, 801] {
+        let mut test_env = setup_receive_only_test_env();
+
+        test_env.feed_data(VarInt::from_u32(0), 800);
+        let mut events = StreamEvents::new();
+        assert!(test_env
+            .stream
+            .on_data(
+                &stream_data(test_env.stream.stream_id, VarInt::from_u32(800), &[], true),
+                &mut events
+            )
+            .is_ok());
+
+        let reset_frame = ResetStreamAt {
+            stream_id: test_env.stream.stream_id.into(),
+            application_error_code: VarInt::from_u8(0),
+            final_size: VarInt::new(*final_size).unwrap(),
+            reliable_size: VarInt::from_u32(200),
+        };
+
+        let mut events = StreamEvents::new();
+        assert_is_transport_error(
+            test_env.stream.on_reset_at(&reset_frame, &mut events),
+            TransportError::FINAL_SIZE_ERROR,
+        );
+        events.wake_all();
+    }
+}
+
 #[test]
 fn exceed_stream_flow_control_window() {
     let mut test_env = setup_receive_only_test_env();
@@ -24,9 +24,9 @@ use core::{
 };
 use s2n_quic_core::{
     ack, application,
-    frame::{MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
+    frame::{MaxStreamData, ResetStream, ResetStreamAt, StopSending, StreamDataBlocked},
     packet::number::PacketNumber,
-    stream::{ops, StreamId},
+    stream::{ops, Priority, StreamId},
     time::{timer, Timestamp},
     transport,
     varint::VarInt,
@@ -79,6 +79,10 @@ pub(super) enum SendStreamState {
     /// peer. The flag indicates whether the reset state had already been
     /// observed by the user.
     ResetSent(StreamError),
+    /// The connection was reset with a reliable size. The data up to the
+    /// reliable size is still transmitted, and the reset is complete once
+    /// both the data and the reset have been acknowledged by the peer.
+    ResetAtSent(StreamError, VarInt),
     /// The connection was reset. The reset was acknowledged by the
     /// peer. The flag indicates whether the reset state had already been
     /// observed by the user.
@@ -95,6 +99,11 @@ pub struct OutgoingResetData {
     final_size: VarInt,
     /// The error code which should get transmitted in the RESET frame
     application_error_code: application::Error,
+    /// The amount of data which is delivered to the peer in spite of the reset.
+    ///
+    /// If this is set, a `RESET_STREAM_AT` frame is transmitted instead of a
+    /// `RESET_STREAM` frame.
+    reliable_size: Option<VarInt>,
 }
 
 /// Writes the `RESET` frames based on the streams flow control window.
@@ -108,6 +117,15 @@ impl ValueToFrameWriter<OutgoingResetData> for ResetStreamToFrameWriter {
         stream_id: StreamId,
         context: &mut W,
     ) -> Option<PacketNumber> {
+        if let Some(reliable_size) = value.reliable_size {
+            return context.write_frame(&ResetStreamAt {
+                stream_id: stream_id.into(),
+                application_error_code: value.application_error_code.into(),
+                final_size: value.final_size,
+                reliable_size,
+            });
+        }
+
         context.write_frame(&ResetStream {
             stream_id: stream_id.into(),
             application_error_code: value.application_error_code.into(),
@@ -261,6 +279,15 @@ impl StreamFlowController {
         }
     }
 
+    /// Stops acquiring connection window beyond the provided offset.
+    ///
+    /// The window which was already acquired for the Stream is retained.
+    pub fn limit_connection_window(&mut self, offset: VarInt) {
+        self.highest_requested_connection_flow_control_window =
+            core::cmp::max(offset, self.acquired_connection_flow_controller_window);
+        self.try_acquire_connection_window();
+    }
+
     /// Returns the window/offset up to which data can be written
     fn available_window(&self) -> VarInt {
         core::cmp::min(
@@ -457,6 +484,10 @@ pub struct SendStream {
     final_state_observed: bool,
     /// Marks the stream as detached from the application
     detached: bool,
+    /// The scheduling priority of the stream
+    priority: Priority,
+    /// Whether the peer supports receiving `RESET_STREAM_AT` frames
+    reset_stream_at_supported: bool,
 }
 
 impl SendStream {
@@ -465,6 +496,7 @@ impl SendStream {
         is_closed: bool,
         initial_window: VarInt,
         max_buffer_capacity: u32,
+        reset_stream_at_supported: bool,
     ) -> SendStream {
         // If the stream is created in closed state directly move into the
         // terminal state.
@@ -485,6 +517,8 @@ impl SendStream {
             write_waiter: None,
             final_state_observed: is_closed,
             detached: is_closed,
+            priority: Priority::default(),
+            reset_stream_at_supported,
         };
 
         if is_closed {
@@ -509,7 +543,7 @@ impl SendStream {
         // The reason for this is that we allow users to enqueue more data than
         // the maximum flow control window.
 
-        if let SendStreamState::Sending = self.state {
+        if let SendStreamState::Sending | SendStreamState::ResetAtSent(..) = self.state {
             self.data_sender
                 .flow_controller_mut()
                 .set_max_stream_data(frame.maximum_stream_data);
@@ -629,6 +663,21 @@ impl SendStream {
                     should_wake = true;
                 }
             }
+            SendStreamState::ResetAtSent(error, reliable_size) => {
+                let _ = self.reset_sync.on_packet_ack(ack_set);
+                self.try_request_reset_at(error, reliable_size);
+
+                // The reset is complete once the peer has acknowledged both the
+                // reset and all of the data up to the reliable size
+                if self.reset_sync.is_delivered()
+                    && matches!(self.data_sender.state(), data_sender::State::Cancelled(_))
+                {
+                    self.state = SendStreamState::ResetAcknowledged(error);
+
+                    // notify the waiter that the stream is finalized
+                    should_wake = true;
+                }
+            }
             _ => {}
         }
 
@@ -654,6 +703,14 @@ impl SendStream {
     ) -> Result<(), OnTransmitError> {
         self.reset_sync.on_transmit(stream_id, context)?;
         self.data_sender.on_transmit(stream_id.into(), context)?;
+
+        if let SendStreamState::ResetAtSent(error, reliable_size) = self.state {
+            // Transmitting the reliable data may have acquired the window the
+            // reset frame was waiting on
+            self.try_request_reset_at(error, reliable_size);
+            self.reset_sync.on_transmit(stream_id, context)?;
+        }
+
         self.data_sender
             .flow_controller_mut()
             .on_transmit(stream_id, context)
@@ -687,7 +744,7 @@ impl SendStream {
 
     pub fn on_flush(&mut self, error: StreamError, events: &mut StreamEvents) {
         match self.data_sender.state() {
-            data_sender::State::Finishing(_) => {
+            data_sender::State::Finishing(_) | data_sender::State::Resetting(_) => {
                 // wait until the data sender is done sending
             }
             _ => {
@@ -701,10 +758,19 @@ impl SendStream {
     pub fn on_connection_window_available(&mut self) {
         // Outstanding flow control requests are only fulfilled if the Stream
         // was still trying to send data.
-        if let SendStreamState::Sending = self.state {
-            self.data_sender
-                .flow_controller_mut()
-                .try_acquire_connection_window();
+        match self.state {
+            SendStreamState::Sending => {
+                self.data_sender
+                    .flow_controller_mut()
+                    .try_acquire_connection_window();
+            }
+            SendStreamState::ResetAtSent(error, reliable_size) => {
+                self.data_sender
+                    .flow_controller_mut()
+                    .try_acquire_connection_window();
+                self.try_request_reset_at(error, reliable_size);
+            }
+            _ => {}
         }
     }
 
@@ -755,6 +821,10 @@ impl SendStream {
             self.detach();
         }
 
+        if let Some(priority) = request.priority {
+            self.priority = priority;
+        }
+
         macro_rules! store_waker {
             ($should_flush:expr) => {
                 // Store the waker, in order to be able to wakeup the caller
@@ -773,11 +843,14 @@ impl SendStream {
         }
 
         if let Some(error_code) = request.reset {
+            let error = StreamError::stream_reset(error_code);
+
             // reset is a best effort operation so ignore the result
-            let _ = self.init_reset(
-                ResetSource::LocalApplication,
-                StreamError::stream_reset(error_code),
-            );
+            let _ = if let Some(reliable_size) = request.reliable_size {
+                self.init_reset_at(error, reliable_size)
+            } else {
+                self.init_reset(ResetSource::LocalApplication, error)
+            };
 
             // mark the stream as resetting
             response.status = ops::Status::Resetting;
@@ -801,7 +874,9 @@ impl SendStream {
         // Do some state checks here. Only write data when the client is still
         // allowed to write (not reset).
         match self.state {
-            SendStreamState::ResetSent(error) | SendStreamState::ResetAcknowledged(error) => {
+            SendStreamState::ResetSent(error)
+            | SendStreamState::ResetAtSent(error, _)
+            | SendStreamState::ResetAcknowledged(error) => {
                 // The reset is now known to have been read by the client.
                 self.final_state_observed = true;
                 self.write_waiter = None;
@@ -894,6 +969,9 @@ impl SendStream {
             data_sender::State::Finished => {
                 response.status = ops::Status::Finished;
             }
+            data_sender::State::Resetting(_) => {
+                response.status = ops::Status::Resetting;
+            }
             data_sender::State::Cancelled(error) => {
                 // TODO determine if the peer has acknowledged the reset
                 response.status = ops::Status::Reset(error);
@@ -955,6 +1033,12 @@ impl SendStream {
             SendStreamState::ResetSent(_) | SendStreamState::ResetAcknowledged(_) => {
                 return InitResetResult::ResetNotNecessary
             }
+            // The delivery of the reliable data can only be aborted by the
+            // connection
+            SendStreamState::ResetAtSent(..) if !reason.is_internal() => {
+                return InitResetResult::ResetNotNecessary
+            }
+            SendStreamState::ResetAtSent(..) => {}
             SendStreamState::Sending
                 if self.data_sender.state() == data_sender::State::Finished =>
             {
@@ -1003,6 +1087,7 @@ impl SendStream {
                         .data_sender
                         .flow_controller()
                         .acquired_connection_flow_controller_window(),
+                    reliable_size: None,
                 });
             }
             (false, _) => {
@@ -1015,6 +1100,81 @@ impl SendStream {
 
         InitResetResult::ResetInitiated
     }
+
+    /// Starts the reset procedure while still delivering the data up to
+    /// `reliable_size` to the peer.
+    ///
+    /// The method will return whether calling this method caused the `Stream`
+    /// to enter a RESET state.
+    fn init_reset_at(&mut self, error: StreamError, reliable_size: VarInt) -> InitResetResult {
+        // Data which was never enqueued can't be delivered
+        let reliable_size = reliable_size.min(self.data_sender.total_enqueued_len());
+
+        if reliable_size == VarInt::from_u8(0) {
+            return self.init_reset(ResetSource::LocalApplication, error);
+        }
+
+        match self.state {
+            SendStreamState::Sending
+                if self.data_sender.state() == data_sender::State::Finished =>
+            {
+                return InitResetResult::ResetNotNecessary
+            }
+            SendStreamState::Sending => {}
+            _ => return InitResetResult::ResetNotNecessary,
+        }
+
+        self.state = SendStreamState::ResetAtSent(error, reliable_size);
+
+        // The application initiated the reset so it is aware about the final state
+        self.final_state_observed = true;
+
+        // Drop any data beyond the reliable size and stop acquiring connection
+        // window for it
+        self.data_sender.stop_sending_at(reliable_size, error);
+        self.data_sender
+            .flow_controller_mut()
+            .limit_connection_window(reliable_size);
+
+        self.try_request_reset_at(error, reliable_size);
+
+        InitResetResult::ResetInitiated
+    }
+
+    /// Requests the delivery of the reset frame for a stream in the
+    /// `ResetAtSent` state, once the frame can be sent.
+    fn try_request_reset_at(&mut self, error: StreamError, reliable_size: VarInt) {
+        let application_error_code = match error {
+            StreamError::StreamReset { error, .. } => error,
+            _ => unreachable!("Non internal reasons must be accommodated by an error code"),
+        };
+
+        let final_size = self
+            .data_sender
+            .flow_controller()
+            .acquired_connection_flow_controller_window();
+
+        if self.reset_stream_at_supported {
+            // The final size can't be smaller than the reliable size, so wait
+            // until the window for the reliable data has been acquired
+            if final_size >= reliable_size {
+                self.reset_sync.request_delivery(OutgoingResetData {
+                    application_error_code,
+                    final_size,
+                    reliable_size: Some(reliable_size),
+                });
+            }
+        } else if matches!(self.data_sender.state(), data_sender::State::Cancelled(_)) {
+            // The peer doesn't support RESET_STREAM_AT frames. The stream is reset
+            // with a RESET_STREAM frame once all of the reliable data has been
+            // acknowledged instead.
+            self.reset_sync.request_delivery(OutgoingResetData {
+                application_error_code,
+                final_size,
+                reliable_size: None,
+            });
+        }
+    }
 }
 
 impl timer::Provider for SendStream {
@@ -1028,6 +1188,8 @@ impl timer::Provider for SendStream {
 impl StreamInterestProvider for SendStream {
     #[inline]
     fn stream_interests(&self, interests: &mut StreamInterests) {
+        interests.priority = self.priority;
+
         match self.state {
             //= https://www.rfc-editor.org/rfc/rfc9000#section-3.3
             //# A sender MUST NOT send any of these frames from a terminal state
@@ -89,6 +89,8 @@ enum Instruction {
     Finish(bool),
     /// Initiates a `RESET` with the given error code
     Reset(ApplicationErrorCode, bool),
+    /// Initiates a `RESET_STREAM_AT` with the given error code and reliable size
+    ResetAt(ApplicationErrorCode, VarInt, bool),
     /// Ingest a `MAX_DATA` frame which indicates the given window.
     SetMaxData(VarInt),
     /// Ingest a `MAX_STREAM_DATA` frame which indicates the given window.
@@ -112,6 +114,13 @@ enum Instruction {
     ),
     /// Checks whether a reset frame is transmitted
     CheckResetTx(ApplicationErrorCode, PacketNumber, VarInt),
+    /// Checks whether a reset_stream_at frame is transmitted
+    CheckResetAtTx(
+        ApplicationErrorCode,
+        PacketNumber,
+        VarInt, // final size
+        VarInt, // reliable size
+    ),
     /// Checks whether a Stream is interested in the given interactions
     CheckInterests(StreamInterests),
     /// Checks that no outgoing data is written in an `on_transmit` call and
@@ -155,6 +164,14 @@ fn execute_instructions(test_env: &mut TestEnvironment, instructions: &[Instruct
 
                 assert_eq!(*expect_success, result.is_ok(), "Unexpected reset result");
             }
+            Instruction::ResetAt(error_code, reliable_size, expect_success) => {
+                let result = test_env.stream.poll_request(
+                    ops::Request::default().reset_at(*error_code, *reliable_size),
+                    None,
+                );
+
+                assert_eq!(*expect_success, result.is_ok(), "Unexpected reset result");
+            }
             Instruction::SetMaxData(max_data) => {
                 let was_waiting_for_connection_window = test_env
                     .stream
@@ -243,6 +260,19 @@ fn execute_instructions(test_env: &mut TestEnvironment, instructions: &[Instruct
                     *expected_final_size,
                 );
             }
+            Instruction::CheckResetAtTx(
+                expected_error_code,
+                expected_packet_number,
+                expected_final_size,
+                expected_reliable_size,
+            ) => {
+                test_env.assert_write_reset_at_frame(
+                    *expected_error_code,
+                    *expected_packet_number,
+                    *expected_final_size,
+                    *expected_reliable_size,
+                );
+            }
             Instruction::CheckInterests(expected_interests) => {
                 assert_eq!(*expected_interests, test_env.stream.get_stream_interests());
             }
@@ -2518,6 +2548,100 @@ fn stream_does_not_try_to_acquire_connection_flow_control_credits_after_reset()
     }
 }
 
+#[test]
+fn reset_at_delivers_reliable_data_and_sends_reset_stream_at() {
+    let test_env_config = TestEnvironmentConfig {
+        max_send_buffer_size: 1000,
+        stream_id: StreamId::initial(endpoint::Type::Client, StreamType::Unidirectional),
+        local_endpoint_type: endpoint::Type::Client,
+        ..Default::default()
+    };
+    let mut test_env = setup_stream_test_env_with_config(test_env_config);
+
+    let reset_error_code = ApplicationErrorCode::new(0x3333_4444).unwrap();
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::EnqueueData(VarInt::from_u32(0), 500, true),
+            Instruction::CheckDataTx(VarInt::from_u32(0), 500, false, false, pn(0)),
+            Instruction::ResetAt(reset_error_code, VarInt::from_u32(200), true),
+            Instruction::CheckInterests(stream_interests(&["ack", "tx"])),
+            // The final size covers everything that was already sent
+            Instruction::CheckResetAtTx(
+                reset_error_code,
+                pn(1),
+                VarInt::from_u32(500),
+                VarInt::from_u32(200),
+            ),
+            Instruction::CheckInterests(stream_interests(&["ack"])),
+            // Only the data up to the reliable size gets retransmitted
+            Instruction::NackPacket(pn(0)),
+            Instruction::CheckInterests(stream_interests(&["ack", "lost"])),
+            Instruction::CheckDataTx(VarInt::from_u32(0), 200, false, false, pn(2)),
+            Instruction::CheckNoTx,
+            Instruction::AckPacket(pn(1), ExpectWakeup(None)),
+            // The stream is not finalized before the reliable data is acknowledged
+            Instruction::CheckInterests(stream_interests(&["ack"])),
+            Instruction::AckPacket(pn(2), ExpectWakeup(None)),
+            Instruction::CheckInterests(stream_interests(&["fin"])),
+        ],
+    );
+
+    assert_matches!(
+        test_env.poll_push(Bytes::from_static(b"1")),
+        Poll::Ready(Err(StreamError::StreamReset { .. })),
+    );
+}
+
+#[test]
+fn reset_at_falls_back_to_reset_stream_if_unsupported() {
+    let test_env_config = TestEnvironmentConfig {
+        max_send_buffer_size: 1000,
+        stream_id: StreamId::initial(endpoint::Type::Client, StreamType::Unidirectional),
+        local_endpoint_type: endpoint::Type::Client,
+        reset_stream_at_supported: false,
+        ..Default::default()
+    };
+    let mut test_env = setup_stream_test_env_with_config(test_env_config);
+
+    let reset_error_code = ApplicationErrorCode::new(0x3333_4444).unwrap();
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::EnqueueData(VarInt::from_u32(0), 500, true),
+            Instruction::CheckDataTx(VarInt::from_u32(0), 500, false, false, pn(0)),
+            Instruction::ResetAt(reset_error_code, VarInt::from_u32(200), true),
+            // The RESET_STREAM frame is held back until the reliable data was delivered
+            Instruction::CheckNoTx,
+            Instruction::CheckInterests(stream_interests(&["ack"])),
+            Instruction::AckPacket(pn(0), ExpectWakeup(None)),
+            Instruction::CheckInterests(stream_interests(&["tx"])),
+            Instruction::CheckResetTx(reset_error_code, pn(1), VarInt::from_u32(500)),
+            Instruction::AckPacket(pn(1), ExpectWakeup(None)),
+            Instruction::CheckInterests(stream_interests(&["fin"])),
+        ],
+    );
+}
+
+#[test]
+fn reset_at_without_reliable_data_sends_reset_stream() {
+    let mut test_env = setup_send_only_test_env();
+
+    let reset_error_code = ApplicationErrorCode::new(0x3333_4444).unwrap();
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::ResetAt(reset_error_code, VarInt::from_u32(100), true),
+            Instruction::CheckResetTx(reset_error_code, pn(0), VarInt::from_u32(0)),
+            Instruction::AckPacket(pn(0), ExpectWakeup(None)),
+            Instruction::CheckInterests(stream_interests(&["fin"])),
+        ],
+    );
+}
+
 #[test]
 fn stream_reports_stream_size_based_on_acquired_connection_window() {
     let test_env_config = TestEnvironmentConfig {
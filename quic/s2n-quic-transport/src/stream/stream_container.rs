@@ -13,11 +13,17 @@ use crate::{
     transmission,
 };
 use alloc::rc::Rc;
-use core::{cell::RefCell, ops::Deref};
+use core::{
+    cell::{Cell, RefCell},
+    ops::Deref,
+};
 use intrusive_collections::{
     intrusive_adapter, KeyAdapter, LinkedList, LinkedListLink, RBTree, RBTreeLink,
 };
-use s2n_quic_core::{stream::StreamId, time::timer};
+use s2n_quic_core::{
+    stream::{Priority, StreamId},
+    time::timer,
+};
 
 // Intrusive list adapter for managing the list of `done` streams
 intrusive_adapter!(DoneStreamsAdapter<S> = Rc<StreamNode<S>>: StreamNode<S> {
@@ -79,6 +85,8 @@ struct StreamNode<S> {
     waiting_for_connection_flow_control_credits_link: LinkedListLink,
     /// Allows the Stream to be part of the `waiting_for_stream_flow_control_credits` collection
     waiting_for_stream_flow_control_credits_link: LinkedListLink,
+    /// The priority with which the Stream is currently placed in the transmission lists
+    priority: Cell<Priority>,
 }
 
 impl<S> StreamNode<S> {
@@ -93,6 +101,7 @@ impl<S> StreamNode<S> {
             waiting_for_retransmission_link: LinkedListLink::new(),
             waiting_for_connection_flow_control_credits_link: LinkedListLink::new(),
             waiting_for_stream_flow_control_credits_link: LinkedListLink::new(),
+            priority: Cell::new(Priority::default()),
         }
     }
 }
@@ -123,6 +132,27 @@ unsafe fn stream_node_rc_from_ref<S>(stream_node: &StreamNode<S>) -> Rc<StreamNo
     temp_node_ptr.deref().clone()
 }
 
+/// Inserts a node into a list which is ordered by stream urgency.
+///
+/// The node is placed behind all other nodes with the same or a lower urgency value. Since
+/// most streams share the same priority, searching from the back of the list is usually
+/// a constant time operation.
+macro_rules! push_by_priority {
+    ($list:expr, $node:expr) => {{
+        let node = $node;
+        let urgency = node.priority.get().urgency();
+        let mut cursor = $list.back_mut();
+        while cursor
+            .get()
+            .map_or(false, |other| other.priority.get().urgency() > urgency)
+        {
+            cursor.move_prev();
+        }
+        // inserting after the null object places the node at the front of the list
+        cursor.insert_after(node);
+    }};
+}
+
 /// Contains all secondary lists of Streams.
 ///
 /// A Stream can be a member in any of those, in addition to being a member of
@@ -133,9 +163,9 @@ struct InterestLists<S> {
     /// Streams which are waiting for packet acknowledgements and
     /// packet loss notifications
     waiting_for_frame_delivery: LinkedList<WaitingForFrameDeliveryAdapter<S>>,
-    /// Streams which need to transmit data
+    /// Streams which need to transmit data, ordered by their priority
     waiting_for_transmission: LinkedList<WaitingForTransmissionAdapter<S>>,
-    /// Streams which need to transmit data
+    /// Streams which need to retransmit data, ordered by their priority
     waiting_for_retransmission: LinkedList<WaitingForRetransmissionAdapter<S>>,
     /// Streams which are blocked on transmission due to waiting on the
     /// connection flow control window to increase
@@ -180,9 +210,19 @@ impl<S: StreamTrait> InterestLists<S> {
 
         macro_rules! sync_interests {
             ($interest:expr, $link_name:ident, $list_name:ident) => {
+                sync_interests!($interest, $link_name, $list_name, {
+                    self.$list_name.push_back(node.clone())
+                })
+            };
+            ($interest:expr, $link_name:ident, $list_name:ident, by_priority) => {
+                sync_interests!($interest, $link_name, $list_name, {
+                    push_by_priority!(self.$list_name, node.clone())
+                })
+            };
+            ($interest:expr, $link_name:ident, $list_name:ident, $insert:block) => {
                 if $interest != node.$link_name.is_linked() {
                     if $interest {
-                        self.$list_name.push_back(node.clone());
+                        $insert
                     } else {
                         // Safety: We know that the node is only ever part of this list.
                         // While elements are in temporary lists, they always get unlinked
@@ -198,6 +238,29 @@ impl<S: StreamTrait> InterestLists<S> {
             };
         }
 
+        if node.priority.get() != interests.priority {
+            // Remove the node from the ordered lists so it gets inserted at
+            // the position of the new priority below
+            macro_rules! unlink {
+                ($link_name:ident, $list_name:ident) => {
+                    if node.$link_name.is_linked() {
+                        // Safety: We know that the node is only ever part of this list.
+                        // While elements are in temporary lists, they always get unlinked
+                        // from those temporary lists while their interest is updated.
+                        let mut cursor = unsafe {
+                            self.$list_name
+                                .cursor_mut_from_ptr(node.deref() as *const StreamNode<S>)
+                        };
+                        cursor.remove();
+                    }
+                };
+            }
+
+            unlink!(waiting_for_transmission_link, waiting_for_transmission);
+            unlink!(waiting_for_retransmission_link, waiting_for_retransmission);
+            node.priority.set(interests.priority);
+        }
+
         sync_interests!(
             interests.delivery_notifications,
             waiting_for_frame_delivery_link,
@@ -206,12 +269,14 @@ impl<S: StreamTrait> InterestLists<S> {
         sync_interests!(
             matches!(interests.transmission, transmission::Interest::NewData),
             waiting_for_transmission_link,
-            waiting_for_transmission
+            waiting_for_transmission,
+            by_priority
         );
         sync_interests!(
             matches!(interests.transmission, transmission::Interest::LostData),
             waiting_for_retransmission_link,
-            waiting_for_retransmission
+            waiting_for_retransmission,
+            by_priority
         );
         sync_interests!(
             interests.connection_flow_control_credits,
@@ -331,10 +396,15 @@ macro_rules! send_on_transmission_list {
         $counter:ident,
         $interest_type:pat,
     ) => {
-        // Head node gets pushed to the back of the list if it has run out of sending credits
+        // Head node yields to the other streams with the same urgency if it has run out of
+        // sending credits. Non-incremental streams keep sending until they are done.
         if $sel.interest_lists.$counter >= $sel.interest_lists.transmission_limit {
             if let Some(node) = $sel.interest_lists.$list_name.pop_front() {
-                $sel.interest_lists.$list_name.push_back(node);
+                if node.priority.get().is_incremental() {
+                    push_by_priority!($sel.interest_lists.$list_name, node);
+                } else {
+                    $sel.interest_lists.$list_name.push_front(node);
+                }
                 $sel.interest_lists.$counter = 0;
             }
         }
@@ -16,7 +16,10 @@ use crate::{
 use core::{task::Context, time::Duration};
 use s2n_quic_core::{
     ack, endpoint,
-    frame::{stream::StreamRef, MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
+    frame::{
+        stream::StreamRef, MaxStreamData, ResetStream, ResetStreamAt, StopSending,
+        StreamDataBlocked,
+    },
     stream::{ops, StreamId},
     time::{timer, Timestamp},
     transport,
@@ -42,6 +45,8 @@ pub struct StreamConfig {
     pub initial_send_window: VarInt,
     /// The maximum buffered amount of data on the sending side
     pub max_send_buffer_size: u32,
+    /// Whether the peer supports receiving `RESET_STREAM_AT` frames
+    pub reset_stream_at_supported: bool,
 }
 
 /// A trait which represents an internally used `Stream`
@@ -78,6 +83,14 @@ pub trait StreamTrait: StreamInterestProvider + timer::Provider + core::fmt::Deb
         events: &mut StreamEvents,
     ) -> Result<(), transport::Error>;
 
+    /// This is called when a `RESET_STREAM_AT` frame had been received for
+    /// this stream
+    fn on_reset_at(
+        &mut self,
+        frame: &ResetStreamAt,
+        events: &mut StreamEvents,
+    ) -> Result<(), transport::Error>;
+
     /// This is called when a `MAX_STREAM_DATA` frame had been received for
     /// this stream
     fn on_max_stream_data(
@@ -211,6 +224,7 @@ impl StreamTrait for StreamImpl {
                 send_is_closed,
                 config.initial_send_window,
                 config.max_send_buffer_size,
+                config.reset_stream_at_supported,
             ),
         }
     }
@@ -249,6 +263,15 @@ impl StreamTrait for StreamImpl {
         self.receive_stream.on_reset(frame, events)
     }
 
+    #[inline]
+    fn on_reset_at(
+        &mut self,
+        frame: &ResetStreamAt,
+        events: &mut StreamEvents,
+    ) -> Result<(), transport::Error> {
+        self.receive_stream.on_reset_at(frame, events)
+    }
+
     #[inline]
     fn on_max_stream_data(
         &mut self,
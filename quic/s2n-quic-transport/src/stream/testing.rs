@@ -17,7 +17,7 @@ use futures_test::task::{new_count_waker, AwokenCount};
 use s2n_quic_core::{
     application::Error as ApplicationErrorCode,
     endpoint,
-    frame::{stream::Stream as StreamFrame, Frame, ResetStream, StreamDataBlocked},
+    frame::{stream::Stream as StreamFrame, Frame, ResetStream, ResetStreamAt, StreamDataBlocked},
     packet::number::{PacketNumber, PacketNumberSpace},
     stream::{ops, StreamError, StreamId, StreamType},
     time::{clock::testing as time, Timestamp},
@@ -355,6 +355,31 @@ impl TestEnvironment {
         );
     }
 
+    /// Asserts that a RESET_STREAM_AT frame was transmitted
+    pub fn assert_write_reset_at_frame(
+        &mut self,
+        expected_error_code: ApplicationErrorCode,
+        expected_packet_number: PacketNumber,
+        expected_final_size: VarInt,
+        expected_reliable_size: VarInt,
+    ) {
+        let mut sent_frame = self.transmit().expect("no frame was written");
+        assert_eq!(
+            expected_packet_number, sent_frame.packet_nr,
+            "packet number mismatch"
+        );
+
+        assert_eq!(
+            Frame::ResetStreamAt(ResetStreamAt {
+                stream_id: self.stream.stream_id.into(),
+                application_error_code: expected_error_code.into(),
+                final_size: expected_final_size,
+                reliable_size: expected_reliable_size,
+            }),
+            sent_frame.as_frame()
+        );
+    }
+
     /// Asserts that a STREAM_DATA_BLOCKED frame was transmitted
     pub fn assert_write_stream_data_blocked_frame(
         &mut self,
@@ -486,6 +511,7 @@ pub struct TestEnvironmentConfig {
     pub transmission_constraint: transmission::Constraint,
     pub local_endpoint_type: endpoint::Type,
     pub max_packet_size: Option<usize>,
+    pub reset_stream_at_supported: bool,
 }
 
 impl Default for TestEnvironmentConfig {
@@ -514,6 +540,7 @@ impl TestEnvironmentConfig {
             max_send_buffer_size: TestEnvironment::DEFAULT_MAX_SEND_BUFFER_SIZE,
             transmission_constraint: transmission::Constraint::None,
             max_packet_size: None,
+            reset_stream_at_supported: true,
         }
     }
 }
@@ -544,6 +571,7 @@ pub fn setup_stream_test_env_with_config(config: TestEnvironmentConfig) -> TestE
         desired_flow_control_window: config.desired_flow_control_window,
         initial_send_window: VarInt::new(config.initial_send_window).unwrap(),
         max_send_buffer_size: config.max_send_buffer_size as u32,
+        reset_stream_at_supported: config.reset_stream_at_supported,
     });
 
     let (waker, wake_counter) = new_count_waker();
@@ -31,6 +31,11 @@ pub enum State {
     /// All outgoing data including the FIN flag had been acknowledged.
     /// The Stream is thereby finalized.
     Finished,
+    /// The Stream was reset with a reliable size. New outgoing data is no
+    /// longer accepted. The data up to the reliable size is still transmitted
+    /// until it has been acknowledged, after which the `Cancelled` state is
+    /// entered.
+    Resetting(StreamError),
     /// Sending data was cancelled due to a Stream RESET.
     Cancelled(StreamError),
 }
@@ -190,6 +195,54 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
         self.check_integrity();
     }
 
+    /// Stops sending outgoing data at or beyond `reliable_size`.
+    ///
+    /// This is a one-way operation - sending can not be resumed.
+    ///
+    /// The data up to `reliable_size` is still transmitted, and retransmitted
+    /// if lost, until it has been acknowledged by the peer. Once that happens
+    /// the sender enters the `Cancelled` state.
+    pub fn stop_sending_at(&mut self, reliable_size: VarInt, error: StreamError) {
+        if !matches!(self.state, State::Sending | State::Finishing(_)) {
+            return;
+        }
+
+        // Data which has already been acknowledged can't be taken back
+        let reliable_size = reliable_size.max(self.buffer.head());
+
+        if reliable_size < self.buffer.total_len() {
+            self.buffer.truncate(reliable_size);
+            self.pending
+                .remove(reliable_size..=VarInt::MAX)
+                .expect("pending should not have a limit");
+            self.lost
+                .remove(reliable_size..=VarInt::MAX)
+                .expect("lost should not have a limit");
+            self.transmission_offset = self.transmission_offset.min(reliable_size);
+
+            if self.pending.is_empty() {
+                // The remaining transmissions only contain data beyond the reliable size
+                self.transmissions.clear();
+            }
+        }
+
+        self.state = State::Resetting(error);
+        self.on_reliable_data_acknowledged();
+        self.check_integrity();
+    }
+
+    /// Enters the `Cancelled` state once all of the data up to the reliable size has been
+    /// acknowledged
+    fn on_reliable_data_acknowledged(&mut self) {
+        if let State::Resetting(error) = self.state {
+            if self.is_idle() {
+                self.state = State::Cancelled(error);
+                self.transmissions.finish();
+                self.buffer.release_all();
+            }
+        }
+    }
+
     /// Returns the amount of bytes that have ever been enqueued for writing on
     /// this Stream. This equals the offset of the highest enqueued byte + 1.
     pub fn total_enqueued_len(&self) -> VarInt {
@@ -326,6 +379,8 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
             self.buffer.release_all();
         }
 
+        self.on_reliable_data_acknowledged();
+
         self.check_integrity();
     }
 
@@ -758,4 +813,55 @@ mod tests {
                 check_model(events, id);
             });
     }
+
+    #[test]
+    fn stop_sending_at_test() {
+        let mut sender: DataSender<_, writer::Stream> = DataSender::new(
+            TestFlowController {
+                max_offset: VarInt::MAX,
+                is_blocked: false,
+            },
+            u32::MAX,
+        );
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        let mut context = MockWriteContext {
+            current_time: time::now(),
+            frame_buffer: &mut frame_buffer,
+            transmission_constraint: transmission::Constraint::None,
+            transmission_mode: transmission::Mode::Normal,
+            endpoint: endpoint::Type::Server,
+        };
+        let id = VarInt::from_u8(4);
+        let error = StreamError::stream_reset(VarInt::from_u8(1).into());
+
+        sender.push(Bytes::from_static(&[1; 100]));
+        sender.on_transmit(id, &mut context).unwrap();
+        context.frame_buffer.flush();
+        let packet = context.frame_buffer.frames[0].packet_nr;
+
+        sender.stop_sending_at(VarInt::from_u8(40), error);
+        assert_eq!(sender.state(), State::Resetting(error));
+        assert_eq!(sender.total_enqueued_len(), VarInt::from_u8(40));
+        assert!(!sender.has_transmission_interest());
+
+        // only the reliable part of the lost data is retransmitted
+        sender.on_packet_loss(&packet);
+        assert!(sender.has_transmission_interest());
+        sender.on_transmit(id, &mut context).unwrap();
+        context.frame_buffer.flush();
+
+        let retransmission = &mut context.frame_buffer.frames[1];
+        let packet = retransmission.packet_nr;
+        if let frame::Frame::Stream(frame) = retransmission.as_frame() {
+            assert_eq!(frame.offset, VarInt::from_u8(0));
+            assert_eq!(frame.data.len(), 40);
+            assert!(!frame.is_fin);
+        } else {
+            panic!("invalid frame");
+        }
+
+        sender.on_packet_ack(&packet);
+        assert_eq!(sender.state(), State::Cancelled(error));
+        assert!(!sender.has_transmission_interest());
+    }
 }
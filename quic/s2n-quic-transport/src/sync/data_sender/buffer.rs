@@ -50,6 +50,39 @@ impl Buffer {
         self.check_integrity();
     }
 
+    /// Removes all of the data at or beyond the provided offset
+    ///
+    /// This method should only be called with an offset that has not been released.
+    pub fn truncate(&mut self, len: VarInt) {
+        debug_assert!(self.head <= len, "cannot truncate released data");
+
+        let mut excess = self.total_len().saturating_sub(len);
+
+        while excess > VarInt::from_u8(0) {
+            let chunk = self
+                .chunks
+                .back_mut()
+                .expect("pending_len should match the chunk lengths");
+            let chunk_len = VarInt::try_from(chunk.len()).unwrap();
+
+            if chunk_len <= excess {
+                // the entire chunk is beyond the offset
+                self.chunks.pop_back();
+                self.pending_len -= chunk_len;
+                excess -= chunk_len;
+                continue;
+            }
+
+            // only the end of the chunk is beyond the offset
+            let retained = chunk_len - excess;
+            chunk.data.truncate(retained.try_into().unwrap());
+            self.pending_len -= excess;
+            break;
+        }
+
+        self.check_integrity();
+    }
+
     /// Returns the total number of bytes the buffer has and is currently holding
     #[inline]
     pub fn total_len(&self) -> VarInt {
@@ -457,6 +490,33 @@ mod tests {
         assert!(buffer.chunks.is_empty());
     }
 
+    #[test]
+    fn truncate_test() {
+        let mut buffer = Buffer::default();
+
+        buffer.push(Bytes::from_static(&[0, 1, 2]));
+        buffer.push(Bytes::from_static(&[3, 4, 5]));
+        buffer.release(VarInt::from_u8(1));
+
+        // trim off the end of the second chunk
+        buffer.truncate(VarInt::from_u8(5));
+        assert_eq!(buffer.total_len(), VarInt::from_u8(5));
+        assert_eq!(buffer.enqueued_len(), VarInt::from_u8(4));
+        assert_eq!(buffer.chunks.len(), 2);
+        assert_eq!(buffer.chunks[1][..], [3, 4]);
+
+        // trim off the entire second chunk and part of the first
+        buffer.truncate(VarInt::from_u8(2));
+        assert_eq!(buffer.total_len(), VarInt::from_u8(2));
+        assert_eq!(buffer.enqueued_len(), VarInt::from_u8(1));
+        assert_eq!(buffer.chunks.len(), 1);
+        assert_eq!(buffer.chunks[0][..], [1]);
+
+        // truncating beyond the end should be ok
+        buffer.truncate(VarInt::from_u8(10));
+        assert_eq!(buffer.total_len(), VarInt::from_u8(2));
+    }
+
     #[test]
     fn varint_max_test() {
         let mut buffer = almost_full_buffer();
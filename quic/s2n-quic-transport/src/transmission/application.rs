@@ -2,13 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ack::AckManager,
+    ack::{AckFrequencySender, AckManager},
     connection,
     contexts::WriteContext,
     dc, endpoint, path,
     path::mtu,
     recovery,
-    space::{datagram, CryptoStream, HandshakeStatus},
+    space::{datagram, CryptoStream, HandshakeStatus, ReceiveSpaces},
     stream::Manager as _,
     sync::{flag, flag::Ping},
     transmission::{self, Mode, Provider as _},
@@ -31,6 +31,8 @@ impl<'a, Config: endpoint::Config> Payload<'a, Config> {
         local_id_registry: &'a mut connection::LocalIdRegistry,
         transmission_mode: transmission::Mode,
         ack_manager: &'a mut AckManager,
+        path_acks: Option<&'a mut ReceiveSpaces>,
+        ack_frequency: &'a mut AckFrequencySender,
         handshake_status: &'a mut HandshakeStatus,
         ping: &'a mut flag::Ping,
         stream_manager: &'a mut Config::StreamManager,
@@ -39,7 +41,8 @@ impl<'a, Config: endpoint::Config> Payload<'a, Config> {
         datagram_manager: &'a mut datagram::Manager<Config>,
         dc_manager: &'a mut dc::Manager<Config>,
     ) -> Self {
-        if transmission_mode != Mode::PathValidationOnly {
+        // Application data is only sent on the active path unless multipath was negotiated
+        if transmission_mode != Mode::PathValidationOnly && !path_manager.is_multipath_enabled() {
             debug_assert_eq!(path_id, path_manager.active_path_id());
         }
 
@@ -47,10 +50,13 @@ impl<'a, Config: endpoint::Config> Payload<'a, Config> {
             Mode::LossRecoveryProbing | Mode::Normal => {
                 transmission::application::Payload::Normal(Normal {
                     ack_manager,
+                    path_acks,
+                    ack_frequency,
                     handshake_status,
                     ping,
                     stream_manager,
                     local_id_registry,
+                    path_id,
                     path_manager,
                     recovery_manager,
                     crypto_stream,
@@ -101,10 +107,13 @@ impl<Config: endpoint::Config> transmission::interest::Provider for Payload<'_,
 
 pub struct Normal<'a, Config: endpoint::Config> {
     ack_manager: &'a mut AckManager,
+    path_acks: Option<&'a mut ReceiveSpaces>,
+    ack_frequency: &'a mut AckFrequencySender,
     handshake_status: &'a mut HandshakeStatus,
     ping: &'a mut Ping,
     stream_manager: &'a mut Config::StreamManager,
     local_id_registry: &'a mut connection::LocalIdRegistry,
+    path_id: path::Id,
     path_manager: &'a mut path::Manager<Config>,
     recovery_manager: &'a mut recovery::Manager<Config>,
     crypto_stream: &'a mut CryptoStream,
@@ -134,6 +143,10 @@ impl<Config: endpoint::Config> Normal<'_, Config> {
         }
         let did_send_ack = self.ack_manager.on_transmit(context);
 
+        if let Some(path_acks) = self.path_acks.as_mut() {
+            path_acks.on_transmit(context);
+        }
+
         // Payloads can only transmit and retransmit
         if can_transmit {
             self.transmit_control_data(context);
@@ -164,6 +177,10 @@ impl<Config: endpoint::Config> Normal<'_, Config> {
             self.ack_manager.on_transmit_complete(context);
         }
 
+        if let Some(path_acks) = self.path_acks.as_mut() {
+            path_acks.on_transmit_complete(context);
+        }
+
         // Alternate between prioritizing datagrams or not each packet
         self.prioritize_datagrams = !self.prioritize_datagrams;
     }
@@ -184,7 +201,10 @@ impl<Config: endpoint::Config> Normal<'_, Config> {
         //# An endpoint MAY include other frames with the PATH_CHALLENGE and
         //# PATH_RESPONSE frames used for path validation.
         // prioritize PATH_CHALLENGE and PATH_RESPONSE frames higher than app data
-        self.path_manager.active_path_mut().on_transmit(context);
+        self.path_manager[self.path_id].on_transmit(context);
+
+        self.ack_frequency
+            .on_transmit(&self.path_manager[self.path_id], context);
 
         self.local_id_registry.on_transmit(context);
 
@@ -198,6 +218,10 @@ impl<Config: endpoint::Config> transmission::interest::Provider for Normal<'_, C
         query: &mut Q,
     ) -> transmission::interest::Result {
         self.ack_manager.transmission_interest(query)?;
+        if let Some(path_acks) = self.path_acks.as_ref() {
+            path_acks.transmission_interest(query)?;
+        }
+        self.ack_frequency.transmission_interest(query)?;
         self.handshake_status.transmission_interest(query)?;
         self.stream_manager.transmission_interest(query)?;
         self.datagram_manager.transmission_interest(query)?;
@@ -205,9 +229,7 @@ impl<Config: endpoint::Config> transmission::interest::Provider for Normal<'_, C
         self.path_manager.transmission_interest(query)?;
         self.crypto_stream.transmission_interest(query)?;
         self.recovery_manager.transmission_interest(query)?;
-        self.path_manager
-            .active_path()
-            .transmission_interest(query)?;
+        self.path_manager[self.path_id].transmission_interest(query)?;
         self.ping.transmission_interest(query)?;
         self.dc_manager.transmission_interest(query)?;
         Ok(())
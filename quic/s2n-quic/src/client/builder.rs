@@ -166,6 +166,35 @@ impl<Providers: ClientProviders> Builder<Providers> {
         ClientProviders
     );
 
+    impl_provider_method!(
+        /// Sets the QUIC versions enabled for the [`Client`]
+        ///
+        /// # Examples
+        ///
+        /// Connect using QUIC version 2, falling back to QUIC version 1
+        ///
+        /// ```rust,no_run
+        /// # use std::error::Error;
+        /// use s2n_quic::{Client, provider::version};
+        ///
+        /// # #[tokio::main]
+        /// # async fn main() -> Result<(), Box<dyn Error>> {
+        /// let versions = version::Config::builder()
+        ///     .with_initial_version(version::V2)?
+        ///     .build()?;
+        ///
+        /// let client = Client::builder()
+        ///     .with_version(versions)?
+        ///     .start()?;
+        /// #
+        /// #    Ok(())
+        /// # }
+        /// ```
+        with_version,
+        version,
+        ClientProviders
+    );
+
     impl_provider_method!(
         /// Sets the event provider for the [`Client`]
         ///
@@ -301,7 +330,7 @@ impl<Providers: ClientProviders> Builder<Providers> {
         ClientProviders
     );
 
-    #[cfg(feature = "unstable-provider-datagram")]
+    #[cfg(any(test, feature = "datagram"))]
     impl_provider_method!(
         /// Sets the datagram provider for the [`Client`]
         with_datagram,
@@ -324,6 +353,35 @@ impl<Providers: ClientProviders> Builder<Providers> {
         ClientProviders
     );
 
+    impl_provider_method!(
+        /// Sets the multipath scheduler provider for the [`Client`]
+        ///
+        /// The scheduler is only used on connections that negotiate the multipath extension.
+        ///
+        /// # Examples
+        ///
+        /// Alternates between the paths of each connection
+        ///
+        /// ```rust,no_run
+        /// # use std::error::Error;
+        /// use s2n_quic::{Client, provider::{limits, path_scheduler}};
+        /// #
+        /// # #[tokio::main]
+        /// # async fn main() -> Result<(), Box<dyn Error>> {
+        /// let limits = limits::Limits::new().with_max_path_id(4)?;
+        /// let client = Client::builder()
+        ///     .with_limits(limits)?
+        ///     .with_path_scheduler(path_scheduler::RoundRobin::default())?
+        ///     .start()?;
+        /// #
+        /// #    Ok(())
+        /// # }
+        /// ```
+        with_path_scheduler,
+        path_scheduler,
+        ClientProviders
+    );
+
     /// Starts the [`Client`] with the configured providers
     ///
     /// # Examples
@@ -18,11 +18,13 @@ impl_providers_state! {
         event: Event,
         limits: Limits,
         mtu: Mtu,
+        version: Version,
         io: IO,
         sync: Sync,
         tls: Tls,
         datagram: Datagram,
         dc: Dc,
+        path_scheduler: PathScheduler,
     }
 
     /// Opaque trait containing all of the configured providers
@@ -39,11 +41,13 @@ impl<
         Event: event::Provider,
         Limits: limits::Provider,
         Mtu: mtu::Provider,
+        Version: version::Provider,
         IO: io::Provider,
         Sync: sync::Provider,
         Tls: tls::Provider,
         Datagram: datagram::Provider,
         Dc: dc::Provider,
+        PathScheduler: path_scheduler::Provider,
     >
     Providers<
         CongestionController,
@@ -55,11 +59,13 @@ impl<
         Event,
         Limits,
         Mtu,
+        Version,
         IO,
         Sync,
         Tls,
         Datagram,
         Dc,
+        PathScheduler,
     >
 {
     pub fn start(self) -> Result<Client, StartError> {
@@ -73,11 +79,13 @@ impl<
             event,
             limits,
             mtu,
+            version,
             io,
             sync,
             tls,
             datagram,
             dc,
+            path_scheduler,
         } = self;
 
         let congestion_controller = congestion_controller.start().map_err(StartError::new)?;
@@ -91,6 +99,7 @@ impl<
         let endpoint_limits = EndpointLimits;
         let limits = limits.start().map_err(StartError::new)?;
         let mtu = mtu.start().map_err(StartError::new)?;
+        let version = version.start().map_err(StartError::new)?;
         let event = event.start().map_err(StartError::new)?;
         let token = Token;
         let sync = sync.start().map_err(StartError::new)?;
@@ -98,6 +107,7 @@ impl<
         let tls = tls.start_client().map_err(StartError::new)?;
         let datagram = datagram.start().map_err(StartError::new)?;
         let dc = dc.start().map_err(StartError::new)?;
+        let path_scheduler = path_scheduler.start().map_err(StartError::new)?;
 
         // Validate providers
         // TODO: Add more validation https://github.com/aws/s2n-quic/issues/285
@@ -123,6 +133,7 @@ impl<
             event,
             limits,
             mtu,
+            version,
             sync,
             tls,
             token,
@@ -130,6 +141,7 @@ impl<
             path_migration,
             datagram,
             dc,
+            path_scheduler,
         };
 
         let (endpoint, connector) = endpoint::Endpoint::new_client(endpoint_config);
@@ -217,6 +229,7 @@ struct EndpointConfig<
     Tls,
     Datagram,
     Dc,
+    PathScheduler,
 > {
     congestion_controller: CongestionController,
     connection_close_formatter: ConnectionCloseFormatter,
@@ -228,6 +241,7 @@ struct EndpointConfig<
     event: Event,
     limits: Limits,
     mtu: path::mtu::Manager<Mtu>,
+    version: s2n_quic_core::version::Config,
     sync: Sync,
     tls: Tls,
     token: Token,
@@ -235,6 +249,7 @@ struct EndpointConfig<
     path_migration: PathMigration,
     datagram: Datagram,
     dc: Dc,
+    path_scheduler: PathScheduler,
 }
 
 impl<
@@ -252,6 +267,7 @@ impl<
         Tls: crypto::tls::Endpoint,
         Datagram: s2n_quic_core::datagram::Endpoint,
         Dc: s2n_quic_core::dc::Endpoint,
+        PathScheduler: path_scheduler::Endpoint,
     > core::fmt::Debug
     for EndpointConfig<
         CongestionController,
@@ -268,6 +284,7 @@ impl<
         Tls,
         Datagram,
         Dc,
+        PathScheduler,
     >
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -290,6 +307,7 @@ impl<
         Tls: crypto::tls::Endpoint,
         Datagram: s2n_quic_core::datagram::Endpoint,
         Dc: s2n_quic_core::dc::Endpoint,
+        PathScheduler: path_scheduler::Endpoint,
     > endpoint::Config
     for EndpointConfig<
         CongestionController,
@@ -306,6 +324,7 @@ impl<
         Tls,
         Datagram,
         Dc,
+        PathScheduler,
     >
 {
     type ConnectionIdFormat = ConnectionID;
@@ -328,6 +347,7 @@ impl<
     type PacketInterceptor = PacketInterceptor;
     type DatagramEndpoint = Datagram;
     type DcEndpoint = Dc;
+    type PathScheduler = PathScheduler;
 
     const ENDPOINT_TYPE: endpoint::Type = endpoint::Type::Client;
 
@@ -348,6 +368,8 @@ impl<
             path_migration: &mut self.path_migration,
             datagram: &mut self.datagram,
             dc: &mut self.dc,
+            path_scheduler: &mut self.path_scheduler,
+            version: &self.version,
         }
     }
 }
@@ -157,6 +157,81 @@ macro_rules! impl_handle_api {
             self.0.remote_address().map(std::net::SocketAddr::from)
         }
 
+        /// Migrates a client connection to a new local address
+        ///
+        /// The connection probes the path from the new address to the server with a
+        /// PATH_CHALLENGE and switches over to it once the server responds. If the path fails
+        /// validation, the connection keeps using the current address. The progress of the
+        /// migration is reported with the `ActiveMigrationUpdated` event, including migrations
+        /// that could not be started because the connection state changed after the request was
+        /// made. Completion can also be observed with [`Self::local_addr`].
+        ///
+        /// The IO provider must be able to send from and receive on the new address. A socket bound
+        /// to an unspecified IP address can send from any of the host's interfaces on the same
+        /// port. Addresses that need a separate socket, such as a different local port or a socket
+        /// bound to a specific interface, can be bound up front with the `with_additional_address`
+        /// and `with_additional_socket` methods of the tokio IO provider builder, which is how a
+        /// connection can fail over between interfaces.
+        ///
+        /// The method will return an error if
+        ///  - the connection is a server connection
+        ///  - the handshake has not been confirmed yet
+        ///  - the server sent the `disable_active_migration` transport parameter
+        ///  - a migration is already in progress
+        ///  - the server has not issued an unused connection ID for the new path
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// let local_addr: std::net::SocketAddr = "192.168.1.2:4433".parse()?;
+        /// connection.migrate(local_addr)?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn migrate(
+            &mut self,
+            local_addr: std::net::SocketAddr,
+        ) -> core::result::Result<(), $crate::connection::MigrationError> {
+            self.0.migrate(local_addr.into())
+        }
+
+        /// Returns the maximum datagram size currently validated on the active path
+        ///
+        /// The value excludes the UDP and IP headers and grows as larger MTU probes are
+        /// acknowledged by the peer, or falls back to the base MTU when an MTU black hole is detected.
+        #[inline]
+        pub fn mtu(&self) -> $crate::connection::Result<u16> {
+            self.0.mtu()
+        }
+
+        /// Restarts the search for a larger MTU on the active path
+        ///
+        /// This skips the remainder of the configured probe interval or black hole cool off period
+        /// and also probes sizes that previously failed, which is useful after a route change.
+        /// The request has no effect if a search is already in progress.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// let mtu = connection.mtu()?;
+        /// connection.probe_mtu()?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn probe_mtu(&mut self) -> $crate::connection::Result<()> {
+            self.0.probe_mtu()
+        }
+
         /// Returns the negotiated server name the connection is using.
         #[inline]
         pub fn server_name(&self) -> $crate::connection::Result<Option<$crate::server::Name>> {
@@ -169,6 +244,26 @@ macro_rules! impl_handle_api {
             self.0.application_protocol()
         }
 
+        /// Returns the status of early data (0-RTT) on the connection
+        ///
+        /// Clients resuming a session can open streams and send data before the handshake
+        /// completes. If the server rejects the early data, those streams fail with
+        /// [`stream::Error::EarlyDataRejected`](`crate::stream::Error::EarlyDataRejected`) and the
+        /// data needs to be sent again on new streams. New streams can be opened once the
+        /// handles to the rejected streams have been dropped or have returned the error.
+        ///
+        /// NOTE: Early data is currently only supported by the `rustls` TLS provider, since s2n-tls
+        /// doesn't support early data on QUIC connections. With the `s2n-tls` provider, including
+        /// clients resumed with `unstable_resumption` session tickets, resumed connections don't
+        /// send early data and this always returns
+        /// [`EarlyDataStatus::NotAttempted`](`crate::connection::EarlyDataStatus::NotAttempted`).
+        #[inline]
+        pub fn early_data_status(
+            &self,
+        ) -> $crate::connection::Result<$crate::connection::EarlyDataStatus> {
+            self.0.early_data_status()
+        }
+
         /// Takes the context provided by the TLS provider.
         ///
         /// This functionality is useful when you need to pass information from the TLS provider to the
@@ -425,6 +520,262 @@ macro_rules! impl_handle_api {
 
             query.into()
         }
+
+        /// Enqueues an unreliable datagram to be sent to the peer
+        ///
+        /// The datagram is never retransmitted. If the send queue of the connection's
+        /// [`default`](crate::provider::datagram::default) datagram provider is full, the
+        /// method waits until capacity is available.
+        ///
+        /// The method will return
+        ///  - `Ok(())` if the datagram was enqueued
+        ///  - `Err(error)` if the datagram could not be enqueued due to an error
+        ///
+        /// # Examples
+        ///
+        /// ```rust,ignore
+        /// # async fn test() -> Result<(), s2n_quic::provider::datagram::default::DatagramError> {
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// handle.send_datagram(bytes::Bytes::from_static(b"hello")).await?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[cfg(any(test, feature = "datagram"))]
+        #[cfg_attr(docsrs, doc(cfg(feature = "datagram")))]
+        #[inline]
+        pub async fn send_datagram(
+            &mut self,
+            data: bytes::Bytes,
+        ) -> core::result::Result<(), $crate::provider::datagram::default::DatagramError> {
+            let mut data = data;
+            futures::future::poll_fn(|cx| self.poll_send_datagram(&mut data, cx)).await
+        }
+
+        /// Polls enqueueing an unreliable datagram to be sent to the peer
+        ///
+        /// On success, `data` is taken and replaced with an empty buffer.
+        ///
+        /// The method will return
+        /// - `Poll::Ready(Ok(()))` if the datagram was enqueued
+        /// - `Poll::Ready(Err(error))` if the datagram could not be enqueued due to an error
+        /// - `Poll::Pending` if the send queue is currently full
+        #[cfg(any(test, feature = "datagram"))]
+        #[cfg_attr(docsrs, doc(cfg(feature = "datagram")))]
+        #[inline]
+        pub fn poll_send_datagram(
+            &mut self,
+            data: &mut bytes::Bytes,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<
+            core::result::Result<(), $crate::provider::datagram::default::DatagramError>,
+        > {
+            use $crate::provider::datagram::default::Sender;
+
+            self.poll_datagram(|sender: &mut Sender| sender.poll_send_datagram(data, cx))
+        }
+
+        /// Enqueues an unreliable datagram to be sent to the peer and tracks its outcome
+        ///
+        /// This behaves like [`Self::send_datagram`], but returns a
+        /// [`DatagramId`](crate::provider::datagram::default::DatagramId) which is reported with
+        /// the [`SendOutcome`](crate::provider::datagram::default::SendOutcome) of the datagram
+        /// by [`Self::datagram_send_outcome`].
+        #[cfg(any(test, feature = "datagram"))]
+        #[cfg_attr(docsrs, doc(cfg(feature = "datagram")))]
+        #[inline]
+        pub async fn send_tracked_datagram(
+            &mut self,
+            data: bytes::Bytes,
+        ) -> core::result::Result<
+            $crate::provider::datagram::default::DatagramId,
+            $crate::provider::datagram::default::DatagramError,
+        > {
+            let mut data = data;
+            futures::future::poll_fn(|cx| self.poll_send_tracked_datagram(&mut data, cx)).await
+        }
+
+        /// Polls enqueueing an unreliable datagram to be sent to the peer and tracks its outcome
+        ///
+        /// The method will return
+        /// - `Poll::Ready(Ok(id))` if the datagram was enqueued
+        /// - `Poll::Ready(Err(error))` if the datagram could not be enqueued due to an error
+        /// - `Poll::Pending` if the send queue is currently full
+        #[cfg(any(test, feature = "datagram"))]
+        #[cfg_attr(docsrs, doc(cfg(feature = "datagram")))]
+        #[inline]
+        pub fn poll_send_tracked_datagram(
+            &mut self,
+            data: &mut bytes::Bytes,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<
+            core::result::Result<
+                $crate::provider::datagram::default::DatagramId,
+                $crate::provider::datagram::default::DatagramError,
+            >,
+        > {
+            use $crate::provider::datagram::default::Sender;
+
+            self.poll_datagram(|sender: &mut Sender| sender.poll_send_tracked_datagram(data, cx))
+        }
+
+        /// Waits for the outcome of a datagram sent with [`Self::send_tracked_datagram`]
+        ///
+        /// Outcomes are reported in the order they become known, which may differ from the
+        /// order the datagrams were sent in.
+        ///
+        /// The method will return
+        ///  - `Ok((id, outcome))` once the outcome of a tracked datagram is known
+        ///  - `Err(error)` if the connection closed and no more outcomes will be reported
+        #[cfg(any(test, feature = "datagram"))]
+        #[cfg_attr(docsrs, doc(cfg(feature = "datagram")))]
+        #[inline]
+        pub async fn datagram_send_outcome(
+            &mut self,
+        ) -> core::result::Result<
+            (
+                $crate::provider::datagram::default::DatagramId,
+                $crate::provider::datagram::default::SendOutcome,
+            ),
+            $crate::provider::datagram::default::DatagramError,
+        > {
+            futures::future::poll_fn(|cx| self.poll_datagram_send_outcome(cx)).await
+        }
+
+        /// Polls for the outcome of a datagram sent with [`Self::send_tracked_datagram`]
+        ///
+        /// The method will return
+        /// - `Poll::Ready(Ok((id, outcome)))` if the outcome of a tracked datagram is known
+        /// - `Poll::Ready(Err(error))` if the connection closed and no more outcomes will be reported
+        /// - `Poll::Pending` if no outcomes are currently available
+        #[cfg(any(test, feature = "datagram"))]
+        #[cfg_attr(docsrs, doc(cfg(feature = "datagram")))]
+        #[inline]
+        pub fn poll_datagram_send_outcome(
+            &mut self,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<
+            core::result::Result<
+                (
+                    $crate::provider::datagram::default::DatagramId,
+                    $crate::provider::datagram::default::SendOutcome,
+                ),
+                $crate::provider::datagram::default::DatagramError,
+            >,
+        > {
+            use $crate::provider::datagram::default::Sender;
+
+            self.poll_datagram(|sender: &mut Sender| sender.poll_send_outcome(cx))
+        }
+
+        /// Receives an unreliable datagram from the peer
+        ///
+        /// The method will return
+        ///  - `Ok(data)` if a datagram was received
+        ///  - `Err(error)` if the connection closed and no more datagrams will be received
+        ///
+        /// # Examples
+        ///
+        /// ```rust,ignore
+        /// # async fn test() -> Result<(), s2n_quic::provider::datagram::default::DatagramError> {
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// while let Ok(datagram) = handle.recv_datagram().await {
+        ///     println!("Received {datagram:?}");
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[cfg(any(test, feature = "datagram"))]
+        #[cfg_attr(docsrs, doc(cfg(feature = "datagram")))]
+        #[inline]
+        pub async fn recv_datagram(
+            &mut self,
+        ) -> core::result::Result<bytes::Bytes, $crate::provider::datagram::default::DatagramError>
+        {
+            futures::future::poll_fn(|cx| self.poll_recv_datagram(cx)).await
+        }
+
+        /// Polls receiving an unreliable datagram from the peer
+        ///
+        /// The method will return
+        /// - `Poll::Ready(Ok(data))` if a datagram was received
+        /// - `Poll::Ready(Err(error))` if the connection closed and no more datagrams will be received
+        /// - `Poll::Pending` if no datagrams are currently available
+        #[cfg(any(test, feature = "datagram"))]
+        #[cfg_attr(docsrs, doc(cfg(feature = "datagram")))]
+        #[inline]
+        pub fn poll_recv_datagram(
+            &mut self,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<
+            core::result::Result<bytes::Bytes, $crate::provider::datagram::default::DatagramError>,
+        > {
+            use $crate::provider::datagram::default::Receiver;
+
+            self.poll_datagram(|receiver: &mut Receiver| receiver.poll_recv_datagram(cx))
+        }
+
+        #[cfg(any(test, feature = "datagram"))]
+        #[inline]
+        fn poll_datagram<Query, ProviderType, Outcome>(
+            &self,
+            query: Query,
+        ) -> core::task::Poll<
+            core::result::Result<Outcome, $crate::provider::datagram::default::DatagramError>,
+        >
+        where
+            Query: FnOnce(
+                &mut ProviderType,
+            ) -> core::task::Poll<
+                core::result::Result<Outcome, $crate::provider::datagram::default::DatagramError>,
+            >,
+            ProviderType: 'static,
+        {
+            use s2n_quic_core::query;
+            let mut query = query::Once::new_mut(query);
+
+            self.0.datagram_mut(&mut query)?;
+
+            let outcome: core::result::Result<_, query::Error> = query.into();
+            outcome?
+        }
+
+        /// API for querying the congestion controller of the connection's active path.
+        ///
+        /// Provides mutable access to the
+        /// [`CongestionController`](crate::provider::congestion_controller::CongestionController)
+        /// created by the congestion controller provider. With the
+        /// [`Dynamic`](crate::provider::congestion_controller::Dynamic) provider, this can be used
+        /// to switch the algorithm of a live connection.
+        ///
+        /// ```ignore
+        /// use s2n_quic::provider::congestion_controller::{Algorithm, DynamicCongestionController};
+        ///
+        /// let outcome = connection
+        ///     .congestion_controller_mut(
+        ///         |cc: &mut DynamicCongestionController| cc.switch_to(Algorithm::Bbr)
+        ///     );
+        /// ```
+        pub fn congestion_controller_mut<Query, CongestionController, Outcome>(
+            &self,
+            query: Query,
+        ) -> core::result::Result<Outcome, s2n_quic_core::query::Error>
+        where
+            Query: FnOnce(&mut CongestionController) -> Outcome,
+            CongestionController: 'static,
+        {
+            use s2n_quic_core::query;
+            let mut query = query::Once::new_mut(query);
+
+            self.0
+                .congestion_controller_mut(&mut query)
+                .map_err(|_| query::Error::ConnectionLockPoisoned)?;
+
+            query.into()
+        }
     };
 }
 
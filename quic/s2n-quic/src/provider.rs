@@ -15,8 +15,10 @@ pub mod event;
 pub mod io;
 pub mod limits;
 pub mod mtu;
+pub mod path_scheduler;
 pub mod stateless_reset_token;
 pub mod tls;
+pub mod version;
 
 // These providers are not currently exposed to applications
 #[allow(dead_code)]
@@ -55,8 +57,8 @@ cfg_if!(
 );
 
 cfg_if!(
-    if #[cfg(any(test, feature = "unstable-provider-datagram"))] {
-        #[cfg_attr(docsrs, doc(cfg(feature = "unstable-provider-datagram")))]
+    if #[cfg(any(test, feature = "datagram"))] {
+        #[cfg_attr(docsrs, doc(cfg(feature = "datagram")))]
         pub mod datagram;
     } else {
         #[allow(dead_code)]
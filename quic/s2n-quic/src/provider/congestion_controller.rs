@@ -12,21 +12,37 @@ pub trait Provider {
     fn start(self) -> Result<Self::Endpoint, Self::Error>;
 }
 
+// Export the types needed to implement the CongestionController trait
+pub use s2n_quic_core::{
+    random::Generator as RandomGenerator,
+    recovery::{
+        bandwidth,
+        congestion_controller::{CongestionController, PathInfo, Publisher},
+        pacing::Pacer,
+        RttEstimator,
+    },
+    time::Timestamp,
+};
+
 cfg_if! {
-    if #[cfg(feature = "unstable-congestion-controller")] {
-        #[cfg_attr(docsrs, doc(cfg(feature = "unstable-congestion-controller")))]
-        // Export the types needed to implement the CongestionController trait
-        pub use s2n_quic_core::{
-            random::Generator as RandomGenerator,
-            recovery::{congestion_controller::{CongestionController, PathInfo, Publisher}, RttEstimator},
-            time::Timestamp,
-        };
+    if #[cfg(feature = "provider-congestion-controller-conformance")] {
+        #[cfg_attr(docsrs, doc(cfg(feature = "provider-congestion-controller-conformance")))]
+        // Export the harness for checking CongestionController implementations
+        pub use s2n_quic_core::recovery::congestion_controller::conformance;
     }
 }
 
-pub use s2n_quic_core::recovery::{bbr::Endpoint as Bbr, cubic::Endpoint as Cubic};
+pub use s2n_quic_core::recovery::{
+    bbr::Endpoint as Bbr, cubic::Endpoint as Cubic, dynamic::Endpoint as Dynamic,
+    new_reno::Endpoint as NewReno, prague::Endpoint as Prague,
+};
 // Build congestion controllers with application provided overrides
-pub use s2n_quic_core::recovery::{bbr::builder as bbr, cubic::builder as cubic};
+pub use s2n_quic_core::recovery::{
+    bbr::builder as bbr, cubic::builder as cubic, dynamic::builder as dynamic,
+    new_reno::builder as new_reno, prague::builder as prague,
+};
+// Select the algorithm of the `Dynamic` congestion controller for each path or connection
+pub use s2n_quic_core::recovery::dynamic::{Algorithm, DynamicCongestionController, Selector};
 pub type Default = Cubic;
 
 impl_provider_utils!();
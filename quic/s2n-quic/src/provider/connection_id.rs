@@ -33,7 +33,7 @@ pub mod default {
     use rand::prelude::*;
     use s2n_quic_core::connection::{
         self,
-        id::{ConnectionInfo, Generator, Validator},
+        id::{shard, ConnectionInfo, Generator, Validator},
     };
 
     #[derive(Debug, Default)]
@@ -59,6 +59,7 @@ pub mod default {
         len: usize,
         lifetime: Option<Duration>,
         rotate_handshake_connection_id: bool,
+        shard: Option<(shard::Encoding, u16)>,
     }
 
     impl Default for Format {
@@ -67,6 +68,7 @@ pub mod default {
                 len: DEFAULT_LEN,
                 lifetime: None,
                 rotate_handshake_connection_id: true,
+                shard: None,
             }
         }
     }
@@ -84,6 +86,7 @@ pub mod default {
         len: usize,
         lifetime: Option<Duration>,
         rotate_handshake_connection_id: bool,
+        shard: Option<(shard::Encoding, u16)>,
     }
 
     impl Default for Builder {
@@ -92,6 +95,7 @@ pub mod default {
                 len: DEFAULT_LEN,
                 lifetime: None,
                 rotate_handshake_connection_id: true,
+                shard: None,
             }
         }
     }
@@ -102,6 +106,12 @@ pub mod default {
             if !(connection::LocalId::MIN_LEN..=connection::id::MAX_LEN).contains(&len) {
                 return Err(connection::id::Error::InvalidLength);
             }
+            // the connection Id needs to contain the shard byte
+            if let Some((encoding, _)) = self.shard {
+                if encoding.offset() as usize >= len {
+                    return Err(connection::id::Error::InvalidLength);
+                }
+            }
             self.len = len;
             Ok(self)
         }
@@ -129,12 +139,33 @@ pub mod default {
             Ok(self)
         }
 
+        /// Encodes the shard `index` in each generated connection Id
+        ///
+        /// This allows packets to be routed to the endpoint shard that owns the connection
+        /// without any shared state. The byte at the encoding's offset must fit in the configured
+        /// connection Id length.
+        pub fn with_shard(
+            mut self,
+            encoding: shard::Encoding,
+            index: u16,
+        ) -> Result<Self, shard::Error> {
+            if index >= encoding.count() {
+                return Err(shard::Error::InvalidIndex);
+            }
+            if encoding.offset() as usize >= self.len {
+                return Err(shard::Error::InvalidOffset);
+            }
+            self.shard = Some((encoding, index));
+            Ok(self)
+        }
+
         /// Builds the [`Format`] into a provider
         pub fn build(self) -> Result<Format, core::convert::Infallible> {
             Ok(Format {
                 len: self.len,
                 lifetime: self.lifetime,
                 rotate_handshake_connection_id: self.rotate_handshake_connection_id,
+                shard: self.shard,
             })
         }
     }
@@ -144,6 +175,10 @@ pub mod default {
             let mut id = [0u8; connection::id::MAX_LEN];
             let id = &mut id[..self.len];
             rand::rng().fill_bytes(id);
+            if let Some((encoding, index)) = self.shard {
+                // the index and offset are checked when configuring the shard
+                let _ = encoding.encode(index, id);
+            }
             (&*id).try_into().expect("length already checked")
         }
 
@@ -246,5 +281,53 @@ pub mod default {
                 .unwrap();
             assert!(!format.rotate_handshake_connection_id());
         }
+
+        #[test]
+        fn shard_test() {
+            let remote_address = &s2n_quic_core::inet::SocketAddress::default();
+            let connection_info = ConnectionInfo::new(remote_address);
+            let encoding = shard::Encoding::new(8).unwrap().with_offset(3).unwrap();
+
+            for index in 0..encoding.count() {
+                let mut format = Format::builder()
+                    .with_shard(encoding, index)
+                    .unwrap()
+                    .build()
+                    .unwrap();
+
+                for _ in 0..10 {
+                    let id = format.generate(&connection_info);
+                    assert_eq!(encoding.decode(id.as_ref()), Some(index));
+                }
+            }
+
+            assert_eq!(
+                Some(shard::Error::InvalidIndex),
+                Format::builder().with_shard(encoding, 8).err()
+            );
+
+            assert_eq!(
+                Some(shard::Error::InvalidOffset),
+                Format::builder()
+                    .with_len(connection::LocalId::MIN_LEN)
+                    .unwrap()
+                    .with_shard(
+                        encoding
+                            .with_offset(connection::LocalId::MIN_LEN as _)
+                            .unwrap(),
+                        0
+                    )
+                    .err()
+            );
+
+            assert_eq!(
+                Some(connection::id::Error::InvalidLength),
+                Format::builder()
+                    .with_shard(encoding.with_offset(10).unwrap(), 0)
+                    .unwrap()
+                    .with_len(8)
+                    .err()
+            );
+        }
     }
 }
@@ -2,10 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! Provides unreliable datagram support
+//!
+//! When the `datagram` feature is enabled, the [`default`] endpoint is used unless another
+//! provider is configured, so datagrams can be sent and received on the connection handle
+//! without any additional setup. Otherwise datagrams are disabled by default.
 
+#[cfg(not(feature = "datagram"))]
 use s2n_quic_core::datagram::Disabled;
 
-// these imports are only accessible if the unstable feature is enabled
+// these imports are only accessible if the `datagram` feature is enabled
 #[allow(unused_imports)]
 pub use s2n_quic_core::datagram::{
     default,
@@ -24,6 +29,9 @@ pub trait Provider {
 
 impl_provider_utils!();
 
+#[cfg(feature = "datagram")]
+pub type Default = default::Endpoint;
+#[cfg(not(feature = "datagram"))]
 pub type Default = Disabled;
 
 impl<T: 'static + Send + Endpoint> Provider for T {
@@ -33,6 +33,19 @@ pub mod tracing;
 #[cfg(feature = "provider-event-console-perf")]
 pub mod console_perf;
 
+/// Provides an implementation that writes a qlog trace for each connection
+#[cfg(any(feature = "provider-event-qlog", test))]
+pub mod qlog;
+
+/// Provides an implementation that records each connection as a tree of spans for
+/// [OpenTelemetry](https://opentelemetry.io)
+#[cfg(feature = "provider-event-opentelemetry")]
+pub mod opentelemetry;
+
+/// Provides a metrics registry which can be scraped by [Prometheus](https://prometheus.io)
+#[cfg(feature = "provider-event-prometheus")]
+pub mod prometheus;
+
 cfg_if! {
     if #[cfg(any(feature = "provider-event-tracing", test))] {
         pub use self::tracing as default;
@@ -0,0 +1,513 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::provider::event;
+use s2n_quic_core::{
+    connection,
+    event::{
+        api::{EndpointType, Frame, HandshakeStatus, KeyType, PathChallengeStatus},
+        metrics::aggregate::AsVariant,
+    },
+};
+use std::collections::{BTreeSet, HashMap};
+use tracing::{field, Span};
+use tracing_subscriber::{filter, registry::LookupSpan, Layer};
+
+/// The target used for all of the spans emitted by the subscriber
+const TARGET: &str = "s2n_quic::otel";
+
+/// An event subscriber that records each connection as a tree of
+/// [`tracing`](https://docs.rs/tracing) spans, which are exported to
+/// [OpenTelemetry](https://opentelemetry.io) by the [`layer`] returned for a tracer
+///
+/// Each connection is a root `quic.connection` span, with the following child spans:
+///
+/// * `quic.handshake` - from the start of the connection until the handshake completes
+/// * `quic.stream` - from the first frame of a stream until both of its sides have
+///   finished or been reset
+/// * `quic.path_migration` - from a change in the active path until the new path is
+///   validated or abandoned
+///
+/// Span fields follow the OpenTelemetry semantic conventions where possible, including
+/// `otel.kind` and `otel.status_code`, which `tracing-opentelemetry` maps onto the
+/// exported span.
+///
+/// NOTE: The set of spans and fields is subject to change and should not be relied
+/// on to remain consistent over time.
+///
+/// # Examples
+///
+/// Enables the OpenTelemetry event subscriber for the server.
+///
+/// ```rust,ignore
+/// use std::error::Error;
+/// use s2n_quic::{provider::event, Server};
+/// use tracing_subscriber::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn Error>> {
+///     let tracer = init_otel_tracer()?;
+///     tracing_subscriber::registry()
+///         .with(event::opentelemetry::layer(tracer))
+///         .init();
+///
+///     let server = Server::builder()
+///         .with_event(event::opentelemetry::Subscriber::default())?
+///         .start()?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Subscriber(());
+
+/// Returns a [`tracing_subscriber`] layer which exports the spans of the [`Subscriber`] to
+/// OpenTelemetry with the given `tracer`
+///
+/// Only the spans and events emitted by the [`Subscriber`] are exported, so the layer can be
+/// combined with other layers without exporting any unrelated spans.
+pub fn layer<S, T>(tracer: T) -> impl Layer<S>
+where
+    S: tracing::Subscriber + for<'span> LookupSpan<'span>,
+    T: opentelemetry::trace::Tracer + 'static,
+    T::Span: Send + Sync,
+{
+    tracing_opentelemetry::layer()
+        .with_tracer(tracer)
+        .with_filter(filter::filter_fn(|metadata| metadata.target() == TARGET))
+}
+
+pub struct ConnectionContext {
+    span: Span,
+    endpoint_type: EndpointType,
+    handshake: Option<Span>,
+    migration: Option<Migration>,
+    streams: HashMap<u64, Stream>,
+    /// The finished streams of each stream type, indexed by the low bits of the stream ID
+    finished_streams: [FinishedStreams; 4],
+}
+
+impl ConnectionContext {
+    /// Returns the stream with the given ID, opening its span on the first frame
+    ///
+    /// `None` is returned for streams which have already finished, so retransmitted or duplicate
+    /// frames don't reopen their spans.
+    fn stream(&mut self, id: u64) -> Option<&mut Stream> {
+        if self.finished_streams[(id & 0b11) as usize].contains(id) {
+            return None;
+        }
+
+        let span = &self.span;
+        let endpoint_type = &self.endpoint_type;
+        let stream = self
+            .streams
+            .entry(id)
+            .or_insert_with(|| Stream::new(span, endpoint_type, id));
+        Some(stream)
+    }
+
+    /// Removes the stream if both of its sides have finished
+    fn on_stream_updated(&mut self, id: u64) {
+        if self.streams.get(&id).is_some_and(Stream::is_finished) {
+            self.streams.remove(&id);
+            self.finished_streams[(id & 0b11) as usize].insert(id);
+        }
+    }
+}
+
+/// The finished streams of a single stream type
+///
+/// Streams of the same type are usually finished in the order they're opened, so each one is
+/// tracked with a high-water mark below which all of the streams have finished. Streams which
+/// finish out of order are kept in a set until the streams before them finish.
+#[derive(Debug, Default)]
+struct FinishedStreams {
+    /// The index of the first stream that hasn't finished
+    next: u64,
+    /// The indexes of the finished streams after `next`
+    out_of_order: BTreeSet<u64>,
+}
+
+impl FinishedStreams {
+    fn contains(&self, id: u64) -> bool {
+        let index = id >> 2;
+        index < self.next || self.out_of_order.contains(&index)
+    }
+
+    fn insert(&mut self, id: u64) {
+        let index = id >> 2;
+
+        if index != self.next {
+            if index > self.next {
+                self.out_of_order.insert(index);
+            }
+            return;
+        }
+
+        // advance the mark past any streams that already finished out of order
+        self.next += 1;
+        while self.out_of_order.remove(&self.next) {
+            self.next += 1;
+        }
+    }
+}
+
+struct Stream {
+    span: Span,
+    is_send_finished: bool,
+    is_recv_finished: bool,
+    bytes_sent: u64,
+    bytes_received: u64,
+}
+
+impl Stream {
+    fn new(parent: &Span, endpoint_type: &EndpointType, id: u64) -> Self {
+        let is_server_initiated = id & 0b01 != 0;
+        let is_unidirectional = id & 0b10 != 0;
+        let is_local = is_server_initiated == matches!(endpoint_type, EndpointType::Server { .. });
+
+        let initiator = if is_local { "local" } else { "remote" };
+        let direction = if is_unidirectional {
+            "unidirectional"
+        } else {
+            "bidirectional"
+        };
+
+        let span = tracing::info_span!(
+            target: TARGET,
+            parent: parent,
+            "quic.stream",
+            quic.stream.id = int(id),
+            quic.stream.initiator = initiator,
+            quic.stream.direction = direction,
+            quic.stream.bytes_sent = field::Empty,
+            quic.stream.bytes_received = field::Empty,
+            quic.stream.reset_sent = field::Empty,
+            quic.stream.reset_received = field::Empty,
+        );
+
+        Self {
+            span,
+            // unidirectional streams only have a single side
+            is_send_finished: is_unidirectional && !is_local,
+            is_recv_finished: is_unidirectional && is_local,
+            bytes_sent: 0,
+            bytes_received: 0,
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.is_send_finished && self.is_recv_finished
+    }
+}
+
+impl Drop for Stream {
+    fn drop(&mut self) {
+        if self.bytes_sent > 0 {
+            self.span
+                .record("quic.stream.bytes_sent", int(self.bytes_sent));
+        }
+        if self.bytes_received > 0 {
+            self.span
+                .record("quic.stream.bytes_received", int(self.bytes_received));
+        }
+    }
+}
+
+struct Migration {
+    span: Span,
+    path_id: u64,
+}
+
+impl event::Subscriber for Subscriber {
+    type ConnectionContext = ConnectionContext;
+
+    #[inline]
+    fn create_connection_context(
+        &mut self,
+        meta: &event::ConnectionMeta,
+        _info: &event::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        let endpoint = endpoint_name(&meta.endpoint_type);
+
+        // each connection is the root of its own trace
+        let span = tracing::info_span!(
+            target: TARGET,
+            parent: None,
+            "quic.connection",
+            otel.kind = endpoint,
+            otel.status_code = field::Empty,
+            otel.status_description = field::Empty,
+            quic.connection.id = int(meta.id),
+            quic.endpoint = endpoint,
+            network.local.address = field::Empty,
+            network.peer.address = field::Empty,
+            tls.server.name = field::Empty,
+            tls.next_protocol = field::Empty,
+            tls.cipher = field::Empty,
+            quic.close.reason = field::Empty,
+        );
+
+        let handshake = tracing::info_span!(target: TARGET, parent: &span, "quic.handshake");
+
+        ConnectionContext {
+            span,
+            endpoint_type: meta.endpoint_type.clone(),
+            handshake: Some(handshake),
+            migration: None,
+            streams: HashMap::new(),
+            finished_streams: Default::default(),
+        }
+    }
+
+    fn on_connection_started(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &event::events::ConnectionStarted,
+    ) {
+        let span = &context.span;
+        span.record(
+            "network.local.address",
+            field::display(&event.path.local_addr),
+        );
+        span.record(
+            "network.peer.address",
+            field::display(&event.path.remote_addr),
+        );
+    }
+
+    fn on_server_name_information(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &event::events::ServerNameInformation,
+    ) {
+        context
+            .span
+            .record("tls.server.name", event.chosen_server_name);
+    }
+
+    fn on_application_protocol_information(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &event::events::ApplicationProtocolInformation,
+    ) {
+        let protocol = String::from_utf8_lossy(event.chosen_application_protocol);
+        context.span.record("tls.next_protocol", &*protocol);
+    }
+
+    fn on_key_update(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &event::events::KeyUpdate,
+    ) {
+        // initial keys always use the same cipher suite, regardless of what was negotiated
+        if matches!(event.key_type, KeyType::Initial { .. }) {
+            return;
+        }
+
+        context
+            .span
+            .record("tls.cipher", event.cipher_suite.as_str());
+    }
+
+    fn on_handshake_status_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &event::events::HandshakeStatusUpdated,
+    ) {
+        match event.status {
+            HandshakeStatus::Complete { .. } => {
+                // dropping the span closes it
+                context.handshake = None;
+            }
+            HandshakeStatus::Confirmed { .. } => {
+                tracing::info!(target: TARGET, parent: &context.span, "quic.handshake.confirmed");
+            }
+            _ => {}
+        }
+    }
+
+    fn on_frame_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &event::events::FrameSent,
+    ) {
+        match event.frame {
+            Frame::Stream {
+                id,
+                offset,
+                len,
+                is_fin,
+                ..
+            } => {
+                let Some(stream) = context.stream(id) else {
+                    return;
+                };
+                stream.bytes_sent = stream.bytes_sent.max(offset + len as u64);
+                stream.is_send_finished |= is_fin;
+                context.on_stream_updated(id);
+            }
+            Frame::ResetStream { id, error_code, .. } => {
+                let Some(stream) = context.stream(id) else {
+                    return;
+                };
+                stream
+                    .span
+                    .record("quic.stream.reset_sent", int(error_code));
+                stream.is_send_finished = true;
+                context.on_stream_updated(id);
+            }
+            _ => {}
+        }
+    }
+
+    fn on_frame_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &event::events::FrameReceived,
+    ) {
+        match event.frame {
+            Frame::Stream {
+                id,
+                offset,
+                len,
+                is_fin,
+                ..
+            } => {
+                let Some(stream) = context.stream(id) else {
+                    return;
+                };
+                stream.bytes_received = stream.bytes_received.max(offset + len as u64);
+                stream.is_recv_finished |= is_fin;
+                context.on_stream_updated(id);
+            }
+            Frame::ResetStream { id, error_code, .. } => {
+                let Some(stream) = context.stream(id) else {
+                    return;
+                };
+                stream
+                    .span
+                    .record("quic.stream.reset_received", int(error_code));
+                stream.is_recv_finished = true;
+                context.on_stream_updated(id);
+            }
+            _ => {}
+        }
+    }
+
+    fn on_active_path_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &event::events::ActivePathUpdated,
+    ) {
+        context.span.record(
+            "network.peer.address",
+            field::display(&event.active.remote_addr),
+        );
+
+        // a new migration replaces any migration that is still in progress
+        let span = tracing::info_span!(
+            target: TARGET,
+            parent: &context.span,
+            "quic.path_migration",
+            quic.path.id = int(event.active.id),
+            quic.path.previous_id = int(event.previous.id),
+            network.peer.address = %event.active.remote_addr,
+            quic.path.previous_peer_address = %event.previous.remote_addr,
+            quic.path.validated = field::Empty,
+        );
+
+        context.migration = Some(Migration {
+            span,
+            path_id: event.active.id,
+        });
+    }
+
+    fn on_path_challenge_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &event::events::PathChallengeUpdated,
+    ) {
+        let Some(migration) = context.migration.as_ref() else {
+            return;
+        };
+
+        if migration.path_id != event.path.id {
+            return;
+        }
+
+        let is_validated = match event.path_challenge_status {
+            PathChallengeStatus::Validated { .. } => true,
+            PathChallengeStatus::Abandoned { .. } => false,
+            _ => return,
+        };
+
+        migration.span.record("quic.path.validated", is_validated);
+        context.migration = None;
+    }
+
+    fn on_connection_migration_denied(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &event::events::ConnectionMigrationDenied,
+    ) {
+        tracing::info!(
+            target: TARGET,
+            parent: &context.span,
+            reason = %event.reason.as_variant().name,
+            "quic.connection_migration_denied"
+        );
+    }
+
+    fn on_connection_closed(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &event::events::ConnectionClosed,
+    ) {
+        let span = &context.span;
+        let error = &event.error;
+
+        span.record("quic.close.reason", field::display(error.as_variant().name));
+
+        match error {
+            connection::Error::Closed { .. } | connection::Error::IdleTimerExpired { .. } => {
+                span.record("otel.status_code", "OK");
+            }
+            _ => {
+                span.record("otel.status_code", "ERROR");
+                span.record("otel.status_description", field::display(error));
+            }
+        }
+
+        // close all of the child spans that are still open
+        context.handshake = None;
+        context.migration = None;
+        context.streams.clear();
+    }
+}
+
+/// Converts the value to an `i64`, which is the only integer type that OpenTelemetry supports
+///
+/// `tracing-opentelemetry` exports `u64` fields as strings, so this keeps the IDs and counts
+/// numeric. All of them are bounded by the QUIC variable-length integer encoding.
+fn int(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+fn endpoint_name(endpoint_type: &EndpointType) -> &'static str {
+    match endpoint_type {
+        EndpointType::Client { .. } => "client",
+        _ => "server",
+    }
+}
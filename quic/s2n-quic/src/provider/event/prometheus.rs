@@ -0,0 +1,518 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use core::{
+    convert::Infallible,
+    fmt::{self, Write as _},
+    future::{ready, Ready},
+    task::{Context, Poll},
+};
+use s2n_quic_core::event::metrics::aggregate::{
+    self, info, BoolRecorder, Info, Metric, NominalRecorder, Recorder, Units,
+};
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// The prefix applied to each exported metric name
+const PREFIX: &str = "s2n_quic_";
+
+/// The content type of the Prometheus text exposition format
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// The number of finite histogram buckets
+///
+/// Bucket `n` has an upper bound of `4^n`, in microseconds for durations and in the
+/// metric's own units otherwise. This covers values up to ~18 minutes or ~1 GiB.
+const BUCKETS: usize = 16;
+
+/// The aggregate event subscriber which records into a [`Registry`]
+pub type Subscriber = aggregate::Subscriber<Registry>;
+
+/// A metrics registry which aggregates endpoint and connection events and renders
+/// them in the [Prometheus text exposition format](https://prometheus.io/docs/instrumenting/exposition_formats/)
+///
+/// Recording values only updates atomic counters, so the registry can be shared by
+/// any number of endpoints. Metrics with the same name are aggregated across all of
+/// the subscribers created from a registry.
+///
+/// Counters are exported with a `_total` suffix, nominal metrics are labeled with
+/// their `variant`, and measures and timers are exported as histograms. Durations are
+/// exported in seconds.
+///
+/// NOTE: The set of exported metrics is derived from the aggregate event metrics and
+/// is subject to change.
+///
+/// # Examples
+///
+/// Enables the Prometheus registry for the server and mounts its [`Handler`] on a
+/// `/metrics` route of an application's HTTP server.
+///
+/// ```rust,ignore
+/// use std::error::Error;
+/// use s2n_quic::{provider::event::prometheus, Server};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn Error>> {
+///     let registry = prometheus::Registry::default();
+///
+///     let server = Server::builder()
+///         .with_event(registry.subscriber())?
+///         .start()?;
+///
+///     let app = axum::Router::new().route_service("/metrics", registry.handler());
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Registry(Arc<Mutex<Families>>);
+
+impl Registry {
+    /// Creates a subscriber which records endpoint and connection events into the registry
+    pub fn subscriber(&self) -> Subscriber {
+        Subscriber::new(self.clone())
+    }
+
+    /// Returns an HTTP handler which responds with the rendered metrics
+    pub fn handler(&self) -> Handler {
+        Handler(self.clone())
+    }
+
+    /// Renders all of the registered metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        // writing to a `String` is infallible
+        let _ = self.write(&mut out);
+        out
+    }
+
+    fn write<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        let families = self.0.lock().unwrap_or_else(|err| err.into_inner());
+
+        for family in families.0.values() {
+            family.write(out)?;
+        }
+
+        Ok(())
+    }
+
+    fn register(
+        &self,
+        info: &'static Info,
+        variant: Option<&'static info::Variant>,
+        kind: Kind,
+    ) -> Series {
+        let name: &'static str = info.name;
+        let variant: Option<&'static str> = variant.map(|variant| &**variant.name);
+
+        let mut families = self.0.lock().unwrap_or_else(|err| err.into_inner());
+
+        let family = families.0.entry(name).or_insert_with(|| Family {
+            info,
+            kind,
+            series: BTreeMap::new(),
+        });
+
+        debug_assert_eq!(family.kind, kind, "{name} registered as {kind:?}");
+
+        family
+            .series
+            .entry(variant)
+            .or_insert_with(|| Series::new(kind))
+            .clone()
+    }
+}
+
+impl fmt::Display for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f)
+    }
+}
+
+impl aggregate::Registry for Registry {
+    type Counter = Series;
+    type BoolCounter = Series;
+    type NominalCounter = Series;
+    type Measure = Series;
+    type Gauge = Series;
+    type Timer = Series;
+    type NominalTimer = Series;
+
+    #[inline]
+    fn register_counter(&self, info: &'static Info) -> Self::Counter {
+        self.register(info, None, Kind::Counter)
+    }
+
+    #[inline]
+    fn register_bool_counter(&self, info: &'static Info) -> Self::BoolCounter {
+        self.register(info, None, Kind::BoolCounter)
+    }
+
+    #[inline]
+    fn register_nominal_counter(
+        &self,
+        info: &'static Info,
+        variant: &'static info::Variant,
+    ) -> Self::NominalCounter {
+        self.register(info, Some(variant), Kind::Counter)
+    }
+
+    #[inline]
+    fn register_measure(&self, info: &'static Info) -> Self::Measure {
+        self.register(info, None, Kind::Histogram)
+    }
+
+    #[inline]
+    fn register_gauge(&self, info: &'static Info) -> Self::Gauge {
+        self.register(info, None, Kind::Gauge)
+    }
+
+    #[inline]
+    fn register_timer(&self, info: &'static Info) -> Self::Timer {
+        self.register(info, None, Kind::Histogram)
+    }
+
+    #[inline]
+    fn register_nominal_timer(
+        &self,
+        info: &'static Info,
+        variant: &'static info::Variant,
+    ) -> Self::NominalTimer {
+        self.register(info, Some(variant), Kind::Histogram)
+    }
+}
+
+/// An HTTP handler which responds to `GET` and `HEAD` requests with the metrics in a [`Registry`]
+///
+/// The handler implements [`tower_service::Service`] so it can be mounted in any
+/// application that routes [`http::Request`]s, regardless of the request body type.
+#[derive(Clone, Debug)]
+pub struct Handler(Registry);
+
+impl Handler {
+    /// Builds the response for the given request
+    pub fn respond<B>(&self, request: &http::Request<B>) -> http::Response<String> {
+        let method = request.method();
+
+        let (status, body) = if method == http::Method::GET {
+            (http::StatusCode::OK, self.0.render())
+        } else if method == http::Method::HEAD {
+            (http::StatusCode::OK, String::new())
+        } else {
+            (http::StatusCode::METHOD_NOT_ALLOWED, String::new())
+        };
+
+        let mut response = http::Response::new(body);
+        *response.status_mut() = status;
+
+        let headers = response.headers_mut();
+        if status == http::StatusCode::OK {
+            headers.insert(
+                http::header::CONTENT_TYPE,
+                http::HeaderValue::from_static(CONTENT_TYPE),
+            );
+        } else {
+            headers.insert(
+                http::header::ALLOW,
+                http::HeaderValue::from_static("GET, HEAD"),
+            );
+        }
+
+        response
+    }
+}
+
+impl<B> tower_service::Service<http::Request<B>> for Handler {
+    type Response = http::Response<String>;
+    type Error = Infallible;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    #[inline]
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    #[inline]
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        ready(Ok(self.respond(&request)))
+    }
+}
+
+#[derive(Debug, Default)]
+struct Families(BTreeMap<&'static str, Family>);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Counter,
+    BoolCounter,
+    Gauge,
+    Histogram,
+}
+
+#[derive(Debug)]
+struct Family {
+    info: &'static Info,
+    kind: Kind,
+    /// The series for the family, keyed by the optional variant name
+    series: BTreeMap<Option<&'static str>, Series>,
+}
+
+impl Family {
+    fn write<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        let is_duration = self.info.units == Units::Duration;
+
+        let mut name = String::with_capacity(PREFIX.len() + self.info.name.len() + 8);
+        name.push_str(PREFIX);
+        for c in self.info.name.chars() {
+            name.push(if c.is_ascii_alphanumeric() { c } else { '_' });
+        }
+
+        let ty = match self.kind {
+            Kind::Counter | Kind::BoolCounter => {
+                if !name.ends_with("_total") {
+                    name.push_str("_total");
+                }
+                "counter"
+            }
+            Kind::Gauge | Kind::Histogram => {
+                if is_duration {
+                    name.push_str("_seconds");
+                }
+                if self.kind == Kind::Gauge {
+                    "gauge"
+                } else {
+                    "histogram"
+                }
+            }
+        };
+
+        writeln!(out, "# TYPE {name} {ty}")?;
+
+        for (variant, series) in &self.series {
+            let variant = *variant;
+            let labels = Labels { variant, le: None };
+
+            match &series.0 {
+                Storage::Counter(counter) => {
+                    writeln!(out, "{name}{labels} {}", counter.load(Ordering::Relaxed))?;
+                }
+                Storage::Bool(counters) => {
+                    for (value, counter) in ["false", "true"].iter().zip(counters.iter()) {
+                        let count = counter.load(Ordering::Relaxed);
+                        writeln!(out, "{name}{{value=\"{value}\"}} {count}")?;
+                    }
+                }
+                Storage::Gauge(gauge) => {
+                    let value = Value(gauge.load(Ordering::Relaxed), is_duration);
+                    writeln!(out, "{name}{labels} {value}")?;
+                }
+                Storage::Histogram(histogram) => {
+                    histogram.write(out, &name, variant, is_duration)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The storage for a single series in a metric family
+#[derive(Clone, Debug)]
+pub struct Series(Storage);
+
+#[derive(Clone, Debug)]
+enum Storage {
+    Counter(Arc<AtomicU64>),
+    Bool(Arc<[AtomicU64; 2]>),
+    Gauge(Arc<AtomicU64>),
+    Histogram(Arc<Histogram>),
+}
+
+impl Series {
+    fn new(kind: Kind) -> Self {
+        Self(match kind {
+            Kind::Counter => Storage::Counter(Default::default()),
+            Kind::BoolCounter => Storage::Bool(Default::default()),
+            Kind::Gauge => Storage::Gauge(Default::default()),
+            Kind::Histogram => Storage::Histogram(Default::default()),
+        })
+    }
+
+    #[inline]
+    fn record_value(&self, value: u64) {
+        match &self.0 {
+            Storage::Counter(counter) => {
+                counter.fetch_add(value, Ordering::Relaxed);
+            }
+            Storage::Bool(counters) => {
+                counters[(value != 0) as usize].fetch_add(1, Ordering::Relaxed);
+            }
+            Storage::Gauge(gauge) => gauge.store(value, Ordering::Relaxed),
+            Storage::Histogram(histogram) => histogram.record(value),
+        }
+    }
+}
+
+impl Recorder for Series {
+    #[inline]
+    fn record<T: Metric>(&self, _info: &'static Info, value: T) {
+        self.record_value(value.as_u64())
+    }
+}
+
+impl NominalRecorder for Series {
+    #[inline]
+    fn record<T: Metric>(&self, _info: &'static Info, _variant: &'static info::Variant, value: T) {
+        // the variant was already resolved to a series on registration
+        self.record_value(value.as_u64())
+    }
+}
+
+impl BoolRecorder for Series {
+    #[inline]
+    fn record(&self, _info: &'static Info, value: bool) {
+        self.record_value(value as u64)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Histogram {
+    /// Non-cumulative bucket counts, with the last bucket holding values beyond the
+    /// largest bound
+    buckets: [AtomicU64; BUCKETS + 1],
+    sum: AtomicU64,
+}
+
+impl Histogram {
+    #[inline]
+    fn record(&self, value: u64) {
+        self.buckets[bucket_index(value)].fetch_add(1, Ordering::Relaxed);
+        self.sum.fetch_add(value, Ordering::Relaxed);
+    }
+
+    fn write<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        name: &str,
+        variant: Option<&str>,
+        is_duration: bool,
+    ) -> fmt::Result {
+        let mut count = 0;
+        for (idx, bucket) in self.buckets.iter().enumerate() {
+            count += bucket.load(Ordering::Relaxed);
+            let le = (idx < BUCKETS).then(|| Value(1 << (2 * idx), is_duration));
+            let labels = Labels {
+                variant,
+                le: Some(le),
+            };
+            writeln!(out, "{name}_bucket{labels} {count}")?;
+        }
+
+        let labels = Labels { variant, le: None };
+        let sum = Value(self.sum.load(Ordering::Relaxed), is_duration);
+        writeln!(out, "{name}_sum{labels} {sum}")?;
+        // the count is derived from the buckets so the two are always consistent
+        writeln!(out, "{name}_count{labels} {count}")?;
+
+        Ok(())
+    }
+}
+
+/// Returns the index of the smallest bucket with a bound that is at least `value`
+#[inline]
+fn bucket_index(value: u64) -> usize {
+    if value <= 1 {
+        return 0;
+    }
+
+    // ceil(log4(value))
+    let bits = (u64::BITS - (value - 1).leading_zeros()) as usize;
+    ((bits + 1) / 2).min(BUCKETS)
+}
+
+/// A recorded value, which is converted from microseconds to seconds for durations
+struct Value(u64, bool);
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.1 {
+            write!(f, "{}", self.0 as f64 / 1_000_000.0)
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+/// The labels for a series
+///
+/// The `le` label is only written for histogram buckets, with `None` written as `+Inf`.
+struct Labels<'a> {
+    variant: Option<&'a str>,
+    le: Option<Option<Value>>,
+}
+
+impl fmt::Display for Labels<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.variant.is_none() && self.le.is_none() {
+            return Ok(());
+        }
+
+        f.write_char('{')?;
+
+        if let Some(variant) = self.variant {
+            write!(f, "variant=\"{}\"", Escaped(variant))?;
+            if self.le.is_some() {
+                f.write_char(',')?;
+            }
+        }
+
+        match &self.le {
+            Some(Some(le)) => write!(f, "le=\"{le}\"")?,
+            Some(None) => f.write_str("le=\"+Inf\"")?,
+            None => {}
+        }
+
+        f.write_char('}')
+    }
+}
+
+/// Escapes a label value
+struct Escaped<'a>(&'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '\\' => f.write_str("\\\\")?,
+                '"' => f.write_str("\\\"")?,
+                '\n' => f.write_str("\\n")?,
+                c => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_index_test() {
+        assert_eq!(bucket_index(0), 0);
+        assert_eq!(bucket_index(1), 0);
+        assert_eq!(bucket_index(2), 1);
+        assert_eq!(bucket_index(4), 1);
+        assert_eq!(bucket_index(5), 2);
+        assert_eq!(bucket_index(16), 2);
+        assert_eq!(bucket_index(17), 3);
+        assert_eq!(bucket_index(1 << 30), 15);
+        assert_eq!(bucket_index((1 << 30) + 1), BUCKETS);
+        assert_eq!(bucket_index(u64::MAX), BUCKETS);
+    }
+}
@@ -0,0 +1,858 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::provider::event;
+use core::{fmt, time::Duration};
+use s2n_quic_core::event::{metrics::aggregate::AsVariant, Event};
+use std::{
+    fmt::Write as _,
+    fs::{self, File},
+    io::{self, BufWriter, Write as _},
+    net::SocketAddr,
+    path::PathBuf,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The record separator which prefixes each JSON text in a JSON-SEQ stream
+///
+/// See <https://www.rfc-editor.org/rfc/rfc7464#section-2.2>
+const RECORD_SEPARATOR: char = '\u{1e}';
+
+/// An event subscriber that writes a [qlog](https://datatracker.ietf.org/doc/draft-ietf-quic-qlog-main-schema/)
+/// trace for each connection
+///
+/// Each connection is written to its own file in the configured directory, named
+/// `<original destination connection id>_<server|client>.sqlog`, so the client and
+/// server traces for a connection share a name. Traces use the JSON-SEQ serialization
+/// and can be loaded into tools like [qvis](https://qvis.quictools.info/).
+///
+/// If a trace file cannot be created (including when a file with the same name already
+/// exists) or written to, tracing is disabled for that connection and the connection itself is unaffected.
+///
+/// NOTE: The set of events and fields that are written is subject to change and
+/// should not be relied on to remain consistent over time.
+///
+/// # Examples
+///
+/// Enables the qlog event subscriber for the server, writing traces to the
+/// directory in the `QLOGDIR` environment variable.
+///
+/// ```rust,ignore
+/// use std::error::Error;
+/// use s2n_quic::{provider::event, Server};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn Error>> {
+///     let subscriber = event::qlog::Builder::default()
+///         .with_directory(std::env::var("QLOGDIR")?)
+///         .build()?;
+///
+///     let server = Server::builder()
+///         .with_event(subscriber)?
+///         .start()?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Subscriber {
+    directory: Arc<PathBuf>,
+    title: Arc<str>,
+}
+
+pub struct Builder {
+    directory: PathBuf,
+    title: String,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            directory: PathBuf::from("."),
+            title: "s2n-quic".to_string(),
+        }
+    }
+}
+
+impl Builder {
+    /// Sets the directory that trace files will be written to
+    ///
+    /// By default, traces are written to the current working directory.
+    pub fn with_directory<P: Into<PathBuf>>(mut self, directory: P) -> Self {
+        self.directory = directory.into();
+        self
+    }
+
+    /// Sets the title that is included in each trace file
+    pub fn with_title<T: Into<String>>(mut self, title: T) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Builds the [`Subscriber`], creating the trace directory if it does not already exist
+    pub fn build(self) -> io::Result<Subscriber> {
+        fs::create_dir_all(&self.directory)?;
+
+        Ok(Subscriber {
+            directory: Arc::new(self.directory),
+            title: self.title.into(),
+        })
+    }
+}
+
+/// The qlog trace for a single connection
+pub struct ConnectionContext {
+    trace: Option<Trace>,
+}
+
+impl ConnectionContext {
+    #[inline]
+    fn with_trace<F: FnOnce(&mut Trace) -> io::Result<()>>(&mut self, f: F) {
+        if let Some(trace) = self.trace.as_mut() {
+            if f(trace).is_err() {
+                // stop tracing the connection if we couldn't write the event
+                self.trace = None;
+            }
+        }
+    }
+}
+
+struct Trace {
+    out: BufWriter<File>,
+    start: event::Timestamp,
+    /// Scratch space for serializing the current record
+    record: String,
+    /// Frames that have been written to a packet that has not yet been sent
+    sent_frames: String,
+    /// A received packet whose frames are still being processed
+    ///
+    /// Processing a frame can emit other events (e.g. an ACK frame updating recovery
+    /// metrics) so the packet is only written once the next packet is sent or received.
+    received: Option<ReceivedPacket>,
+}
+
+struct ReceivedPacket {
+    time: Duration,
+    header: event::events::PacketHeader,
+    frames: String,
+}
+
+impl Trace {
+    fn open(
+        subscriber: &Subscriber,
+        meta: &event::ConnectionMeta,
+        info: &event::ConnectionInfo,
+    ) -> io::Result<Self> {
+        let vantage_point = vantage_point(&meta.endpoint_type);
+        let group_id = Hex(info.original_destination_connection_id.bytes);
+        let path = subscriber
+            .directory
+            .join(format!("{group_id}_{vantage_point}.sqlog"));
+        // never overwrite an existing trace
+        let file = File::options().write(true).create_new(true).open(path)?;
+        let out = BufWriter::new(file);
+
+        let mut trace = Self {
+            out,
+            start: meta.timestamp,
+            record: String::new(),
+            sent_frames: String::new(),
+            received: None,
+        };
+
+        // Relative event times are anchored to the wall clock time that the connection started
+        let reference_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        let mut header = Object::record(&mut trace.record);
+        header.str("qlog_version", "0.3");
+        header.str("qlog_format", "JSON-SEQ");
+        header.str("title", &subscriber.title);
+        header.object("trace", |t| {
+            t.object("vantage_point", |v| {
+                v.str("type", vantage_point);
+            });
+            t.object("common_fields", |c| {
+                c.str("time_format", "relative");
+                c.num("reference_time", Millis(reference_time));
+                c.str("group_id", &group_id);
+                c.str("protocol_type", "QUIC");
+            });
+        });
+        header.finish();
+        trace.flush_record()?;
+
+        Ok(trace)
+    }
+
+    /// Writes a single qlog event record with the provided data
+    fn event<F: FnOnce(&mut Object)>(&mut self, time: Duration, name: &str, data: F) {
+        let mut record = Object::record(&mut self.record);
+        record.num("time", Millis(time));
+        record.str("name", name);
+        record.object("data", data);
+        record.finish();
+    }
+
+    fn write<F: FnOnce(&mut Object)>(
+        &mut self,
+        meta: &event::ConnectionMeta,
+        name: &str,
+        data: F,
+    ) -> io::Result<()> {
+        let time = meta.timestamp.saturating_duration_since(self.start);
+        self.event(time, name, data);
+        self.flush_record()
+    }
+
+    /// Writes the pending received packet, now that all of its frames have been processed
+    fn flush_received(&mut self) -> io::Result<()> {
+        if let Some(packet) = self.received.take() {
+            self.event(packet.time, "transport:packet_received", |data| {
+                data.object("header", |h| packet_header(h, &packet.header));
+                data.raw("frames", |out| {
+                    out.push('[');
+                    out.push_str(&packet.frames);
+                    out.push(']');
+                });
+            });
+            self.flush_record()?;
+        }
+        Ok(())
+    }
+
+    fn flush_record(&mut self) -> io::Result<()> {
+        self.out.write_all(self.record.as_bytes())?;
+        self.record.clear();
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.flush_received()?;
+        self.out.flush()
+    }
+}
+
+impl Drop for Trace {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
+
+impl event::Subscriber for Subscriber {
+    type ConnectionContext = ConnectionContext;
+
+    #[inline]
+    fn create_connection_context(
+        &mut self,
+        meta: &event::ConnectionMeta,
+        info: &event::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        ConnectionContext {
+            trace: Trace::open(self, meta, info).ok(),
+        }
+    }
+
+    fn on_connection_started(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &event::events::ConnectionStarted,
+    ) {
+        context.with_trace(|trace| {
+            trace.write(meta, "connectivity:connection_started", |data| {
+                let local = SocketAddr::from(&event.path.local_addr);
+                let remote = SocketAddr::from(&event.path.remote_addr);
+                data.str("ip_version", if remote.is_ipv4() { "ipv4" } else { "ipv6" });
+                data.str("src_ip", local.ip());
+                data.num("src_port", local.port());
+                data.str("dst_ip", remote.ip());
+                data.num("dst_port", remote.port());
+                data.str("src_cid", Hex(event.path.local_cid.bytes));
+                data.str("dst_cid", Hex(event.path.remote_cid.bytes));
+            })
+        })
+    }
+
+    fn on_frame_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &event::events::FrameSent,
+    ) {
+        if let Some(trace) = context.trace.as_mut() {
+            push_frame(&mut trace.sent_frames, &event.frame);
+        }
+    }
+
+    fn on_packet_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &event::events::PacketSent,
+    ) {
+        context.with_trace(|trace| {
+            trace.flush_received()?;
+            let frames = core::mem::take(&mut trace.sent_frames);
+            let result = trace.write(meta, "transport:packet_sent", |data| {
+                data.object("header", |h| packet_header(h, &event.packet_header));
+                data.object("raw", |raw| {
+                    raw.num("length", event.packet_len);
+                });
+                data.raw("frames", |out| {
+                    out.push('[');
+                    out.push_str(&frames);
+                    out.push(']');
+                });
+            });
+            // reuse the frame buffer allocation for the next packet
+            trace.sent_frames = frames;
+            trace.sent_frames.clear();
+            result
+        })
+    }
+
+    fn on_packet_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &event::events::PacketReceived,
+    ) {
+        context.with_trace(|trace| {
+            trace.flush_received()?;
+            trace.received = Some(ReceivedPacket {
+                time: meta.timestamp.saturating_duration_since(trace.start),
+                header: event.packet_header.clone(),
+                frames: String::new(),
+            });
+            Ok(())
+        })
+    }
+
+    fn on_frame_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &event::ConnectionMeta,
+        event: &event::events::FrameReceived,
+    ) {
+        if let Some(packet) = context
+            .trace
+            .as_mut()
+            .and_then(|trace| trace.received.as_mut())
+        {
+            push_frame(&mut packet.frames, &event.frame);
+        }
+    }
+
+    fn on_packet_lost(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &event::events::PacketLost,
+    ) {
+        context.with_trace(|trace| {
+            trace.write(meta, "recovery:packet_lost", |data| {
+                data.object("header", |h| packet_header(h, &event.packet_header));
+                data.object("raw", |raw| {
+                    raw.num("length", event.bytes_lost);
+                });
+                data.bool("is_mtu_probe", event.is_mtu_probe);
+            })
+        })
+    }
+
+    fn on_packet_dropped(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &event::events::PacketDropped,
+    ) {
+        context.with_trace(|trace| {
+            trace.write(meta, "transport:packet_dropped", |data| {
+                data.str(
+                    "trigger",
+                    event.reason.as_variant().name.to_ascii_lowercase(),
+                );
+            })
+        })
+    }
+
+    fn on_recovery_metrics(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &event::events::RecoveryMetrics,
+    ) {
+        context.with_trace(|trace| {
+            trace.write(meta, "recovery:metrics_updated", |data| {
+                data.num("min_rtt", Millis(event.min_rtt));
+                data.num("smoothed_rtt", Millis(event.smoothed_rtt));
+                data.num("latest_rtt", Millis(event.latest_rtt));
+                data.num("rtt_variance", Millis(event.rtt_variance));
+                data.num("pto_count", event.pto_count);
+                data.num("congestion_window", event.congestion_window);
+                data.num("bytes_in_flight", event.bytes_in_flight);
+            })
+        })
+    }
+
+    fn on_key_update(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &event::events::KeyUpdate,
+    ) {
+        use event::events::KeyType;
+
+        let (space, generation) = match event.key_type {
+            KeyType::Initial { .. } => ("initial", None),
+            KeyType::Handshake { .. } => ("handshake", None),
+            KeyType::ZeroRtt { .. } => ("0rtt", None),
+            KeyType::OneRtt { generation, .. } => ("1rtt", Some(generation)),
+            _ => return,
+        };
+
+        context.with_trace(|trace| {
+            trace.write(meta, "security:key_updated", |data| {
+                data.str("key_type", KeyName(space, &meta.endpoint_type));
+                data.str("cipher_suite", event.cipher_suite.as_str());
+                if let Some(generation) = generation {
+                    data.num("key_phase", generation);
+                }
+                let trigger = if generation.unwrap_or(0) == 0 {
+                    "tls"
+                } else {
+                    "local_update"
+                };
+                data.str("trigger", trigger);
+            })
+        })
+    }
+
+    fn on_key_space_discarded(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &event::events::KeySpaceDiscarded,
+    ) {
+        use event::events::KeySpace;
+
+        let space = match event.space {
+            KeySpace::Initial { .. } => "initial",
+            KeySpace::Handshake { .. } => "handshake",
+            KeySpace::ZeroRtt { .. } => "0rtt",
+            KeySpace::OneRtt { .. } => "1rtt",
+            _ => return,
+        };
+
+        context.with_trace(|trace| {
+            trace.write(meta, "security:key_discarded", |data| {
+                data.str("key_type", KeyName(space, &meta.endpoint_type));
+                data.str("trigger", "tls");
+            })
+        })
+    }
+
+    fn on_path_created(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &event::events::PathCreated,
+    ) {
+        context.with_trace(|trace| {
+            trace.write(meta, event::events::PathCreated::NAME, |data| {
+                data.object("active", |p| path(p, &event.active));
+                data.object("new", |p| path(p, &event.new));
+            })
+        })
+    }
+
+    fn on_active_path_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &event::events::ActivePathUpdated,
+    ) {
+        context.with_trace(|trace| {
+            trace.write(meta, event::events::ActivePathUpdated::NAME, |data| {
+                data.object("previous", |p| path(p, &event.previous));
+                data.object("active", |p| path(p, &event.active));
+            })
+        })
+    }
+
+    fn on_mtu_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &event::events::MtuUpdated,
+    ) {
+        context.with_trace(|trace| {
+            trace.write(meta, "connectivity:mtu_updated", |data| {
+                data.num("path_id", event.path_id);
+                data.num("new", event.mtu);
+                data.bool("done", event.search_complete);
+            })
+        })
+    }
+
+    fn on_connection_closed(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &event::ConnectionMeta,
+        event: &event::events::ConnectionClosed,
+    ) {
+        context.with_trace(|trace| {
+            trace.write(meta, "connectivity:connection_closed", |data| {
+                data.str("reason", &event.error);
+            })?;
+            trace.finish()
+        })
+    }
+}
+
+fn vantage_point(endpoint_type: &event::events::EndpointType) -> &'static str {
+    match endpoint_type {
+        event::events::EndpointType::Client { .. } => "client",
+        _ => "server",
+    }
+}
+
+fn packet_header(h: &mut Object, header: &event::events::PacketHeader) {
+    use event::events::PacketHeader;
+
+    let (packet_type, number) = match header {
+        PacketHeader::Initial { number, .. } => ("initial", Some(*number)),
+        PacketHeader::Handshake { number, .. } => ("handshake", Some(*number)),
+        PacketHeader::ZeroRtt { number, .. } => ("0RTT", Some(*number)),
+        PacketHeader::OneRtt { number, .. } => ("1RTT", Some(*number)),
+        PacketHeader::Retry { .. } => ("retry", None),
+        PacketHeader::VersionNegotiation { .. } => ("version_negotiation", None),
+        PacketHeader::StatelessReset { .. } => ("stateless_reset", None),
+        _ => ("unknown", None),
+    };
+
+    h.str("packet_type", packet_type);
+    if let Some(number) = number {
+        h.num("packet_number", number);
+    }
+}
+
+fn path(p: &mut Object, path: &event::events::Path) {
+    p.num("id", path.id);
+    p.str("local_addr", &path.local_addr);
+    p.str("remote_addr", &path.remote_addr);
+    p.str("local_cid", Hex(path.local_cid.bytes));
+    p.str("remote_cid", Hex(path.remote_cid.bytes));
+}
+
+/// Appends the qlog representation of `frame` to a comma-separated list of frames
+fn push_frame(out: &mut String, frame: &event::events::Frame) {
+    use event::events::{Frame, StreamType};
+
+    if !out.is_empty() {
+        out.push(',');
+    }
+
+    let stream_type = |stream_type: &StreamType| match stream_type {
+        StreamType::Unidirectional { .. } => "unidirectional",
+        _ => "bidirectional",
+    };
+
+    let mut f = Object::new(out);
+    match frame {
+        Frame::Padding { .. } => f.str("frame_type", "padding"),
+        Frame::Ping { .. } => f.str("frame_type", "ping"),
+        Frame::Ack {
+            ecn_counts,
+            largest_acknowledged,
+            ack_range_count,
+            ..
+        } => {
+            f.str("frame_type", "ack");
+            f.num("largest_acknowledged", largest_acknowledged);
+            f.num("ack_range_count", ack_range_count);
+            if let Some(ecn) = ecn_counts {
+                f.num("ect0", ecn.ect_0_count);
+                f.num("ect1", ecn.ect_1_count);
+                f.num("ce", ecn.ce_count);
+            }
+        }
+        Frame::ResetStream {
+            id,
+            error_code,
+            final_size,
+            ..
+        } => {
+            f.str("frame_type", "reset_stream");
+            f.num("stream_id", id);
+            f.num("error_code", error_code);
+            f.num("final_size", final_size);
+        }
+        Frame::StopSending { id, error_code, .. } => {
+            f.str("frame_type", "stop_sending");
+            f.num("stream_id", id);
+            f.num("error_code", error_code);
+        }
+        Frame::Crypto { offset, len, .. } => {
+            f.str("frame_type", "crypto");
+            f.num("offset", offset);
+            f.num("length", len);
+        }
+        Frame::NewToken { .. } => f.str("frame_type", "new_token"),
+        Frame::Stream {
+            id,
+            offset,
+            len,
+            is_fin,
+            ..
+        } => {
+            f.str("frame_type", "stream");
+            f.num("stream_id", id);
+            f.num("offset", offset);
+            f.num("length", len);
+            f.bool("fin", *is_fin);
+        }
+        Frame::MaxData { value, .. } => {
+            f.str("frame_type", "max_data");
+            f.num("maximum", value);
+        }
+        Frame::MaxStreamData { id, value, .. } => {
+            f.str("frame_type", "max_stream_data");
+            f.num("stream_id", id);
+            f.num("maximum", value);
+        }
+        Frame::MaxStreams {
+            stream_type: ty,
+            value,
+            ..
+        } => {
+            f.str("frame_type", "max_streams");
+            f.str("stream_type", stream_type(ty));
+            f.num("maximum", value);
+        }
+        Frame::DataBlocked { data_limit, .. } => {
+            f.str("frame_type", "data_blocked");
+            f.num("limit", data_limit);
+        }
+        Frame::StreamDataBlocked {
+            stream_id,
+            stream_data_limit,
+            ..
+        } => {
+            f.str("frame_type", "stream_data_blocked");
+            f.num("stream_id", stream_id);
+            f.num("limit", stream_data_limit);
+        }
+        Frame::StreamsBlocked {
+            stream_type: ty,
+            stream_limit,
+            ..
+        } => {
+            f.str("frame_type", "streams_blocked");
+            f.str("stream_type", stream_type(ty));
+            f.num("limit", stream_limit);
+        }
+        Frame::NewConnectionId {
+            sequence_number,
+            retire_prior_to,
+            ..
+        } => {
+            f.str("frame_type", "new_connection_id");
+            f.num("sequence_number", sequence_number);
+            f.num("retire_prior_to", retire_prior_to);
+        }
+        Frame::RetireConnectionId { .. } => f.str("frame_type", "retire_connection_id"),
+        Frame::PathChallenge { .. } => f.str("frame_type", "path_challenge"),
+        Frame::PathResponse { .. } => f.str("frame_type", "path_response"),
+        Frame::ConnectionClose { .. } => f.str("frame_type", "connection_close"),
+        Frame::HandshakeDone { .. } => f.str("frame_type", "handshake_done"),
+        Frame::Datagram { len, .. } => {
+            f.str("frame_type", "datagram");
+            f.num("length", len);
+        }
+        Frame::DcStatelessResetTokens { .. } => f.str("frame_type", "dc_stateless_reset_tokens"),
+        Frame::AckFrequency {
+            sequence_number,
+            ack_eliciting_threshold,
+            request_max_ack_delay,
+            reordering_threshold,
+            ..
+        } => {
+            f.str("frame_type", "ack_frequency");
+            f.num("sequence_number", sequence_number);
+            f.num("ack_eliciting_threshold", ack_eliciting_threshold);
+            f.num("request_max_ack_delay", Millis(*request_max_ack_delay));
+            f.num("reordering_threshold", reordering_threshold);
+        }
+        Frame::ImmediateAck { .. } => f.str("frame_type", "immediate_ack"),
+        Frame::ResetStreamAt {
+            id,
+            error_code,
+            final_size,
+            reliable_size,
+            ..
+        } => {
+            f.str("frame_type", "reset_stream_at");
+            f.num("stream_id", id);
+            f.num("error_code", error_code);
+            f.num("final_size", final_size);
+            f.num("reliable_size", reliable_size);
+        }
+        Frame::PathAck {
+            path_id,
+            largest_acknowledged,
+            ack_range_count,
+            ..
+        } => {
+            f.str("frame_type", "path_ack");
+            f.num("path_id", path_id);
+            f.num("largest_acknowledged", largest_acknowledged);
+            f.num("ack_range_count", ack_range_count);
+        }
+        Frame::PathAbandon {
+            path_id,
+            error_code,
+            ..
+        } => {
+            f.str("frame_type", "path_abandon");
+            f.num("path_id", path_id);
+            f.num("error_code", error_code);
+        }
+        _ => f.str("frame_type", "unknown"),
+    }
+    f.finish_inline();
+}
+
+/// A minimal JSON object writer
+///
+/// Only the subset of JSON needed for qlog records is supported.
+struct Object<'a> {
+    out: &'a mut String,
+    is_empty: bool,
+}
+
+impl<'a> Object<'a> {
+    /// Starts a top-level JSON-SEQ record
+    #[inline]
+    fn record(out: &'a mut String) -> Self {
+        out.push(RECORD_SEPARATOR);
+        Self::new(out)
+    }
+
+    #[inline]
+    fn new(out: &'a mut String) -> Self {
+        out.push('{');
+        Self {
+            out,
+            is_empty: true,
+        }
+    }
+
+    #[inline]
+    fn key(&mut self, key: &str) {
+        if !core::mem::take(&mut self.is_empty) {
+            self.out.push(',');
+        }
+        self.out.push('"');
+        self.out.push_str(key);
+        self.out.push_str("\":");
+    }
+
+    /// Writes a string value, escaping it as required
+    fn str<V: fmt::Display>(&mut self, key: &str, value: V) {
+        self.key(key);
+        self.out.push('"');
+        let _ = write!(Escaped(&mut *self.out), "{value}");
+        self.out.push('"');
+    }
+
+    fn num<V: fmt::Display>(&mut self, key: &str, value: V) {
+        self.key(key);
+        let _ = write!(self.out, "{value}");
+    }
+
+    fn bool(&mut self, key: &str, value: bool) {
+        self.key(key);
+        self.out.push_str(if value { "true" } else { "false" });
+    }
+
+    fn object<F: FnOnce(&mut Object)>(&mut self, key: &str, f: F) {
+        self.key(key);
+        let mut object = Object::new(&mut *self.out);
+        f(&mut object);
+        object.finish_inline();
+    }
+
+    /// Writes an already-serialized JSON value
+    fn raw<F: FnOnce(&mut String)>(&mut self, key: &str, f: F) {
+        self.key(key);
+        f(&mut *self.out);
+    }
+
+    /// Closes the object as a nested value
+    #[inline]
+    fn finish_inline(self) {
+        self.out.push('}');
+    }
+
+    /// Closes the object as a top-level JSON-SEQ record
+    #[inline]
+    fn finish(self) {
+        self.out.push_str("}\n");
+    }
+}
+
+/// Escapes characters that are not permitted in JSON strings
+struct Escaped<'a>(&'a mut String);
+
+impl fmt::Write for Escaped<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '"' => self.0.push_str("\\\""),
+                '\\' => self.0.push_str("\\\\"),
+                '\n' => self.0.push_str("\\n"),
+                '\r' => self.0.push_str("\\r"),
+                '\t' => self.0.push_str("\\t"),
+                c if c.is_control() => {
+                    let _ = write!(self.0, "\\u{:04x}", c as u32);
+                }
+                c => self.0.push(c),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Formats a duration as fractional milliseconds
+struct Millis(Duration);
+
+impl fmt::Display for Millis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.3}", self.0.as_secs_f64() * 1000.0)
+    }
+}
+
+/// Formats bytes as lowercase hex
+struct Hex<'a>(&'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats the qlog `key_type` for a packet number space from the perspective of the endpoint
+struct KeyName<'a>(&'static str, &'a event::events::EndpointType);
+
+impl fmt::Display for KeyName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}_{}_secret", vantage_point(self.1), self.0)
+    }
+}
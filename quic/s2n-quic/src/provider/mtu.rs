@@ -7,7 +7,9 @@
 //! should implement this provider to override the MTU configuration for
 //! specific paths.
 
-pub use s2n_quic_core::path::mtu::{Builder, Config, Endpoint, Inherit as Default, PathInfo};
+pub use s2n_quic_core::path::mtu::{
+    Builder, Config, Endpoint, Inherit as Default, PathInfo, SearchStrategy,
+};
 
 pub trait Provider {
     type Config: 'static + Send + Endpoint;
@@ -0,0 +1,31 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides the scheduler for connections using the multipath extension
+//!
+//! The scheduler selects which path carries each packet once the multipath extension is
+//! negotiated. Multipath is enabled with
+//! [`Limits::with_max_path_id`](crate::provider::limits::Limits::with_max_path_id). By default,
+//! each packet is sent on the path with the lowest RTT.
+
+pub use s2n_quic_transport::path::scheduler::{
+    Candidate, Endpoint, MinRtt as Default, MinRtt, RoundRobin, Scheduler,
+};
+
+pub trait Provider {
+    type Endpoint: 'static + Send + Endpoint;
+    type Error: 'static + core::fmt::Display + Send + Sync;
+
+    fn start(self) -> Result<Self::Endpoint, Self::Error>;
+}
+
+impl_provider_utils!();
+
+impl<T: 'static + Send + Endpoint> Provider for T {
+    type Endpoint = T;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Endpoint, Self::Error> {
+        Ok(self)
+    }
+}
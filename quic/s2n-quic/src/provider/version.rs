@@ -0,0 +1,35 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides the QUIC versions enabled on an endpoint
+//!
+//! By default, endpoints support both QUIC version 1 ([RFC 9000](https://www.rfc-editor.org/rfc/rfc9000))
+//! and QUIC version 2 ([RFC 9369](https://www.rfc-editor.org/rfc/rfc9369)), with clients initiating
+//! connections using version 1.
+//!
+//! If more than one version is enabled, endpoints use compatible version negotiation
+//! ([RFC 9368](https://www.rfc-editor.org/rfc/rfc9368)) to switch to the most preferred
+//! version during the handshake.
+//!
+//! **NOTE**: compatible version negotiation is not supported by the `rustls` TLS provider.
+//! Endpoints using `rustls` only advertise and accept the version the connection was
+//! started with, so both peers need to be configured with the same initial version to
+//! use QUIC version 2.
+
+pub use s2n_quic_core::version::{Builder, Config, Config as Default, Error, Version, V1, V2};
+
+pub trait Provider {
+    type Error: 'static + core::fmt::Display + Send + Sync;
+
+    fn start(self) -> Result<Config, Self::Error>;
+}
+
+impl_provider_utils!();
+
+impl Provider for Config {
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Config, Self::Error> {
+        Ok(self)
+    }
+}
@@ -18,13 +18,21 @@ pub use s2n_quic_core::application::ServerName as Name;
 /// A QUIC server endpoint, capable of accepting connections
 pub struct Server {
     acceptor: Acceptor,
+    /// The acceptors of the other endpoints of a sharded server
+    shards: Vec<Acceptor>,
+    /// The index of the acceptor that is polled first, which rotates so every shard gets a turn
+    next_acceptor: usize,
     local_addr: s2n_quic_core::inet::SocketAddress,
+    /// Connection IDs generated by the endpoint's format, used to check the shard encoding
+    #[cfg_attr(not(unix), allow(dead_code))]
+    connection_id_samples: Vec<s2n_quic_core::connection::LocalId>,
 }
 
 impl fmt::Debug for Server {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Server")
             .field("local_addr", &self.local_addr().ok())
+            .field("shards", &(self.shards.len() + 1))
             .finish()
     }
 }
@@ -51,6 +59,87 @@ impl Server {
         Builder::default()
     }
 
+    /// Starts a [`Server`] which runs an endpoint on each of the `shards`
+    ///
+    /// `start` is called with each shard and should return a [`Server`] using the shard for its
+    /// IO provider and a connection ID format which encodes the shard index. The connections of
+    /// all of the shards are accepted from the returned [`Server`].
+    ///
+    /// An error is returned if the connection IDs generated by a shard's endpoint don't encode the
+    /// index of the shard, since packets for its connections would be routed to other shards.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::{error::Error, path::Path};
+    /// # use s2n_quic::{provider::{connection_id, io}, Server};
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let shards = io::tokio::shard::Builder::default()
+    ///     .with_address("127.0.0.1:443".parse()?)?
+    ///     .with_shard_count(4)?
+    ///     .build()?;
+    ///
+    /// let server = Server::sharded(shards, |shard| {
+    ///     let connection_id = connection_id::default::Format::builder()
+    ///         .with_shard(shard.encoding(), shard.index())?
+    ///         .build()?;
+    ///     let io = io::Default::builder().with_shard(shard)?.build()?;
+    ///
+    ///     let server = Server::builder()
+    ///         .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+    ///         .with_connection_id(connection_id)?
+    ///         .with_io(io)?
+    ///         .start()?;
+    ///
+    ///     Ok::<_, Box<dyn Error + Send + Sync>>(server)
+    /// })?;
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn sharded<F, E>(
+        shards: Vec<io::tokio::shard::Shard>,
+        mut start: F,
+    ) -> Result<Self, StartError>
+    where
+        F: FnMut(io::tokio::shard::Shard) -> Result<Self, E>,
+        E: 'static + fmt::Display + Send + Sync,
+    {
+        let mut start = |shard: io::tokio::shard::Shard| {
+            let (encoding, index) = (shard.encoding(), shard.index());
+            let server = start(shard).map_err(StartError::new)?;
+
+            let is_encoded = server
+                .connection_id_samples
+                .iter()
+                .all(|id| encoding.decode(id.as_bytes()) == Some(index));
+            if !is_encoded {
+                return Err(StartError::new(
+                    "the connection id format doesn't encode the shard index",
+                ));
+            }
+
+            Ok(server)
+        };
+
+        let mut shards = shards.into_iter();
+
+        let mut server = shards
+            .next()
+            .ok_or_else(|| StartError::new("at least one shard is required"))
+            .and_then(&mut start)?;
+
+        for shard in shards {
+            let shard = start(shard)?;
+            server.shards.push(shard.acceptor);
+            server.shards.extend(shard.shards);
+        }
+
+        Ok(server)
+    }
+
     /// Accepts a new incoming [`Connection`] from this [`Server`].
     ///
     /// This function will yield once a new QUIC connection is established. When established,
@@ -95,10 +184,33 @@ impl Server {
     ///   None is returned, this function should not be called again.
     pub fn poll_accept(&mut self, cx: &mut Context) -> Poll<Option<Connection>> {
         s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| {
-            match self.acceptor.poll_accept(cx) {
-                Poll::Ready(Some(connection)) => Poll::Ready(Some(Connection::new(connection))),
-                Poll::Ready(None) => Poll::Ready(None),
-                Poll::Pending => Poll::Pending,
+            let mut is_open = false;
+            let count = self.shards.len() + 1;
+
+            // Start with the acceptor after the one that last returned a connection, so a busy
+            // shard can't starve the others
+            for offset in 0..count {
+                let index = (self.next_acceptor + offset) % count;
+                let acceptor = match index.checked_sub(1) {
+                    Some(shard) => &mut self.shards[shard],
+                    None => &mut self.acceptor,
+                };
+
+                match acceptor.poll_accept(cx) {
+                    Poll::Ready(Some(connection)) => {
+                        self.next_acceptor = (index + 1) % count;
+                        return Poll::Ready(Some(Connection::new(connection)));
+                    }
+                    Poll::Ready(None) => {}
+                    Poll::Pending => is_open = true,
+                }
+            }
+
+            // the server is closed once all of the shards are closed
+            if is_open {
+                Poll::Pending
+            } else {
+                Poll::Ready(None)
             }
         })
     }
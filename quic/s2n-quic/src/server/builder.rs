@@ -191,6 +191,35 @@ impl<Providers: ServerProviders> Builder<Providers> {
         ServerProviders
     );
 
+    impl_provider_method!(
+        /// Sets the QUIC versions enabled for the [`Server`]
+        ///
+        /// # Examples
+        ///
+        /// Only accept connections using QUIC version 1
+        ///
+        /// ```rust,no_run
+        /// # use std::error::Error;
+        /// use s2n_quic::{Server, provider::version};
+        ///
+        /// # #[tokio::main]
+        /// # async fn main() -> Result<(), Box<dyn Error>> {
+        /// let versions = version::Config::builder()
+        ///     .with_versions(&[version::V1])?
+        ///     .build()?;
+        ///
+        /// let server = Server::builder()
+        ///     .with_version(versions)?
+        ///     .start()?;
+        /// #
+        /// #    Ok(())
+        /// # }
+        /// ```
+        with_version,
+        version,
+        ServerProviders
+    );
+
     impl_provider_method!(
         /// Sets the event provider for the [`Server`]
         ///
@@ -351,7 +380,7 @@ impl<Providers: ServerProviders> Builder<Providers> {
         ServerProviders
     );
 
-    #[cfg(feature = "unstable-provider-datagram")]
+    #[cfg(any(test, feature = "datagram"))]
     impl_provider_method!(
         /// Sets the datagram provider for the [`Server`]
         with_datagram,
@@ -374,6 +403,35 @@ impl<Providers: ServerProviders> Builder<Providers> {
         ServerProviders
     );
 
+    impl_provider_method!(
+        /// Sets the multipath scheduler provider for the [`Server`]
+        ///
+        /// The scheduler is only used on connections that negotiate the multipath extension.
+        ///
+        /// # Examples
+        ///
+        /// Alternates between the paths of each connection
+        ///
+        /// ```rust,no_run
+        /// # use std::error::Error;
+        /// use s2n_quic::{Server, provider::{limits, path_scheduler}};
+        /// #
+        /// # #[tokio::main]
+        /// # async fn main() -> Result<(), Box<dyn Error>> {
+        /// let limits = limits::Limits::new().with_max_path_id(4)?;
+        /// let server = Server::builder()
+        ///     .with_limits(limits)?
+        ///     .with_path_scheduler(path_scheduler::RoundRobin::default())?
+        ///     .start()?;
+        /// #
+        /// #    Ok(())
+        /// # }
+        /// ```
+        with_path_scheduler,
+        path_scheduler,
+        ServerProviders
+    );
+
     /// Starts the [`Server`] with the configured providers
     ///
     /// # Examples
@@ -6,6 +6,12 @@ use core::marker::PhantomData;
 use s2n_quic_core::{connection::id::Generator, crypto, path};
 use s2n_quic_transport::{connection, endpoint, stream};
 
+/// The number of connection IDs sampled from the format when the server starts
+///
+/// Each random connection ID has a `1 / count` chance of decoding to any given shard, so several
+/// are needed to tell if the format encodes the shard index.
+const CONNECTION_ID_SAMPLES: usize = 8;
+
 impl_providers_state! {
     #[derive(Debug, Default)]
     struct Providers {
@@ -19,6 +25,7 @@ impl_providers_state! {
         event: Event,
         limits: Limits,
         mtu: Mtu,
+        version: Version,
         io: IO,
         path_migration: PathMigration,
         sync: Sync,
@@ -26,6 +33,7 @@ impl_providers_state! {
         address_token: AddressToken,
         datagram: Datagram,
         dc: Dc,
+        path_scheduler: PathScheduler,
     }
 
     /// Opaque trait containing all of the configured providers
@@ -43,6 +51,7 @@ impl<
         Event: event::Provider,
         Limits: limits::Provider,
         Mtu: mtu::Provider,
+        Version: version::Provider,
         IO: io::Provider,
         PathMigration: path_migration::Provider,
         Sync: sync::Provider,
@@ -50,6 +59,7 @@ impl<
         AddressToken: address_token::Provider,
         Datagram: datagram::Provider,
         Dc: dc::Provider,
+        PathScheduler: path_scheduler::Provider,
     >
     Providers<
         CongestionController,
@@ -62,6 +72,7 @@ impl<
         Event,
         Limits,
         Mtu,
+        Version,
         IO,
         PathMigration,
         Sync,
@@ -69,6 +80,7 @@ impl<
         AddressToken,
         Datagram,
         Dc,
+        PathScheduler,
     >
 {
     pub fn start(self) -> Result<Server, StartError> {
@@ -83,6 +95,7 @@ impl<
             event,
             limits,
             mtu,
+            version,
             address_token,
             io,
             path_migration,
@@ -90,19 +103,21 @@ impl<
             tls,
             datagram,
             dc,
+            path_scheduler,
         } = self;
 
         let congestion_controller = congestion_controller.start().map_err(StartError::new)?;
         let connection_close_formatter = connection_close_formatter
             .start()
             .map_err(StartError::new)?;
-        let connection_id = connection_id.start().map_err(StartError::new)?;
+        let mut connection_id = connection_id.start().map_err(StartError::new)?;
         let packet_interceptor = packet_interceptor.start().map_err(StartError::new)?;
         let stateless_reset_token = stateless_reset_token.start().map_err(StartError::new)?;
         let random = random.start().map_err(StartError::new)?;
         let endpoint_limits = endpoint_limits.start().map_err(StartError::new)?;
         let limits = limits.start().map_err(StartError::new)?;
         let mtu = mtu.start().map_err(StartError::new)?;
+        let version = version.start().map_err(StartError::new)?;
         let event = event.start().map_err(StartError::new)?;
         let address_token = address_token.start().map_err(StartError::new)?;
         let sync = sync.start().map_err(StartError::new)?;
@@ -110,6 +125,7 @@ impl<
         let tls = tls.start_server().map_err(StartError::new)?;
         let datagram = datagram.start().map_err(StartError::new)?;
         let dc = dc.start().map_err(StartError::new)?;
+        let path_scheduler = path_scheduler.start().map_err(StartError::new)?;
 
         // Validate providers
         // TODO: Add more validation https://github.com/aws/s2n-quic/issues/285
@@ -124,6 +140,15 @@ impl<
         };
         let mtu = path::mtu::Manager::new(mtu);
 
+        // sample the connection ID format so a sharded server can check that it encodes the shard
+        let connection_id_samples = {
+            let remote_address = s2n_quic_core::inet::SocketAddress::default();
+            let connection_info = connection::id::ConnectionInfo::new(&remote_address);
+            (0..CONNECTION_ID_SAMPLES)
+                .map(|_| connection_id.generate(&connection_info))
+                .collect()
+        };
+
         let endpoint_config = EndpointConfig {
             congestion_controller,
             connection_close_formatter,
@@ -135,6 +160,7 @@ impl<
             event,
             limits,
             mtu,
+            version,
             sync,
             tls,
             address_token,
@@ -142,6 +168,7 @@ impl<
             path_migration,
             datagram,
             dc,
+            path_scheduler,
         };
 
         let (endpoint, acceptor) = endpoint::Endpoint::new_server(endpoint_config);
@@ -151,7 +178,10 @@ impl<
 
         Ok(Server {
             acceptor,
+            shards: Vec::new(),
+            next_acceptor: 0,
             local_addr,
+            connection_id_samples,
         })
     }
 }
@@ -175,6 +205,7 @@ struct EndpointConfig<
     AddressToken,
     Datagram,
     Dc,
+    PathScheduler,
 > {
     congestion_controller: CongestionController,
     connection_close_formatter: ConnectionCloseFormatter,
@@ -186,6 +217,7 @@ struct EndpointConfig<
     event: Event,
     limits: Limits,
     mtu: path::mtu::Manager<Mtu>,
+    version: s2n_quic_core::version::Config,
     sync: Sync,
     tls: Tls,
     address_token: AddressToken,
@@ -193,6 +225,7 @@ struct EndpointConfig<
     path_migration: PathMigration,
     datagram: Datagram,
     dc: Dc,
+    path_scheduler: PathScheduler,
 }
 
 impl<
@@ -213,6 +246,7 @@ impl<
         AddressToken: address_token::Format,
         Datagram: s2n_quic_core::datagram::Endpoint,
         Dc: s2n_quic_core::dc::Endpoint,
+        PathScheduler: path_scheduler::Endpoint,
     > core::fmt::Debug
     for EndpointConfig<
         CongestionController,
@@ -232,6 +266,7 @@ impl<
         AddressToken,
         Datagram,
         Dc,
+        PathScheduler,
     >
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -257,6 +292,7 @@ impl<
         AddressToken: address_token::Format,
         Datagram: s2n_quic_core::datagram::Endpoint,
         Dc: s2n_quic_core::dc::Endpoint,
+        PathScheduler: path_scheduler::Endpoint,
     > endpoint::Config
     for EndpointConfig<
         CongestionController,
@@ -276,6 +312,7 @@ impl<
         AddressToken,
         Datagram,
         Dc,
+        PathScheduler,
     >
 {
     type ConnectionIdFormat = ConnectionID;
@@ -298,6 +335,7 @@ impl<
     type PacketInterceptor = PacketInterceptor;
     type DatagramEndpoint = Datagram;
     type DcEndpoint = Dc;
+    type PathScheduler = PathScheduler;
 
     const ENDPOINT_TYPE: endpoint::Type = endpoint::Type::Server;
 
@@ -318,6 +356,8 @@ impl<
             path_migration: &mut self.path_migration,
             datagram: &mut self.datagram,
             dc: &mut self.dc,
+            path_scheduler: &mut self.path_scheduler,
+            version: &self.version,
         }
     }
 }
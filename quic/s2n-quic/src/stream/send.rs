@@ -392,6 +392,108 @@ macro_rules! impl_send_stream_api {
             let $stream = self;
             $dispatch_body
         }
+
+        /// Closes the stream with an [error code](crate::application::Error), while still
+        /// delivering the data up to `reliable_size` to the peer.
+        ///
+        /// After calling this, the stream will not accept any additional data to be sent to the
+        /// peer. Any data at or beyond `reliable_size` is discarded. The data before it is still
+        /// transmitted, and retransmitted if lost, and the peer will be notified of the [error
+        /// code](crate::application::Error) only after reading it. This is useful for protocols
+        /// that need the peer to receive a header, such as a session identifier, before it can
+        /// make sense of the reset.
+        ///
+        /// If the peer doesn't support reliable resets, the stream is reset once all of the data
+        /// up to `reliable_size` has been acknowledged.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the stream was reset successfully.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error). The
+        ///   stream may have been reset previously, or the connection itself was closed.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// let header = bytes::Bytes::from_static(b"session-id");
+        /// let header_len = header.len() as u64;
+        /// stream.send(header).await?;
+        ///
+        /// // make sure the peer receives the header before the stream is reset
+        /// stream.reset_at(123u8.into(), header_len)?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn reset_at(
+            &mut self,
+            error_code: $crate::application::Error,
+            reliable_size: u64,
+        ) -> $crate::stream::Result<()> {
+            let reliable_size = s2n_quic_core::varint::VarInt::new(reliable_size)
+                .unwrap_or(s2n_quic_core::varint::VarInt::MAX);
+
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.reset_at(error_code, reliable_size)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
+        /// Sets the scheduling [`Priority`](crate::stream::Priority) of the stream.
+        ///
+        /// Streams with a lower urgency value are given the opportunity to send data before
+        /// streams with a higher value. Streams sharing an urgency either take turns sending
+        /// data, if they are incremental, or are sent one after another.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the priority was updated successfully.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// use s2n_quic::stream::Priority;
+        ///
+        /// // send control messages ahead of any bulk transfers
+        /// stream.set_priority(Priority::new(0, false))?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_priority(
+            &mut self,
+            priority: $crate::stream::Priority,
+        ) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.set_priority(priority)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
     };
 }
 
@@ -30,17 +30,22 @@ mod setup;
 use setup::*;
 
 mod blackhole;
+mod congestion_controller;
 mod connection_migration;
+mod datagram;
 mod deduplicate;
 mod handshake_cid_rotation;
 mod interceptor;
 mod mtu;
+mod multipath;
 mod no_tls;
 mod platform_events;
 mod pto;
+mod qlog;
 mod self_test;
 mod skip_packets;
 mod tls_context;
+mod version;
 
 // TODO: https://github.com/aws/s2n-quic/issues/1726
 //
@@ -57,6 +62,14 @@ mod dc;
 mod fips;
 #[cfg(not(target_os = "windows"))]
 mod mtls;
+#[cfg(feature = "provider-event-opentelemetry")]
+mod opentelemetry;
+#[cfg(feature = "provider-event-prometheus")]
+mod prometheus;
+#[cfg(unix)]
+mod sharded;
+#[cfg(any(feature = "s2n-quic-rustls", feature = "s2n-quic-tls"))]
+mod zero_rtt;
 
 mod exporter;
 mod initial_rtt;
@@ -0,0 +1,172 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::congestion_controller::{
+    dynamic, Algorithm, DynamicCongestionController, PathInfo,
+};
+
+/// Switches the congestion controller of a connection based on the server name
+/// after the connection has been accepted
+#[test]
+fn switch_algorithm_on_live_connection() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(10));
+
+    let algorithms = Arc::new(Mutex::new(vec![]));
+    let server_algorithms = algorithms.clone();
+
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_congestion_controller(dynamic::Builder::default().build(Algorithm::Cubic))?
+            .start()?;
+
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+
+            let server_name = connection.server_name().unwrap();
+            let algorithm = if server_name.as_deref() == Some("localhost") {
+                Algorithm::Bbr
+            } else {
+                Algorithm::NewReno
+            };
+
+            let initial = connection
+                .congestion_controller_mut(|cc: &mut DynamicCongestionController| {
+                    let initial = cc.algorithm();
+                    cc.switch_to(algorithm);
+                    initial
+                })
+                .unwrap();
+            server_algorithms.lock().unwrap().push(initial);
+
+            let mut stream = connection
+                .accept_bidirectional_stream()
+                .await
+                .unwrap()
+                .unwrap();
+            // The switch is applied when the next packet is sent, so check the algorithm
+            // after the first chunk has been echoed
+            let chunk = stream.receive().await.unwrap().unwrap();
+            stream.send(chunk).await.unwrap();
+            stream.flush().await.unwrap();
+
+            let current = connection
+                .congestion_controller_mut(|cc: &mut DynamicCongestionController| cc.algorithm())
+                .unwrap();
+            server_algorithms.lock().unwrap().push(current);
+
+            while let Ok(Some(chunk)) = stream.receive().await {
+                let _ = stream.send(chunk).await;
+            }
+        });
+
+        let client = build_client(handle)?;
+        start_client(client, addr, Data::new(100_000))?;
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    assert_eq!(
+        &[Algorithm::Cubic, Algorithm::Bbr][..],
+        &algorithms.lock().unwrap()[..]
+    );
+}
+
+/// Selects the algorithm of a connection from the server name negotiated in the handshake
+#[test]
+fn select_algorithm_after_handshake() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(10));
+
+    let algorithms = Arc::new(Mutex::new(vec![]));
+    let server_algorithms = algorithms.clone();
+
+    test(model, |handle| {
+        // The server name is only known once the handshake completes, so paths start with CUBIC
+        let selector = |path_info: &PathInfo| match path_info.server_name.map(|name| &**name) {
+            Some("localhost") => Algorithm::Bbr,
+            _ => Algorithm::Cubic,
+        };
+
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_congestion_controller(dynamic::Builder::default().build(selector))?
+            .start()?;
+
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let mut stream = connection
+                .accept_bidirectional_stream()
+                .await
+                .unwrap()
+                .unwrap();
+            let chunk = stream.receive().await.unwrap().unwrap();
+            stream.send(chunk).await.unwrap();
+            stream.flush().await.unwrap();
+
+            let current = connection
+                .congestion_controller_mut(|cc: &mut DynamicCongestionController| cc.algorithm())
+                .unwrap();
+            server_algorithms.lock().unwrap().push(current);
+
+            while let Ok(Some(chunk)) = stream.receive().await {
+                let _ = stream.send(chunk).await;
+            }
+        });
+
+        let client = build_client(handle)?;
+        start_client(client, addr, Data::new(100_000))?;
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    assert_eq!(&[Algorithm::Bbr][..], &algorithms.lock().unwrap()[..]);
+}
+
+#[test]
+fn congestion_controller_type_mismatch() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+
+            // The default congestion controller is not dynamic
+            let result = connection
+                .congestion_controller_mut(|cc: &mut DynamicCongestionController| cc.algorithm());
+            assert!(matches!(
+                result,
+                Err(s2n_quic_core::query::Error::ContextTypeMismatch)
+            ));
+
+            while let Ok(Some(mut stream)) = connection.accept_bidirectional_stream().await {
+                while let Ok(Some(chunk)) = stream.receive().await {
+                    let _ = stream.send(chunk).await;
+                }
+            }
+        });
+
+        let client = build_client(handle)?;
+        start_client(client, addr, Data::new(1000))?;
+
+        Ok(addr)
+    })
+    .unwrap();
+}
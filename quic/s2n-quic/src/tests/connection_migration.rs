@@ -185,7 +185,7 @@ impl Interceptor for RebindPortBeforeLastHandshakePacket {
 }
 
 /// Rebinds the IP of an address
-fn rebind_ip(mut addr: SocketAddr) -> SocketAddr {
+pub(super) fn rebind_ip(mut addr: SocketAddr) -> SocketAddr {
     let ip = match addr.ip() {
         std::net::IpAddr::V4(ip) => {
             let mut v = u32::from_be_bytes(ip.octets());
@@ -224,6 +224,325 @@ fn ip_and_port_rebind_test() {
     run_test(|addr| rebind_ip(rebind_port(addr)));
 }
 
+/// Ensures that a client is able to actively migrate to a new local address
+fn active_migration_test<F>(on_migrate: F)
+where
+    F: FnOnce(SocketAddr) -> SocketAddr + Send + 'static,
+{
+    let model = Model::default();
+    let rtt = Duration::from_millis(10);
+    model.set_delay(rtt / 2);
+
+    let client_socket = Arc::new(Mutex::new(None));
+    let client_socket_pub = client_socket.clone();
+    let on_socket = move |socket: io::Socket| {
+        *client_socket_pub.lock().unwrap() = Some(socket);
+    };
+
+    let expected_paths = Arc::new(Mutex::new(vec![]));
+    let expected_paths_pub = expected_paths.clone();
+
+    let active_paths = recorder::ActivePathUpdated::new();
+    let active_path_sub = active_paths.clone();
+
+    let migration_events = recorder::ActiveMigrationUpdated::new();
+    let migration_events_sub = migration_events.clone();
+
+    test(model, move |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), active_path_sub))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let client_io = handle.builder().on_socket(on_socket).build()?;
+
+        let client = Client::builder()
+            .with_io(client_io)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), migration_events_sub))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_server(server)?;
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut conn = client.connect(connect).await.unwrap();
+            let mut stream = conn.open_bidirectional_stream().await.unwrap();
+
+            stream.send(Bytes::from_static(b"A")).await.unwrap();
+
+            // wait for the handshake to be confirmed and the server to issue connection IDs
+            delay(rtt * 4).await;
+
+            let socket = client_socket.lock().unwrap().clone().unwrap();
+            let local_addr = on_migrate(socket.local_addr().unwrap());
+            socket.add_address(local_addr);
+            expected_paths_pub.lock().unwrap().push(local_addr);
+
+            conn.migrate(local_addr).unwrap();
+            assert_eq!(
+                conn.migrate(local_addr),
+                Err(crate::connection::MigrationError::InProgress)
+            );
+
+            // the client switches to the new address once the path is validated
+            delay(rtt * 4).await;
+            assert_eq!(conn.local_addr().unwrap(), local_addr);
+
+            stream.send(Bytes::from_static(b"B")).await.unwrap();
+            stream.finish().unwrap();
+
+            let mut received = vec![];
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                received.extend_from_slice(&chunk);
+            }
+            assert_eq!(&received[..], &b"AB"[..]);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let expected_paths = expected_paths.lock().unwrap();
+    assert_eq!(&*active_paths.events().lock().unwrap(), &*expected_paths);
+
+    let migration_events = migration_events.events();
+    let migration_events = migration_events.lock().unwrap();
+    assert_eq!(migration_events.len(), 2);
+    assert!(matches!(
+        migration_events[0],
+        (events::ActiveMigrationStatus::Started, addr) if addr == expected_paths[0]
+    ));
+    assert!(matches!(
+        migration_events[1],
+        (events::ActiveMigrationStatus::Migrated, addr) if addr == expected_paths[0]
+    ));
+}
+
+#[test]
+fn client_active_migration_test() {
+    active_migration_test(rebind_ip);
+}
+
+/// Ensures that a client can migrate to a different local port, which requires a separate
+/// socket
+#[test]
+fn client_active_migration_port_test() {
+    active_migration_test(rebind_port);
+}
+
+/// Ensures that a client using the tokio IO provider is able to migrate to another local
+/// address
+async fn tokio_active_migration_test<F>(
+    client_io: crate::provider::io::tokio::Provider,
+    on_migrate: F,
+) where
+    F: FnOnce(SocketAddr) -> SocketAddr,
+{
+    use crate::connection::MigrationError;
+
+    let mut server = Server::builder()
+        .with_io("127.0.0.1:0")
+        .unwrap()
+        .with_tls(SERVER_CERTS)
+        .unwrap()
+        .with_event(tracing_events())
+        .unwrap()
+        .start()
+        .unwrap();
+    let server_addr = server.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        while let Some(mut connection) = server.accept().await {
+            tokio::spawn(async move {
+                while let Ok(Some(mut stream)) = connection.accept_bidirectional_stream().await {
+                    tokio::spawn(async move {
+                        while let Ok(Some(chunk)) = stream.receive().await {
+                            let _ = stream.send(chunk).await;
+                        }
+                    });
+                }
+            });
+        }
+    });
+
+    let migration_events = recorder::ActiveMigrationUpdated::new();
+    let client = Client::builder()
+        .with_io(client_io)
+        .unwrap()
+        .with_tls(certificates::CERT_PEM)
+        .unwrap()
+        .with_event((tracing_events(), migration_events.clone()))
+        .unwrap()
+        .start()
+        .unwrap();
+
+    let connect = Connect::new(server_addr).with_server_name("localhost");
+    let mut conn = client.connect(connect).await.unwrap();
+    let mut stream = conn.open_bidirectional_stream().await.unwrap();
+
+    stream.send(Bytes::from_static(b"A")).await.unwrap();
+    assert_eq!(&stream.receive().await.unwrap().unwrap()[..], b"A");
+
+    let local_addr = on_migrate(conn.local_addr().unwrap());
+
+    // wait for the handshake to be confirmed and the server to issue connection IDs
+    loop {
+        match conn.migrate(local_addr) {
+            Ok(()) => break,
+            Err(
+                MigrationError::HandshakeNotConfirmed | MigrationError::NoAvailableConnectionId,
+            ) => tokio::time::sleep(Duration::from_millis(10)).await,
+            Err(error) => panic!("unexpected migration error: {error}"),
+        }
+    }
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while conn.local_addr().unwrap() != local_addr {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("the client should migrate to the new address");
+
+    // the server keeps echoing data on the new path
+    stream.send(Bytes::from_static(b"B")).await.unwrap();
+    assert_eq!(&stream.receive().await.unwrap().unwrap()[..], b"B");
+
+    let migration_events = migration_events.events();
+    let migration_events = migration_events.lock().unwrap();
+    assert!(matches!(
+        migration_events[..],
+        [
+            (events::ActiveMigrationStatus::Started, started),
+            (events::ActiveMigrationStatus::Migrated, migrated),
+        ] if started == local_addr && migrated == local_addr
+    ));
+}
+
+/// Ensures that a client using the tokio IO provider is able to migrate to another local
+/// address that its socket can send from
+///
+/// The client socket is bound to the unspecified address so it can send from any of the
+/// loopback addresses.
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn tokio_client_active_migration_test() {
+    use std::net::Ipv4Addr;
+
+    let client_io = crate::provider::io::tokio::Builder::default()
+        .with_receive_address("0.0.0.0:0".parse().unwrap())
+        .unwrap()
+        .build()
+        .unwrap();
+
+    tokio_active_migration_test(client_io, |mut local_addr| {
+        local_addr.set_ip(Ipv4Addr::new(127, 0, 0, 2).into());
+        local_addr
+    })
+    .await;
+}
+
+/// Ensures that a client using the tokio IO provider is able to migrate to an additional
+/// socket, such as one bound to another interface
+#[tokio::test]
+async fn tokio_client_active_migration_socket_test() {
+    let socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let socket_addr = socket.local_addr().unwrap();
+
+    let client_io = crate::provider::io::tokio::Builder::default()
+        .with_receive_address("127.0.0.1:0".parse().unwrap())
+        .unwrap()
+        .with_additional_socket(socket)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    tokio_active_migration_test(client_io, move |local_addr| {
+        assert_ne!(local_addr, socket_addr);
+        socket_addr
+    })
+    .await;
+}
+
+/// Ensures that a client migrates to the server's preferred address once the handshake is
+/// confirmed
+#[test]
+fn preferred_address_test() {
+    let model = Model::default();
+    let rtt = Duration::from_millis(10);
+    model.set_delay(rtt / 2);
+
+    // the server receives packets on both its original and preferred address
+    let preferred_addr: SocketAddr = "2.0.0.1:4433".parse().unwrap();
+    let on_socket = move |socket: io::Socket| {
+        socket.add_address(preferred_addr);
+    };
+
+    let migration_events = recorder::PreferredAddressMigrationUpdated::new();
+    let migration_sub = migration_events.clone();
+
+    test(model, move |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().on_socket(on_socket).build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_limits(
+                provider::limits::Limits::default()
+                    .with_preferred_address(preferred_addr)
+                    .unwrap(),
+            )?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), migration_sub))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_server(server)?;
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut conn = client.connect(connect).await.unwrap();
+            let mut stream = conn.open_bidirectional_stream().await.unwrap();
+
+            stream.send(Bytes::from_static(b"A")).await.unwrap();
+
+            // wait for the handshake to be confirmed and the preferred address to be validated
+            delay(rtt * 4).await;
+            assert_eq!(conn.remote_addr().unwrap(), preferred_addr);
+
+            stream.send(Bytes::from_static(b"B")).await.unwrap();
+            stream.finish().unwrap();
+
+            let mut received = vec![];
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                received.extend_from_slice(&chunk);
+            }
+            assert_eq!(&received[..], &b"AB"[..]);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let migration_events = migration_events.events();
+    let migration_events = migration_events.lock().unwrap();
+    assert_eq!(migration_events.len(), 2);
+    assert!(matches!(
+        migration_events[0],
+        (events::PreferredAddressMigrationStatus::Started, addr) if addr == preferred_addr
+    ));
+    assert!(matches!(
+        migration_events[1],
+        (events::PreferredAddressMigrationStatus::Migrated, addr) if addr == preferred_addr
+    ));
+}
+
 // Changes the port of the second datagram received
 #[derive(Default)]
 struct RebindPortBeforeHandshakeConfirmed {
@@ -0,0 +1,132 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::datagram::default::{Endpoint, SendOutcome};
+
+const DATAGRAM_COUNT: usize = 10;
+
+/// Ensures datagrams sent on the connection handle are received by the peer and
+/// report the `Acked` outcome
+#[test]
+fn send_and_recv_datagrams() {
+    let model = Model::default();
+
+    let received = Arc::new(Mutex::new(vec![]));
+    let outcomes = Arc::new(Mutex::new(vec![]));
+
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_datagram(Endpoint::builder().with_recv_capacity(100)?.build()?)?
+            .start()?;
+        let server_addr = server.local_addr()?;
+
+        let received = received.clone();
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            while received.lock().unwrap().len() < DATAGRAM_COUNT {
+                let datagram = connection.recv_datagram().await.unwrap();
+                received.lock().unwrap().push(datagram);
+            }
+        });
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            // use a small send queue so the sender has to wait for capacity
+            .with_datagram(Endpoint::builder().with_send_capacity(2)?.build()?)?
+            .start()?;
+
+        let outcomes = outcomes.clone();
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut ids = vec![];
+            for i in 0..DATAGRAM_COUNT {
+                let data = Bytes::from(vec![i as u8; 100]);
+                ids.push(connection.send_tracked_datagram(data).await.unwrap());
+            }
+
+            while outcomes.lock().unwrap().len() < ids.len() {
+                let outcome = connection.datagram_send_outcome().await.unwrap();
+                assert!(ids.contains(&outcome.0));
+                outcomes.lock().unwrap().push(outcome.1);
+            }
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), DATAGRAM_COUNT);
+    for (i, datagram) in received.iter().enumerate() {
+        assert_eq!(&datagram[..], &[i as u8; 100][..]);
+    }
+
+    let outcomes = outcomes.lock().unwrap();
+    assert_eq!(outcomes.len(), DATAGRAM_COUNT);
+    assert!(outcomes
+        .iter()
+        .all(|outcome| *outcome == SendOutcome::Acked));
+}
+
+/// Ensures the connection handle can send and receive datagrams without configuring a datagram
+/// provider when the `datagram` feature is enabled
+#[test]
+#[cfg(feature = "datagram")]
+fn default_provider_datagrams() {
+    let model = Model::default();
+
+    let received = Arc::new(Mutex::new(vec![]));
+
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let server_addr = server.local_addr()?;
+
+        let received = received.clone();
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let datagram = connection.recv_datagram().await.unwrap();
+            received.lock().unwrap().push(datagram);
+        });
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let id = connection
+                .send_tracked_datagram(Bytes::from_static(b"hello"))
+                .await
+                .unwrap();
+
+            // keep the connection open until the datagram is acknowledged
+            let outcome = connection.datagram_send_outcome().await.unwrap();
+            assert_eq!(outcome, (id, SendOutcome::Acked));
+        });
+
+        Ok(server_addr)
+    })
+    .unwrap();
+
+    let received = received.lock().unwrap();
+    assert_eq!(&received[..], &[Bytes::from_static(b"hello")]);
+}
@@ -506,6 +506,151 @@ fn mtu_blackhole() {
     ));
 }
 
+// if the network MTU is restored after an MTU black hole, the search for a larger MTU
+// restarts after the configured cool off period and the jumbo MTU is confirmed again
+#[test]
+fn mtu_blackhole_cool_off() {
+    let model = Model::default();
+    let rtt = Duration::from_millis(100);
+    let max_mtu = 9001;
+    let subscriber = recorder::MtuUpdated::new();
+    let events = subscriber.events();
+
+    model.set_delay(rtt / 2);
+    model.set_max_udp_payload(max_mtu);
+
+    test(model.clone(), |handle| {
+        let mtu_config = mtu::Config::builder()
+            .with_max_mtu(max_mtu)?
+            .with_black_hole_cool_off(rtt * 20)
+            .build()?;
+        let server = Server::builder()
+            .with_io(handle.builder().with_max_mtu(max_mtu).build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(456))?
+            .with_mtu(CustomMtu(mtu_config))?
+            .start()?;
+        let client = Client::builder()
+            .with_io(handle.builder().with_max_mtu(max_mtu).build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(30_000_000))?;
+
+        spawn(async move {
+            // let all packets go through for 10 RTTs - this will reach the end of MTU probing
+            delay(rtt * 10).await;
+
+            // decrease the MTU to trigger a blackhole
+            model.set_max_udp_payload(1200);
+            delay(rtt * 10).await;
+
+            // restore the MTU before the cool off period elapses
+            model.set_max_udp_payload(max_mtu);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let events = events.lock().unwrap().clone();
+
+    let blackhole = events
+        .iter()
+        .position(|event| matches!(event.cause, events::MtuUpdatedCause::Blackhole { .. }))
+        .expect("a blackhole should be detected");
+    assert!(matches!(
+        events[blackhole + 1].cause,
+        events::MtuUpdatedCause::BlackholeCoolOffElapsed { .. }
+    ));
+
+    // MTU was raised back to jumbo after the cool off period
+    let last_mtu = events.last().unwrap();
+    assert_eq!(8943, last_mtu.mtu);
+    assert!(last_mtu.search_complete);
+}
+
+// the application can restart the search for a larger MTU without waiting for the
+// black hole cool off period to elapse
+#[test]
+fn mtu_probe_requested() {
+    let model = Model::default();
+    let rtt = Duration::from_millis(100);
+    let max_mtu = 9001;
+    let subscriber = recorder::MtuUpdated::new();
+    let events = subscriber.events();
+
+    model.set_delay(rtt / 2);
+    model.set_max_udp_payload(max_mtu);
+
+    test(model.clone(), |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().with_max_mtu(max_mtu).build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let client = Client::builder()
+            .with_io(handle.builder().with_max_mtu(max_mtu).build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let mut connection_handle = connection.handle();
+
+            spawn(async move {
+                // let all packets go through for 10 RTTs - this will reach the end of MTU probing
+                delay(rtt * 10).await;
+                assert_eq!(8943, connection_handle.mtu().unwrap());
+
+                // decrease the MTU to trigger a blackhole
+                model.set_max_udp_payload(1200);
+                delay(rtt * 10).await;
+                assert_eq!(1200, connection_handle.mtu().unwrap());
+
+                // restore the MTU and request a new search right away
+                model.set_max_udp_payload(max_mtu);
+                connection_handle.probe_mtu().unwrap();
+            });
+
+            while let Ok(Some(mut stream)) = connection.accept_bidirectional_stream().await {
+                spawn(async move {
+                    while let Ok(Some(chunk)) = stream.receive().await {
+                        let _ = stream.send(chunk).await;
+                    }
+                });
+            }
+        });
+
+        start_client(client, addr, Data::new(30_000_000))?;
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let events = events.lock().unwrap().clone();
+
+    assert!(events
+        .iter()
+        .any(|event| matches!(event.cause, events::MtuUpdatedCause::ProbeRequested { .. })));
+    assert!(!events.iter().any(|event| matches!(
+        event.cause,
+        events::MtuUpdatedCause::BlackholeCoolOffElapsed { .. }
+    )));
+
+    // MTU was raised back to jumbo without waiting for the cool off period
+    let last_mtu = events.last().unwrap();
+    assert_eq!(8943, last_mtu.mtu);
+    assert!(last_mtu.search_complete);
+}
+
 // ensure the server enforces the minimum MTU for all initial packets
 #[test]
 fn minimum_initial_packet() {
@@ -0,0 +1,104 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::{limits::Limits, path_scheduler};
+use connection_migration::rebind_ip;
+
+/// Ensures that packets sent on a slow path aren't declared lost when packets sent after them on
+/// a faster path are acknowledged
+#[test]
+fn multipath_different_rtt_test() {
+    let model = Model::default();
+    let rtt = Duration::from_millis(10);
+    model.set_delay(rtt / 2);
+
+    let client_socket = Arc::new(Mutex::new(None));
+    let client_socket_pub = client_socket.clone();
+    let on_socket = move |socket: io::Socket| {
+        *client_socket_pub.lock().unwrap() = Some(socket);
+    };
+
+    let server_losses = recorder::PacketLost::new();
+    let server_losses_sub = server_losses.clone();
+    let client_losses = recorder::PacketLost::new();
+    let client_losses_sub = client_losses.clone();
+
+    let model_pub = model.clone();
+
+    test(model, move |handle| {
+        let limits = Limits::new().with_max_path_id(4)?;
+
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), server_losses_sub))?
+            .with_random(Random::with_seed(456))?
+            .with_limits(limits)?
+            .with_path_scheduler(path_scheduler::RoundRobin::default())?
+            .start()?;
+
+        let client_io = handle.builder().on_socket(on_socket).build()?;
+
+        let client = Client::builder()
+            .with_io(client_io)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), client_losses_sub))?
+            .with_random(Random::with_seed(456))?
+            .with_limits(limits)?
+            .with_path_scheduler(path_scheduler::RoundRobin::default())?
+            .start()?;
+
+        let addr = start_server(server)?;
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut conn = client.connect(connect).await.unwrap();
+
+            // wait for the handshake to be confirmed and the server to issue connection IDs
+            delay(rtt * 4).await;
+
+            // open a second path with 10 times the round-trip time of the first path
+            let socket = client_socket.lock().unwrap().clone().unwrap();
+            let local_addr = rebind_ip(socket.local_addr().unwrap());
+            model_pub.set_address_delay(local_addr.ip(), rtt * 9 / 2);
+            socket.add_address(local_addr);
+            conn.migrate(local_addr).unwrap();
+
+            // wait for the new path to be validated
+            delay(rtt * 30).await;
+
+            // the round-robin schedulers interleave the packets of both paths
+            let stream = conn.open_bidirectional_stream().await.unwrap();
+            let (mut recv, mut send) = stream.split();
+
+            let mut send_data = Data::new(1_000_000);
+            let mut recv_data = send_data;
+
+            primary::spawn(async move {
+                while let Some(chunk) = send_data.send_one(usize::MAX) {
+                    send.send(chunk).await.unwrap();
+                }
+                send.finish().unwrap();
+            });
+
+            while let Some(chunk) = recv.receive().await.unwrap() {
+                recv_data.receive(&[chunk]);
+            }
+            assert!(recv_data.is_finished());
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    // The network doesn't drop packets, so only MTU probes that exceed the maximum UDP payload
+    // may be lost
+    for losses in [server_losses, client_losses] {
+        let losses = losses.events();
+        let losses = losses.lock().unwrap();
+        assert!(
+            losses.iter().all(|is_mtu_probe| *is_mtu_probe),
+            "packets were declared lost: {losses:?}"
+        );
+    }
+}
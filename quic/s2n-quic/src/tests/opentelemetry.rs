@@ -0,0 +1,223 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::event::opentelemetry;
+use core::fmt::{self, Write as _};
+use tracing::{
+    field::{Field, Visit},
+    span,
+};
+use tracing_subscriber::{layer::Context, prelude::*, registry::LookupSpan, Layer};
+
+/// Runs a client which echoes data with the server
+fn run(model: Model) {
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(opentelemetry::Subscriber::default())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(opentelemetry::Subscriber::default())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(10_000))?;
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+/// Runs a client which echoes data with the server and returns the recorded span lifecycles
+fn trace(model: Model) -> Vec<String> {
+    let spans = Spans::default();
+    let dispatch = tracing_subscriber::registry().with(spans.clone());
+
+    tracing::subscriber::with_default(dispatch, || run(model));
+
+    spans.take()
+}
+
+#[test]
+fn opentelemetry_spans_test() {
+    let log = trace(Model::default());
+    let count = |line: &str| log.iter().filter(|l| l.starts_with(line)).count();
+
+    // each endpoint has a connection span, which has a handshake span that closes once
+    // the handshake completes
+    assert_eq!(count("new quic.connection"), 2, "{log:#?}");
+    assert_eq!(count("new quic.handshake"), 2, "{log:#?}");
+    assert_eq!(count("close quic.handshake"), 2, "{log:#?}");
+
+    // the client opens a bidirectional stream, which the server accepts and echoes back
+    let stream = "new quic.stream quic.stream.id=0";
+    for line in [
+        format!("{stream} quic.stream.initiator=local quic.stream.direction=bidirectional"),
+        format!("{stream} quic.stream.initiator=remote quic.stream.direction=bidirectional"),
+    ] {
+        assert!(log.contains(&line), "missing {line:?} in {log:#?}");
+    }
+    assert_eq!(count("close quic.stream"), 2, "{log:#?}");
+
+    for line in [
+        "record quic.connection tls.server.name=localhost",
+        "record quic.connection tls.cipher=TLS_",
+        "record quic.stream quic.stream.bytes_sent=10000",
+        "record quic.stream quic.stream.bytes_received=10000",
+    ] {
+        assert!(count(line) > 0, "missing {line:?} in {log:#?}");
+    }
+}
+
+/// Ensures retransmitted and duplicated stream frames don't reopen the spans of finished streams
+#[test]
+fn opentelemetry_finished_stream_test() {
+    let model = Model::default();
+    model.set_drop_rate(0.1).set_retransmit_rate(0.2);
+    let log = trace(model);
+    let count = |line: &str| log.iter().filter(|l| l.starts_with(line)).count();
+
+    // each endpoint only has a single span for the stream, even though frames for it keep
+    // arriving after it finished
+    assert_eq!(count("new quic.stream"), 2, "{log:#?}");
+    assert_eq!(count("close quic.stream"), 2, "{log:#?}");
+}
+
+/// Ensures the spans are exported to OpenTelemetry as a tree for each connection
+#[test]
+fn opentelemetry_export_test() {
+    use ::opentelemetry::{
+        trace::{SpanId, SpanKind, TracerProvider as _},
+        Value,
+    };
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider, SpanData};
+
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let dispatch =
+        tracing_subscriber::registry().with(opentelemetry::layer(provider.tracer("s2n-quic")));
+
+    tracing::subscriber::with_default(dispatch, || run(Model::default()));
+
+    provider.force_flush().unwrap();
+    let spans = exporter.get_finished_spans().unwrap();
+    let names: Vec<_> = spans.iter().map(|span| &span.name).collect();
+    let attribute = |span: &SpanData, key: &str| {
+        span.attributes
+            .iter()
+            .find(|kv| kv.key.as_str() == key)
+            .map(|kv| kv.value.clone())
+    };
+
+    let connections: Vec<_> = spans
+        .iter()
+        .filter(|span| span.name == "quic.connection")
+        .collect();
+    assert_eq!(connections.len(), 2, "{names:?}");
+
+    for connection in connections {
+        // each connection is the root of its own trace
+        assert_eq!(connection.parent_span_id, SpanId::INVALID);
+
+        let endpoint = match connection.span_kind {
+            SpanKind::Client => "client",
+            SpanKind::Server => "server",
+            ref kind => panic!("unexpected span kind {kind:?}"),
+        };
+        assert_eq!(
+            attribute(connection, "quic.endpoint"),
+            Some(Value::from(endpoint))
+        );
+        assert_eq!(
+            attribute(connection, "tls.server.name"),
+            Some(Value::from("localhost"))
+        );
+        assert!(attribute(connection, "tls.cipher").is_some());
+
+        let children: Vec<_> = spans
+            .iter()
+            .filter(|span| {
+                span.span_context.trace_id() == connection.span_context.trace_id()
+                    && span.span_context.span_id() != connection.span_context.span_id()
+            })
+            .collect();
+
+        // the handshake and stream spans are the children of the connection span
+        for name in ["quic.handshake", "quic.stream"] {
+            let child = children
+                .iter()
+                .find(|span| span.name == name)
+                .unwrap_or_else(|| panic!("missing {name:?} in {names:?}"));
+            assert_eq!(child.parent_span_id, connection.span_context.span_id());
+        }
+
+        let stream = children
+            .iter()
+            .find(|span| span.name == "quic.stream")
+            .unwrap();
+        assert_eq!(attribute(stream, "quic.stream.id"), Some(Value::I64(0)));
+        assert_eq!(
+            attribute(stream, "quic.stream.bytes_sent"),
+            Some(Value::I64(10_000))
+        );
+        assert_eq!(
+            attribute(stream, "quic.stream.bytes_received"),
+            Some(Value::I64(10_000))
+        );
+    }
+}
+
+/// Records the lifecycle and fields of the spans emitted by the subscriber
+#[derive(Clone, Default)]
+struct Spans(Arc<Mutex<Vec<String>>>);
+
+impl Spans {
+    fn push(&self, line: String) {
+        self.0.lock().unwrap().push(line);
+    }
+
+    fn take(&self) -> Vec<String> {
+        core::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+impl<S> Layer<S> for Spans
+where
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, _id: &span::Id, _ctx: Context<'_, S>) {
+        let mut line = format!("new {}", attrs.metadata().name());
+        attrs.record(&mut Fields(&mut line));
+        self.push(line);
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        let span = ctx.span(id).unwrap();
+        let mut line = format!("record {}", span.name());
+        values.record(&mut Fields(&mut line));
+        self.push(line);
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let span = ctx.span(&id).unwrap();
+        self.push(format!("close {}", span.name()));
+    }
+}
+
+struct Fields<'a>(&'a mut String);
+
+impl Visit for Fields<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        let _ = write!(self.0, " {}={value}", field.name());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        let _ = write!(self.0, " {}={value:?}", field.name());
+    }
+}
@@ -0,0 +1,77 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::event::prometheus;
+
+#[test]
+fn prometheus_registry_test() {
+    let model = Model::default();
+    let registry = prometheus::Registry::default();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), registry.subscriber()))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), registry.subscriber()))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(10_000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let output = registry.render();
+    let lines: Vec<_> = output.lines().collect();
+
+    for line in [
+        "# TYPE s2n_quic_connection_started_total counter",
+        "# TYPE s2n_quic_handshake_status_updated_complete_latency_seconds histogram",
+        "# TYPE s2n_quic_packet_lost_bytes histogram",
+        "# TYPE s2n_quic_datagram_sent_bytes_total counter",
+        "s2n_quic_connection_started_total 2",
+    ] {
+        assert!(lines.contains(&line), "missing {line:?} in:\n{output}");
+    }
+
+    // both endpoints complete the handshake and count towards the same histogram
+    assert!(lines.contains(
+        &"s2n_quic_handshake_status_updated_complete_latency_seconds_bucket{le=\"+Inf\"} 2"
+    ));
+
+    // nominal metrics are labeled with their variant
+    assert!(lines
+        .iter()
+        .any(|line| line.starts_with("s2n_quic_connection_closed_error_total{variant=\"")));
+
+    // every sample line has a name and a value
+    for line in lines.iter().filter(|line| !line.starts_with('#')) {
+        let (name, value) = line.rsplit_once(' ').unwrap();
+        assert!(name.starts_with("s2n_quic_"), "{line}");
+        assert!(value.parse::<f64>().is_ok(), "{line}");
+    }
+
+    let handler = registry.handler();
+
+    let request = http::Request::get("/metrics").body(()).unwrap();
+    let response = handler.respond(&request);
+    assert_eq!(response.status(), http::StatusCode::OK);
+    assert_eq!(
+        response.headers()[http::header::CONTENT_TYPE],
+        prometheus::CONTENT_TYPE
+    );
+    assert!(response
+        .body()
+        .contains("# TYPE s2n_quic_connection_started_total counter"));
+
+    let request = http::Request::post("/metrics").body(()).unwrap();
+    let response = handler.respond(&request);
+    assert_eq!(response.status(), http::StatusCode::METHOD_NOT_ALLOWED);
+}
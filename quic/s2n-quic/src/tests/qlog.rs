@@ -0,0 +1,75 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::event::qlog;
+use std::fs;
+
+#[test]
+fn qlog_trace_per_connection() {
+    let directory = std::env::temp_dir().join(format!("s2n-quic-qlog-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+
+    let model = Model::default();
+    let qlog = qlog::Builder::default()
+        .with_directory(&directory)
+        .build()
+        .unwrap();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), qlog.clone()))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), qlog.clone()))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(10_000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let mut files: Vec<_> = fs::read_dir(&directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+    // both traces are named after the original destination connection ID
+    assert_eq!(files.len(), 2);
+    let group_id = files[0].strip_suffix("_client.sqlog").unwrap().to_string();
+    assert_eq!(files[1], format!("{group_id}_server.sqlog"));
+
+    for file in files {
+        let trace = fs::read_to_string(directory.join(&file)).unwrap();
+        let records: Vec<_> = trace.lines().collect();
+
+        // every record is prefixed with the JSON-SEQ record separator
+        assert!(records.iter().all(|record| record.starts_with('\u{1e}')));
+        assert!(records[0].contains("\"qlog_format\":\"JSON-SEQ\""));
+        assert!(records[0].contains(&format!("\"group_id\":\"{group_id}\"")));
+
+        for name in [
+            "connectivity:connection_started",
+            "transport:packet_sent",
+            "transport:packet_received",
+            "recovery:metrics_updated",
+            "security:key_updated",
+        ] {
+            let name = format!("\"name\":\"{name}\"");
+            assert!(
+                records.iter().any(|record| record.contains(&name)),
+                "{file} is missing {name}"
+            );
+        }
+
+        assert!(trace.contains("\"frame_type\":\"stream\""));
+    }
+
+    let _ = fs::remove_dir_all(&directory);
+}
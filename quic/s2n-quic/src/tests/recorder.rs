@@ -97,6 +97,30 @@ event_recorder!(
     }
 );
 
+event_recorder!(
+    PreferredAddressMigrationUpdated,
+    PreferredAddressMigrationUpdated,
+    on_preferred_address_migration_updated,
+    (events::PreferredAddressMigrationStatus, SocketAddr),
+    |event: &events::PreferredAddressMigrationUpdated,
+     storage: &mut Vec<(events::PreferredAddressMigrationStatus, SocketAddr)>| {
+        let addr = (&event.path.remote_addr).into();
+        storage.push((event.status.clone(), addr));
+    }
+);
+
+event_recorder!(
+    ActiveMigrationUpdated,
+    ActiveMigrationUpdated,
+    on_active_migration_updated,
+    (events::ActiveMigrationStatus, SocketAddr),
+    |event: &events::ActiveMigrationUpdated,
+     storage: &mut Vec<(events::ActiveMigrationStatus, SocketAddr)>| {
+        let addr = (&event.local_addr).into();
+        storage.push((event.status.clone(), addr));
+    }
+);
+
 event_recorder!(
     PacketDropped,
     PacketDropped,
@@ -155,6 +179,16 @@ event_recorder!(
     }
 );
 
+event_recorder!(
+    PacketLost,
+    PacketLost,
+    on_packet_lost,
+    bool,
+    |event: &events::PacketLost, storage: &mut Vec<bool>| {
+        storage.push(event.is_mtu_probe);
+    }
+);
+
 event_recorder!(
     ConnectionStarted,
     ConnectionStarted,
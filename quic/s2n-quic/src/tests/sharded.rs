@@ -0,0 +1,84 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::{connection_id, io::tokio::shard};
+
+const SHARD_COUNT: u16 = 4;
+
+fn shards() -> Vec<shard::Shard> {
+    shard::Builder::default()
+        .with_address("127.0.0.1:0".parse().unwrap())
+        .unwrap()
+        .with_shard_count(SHARD_COUNT)
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+fn start_shard<C: connection_id::Provider>(
+    shard: shard::Shard,
+    connection_id: C,
+) -> Result<Server, Box<dyn std::error::Error + Send + Sync>> {
+    let io = provider::io::Default::builder()
+        .with_shard(shard)?
+        .build()?;
+
+    let server = Server::builder()
+        .with_tls(SERVER_CERTS)?
+        .with_connection_id(connection_id)?
+        .with_io(io)?
+        .start()?;
+
+    Ok(server)
+}
+
+/// Ensures a client can connect to a sharded server and the connection is accepted
+#[tokio::test]
+async fn sharded_server_test() {
+    let mut server = Server::sharded(shards(), |shard| {
+        let connection_id = connection_id::default::Format::builder()
+            .with_shard(shard.encoding(), shard.index())?
+            .build()?;
+        start_shard(shard, connection_id)
+    })
+    .unwrap();
+    let server_addr = server.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        while let Some(mut connection) = server.accept().await {
+            tokio::spawn(async move {
+                while let Ok(Some(mut stream)) = connection.accept_bidirectional_stream().await {
+                    while let Ok(Some(chunk)) = stream.receive().await {
+                        let _ = stream.send(chunk).await;
+                    }
+                }
+            });
+        }
+    });
+
+    let client = Client::builder()
+        .with_io("127.0.0.1:0")
+        .unwrap()
+        .with_tls(certificates::CERT_PEM)
+        .unwrap()
+        .start()
+        .unwrap();
+
+    let connect = Connect::new(server_addr).with_server_name("localhost");
+    let mut conn = client.connect(connect).await.unwrap();
+    let mut stream = conn.open_bidirectional_stream().await.unwrap();
+    stream.send(Bytes::from_static(b"A")).await.unwrap();
+    assert_eq!(&stream.receive().await.unwrap().unwrap()[..], b"A");
+}
+
+/// Ensures a sharded server fails to start if a shard's connection IDs don't encode its index
+#[tokio::test]
+async fn sharded_server_missing_encoding_test() {
+    let error = Server::sharded(shards(), |shard| {
+        start_shard(shard, connection_id::default::Provider::default())
+    })
+    .unwrap_err();
+
+    assert!(error.to_string().contains("shard index"));
+}
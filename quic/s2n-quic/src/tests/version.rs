@@ -0,0 +1,282 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::version;
+use s2n_codec::EncoderBuffer;
+use s2n_quic_core::{
+    event::api::Subject,
+    packet::interceptor::{Datagram, Interceptor},
+};
+
+/// Returns the versions of all of the long header packets that were sent
+fn long_header_versions(events: &[events::PacketSent]) -> Vec<u32> {
+    let mut versions: Vec<u32> = events
+        .iter()
+        .filter_map(|event| match event.packet_header {
+            events::PacketHeader::Initial { version, .. }
+            | events::PacketHeader::Handshake { version, .. }
+            | events::PacketHeader::ZeroRtt { version, .. } => Some(version),
+            _ => None,
+        })
+        .collect();
+    versions.dedup();
+    versions
+}
+
+fn test_with_versions(client: version::Config, server: version::Config) -> Vec<u32> {
+    let model = Model::default();
+    let subscriber = recorder::PacketSent::new();
+    let events = subscriber.events();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_version(server)?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(456))?
+            .with_version(client)?
+            .start()?;
+
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(1000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let events = events.lock().unwrap();
+    long_header_versions(&events)
+}
+
+#[test]
+fn v1_test() {
+    let versions = test_with_versions(Default::default(), Default::default());
+    assert_eq!(versions, [version::V1]);
+}
+
+#[test]
+fn v2_test() {
+    let config = version::Config::builder()
+        .with_initial_version(version::V2)
+        .unwrap()
+        .build()
+        .unwrap();
+    let versions = test_with_versions(config, config);
+    assert_eq!(versions, [version::V2]);
+}
+
+#[test]
+fn v2_only_test() {
+    let config = version::Config::builder()
+        .with_versions(&[version::V2])
+        .unwrap()
+        .build()
+        .unwrap();
+    let versions = test_with_versions(config, config);
+    assert_eq!(versions, [version::V2]);
+}
+
+/// Returns a config with the given versions, in order of preference
+fn versions(versions: &[u32]) -> version::Config {
+    version::Config::builder()
+        .with_versions(versions)
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+// The rustls provider is unable to switch versions during the handshake
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn compatible_upgrade_test() {
+    let server = versions(&[version::V2, version::V1]);
+    let versions = test_with_versions(Default::default(), server);
+    // the client starts with V1 and switches to V2 after the server's first Initial packet
+    assert_eq!(versions, [version::V1, version::V2]);
+}
+
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn compatible_downgrade_test() {
+    let client = versions(&[version::V2, version::V1]);
+    let versions = test_with_versions(client, Default::default());
+    assert_eq!(versions, [version::V2, version::V1]);
+}
+
+#[test]
+fn compatible_not_advertised_test() {
+    // the server can't switch to a version the client didn't advertise
+    let client = versions(&[version::V1]);
+    let server = versions(&[version::V2, version::V1]);
+    let versions = test_with_versions(client, server);
+    assert_eq!(versions, [version::V1]);
+}
+
+/// Drops the first datagrams sent by the server
+struct DropFirstFlight {
+    remaining: usize,
+}
+
+impl Interceptor for DropFirstFlight {
+    fn intercept_tx_datagram(
+        &mut self,
+        _subject: &Subject,
+        _datagram: &Datagram,
+        payload: &mut EncoderBuffer,
+    ) {
+        if self.remaining > 0 {
+            self.remaining -= 1;
+            payload.set_position(0);
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn compatible_upgrade_first_flight_loss_test() {
+    let model = Model::default();
+    let packet_sent = recorder::PacketSent::new();
+    let packet_sent_events = packet_sent.events();
+    let packet_dropped = recorder::PacketDropped::new();
+    let packet_dropped_events = packet_dropped.events();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), packet_dropped))?
+            .with_random(Random::with_seed(456))?
+            .with_version(versions(&[version::V2, version::V1]))?
+            .with_packet_interceptor(DropFirstFlight { remaining: 3 })?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), packet_sent))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(1000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    // the client retransmits its Initial packets with the original version before
+    // receiving the server's first Initial packet
+    let packet_sent_events = packet_sent_events.lock().unwrap();
+    let original_initials = packet_sent_events
+        .iter()
+        .filter(|event| {
+            matches!(
+                event.packet_header,
+                events::PacketHeader::Initial {
+                    version: version::V1,
+                    ..
+                }
+            )
+        })
+        .count();
+    assert!(original_initials > 1);
+    assert_eq!(
+        long_header_versions(&packet_sent_events),
+        [version::V1, version::V2]
+    );
+
+    // the server keeps accepting the client's original version after switching
+    let packet_dropped_events = packet_dropped_events.lock().unwrap();
+    assert!(!packet_dropped_events.contains(&recorder::PacketDropReason::VersionMismatch));
+}
+
+/// Returns the versions of the long header packets sent by a client using the given
+/// TLS providers
+#[cfg(feature = "s2n-quic-rustls")]
+fn test_with_rustls(
+    rustls_client: bool,
+    client: version::Config,
+    server: version::Config,
+) -> Vec<u32> {
+    use crate::provider::tls::rustls;
+
+    let model = Model::default();
+    let subscriber = recorder::PacketSent::new();
+    let events = subscriber.events();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_version(server)?;
+        let server = if rustls_client {
+            server.with_tls(SERVER_CERTS)?.start()?
+        } else {
+            let tls = rustls::Server::builder()
+                .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)?
+                .build()?;
+            server.with_tls(tls)?.start()?
+        };
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(456))?
+            .with_version(client)?;
+        let client = if rustls_client {
+            let tls = rustls::Client::builder()
+                .with_certificate(certificates::CERT_PEM)?
+                .build()?;
+            client.with_tls(tls)?.start()?
+        } else {
+            client.with_tls(certificates::CERT_PEM)?.start()?
+        };
+
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(1000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let events = events.lock().unwrap();
+    long_header_versions(&events)
+}
+
+#[cfg(feature = "s2n-quic-rustls")]
+#[test]
+fn rustls_client_compatible_upgrade_test() {
+    // rustls clients don't advertise any other versions, so the server can't switch
+    let server = versions(&[version::V2, version::V1]);
+    let versions = test_with_rustls(true, Default::default(), server);
+    assert_eq!(versions, [version::V1]);
+}
+
+#[cfg(feature = "s2n-quic-rustls")]
+#[test]
+fn rustls_server_compatible_downgrade_test() {
+    // rustls servers keep the version the client started with
+    let client = versions(&[version::V2, version::V1]);
+    let versions = test_with_rustls(false, client, Default::default());
+    assert_eq!(versions, [version::V2]);
+}
+
+#[cfg(feature = "s2n-quic-rustls")]
+#[test]
+fn rustls_v2_test() {
+    // rustls endpoints can still use V2 if both peers start with it
+    let config = version::Config::builder()
+        .with_initial_version(version::V2)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(test_with_rustls(true, config, config), [version::V2]);
+    assert_eq!(test_with_rustls(false, config, config), [version::V2]);
+}
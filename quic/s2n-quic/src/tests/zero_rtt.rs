@@ -0,0 +1,231 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Early data is only supported by the rustls provider. s2n-tls doesn't support early data on
+//! QUIC connections, so it resumes sessions without sending early data and clients wait for the
+//! handshake to complete before sending stream data.
+
+use super::*;
+use crate::connection::EarlyDataStatus;
+
+/// The result of a handshake performed by the client
+#[derive(Debug)]
+struct Handshake {
+    status: EarlyDataStatus,
+    /// The number of streams that failed because the early data was rejected
+    rejected_streams: usize,
+}
+
+/// Performs a handshake and echoes some data on `stream_count` streams
+///
+/// Streams that fail because the early data was rejected are opened again after the
+/// handshake completes.
+fn handshake<F>(stream_count: usize, endpoints: F) -> Handshake
+where
+    F: FnOnce(&io::Handle) -> io::Result<(Server, Client)>,
+{
+    let result = Arc::new(Mutex::new(None));
+    let client_result = result.clone();
+
+    test(Model::default(), |handle| {
+        let (server, client) = endpoints(handle)?;
+        let addr = start_server(server)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // the streams are opened before the handshake completes when sending early data
+            let mut streams = vec![];
+            for _ in 0..stream_count {
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                stream.send(Bytes::from_static(b"hello")).await.unwrap();
+                stream.finish().unwrap();
+                streams.push(stream);
+            }
+
+            let mut rejected_streams = 0;
+            for mut stream in streams {
+                let mut echo = vec![];
+                let is_rejected = loop {
+                    match stream.receive().await {
+                        Ok(Some(chunk)) => echo.extend_from_slice(&chunk),
+                        Ok(None) => break false,
+                        Err(crate::stream::Error::EarlyDataRejected { .. }) => break true,
+                        Err(error) => panic!("unexpected stream error: {error}"),
+                    }
+                };
+
+                if is_rejected {
+                    rejected_streams += 1;
+                } else {
+                    assert_eq!(echo, b"hello");
+                }
+            }
+
+            // the rejected streams are sent again once the handshake completes
+            for _ in 0..rejected_streams {
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                stream.send(Bytes::from_static(b"hello")).await.unwrap();
+                stream.finish().unwrap();
+
+                let mut echo = vec![];
+                while let Some(chunk) = stream.receive().await.unwrap() {
+                    echo.extend_from_slice(&chunk);
+                }
+                assert_eq!(echo, b"hello");
+            }
+
+            *client_result.lock().unwrap() = Some(Handshake {
+                status: connection.early_data_status().unwrap(),
+                rejected_streams,
+            });
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let result = result.lock().unwrap().take();
+    result.expect("client should complete the exchange")
+}
+
+#[cfg(feature = "s2n-quic-rustls")]
+mod with_rustls {
+    use super::*;
+    use crate::provider::{
+        limits::Limits,
+        tls::rustls::{self, early_data::AntiReplay},
+    };
+
+    fn build_providers<A: AntiReplay>(
+        anti_replay: A,
+    ) -> io::Result<(rustls::Server, rustls::Client)> {
+        let server = rustls::Server::builder()
+            .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)?
+            .with_early_data(anti_replay)?
+            .build()?;
+        let client = rustls::Client::builder()
+            .with_certificate(certificates::CERT_PEM)?
+            .with_early_data(true)?
+            .build()?;
+        Ok((server, client))
+    }
+
+    fn endpoints(
+        handle: &io::Handle,
+        server_tls: &rustls::Server,
+        server_limits: Limits,
+        client_tls: &rustls::Client,
+    ) -> io::Result<(Server, Client)> {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(server_tls.clone())?
+            .with_limits(server_limits)?
+            .with_event(tracing_events())?
+            .start()?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(client_tls.clone())?
+            .with_event(tracing_events())?
+            .start()?;
+        Ok((server, client))
+    }
+
+    #[test]
+    fn zero_rtt_accepted() {
+        let (server_tls, client_tls) =
+            build_providers(rustls::early_data::SingleUseTickets).unwrap();
+
+        // the first handshake provides the client with a session ticket
+        let first = handshake(1, |handle| {
+            endpoints(handle, &server_tls, Limits::default(), &client_tls)
+        });
+        assert_eq!(first.status, EarlyDataStatus::NotAttempted);
+
+        let resumed = handshake(1, |handle| {
+            endpoints(handle, &server_tls, Limits::default(), &client_tls)
+        });
+        assert_eq!(resumed.status, EarlyDataStatus::Accepted);
+        assert_eq!(resumed.rejected_streams, 0);
+    }
+
+    #[test]
+    fn zero_rtt_rejected() {
+        let (server_tls, client_tls) = build_providers(|_ticket: &[u8]| false).unwrap();
+
+        let first = handshake(1, |handle| {
+            endpoints(handle, &server_tls, Limits::default(), &client_tls)
+        });
+        assert_eq!(first.status, EarlyDataStatus::NotAttempted);
+
+        // the stream opened with the rejected early data fails and is opened again
+        let resumed = handshake(1, |handle| {
+            endpoints(handle, &server_tls, Limits::default(), &client_tls)
+        });
+        assert_eq!(resumed.status, EarlyDataStatus::Rejected);
+        assert_eq!(resumed.rejected_streams, 1);
+    }
+
+    /// A server that rejects early data is free to lower its limits, which the client needs to
+    /// respect instead of the ones it remembered from the previous connection
+    #[test]
+    fn zero_rtt_rejected_lowered_limits() {
+        let (server_tls, client_tls) = build_providers(|_ticket: &[u8]| false).unwrap();
+
+        let first = handshake(3, |handle| {
+            endpoints(handle, &server_tls, Limits::default(), &client_tls)
+        });
+        assert_eq!(first.status, EarlyDataStatus::NotAttempted);
+
+        let lowered_limits = Limits::default()
+            .with_data_window(8)
+            .unwrap()
+            .with_bidirectional_remote_data_window(8)
+            .unwrap()
+            .with_max_open_remote_bidirectional_streams(1)
+            .unwrap();
+
+        // the client sends more streams and data in 0-RTT than the lowered limits allow
+        let resumed = handshake(3, |handle| {
+            endpoints(handle, &server_tls, lowered_limits, &client_tls)
+        });
+        assert_eq!(resumed.status, EarlyDataStatus::Rejected);
+        assert_eq!(resumed.rejected_streams, 3);
+    }
+}
+
+#[cfg(feature = "s2n-quic-tls")]
+mod with_s2n_tls {
+    use super::*;
+
+    /// s2n-tls resumes the session without attempting to send early data
+    #[test]
+    fn zero_rtt_not_attempted() {
+        let handler = SessionTicketHandler::default();
+
+        for _ in 0..2 {
+            let result = handshake(1, |handle| {
+                let server = Server::builder()
+                    .with_io(handle.builder().build()?)?
+                    .with_tls(build_server_resumption_provider(
+                        certificates::CERT_PEM,
+                        certificates::KEY_PEM,
+                    )?)?
+                    .with_event(tracing_events())?
+                    .start()?;
+                let client = Client::builder()
+                    .with_io(handle.builder().build()?)?
+                    .with_tls(build_client_resumption_provider(
+                        certificates::CERT_PEM,
+                        &handler,
+                    )?)?
+                    .with_event(tracing_events())?
+                    .start()?;
+                Ok((server, client))
+            });
+            assert_eq!(result.status, EarlyDataStatus::NotAttempted);
+            assert_eq!(result.rejected_streams, 0);
+        }
+    }
+}